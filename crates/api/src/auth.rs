@@ -0,0 +1,173 @@
+use crate::error::{ApiError, Result};
+use crate::AuthMethod;
+use async_trait::async_trait;
+use reqwest::RequestBuilder;
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Source of a fresh token when the server rejects the current one.
+#[async_trait]
+pub trait TokenRefresh: Send + Sync {
+    async fn refresh(&self) -> Result<String>;
+}
+
+/// Credentials shared by every clone of a client, so a token refreshed by one
+/// request is used by all the others.
+#[derive(Default)]
+pub(crate) struct AuthLayer {
+    method: RwLock<Option<AuthMethod>>,
+    /// Where the token came from ("env ATLASSIAN_API_TOKEN", a keyring
+    /// entry, ...), named in authentication errors.
+    source: Option<String>,
+    refresher: Option<Arc<dyn TokenRefresh>>,
+    /// Held while refreshing so concurrent 401s refresh only once.
+    refreshing: Mutex<()>,
+}
+
+impl AuthLayer {
+    pub(crate) fn with_method(&self, method: AuthMethod) -> Self {
+        Self {
+            method: RwLock::new(Some(method)),
+            source: self.source.clone(),
+            refresher: self.refresher.clone(),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn with_source(&self, source: String) -> Self {
+        Self {
+            method: RwLock::new(self.method()),
+            source: Some(source),
+            refresher: self.refresher.clone(),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn with_refresher(&self, refresher: Arc<dyn TokenRefresh>) -> Self {
+        Self {
+            method: RwLock::new(self.method()),
+            source: self.source.clone(),
+            refresher: Some(refresher),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    fn method(&self) -> Option<AuthMethod> {
+        self.method
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// The secret currently sent, to tell whether another request refreshed it.
+    pub(crate) fn token(&self) -> Option<String> {
+        self.method().map(|method| match method {
+            AuthMethod::Basic { token, .. } | AuthMethod::Bearer { token } => token,
+            AuthMethod::ApiKey { key, .. } => key,
+        })
+    }
+
+    pub(crate) fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.method() {
+            Some(AuthMethod::Basic { username, token }) => {
+                request.basic_auth(username, Some(token))
+            }
+            Some(AuthMethod::Bearer { token }) => request.bearer_auth(token),
+            Some(AuthMethod::ApiKey { scheme, key }) => {
+                request.header(reqwest::header::AUTHORIZATION, format!("{scheme} {key}"))
+            }
+            None => request,
+        }
+    }
+
+    /// Message for a 401, naming where the rejected token came from.
+    pub(crate) fn rejected(&self) -> ApiError {
+        let message = match &self.source {
+            Some(source) => format!("Invalid or expired credentials (token from {source})"),
+            None => "Invalid or expired credentials".to_string(),
+        };
+        ApiError::AuthenticationFailed { message }
+    }
+
+    /// Replace `rejected` with a fresh token. Returns false when there is no
+    /// way to refresh, so the 401 should be surfaced as is.
+    pub(crate) async fn refresh(&self, rejected: Option<&str>) -> Result<bool> {
+        let Some(refresher) = &self.refresher else {
+            return Ok(false);
+        };
+        let _guard = self.refreshing.lock().await;
+        if self.token().as_deref() != rejected {
+            debug!("Token already refreshed by another request");
+            return Ok(true);
+        }
+
+        debug!("Refreshing rejected token");
+        let fresh = refresher.refresh().await.map_err(|e| {
+            warn!(error = %e, "Token refresh failed");
+            e
+        })?;
+        let mut method = self.method.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(method) = method.as_mut() {
+            swap_token(method, fresh);
+        }
+        Ok(true)
+    }
+}
+
+fn swap_token(method: &mut AuthMethod, fresh: String) {
+    match method {
+        AuthMethod::Basic { token, .. } | AuthMethod::Bearer { token } => *token = fresh,
+        AuthMethod::ApiKey { key, .. } => *key = fresh,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counting(AtomicUsize);
+
+    #[async_trait]
+    impl TokenRefresh for Counting {
+        async fn refresh(&self) -> Result<String> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("fresh-{n}"))
+        }
+    }
+
+    fn basic(token: &str) -> AuthMethod {
+        AuthMethod::Basic {
+            username: "me@example.com".to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_swaps_token_once() {
+        let refresher = Arc::new(Counting(AtomicUsize::new(0)));
+        let layer = AuthLayer::default()
+            .with_method(basic("stale"))
+            .with_refresher(refresher.clone());
+
+        assert!(layer.refresh(Some("stale")).await.unwrap());
+        assert_eq!(layer.token().as_deref(), Some("fresh-1"));
+
+        // A second request that also saw "stale" reuses the new token
+        assert!(layer.refresh(Some("stale")).await.unwrap());
+        assert_eq!(refresher.0.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_refresher() {
+        let layer = AuthLayer::default()
+            .with_method(basic("stale"))
+            .with_source("env ATLASSIAN_API_TOKEN".to_string());
+        assert!(!layer.refresh(Some("stale")).await.unwrap());
+        assert_eq!(
+            layer.rejected().to_string(),
+            "Authentication failed: Invalid or expired credentials (token from env ATLASSIAN_API_TOKEN)"
+        );
+    }
+}
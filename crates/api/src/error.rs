@@ -1,3 +1,4 @@
+use serde_json::Value;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -31,6 +32,11 @@ pub enum ApiError {
 
     #[error("Invalid response format: {0}")]
     InvalidResponse(String),
+
+    #[error(
+        "Explain mode: {method} {path} was not sent and the rest of the command needs its response"
+    )]
+    ExplainStopped { method: String, path: String },
 }
 
 impl ApiError {
@@ -59,4 +65,133 @@ impl ApiError {
     }
 }
 
+/// Turn an error response body into a readable message. Understands Jira's
+/// `errorMessages`/`errors`, Confluence's v1 `message`/`data.errors` and v2
+/// `errors[]`, and Bitbucket's `error` object; anything else is returned as is.
+pub fn describe_error_body(body: &str) -> String {
+    let body = body.trim();
+    let messages = serde_json::from_str::<Value>(body)
+        .map(|value| error_messages(&value))
+        .unwrap_or_default();
+    if messages.is_empty() {
+        body.to_string()
+    } else {
+        messages.join("; ")
+    }
+}
+
+fn error_messages(value: &Value) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut push = |message: String| {
+        let message = message.trim().to_string();
+        if !message.is_empty() && !messages.contains(&message) {
+            messages.push(message);
+        }
+    };
+
+    // Jira: {"errorMessages": [...], "errors": {"field": "problem"}}
+    for message in value["errorMessages"].as_array().into_iter().flatten() {
+        push(text(message));
+    }
+    if let Some(errors) = value["errors"].as_object() {
+        for (field, problem) in errors {
+            push(format!("{field}: {}", text(problem)));
+        }
+    }
+
+    // Confluence v2: {"errors": [{"title": ..., "detail": ...}]}
+    for error in value["errors"].as_array().into_iter().flatten() {
+        let title = text(&error["title"]);
+        match error["detail"].as_str() {
+            Some(detail) if !detail.is_empty() && detail != title => {
+                push(format!("{title}: {detail}"))
+            }
+            _ => push(title),
+        }
+    }
+
+    // Confluence v1: {"message": "com.atlassian...Exception: text", "data": {"errors": [...]}}
+    if let Some(message) = value["message"].as_str() {
+        push(strip_exception(message).to_string());
+    }
+    for error in value["data"]["errors"].as_array().into_iter().flatten() {
+        push(text(&error["message"]["translation"]));
+    }
+
+    // Bitbucket: {"type": "error", "error": {"message", "detail", "fields": {"name": [...]}}}
+    let error = &value["error"];
+    if error.is_object() {
+        push(text(&error["message"]));
+        push(text(&error["detail"]));
+        if let Some(fields) = error["fields"].as_object() {
+            for (field, problems) in fields {
+                push(format!("{field}: {}", text(problems)));
+            }
+        }
+    }
+
+    messages
+}
+
+/// A message string, or a list of them joined; empty for anything else.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(text).collect::<Vec<_>>().join(", "),
+        _ => String::new(),
+    }
+}
+
+/// Drop a leading Java exception class name ("com.atlassian.x.FooException: ").
+fn strip_exception(message: &str) -> &str {
+    match message.split_once(": ") {
+        Some((class, rest)) if class.contains('.') && !class.contains(' ') => rest,
+        _ => message,
+    }
+}
+
 pub type Result<T> = std::result::Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_jira_errors() {
+        let body = r#"{"errorMessages":["Issue does not exist"],"errors":{"priority":"The priority 'Urgent' does not exist"}}"#;
+        assert_eq!(
+            describe_error_body(body),
+            "Issue does not exist; priority: The priority 'Urgent' does not exist"
+        );
+    }
+
+    #[test]
+    fn test_describe_confluence_errors() {
+        let v2 = r#"{"errors":[{"status":400,"code":"INVALID_REQUEST_PARAMETER","title":"Provided value {abc} for 'id' is not the correct type","detail":null}]}"#;
+        assert_eq!(
+            describe_error_body(v2),
+            "Provided value {abc} for 'id' is not the correct type"
+        );
+
+        let v1 = r#"{"statusCode":400,"data":{"authorized":true,"valid":false,"errors":[{"message":{"key":"title.exists","translation":"A page with this title already exists","args":[]}}]},"message":"com.atlassian.confluence.api.service.exceptions.BadRequestException: Could not create content"}"#;
+        assert_eq!(
+            describe_error_body(v1),
+            "Could not create content; A page with this title already exists"
+        );
+    }
+
+    #[test]
+    fn test_describe_bitbucket_errors() {
+        let body = r#"{"type":"error","error":{"message":"Bad request","fields":{"name":["Repository with this Slug and Owner already exists."]}}}"#;
+        assert_eq!(
+            describe_error_body(body),
+            "Bad request; name: Repository with this Slug and Owner already exists."
+        );
+    }
+
+    #[test]
+    fn test_describe_unknown_body() {
+        assert_eq!(describe_error_body("  Bad gateway\n"), "Bad gateway");
+        assert_eq!(describe_error_body(r#"{"foo":1}"#), r#"{"foo":1}"#);
+    }
+}
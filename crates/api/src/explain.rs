@@ -0,0 +1,199 @@
+use reqwest::Method;
+use serde_json::{json, Map, Value};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+
+/// Longest string kept in a payload skeleton.
+const MAX_STRING: usize = 80;
+/// Array elements shown in a payload skeleton.
+const MAX_ITEMS: usize = 3;
+
+/// One call a command made, or would have made, in explain mode.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplainedCall {
+    pub method: String,
+    /// Path and query, relative to the product's base URL.
+    pub path: String,
+    pub payload: Option<Value>,
+    /// Reads are sent so the command can plan; writes are only recorded.
+    pub sent: bool,
+}
+
+/// Explain mode: reads go out as usual, writes are recorded instead of sent.
+/// Clones share the log, so every client built for a command adds to it.
+#[derive(Clone, Debug, Default)]
+pub struct ExplainLog {
+    calls: Arc<Mutex<Vec<ExplainedCall>>>,
+}
+
+impl ExplainLog {
+    pub fn calls(&self) -> Vec<ExplainedCall> {
+        self.calls.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub(crate) fn record(&self, method: &Method, path: String, payload: Option<Value>, sent: bool) {
+        self.calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(ExplainedCall {
+                method: method.to_string(),
+                path,
+                payload: payload.as_ref().map(skeleton),
+                sent,
+            });
+    }
+
+    /// The recorded calls, numbered, with the payload skeleton under each write.
+    pub fn report(&self) -> String {
+        let calls = self.calls();
+        let writes = calls.iter().filter(|c| !c.sent).count();
+        let mut report = format!(
+            "Explain: {} API call(s), {writes} write(s) not sent",
+            calls.len()
+        );
+        for (n, call) in calls.iter().enumerate() {
+            let _ = write!(report, "\n{:>3}. {:<6} {}", n + 1, call.method, call.path);
+            if !call.sent {
+                report.push_str("  (not sent)");
+            }
+            if let Some(payload) = &call.payload {
+                let _ = write!(report, "\n          {payload}");
+            }
+        }
+        report
+    }
+}
+
+/// Whether a request only reads. Besides GET, this covers the POST endpoints
+/// that take a query in the body (Jira search, workflow lookup, GraphQL
+/// queries).
+pub(crate) fn is_read(method: &Method, path: &str, body: Option<&Value>) -> bool {
+    if method == Method::GET || method == Method::HEAD {
+        return true;
+    }
+    if method != Method::POST {
+        return false;
+    }
+    let path = path.split('?').next().unwrap_or(path).trim_end_matches('/');
+    if path.ends_with("/graphql") {
+        let query = body
+            .and_then(|b| b.get("query"))
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        return !query.trim_start().starts_with("mutation");
+    }
+    path.ends_with("/search")
+        || path.ends_with("/search/jql")
+        || path.ends_with("/rest/api/3/workflows")
+        || path.ends_with("/rest/api/3/jql/parse")
+        || path.ends_with("/rest/api/3/issue/bulkfetch")
+        || path.ends_with("/rest/api/3/expression/eval")
+}
+
+/// The payload with long strings and arrays cut down, for reading at a glance.
+fn skeleton(value: &Value) -> Value {
+    match value {
+        Value::String(s) if s.chars().count() > MAX_STRING => {
+            let kept: String = s.chars().take(MAX_STRING - 1).collect();
+            Value::String(format!("{kept}…"))
+        }
+        Value::Array(items) if items.len() > MAX_ITEMS => {
+            let mut kept: Vec<Value> = items.iter().take(MAX_ITEMS).map(skeleton).collect();
+            kept.push(Value::String(format!("… {} more", items.len() - MAX_ITEMS)));
+            Value::Array(kept)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(skeleton).collect()),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(key, value)| (key.clone(), skeleton(value)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// A stand-in for the response to a write that was not sent: the payload
+/// itself plus the identifiers create endpoints usually return, so commands
+/// can carry on to their next call.
+pub(crate) fn placeholder_response(payload: Option<&Value>) -> Value {
+    let mut response = match payload {
+        Some(Value::Object(obj)) => obj.clone(),
+        _ => Map::new(),
+    };
+    for (field, value) in [
+        ("id", json!("explain")),
+        ("key", json!("EXPLAIN-1")),
+        ("name", json!("explain")),
+        ("self", json!("")),
+    ] {
+        response.entry(field).or_insert(value);
+    }
+    Value::Object(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_read() {
+        assert!(is_read(&Method::GET, "/rest/api/3/issue/DEV-1", None));
+        assert!(is_read(&Method::POST, "/rest/api/3/search/jql", None));
+        assert!(is_read(
+            &Method::POST,
+            "/rest/api/3/search?validateQuery=strict",
+            None
+        ));
+        assert!(!is_read(&Method::POST, "/rest/api/3/issue", None));
+        assert!(!is_read(&Method::PUT, "/rest/api/3/issue/DEV-1", None));
+        assert!(!is_read(&Method::DELETE, "/rest/api/3/component/10", None));
+        assert!(is_read(
+            &Method::POST,
+            "/gateway/api/graphql",
+            Some(&json!({ "query": "query { me { user { name } } }" }))
+        ));
+        assert!(!is_read(
+            &Method::POST,
+            "/gateway/api/graphql",
+            Some(&json!({ "query": " mutation { archive }" }))
+        ));
+    }
+
+    #[test]
+    fn test_report() {
+        let log = ExplainLog::default();
+        log.record(
+            &Method::GET,
+            "/rest/api/3/project/DEV".to_string(),
+            None,
+            true,
+        );
+        log.record(
+            &Method::POST,
+            "/rest/api/3/component".to_string(),
+            Some(json!({ "name": "Backend", "labels": ["a", "b", "c", "d", "e"] })),
+            false,
+        );
+        assert_eq!(
+            log.report(),
+            "Explain: 2 API call(s), 1 write(s) not sent\n  \
+             1. GET    /rest/api/3/project/DEV\n  \
+             2. POST   /rest/api/3/component  (not sent)\n          \
+             {\"labels\":[\"a\",\"b\",\"c\",\"… 2 more\"],\"name\":\"Backend\"}"
+        );
+    }
+
+    #[test]
+    fn test_placeholder_response() {
+        assert_eq!(
+            placeholder_response(Some(&json!({ "name": "Backend", "project": "DEV" }))),
+            json!({
+                "name": "Backend",
+                "project": "DEV",
+                "id": "explain",
+                "key": "EXPLAIN-1",
+                "self": ""
+            })
+        );
+    }
+}
@@ -1,68 +1,140 @@
+pub mod auth;
 pub mod error;
+pub mod explain;
 pub mod pagination;
+pub mod pool;
 pub mod ratelimit;
+pub mod redact;
 pub mod retry;
+pub mod stream;
 
-use error::{ApiError, Result};
+use auth::{AuthLayer, TokenRefresh};
+use error::{describe_error_body, ApiError, Result};
+use explain::ExplainLog;
+use futures::stream::Stream;
+use pool::{ConnectionStats, PoolConfig};
 use ratelimit::RateLimiter;
+use redact::TraceRedaction;
 use reqwest::{Client, Method, RequestBuilder, StatusCode};
 use retry::{retry_with_backoff, RetryConfig};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::time::Duration;
-use tracing::{debug, error, warn};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
 use url::Url;
 
 #[derive(Clone, Debug)]
 pub enum AuthMethod {
-    Basic { username: String, token: String },
-    Bearer { token: String },
+    Basic {
+        username: String,
+        token: String,
+    },
+    Bearer {
+        token: String,
+    },
+    /// Product API key sent as `Authorization: <scheme> <key>` (Opsgenie's
+    /// `GenieKey`, Statuspage's `OAuth`).
+    ApiKey {
+        scheme: String,
+        key: String,
+    },
 }
 
+/// HTTP client for Atlassian APIs. Clones share one connection pool, so a single
+/// client should be cloned into concurrent jobs rather than rebuilt per job.
 #[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: Url,
-    auth: Option<AuthMethod>,
+    auth: Arc<AuthLayer>,
     retry_config: RetryConfig,
     rate_limiter: RateLimiter,
+    stats: ConnectionStats,
+    trace_http: bool,
+    trace_redaction: Option<TraceRedaction>,
+    explain: Option<ExplainLog>,
 }
 
 impl ApiClient {
     pub fn new(base_url: impl AsRef<str>) -> Result<Self> {
+        Self::with_pool(base_url, &PoolConfig::default(), ConnectionStats::default())
+    }
+
+    /// Build a client with pool tuning, recording traffic into `stats` (which may be
+    /// shared between several clients).
+    pub fn with_pool(
+        base_url: impl AsRef<str>,
+        pool: &PoolConfig,
+        stats: ConnectionStats,
+    ) -> Result<Self> {
         let url = Url::parse(base_url.as_ref()).map_err(ApiError::InvalidUrl)?;
 
-        let client = Client::builder()
+        let builder = Client::builder()
             .user_agent(format!("atlassian-cli/{}", env!("CARGO_PKG_VERSION")))
-            .timeout(Duration::from_secs(30))
+            // No total timeout: streamed exports can take far longer than any
+            // single read, so only connecting and each read are bounded
+            .connect_timeout(Duration::from_secs(30))
+            .read_timeout(Duration::from_secs(30))
+            .gzip(true)
+            .deflate(true)
+            .connector_layer(stats.layer());
+
+        let client = pool
+            .apply(builder)
             .build()
             .map_err(ApiError::RequestFailed)?;
 
         Ok(Self {
             client,
             base_url: url,
-            auth: None,
+            auth: Arc::default(),
             retry_config: RetryConfig::default(),
             rate_limiter: RateLimiter::new(),
+            stats,
+            trace_http: false,
+            trace_redaction: None,
+            explain: None,
         })
     }
 
-    pub fn with_basic_auth(
-        mut self,
-        username: impl Into<String>,
-        token: impl Into<String>,
-    ) -> Self {
-        self.auth = Some(AuthMethod::Basic {
+    pub fn with_basic_auth(self, username: impl Into<String>, token: impl Into<String>) -> Self {
+        self.with_auth(AuthMethod::Basic {
             username: username.into(),
             token: token.into(),
-        });
-        self
+        })
     }
 
-    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
-        self.auth = Some(AuthMethod::Bearer {
+    pub fn with_bearer_token(self, token: impl Into<String>) -> Self {
+        self.with_auth(AuthMethod::Bearer {
             token: token.into(),
-        });
+        })
+    }
+
+    pub fn with_api_key(self, scheme: impl Into<String>, key: impl Into<String>) -> Self {
+        self.with_auth(AuthMethod::ApiKey {
+            scheme: scheme.into(),
+            key: key.into(),
+        })
+    }
+
+    fn with_auth(mut self, method: AuthMethod) -> Self {
+        self.auth = Arc::new(self.auth.with_method(method));
+        self
+    }
+
+    /// Name where the token came from (e.g. `env ATLASSIAN_API_TOKEN`) in
+    /// authentication errors.
+    pub fn with_credential_source(mut self, source: impl Into<String>) -> Self {
+        self.auth = Arc::new(self.auth.with_source(source.into()));
+        self
+    }
+
+    /// On a 401, fetch a new token from `refresher` and retry the request once.
+    pub fn with_token_refresh(mut self, refresher: Arc<dyn TokenRefresh>) -> Self {
+        self.auth = Arc::new(self.auth.with_refresher(refresher));
         self
     }
 
@@ -71,6 +143,46 @@ impl ApiClient {
         self
     }
 
+    /// Log every request with its status and latency.
+    pub fn with_http_trace(mut self, enabled: bool) -> Self {
+        self.trace_http = enabled;
+        self
+    }
+
+    /// Mask query parameter values in `--trace-http` logs.
+    pub fn with_trace_redaction(mut self, redaction: TraceRedaction) -> Self {
+        self.trace_redaction = Some(redaction);
+        self
+    }
+
+    /// Record writes in `log` instead of sending them (`--explain`).
+    pub fn with_explain(mut self, log: ExplainLog) -> Self {
+        self.explain = Some(log);
+        self
+    }
+
+    /// For requests sent outside this client (multipart uploads): in explain
+    /// mode, record the write and return true, and the caller must not send it.
+    pub fn explained(&self, method: Method, path: &str) -> bool {
+        match &self.explain {
+            Some(log) => {
+                log.record(&method, path.to_string(), None, false);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// True in `--explain` mode: writes are recorded, not sent, so callers must
+    /// not record their outcome in local state either.
+    pub fn is_explaining(&self) -> bool {
+        self.explain.is_some()
+    }
+
+    pub fn connection_stats(&self) -> &ConnectionStats {
+        &self.stats
+    }
+
     pub fn base_url(&self) -> &str {
         self.base_url.as_str()
     }
@@ -79,6 +191,15 @@ impl ApiClient {
         self.request(Method::GET, path, Option::<&()>::None).await
     }
 
+    /// GET a resource that may not exist: `Ok(None)` when the server answers 404.
+    pub async fn get_optional<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path).await {
+            Ok(value) => Ok(Some(value)),
+            Err(ApiError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     pub async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
         &self,
         path: &str,
@@ -106,97 +227,220 @@ impl ApiClient {
         path: &str,
         body: Option<&B>,
     ) -> Result<T> {
-        if let Some(wait_secs) = self.rate_limiter.check_limit().await {
-            warn!(wait_secs, "Rate limit reached, waiting");
-            tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+        let joined = self.resolve(path)?;
+        if let Some(log) = &self.explain {
+            let payload = body.map(serde_json::to_value).transpose()?;
+            let read = explain::is_read(&method, path, payload.as_ref());
+            log.record(&method, path.to_string(), payload.clone(), read);
+            if !read {
+                let response = explain::placeholder_response(payload.as_ref());
+                return serde_json::from_value(response)
+                    .or_else(|_| serde_json::from_value(Value::Null))
+                    .map_err(|_| ApiError::ExplainStopped {
+                        method: method.to_string(),
+                        path: path.to_string(),
+                    });
+            }
         }
 
-        let url = self.base_url.clone();
-        let joined = url
-            .join(path.strip_prefix('/').unwrap_or(path))
-            .map_err(ApiError::InvalidUrl)?;
-
         debug!(method = %method, url = %joined, "Sending request");
 
-        let result = retry_with_backoff(&self.retry_config, || async {
-            let mut req = self.client.request(method.clone(), joined.clone());
-            req = self.apply_auth(req);
+        let result = self
+            .authenticated(|| {
+                self.retrying(|| async {
+                    let response = self.send(&method, &joined, body).await?;
+                    let bytes = response.bytes().await.map_err(ApiError::RequestFailed)?;
+                    self.stats.record_received(bytes.len());
+                    serde_json::from_slice::<T>(&bytes).map_err(|e| {
+                        error!("Failed to parse JSON response: {}", e);
+                        ApiError::InvalidResponse(e.to_string())
+                    })
+                })
+            })
+            .await?;
 
-            if let Some(body) = body {
-                req = req.json(body);
+        Ok(result)
+    }
+
+    /// Send a request and stream the elements of a JSON array in the response
+    /// (the top-level array, or the array under `field`) as they are received.
+    ///
+    /// Only the initial request is retried; once the body starts streaming,
+    /// errors are surfaced to the caller.
+    pub async fn request_array_stream<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        field: Option<&str>,
+    ) -> Result<impl Stream<Item = Result<T>>> {
+        let joined = self.resolve(path)?;
+        if let Some(log) = &self.explain {
+            let payload = body.map(serde_json::to_value).transpose()?;
+            let read = explain::is_read(&method, path, payload.as_ref());
+            log.record(&method, path.to_string(), payload, read);
+            if !read {
+                return Err(ApiError::ExplainStopped {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                });
             }
+        }
 
-            let response = req.send().await.map_err(ApiError::RequestFailed)?;
-
-            self.rate_limiter.update_from_response(&response).await;
-
-            let status = response.status();
-
-            match status {
-                StatusCode::UNAUTHORIZED => Err(ApiError::AuthenticationFailed {
-                    message: "Invalid or expired credentials".to_string(),
-                }),
-                StatusCode::NOT_FOUND => {
-                    let resource = joined.path().to_string();
-                    Err(ApiError::NotFound { resource })
-                }
-                StatusCode::BAD_REQUEST => {
-                    let message = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Bad request".to_string());
-                    Err(ApiError::BadRequest { message })
-                }
-                StatusCode::TOO_MANY_REQUESTS => {
-                    let retry_after = response
-                        .headers()
-                        .get("retry-after")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(60);
-                    Err(ApiError::RateLimitExceeded { retry_after })
-                }
-                status if status.is_server_error() => {
-                    let message = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Server error".to_string());
-                    Err(ApiError::ServerError {
-                        status: status.as_u16(),
-                        message,
-                    })
-                }
-                status if status.is_success() => response.json::<T>().await.map_err(|e| {
-                    error!("Failed to parse JSON response: {}", e);
-                    ApiError::InvalidResponse(e.to_string())
-                }),
-                _ => {
-                    let message = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| format!("Unexpected status: {}", status));
-                    Err(ApiError::ServerError {
-                        status: status.as_u16(),
-                        message,
-                    })
-                }
+        debug!(method = %method, url = %joined, "Sending streaming request");
+
+        let response = self
+            .authenticated(|| self.retrying(|| self.send(&method, &joined, body)))
+            .await?;
+
+        Ok(stream::json_array_stream(
+            response,
+            field,
+            self.stats.clone(),
+        ))
+    }
+
+    /// Run `operation`, and when it is rejected with a 401 that a token refresh
+    /// can fix, run it once more with the new token.
+    async fn authenticated<F, Fut, T>(&self, operation: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let token = self.auth.token();
+        match operation().await {
+            Err(ApiError::AuthenticationFailed { .. })
+                if self.auth.refresh(token.as_deref()).await? =>
+            {
+                debug!("Retrying with refreshed token");
+                operation().await
             }
+            result => result,
+        }
+    }
+
+    /// Run `operation` with the retry policy, counting repeated attempts in the stats.
+    async fn retrying<F, Fut, T>(&self, operation: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let attempts = AtomicUsize::new(0);
+        let result = retry_with_backoff(&self.retry_config, || {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            operation()
         })
-        .await?;
+        .await;
+        self.stats
+            .record_retries(attempts.load(Ordering::Relaxed).saturating_sub(1));
+        result
+    }
 
-        Ok(result)
+    fn resolve(&self, path: &str) -> Result<Url> {
+        self.base_url
+            .join(path.strip_prefix('/').unwrap_or(path))
+            .map_err(ApiError::InvalidUrl)
     }
 
-    pub fn apply_auth(&self, request: RequestBuilder) -> RequestBuilder {
-        match &self.auth {
-            Some(AuthMethod::Basic { username, token }) => {
-                request.basic_auth(username, Some(token))
+    /// Send a single attempt, returning the response only if it succeeded.
+    async fn send<B: Serialize + ?Sized>(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&B>,
+    ) -> Result<reqwest::Response> {
+        if let Some(wait_secs) = self.rate_limiter.check_limit().await {
+            warn!(wait_secs, "Rate limit reached, waiting");
+            tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+        }
+
+        let mut req = self.client.request(method.clone(), url.clone());
+        req = self.apply_auth(req);
+
+        if let Some(body) = body {
+            let bytes = serde_json::to_vec(body)?;
+            self.stats.record_sent(bytes.len());
+            req = req
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(bytes);
+        }
+
+        let started = Instant::now();
+        self.stats.record_request();
+        let response = req.send().await.map_err(ApiError::RequestFailed)?;
+
+        self.rate_limiter.update_from_response(&response).await;
+
+        let status = response.status();
+
+        if self.trace_http {
+            let url = match &self.trace_redaction {
+                Some(redaction) => redaction.url(url),
+                None => url.to_string(),
+            };
+            info!(
+                method = %method,
+                url = %url,
+                status = status.as_u16(),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "HTTP request"
+            );
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED => Err(self.auth.rejected()),
+            StatusCode::NOT_FOUND => {
+                let resource = url.path().to_string();
+                Err(ApiError::NotFound { resource })
+            }
+            StatusCode::BAD_REQUEST => {
+                let message = response
+                    .text()
+                    .await
+                    .map(|body| describe_error_body(&body))
+                    .unwrap_or_else(|_| "Bad request".to_string());
+                Err(ApiError::BadRequest { message })
+            }
+            StatusCode::TOO_MANY_REQUESTS => {
+                self.stats.record_rate_limited();
+                let retry_after = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(60);
+                Err(ApiError::RateLimitExceeded { retry_after })
+            }
+            status if status.is_server_error() => {
+                let message = response
+                    .text()
+                    .await
+                    .map(|body| describe_error_body(&body))
+                    .unwrap_or_else(|_| "Server error".to_string());
+                Err(ApiError::ServerError {
+                    status: status.as_u16(),
+                    message,
+                })
+            }
+            status if status.is_success() => Ok(response),
+            _ => {
+                let message = response
+                    .text()
+                    .await
+                    .map(|body| describe_error_body(&body))
+                    .unwrap_or_else(|_| format!("Unexpected status: {}", status));
+                Err(ApiError::ServerError {
+                    status: status.as_u16(),
+                    message,
+                })
             }
-            Some(AuthMethod::Bearer { token }) => request.bearer_auth(token),
-            None => request,
         }
     }
 
+    pub fn apply_auth(&self, request: RequestBuilder) -> RequestBuilder {
+        self.auth.apply(request)
+    }
+
     pub fn rate_limiter(&self) -> &RateLimiter {
         &self.rate_limiter
     }
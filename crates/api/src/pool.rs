@@ -0,0 +1,242 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Connection pool tuning for the underlying HTTP client.
+///
+/// Every clone of an [`ApiClient`](crate::ApiClient) shares the same pool, so
+/// concurrent bulk jobs reuse warm connections instead of re-handshaking.
+#[derive(Debug, Clone, Default)]
+pub struct PoolConfig {
+    /// Maximum idle connections kept per host (unbounded by default).
+    pub max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept before being closed.
+    pub idle_timeout: Option<Duration>,
+    /// TCP keepalive interval for open connections.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl PoolConfig {
+    pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+    pub const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+    pub(crate) fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        let mut builder = builder
+            .pool_idle_timeout(self.idle_timeout.unwrap_or(Self::DEFAULT_IDLE_TIMEOUT))
+            .tcp_keepalive(self.tcp_keepalive.unwrap_or(Self::DEFAULT_TCP_KEEPALIVE));
+        if let Some(max_idle) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        builder
+    }
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicUsize,
+    connections: AtomicUsize,
+    retries: AtomicUsize,
+    rate_limited: AtomicUsize,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+/// Counts requests sent, connections opened, retries, 429 responses and bytes
+/// transferred, so connection reuse and throughput can be checked.
+/// Cloning shares the counters.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStats {
+    counters: Arc<Counters>,
+}
+
+impl ConnectionStats {
+    pub fn requests(&self) -> usize {
+        self.counters.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn connections_opened(&self) -> usize {
+        self.counters.connections.load(Ordering::Relaxed)
+    }
+
+    /// Requests served over an already-open connection.
+    pub fn connections_reused(&self) -> usize {
+        self.requests().saturating_sub(self.connections_opened())
+    }
+
+    /// Attempts that repeated an earlier failed attempt.
+    pub fn retries(&self) -> usize {
+        self.counters.retries.load(Ordering::Relaxed)
+    }
+
+    /// Responses with status 429 Too Many Requests.
+    pub fn rate_limited(&self) -> usize {
+        self.counters.rate_limited.load(Ordering::Relaxed)
+    }
+
+    /// Request body bytes sent.
+    pub fn bytes_sent(&self) -> u64 {
+        self.counters.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Response body bytes received (after decompression).
+    pub fn bytes_received(&self) -> u64 {
+        self.counters.bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// One-line summary for `--stats`, including the command's wall time.
+    pub fn summary(&self, elapsed: Duration) -> String {
+        format!(
+            "{} requests, {} retries, {} rate-limited (429), {} sent, {} received, {} connections opened, {:.2}s wall time",
+            self.requests(),
+            self.retries(),
+            self.rate_limited(),
+            format_bytes(self.bytes_sent()),
+            format_bytes(self.bytes_received()),
+            self.connections_opened(),
+            elapsed.as_secs_f64()
+        )
+    }
+
+    pub(crate) fn record_request(&self) {
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_retries(&self, retries: usize) {
+        self.counters.retries.fetch_add(retries, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rate_limited(&self) {
+        self.counters.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_sent(&self, bytes: usize) {
+        self.counters
+            .bytes_sent
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_received(&self, bytes: usize) {
+        self.counters
+            .bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn layer(&self) -> CountingLayer {
+        CountingLayer {
+            stats: self.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ConnectionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, {} connections opened, {} reused",
+            self.requests(),
+            self.connections_opened(),
+            self.connections_reused()
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Connector layer that counts every new connection the pool has to establish.
+#[derive(Clone)]
+pub(crate) struct CountingLayer {
+    stats: ConnectionStats,
+}
+
+impl<S> Layer<S> for CountingLayer {
+    type Service = CountingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountingConnector {
+            inner,
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct CountingConnector<S> {
+    inner: S,
+    stats: ConnectionStats,
+}
+
+impl<S, R> Service<R> for CountingConnector<S>
+where
+    S: Service<R>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        self.stats
+            .counters
+            .connections
+            .fetch_add(1, Ordering::Relaxed);
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reused_is_requests_minus_connections() {
+        let stats = ConnectionStats::default();
+        let shared = stats.clone();
+        for _ in 0..5 {
+            shared.record_request();
+        }
+        stats.counters.connections.fetch_add(2, Ordering::Relaxed);
+
+        assert_eq!(stats.connections_reused(), 3);
+        assert_eq!(
+            stats.to_string(),
+            "5 requests, 2 connections opened, 3 reused"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let stats = ConnectionStats::default();
+        stats.record_request();
+        stats.record_request();
+        stats.record_retries(1);
+        stats.record_rate_limited();
+        stats.record_sent(512);
+        stats.record_received(3 * 1024 * 1024 / 2);
+
+        assert_eq!(
+            stats.summary(Duration::from_millis(2310)),
+            "2 requests, 1 retries, 1 rate-limited (429), 512 B sent, 1.5 MB received, 0 connections opened, 2.31s wall time"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use url::Url;
+
+pub use atlassian_cli_output::{normalize_field, REDACTED};
+
+/// Query parameters masked in `--trace-http` logs. Names match case-insensitively
+/// and ignoring `_`, `-` and spaces, so `customer_email` also covers
+/// `customerEmail`. Values that contain an email address are always masked.
+#[derive(Debug, Clone, Default)]
+pub struct TraceRedaction {
+    fields: Arc<[String]>,
+}
+
+impl TraceRedaction {
+    pub fn new<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|f| normalize_field(f.as_ref()))
+                .collect(),
+        }
+    }
+
+    fn masks(&self, name: &str, value: &str) -> bool {
+        let name = normalize_field(name);
+        value.contains('@') || self.fields.contains(&name)
+    }
+
+    /// `url` with the values of matching query parameters replaced.
+    pub fn url(&self, url: &Url) -> String {
+        if url.query().is_none() {
+            return url.to_string();
+        }
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if self.masks(&name, &value) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+
+        let mut redacted = url.clone();
+        redacted.query_pairs_mut().clear().extend_pairs(pairs);
+        redacted.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url() {
+        let redaction = TraceRedaction::new(["customer_email", "accountId"]);
+        let url = Url::parse(
+            "https://acme.atlassian.net/rest/api/3/user/search?query=ana%40example.com&maxResults=5",
+        )
+        .unwrap();
+        assert_eq!(
+            redaction.url(&url),
+            "https://acme.atlassian.net/rest/api/3/user/search?query=%5Bredacted%5D&maxResults=5"
+        );
+
+        let url = Url::parse("https://acme.atlassian.net/rest/api/3/user?account_id=5b10").unwrap();
+        assert!(redaction.url(&url).ends_with("account_id=%5Bredacted%5D"));
+
+        let url = Url::parse("https://acme.atlassian.net/rest/api/3/issue/DEV-1").unwrap();
+        assert_eq!(redaction.url(&url), url.as_str());
+    }
+
+    #[test]
+    fn test_normalize_field() {
+        assert_eq!(normalize_field("Customer_Email"), "customeremail");
+        assert_eq!(normalize_field("customerEmail"), "customeremail");
+    }
+}
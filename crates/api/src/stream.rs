@@ -0,0 +1,231 @@
+use crate::error::{ApiError, Result};
+use crate::pool::ConnectionStats;
+use async_stream::try_stream;
+use futures::stream::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// Incrementally parses the elements of a JSON array out of a chunked body, so
+/// large responses can be processed without buffering the whole document.
+///
+/// The array is either the top-level value (`field: None`) or the value of a
+/// top-level object key such as `issues` or `values`. Only one element is held
+/// in memory at a time.
+pub struct JsonArrayReader<T> {
+    field: Option<Vec<u8>>,
+    mode: Mode,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    key_buf: Vec<u8>,
+    candidate_key: Option<Vec<u8>>,
+    current_key: Option<Vec<u8>>,
+    element: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Seeking,
+    InArray { depth: usize },
+    Done,
+}
+
+impl<T: DeserializeOwned> JsonArrayReader<T> {
+    pub fn new(field: Option<&str>) -> Self {
+        Self {
+            field: field.map(|f| f.as_bytes().to_vec()),
+            mode: Mode::Seeking,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            key_buf: Vec::new(),
+            candidate_key: None,
+            current_key: None,
+            element: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Feed the next chunk of the body, returning every element completed by it.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+
+        for &byte in chunk {
+            if self.mode == Mode::Done {
+                break;
+            }
+
+            if let Mode::InArray { depth } = self.mode {
+                if !self.in_string && self.depth == depth && (byte == b',' || byte == b']') {
+                    if let Some(item) = self.take_element()? {
+                        items.push(item);
+                    }
+                    if byte == b']' {
+                        self.depth -= 1;
+                        self.mode = Mode::Done;
+                    }
+                    continue;
+                }
+                self.element.push(byte);
+            }
+
+            self.scan(byte);
+        }
+
+        Ok(items)
+    }
+
+    /// Confirm the array was found and fully read once the body is exhausted.
+    pub fn finish(self) -> Result<()> {
+        match self.mode {
+            Mode::Done => Ok(()),
+            Mode::Seeking => Err(ApiError::InvalidResponse(match &self.field {
+                Some(field) => {
+                    format!("Response has no '{}' array", String::from_utf8_lossy(field))
+                }
+                None => "Response is not a JSON array".to_string(),
+            })),
+            Mode::InArray { .. } => Err(ApiError::InvalidResponse(
+                "Response ended before the JSON array was closed".to_string(),
+            )),
+        }
+    }
+
+    fn take_element(&mut self) -> Result<Option<T>> {
+        let raw = std::mem::take(&mut self.element);
+        if raw.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+        serde_json::from_slice(&raw)
+            .map(Some)
+            .map_err(ApiError::from)
+    }
+
+    /// Track strings and nesting, and detect the start of the target array.
+    fn scan(&mut self, byte: u8) {
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.mode == Mode::Seeking && self.depth == 1 {
+                    self.candidate_key = Some(std::mem::take(&mut self.key_buf));
+                }
+                return;
+            }
+            if self.mode == Mode::Seeking && self.depth == 1 {
+                self.key_buf.push(byte);
+            }
+            return;
+        }
+
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                self.key_buf.clear();
+            }
+            b'{' => self.depth += 1,
+            b'[' => {
+                if self.mode == Mode::Seeking && self.is_target_array() {
+                    self.mode = Mode::InArray {
+                        depth: self.depth + 1,
+                    };
+                }
+                self.depth += 1;
+            }
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            b':' if self.depth == 1 => self.current_key = self.candidate_key.take(),
+            b',' if self.depth == 1 => self.current_key = None,
+            _ => {}
+        }
+    }
+
+    fn is_target_array(&self) -> bool {
+        match &self.field {
+            None => self.depth == 0,
+            Some(field) => self.depth == 1 && self.current_key.as_ref() == Some(field),
+        }
+    }
+}
+
+/// Stream the elements of a JSON array out of a response body as they arrive.
+pub fn json_array_stream<T>(
+    response: reqwest::Response,
+    field: Option<&str>,
+    stats: ConnectionStats,
+) -> impl Stream<Item = Result<T>>
+where
+    T: DeserializeOwned,
+{
+    let mut reader = JsonArrayReader::<T>::new(field);
+    let mut body = response.bytes_stream();
+
+    try_stream! {
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(ApiError::RequestFailed)?;
+            stats.record_received(chunk.len());
+            for item in reader.feed(&chunk)? {
+                yield item;
+            }
+        }
+        reader.finish()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn read_all(body: &str, field: Option<&str>, chunk_size: usize) -> Result<Vec<Value>> {
+        let mut reader = JsonArrayReader::new(field);
+        let mut items = Vec::new();
+        for chunk in body.as_bytes().chunks(chunk_size) {
+            items.extend(reader.feed(chunk)?);
+        }
+        reader.finish()?;
+        Ok(items)
+    }
+
+    #[test]
+    fn test_top_level_array_across_chunks() {
+        let body = r#"[{"key": "A-1", "s": "a,]b"}, {"key": "A-2", "n": [1, 2]}, 3]"#;
+        for chunk_size in [1, 3, 7, body.len()] {
+            let items = read_all(body, None, chunk_size).unwrap();
+            assert_eq!(
+                items,
+                vec![
+                    json!({"key": "A-1", "s": "a,]b"}),
+                    json!({"key": "A-2", "n": [1, 2]}),
+                    json!(3)
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_named_field_skips_other_keys() {
+        let body = r#"{"names": ["x"], "meta": {"issues": [9]}, "total": 2, "issues": [{"key": "A-1"}, {"key": "A-2"}]}"#;
+        let items = read_all(body, Some("issues"), 5).unwrap();
+        assert_eq!(items, vec![json!({"key": "A-1"}), json!({"key": "A-2"})]);
+    }
+
+    #[test]
+    fn test_escaped_quotes_in_strings() {
+        let body = r#"{"issues": [{"summary": "say \"hi\" ]"}]}"#;
+        let items = read_all(body, Some("issues"), 4).unwrap();
+        assert_eq!(items, vec![json!({"summary": "say \"hi\" ]"})]);
+    }
+
+    #[test]
+    fn test_empty_and_missing_arrays() {
+        assert!(read_all(r#"{"issues": []}"#, Some("issues"), 2)
+            .unwrap()
+            .is_empty());
+        assert!(read_all(r#"{"values": []}"#, Some("issues"), 2).is_err());
+        assert!(read_all(r#"[{"a": 1}"#, None, 2).is_err());
+    }
+}
@@ -1,20 +1,159 @@
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 #[cfg(unix)]
 use std::os::unix::fs::OpenOptionsExt;
 
+mod vault;
+
+pub use vault::{Vault, VaultKey};
+
 /// Helper to construct a key for profile secrets.
 pub fn token_key(profile: &str) -> String {
     profile.to_string()
 }
 
+/// Key for a profile's Statuspage API key, stored next to its Atlassian token.
+pub fn statuspage_token_key(profile: &str) -> String {
+    format!("{profile}:statuspage")
+}
+
 fn credentials_path() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(".atlassian-cli").join("credentials"))
 }
 
+fn vault_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".atlassian-cli").join("credentials.vault"))
+}
+
+const KEYRING_SERVICE: &str = "atlassian-cli";
+
+/// Backend that holds API tokens.
+#[derive(Debug, Clone)]
+pub enum CredentialStore {
+    /// Plaintext JSON file with 600 permissions.
+    File,
+    /// Encrypted vault; plaintext credentials are migrated into it on first use.
+    EncryptedFile(VaultKey),
+    /// The operating system's keyring, under the `atlassian-cli` service.
+    Keyring,
+}
+
+impl CredentialStore {
+    pub fn get(&self, account: &str) -> Result<Option<String>> {
+        match self {
+            CredentialStore::File => get_secret(account),
+            CredentialStore::EncryptedFile(key) => Ok(open_vault(key)?.load()?.remove(account)),
+            CredentialStore::Keyring => match keyring_entry(account)?.get_password() {
+                Ok(secret) => Ok(Some(secret)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(err) => Err(err).context("Unable to read from the system keyring"),
+            },
+        }
+    }
+
+    pub fn set(&self, account: &str, secret: &str) -> Result<()> {
+        match self {
+            CredentialStore::File => set_secret(account, secret),
+            CredentialStore::EncryptedFile(key) => {
+                let vault = open_vault(key)?;
+                let mut creds = vault.load()?;
+                creds.insert(account.to_string(), secret.to_string());
+                vault.save(&creds)
+            }
+            CredentialStore::Keyring => keyring_entry(account)?
+                .set_password(secret)
+                .context("Unable to write to the system keyring"),
+        }
+    }
+
+    pub fn delete(&self, account: &str) -> Result<()> {
+        match self {
+            CredentialStore::File => delete_secret(account),
+            CredentialStore::EncryptedFile(key) => {
+                let vault = open_vault(key)?;
+                let mut creds = vault.load()?;
+                if creds.remove(account).is_some() {
+                    vault.save(&creds)?;
+                }
+                Ok(())
+            }
+            CredentialStore::Keyring => match keyring_entry(account)?.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(err) => Err(err).context("Unable to delete from the system keyring"),
+            },
+        }
+    }
+}
+
+fn keyring_entry(account: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, account).context("Unable to open the system keyring")
+}
+
+/// Open the default vault. The first time, any plaintext credentials file is
+/// migrated into it; the vault records that so later runs skip the check.
+fn open_vault(key: &VaultKey) -> Result<Vault> {
+    let path = vault_path().context("Cannot determine home directory")?;
+    let vault = Vault::new(path, key.clone());
+
+    if !vault.is_migrated()? {
+        let plaintext = credentials_path().context("Cannot determine home directory")?;
+        let migrated = vault.migrate_from(&plaintext)?;
+        if migrated > 0 {
+            eprintln!("Migrated {migrated} plaintext credentials into the encrypted vault");
+        }
+    }
+    Ok(vault)
+}
+
+/// Run an external credential helper (`program args...`) and return the token
+/// it prints on stdout. The helper's stderr is passed through so it can prompt
+/// or report errors.
+pub fn run_credential_helper(command: &[String]) -> Result<String> {
+    let (program, args) = command
+        .split_first()
+        .context("credential_helper command is empty")?;
+
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("Failed to run credential helper `{program}`"))?;
+    if !output.status.success() {
+        anyhow::bail!("Credential helper `{program}` failed ({})", output.status);
+    }
+
+    let token = String::from_utf8(output.stdout)
+        .with_context(|| format!("Credential helper `{program}` printed invalid UTF-8"))?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        anyhow::bail!("Credential helper `{program}` printed no token");
+    }
+    Ok(token)
+}
+
+/// Write a file readable only by the current user (600 on unix).
+pub(crate) fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
 /// Store a secret in the credentials file with 600 permissions.
 pub fn set_secret(account: &str, secret: &str) -> Result<()> {
     let path = credentials_path().context("Cannot determine home directory")?;
@@ -31,30 +170,7 @@ pub fn set_secret(account: &str, secret: &str) -> Result<()> {
 
     creds.insert(account.to_string(), secret.to_string());
 
-    #[cfg(unix)]
-    {
-        use std::io::Write;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .mode(0o600)
-            .open(&path)?;
-        let json = serde_json::to_string_pretty(&creds)?;
-        file.write_all(json.as_bytes())?;
-    }
-
-    #[cfg(not(unix))]
-    {
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&path)?;
-        serde_json::to_writer_pretty(file, &creds)?;
-    }
-
-    Ok(())
+    write_private(&path, serde_json::to_string_pretty(&creds)?.as_bytes())
 }
 
 /// Get a secret from the credentials file.
@@ -78,28 +194,33 @@ pub fn delete_secret(account: &str) -> Result<()> {
     let mut creds: HashMap<String, String> = serde_json::from_str(&content).unwrap_or_default();
     creds.remove(account);
 
-    #[cfg(unix)]
-    {
-        use std::io::Write;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .mode(0o600)
-            .open(&path)?;
-        let json = serde_json::to_string_pretty(&creds)?;
-        file.write_all(json.as_bytes())?;
-    }
+    write_private(&path, serde_json::to_string_pretty(&creds)?.as_bytes())
+}
 
-    #[cfg(not(unix))]
-    {
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&path)?;
-        serde_json::to_writer_pretty(file, &creds)?;
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn command(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|p| p.to_string()).collect()
     }
 
-    Ok(())
+    #[test]
+    fn test_run_credential_helper() {
+        let token = run_credential_helper(&command(&["sh", "-c", "echo '  secret-token  '"]));
+        assert_eq!(token.unwrap(), "secret-token");
+
+        let failed = run_credential_helper(&command(&["sh", "-c", "exit 3"])).unwrap_err();
+        assert!(failed
+            .to_string()
+            .starts_with("Credential helper `sh` failed"));
+
+        let empty = run_credential_helper(&command(&["true"])).unwrap_err();
+        assert_eq!(
+            empty.to_string(),
+            "Credential helper `true` printed no token"
+        );
+
+        assert!(run_credential_helper(&[]).is_err());
+    }
 }
@@ -0,0 +1,314 @@
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::write_private;
+
+const VAULT_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+/// Where the vault encryption key comes from.
+#[derive(Debug, Clone)]
+pub enum VaultKey {
+    /// A passphrase, e.g. from `ATLASSIAN_CLI_VAULT_PASSPHRASE`.
+    Passphrase(String),
+    /// A file whose contents are used as the passphrase.
+    KeyFile(PathBuf),
+}
+
+impl VaultKey {
+    fn secret(&self) -> Result<Vec<u8>> {
+        let secret = match self {
+            VaultKey::Passphrase(passphrase) => passphrase.as_bytes().to_vec(),
+            VaultKey::KeyFile(path) => fs::read(path)
+                .with_context(|| format!("Unable to read vault key file {}", path.display()))?
+                .trim_ascii()
+                .to_vec(),
+        };
+        if secret.is_empty() {
+            bail!("Vault passphrase cannot be empty");
+        }
+        Ok(secret)
+    }
+}
+
+/// On-disk format: the credentials map as JSON, sealed with ChaCha20-Poly1305
+/// under a key derived from the passphrase with Argon2id.
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    /// Set once the plaintext credentials file has been migrated in.
+    #[serde(default)]
+    migrated: bool,
+}
+
+/// Credentials encrypted at rest for hosts without a usable keyring.
+pub struct Vault {
+    path: PathBuf,
+    key: VaultKey,
+}
+
+impl Vault {
+    pub fn new(path: PathBuf, key: VaultKey) -> Self {
+        Self { path, key }
+    }
+
+    /// Read the vault file without decrypting it; `None` if there is none yet.
+    fn read_file(&self) -> Result<Option<VaultFile>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let raw = fs::read_to_string(&self.path)
+            .with_context(|| format!("Unable to read vault {}", self.path.display()))?;
+        let file: VaultFile = serde_json::from_str(&raw)
+            .with_context(|| format!("Malformed vault file {}", self.path.display()))?;
+        if file.version != VAULT_VERSION {
+            bail!("Unsupported vault version {}", file.version);
+        }
+        Ok(Some(file))
+    }
+
+    /// Whether plaintext credentials have already been migrated into the vault.
+    pub fn is_migrated(&self) -> Result<bool> {
+        Ok(self.read_file()?.is_some_and(|file| file.migrated))
+    }
+
+    /// Decrypt all stored credentials; a missing vault is empty.
+    pub fn load(&self) -> Result<HashMap<String, String>> {
+        let Some(file) = self.read_file()? else {
+            return Ok(HashMap::new());
+        };
+
+        let salt = STANDARD.decode(&file.salt).context("Invalid vault salt")?;
+        let nonce = STANDARD
+            .decode(&file.nonce)
+            .context("Invalid vault nonce")?;
+        let ciphertext = STANDARD
+            .decode(&file.ciphertext)
+            .context("Invalid vault ciphertext")?;
+        if nonce.len() != 12 {
+            bail!("Invalid vault nonce");
+        }
+
+        let cipher = self.cipher(&salt)?;
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+            .map_err(|_| anyhow!("Unable to decrypt vault: wrong passphrase or corrupted file"))?;
+
+        serde_json::from_slice(&plaintext).context("Malformed vault contents")
+    }
+
+    /// Encrypt and write all credentials, using a fresh salt and nonce.
+    pub fn save(&self, creds: &HashMap<String, String>) -> Result<()> {
+        let migrated = self.is_migrated()?;
+        self.write(creds, migrated)
+    }
+
+    /// Write the sealed vault to a temporary file next to it and rename it
+    /// into place, so a failed write never leaves a truncated vault behind.
+    fn write(&self, creds: &HashMap<String, String>, migrated: bool) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let plaintext = serde_json::to_vec(creds)?;
+        let ciphertext = self
+            .cipher(&salt)?
+            .encrypt(&nonce, plaintext.as_ref())
+            .map_err(|_| anyhow!("Unable to encrypt vault"))?;
+
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+            migrated,
+        };
+
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        write_private(&temp, serde_json::to_string_pretty(&file)?.as_bytes())?;
+        if let Err(err) = fs::rename(&temp, &self.path) {
+            let _ = fs::remove_file(&temp);
+            return Err(err)
+                .with_context(|| format!("Unable to write vault {}", self.path.display()));
+        }
+        Ok(())
+    }
+
+    /// Move entries from a plaintext credentials file into the vault and delete
+    /// the plaintext file. Entries already in the vault win. The vault records
+    /// that the migration ran, even when there was nothing to move, so it is
+    /// not repeated. Returns how many credentials were migrated.
+    pub fn migrate_from(&self, plaintext_path: &Path) -> Result<usize> {
+        if self.is_migrated()? {
+            return Ok(0);
+        }
+        if !plaintext_path.exists() {
+            let creds = self.load()?;
+            self.write(&creds, true)?;
+            return Ok(0);
+        }
+
+        let content = fs::read_to_string(plaintext_path)?;
+        let plain: HashMap<String, String> = serde_json::from_str(&content)
+            .with_context(|| format!("Malformed credentials file {}", plaintext_path.display()))?;
+
+        let mut creds = self.load()?;
+        let mut migrated = 0;
+        for (account, secret) in plain {
+            if let Entry::Vacant(entry) = creds.entry(account) {
+                entry.insert(secret);
+                migrated += 1;
+            }
+        }
+        self.write(&creds, true)?;
+
+        fs::remove_file(plaintext_path).with_context(|| {
+            format!(
+                "Unable to remove plaintext credentials {}",
+                plaintext_path.display()
+            )
+        })?;
+        Ok(migrated)
+    }
+
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(&self.key.secret()?, salt, &mut key)
+            .map_err(|err| anyhow!("Unable to derive vault key: {err}"))?;
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn vault(dir: &TempDir, passphrase: &str) -> Vault {
+        Vault::new(
+            dir.path().join("credentials.vault"),
+            VaultKey::Passphrase(passphrase.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_round_trip_is_encrypted() {
+        let dir = TempDir::new().unwrap();
+        let vault = vault(&dir, "correct horse");
+
+        let mut creds = HashMap::new();
+        creds.insert("work".to_string(), "token-123".to_string());
+        vault.save(&creds).unwrap();
+
+        let raw = fs::read_to_string(dir.path().join("credentials.vault")).unwrap();
+        assert!(!raw.contains("token-123"));
+        assert_eq!(vault.load().unwrap(), creds);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let dir = TempDir::new().unwrap();
+        let mut creds = HashMap::new();
+        creds.insert("work".to_string(), "token-123".to_string());
+        vault(&dir, "right").save(&creds).unwrap();
+
+        let err = vault(&dir, "wrong").load().unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn test_key_file() {
+        let dir = TempDir::new().unwrap();
+        let key_path = dir.path().join("vault.key");
+        fs::write(&key_path, "from-a-file\n").unwrap();
+
+        let by_file = Vault::new(
+            dir.path().join("credentials.vault"),
+            VaultKey::KeyFile(key_path),
+        );
+        let mut creds = HashMap::new();
+        creds.insert("work".to_string(), "token-123".to_string());
+        by_file.save(&creds).unwrap();
+
+        assert_eq!(vault(&dir, "from-a-file").load().unwrap(), creds);
+    }
+
+    #[test]
+    fn test_migrate_from_plaintext() {
+        let dir = TempDir::new().unwrap();
+        let plain = dir.path().join("credentials");
+        fs::write(&plain, r#"{"work": "old", "personal": "token-456"}"#).unwrap();
+
+        let vault = vault(&dir, "secret");
+        let mut existing = HashMap::new();
+        existing.insert("work".to_string(), "new".to_string());
+        vault.save(&existing).unwrap();
+
+        assert_eq!(vault.migrate_from(&plain).unwrap(), 1);
+        assert!(!plain.exists());
+
+        let creds = vault.load().unwrap();
+        assert_eq!(creds["work"], "new");
+        assert_eq!(creds["personal"], "token-456");
+        assert!(vault.is_migrated().unwrap());
+
+        // A plaintext file written later is left alone: the migration ran once
+        fs::write(&plain, r#"{"late": "token-789"}"#).unwrap();
+        assert_eq!(vault.migrate_from(&plain).unwrap(), 0);
+        assert!(plain.exists());
+        assert!(!vault.load().unwrap().contains_key("late"));
+
+        // Saving keeps the marker
+        vault.save(&creds).unwrap();
+        assert!(vault.is_migrated().unwrap());
+    }
+
+    #[test]
+    fn test_migration_recorded_without_plaintext() {
+        let dir = TempDir::new().unwrap();
+        let vault = vault(&dir, "secret");
+        assert!(!vault.is_migrated().unwrap());
+
+        assert_eq!(
+            vault.migrate_from(&dir.path().join("credentials")).unwrap(),
+            0
+        );
+        assert!(vault.is_migrated().unwrap());
+        assert!(vault.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_save_replaces_vault_atomically() {
+        let dir = TempDir::new().unwrap();
+        let vault = vault(&dir, "secret");
+        let mut creds = HashMap::new();
+        creds.insert("work".to_string(), "token-123".to_string());
+        vault.save(&creds).unwrap();
+        creds.insert("personal".to_string(), "token-456".to_string());
+        vault.save(&creds).unwrap();
+
+        assert_eq!(vault.load().unwrap(), creds);
+        let files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec!["credentials.vault"]);
+    }
+}
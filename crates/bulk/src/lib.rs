@@ -1,10 +1,13 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use futures::stream::{self, StreamExt, TryStreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
 use thiserror::Error;
 use tokio::sync::Semaphore;
+pub use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 #[derive(Error, Debug)]
@@ -12,6 +15,17 @@ pub enum BulkError {
     #[error("Multiple tasks failed: {count} failures")]
     MultipleFailed { count: usize },
 
+    #[error(
+        "Interrupted: {completed} completed, {failed} failed, {not_started} not started, \
+         {abandoned} abandoned mid-request"
+    )]
+    Interrupted {
+        completed: usize,
+        failed: usize,
+        not_started: usize,
+        abandoned: usize,
+    },
+
     #[error("Semaphore acquire error: {0}")]
     SemaphoreError(#[from] tokio::sync::AcquireError),
 }
@@ -39,11 +53,29 @@ impl Default for BulkConfig {
 pub struct BulkResult<T> {
     pub successful: Vec<T>,
     pub failed: Vec<(usize, anyhow::Error)>,
+    /// Items that never ran because the run was cancelled.
+    pub not_started: Vec<usize>,
+    /// Items still in flight when the grace period after cancellation ran out.
+    pub abandoned: Vec<usize>,
 }
 
 impl<T> BulkResult<T> {
     pub fn is_complete_success(&self) -> bool {
-        self.failed.is_empty()
+        self.failed.is_empty() && !self.is_interrupted()
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        !self.not_started.is_empty() || !self.abandoned.is_empty()
+    }
+
+    /// The same summary `execute` fails with when a run is cancelled.
+    pub fn interrupted_error(&self) -> BulkError {
+        BulkError::Interrupted {
+            completed: self.successful.len(),
+            failed: self.failed.len(),
+            not_started: self.not_started.len(),
+            abandoned: self.abandoned.len(),
+        }
     }
 
     pub fn success_count(&self) -> usize {
@@ -55,12 +87,70 @@ impl<T> BulkResult<T> {
     }
 }
 
+/// One operation of a bulk run, as shown in a dry-run preview.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedOperation {
+    pub item: String,
+    pub action: String,
+    pub target: String,
+}
+
+impl PlannedOperation {
+    pub fn new(
+        item: impl Into<String>,
+        action: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        Self {
+            item: item.into(),
+            action: action.into(),
+            target: target.into(),
+        }
+    }
+}
+
+/// How one item of a run ended. Dry-run items are `Done(None)`.
+enum Outcome<R> {
+    Done(R),
+    Failed(anyhow::Error),
+    NotStarted,
+    Abandoned,
+}
+
+#[derive(Default)]
+struct OutcomeCounts {
+    completed: usize,
+    failed: usize,
+    not_started: usize,
+    abandoned: usize,
+}
+
+impl OutcomeCounts {
+    fn interrupted(&self) -> bool {
+        self.not_started + self.abandoned > 0
+    }
+
+    fn into_error(self) -> BulkError {
+        BulkError::Interrupted {
+            completed: self.completed,
+            failed: self.failed,
+            not_started: self.not_started,
+            abandoned: self.abandoned,
+        }
+    }
+}
+
+/// In-flight items get this long to finish after cancellation.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
 /// Executes multiple operations with optional concurrency limits and dry-run support.
 pub struct BulkExecutor {
     concurrency: usize,
     dry_run: bool,
     show_progress: bool,
     fail_fast: bool,
+    cancel: CancellationToken,
+    grace_period: Duration,
 }
 
 impl BulkExecutor {
@@ -70,6 +160,8 @@ impl BulkExecutor {
             dry_run,
             show_progress: true,
             fail_fast: false,
+            cancel: CancellationToken::new(),
+            grace_period: DEFAULT_GRACE_PERIOD,
         }
     }
 
@@ -79,6 +171,8 @@ impl BulkExecutor {
             dry_run: config.dry_run,
             show_progress: config.show_progress,
             fail_fast: config.fail_fast,
+            cancel: CancellationToken::new(),
+            grace_period: DEFAULT_GRACE_PERIOD,
         }
     }
 
@@ -92,6 +186,19 @@ impl BulkExecutor {
         self
     }
 
+    /// Stop scheduling new items once `cancel` fires. Items already running
+    /// get [`with_grace_period`](Self::with_grace_period) to finish before they
+    /// are abandoned.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
     pub async fn run<T, Fut, F>(&self, items: Vec<T>, job: F) -> Result<()>
     where
         T: Send + Sync + std::fmt::Debug + 'static,
@@ -110,52 +217,37 @@ impl BulkExecutor {
             "Starting bulk execution"
         );
 
-        let semaphore = Arc::new(Semaphore::new(self.concurrency));
-        let job = Arc::new(job);
         let progress = self.create_progress_bar(total);
-        let dry_run = self.dry_run;
-
-        let results = stream::iter(items.into_iter().enumerate().map(|(idx, item)| {
-            let job = Arc::clone(&job);
-            let semaphore = Arc::clone(&semaphore);
-            let progress = progress.clone();
-            async move {
-                let _permit = semaphore.acquire().await?;
-                if dry_run {
-                    info!(?item, "Dry run: skipping execution");
-                    progress.inc(1);
-                    return Ok(());
-                }
-                debug!(index = idx, "Processing item");
-                match job(item).await {
-                    Ok(()) => {
-                        progress.inc(1);
-                        Ok(())
-                    }
-                    Err(e) => {
-                        warn!(index = idx, error = %e, "Task failed");
-                        progress.inc(1);
-                        Err(e)
-                    }
+        let outcomes = self.drive(items, job, &progress).await?;
+
+        let mut counts = OutcomeCounts::default();
+        let mut failures = Vec::new();
+        for (_, outcome) in outcomes {
+            match outcome {
+                Outcome::Done(_) => counts.completed += 1,
+                Outcome::Failed(e) => {
+                    counts.failed += 1;
+                    failures.push(e);
                 }
+                Outcome::NotStarted => counts.not_started += 1,
+                Outcome::Abandoned => counts.abandoned += 1,
             }
-        }))
-        .buffer_unordered(self.concurrency);
+        }
 
-        if self.fail_fast {
-            results.try_collect::<Vec<_>>().await?;
-        } else {
-            let all_results: Vec<Result<()>> = results.collect().await;
-            let failures: Vec<_> = all_results.into_iter().filter_map(|r| r.err()).collect();
-
-            if !failures.is_empty() {
-                warn!(failure_count = failures.len(), "Some tasks failed");
-                progress.finish_with_message(format!("Completed with {} failures", failures.len()));
-                return Err(BulkError::MultipleFailed {
-                    count: failures.len(),
-                }
-                .into());
+        if counts.interrupted() {
+            let err = counts.into_error();
+            warn!(%err, "Bulk execution interrupted");
+            progress.abandon_with_message(err.to_string());
+            return Err(err.into());
+        }
+
+        if !failures.is_empty() {
+            warn!(failure_count = failures.len(), "Some tasks failed");
+            progress.finish_with_message(format!("Completed with {} failures", failures.len()));
+            return Err(BulkError::MultipleFailed {
+                count: failures.len(),
             }
+            .into());
         }
 
         progress.finish_with_message("All tasks completed successfully");
@@ -163,6 +255,38 @@ impl BulkExecutor {
         Ok(())
     }
 
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Describe every item with `describe`. In dry-run mode the plan is returned
+    /// without calling `job`; otherwise the jobs run and the plan is returned as
+    /// the record of what was done.
+    pub async fn run_planned<T, D, Fut, F>(
+        &self,
+        items: Vec<T>,
+        describe: D,
+        job: F,
+    ) -> Result<Vec<PlannedOperation>>
+    where
+        T: Send + Sync + std::fmt::Debug + 'static,
+        D: Fn(&T) -> PlannedOperation,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<()>> + Send,
+    {
+        let plan: Vec<PlannedOperation> = items.iter().map(describe).collect();
+        if self.dry_run {
+            info!(
+                operations = plan.len(),
+                "Dry run: collected planned operations"
+            );
+            return Ok(plan);
+        }
+
+        self.run(items, job).await?;
+        Ok(plan)
+    }
+
     pub async fn execute_with_results<T, R, Fut, F>(
         &self,
         items: Vec<T>,
@@ -179,6 +303,8 @@ impl BulkExecutor {
             return Ok(BulkResult {
                 successful: vec![],
                 failed: vec![],
+                not_started: vec![],
+                abandoned: vec![],
             });
         }
 
@@ -189,47 +315,40 @@ impl BulkExecutor {
             "Starting bulk execution with results"
         );
 
-        let semaphore = Arc::new(Semaphore::new(self.concurrency));
-        let job = Arc::new(job);
         let progress = self.create_progress_bar(total);
-        let dry_run = self.dry_run;
-
-        let results: Vec<(usize, Result<R>)> =
-            stream::iter(items.into_iter().enumerate().map(|(idx, item)| {
-                let job = Arc::clone(&job);
-                let semaphore = Arc::clone(&semaphore);
-                let progress = progress.clone();
-                async move {
-                    let _permit = semaphore.acquire().await?;
-                    if dry_run {
-                        info!(?item, "Dry run: skipping execution");
-                        progress.inc(1);
-                        return Ok::<(usize, Result<R>), anyhow::Error>((
-                            idx,
-                            Err(anyhow::anyhow!("Dry run")),
-                        ));
-                    }
-                    debug!(index = idx, "Processing item");
-                    let result = job(item).await;
-                    progress.inc(1);
-                    Ok((idx, result))
-                }
-            }))
-            .buffer_unordered(self.concurrency)
-            .try_collect()
-            .await?;
+        let outcomes = self.drive(items, job, &progress).await?;
 
         let mut successful = Vec::new();
         let mut failed = Vec::new();
-
-        for (idx, result) in results {
-            match result {
-                Ok(value) => successful.push(value),
-                Err(error) => failed.push((idx, error)),
+        let mut not_started = Vec::new();
+        let mut abandoned = Vec::new();
+
+        for (idx, outcome) in outcomes {
+            match outcome {
+                Outcome::Done(Some(value)) => successful.push(value),
+                Outcome::Done(None) => failed.push((idx, anyhow::anyhow!("Dry run"))),
+                Outcome::Failed(error) => failed.push((idx, error)),
+                Outcome::NotStarted => not_started.push(idx),
+                Outcome::Abandoned => abandoned.push(idx),
             }
         }
 
-        if !failed.is_empty() {
+        if !not_started.is_empty() || !abandoned.is_empty() {
+            warn!(
+                success_count = successful.len(),
+                failure_count = failed.len(),
+                not_started = not_started.len(),
+                abandoned = abandoned.len(),
+                "Bulk execution interrupted"
+            );
+            progress.abandon_with_message(format!(
+                "Interrupted: {} succeeded, {} failed, {} not started, {} abandoned",
+                successful.len(),
+                failed.len(),
+                not_started.len(),
+                abandoned.len()
+            ));
+        } else if !failed.is_empty() {
             warn!(
                 success_count = successful.len(),
                 failure_count = failed.len(),
@@ -247,10 +366,95 @@ impl BulkExecutor {
         info!(
             success = successful.len(),
             failures = failed.len(),
+            not_started = not_started.len(),
+            abandoned = abandoned.len(),
             "Bulk execution completed"
         );
 
-        Ok(BulkResult { successful, failed })
+        Ok(BulkResult {
+            successful,
+            failed,
+            not_started,
+            abandoned,
+        })
+    }
+
+    /// Run every item under the concurrency limit and report how each ended.
+    /// After cancellation, queued items are not started and running ones are
+    /// abandoned once the grace period runs out. With fail-fast the first
+    /// failure is returned as the error and nothing further runs.
+    async fn drive<T, R, Fut, F>(
+        &self,
+        items: Vec<T>,
+        job: F,
+        progress: &ProgressBar,
+    ) -> Result<Vec<(usize, Outcome<Option<R>>)>>
+    where
+        T: Send + Sync + std::fmt::Debug + 'static,
+        R: Send + 'static,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<R>> + Send,
+    {
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let job = Arc::new(job);
+        let dry_run = self.dry_run;
+        let grace_period = self.grace_period;
+
+        let outcomes = stream::iter(items.into_iter().enumerate().map(|(idx, item)| {
+            let job = Arc::clone(&job);
+            let semaphore = Arc::clone(&semaphore);
+            let progress = progress.clone();
+            let cancel = self.cancel.clone();
+            async move {
+                let _permit = semaphore.acquire().await?;
+                if cancel.is_cancelled() {
+                    debug!(index = idx, "Cancelled: not starting item");
+                    return Ok((idx, Outcome::NotStarted));
+                }
+                if dry_run {
+                    info!(?item, "Dry run: skipping execution");
+                    progress.inc(1);
+                    return Ok((idx, Outcome::Done(None)));
+                }
+                debug!(index = idx, "Processing item");
+
+                let task = job(item);
+                tokio::pin!(task);
+                let outcome = tokio::select! {
+                    result = &mut task => match result {
+                        Ok(value) => Outcome::Done(Some(value)),
+                        Err(e) => {
+                            warn!(index = idx, error = %e, "Task failed");
+                            Outcome::Failed(e)
+                        }
+                    },
+                    _ = async {
+                        cancel.cancelled().await;
+                        tokio::time::sleep(grace_period).await;
+                    } => {
+                        warn!(index = idx, "Cancelled: abandoned item after grace period");
+                        Outcome::Abandoned
+                    }
+                };
+                progress.inc(1);
+                Ok::<_, anyhow::Error>((idx, outcome))
+            }
+        }))
+        .buffer_unordered(self.concurrency);
+
+        if self.fail_fast {
+            outcomes
+                .and_then(|(idx, outcome)| async move {
+                    match outcome {
+                        Outcome::Failed(e) => Err(e),
+                        outcome => Ok((idx, outcome)),
+                    }
+                })
+                .try_collect()
+                .await
+        } else {
+            outcomes.try_collect().await
+        }
     }
 
     fn create_progress_bar(&self, total: usize) -> ProgressBar {
@@ -370,6 +574,43 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 0);
     }
 
+    #[tokio::test]
+    async fn test_run_planned() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let describe = |item: &i32| PlannedOperation::new(format!("DEV-{item}"), "assign", "ana");
+        let job = |counter: Arc<AtomicUsize>| {
+            move |_item: i32| {
+                let counter = Arc::clone(&counter);
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }
+        };
+
+        let plan = BulkExecutor::new(2, true)
+            .with_progress(false)
+            .run_planned(vec![1, 2], describe, job(Arc::clone(&counter)))
+            .await
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            plan,
+            vec![
+                PlannedOperation::new("DEV-1", "assign", "ana"),
+                PlannedOperation::new("DEV-2", "assign", "ana"),
+            ]
+        );
+
+        let done = BulkExecutor::new(2, false)
+            .with_progress(false)
+            .run_planned(vec![1, 2, 3], describe, job(Arc::clone(&counter)))
+            .await
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(done.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_run_with_error() {
         let executor = BulkExecutor::new(2, false).with_fail_fast(true);
@@ -439,4 +680,78 @@ mod tests {
         assert!(result.is_ok());
         assert!(max_concurrent.load(Ordering::SeqCst) <= 2);
     }
+
+    #[tokio::test]
+    async fn test_cancelled_run_reports_partial_summary() {
+        let cancel = CancellationToken::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+        let job_cancel = cancel.clone();
+
+        // The second item cancels the run; items after it must not start
+        let result = BulkExecutor::new(1, false)
+            .with_progress(false)
+            .with_cancellation(cancel)
+            .run(vec![1, 2, 3, 4], move |item| {
+                let counter = Arc::clone(&counter_clone);
+                let cancel = job_cancel.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    if item == 2 {
+                        cancel.cancel();
+                    }
+                    Ok(())
+                }
+            })
+            .await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        match result.unwrap_err().downcast::<BulkError>().unwrap() {
+            BulkError::Interrupted {
+                completed,
+                failed,
+                not_started,
+                abandoned,
+            } => assert_eq!((completed, failed, not_started, abandoned), (2, 0, 2, 0)),
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_abandons_after_grace_period() {
+        use std::time::Duration;
+
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            trigger.cancel();
+        });
+
+        let result = BulkExecutor::new(2, false)
+            .with_progress(false)
+            .with_cancellation(cancel)
+            .with_grace_period(Duration::from_millis(10))
+            .execute_with_results(vec![1, 2, 3], |item| async move {
+                if item == 1 {
+                    return Ok(item);
+                }
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(item)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.successful, vec![1]);
+        assert!(result.failed.is_empty());
+        assert!(result.not_started.is_empty());
+        let mut abandoned = result.abandoned.clone();
+        abandoned.sort();
+        assert_eq!(abandoned, vec![1, 2]);
+        assert!(!result.is_complete_success());
+        assert_eq!(
+            result.interrupted_error().to_string(),
+            "Interrupted: 1 completed, 0 failed, 0 not started, 2 abandoned mid-request"
+        );
+    }
 }
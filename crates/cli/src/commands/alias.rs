@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_config::Config;
+use atlassian_cli_output::OutputRenderer;
+use clap::Subcommand;
+use serde::Serialize;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AliasCommand {
+    /// List configured aliases
+    List,
+    /// Create or replace an alias
+    Set {
+        /// Alias name used in place of the command
+        name: String,
+        /// Command the alias expands to, e.g. 'jira search -a @me -s "In Progress"'
+        command: String,
+    },
+    /// Remove an alias
+    Remove {
+        /// Alias name
+        name: String,
+    },
+}
+
+#[derive(Serialize)]
+struct AliasRow<'a> {
+    name: &'a str,
+    command: &'a str,
+}
+
+pub fn handle(
+    command: AliasCommand,
+    config: &mut Config,
+    config_path: Option<&Path>,
+    renderer: &OutputRenderer,
+    builtins: &[String],
+) -> Result<()> {
+    match command {
+        AliasCommand::List => {
+            if config.aliases.is_empty() {
+                println!("No aliases configured. Add one with `atlassian-cli alias set <name> <command>`.");
+                return Ok(());
+            }
+            let rows: Vec<AliasRow> = config
+                .aliases
+                .iter()
+                .map(|(name, command)| AliasRow { name, command })
+                .collect();
+            renderer.render(&rows)
+        }
+        AliasCommand::Set { name, command } => {
+            if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+                bail!("Invalid alias name '{name}'");
+            }
+            if builtins.iter().any(|b| b == &name) {
+                bail!("'{name}' is a built-in command and cannot be used as an alias");
+            }
+            if split_words(&command)?.is_empty() {
+                bail!("Alias '{name}' needs a command to expand to");
+            }
+
+            config.aliases.insert(name.clone(), command.clone());
+            config
+                .save(config_path)
+                .context("Unable to persist configuration file")?;
+            tracing::info!(alias = %name, "Alias saved");
+            println!("✅ Alias '{name}' = {command}");
+            Ok(())
+        }
+        AliasCommand::Remove { name } => {
+            if config.aliases.remove(&name).is_none() {
+                bail!("Alias '{name}' does not exist");
+            }
+            config
+                .save(config_path)
+                .context("Unable to persist configuration file")?;
+            tracing::info!(alias = %name, "Alias removed");
+            println!("✅ Removed alias '{name}'");
+            Ok(())
+        }
+    }
+}
+
+/// Replace the first command word with its alias expansion. Global options
+/// before it are kept in place; `value_options` lists the ones that take a
+/// separate value so it is not mistaken for the command. Built-in commands
+/// always win over aliases of the same name.
+pub fn expand(
+    args: Vec<String>,
+    aliases: &BTreeMap<String, String>,
+    builtins: &[String],
+    value_options: &[String],
+) -> Result<Vec<String>> {
+    let mut index = 1;
+    while let Some(arg) = args.get(index) {
+        if arg == "--" {
+            return Ok(args);
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            break;
+        }
+        index += if value_options.contains(arg) { 2 } else { 1 };
+    }
+
+    let Some(command) = args
+        .get(index)
+        .filter(|word| !builtins.contains(word))
+        .and_then(|word| aliases.get(word))
+    else {
+        return Ok(args);
+    };
+
+    let words = split_words(command)
+        .with_context(|| format!("Invalid alias '{}': {command}", args[index]))?;
+    let mut expanded = args[..index].to_vec();
+    expanded.extend(words);
+    expanded.extend_from_slice(&args[index + 1..]);
+    Ok(expanded)
+}
+
+/// Split an alias command into words, honouring single and double quotes and
+/// backslash escapes the way a POSIX shell would.
+pub fn split_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => bail!("Unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => bail!("Unterminated double quote"),
+                        },
+                        Some(c) => current.push(c),
+                        None => bail!("Unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"jira search -a @me -s "In Progress""#).unwrap(),
+            strings(&["jira", "search", "-a", "@me", "-s", "In Progress"])
+        );
+        assert_eq!(
+            split_words(r#"  a 'b c'  d\ e "f \"g\"" '' "#).unwrap(),
+            strings(&["a", "b c", "d e", "f \"g\"", ""])
+        );
+        assert!(split_words("jira search 'open").is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let aliases = BTreeMap::from([
+            (
+                "standup".to_string(),
+                r#"jira search -a @me -s "In Progress""#.to_string(),
+            ),
+            ("jira".to_string(), "confluence search".to_string()),
+        ]);
+        let builtins = strings(&["jira", "confluence"]);
+        let value_options = strings(&["-p", "--profile", "--output"]);
+        let expand_args =
+            |args: &[&str]| expand(strings(args), &aliases, &builtins, &value_options).unwrap();
+
+        assert_eq!(
+            expand_args(&[
+                "atlassian-cli",
+                "-p",
+                "work",
+                "--debug",
+                "standup",
+                "--limit",
+                "5"
+            ]),
+            strings(&[
+                "atlassian-cli",
+                "-p",
+                "work",
+                "--debug",
+                "jira",
+                "search",
+                "-a",
+                "@me",
+                "-s",
+                "In Progress",
+                "--limit",
+                "5"
+            ])
+        );
+        assert_eq!(
+            expand_args(&["atlassian-cli", "--profile", "standup", "jira", "get"]),
+            strings(&["atlassian-cli", "--profile", "standup", "jira", "get"])
+        );
+        assert_eq!(
+            expand_args(&["atlassian-cli", "jira", "standup"]),
+            strings(&["atlassian-cli", "jira", "standup"])
+        );
+    }
+}
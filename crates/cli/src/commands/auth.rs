@@ -1,18 +1,133 @@
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
-use atlassian_cli_auth::token_key;
-use atlassian_cli_config::Config;
+use async_trait::async_trait;
+use atlassian_cli_api::auth::TokenRefresh;
+use atlassian_cli_api::error::ApiError;
+use atlassian_cli_auth::{
+    run_credential_helper, statuspage_token_key, token_key, CredentialStore, VaultKey,
+};
+use atlassian_cli_config::{Config, CredentialHelper, CredentialStoreKind};
 use atlassian_cli_output::OutputRenderer;
 use clap::{Args, Subcommand};
 use serde::Serialize;
 use url::Url;
 
-/// Multi-tier token lookup: env var → credentials file
-fn get_token(profile_name: &str) -> Option<String> {
+/// Build the credential store selected in config. The encrypted store is
+/// unlocked with `ATLASSIAN_CLI_VAULT_PASSPHRASE` or a key file.
+pub fn credential_store(config: &Config) -> Result<CredentialStore> {
+    match config.credential_store {
+        CredentialStoreKind::File => Ok(CredentialStore::File),
+        CredentialStoreKind::Keyring => Ok(CredentialStore::Keyring),
+        CredentialStoreKind::EncryptedFile => {
+            if let Some(passphrase) = std::env::var("ATLASSIAN_CLI_VAULT_PASSPHRASE")
+                .ok()
+                .filter(|p| !p.is_empty())
+            {
+                return Ok(CredentialStore::EncryptedFile(VaultKey::Passphrase(
+                    passphrase,
+                )));
+            }
+
+            let key_file = std::env::var_os("ATLASSIAN_CLI_VAULT_KEY_FILE")
+                .map(Into::into)
+                .or_else(|| config.vault_key_file.clone())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "The encrypted credential store is locked. Set ATLASSIAN_CLI_VAULT_PASSPHRASE, ATLASSIAN_CLI_VAULT_KEY_FILE, or vault_key_file in config"
+                    )
+                })?;
+            Ok(CredentialStore::EncryptedFile(VaultKey::KeyFile(key_file)))
+        }
+    }
+}
+
+/// Describe the configured credential store for diagnostics.
+pub fn credential_store_label(config: &Config) -> &'static str {
+    match config.credential_store {
+        CredentialStoreKind::File => "credential store (file)",
+        CredentialStoreKind::EncryptedFile => "credential store (encrypted-file)",
+        CredentialStoreKind::Keyring => "credential store (keyring)",
+    }
+}
+
+/// Look up a profile's token in the configured credential store.
+pub fn stored_token(config: &Config, profile_name: &str) -> Option<String> {
+    stored_secret(config, profile_name, &token_key(profile_name))
+}
+
+/// Look up a profile's Statuspage API key in the configured credential store.
+pub fn stored_statuspage_token(config: &Config, profile_name: &str) -> Option<String> {
+    stored_secret(config, profile_name, &statuspage_token_key(profile_name))
+}
+
+fn stored_secret(config: &Config, profile_name: &str, key: &str) -> Option<String> {
+    let lookup = credential_store(config).and_then(|store| store.get(key));
+    match lookup {
+        Ok(token) => token,
+        Err(err) => {
+            tracing::warn!(
+                profile = profile_name,
+                "Unable to read stored token: {err:#}"
+            );
+            None
+        }
+    }
+}
+
+/// Run the profile's credential helper, returning the token and a label for
+/// diagnostics. `None` when the profile has no helper configured.
+pub fn helper_token(config: &Config, profile_name: &str) -> Result<Option<(String, String)>> {
+    let Some(helper) = config.resolved_profile(profile_name)?.credential_helper else {
+        return Ok(None);
+    };
+
+    let token = match &helper {
+        CredentialHelper::Exec(command) => run_credential_helper(command),
+    }
+    .with_context(|| format!("Failed to get a token for profile '{profile_name}'"))?;
+    Ok(Some((token, helper.label())))
+}
+
+/// Re-runs a profile's credential helper when the server rejects its token,
+/// for helpers that hand out short-lived tokens.
+struct HelperRefresh {
+    command: Vec<String>,
+}
+
+#[async_trait]
+impl TokenRefresh for HelperRefresh {
+    async fn refresh(&self) -> atlassian_cli_api::error::Result<String> {
+        let command = self.command.clone();
+        tokio::task::spawn_blocking(move || run_credential_helper(&command))
+            .await
+            .map_err(|e| anyhow!(e))
+            .and_then(|token| token)
+            .map_err(|e| ApiError::AuthenticationFailed {
+                message: format!("Credential helper could not refresh the token: {e:#}"),
+            })
+    }
+}
+
+/// Token refresh hook for a profile whose token comes from a credential helper.
+pub fn helper_refresh(config: &Config, profile_name: &str) -> Option<Arc<dyn TokenRefresh>> {
+    let helper = config
+        .resolved_profile(profile_name)
+        .ok()?
+        .credential_helper?;
+    match &helper {
+        CredentialHelper::Exec(command) => Some(Arc::new(HelperRefresh {
+            command: command.clone(),
+        })),
+    }
+}
+
+/// Multi-tier token lookup: env var → credential helper → credential store
+fn get_token(config: &Config, profile_name: &str) -> Result<Option<String>> {
     // 1. Check profile-specific env var: ATLASSIAN_CLI_TOKEN_{PROFILE}
     let profile_env_var = format!("ATLASSIAN_CLI_TOKEN_{}", profile_name.to_uppercase());
-    std::env::var(&profile_env_var)
+    let env_token = std::env::var(&profile_env_var)
         .ok()
         .filter(|t| !t.trim().is_empty())
         .or_else(|| {
@@ -20,18 +135,26 @@ fn get_token(profile_name: &str) -> Option<String> {
             std::env::var("ATLASSIAN_API_TOKEN")
                 .ok()
                 .filter(|t| !t.trim().is_empty())
-        })
-        .or_else(|| {
-            // 3. Try credentials file
-            let secret_key = token_key(profile_name);
-            atlassian_cli_auth::get_secret(&secret_key).ok().flatten()
-        })
+        });
+    if env_token.is_some() {
+        return Ok(env_token);
+    }
+
+    // 3. Run the profile's credential helper
+    if let Some((token, _)) = helper_token(config, profile_name)? {
+        return Ok(Some(token));
+    }
+
+    // 4. Try the credential store
+    Ok(stored_token(config, profile_name))
 }
 
 #[derive(Subcommand, Debug, Clone)]
 pub enum AuthCommand {
     /// Add or update a profile and store credentials securely
     Login(LoginArgs),
+    /// Store a Statuspage API key and page ID for a profile
+    Statuspage(StatuspageLoginArgs),
     /// Remove stored credentials (and optionally the profile)
     Logout(LogoutArgs),
     /// List configured profiles
@@ -75,6 +198,19 @@ pub struct LoginArgs {
     pub default: bool,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct StatuspageLoginArgs {
+    /// Existing profile to attach the Statuspage credentials to.
+    #[arg(long)]
+    pub profile: String,
+    /// Statuspage page ID (shown in the page's API settings).
+    #[arg(long)]
+    pub page_id: String,
+    /// Statuspage API key (falls back to STATUSPAGE_API_KEY env or interactive prompt).
+    #[arg(long, env = "STATUSPAGE_API_KEY")]
+    pub token: Option<String>,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct LogoutArgs {
     /// Profile to remove credentials for.
@@ -93,6 +229,7 @@ pub async fn handle(
 ) -> Result<()> {
     match command {
         AuthCommand::Login(args) => login(args, config, config_path),
+        AuthCommand::Statuspage(args) => statuspage_login(args, config, config_path),
         AuthCommand::Logout(args) => logout(args, config, config_path),
         AuthCommand::List => list_profiles(config, renderer),
         AuthCommand::Whoami(args) => whoami(args, config).await,
@@ -126,8 +263,9 @@ fn login(args: LoginArgs, config: &mut Config, config_path: Option<&Path>) -> Re
     }
 
     let secret_key = token_key(&args.profile);
-    atlassian_cli_auth::set_secret(&secret_key, &token)
-        .context("Failed to store token in credentials file")?;
+    credential_store(config)?
+        .set(&secret_key, &token)
+        .context("Failed to store token in credential store")?;
 
     config
         .save(config_path)
@@ -141,15 +279,61 @@ fn login(args: LoginArgs, config: &mut Config, config_path: Option<&Path>) -> Re
     Ok(())
 }
 
+fn statuspage_login(
+    args: StatuspageLoginArgs,
+    config: &mut Config,
+    config_path: Option<&Path>,
+) -> Result<()> {
+    if !config.profiles.contains_key(&args.profile) {
+        return Err(anyhow!(
+            "Profile '{}' does not exist. Run `atlassian-cli auth login` first.",
+            args.profile
+        ));
+    }
+    if args.page_id.trim().is_empty() {
+        return Err(anyhow!("Statuspage page ID cannot be empty"));
+    }
+
+    let token = match args.token {
+        Some(token) if !token.trim().is_empty() => token.trim().to_owned(),
+        _ => read_secret(
+            "You can create an API key under your Statuspage user's API info page",
+            "Enter Statuspage API key: ",
+        )
+        .context("Failed to read Statuspage API key from prompt")?,
+    };
+    if token.is_empty() {
+        return Err(anyhow!("Statuspage API key cannot be empty"));
+    }
+
+    credential_store(config)?
+        .set(&statuspage_token_key(&args.profile), &token)
+        .context("Failed to store Statuspage API key in credential store")?;
+
+    if let Some(profile) = config.profiles.get_mut(&args.profile) {
+        profile.statuspage_page_id = Some(args.page_id.trim().to_string());
+    }
+    config
+        .save(config_path)
+        .context("Unable to persist configuration file")?;
+
+    tracing::info!(profile = %args.profile, "Statuspage credentials stored");
+    Ok(())
+}
+
 fn logout(args: LogoutArgs, config: &mut Config, config_path: Option<&Path>) -> Result<()> {
     let _profile = config
         .profiles
         .get(&args.profile)
         .ok_or_else(|| anyhow!("Profile '{}' does not exist", args.profile))?;
 
-    let secret_key = token_key(&args.profile);
-    if let Err(e) = atlassian_cli_auth::delete_secret(&secret_key) {
-        tracing::warn!("Failed to delete token from credentials file: {e}");
+    for secret_key in [
+        token_key(&args.profile),
+        statuspage_token_key(&args.profile),
+    ] {
+        if let Err(e) = credential_store(config).and_then(|store| store.delete(&secret_key)) {
+            tracing::warn!("Failed to delete token from credential store: {e:#}");
+        }
     }
 
     if args.remove_profile {
@@ -181,10 +365,25 @@ fn list_profiles(config: &Config, renderer: &OutputRenderer) -> Result<()> {
         is_default: bool,
     }
 
+    // Inherited values are shown; a broken `extends` chain lists the profile as written
+    let profiles: Vec<_> = config
+        .profiles
+        .iter()
+        .map(|(name, own)| {
+            let profile = config
+                .resolved_profile(name)
+                .unwrap_or_else(|_| own.clone());
+            (name, profile)
+        })
+        .collect();
+
     let mut rows = Vec::new();
-    for (name, profile) in &config.profiles {
+    for (name, profile) in &profiles {
+        let name = name.as_str();
         let base_url = profile.base_url.as_deref().unwrap_or("");
-        let has_token = get_token(name).is_some();
+        // Profiles with a credential helper are not run just to list them
+        let has_token =
+            profile.credential_helper.is_some() || get_token(config, name).ok().flatten().is_some();
         let row = Row {
             name,
             base_url,
@@ -207,12 +406,17 @@ fn list_profiles(config: &Config, renderer: &OutputRenderer) -> Result<()> {
 }
 
 fn read_token_from_stdin() -> Result<String> {
+    read_secret(
+        "You can get the API token from: https://id.atlassian.com/manage-profile/security/api-tokens",
+        "Enter API token: ",
+    )
+}
+
+fn read_secret(hint: &str, prompt: &str) -> Result<String> {
     use std::io::{self, Write};
 
-    println!(
-        "You can get the API token from: https://id.atlassian.com/manage-profile/security/api-tokens"
-    );
-    print!("Enter API token: ");
+    println!("{hint}");
+    print!("{prompt}");
     io::stdout().flush().context("Failed to flush stdout")?;
 
     let token = rpassword::read_password().context("Failed to read token")?;
@@ -221,7 +425,7 @@ fn read_token_from_stdin() -> Result<String> {
 
 async fn whoami(args: WhoamiArgs, config: &Config) -> Result<()> {
     let (profile_name, profile) = config
-        .resolve_profile(args.profile.as_deref())
+        .resolve_profile(args.profile.as_deref())?
         .context("No profile found. Use `atlassian-cli auth login` to create one.")?;
 
     let base_url = profile
@@ -230,7 +434,7 @@ async fn whoami(args: WhoamiArgs, config: &Config) -> Result<()> {
         .context("Profile missing base_url")?;
     let email = profile.email.as_deref().context("Profile missing email")?;
 
-    let token = get_token(profile_name).ok_or_else(|| {
+    let token = get_token(config, profile_name)?.ok_or_else(|| {
         anyhow!(
             "No token found for profile '{profile_name}'. Set ATLASSIAN_CLI_TOKEN_{} env var or run `atlassian-cli auth login`",
             profile_name.to_uppercase()
@@ -264,7 +468,7 @@ async fn whoami(args: WhoamiArgs, config: &Config) -> Result<()> {
 
 async fn test_auth(args: TestArgs, config: &Config) -> Result<()> {
     let (profile_name, profile) = config
-        .resolve_profile(args.profile.as_deref())
+        .resolve_profile(args.profile.as_deref())?
         .context("No profile found. Use `atlassian-cli auth login` to create one.")?;
 
     let base_url = profile
@@ -273,7 +477,7 @@ async fn test_auth(args: TestArgs, config: &Config) -> Result<()> {
         .context("Profile missing base_url")?;
     let email = profile.email.as_deref().context("Profile missing email")?;
 
-    let token = get_token(profile_name).ok_or_else(|| {
+    let token = get_token(config, profile_name)?.ok_or_else(|| {
         anyhow!(
             "No token found for profile '{profile_name}'. Set ATLASSIAN_CLI_TOKEN_{} env var or run `atlassian-cli auth login`",
             profile_name.to_uppercase()
@@ -0,0 +1,389 @@
+use anyhow::{bail, Context, Result};
+use atlassian_cli_bulk::PlannedOperation;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::BitbucketContext;
+use crate::commands::dry_run::render_plan;
+
+// Archive a single repository: Bitbucket Cloud has no archived state, so the
+// convention is a push restriction nobody is exempt from, a marked description
+// (and optionally name), issues and wiki switched off, and a tombstone README.
+
+const DESCRIPTION_MARKER: &str = "[ARCHIVED] ";
+const NAME_PREFIX: &str = "archived-";
+
+#[derive(Deserialize)]
+struct Repo {
+    slug: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    mainbranch: Option<BranchRef>,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RestrictionList {
+    values: Vec<Restriction>,
+}
+
+#[derive(Deserialize)]
+struct Restriction {
+    id: i64,
+    kind: String,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    users: Vec<Value>,
+    #[serde(default)]
+    groups: Vec<Value>,
+}
+
+#[derive(Serialize)]
+struct ArchiveRow {
+    slug: String,
+    name: String,
+    description: String,
+    read_only: bool,
+}
+
+pub async fn archive_repo(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+    tombstone: Option<&str>,
+    rename: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let repo = fetch_repo(ctx, workspace, slug).await?;
+    let restricted = archive_restriction(ctx, workspace, slug).await?.is_some();
+    let name = repo.name.clone().unwrap_or_else(|| slug.to_string());
+    let description = archived_description(repo.description.as_deref());
+    let new_name = if rename { archived_name(&name) } else { name };
+
+    if dry_run {
+        let mut plan = Vec::new();
+        if let Some(message) = tombstone {
+            plan.push(PlannedOperation::new(
+                slug,
+                "commit README.md",
+                format!("{}: {message}", main_branch(&repo)?),
+            ));
+        }
+        if !restricted {
+            plan.push(PlannedOperation::new(slug, "restrict push", "*"));
+        }
+        plan.push(PlannedOperation::new(slug, "set description", &description));
+        plan.push(PlannedOperation::new(slug, "disable", "issues, wiki"));
+        if rename {
+            plan.push(PlannedOperation::new(slug, "rename", &new_name));
+        }
+        return render_plan(ctx.renderer, &plan);
+    }
+
+    // The README goes in first; once pushes are restricted it can't be committed
+    if let Some(message) = tombstone {
+        let branch = main_branch(&repo)?;
+        commit_readme(
+            ctx,
+            workspace,
+            slug,
+            branch,
+            &tombstone_readme(slug, message),
+        )
+        .await?;
+    }
+
+    if !restricted {
+        let path = format!("/2.0/repositories/{workspace}/{slug}/branch-restrictions");
+        let _: Value = ctx
+            .client
+            .post(
+                &path,
+                &json!({
+                    "kind": "push",
+                    "branch_match_kind": "glob",
+                    "pattern": "*",
+                    "users": [],
+                    "groups": []
+                }),
+            )
+            .await
+            .with_context(|| format!("Failed to restrict pushes to {workspace}/{slug}"))?;
+    }
+
+    let mut payload = json!({
+        "description": description,
+        "has_issues": false,
+        "has_wiki": false
+    });
+    if rename {
+        payload["name"] = json!(new_name);
+    }
+    let updated = update_repo(ctx, workspace, slug, &payload).await?;
+
+    tracing::info!(
+        slug = updated.slug.as_str(),
+        workspace,
+        "Repository archived"
+    );
+    println!("✓ Repository {workspace}/{slug} archived");
+    ctx.renderer.render(&ArchiveRow {
+        slug: updated.slug,
+        name: updated.name.unwrap_or_default(),
+        description: updated.description.unwrap_or_default(),
+        read_only: true,
+    })
+}
+
+/// Undo `archive_repo`'s restriction, name and description markers. Issues,
+/// wiki and any tombstone README are left for the owner to restore.
+pub async fn unarchive_repo(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let repo = fetch_repo(ctx, workspace, slug).await?;
+    let restriction = archive_restriction(ctx, workspace, slug).await?;
+    let name = repo.name.clone().unwrap_or_else(|| slug.to_string());
+    let description = repo.description.clone().unwrap_or_default();
+    let new_name = restored_name(&name);
+    let new_description = restored_description(&description);
+
+    if restriction.is_none() && new_name == name && new_description == description {
+        bail!("Repository {workspace}/{slug} is not archived");
+    }
+
+    if dry_run {
+        let mut plan = Vec::new();
+        if let Some(id) = restriction {
+            plan.push(PlannedOperation::new(
+                slug,
+                "remove push restriction",
+                id.to_string(),
+            ));
+        }
+        if new_description != description {
+            plan.push(PlannedOperation::new(
+                slug,
+                "set description",
+                new_description,
+            ));
+        }
+        if new_name != name {
+            plan.push(PlannedOperation::new(slug, "rename", &new_name));
+        }
+        return render_plan(ctx.renderer, &plan);
+    }
+
+    if let Some(id) = restriction {
+        let path = format!("/2.0/repositories/{workspace}/{slug}/branch-restrictions/{id}");
+        let _: Value =
+            ctx.client.delete(&path).await.with_context(|| {
+                format!("Failed to lift push restriction on {workspace}/{slug}")
+            })?;
+    }
+
+    let mut payload = json!({ "description": new_description });
+    if new_name != name {
+        payload["name"] = json!(new_name);
+    }
+    let updated = update_repo(ctx, workspace, slug, &payload).await?;
+
+    tracing::info!(
+        slug = updated.slug.as_str(),
+        workspace,
+        "Repository unarchived"
+    );
+    println!("✓ Repository {workspace}/{slug} unarchived");
+    ctx.renderer.render(&ArchiveRow {
+        slug: updated.slug,
+        name: updated.name.unwrap_or_default(),
+        description: updated.description.unwrap_or_default(),
+        read_only: false,
+    })
+}
+
+async fn fetch_repo(ctx: &BitbucketContext<'_>, workspace: &str, slug: &str) -> Result<Repo> {
+    ctx.client
+        .get(&format!("/2.0/repositories/{workspace}/{slug}"))
+        .await
+        .with_context(|| format!("Failed to fetch repository {workspace}/{slug}"))
+}
+
+async fn update_repo(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+    payload: &Value,
+) -> Result<Repo> {
+    ctx.client
+        .put(&format!("/2.0/repositories/{workspace}/{slug}"), payload)
+        .await
+        .with_context(|| format!("Failed to update repository {workspace}/{slug}"))
+}
+
+/// ID of the push restriction on every branch with nobody exempt, if present.
+async fn archive_restriction(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+) -> Result<Option<i64>> {
+    let path =
+        format!("/2.0/repositories/{workspace}/{slug}/branch-restrictions?kind=push&pagelen=100");
+    let response: RestrictionList =
+        ctx.client.get(&path).await.with_context(|| {
+            format!("Failed to list branch restrictions for {workspace}/{slug}")
+        })?;
+
+    Ok(response
+        .values
+        .iter()
+        .find(|r| is_archive_restriction(r))
+        .map(|r| r.id))
+}
+
+fn is_archive_restriction(restriction: &Restriction) -> bool {
+    restriction.kind == "push"
+        && restriction.pattern.as_deref() == Some("*")
+        && restriction.users.is_empty()
+        && restriction.groups.is_empty()
+}
+
+fn main_branch(repo: &Repo) -> Result<&str> {
+    match &repo.mainbranch {
+        Some(branch) => Ok(&branch.name),
+        None => bail!(
+            "Repository {} has no main branch to commit a tombstone README to",
+            repo.slug
+        ),
+    }
+}
+
+/// Commit a single file through the `src` endpoint, which takes form data.
+async fn commit_readme(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+    branch: &str,
+    content: &str,
+) -> Result<()> {
+    let form = reqwest::multipart::Form::new()
+        .text("README.md", content.to_string())
+        .text("message", "Archive repository: add tombstone README")
+        .text("branch", branch.to_string());
+
+    // Note: This uses the raw reqwest client for the multipart commit
+    let path = format!("/2.0/repositories/{workspace}/{slug}/src");
+    if ctx.client.explained(reqwest::Method::POST, &path) {
+        return Ok(());
+    }
+    let url = format!("{}{path}", ctx.client.base_url().trim_end_matches('/'));
+    let request = ctx
+        .client
+        .apply_auth(reqwest::Client::new().post(url).multipart(form));
+
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to commit tombstone README to {workspace}/{slug}"))?;
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        bail!("Failed to commit tombstone README: {error_text}");
+    }
+
+    tracing::info!(workspace, slug, branch, "Tombstone README committed");
+    Ok(())
+}
+
+fn tombstone_readme(slug: &str, message: &str) -> String {
+    format!(
+        "# {slug} (archived)\n\n> **This repository is archived and read-only.**\n\n{message}\n"
+    )
+}
+
+/// The tombstone message goes in the README only, so `unarchive` can restore
+/// the original description by dropping the marker.
+fn archived_description(current: Option<&str>) -> String {
+    let text = restored_description(current.unwrap_or_default());
+    format!("{DESCRIPTION_MARKER}{}", text.trim())
+        .trim_end()
+        .to_string()
+}
+
+fn restored_description(description: &str) -> &str {
+    description
+        .strip_prefix(DESCRIPTION_MARKER.trim_end())
+        .map_or(description, str::trim_start)
+}
+
+fn archived_name(name: &str) -> String {
+    if name.starts_with(NAME_PREFIX) {
+        name.to_string()
+    } else {
+        format!("{NAME_PREFIX}{name}")
+    }
+}
+
+fn restored_name(name: &str) -> String {
+    name.strip_prefix(NAME_PREFIX).unwrap_or(name).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_markers() {
+        assert_eq!(
+            archived_description(Some("Payments API")),
+            "[ARCHIVED] Payments API"
+        );
+        assert_eq!(archived_description(None), "[ARCHIVED]");
+        assert_eq!(
+            archived_description(Some("[ARCHIVED] Payments API")),
+            "[ARCHIVED] Payments API"
+        );
+        assert_eq!(
+            restored_description("[ARCHIVED] Payments API"),
+            "Payments API"
+        );
+        assert_eq!(restored_description("[ARCHIVED]"), "");
+        assert_eq!(restored_description("Payments API"), "Payments API");
+        assert_eq!(
+            restored_description(&archived_description(Some("Payments API"))),
+            "Payments API"
+        );
+
+        assert_eq!(archived_name("payments"), "archived-payments");
+        assert_eq!(archived_name("archived-payments"), "archived-payments");
+        assert_eq!(restored_name("archived-payments"), "payments");
+    }
+
+    #[test]
+    fn test_archive_restriction() {
+        let restrictions: RestrictionList = serde_json::from_value(json!({
+            "values": [
+                { "id": 1, "kind": "push", "pattern": "main", "users": [], "groups": [] },
+                { "id": 2, "kind": "push", "pattern": "*", "users": [{ "uuid": "{u}" }], "groups": [] },
+                { "id": 3, "kind": "push", "pattern": "*" }
+            ]
+        }))
+        .unwrap();
+        let found: Vec<i64> = restrictions
+            .values
+            .iter()
+            .filter(|r| is_archive_restriction(r))
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(found, vec![3]);
+    }
+}
@@ -0,0 +1,294 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::utils::{fetch_paged, strip_api_host, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
+
+// ============================================================================
+// API Response Structs
+// ============================================================================
+
+#[derive(Deserialize)]
+struct CacheList {
+    values: Vec<PipelineCache>,
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PipelineCache {
+    uuid: String,
+    name: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    file_size_bytes: Option<u64>,
+    #[serde(default)]
+    created_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Download {
+    name: String,
+    #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
+    created_on: Option<String>,
+    #[serde(default)]
+    downloads: Option<u64>,
+}
+
+// ============================================================================
+// Output Structs
+// ============================================================================
+
+#[derive(Serialize)]
+struct CacheRow {
+    uuid: String,
+    name: String,
+    path: String,
+    size: String,
+    created: String,
+}
+
+#[derive(Serialize)]
+struct ArtifactRow {
+    name: String,
+    size: String,
+    downloads: u64,
+    created: String,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn format_size(bytes: Option<u64>) -> String {
+    let Some(bytes) = bytes else {
+        return String::new();
+    };
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+async fn fetch_caches(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+) -> Result<Vec<PipelineCache>> {
+    let mut caches = Vec::new();
+    let mut path = format!("/2.0/repositories/{workspace}/{repo_slug}/pipelines-config/caches");
+
+    loop {
+        let response: CacheList = ctx.client.get(&path).await.with_context(|| {
+            format!("Failed to list pipeline caches for {workspace}/{repo_slug}")
+        })?;
+        caches.extend(response.values);
+        match response.next {
+            Some(next) => path = strip_api_host(&next),
+            None => break,
+        }
+    }
+
+    Ok(caches)
+}
+
+// ============================================================================
+// Caches
+// ============================================================================
+
+pub async fn list_caches(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+) -> Result<()> {
+    let caches = fetch_caches(ctx, workspace, repo_slug).await?;
+
+    if caches.is_empty() {
+        tracing::info!(workspace, repo_slug, "No pipeline caches found");
+        return Ok(());
+    }
+
+    let rows: Vec<CacheRow> = caches
+        .into_iter()
+        .map(|cache| CacheRow {
+            uuid: cache.uuid,
+            name: cache.name,
+            path: cache.path.unwrap_or_default(),
+            size: format_size(cache.file_size_bytes),
+            created: cache.created_on.unwrap_or_default(),
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+pub async fn delete_cache(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    cache_uuid: &str,
+) -> Result<()> {
+    let path =
+        format!("/2.0/repositories/{workspace}/{repo_slug}/pipelines-config/caches/{cache_uuid}");
+    let _: Value = ctx.client.delete(&path).await.with_context(|| {
+        format!("Failed to delete pipeline cache {cache_uuid} on {workspace}/{repo_slug}")
+    })?;
+
+    tracing::info!(cache_uuid, workspace, repo_slug, "Pipeline cache deleted");
+    println!("✓ Deleted pipeline cache {cache_uuid} on {workspace}/{repo_slug}");
+    Ok(())
+}
+
+/// Delete every cache in the repository, or only caches with the given name.
+pub async fn clear_caches(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    name: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let caches: Vec<PipelineCache> = fetch_caches(ctx, workspace, repo_slug)
+        .await?
+        .into_iter()
+        .filter(|cache| name.map(|n| cache.name == n).unwrap_or(true))
+        .collect();
+
+    if caches.is_empty() {
+        tracing::info!(workspace, repo_slug, "No matching pipeline caches to clear");
+        return Ok(());
+    }
+
+    for cache in &caches {
+        if dry_run {
+            println!("Would delete cache {} ({})", cache.name, cache.uuid);
+            continue;
+        }
+        delete_cache(ctx, workspace, repo_slug, &cache.uuid).await?;
+    }
+
+    if !dry_run {
+        println!(
+            "✓ Cleared {} pipeline cache(s) on {workspace}/{repo_slug}",
+            caches.len()
+        );
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Artifacts
+// ============================================================================
+
+/// List artifacts published to the repository Downloads area (where pipeline
+/// steps upload build outputs).
+pub async fn list_artifacts(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    page: &PaginationArgs,
+) -> Result<()> {
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/downloads");
+    let what = format!("artifacts for {workspace}/{repo_slug}");
+    let artifacts: Vec<Download> = fetch_paged(ctx, &path, page, Paging::Offset, &what).await?;
+
+    if artifacts.is_empty() {
+        tracing::info!(workspace, repo_slug, "No artifacts found");
+        return Ok(());
+    }
+
+    let rows: Vec<ArtifactRow> = artifacts
+        .into_iter()
+        .map(|artifact| ArtifactRow {
+            name: artifact.name,
+            size: format_size(artifact.size),
+            downloads: artifact.downloads.unwrap_or(0),
+            created: artifact.created_on.unwrap_or_default(),
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+pub async fn download_artifact(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    name: &str,
+    output: Option<&Path>,
+) -> Result<()> {
+    let output = output.unwrap_or_else(|| Path::new(name));
+
+    // Downloads redirect to object storage, so this uses the raw reqwest client
+    let url = format!(
+        "{}/2.0/repositories/{workspace}/{repo_slug}/downloads/{}",
+        ctx.client.base_url().trim_end_matches('/'),
+        urlencoding::encode(name)
+    );
+    let request = ctx.client.apply_auth(reqwest::Client::new().get(url));
+
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to download artifact {name}"))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to download artifact {name}: HTTP {}",
+            response.status()
+        ));
+    }
+
+    let content = response
+        .bytes()
+        .await
+        .context("Failed to read artifact content")?;
+
+    std::fs::write(output, &content)
+        .with_context(|| format!("Failed to write file: {}", output.display()))?;
+
+    tracing::info!(name, file = %output.display(), "Artifact downloaded");
+    println!(
+        "✓ Downloaded {name} ({}) to {}",
+        format_size(Some(content.len() as u64)),
+        output.display()
+    );
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(None), "");
+        assert_eq!(format_size(Some(512)), "512 B");
+        assert_eq!(format_size(Some(2048)), "2.0 KB");
+        assert_eq!(format_size(Some(5 * 1024 * 1024)), "5.0 MB");
+    }
+
+    #[test]
+    fn test_strip_api_host() {
+        assert_eq!(
+            strip_api_host("https://api.bitbucket.org/2.0/repositories/ws/r/downloads?page=2"),
+            "/2.0/repositories/ws/r/downloads?page=2"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use url::form_urlencoded;
+
+use super::utils::BitbucketContext;
+
+// One-screen release check for a branch: head commit, latest pipeline,
+// commit build statuses (Pipelines, Bamboo, other CI) and open pull requests
+
+#[derive(Deserialize)]
+struct Branch {
+    #[serde(default)]
+    target: Option<Commit>,
+}
+
+#[derive(Deserialize)]
+struct Commit {
+    hash: String,
+    #[serde(default)]
+    date: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    author: Option<Author>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    #[serde(default)]
+    raw: Option<String>,
+    #[serde(default)]
+    user: Option<User>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+    #[serde(default)]
+    size: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Pipeline {
+    #[serde(default)]
+    build_number: Option<i64>,
+    #[serde(default)]
+    state: Option<PipelineState>,
+    #[serde(default)]
+    created_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PipelineState {
+    name: String,
+    #[serde(default)]
+    result: Option<Named>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct BuildStatus {
+    state: String,
+}
+
+#[derive(Serialize)]
+struct BranchStatus {
+    branch: String,
+    status: &'static str,
+    commit: String,
+    commit_date: String,
+    author: String,
+    message: String,
+    pipeline: String,
+    pipeline_state: String,
+    pipeline_created: String,
+    build_statuses: String,
+    open_prs: usize,
+}
+
+pub async fn branch_status(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    branch_name: &str,
+) -> Result<()> {
+    let base = format!("/2.0/repositories/{workspace}/{repo_slug}");
+
+    let branch: Branch = ctx
+        .client
+        .get(&format!("{base}/refs/branches/{branch_name}"))
+        .await
+        .with_context(|| {
+            format!("Failed to fetch branch {branch_name} from {workspace}/{repo_slug}")
+        })?;
+    let commit = branch
+        .target
+        .with_context(|| format!("Branch {branch_name} has no head commit"))?;
+
+    let pipelines_path = format!(
+        "{base}/pipelines?{}",
+        form_urlencoded::Serializer::new(String::new())
+            .append_pair("sort", "-created_on")
+            .append_pair("pagelen", "1")
+            .append_pair("q", &format!("target.ref_name=\"{branch_name}\""))
+            .finish()
+    );
+    let prs_path = format!(
+        "{base}/pullrequests?{}",
+        form_urlencoded::Serializer::new(String::new())
+            .append_pair("state", "OPEN")
+            .append_pair("pagelen", "50")
+            .append_pair("q", &format!("destination.branch.name=\"{branch_name}\""))
+            .finish()
+    );
+    let statuses_path = format!("{base}/commit/{}/statuses?pagelen=100", commit.hash);
+
+    let (pipelines, prs, statuses) = futures::try_join!(
+        async {
+            ctx.client
+                .get::<Page<Pipeline>>(&pipelines_path)
+                .await
+                .with_context(|| format!("Failed to list pipelines for {branch_name}"))
+        },
+        async {
+            ctx.client
+                .get::<Page<serde_json::Value>>(&prs_path)
+                .await
+                .with_context(|| format!("Failed to list pull requests into {branch_name}"))
+        },
+        async {
+            ctx.client
+                .get::<Page<BuildStatus>>(&statuses_path)
+                .await
+                .with_context(|| format!("Failed to list build statuses for {}", commit.hash))
+        },
+    )?;
+
+    let pipeline = pipelines.values.into_iter().next();
+    let pipeline_state = pipeline
+        .as_ref()
+        .and_then(|p| p.state.as_ref())
+        .map(|s| s.result.as_ref().map_or(&s.name, |r| &r.name).clone())
+        .unwrap_or_default();
+    let build_states: Vec<&str> = statuses.values.iter().map(|s| s.state.as_str()).collect();
+
+    let view = BranchStatus {
+        branch: branch_name.to_string(),
+        status: overall_status(&pipeline_state, &build_states),
+        commit: commit.hash.get(..12).unwrap_or(&commit.hash).to_string(),
+        commit_date: commit.date.unwrap_or_default(),
+        author: commit
+            .author
+            .map(|a| a.user.map(|u| u.display_name).or(a.raw).unwrap_or_default())
+            .unwrap_or_default(),
+        message: commit
+            .message
+            .as_deref()
+            .and_then(|m| m.lines().next())
+            .unwrap_or("")
+            .to_string(),
+        pipeline: pipeline
+            .as_ref()
+            .and_then(|p| p.build_number)
+            .map(|n| format!("#{n}"))
+            .unwrap_or_default(),
+        pipeline_created: pipeline.and_then(|p| p.created_on).unwrap_or_default(),
+        pipeline_state,
+        build_statuses: count_states(&build_states),
+        open_prs: prs.size.unwrap_or(prs.values.len()),
+    };
+
+    tracing::debug!(
+        workspace,
+        repo_slug,
+        branch_name,
+        status = view.status,
+        "Branch status"
+    );
+    ctx.renderer.render(&view)
+}
+
+/// Roll the latest pipeline and every commit build status into one badge:
+/// any failure wins, then anything still running, then success.
+fn overall_status(pipeline_state: &str, build_states: &[&str]) -> &'static str {
+    let states: Vec<&str> = std::iter::once(pipeline_state)
+        .filter(|s| !s.is_empty())
+        .chain(build_states.iter().copied())
+        .collect();
+
+    if states
+        .iter()
+        .any(|s| matches!(*s, "FAILED" | "ERROR" | "STOPPED" | "EXPIRED"))
+    {
+        "failing"
+    } else if states
+        .iter()
+        .any(|s| matches!(*s, "INPROGRESS" | "IN_PROGRESS" | "PENDING" | "RUNNING"))
+    {
+        "running"
+    } else if states.contains(&"SUCCESSFUL") {
+        "passing"
+    } else {
+        "no builds"
+    }
+}
+
+/// e.g. "1 FAILED, 2 SUCCESSFUL" (states in alphabetical order).
+fn count_states(states: &[&str]) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for state in states {
+        *counts.entry(state).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(state, n)| format!("{n} {state}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overall_status() {
+        assert_eq!(overall_status("SUCCESSFUL", &["SUCCESSFUL"]), "passing");
+        assert_eq!(overall_status("SUCCESSFUL", &["INPROGRESS"]), "running");
+        assert_eq!(overall_status("IN_PROGRESS", &["FAILED"]), "failing");
+        assert_eq!(overall_status("", &["SUCCESSFUL"]), "passing");
+        assert_eq!(overall_status("", &[]), "no builds");
+    }
+
+    #[test]
+    fn test_count_states() {
+        assert_eq!(
+            count_states(&["SUCCESSFUL", "FAILED", "SUCCESSFUL"]),
+            "1 FAILED, 2 SUCCESSFUL"
+        );
+        assert_eq!(count_states(&[]), "");
+    }
+}
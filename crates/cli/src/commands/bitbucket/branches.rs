@@ -1,13 +1,8 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
-
-#[derive(Deserialize)]
-struct BranchList {
-    values: Vec<Branch>,
-}
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
 
 #[derive(Deserialize)]
 struct Branch {
@@ -48,18 +43,11 @@ pub async fn list_branches(
     ctx: &BitbucketContext<'_>,
     workspace: &str,
     repo_slug: &str,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    let query = form_urlencoded::Serializer::new(String::new())
-        .append_pair("pagelen", &limit.min(100).to_string())
-        .finish();
-    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/refs/branches?{query}");
-
-    let response: BranchList = ctx
-        .client
-        .get(&path)
-        .await
-        .with_context(|| format!("Failed to list branches for {workspace}/{repo_slug}"))?;
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/refs/branches");
+    let what = format!("branches for {workspace}/{repo_slug}");
+    let branches: Vec<Branch> = fetch_paged(ctx, &path, page, Paging::Offset, &what).await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -69,8 +57,7 @@ pub async fn list_branches(
         message: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = branches
         .iter()
         .map(|branch| Row {
             name: branch.name.as_str(),
@@ -0,0 +1,256 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::BitbucketContext;
+
+// Branching model settings: development/production branches and branch type prefixes
+
+const BRANCH_TYPES: [&str; 4] = ["feature", "bugfix", "release", "hotfix"];
+
+#[derive(Deserialize)]
+struct Settings {
+    #[serde(default)]
+    development: Option<BranchSetting>,
+    #[serde(default)]
+    production: Option<BranchSetting>,
+    #[serde(default)]
+    branch_types: Vec<BranchType>,
+}
+
+#[derive(Deserialize)]
+struct BranchSetting {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    use_mainbranch: bool,
+    /// Only reported for production, which can be switched off.
+    #[serde(default)]
+    enabled: Option<bool>,
+    #[serde(default = "default_true")]
+    is_valid: bool,
+}
+
+#[derive(Deserialize)]
+struct BranchType {
+    kind: String,
+    #[serde(default)]
+    prefix: Option<String>,
+    #[serde(default)]
+    enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct ModelRow {
+    setting: String,
+    value: String,
+    enabled: bool,
+}
+
+/// Requested changes; unset fields are left as they are.
+#[derive(Debug, Default)]
+pub struct ModelUpdate {
+    pub development: Option<String>,
+    pub development_use_main: bool,
+    pub production: Option<String>,
+    pub production_use_main: bool,
+    pub no_production: bool,
+    pub prefixes: Vec<String>,
+    pub disable_types: Vec<String>,
+}
+
+pub async fn get_branching_model(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+) -> Result<()> {
+    let settings = fetch_settings(ctx, workspace, repo_slug).await?;
+    ctx.renderer.render(&model_rows(&settings))
+}
+
+pub async fn set_branching_model(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    update: &ModelUpdate,
+) -> Result<()> {
+    let payload = settings_payload(update)?;
+    if payload.as_object().is_some_and(|o| o.is_empty()) {
+        bail!("Nothing to change: pass a branch, --prefix or --disable-type option");
+    }
+
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/branching-model/settings");
+    let settings: Settings =
+        ctx.client.put(&path, &payload).await.with_context(|| {
+            format!("Failed to update branching model for {workspace}/{repo_slug}")
+        })?;
+
+    tracing::info!(workspace, repo_slug, "Branching model updated successfully");
+    println!("✓ Branching model updated for {workspace}/{repo_slug}");
+    ctx.renderer.render(&model_rows(&settings))
+}
+
+async fn fetch_settings(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+) -> Result<Settings> {
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/branching-model/settings");
+    ctx.client
+        .get(&path)
+        .await
+        .with_context(|| format!("Failed to get branching model for {workspace}/{repo_slug}"))
+}
+
+/// One row per branch and branch type, in the order Bitbucket shows them.
+fn model_rows(settings: &Settings) -> Vec<ModelRow> {
+    let branch_row = |setting: &str, branch: Option<&BranchSetting>| {
+        let (value, enabled) = match branch {
+            Some(b) if b.use_mainbranch => ("(main branch)".to_string(), b.enabled.unwrap_or(true)),
+            Some(b) => {
+                let mut name = b.name.clone().unwrap_or_default();
+                if !b.is_valid {
+                    name.push_str(" (branch not found)");
+                }
+                (name, b.enabled.unwrap_or(true))
+            }
+            None => (String::new(), false),
+        };
+        ModelRow {
+            setting: setting.to_string(),
+            value,
+            enabled,
+        }
+    };
+
+    let mut rows = vec![
+        branch_row("development", settings.development.as_ref()),
+        branch_row("production", settings.production.as_ref()),
+    ];
+    rows.extend(settings.branch_types.iter().map(|t| ModelRow {
+        setting: t.kind.clone(),
+        value: t.prefix.clone().unwrap_or_default(),
+        enabled: t.enabled,
+    }));
+    rows
+}
+
+/// Build the settings PUT body; branch types are sent only for the kinds changed.
+fn settings_payload(update: &ModelUpdate) -> Result<Value> {
+    let mut payload = json!({});
+
+    if update.development_use_main {
+        payload["development"] = json!({ "use_mainbranch": true });
+    } else if let Some(name) = &update.development {
+        payload["development"] = json!({ "name": name, "use_mainbranch": false });
+    }
+
+    if update.no_production {
+        payload["production"] = json!({ "enabled": false });
+    } else if update.production_use_main {
+        payload["production"] = json!({ "enabled": true, "use_mainbranch": true });
+    } else if let Some(name) = &update.production {
+        payload["production"] = json!({ "enabled": true, "name": name, "use_mainbranch": false });
+    }
+
+    let mut branch_types: Vec<Value> = Vec::new();
+    for prefix in &update.prefixes {
+        let (kind, prefix) = prefix
+            .split_once('=')
+            .with_context(|| format!("Invalid prefix '{prefix}': expected KIND=PREFIX"))?;
+        check_kind(kind)?;
+        branch_types.push(json!({ "kind": kind, "enabled": true, "prefix": prefix }));
+    }
+    for kind in &update.disable_types {
+        check_kind(kind)?;
+        if branch_types.iter().any(|t| t["kind"] == kind.as_str()) {
+            bail!("Branch type '{kind}' is both given a prefix and disabled");
+        }
+        branch_types.push(json!({ "kind": kind, "enabled": false }));
+    }
+    if !branch_types.is_empty() {
+        payload["branch_types"] = Value::Array(branch_types);
+    }
+
+    Ok(payload)
+}
+
+fn check_kind(kind: &str) -> Result<()> {
+    if !BRANCH_TYPES.contains(&kind) {
+        bail!(
+            "Unknown branch type '{kind}': expected one of {}",
+            BRANCH_TYPES.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_rows() {
+        let settings: Settings = serde_json::from_value(json!({
+            "development": { "name": null, "use_mainbranch": true, "is_valid": true },
+            "production": { "name": "prod", "use_mainbranch": false, "enabled": true, "is_valid": false },
+            "branch_types": [
+                { "kind": "feature", "prefix": "feature/", "enabled": true },
+                { "kind": "hotfix", "enabled": false }
+            ]
+        }))
+        .unwrap();
+
+        let rows = model_rows(&settings);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].value, "(main branch)");
+        assert_eq!(rows[1].value, "prod (branch not found)");
+        assert_eq!(
+            rows[3],
+            ModelRow {
+                setting: "hotfix".to_string(),
+                value: String::new(),
+                enabled: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_settings_payload() {
+        let update = ModelUpdate {
+            development: Some("develop".to_string()),
+            production_use_main: true,
+            prefixes: vec!["feature=feat/".to_string(), "release=release/".to_string()],
+            disable_types: vec!["hotfix".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            settings_payload(&update).unwrap(),
+            json!({
+                "development": { "name": "develop", "use_mainbranch": false },
+                "production": { "enabled": true, "use_mainbranch": true },
+                "branch_types": [
+                    { "kind": "feature", "enabled": true, "prefix": "feat/" },
+                    { "kind": "release", "enabled": true, "prefix": "release/" },
+                    { "kind": "hotfix", "enabled": false }
+                ]
+            })
+        );
+
+        let bad = ModelUpdate {
+            prefixes: vec!["task=task/".to_string()],
+            ..Default::default()
+        };
+        assert!(settings_payload(&bad)
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown branch type 'task'"));
+
+        let empty = settings_payload(&ModelUpdate::default()).unwrap();
+        assert_eq!(empty, json!({}));
+    }
+}
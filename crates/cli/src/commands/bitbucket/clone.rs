@@ -0,0 +1,275 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use super::utils::{self, BitbucketContext};
+
+// Workspace-wide local checkouts: list repositories through the API, then clone
+// or update each one with git. Authentication is left to git (SSH keys or a
+// credential helper) so no token ends up in remote URLs.
+
+#[derive(Deserialize)]
+struct RepoPage {
+    values: Vec<Repo>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Repo {
+    slug: String,
+    #[serde(default)]
+    links: RepoLinks,
+}
+
+#[derive(Deserialize, Default)]
+struct RepoLinks {
+    #[serde(default)]
+    clone: Vec<CloneLink>,
+}
+
+#[derive(Deserialize)]
+struct CloneLink {
+    name: String,
+    href: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CloneRow {
+    repo: String,
+    status: &'static str,
+    path: String,
+    detail: String,
+}
+
+struct CloneJob {
+    slug: String,
+    url: String,
+    path: PathBuf,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn bulk_clone(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    filter: Option<&str>,
+    dir: &Path,
+    mirror: bool,
+    protocol: &str,
+    concurrency: usize,
+    dry_run: bool,
+) -> Result<()> {
+    if !matches!(protocol, "https" | "ssh") {
+        anyhow::bail!("Invalid protocol '{protocol}'. Must be one of: https, ssh");
+    }
+
+    let repos = fetch_repos(ctx, workspace, filter).await?;
+    if repos.is_empty() {
+        println!("No repositories matched in workspace {workspace}");
+        return Ok(());
+    }
+
+    let jobs = repos
+        .into_iter()
+        .map(|repo| {
+            let url = clone_url(&repo.links.clone, protocol)
+                .ok_or_else(|| anyhow!("Repository {} has no {protocol} clone link", repo.slug))?;
+            let path = dir.join(checkout_dir(&repo.slug, mirror));
+            Ok(CloneJob {
+                slug: repo.slug,
+                url,
+                path,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+        for job in &jobs {
+            let action = if job.path.exists() { "update" } else { "clone" };
+            println!("  Would {action}: {} -> {}", job.slug, job.path.display());
+        }
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+    let mut rows: Vec<CloneRow> = stream::iter(jobs)
+        .map(|job| sync_checkout(job, mirror))
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+    rows.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+    let count = |status: &str| rows.iter().filter(|r| r.status == status).count();
+    let (new, updated, failed) = (count("new"), count("updated"), count("failed"));
+    tracing::info!(workspace, new, updated, failed, "Bulk clone finished");
+
+    ctx.renderer.render(&rows)?;
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} repositories failed to clone or update",
+            rows.len()
+        );
+    }
+    println!("✓ {new} new, {updated} updated");
+    Ok(())
+}
+
+async fn fetch_repos(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    filter: Option<&str>,
+) -> Result<Vec<Repo>> {
+    let mut path = format!("/2.0/repositories/{workspace}?pagelen=100");
+    if let Some(filter) = filter {
+        path.push_str(&format!(
+            "&q={}",
+            urlencoding::encode(&filter_query(filter))
+        ));
+    }
+
+    let mut repos = Vec::new();
+    loop {
+        let page: RepoPage =
+            ctx.client.get(&path).await.with_context(|| {
+                format!("Failed to list repositories for workspace {workspace}")
+            })?;
+        repos.extend(page.values);
+        match page.next {
+            Some(next) => path = utils::strip_api_host(&next),
+            None => break,
+        }
+    }
+
+    Ok(repos)
+}
+
+/// Clone a repository that is not checked out yet, or update an existing checkout.
+async fn sync_checkout(job: CloneJob, mirror: bool) -> CloneRow {
+    let path = job.path.display().to_string();
+    let (status, args): (&'static str, Vec<&str>) = if job.path.exists() {
+        let update = if mirror {
+            vec!["-C", &path, "remote", "update", "--prune"]
+        } else {
+            vec!["-C", &path, "pull", "--ff-only"]
+        };
+        ("updated", update)
+    } else {
+        let mut clone = vec!["clone"];
+        if mirror {
+            clone.push("--mirror");
+        }
+        clone.extend([job.url.as_str(), path.as_str()]);
+        ("new", clone)
+    };
+
+    let output = Command::new("git")
+        .args(&args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await;
+
+    let (status, detail) = match output {
+        Ok(out) if out.status.success() => (status, String::new()),
+        Ok(out) => ("failed", last_line(&String::from_utf8_lossy(&out.stderr))),
+        Err(err) => ("failed", format!("Failed to run git: {err}")),
+    };
+    if status == "failed" {
+        tracing::warn!(repo = job.slug.as_str(), %detail, "git failed");
+    }
+
+    CloneRow {
+        repo: job.slug,
+        status,
+        path,
+        detail,
+    }
+}
+
+/// Turn `project=PLAT`-style shorthands into a Bitbucket query; anything else
+/// is passed through as a raw query (e.g. `project.key="PLAT" AND language="rust"`).
+pub(super) fn filter_query(filter: &str) -> String {
+    let Some((key, value)) = filter.split_once('=') else {
+        return filter.to_string();
+    };
+    let field = match key.trim() {
+        "project" => "project.key",
+        "name" | "slug" | "language" => key.trim(),
+        _ => return filter.to_string(),
+    };
+    let value = value.trim();
+    if value.starts_with('"') || value.contains(char::is_whitespace) {
+        return filter.to_string();
+    }
+    format!("{field}=\"{}\"", value.replace('"', ""))
+}
+
+fn clone_url(links: &[CloneLink], protocol: &str) -> Option<String> {
+    links
+        .iter()
+        .find(|link| link.name == protocol)
+        .map(|link| link.href.clone())
+}
+
+fn checkout_dir(slug: &str, mirror: bool) -> String {
+    if mirror {
+        format!("{slug}.git")
+    } else {
+        slug.to_string()
+    }
+}
+
+fn last_line(stderr: &str) -> String {
+    stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or("git exited with an error")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_query() {
+        assert_eq!(filter_query("project=PLAT"), "project.key=\"PLAT\"");
+        assert_eq!(filter_query("language = rust"), "language=\"rust\"");
+        assert_eq!(
+            filter_query("project.key=\"PLAT\" AND is_private=true"),
+            "project.key=\"PLAT\" AND is_private=true"
+        );
+        assert_eq!(filter_query("name ~ \"api\""), "name ~ \"api\"");
+        assert_eq!(
+            filter_query("project=PLAT AND language=rust"),
+            "project=PLAT AND language=rust"
+        );
+    }
+
+    #[test]
+    fn test_clone_targets() {
+        let repo: Repo = serde_json::from_value(serde_json::json!({
+            "slug": "api",
+            "links": { "clone": [
+                { "name": "https", "href": "https://bitbucket.org/team/api.git" },
+                { "name": "ssh", "href": "git@bitbucket.org:team/api.git" }
+            ] }
+        }))
+        .unwrap();
+        assert_eq!(
+            clone_url(&repo.links.clone, "ssh").as_deref(),
+            Some("git@bitbucket.org:team/api.git")
+        );
+        assert_eq!(checkout_dir("api", true), "api.git");
+        assert_eq!(
+            last_line("Cloning...\nfatal: repository not found\n\n"),
+            "fatal: repository not found"
+        );
+    }
+}
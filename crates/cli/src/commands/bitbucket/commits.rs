@@ -1,13 +1,8 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
-
-#[derive(Deserialize)]
-struct CommitList {
-    values: Vec<Commit>,
-}
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
 
 #[derive(Deserialize)]
 struct Commit {
@@ -84,28 +79,17 @@ pub async fn list_commits(
     workspace: &str,
     repo_slug: &str,
     branch: Option<&str>,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    let mut query = form_urlencoded::Serializer::new(String::new());
-    query.append_pair("pagelen", &limit.min(100).to_string());
-
     let path = if let Some(b) = branch {
-        format!(
-            "/2.0/repositories/{workspace}/{repo_slug}/commits/{b}?{}",
-            query.finish()
-        )
+        format!("/2.0/repositories/{workspace}/{repo_slug}/commits/{b}")
     } else {
-        format!(
-            "/2.0/repositories/{workspace}/{repo_slug}/commits?{}",
-            query.finish()
-        )
+        format!("/2.0/repositories/{workspace}/{repo_slug}/commits")
     };
 
-    let response: CommitList = ctx
-        .client
-        .get(&path)
-        .await
-        .with_context(|| format!("Failed to list commits for {workspace}/{repo_slug}"))?;
+    // The commits collection only pages forwards through `next` links
+    let what = format!("commits for {workspace}/{repo_slug}");
+    let commits: Vec<Commit> = fetch_paged(ctx, &path, page, Paging::Cursor, &what).await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -115,8 +99,7 @@ pub async fn list_commits(
         date: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = commits
         .iter()
         .map(|commit| Row {
             hash: &commit.hash[..7.min(commit.hash.len())],
@@ -280,3 +263,108 @@ pub async fn browse_source(
 
     ctx.renderer.render(&rows)
 }
+
+pub async fn add_commit_comment(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    commit_hash: &str,
+    text: &str,
+    file: Option<&str>,
+    line: Option<u32>,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Comment {
+        id: i64,
+    }
+
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/commit/{commit_hash}/comments");
+    let comment: Comment = ctx
+        .client
+        .post(&path, &comment_payload(text, file, line))
+        .await
+        .with_context(|| {
+            format!("Failed to comment on commit {commit_hash} in {workspace}/{repo_slug}")
+        })?;
+
+    tracing::info!(
+        comment_id = comment.id,
+        commit_hash,
+        "Commit comment added successfully"
+    );
+
+    println!("✓ Comment added to commit {}", short_hash(commit_hash));
+    Ok(())
+}
+
+pub async fn approve_commit(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    commit_hash: &str,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Approval {
+        user: User,
+    }
+
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/commit/{commit_hash}/approve");
+    let approval: Approval = ctx
+        .client
+        .post(&path, &serde_json::json!({}))
+        .await
+        .with_context(|| {
+            format!("Failed to approve commit {commit_hash} in {workspace}/{repo_slug}")
+        })?;
+
+    tracing::info!(
+        commit_hash,
+        workspace,
+        repo_slug,
+        "Commit approved successfully"
+    );
+
+    println!(
+        "✓ Commit {} approved by {}",
+        short_hash(commit_hash),
+        approval.user.display_name
+    );
+    Ok(())
+}
+
+/// Comment body; `file` (and optionally `line` in the new version) makes it inline.
+fn comment_payload(text: &str, file: Option<&str>, line: Option<u32>) -> serde_json::Value {
+    let mut payload = serde_json::json!({ "content": { "raw": text } });
+    if let Some(file) = file {
+        payload["inline"] = serde_json::json!({ "path": file });
+        if let Some(line) = line {
+            payload["inline"]["to"] = serde_json::json!(line);
+        }
+    }
+    payload
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..7.min(hash.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_comment_payload() {
+        assert_eq!(
+            comment_payload("Nice", None, None),
+            json!({ "content": { "raw": "Nice" } })
+        );
+        assert_eq!(
+            comment_payload("Typo here", Some("src/main.rs"), Some(42)),
+            json!({
+                "content": { "raw": "Typo here" },
+                "inline": { "path": "src/main.rs", "to": 42 }
+            })
+        );
+    }
+}
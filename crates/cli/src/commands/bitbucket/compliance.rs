@@ -0,0 +1,376 @@
+use anyhow::{Context, Result};
+use atlassian_cli_output::OutputFormat;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::clone::filter_query;
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
+
+// Workspace compliance audit: every repository is checked for restrictions on
+// its main branch, default reviewers and recent pushes. One row per finding so
+// the JSON output can feed dashboards directly.
+
+#[derive(Deserialize)]
+struct Repo {
+    slug: String,
+    #[serde(default)]
+    mainbranch: Option<BranchRef>,
+}
+
+#[derive(Deserialize)]
+struct BranchRef {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Restriction {
+    kind: String,
+    #[serde(default)]
+    branch_match_kind: Option<String>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    branch_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Branch {
+    #[serde(default)]
+    target: Option<Target>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    #[serde(default)]
+    date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct FindingRow {
+    repo: String,
+    finding: &'static str,
+    detail: String,
+}
+
+impl FindingRow {
+    fn new(repo: &str, finding: &'static str, detail: String) -> Self {
+        Self {
+            repo: repo.to_string(),
+            finding,
+            detail,
+        }
+    }
+}
+
+/// What a single repository scan found out.
+struct RepoAudit {
+    main_branch: Option<String>,
+    restriction_kinds: Vec<String>,
+    default_reviewers: usize,
+    last_push: Option<DateTime<Utc>>,
+}
+
+pub async fn compliance_report(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    filter: Option<&str>,
+    stale_days: i64,
+    concurrency: usize,
+) -> Result<()> {
+    let mut path = format!("/2.0/repositories/{workspace}");
+    if let Some(filter) = filter {
+        path.push_str(&format!(
+            "?q={}",
+            urlencoding::encode(&filter_query(filter))
+        ));
+    }
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let repos: Vec<Repo> =
+        fetch_paged(ctx, &path, &everything, Paging::Cursor, "repositories").await?;
+    if repos.is_empty() {
+        println!("No repositories matched in workspace {workspace}");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let scanned = repos.len();
+    let mut findings: Vec<FindingRow> = stream::iter(repos)
+        .map(|repo| async move {
+            match audit_repo(ctx, workspace, &repo).await {
+                Ok(audit) => repo_findings(&repo.slug, &audit, now, stale_days),
+                Err(err) => vec![FindingRow::new(
+                    &repo.slug,
+                    "scan-failed",
+                    format!("{err:#}"),
+                )],
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .flat_map(stream::iter)
+        .collect()
+        .await;
+    findings.sort_by(|a, b| a.repo.cmp(&b.repo));
+
+    let mut flagged: Vec<&str> = findings.iter().map(|f| f.repo.as_str()).collect();
+    flagged.dedup();
+    tracing::info!(
+        workspace,
+        scanned,
+        flagged = flagged.len(),
+        findings = findings.len(),
+        "Compliance scan finished"
+    );
+
+    if findings.is_empty() && ctx.renderer.format() == OutputFormat::Table {
+        println!("✓ All {scanned} repositories in {workspace} are compliant");
+        return Ok(());
+    }
+    ctx.renderer.render(&findings)?;
+    if ctx.renderer.format() == OutputFormat::Table {
+        println!(
+            "\n{} finding(s) in {} of {scanned} repositories",
+            findings.len(),
+            flagged.len()
+        );
+    }
+    Ok(())
+}
+
+async fn audit_repo(ctx: &BitbucketContext<'_>, workspace: &str, repo: &Repo) -> Result<RepoAudit> {
+    let slug = repo.slug.as_str();
+    let Some(main) = repo.mainbranch.as_ref().map(|b| b.name.clone()) else {
+        // Empty repository: nothing to protect or push to yet
+        return Ok(RepoAudit {
+            main_branch: None,
+            restriction_kinds: Vec::new(),
+            default_reviewers: 0,
+            last_push: None,
+        });
+    };
+
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let restrictions: Vec<Restriction> = fetch_paged(
+        ctx,
+        &format!("/2.0/repositories/{workspace}/{slug}/branch-restrictions"),
+        &everything,
+        Paging::Cursor,
+        &format!("branch restrictions for {workspace}/{slug}"),
+    )
+    .await?;
+    let reviewers: Vec<serde_json::Value> = fetch_paged(
+        ctx,
+        &format!("/2.0/repositories/{workspace}/{slug}/default-reviewers"),
+        &everything,
+        Paging::Cursor,
+        &format!("default reviewers for {workspace}/{slug}"),
+    )
+    .await?;
+    let branch: Branch = ctx
+        .client
+        .get(&format!(
+            "/2.0/repositories/{workspace}/{slug}/refs/branches/{}",
+            urlencoding::encode(&main)
+        ))
+        .await
+        .with_context(|| format!("Failed to get branch {main} of {workspace}/{slug}"))?;
+
+    let mut restriction_kinds: Vec<String> = restrictions
+        .into_iter()
+        .filter(|r| restricts_branch(r, &main))
+        .map(|r| r.kind)
+        .collect();
+    restriction_kinds.sort();
+    restriction_kinds.dedup();
+
+    Ok(RepoAudit {
+        main_branch: Some(main),
+        restriction_kinds,
+        default_reviewers: reviewers.len(),
+        last_push: branch.target.and_then(|t| t.date),
+    })
+}
+
+fn repo_findings(
+    slug: &str,
+    audit: &RepoAudit,
+    now: DateTime<Utc>,
+    stale_days: i64,
+) -> Vec<FindingRow> {
+    let Some(main) = &audit.main_branch else {
+        return vec![FindingRow::new(
+            slug,
+            "empty",
+            "repository has no main branch".to_string(),
+        )];
+    };
+
+    let mut findings = Vec::new();
+    if audit.restriction_kinds.is_empty() {
+        findings.push(FindingRow::new(
+            slug,
+            "unprotected-main",
+            format!("no branch restrictions apply to {main}"),
+        ));
+    }
+    if audit.default_reviewers == 0 {
+        findings.push(FindingRow::new(
+            slug,
+            "no-default-reviewers",
+            "no default reviewers configured".to_string(),
+        ));
+    }
+    match audit.last_push {
+        Some(pushed) => {
+            let idle = now.signed_duration_since(pushed).num_days();
+            if idle > stale_days {
+                findings.push(FindingRow::new(
+                    slug,
+                    "stale",
+                    format!(
+                        "no pushes to {main} in {idle} days (last {})",
+                        pushed.format("%Y-%m-%d")
+                    ),
+                ));
+            }
+        }
+        None => findings.push(FindingRow::new(
+            slug,
+            "stale",
+            format!("no commits found on {main}"),
+        )),
+    }
+    findings
+}
+
+/// Whether a branch restriction covers `branch`. Restrictions on branching
+/// model types are counted for the main branch, which is always the
+/// development or production branch of the model.
+fn restricts_branch(restriction: &Restriction, branch: &str) -> bool {
+    match restriction.branch_match_kind.as_deref() {
+        Some("branching_model") => matches!(
+            restriction.branch_type.as_deref(),
+            Some("development" | "production")
+        ),
+        _ => restriction
+            .pattern
+            .as_deref()
+            .is_some_and(|pattern| glob_matches(pattern, branch)),
+    }
+}
+
+/// Match a Bitbucket branch pattern where `*` stands for any run of characters.
+pub(super) fn glob_matches(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == name;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if name.len() < first.len() + last.len() || !name.starts_with(first) || !name.ends_with(last) {
+        return false;
+    }
+    let mut rest = &name[first.len()..name.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn restriction(kind: &str, pattern: Option<&str>, branch_type: Option<&str>) -> Restriction {
+        Restriction {
+            kind: kind.to_string(),
+            branch_match_kind: Some(
+                if branch_type.is_some() {
+                    "branching_model"
+                } else {
+                    "glob"
+                }
+                .to_string(),
+            ),
+            pattern: pattern.map(str::to_string),
+            branch_type: branch_type.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("main", "main"));
+        assert!(!glob_matches("main", "main2"));
+        assert!(glob_matches("*", "main"));
+        assert!(glob_matches("release/*", "release/1.0"));
+        assert!(!glob_matches("release/*", "main"));
+        assert!(glob_matches("m*n", "main"));
+        assert!(glob_matches("*a*", "main"));
+        assert!(!glob_matches("ma*ain", "main"));
+    }
+
+    #[test]
+    fn test_restricts_branch() {
+        assert!(restricts_branch(
+            &restriction("push", Some("main"), None),
+            "main"
+        ));
+        assert!(!restricts_branch(
+            &restriction("push", Some("release/*"), None),
+            "main"
+        ));
+        assert!(restricts_branch(
+            &restriction("force", None, Some("production")),
+            "main"
+        ));
+        assert!(!restricts_branch(
+            &restriction("force", None, Some("feature")),
+            "main"
+        ));
+    }
+
+    #[test]
+    fn test_repo_findings() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let compliant = RepoAudit {
+            main_branch: Some("main".to_string()),
+            restriction_kinds: vec!["push".to_string()],
+            default_reviewers: 2,
+            last_push: Some(now - chrono::Duration::days(3)),
+        };
+        assert!(repo_findings("api", &compliant, now, 90).is_empty());
+
+        let neglected = RepoAudit {
+            restriction_kinds: Vec::new(),
+            default_reviewers: 0,
+            last_push: Some(now - chrono::Duration::days(200)),
+            ..compliant
+        };
+        let findings: Vec<&str> = repo_findings("api", &neglected, now, 90)
+            .iter()
+            .map(|f| f.finding)
+            .collect();
+        assert_eq!(
+            findings,
+            vec!["unprotected-main", "no-default-reviewers", "stale"]
+        );
+
+        let empty = RepoAudit {
+            main_branch: None,
+            restriction_kinds: Vec::new(),
+            default_reviewers: 0,
+            last_push: None,
+        };
+        assert_eq!(repo_findings("new", &empty, now, 90)[0].finding, "empty");
+    }
+}
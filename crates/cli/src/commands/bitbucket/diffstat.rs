@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use atlassian_cli_output::OutputFormat;
+use serde::{Deserialize, Serialize};
+
+use super::utils::{self, BitbucketContext};
+
+#[derive(Deserialize)]
+struct DiffstatPage {
+    values: Vec<DiffstatEntry>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiffstatEntry {
+    status: String,
+    #[serde(default)]
+    lines_added: u64,
+    #[serde(default)]
+    lines_removed: u64,
+    #[serde(default)]
+    old: Option<FileRef>,
+    #[serde(default)]
+    new: Option<FileRef>,
+}
+
+#[derive(Deserialize)]
+struct FileRef {
+    path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct FileRow {
+    path: String,
+    status: String,
+    additions: u64,
+    deletions: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    renamed_from: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct DiffstatSummary {
+    files: usize,
+    additions: u64,
+    deletions: u64,
+    added: usize,
+    modified: usize,
+    removed: usize,
+    renamed: usize,
+}
+
+pub async fn list_pr_files(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+    name_only: bool,
+) -> Result<()> {
+    let files = fetch_files(ctx, workspace, repo_slug, pr_id).await?;
+
+    if name_only && ctx.renderer.format() == OutputFormat::Table {
+        for file in &files {
+            println!("{}", file.path);
+        }
+        return Ok(());
+    }
+    if name_only {
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        return ctx.renderer.render(&paths);
+    }
+
+    if files.is_empty() {
+        tracing::info!(
+            pr_id,
+            workspace,
+            repo_slug,
+            "No files changed in pull request"
+        );
+        return Ok(());
+    }
+
+    ctx.renderer.render(&files)
+}
+
+pub async fn pr_diffstat(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+) -> Result<()> {
+    let files = fetch_files(ctx, workspace, repo_slug, pr_id).await?;
+    let summary = summarize(&files);
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&summary);
+    }
+
+    println!(
+        "Pull request #{pr_id}: {} files changed, {} insertions(+), {} deletions(-)",
+        summary.files, summary.additions, summary.deletions
+    );
+    println!(
+        "  added {}, modified {}, removed {}, renamed {}",
+        summary.added, summary.modified, summary.removed, summary.renamed
+    );
+    Ok(())
+}
+
+async fn fetch_files(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+) -> Result<Vec<FileRow>> {
+    let mut files = Vec::new();
+    let mut path = format!(
+        "/2.0/repositories/{workspace}/{repo_slug}/pullrequests/{pr_id}/diffstat?pagelen=500"
+    );
+
+    loop {
+        let page: DiffstatPage = ctx.client.get(&path).await.with_context(|| {
+            format!("Failed to get diffstat for pull request {pr_id} in {workspace}/{repo_slug}")
+        })?;
+        files.extend(page.values.into_iter().map(file_row));
+        match page.next {
+            Some(next) => path = utils::strip_api_host(&next),
+            None => break,
+        }
+    }
+
+    Ok(files)
+}
+
+/// Files are listed by their new path; removed files by their old path.
+fn file_row(entry: DiffstatEntry) -> FileRow {
+    let (path, renamed_from) = match (entry.old, entry.new) {
+        (Some(old), Some(new)) if old.path != new.path => (new.path, Some(old.path)),
+        (_, Some(file)) | (Some(file), None) => (file.path, None),
+        (None, None) => (String::new(), None),
+    };
+    FileRow {
+        path,
+        status: entry.status,
+        additions: entry.lines_added,
+        deletions: entry.lines_removed,
+        renamed_from,
+    }
+}
+
+fn summarize(files: &[FileRow]) -> DiffstatSummary {
+    let mut summary = DiffstatSummary {
+        files: files.len(),
+        ..Default::default()
+    };
+    for file in files {
+        summary.additions += file.additions;
+        summary.deletions += file.deletions;
+        match file.status.as_str() {
+            "added" => summary.added += 1,
+            "removed" => summary.removed += 1,
+            "renamed" => summary.renamed += 1,
+            _ => summary.modified += 1,
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entries() -> Vec<FileRow> {
+        let page: DiffstatPage = serde_json::from_value(json!({
+            "values": [
+                { "status": "modified", "lines_added": 10, "lines_removed": 2,
+                  "old": { "path": "src/lib.rs" }, "new": { "path": "src/lib.rs" } },
+                { "status": "added", "lines_added": 40, "lines_removed": 0,
+                  "old": null, "new": { "path": "src/diff.rs" } },
+                { "status": "removed", "lines_added": 0, "lines_removed": 7,
+                  "old": { "path": "old.txt" }, "new": null },
+                { "status": "renamed", "lines_added": 1, "lines_removed": 1,
+                  "old": { "path": "a.md" }, "new": { "path": "docs/a.md" } }
+            ]
+        }))
+        .unwrap();
+        page.values.into_iter().map(file_row).collect()
+    }
+
+    #[test]
+    fn test_file_rows() {
+        let rows = entries();
+        let paths: Vec<&str> = rows.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["src/lib.rs", "src/diff.rs", "old.txt", "docs/a.md"]
+        );
+        assert_eq!(rows[3].renamed_from.as_deref(), Some("a.md"));
+        assert!(rows[0].renamed_from.is_none());
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(&entries()),
+            DiffstatSummary {
+                files: 4,
+                additions: 51,
+                deletions: 10,
+                added: 1,
+                modified: 1,
+                removed: 1,
+                renamed: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_output::OutputFormat;
+use serde::Deserialize;
+
+use super::pipelines::format_elapsed;
+use super::pullrequests::merge_pull_request;
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::cancel;
+use crate::commands::pagination::{PaginationArgs, Paging};
+
+#[derive(Deserialize)]
+struct PullRequest {
+    state: String,
+    #[serde(default)]
+    participants: Vec<Participant>,
+}
+
+#[derive(Deserialize)]
+struct Participant {
+    #[serde(default)]
+    approved: bool,
+}
+
+#[derive(Deserialize)]
+struct Task {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct BuildStatus {
+    state: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    key: Option<String>,
+}
+
+/// Snapshot of the merge gates for a pull request.
+#[derive(Debug, Default, PartialEq)]
+struct Gates {
+    approvals: usize,
+    required_approvals: usize,
+    open_tasks: usize,
+    builds_total: usize,
+    builds_passed: usize,
+    failed_builds: Vec<String>,
+}
+
+impl Gates {
+    fn ready(&self) -> bool {
+        self.approvals >= self.required_approvals
+            && self.open_tasks == 0
+            && self.builds_passed == self.builds_total
+    }
+
+    fn summary(&self) -> String {
+        let mark = |ok: bool| if ok { "✅" } else { "⏳" };
+        let builds = if self.builds_total == 0 {
+            "no builds".to_string()
+        } else {
+            format!("builds {}/{}", self.builds_passed, self.builds_total)
+        };
+        format!(
+            "approvals {}/{} {}  tasks {} open {}  {} {}",
+            self.approvals,
+            self.required_approvals,
+            mark(self.approvals >= self.required_approvals),
+            self.open_tasks,
+            mark(self.open_tasks == 0),
+            builds,
+            mark(self.builds_passed == self.builds_total),
+        )
+    }
+}
+
+/// Poll a pull request until it has enough approvals, no open tasks and green
+/// builds on its head commit, then merge it.
+#[allow(clippy::too_many_arguments)]
+pub async fn merge_when_ready(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+    merge_strategy: Option<&str>,
+    message: Option<&str>,
+    required_approvals: usize,
+    timeout: Duration,
+    interval: u64,
+) -> Result<()> {
+    let start = Instant::now();
+    let is_table = ctx.renderer.format() == OutputFormat::Table;
+
+    if is_table {
+        eprintln!("Waiting for pull request #{pr_id} to be ready to merge... (Ctrl-C to stop)");
+    }
+
+    loop {
+        let gates = fetch_gates(ctx, workspace, repo_slug, pr_id, required_approvals).await?;
+
+        if is_table {
+            use std::io::Write;
+            print!("\x1B[2K\r[{}] {}", format_elapsed(start), gates.summary());
+            std::io::stdout().flush().ok();
+        }
+
+        if !gates.failed_builds.is_empty() {
+            if is_table {
+                println!();
+            }
+            bail!(
+                "Not merging pull request #{pr_id}: builds failed ({})",
+                gates.failed_builds.join(", ")
+            );
+        }
+
+        if gates.ready() {
+            if is_table {
+                println!("\n✓ All merge checks passed, merging pull request #{pr_id}");
+            }
+            break;
+        }
+
+        if start.elapsed() >= timeout {
+            if is_table {
+                println!();
+            }
+            bail!(
+                "Timed out after {} waiting for pull request #{pr_id} to be ready",
+                format_elapsed(start)
+            );
+        }
+
+        if !cancel::sleep_unless_cancelled(Duration::from_secs(interval)).await {
+            if is_table {
+                println!();
+            }
+            bail!(
+                "Stopped waiting for pull request #{pr_id} after {} ({}); not merged: {}",
+                format_elapsed(start),
+                cancel::reason(),
+                gates.summary()
+            );
+        }
+    }
+
+    merge_pull_request(ctx, workspace, repo_slug, pr_id, merge_strategy, message).await
+}
+
+async fn fetch_gates(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+    required_approvals: usize,
+) -> Result<Gates> {
+    let base = format!("/2.0/repositories/{workspace}/{repo_slug}/pullrequests/{pr_id}");
+
+    let pr: PullRequest = ctx.client.get(&base).await.with_context(|| {
+        format!("Failed to get pull request {pr_id} in {workspace}/{repo_slug}")
+    })?;
+    if pr.state != "OPEN" {
+        return Err(anyhow!(
+            "Pull request #{pr_id} is {} and cannot be merged",
+            pr.state
+        ));
+    }
+
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let tasks: Vec<Task> = fetch_paged(
+        ctx,
+        &format!("{base}/tasks"),
+        &everything,
+        Paging::Cursor,
+        &format!("tasks for pull request {pr_id}"),
+    )
+    .await?;
+    let statuses: Vec<BuildStatus> = fetch_paged(
+        ctx,
+        &format!("{base}/statuses"),
+        &everything,
+        Paging::Cursor,
+        &format!("build statuses for pull request {pr_id}"),
+    )
+    .await?;
+
+    Ok(evaluate(&pr, &tasks, &statuses, required_approvals))
+}
+
+fn evaluate(
+    pr: &PullRequest,
+    tasks: &[Task],
+    statuses: &[BuildStatus],
+    required_approvals: usize,
+) -> Gates {
+    Gates {
+        approvals: pr.participants.iter().filter(|p| p.approved).count(),
+        required_approvals,
+        open_tasks: tasks.iter().filter(|t| t.state == "UNRESOLVED").count(),
+        builds_total: statuses.len(),
+        builds_passed: statuses.iter().filter(|s| s.state == "SUCCESSFUL").count(),
+        failed_builds: statuses
+            .iter()
+            .filter(|s| matches!(s.state.as_str(), "FAILED" | "STOPPED"))
+            .map(|s| {
+                s.name
+                    .clone()
+                    .or_else(|| s.key.clone())
+                    .unwrap_or_else(|| "unnamed build".to_string())
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_gates() {
+        let pr = PullRequest {
+            state: "OPEN".to_string(),
+            participants: vec![
+                Participant { approved: true },
+                Participant { approved: false },
+            ],
+        };
+        let tasks = vec![
+            Task {
+                state: "RESOLVED".to_string(),
+            },
+            Task {
+                state: "UNRESOLVED".to_string(),
+            },
+        ];
+        let statuses = vec![
+            BuildStatus {
+                state: "SUCCESSFUL".to_string(),
+                name: Some("lint".to_string()),
+                key: None,
+            },
+            BuildStatus {
+                state: "INPROGRESS".to_string(),
+                name: Some("test".to_string()),
+                key: None,
+            },
+        ];
+
+        let gates = evaluate(&pr, &tasks, &statuses, 1);
+        assert_eq!(gates.approvals, 1);
+        assert_eq!(gates.open_tasks, 1);
+        assert_eq!(gates.builds_passed, 1);
+        assert!(!gates.ready());
+        assert!(gates.failed_builds.is_empty());
+
+        let gates = evaluate(&pr, &tasks[..1], &statuses[..1], 1);
+        assert!(gates.ready());
+        assert_eq!(
+            gates.summary(),
+            "approvals 1/1 ✅  tasks 0 open ✅  builds 1/1 ✅"
+        );
+
+        let failed = vec![BuildStatus {
+            state: "FAILED".to_string(),
+            name: None,
+            key: Some("ci/build".to_string()),
+        }];
+        assert_eq!(
+            evaluate(&pr, &[], &failed, 2).failed_builds,
+            vec!["ci/build"]
+        );
+    }
+}
@@ -4,17 +4,38 @@ use atlassian_cli_output::OutputRenderer;
 use clap::{Args, Subcommand};
 
 // Submodules
+mod archive;
+mod artifacts;
+mod branch_status;
 mod branches;
+mod branching_model;
 mod bulk;
+mod clone;
 mod commits;
+mod compliance;
+mod diffstat;
+mod merge_watch;
 mod permissions;
+mod pipeline_lint;
 mod pipelines;
+mod pr_checks;
+mod pr_export;
 mod pullrequests;
 mod repos;
+mod runners;
+mod sync;
+mod tags;
 pub mod utils;
+mod webhook_listen;
 mod webhooks;
 mod workspaces;
 
+use super::cancel;
+use super::dates;
+use super::entity_url;
+use super::multi_profile::{self, ProfileTarget};
+use super::pagination::PaginationArgs;
+use super::webhook_events;
 use utils::BitbucketContext;
 
 #[derive(Args, Debug, Clone)]
@@ -37,6 +58,10 @@ enum BitbucketCommands {
     #[command(subcommand)]
     Branch(BranchCommands),
 
+    /// Branching model (development/production branches and branch prefixes).
+    #[command(subcommand)]
+    BranchingModel(BranchingModelCommands),
+
     /// Pull request operations.
     #[command(subcommand)]
     Pr(PrCommands),
@@ -69,6 +94,14 @@ enum BitbucketCommands {
     #[command(subcommand)]
     Commit(CommitCommands),
 
+    /// Self-hosted pipeline runner operations.
+    #[command(subcommand)]
+    Runner(RunnerCommands),
+
+    /// Workspace reports.
+    #[command(subcommand)]
+    Report(ReportCommands),
+
     /// Bulk operations.
     #[command(subcommand)]
     Bulk(BulkCommands),
@@ -81,8 +114,11 @@ enum BitbucketCommands {
 enum RepoCommands {
     /// List repositories inside a workspace.
     List {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        /// Only repositories tagged with this topic (repeat for several; all must match).
+        #[arg(long)]
+        tag: Vec<String>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Show repository metadata.
     Get { slug: String },
@@ -102,6 +138,9 @@ enum RepoCommands {
         /// Project key to associate with.
         #[arg(long)]
         project: Option<String>,
+        /// Succeed and show the existing repository if the slug is already taken.
+        #[arg(long)]
+        if_not_exists: bool,
     },
     /// Update repository metadata.
     Update {
@@ -116,6 +155,9 @@ enum RepoCommands {
         /// Programming language.
         #[arg(long)]
         language: Option<String>,
+        /// Show a diff of what would change without updating the repository.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Delete a repository.
     Delete {
@@ -125,6 +167,51 @@ enum RepoCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Archive a repository: restrict all pushes, mark the description and turn off issues and wiki.
+    Archive {
+        /// Repository slug.
+        slug: String,
+        /// Commit a README saying why, e.g. "Moved to mono-repo", before restricting pushes.
+        #[arg(long, value_name = "MESSAGE")]
+        tombstone: Option<String>,
+        /// Also prefix the repository name with "archived-" (this changes the slug).
+        #[arg(long)]
+        rename: bool,
+        /// Show the planned changes without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Reverse `repo archive`: lift the push restriction and remove the name and description markers.
+    Unarchive {
+        /// Repository slug.
+        slug: String,
+        /// Show the planned changes without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Classify repositories with topics, kept on a `[topics: ...]` line of the description.
+    #[command(subcommand)]
+    Tag(RepoTagCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum RepoTagCommands {
+    /// Add topics to a repository.
+    Add {
+        /// Repository slug.
+        slug: String,
+        /// Topics to add.
+        #[arg(required = true)]
+        topics: Vec<String>,
+    },
+    /// Remove topics from a repository.
+    Remove {
+        /// Repository slug.
+        slug: String,
+        /// Topics to remove.
+        #[arg(required = true)]
+        topics: Vec<String>,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -133,8 +220,8 @@ enum BranchCommands {
     List {
         /// Repository slug.
         repo: String,
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get branch details.
     Get {
@@ -143,6 +230,13 @@ enum BranchCommands {
         /// Branch name.
         branch: String,
     },
+    /// Summarize a branch: head commit, latest pipeline, build statuses and open PRs into it.
+    Status {
+        /// Repository slug.
+        repo: String,
+        /// Branch name.
+        branch: String,
+    },
     /// Create a new branch.
     Create {
         /// Repository slug.
@@ -189,6 +283,57 @@ enum BranchCommands {
         /// Repository slug.
         repo: String,
     },
+    /// Merge one branch into another through a pull request.
+    Sync {
+        /// Repository slug.
+        repo: String,
+        /// Branch to merge from.
+        #[arg(long)]
+        from: String,
+        /// Branch to merge into.
+        #[arg(long)]
+        to: String,
+        /// Open the pull request without merging it.
+        #[arg(long)]
+        via_pr: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BranchingModelCommands {
+    /// Show development/production branches and branch type prefixes.
+    Get {
+        /// Repository slug.
+        #[arg(long)]
+        repo: String,
+    },
+    /// Update the branching model; options not given are left unchanged.
+    Set {
+        /// Repository slug.
+        #[arg(long)]
+        repo: String,
+        /// Development branch name.
+        #[arg(long, conflicts_with = "development_use_main")]
+        development: Option<String>,
+        /// Use the main branch as the development branch.
+        #[arg(long)]
+        development_use_main: bool,
+        /// Production branch name (enables the production branch).
+        #[arg(long, conflicts_with_all = ["production_use_main", "no_production"])]
+        production: Option<String>,
+        /// Use the main branch as the production branch.
+        #[arg(long, conflicts_with = "no_production")]
+        production_use_main: bool,
+        /// Disable the production branch.
+        #[arg(long)]
+        no_production: bool,
+        /// Branch type prefix as KIND=PREFIX, e.g. feature=feature/ (repeatable; kinds: feature, bugfix, release, hotfix).
+        #[arg(long = "prefix", value_name = "KIND=PREFIX")]
+        prefixes: Vec<String>,
+        /// Branch type to disable (repeatable).
+        #[arg(long = "disable-type", value_name = "KIND")]
+        disable_types: Vec<String>,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -199,14 +344,15 @@ enum PrCommands {
         repo: String,
         #[arg(long, default_value = "OPEN")]
         state: String,
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get pull request details.
     Get {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// Create a new pull request.
@@ -214,8 +360,8 @@ enum PrCommands {
         /// Repository slug.
         repo: String,
         /// PR title.
-        #[arg(long)]
-        title: String,
+        #[arg(long, required_unless_present = "link_jira")]
+        title: Option<String>,
         /// Source branch.
         #[arg(long)]
         source: String,
@@ -228,12 +374,16 @@ enum PrCommands {
         /// Reviewer UUIDs (comma-separated).
         #[arg(long, value_delimiter = ',')]
         reviewers: Vec<String>,
+        /// Prefix the title with "KEY: summary" and link the Jira issue named in the source branch.
+        #[arg(long)]
+        link_jira: bool,
     },
     /// Update pull request.
     Update {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
         /// New title.
         #[arg(long)]
@@ -246,7 +396,8 @@ enum PrCommands {
     Merge {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
         /// Merge strategy: merge_commit, squash, or fast_forward.
         #[arg(long)]
@@ -254,47 +405,112 @@ enum PrCommands {
         /// Merge commit message.
         #[arg(long)]
         message: Option<String>,
+        /// Wait for approvals, resolved tasks and green builds, then merge.
+        #[arg(long)]
+        when_ready: bool,
+        /// Give up waiting after this long (e.g. 30m, 2h).
+        #[arg(long, default_value = "2h", requires = "when_ready")]
+        timeout: String,
+        /// Poll interval in seconds.
+        #[arg(
+            long,
+            default_value_t = 30,
+            requires = "when_ready",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        interval: u64,
+        /// Approvals required before merging.
+        #[arg(long, default_value_t = 1, requires = "when_ready")]
+        approvals: usize,
+    },
+    /// Check whether a pull request can be merged; exits non-zero when blocked.
+    Checks {
+        /// Repository slug.
+        repo: String,
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
+        pr_id: i64,
+        /// Approvals required (defaults to the destination branch's restriction).
+        #[arg(long)]
+        required_approvals: Option<usize>,
     },
     /// Decline/close pull request.
     Decline {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// Approve pull request.
     Approve {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// Remove approval from pull request.
     Unapprove {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// View pull request diff.
     Diff {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
+        pr_id: i64,
+    },
+    /// Bundle metadata, diff, comments, approvals, activity and build statuses
+    /// into a zip for review records.
+    Export {
+        /// Repository slug.
+        repo: String,
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
+        pr_id: i64,
+        /// Archive path, e.g. pr-123.zip.
+        #[arg(long)]
+        output: std::path::PathBuf,
+    },
+    /// List files changed in a pull request with additions and deletions.
+    Files {
+        /// Repository slug.
+        repo: String,
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
+        pr_id: i64,
+        /// Print only the changed paths.
+        #[arg(long)]
+        name_only: bool,
+    },
+    /// Summarize lines and files changed in a pull request.
+    Diffstat {
+        /// Repository slug.
+        repo: String,
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// List pull request comments.
     Comments {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
     },
     /// Add comment to pull request.
     Comment {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
         /// Comment text.
         #[arg(long)]
@@ -304,7 +520,8 @@ enum PrCommands {
     Reviewers {
         /// Repository slug.
         repo: String,
-        /// Pull request ID.
+        /// Pull request ID or URL.
+        #[arg(value_parser = entity_url::pull_request_id)]
         pr_id: i64,
         /// Reviewer UUIDs (comma-separated).
         #[arg(long, value_delimiter = ',')]
@@ -316,8 +533,8 @@ enum PrCommands {
 enum WorkspaceCommands {
     /// List workspaces.
     List {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get workspace details.
     Get { slug: String },
@@ -327,8 +544,8 @@ enum WorkspaceCommands {
 enum ProjectCommands {
     /// List projects in workspace.
     List {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get project details.
     Get { key: String },
@@ -373,9 +590,8 @@ enum PipelineCommands {
     List {
         /// Repository slug.
         repo: String,
-        /// Maximum number of results.
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
         /// Sort field (prefix with - for desc): created_on, -created_on.
         #[arg(long)]
         sort: Option<String>,
@@ -385,9 +601,6 @@ enum PipelineCommands {
         /// Filter by branch name.
         #[arg(long)]
         branch: Option<String>,
-        /// Fetch all pages (ignores --limit).
-        #[arg(long)]
-        all: bool,
     },
     /// Get pipeline details.
     Get {
@@ -439,6 +652,98 @@ enum PipelineCommands {
         #[arg(long)]
         steps: bool,
     },
+    /// Validate a local pipelines configuration without pushing it.
+    Lint {
+        /// Path to the pipelines file.
+        #[arg(long, default_value = "bitbucket-pipelines.yml")]
+        file: std::path::PathBuf,
+    },
+    /// Pipeline cache operations.
+    #[command(subcommand)]
+    Caches(CacheCommands),
+    /// Pipeline artifact operations (files published to repository Downloads).
+    #[command(subcommand)]
+    Artifacts(ArtifactCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum CacheCommands {
+    /// List pipeline caches.
+    List {
+        /// Repository slug.
+        repo: String,
+    },
+    /// Delete a single pipeline cache.
+    Delete {
+        /// Repository slug.
+        repo: String,
+        /// Cache UUID.
+        uuid: String,
+    },
+    /// Delete all pipeline caches (or all caches with a given name).
+    Clear {
+        /// Repository slug.
+        repo: String,
+        /// Only clear caches with this name (e.g. node, pip).
+        #[arg(long)]
+        name: Option<String>,
+        /// Dry run mode.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ArtifactCommands {
+    /// List artifacts.
+    List {
+        /// Repository slug.
+        repo: String,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Download an artifact.
+    Download {
+        /// Repository slug.
+        repo: String,
+        /// Artifact file name.
+        name: String,
+        /// Output file path (defaults to the artifact name).
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum RunnerCommands {
+    /// List runners in the workspace (or a repository).
+    List {
+        /// Repository slug (lists repository runners instead of workspace runners).
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Register a runner and print the OAuth credentials needed to start it.
+    Create {
+        /// Runner name.
+        name: String,
+        /// Repository slug (creates a repository runner instead of a workspace runner).
+        #[arg(long)]
+        repo: Option<String>,
+        /// Runner labels (comma-separated, `self.hosted` is always added).
+        #[arg(long, value_delimiter = ',', default_value = "linux")]
+        labels: Vec<String>,
+    },
+    /// Delete a runner.
+    Delete {
+        /// Runner UUID.
+        uuid: String,
+        /// Repository slug (for repository runners).
+        #[arg(long)]
+        repo: Option<String>,
+        /// Skip confirmation prompt.
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -448,6 +753,8 @@ enum WebhookCommands {
         /// Repository slug.
         repo: String,
     },
+    /// List the event names accepted by --events.
+    Events,
     /// Create webhook.
     Create {
         /// Repository slug.
@@ -472,6 +779,32 @@ enum WebhookCommands {
         /// Webhook UUID.
         uuid: String,
     },
+    /// Register (or reuse) a webhook and run a script for each signed delivery,
+    /// served from a local HTTP listener.
+    Listen {
+        /// Repository slug.
+        #[arg(long)]
+        repo: String,
+        /// Public URL Bitbucket delivers to, e.g. a tunnel to the listen address.
+        #[arg(long)]
+        url: String,
+        /// Events (comma-separated).
+        #[arg(long, value_delimiter = ',', required = true)]
+        events: Vec<String>,
+        /// Program run per delivery; the payload arrives on stdin and the event
+        /// in BITBUCKET_EVENT.
+        #[arg(long)]
+        exec: std::path::PathBuf,
+        /// Webhook secret used to sign deliveries.
+        #[arg(long, env = "BITBUCKET_WEBHOOK_SECRET", hide_env_values = true)]
+        secret: String,
+        /// Local address to listen on.
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        listen: std::net::SocketAddr,
+        /// Keep a webhook created by this command when it exits.
+        #[arg(long)]
+        keep: bool,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -537,8 +870,8 @@ enum CommitCommands {
         /// Branch name.
         #[arg(long)]
         branch: Option<String>,
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get commit details.
     Get {
@@ -565,6 +898,45 @@ enum CommitCommands {
         #[arg(long)]
         path: Option<String>,
     },
+    /// Add comment to commit.
+    Comment {
+        /// Repository slug.
+        repo: String,
+        /// Commit hash.
+        hash: String,
+        /// Comment text.
+        #[arg(long)]
+        text: String,
+        /// File to comment on inline.
+        #[arg(long)]
+        file: Option<String>,
+        /// Line in the new version of the file.
+        #[arg(long, requires = "file")]
+        line: Option<u32>,
+    },
+    /// Approve commit.
+    Approve {
+        /// Repository slug.
+        repo: String,
+        /// Commit hash.
+        hash: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ReportCommands {
+    /// Flag repositories with an unprotected main branch, no default reviewers or no recent pushes.
+    Compliance {
+        /// Repository filter, e.g. project=PLAT or a raw query like language="rust".
+        #[arg(long)]
+        filter: Option<String>,
+        /// Days without a push to the main branch before a repository is stale.
+        #[arg(long, default_value_t = 180)]
+        stale_days: i64,
+        /// Number of repositories scanned in parallel.
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -574,6 +946,10 @@ enum BulkCommands {
         /// Days threshold for staleness.
         #[arg(long, default_value_t = 180)]
         days: i64,
+        /// Stale when not updated since this date (YYYY-MM-DD, or relative such as 6m or
+        /// '1 year ago'); replaces --days.
+        #[arg(long, value_parser = dates::parse_date, conflicts_with = "days")]
+        older_than: Option<chrono::NaiveDate>,
         /// Dry run mode.
         #[arg(long)]
         dry_run: bool,
@@ -589,11 +965,67 @@ enum BulkCommands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Clone or update every repository in the workspace with git.
+    Clone {
+        /// Repository filter, e.g. project=PLAT or a raw query like language="rust".
+        #[arg(long)]
+        filter: Option<String>,
+        /// Directory the repositories are checked out into.
+        #[arg(long, default_value = ".")]
+        dir: std::path::PathBuf,
+        /// Create bare mirror clones (updated with `git remote update`).
+        #[arg(long)]
+        mirror: bool,
+        /// Clone protocol (https or ssh).
+        #[arg(long, default_value = "https")]
+        protocol: String,
+        /// Number of git processes run in parallel.
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Dry run mode.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge each branch into the next one to propagate fixes, stopping at the first conflict.
+    Cascade {
+        /// Repository slug.
+        #[arg(long)]
+        repo: String,
+        /// Branches in merge order (comma-separated), e.g. release/1.x,release/2.x,main.
+        #[arg(long, value_delimiter = ',', required = true)]
+        branches: Vec<String>,
+        /// Dry run mode.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl BitbucketArgs {
+    /// Commands that only read local files and need no profile or workspace.
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self.command,
+            BitbucketCommands::Pipeline(PipelineCommands::Lint { .. })
+        )
+    }
 }
 
+/// Run a command for which [`BitbucketArgs::is_local`] is true.
+pub fn execute_local(args: BitbucketArgs, renderer: &OutputRenderer) -> Result<()> {
+    match args.command {
+        BitbucketCommands::Pipeline(PipelineCommands::Lint { file }) => {
+            pipeline_lint::lint_file(renderer, &file)
+        }
+        _ => Err(anyhow::anyhow!("Command needs a Bitbucket profile")),
+    }
+}
+
+/// `jira_client` talks to the profile's Atlassian site, for commands that pull
+/// in Jira data.
 pub async fn execute(
     args: BitbucketArgs,
     client: ApiClient,
+    jira_client: ApiClient,
     renderer: &OutputRenderer,
     inferred_workspace: Option<&str>,
 ) -> Result<()> {
@@ -619,7 +1051,9 @@ pub async fn execute(
 
     match args.command {
         BitbucketCommands::Repo(cmd) => match cmd {
-            RepoCommands::List { limit } => repos::list_repos(&ctx, &workspace, limit).await,
+            RepoCommands::List { tag, page } => {
+                repos::list_repos(&ctx, &workspace, &page, &tag).await
+            }
             RepoCommands::Get { slug } => repos::get_repo(&ctx, &workspace, &slug).await,
             RepoCommands::Create {
                 slug,
@@ -627,6 +1061,7 @@ pub async fn execute(
                 description,
                 private,
                 project,
+                if_not_exists,
             } => {
                 repos::create_repo(
                     &ctx,
@@ -636,6 +1071,7 @@ pub async fn execute(
                     description.as_deref(),
                     private,
                     project.as_deref(),
+                    if_not_exists,
                 )
                 .await
             }
@@ -644,6 +1080,7 @@ pub async fn execute(
                 name,
                 description,
                 language,
+                dry_run,
             } => {
                 repos::update_repo(
                     &ctx,
@@ -652,20 +1089,49 @@ pub async fn execute(
                     name.as_deref(),
                     description.as_deref(),
                     language.as_deref(),
+                    dry_run,
                 )
                 .await
             }
             RepoCommands::Delete { slug, force } => {
                 repos::delete_repo(&ctx, &workspace, &slug, force).await
             }
+            RepoCommands::Archive {
+                slug,
+                tombstone,
+                rename,
+                dry_run,
+            } => {
+                archive::archive_repo(
+                    &ctx,
+                    &workspace,
+                    &slug,
+                    tombstone.as_deref(),
+                    rename,
+                    dry_run,
+                )
+                .await
+            }
+            RepoCommands::Unarchive { slug, dry_run } => {
+                archive::unarchive_repo(&ctx, &workspace, &slug, dry_run).await
+            }
+            RepoCommands::Tag(RepoTagCommands::Add { slug, topics }) => {
+                tags::update_tags(&ctx, &workspace, &slug, &topics, &[]).await
+            }
+            RepoCommands::Tag(RepoTagCommands::Remove { slug, topics }) => {
+                tags::update_tags(&ctx, &workspace, &slug, &[], &topics).await
+            }
         },
         BitbucketCommands::Branch(cmd) => match cmd {
-            BranchCommands::List { repo, limit } => {
-                branches::list_branches(&ctx, &workspace, &repo, limit).await
+            BranchCommands::List { repo, page } => {
+                branches::list_branches(&ctx, &workspace, &repo, &page).await
             }
             BranchCommands::Get { repo, branch } => {
                 branches::get_branch(&ctx, &workspace, &repo, &branch).await
             }
+            BranchCommands::Status { repo, branch } => {
+                branch_status::branch_status(&ctx, &workspace, &repo, &branch).await
+            }
             BranchCommands::Create { repo, branch, from } => {
                 branches::create_branch(&ctx, &workspace, &repo, &branch, &from).await
             }
@@ -689,10 +1155,16 @@ pub async fn execute(
             BranchCommands::Restrictions { repo } => {
                 branches::list_restrictions(&ctx, &workspace, &repo).await
             }
+            BranchCommands::Sync {
+                repo,
+                from,
+                to,
+                via_pr,
+            } => sync::sync_branch(&ctx, &workspace, &repo, &from, &to, via_pr).await,
         },
         BitbucketCommands::Pr(cmd) => match cmd {
-            PrCommands::List { repo, state, limit } => {
-                pullrequests::list_pull_requests(&ctx, &workspace, &repo, &state, limit).await
+            PrCommands::List { repo, state, page } => {
+                pullrequests::list_pull_requests(&ctx, &workspace, &repo, &state, &page).await
             }
             PrCommands::Get { repo, pr_id } => {
                 pullrequests::get_pull_request(&ctx, &workspace, &repo, pr_id).await
@@ -704,7 +1176,20 @@ pub async fn execute(
                 destination,
                 description,
                 reviewers,
+                link_jira,
             } => {
+                let (title, description) = if link_jira {
+                    let (title, description) = pullrequests::link_jira_issue(
+                        &jira_client,
+                        &source,
+                        title.as_deref(),
+                        description.as_deref(),
+                    )
+                    .await?;
+                    (title, Some(description))
+                } else {
+                    (title.unwrap_or_default(), description)
+                };
                 pullrequests::create_pull_request(
                     &ctx,
                     &workspace,
@@ -733,21 +1218,48 @@ pub async fn execute(
                 )
                 .await
             }
+            PrCommands::Checks {
+                repo,
+                pr_id,
+                required_approvals,
+            } => {
+                pr_checks::check_pull_request(&ctx, &workspace, &repo, pr_id, required_approvals)
+                    .await
+            }
             PrCommands::Merge {
                 repo,
                 pr_id,
                 strategy,
                 message,
+                when_ready,
+                timeout,
+                interval,
+                approvals,
             } => {
-                pullrequests::merge_pull_request(
-                    &ctx,
-                    &workspace,
-                    &repo,
-                    pr_id,
-                    strategy.as_deref(),
-                    message.as_deref(),
-                )
-                .await
+                if when_ready {
+                    merge_watch::merge_when_ready(
+                        &ctx,
+                        &workspace,
+                        &repo,
+                        pr_id,
+                        strategy.as_deref(),
+                        message.as_deref(),
+                        approvals,
+                        cancel::parse_duration(&timeout)?,
+                        interval,
+                    )
+                    .await
+                } else {
+                    pullrequests::merge_pull_request(
+                        &ctx,
+                        &workspace,
+                        &repo,
+                        pr_id,
+                        strategy.as_deref(),
+                        message.as_deref(),
+                    )
+                    .await
+                }
             }
             PrCommands::Decline { repo, pr_id } => {
                 pullrequests::decline_pull_request(&ctx, &workspace, &repo, pr_id).await
@@ -761,6 +1273,19 @@ pub async fn execute(
             PrCommands::Diff { repo, pr_id } => {
                 pullrequests::get_pr_diff(&ctx, &workspace, &repo, pr_id).await
             }
+            PrCommands::Export {
+                repo,
+                pr_id,
+                output,
+            } => pr_export::export_pull_request(&ctx, &workspace, &repo, pr_id, &output).await,
+            PrCommands::Files {
+                repo,
+                pr_id,
+                name_only,
+            } => diffstat::list_pr_files(&ctx, &workspace, &repo, pr_id, name_only).await,
+            PrCommands::Diffstat { repo, pr_id } => {
+                diffstat::pr_diffstat(&ctx, &workspace, &repo, pr_id).await
+            }
             PrCommands::Comments { repo, pr_id } => {
                 pullrequests::list_pr_comments(&ctx, &workspace, &repo, pr_id).await
             }
@@ -772,12 +1297,12 @@ pub async fn execute(
             }
         },
         BitbucketCommands::Workspace(cmd) => match cmd {
-            WorkspaceCommands::List { limit } => workspaces::list_workspaces(&ctx, limit).await,
+            WorkspaceCommands::List { page } => workspaces::list_workspaces(&ctx, &page).await,
             WorkspaceCommands::Get { slug } => workspaces::get_workspace(&ctx, &slug).await,
         },
         BitbucketCommands::Project(cmd) => match cmd {
-            ProjectCommands::List { limit } => {
-                workspaces::list_projects(&ctx, &workspace, limit).await
+            ProjectCommands::List { page } => {
+                workspaces::list_projects(&ctx, &workspace, &page).await
             }
             ProjectCommands::Get { key } => workspaces::get_project(&ctx, &workspace, &key).await,
             ProjectCommands::Create {
@@ -817,21 +1342,19 @@ pub async fn execute(
         BitbucketCommands::Pipeline(cmd) => match cmd {
             PipelineCommands::List {
                 repo,
-                limit,
+                page,
                 sort,
                 recent,
                 branch,
-                all,
             } => {
                 pipelines::list_pipelines(
                     &ctx,
                     &workspace,
                     &repo,
-                    limit,
+                    &page,
                     sort.as_deref(),
                     recent,
                     branch.as_deref(),
-                    all,
                 )
                 .await
             }
@@ -860,11 +1383,50 @@ pub async fn execute(
                 interval,
                 steps,
             } => pipelines::watch_pipeline(&ctx, &workspace, &repo, &uuid, interval, steps).await,
+            PipelineCommands::Lint { file } => pipeline_lint::lint_file(renderer, &file),
+            PipelineCommands::Caches(cmd) => match cmd {
+                CacheCommands::List { repo } => {
+                    artifacts::list_caches(&ctx, &workspace, &repo).await
+                }
+                CacheCommands::Delete { repo, uuid } => {
+                    artifacts::delete_cache(&ctx, &workspace, &repo, &uuid).await
+                }
+                CacheCommands::Clear {
+                    repo,
+                    name,
+                    dry_run,
+                } => {
+                    artifacts::clear_caches(&ctx, &workspace, &repo, name.as_deref(), dry_run).await
+                }
+            },
+            PipelineCommands::Artifacts(cmd) => match cmd {
+                ArtifactCommands::List { repo, page } => {
+                    artifacts::list_artifacts(&ctx, &workspace, &repo, &page).await
+                }
+                ArtifactCommands::Download { repo, name, output } => {
+                    artifacts::download_artifact(&ctx, &workspace, &repo, &name, output.as_deref())
+                        .await
+                }
+            },
+        },
+        BitbucketCommands::Runner(cmd) => match cmd {
+            RunnerCommands::List { repo } => {
+                runners::list_runners(&ctx, &workspace, repo.as_deref()).await
+            }
+            RunnerCommands::Create { name, repo, labels } => {
+                runners::create_runner(&ctx, &workspace, repo.as_deref(), &name, &labels).await
+            }
+            RunnerCommands::Delete { uuid, repo, force } => {
+                runners::delete_runner(&ctx, &workspace, repo.as_deref(), &uuid, force).await
+            }
         },
         BitbucketCommands::Webhook(cmd) => match cmd {
             WebhookCommands::List { repo } => {
                 webhooks::list_webhooks(&ctx, &workspace, &repo).await
             }
+            WebhookCommands::Events => {
+                webhook_events::render_events(ctx.renderer, webhook_events::BITBUCKET_EVENTS)
+            }
             WebhookCommands::Create {
                 repo,
                 url,
@@ -886,6 +1448,20 @@ pub async fn execute(
             WebhookCommands::Delete { repo, uuid } => {
                 webhooks::delete_webhook(&ctx, &workspace, &repo, &uuid).await
             }
+            WebhookCommands::Listen {
+                repo,
+                url,
+                events,
+                exec,
+                secret,
+                listen,
+                keep,
+            } => {
+                webhook_listen::listen(
+                    &ctx, &workspace, &repo, &url, events, &exec, &secret, listen, keep,
+                )
+                .await
+            }
         },
         BitbucketCommands::SshKey(cmd) => match cmd {
             SshKeyCommands::List { repo } => webhooks::list_ssh_keys(&ctx, &workspace, &repo).await,
@@ -912,12 +1488,36 @@ pub async fn execute(
                 permissions::revoke_repo_permission(&ctx, &workspace, &repo, &user_uuid).await
             }
         },
-        BitbucketCommands::Commit(cmd) => match cmd {
-            CommitCommands::List {
+        BitbucketCommands::BranchingModel(cmd) => match cmd {
+            BranchingModelCommands::Get { repo } => {
+                branching_model::get_branching_model(&ctx, &workspace, &repo).await
+            }
+            BranchingModelCommands::Set {
                 repo,
-                branch,
-                limit,
-            } => commits::list_commits(&ctx, &workspace, &repo, branch.as_deref(), limit).await,
+                development,
+                development_use_main,
+                production,
+                production_use_main,
+                no_production,
+                prefixes,
+                disable_types,
+            } => {
+                let update = branching_model::ModelUpdate {
+                    development,
+                    development_use_main,
+                    production,
+                    production_use_main,
+                    no_production,
+                    prefixes,
+                    disable_types,
+                };
+                branching_model::set_branching_model(&ctx, &workspace, &repo, &update).await
+            }
+        },
+        BitbucketCommands::Commit(cmd) => match cmd {
+            CommitCommands::List { repo, branch, page } => {
+                commits::list_commits(&ctx, &workspace, &repo, branch.as_deref(), &page).await
+            }
             CommitCommands::Get { repo, hash } => {
                 commits::get_commit(&ctx, &workspace, &repo, &hash).await
             }
@@ -927,9 +1527,53 @@ pub async fn execute(
             CommitCommands::Browse { repo, commit, path } => {
                 commits::browse_source(&ctx, &workspace, &repo, &commit, path.as_deref()).await
             }
+            CommitCommands::Comment {
+                repo,
+                hash,
+                text,
+                file,
+                line,
+            } => {
+                commits::add_commit_comment(
+                    &ctx,
+                    &workspace,
+                    &repo,
+                    &hash,
+                    &text,
+                    file.as_deref(),
+                    line,
+                )
+                .await
+            }
+            CommitCommands::Approve { repo, hash } => {
+                commits::approve_commit(&ctx, &workspace, &repo, &hash).await
+            }
+        },
+        BitbucketCommands::Report(cmd) => match cmd {
+            ReportCommands::Compliance {
+                filter,
+                stale_days,
+                concurrency,
+            } => {
+                compliance::compliance_report(
+                    &ctx,
+                    &workspace,
+                    filter.as_deref(),
+                    stale_days,
+                    concurrency,
+                )
+                .await
+            }
         },
         BitbucketCommands::Bulk(cmd) => match cmd {
-            BulkCommands::ArchiveRepos { days, dry_run } => {
+            BulkCommands::ArchiveRepos {
+                days,
+                older_than,
+                dry_run,
+            } => {
+                let days = older_than.map_or(days, |date| {
+                    (chrono::Local::now().date_naive() - date).num_days()
+                });
                 bulk::archive_stale_repos(&ctx, &workspace, days, dry_run).await
             }
             BulkCommands::DeleteBranches {
@@ -937,7 +1581,74 @@ pub async fn execute(
                 exclude,
                 dry_run,
             } => bulk::delete_merged_branches(&ctx, &workspace, &repo, exclude, dry_run).await,
+            BulkCommands::Clone {
+                filter,
+                dir,
+                mirror,
+                protocol,
+                concurrency,
+                dry_run,
+            } => {
+                clone::bulk_clone(
+                    &ctx,
+                    &workspace,
+                    filter.as_deref(),
+                    &dir,
+                    mirror,
+                    &protocol,
+                    concurrency,
+                    dry_run,
+                )
+                .await
+            }
+            BulkCommands::Cascade {
+                repo,
+                branches,
+                dry_run,
+            } => sync::cascade(&ctx, &workspace, &repo, &branches, dry_run).await,
         },
         BitbucketCommands::Whoami => unreachable!("handled above"),
     }
 }
+
+/// Fan a read-only Bitbucket command out across several profiles and render the merged rows.
+///
+/// Each profile uses its own configured workspace unless `--workspace` is given explicitly.
+pub async fn execute_multi_profile(
+    args: BitbucketArgs,
+    targets: Vec<ProfileTarget>,
+    renderer: &OutputRenderer,
+) -> Result<()> {
+    let (tags, page) = match args.command {
+        BitbucketCommands::Repo(RepoCommands::List { tag, page }) => (tag, page),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "--profiles/--all-profiles is only supported for `bitbucket repo list`"
+            ))
+        }
+    };
+    let (tags, page) = (&tags, &page);
+    let explicit_workspace = args.workspace.as_deref();
+
+    let rows = multi_profile::fan_out(&targets, |target| {
+        let ctx = BitbucketContext {
+            client: target.client.clone(),
+            renderer,
+        };
+        let workspace = explicit_workspace.or(target.workspace.as_deref());
+        async move {
+            let workspace = workspace.ok_or_else(|| {
+                anyhow::anyhow!("Workspace required for profile '{}'", target.name)
+            })?;
+            repos::fetch_repo_rows(&ctx, workspace, page, tags).await
+        }
+    })
+    .await?;
+
+    if rows.is_empty() {
+        tracing::info!("No repositories returned for any profile; check permissions.");
+        return Ok(());
+    }
+
+    renderer.render_with_id(&rows, "slug")
+}
@@ -0,0 +1,562 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::OutputRenderer;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+// Offline validation of bitbucket-pipelines.yml. Covers the structural rules
+// Bitbucket rejects most often; anything it cannot judge is left to the server.
+
+const TOP_LEVEL_KEYS: &[&str] = &[
+    "image",
+    "clone",
+    "options",
+    "definitions",
+    "pipelines",
+    "labels",
+    "export",
+];
+const PIPELINE_SECTIONS: &[&str] = &[
+    "default",
+    "branches",
+    "tags",
+    "bookmarks",
+    "pull-requests",
+    "custom",
+];
+const STEP_KEYS: &[&str] = &[
+    "name",
+    "script",
+    "after-script",
+    "image",
+    "caches",
+    "services",
+    "artifacts",
+    "size",
+    "max-time",
+    "trigger",
+    "deployment",
+    "condition",
+    "clone",
+    "runs-on",
+    "oidc",
+    "fail-fast",
+    "output-variables",
+];
+const BUILTIN_CACHES: &[&str] = &[
+    "docker",
+    "composer",
+    "dotnetcore",
+    "gradle",
+    "ivy2",
+    "maven",
+    "node",
+    "pip",
+    "sbt",
+];
+const STEP_SIZES: &[&str] = &["1x", "2x", "4x", "8x", "16x", "32x"];
+const MAX_TIME_MINUTES: u64 = 720;
+const MAX_SERVICES: usize = 5;
+const MAX_STEPS: usize = 100;
+const BUILD_CONTAINER_MEMORY: u64 = 1024;
+const DEFAULT_SERVICE_MEMORY: u64 = 1024;
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Finding {
+    severity: &'static str,
+    location: String,
+    message: String,
+}
+
+pub fn lint_file(renderer: &OutputRenderer, file: &Path) -> Result<()> {
+    let text = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let findings = lint(&text);
+    let errors = findings.iter().filter(|f| f.severity == "error").count();
+
+    if findings.is_empty() {
+        println!("✓ {} is valid", file.display());
+        return Ok(());
+    }
+    renderer.render(&findings)?;
+    if errors > 0 {
+        bail!("{} has {errors} error(s)", file.display());
+    }
+    println!(
+        "✓ {} is valid ({} warning(s))",
+        file.display(),
+        findings.len()
+    );
+    Ok(())
+}
+
+fn lint(text: &str) -> Vec<Finding> {
+    let mut linter = Linter::default();
+    let mut doc: Value = match serde_yaml::from_str(text) {
+        Ok(doc) => doc,
+        Err(err) => {
+            linter.error("file", format!("Invalid YAML: {err}"));
+            return linter.findings;
+        }
+    };
+    if let Err(err) = doc.apply_merge() {
+        linter.error("file", format!("Invalid YAML merge key: {err}"));
+        return linter.findings;
+    }
+    linter.document(&doc);
+    linter.findings
+}
+
+#[derive(Default)]
+struct Linter {
+    findings: Vec<Finding>,
+    caches: HashSet<String>,
+    services: Vec<(String, u64)>,
+}
+
+impl Linter {
+    fn error(&mut self, location: &str, message: impl Into<String>) {
+        self.push("error", location, message.into());
+    }
+
+    fn warning(&mut self, location: &str, message: impl Into<String>) {
+        self.push("warning", location, message.into());
+    }
+
+    fn push(&mut self, severity: &'static str, location: &str, message: String) {
+        self.findings.push(Finding {
+            severity,
+            location: location.to_string(),
+            message,
+        });
+    }
+
+    fn document(&mut self, doc: &Value) {
+        let Some(root) = doc.as_mapping() else {
+            self.error("file", "Top level must be a mapping");
+            return;
+        };
+        for key in keys(root) {
+            if !TOP_LEVEL_KEYS.contains(&key.as_str()) {
+                self.warning(&key, "Unknown top-level key");
+            }
+        }
+
+        if let Some(options) = root.get("options") {
+            self.size_and_time(options, "options");
+        }
+        if let Some(definitions) = root.get("definitions") {
+            self.definitions(definitions);
+        }
+
+        match root.get("pipelines").map(|p| (p, p.as_mapping())) {
+            None => self.error("pipelines", "Missing required 'pipelines' section"),
+            Some((_, None)) => self.error("pipelines", "'pipelines' must be a mapping"),
+            Some((_, Some(pipelines))) => self.pipelines(pipelines),
+        }
+    }
+
+    fn definitions(&mut self, definitions: &Value) {
+        if let Some(caches) = definitions.get("caches").and_then(Value::as_mapping) {
+            self.caches.extend(keys(caches));
+        }
+        let Some(services) = definitions.get("services").and_then(Value::as_mapping) else {
+            return;
+        };
+        for (name, service) in services {
+            let name = key_text(name);
+            let location = format!("definitions.services.{name}");
+            if name != "docker" && service.get("image").is_none() {
+                self.error(&location, "Service must define an 'image'");
+            }
+            let memory = match service.get("memory") {
+                None => DEFAULT_SERVICE_MEMORY,
+                Some(memory) => match memory.as_u64() {
+                    Some(memory) if memory >= 128 => memory,
+                    _ => {
+                        self.error(&location, "'memory' must be a number of MB, at least 128");
+                        DEFAULT_SERVICE_MEMORY
+                    }
+                },
+            };
+            self.services.push((name, memory));
+        }
+    }
+
+    fn pipelines(&mut self, pipelines: &Mapping) {
+        for (section, value) in pipelines {
+            let section = key_text(section);
+            let location = format!("pipelines.{section}");
+            match section.as_str() {
+                "default" => self.pipeline(value, &location, false),
+                _ if PIPELINE_SECTIONS.contains(&section.as_str()) => {
+                    let Some(named) = value.as_mapping() else {
+                        self.error(&location, "Must map patterns or names to pipelines");
+                        continue;
+                    };
+                    for (name, pipeline) in named {
+                        let location = format!("{location}.{}", key_text(name));
+                        self.pipeline(pipeline, &location, section == "custom");
+                    }
+                }
+                _ => self.error(&location, "Unknown pipeline section"),
+            }
+        }
+    }
+
+    fn pipeline(&mut self, pipeline: &Value, location: &str, custom: bool) {
+        let Some(items) = pipeline.as_sequence() else {
+            self.error(location, "Pipeline must be a list of steps");
+            return;
+        };
+        if items.is_empty() {
+            self.error(location, "Pipeline has no steps");
+            return;
+        }
+
+        let mut steps = 0;
+        for (index, item) in items.iter().enumerate() {
+            let location = format!("{location}[{index}]");
+            let Some((kind, body)) = single_entry(item) else {
+                self.error(
+                    &location,
+                    "Expected exactly one of 'step', 'parallel', 'stage' or 'variables'",
+                );
+                continue;
+            };
+            match kind.as_str() {
+                "step" => {
+                    steps += 1;
+                    self.step(body, &format!("{location}.step"), index == 0);
+                }
+                "parallel" => steps += self.parallel(body, &format!("{location}.parallel")),
+                "stage" => steps += self.stage(body, &format!("{location}.stage"), index == 0),
+                "variables" if custom && index == 0 => {}
+                "variables" => self.error(
+                    &location,
+                    "'variables' is only allowed as the first item of a custom pipeline",
+                ),
+                "import" => {}
+                other => self.error(&location, format!("Unknown pipeline item '{other}'")),
+            }
+        }
+
+        if steps > MAX_STEPS {
+            self.error(
+                location,
+                format!("Pipeline has {steps} steps; the limit is {MAX_STEPS}"),
+            );
+        }
+    }
+
+    fn parallel(&mut self, parallel: &Value, location: &str) -> usize {
+        let items = match parallel {
+            Value::Sequence(items) => items,
+            Value::Mapping(map) => match map.get("steps").and_then(Value::as_sequence) {
+                Some(items) => items,
+                None => {
+                    self.error(location, "'parallel' must list its 'steps'");
+                    return 0;
+                }
+            },
+            _ => {
+                self.error(location, "'parallel' must be a list of steps");
+                return 0;
+            }
+        };
+        if items.len() < 2 {
+            self.warning(
+                location,
+                "'parallel' with fewer than two steps runs serially",
+            );
+        }
+        self.nested_steps(items, location, false)
+    }
+
+    fn stage(&mut self, stage: &Value, location: &str, first: bool) -> usize {
+        match stage.get("steps").and_then(Value::as_sequence) {
+            Some(items) => self.nested_steps(items, location, first),
+            None => {
+                self.error(location, "'stage' must list its 'steps'");
+                0
+            }
+        }
+    }
+
+    fn nested_steps(&mut self, items: &[Value], location: &str, first: bool) -> usize {
+        for (index, item) in items.iter().enumerate() {
+            let location = format!("{location}[{index}]");
+            match item.get("step") {
+                Some(step) => self.step(step, &format!("{location}.step"), first && index == 0),
+                None => self.error(&location, "Expected a 'step'"),
+            }
+        }
+        items.len()
+    }
+
+    fn step(&mut self, step: &Value, location: &str, first: bool) {
+        let Some(map) = step.as_mapping() else {
+            self.error(location, "Step must be a mapping");
+            return;
+        };
+        for key in keys(map) {
+            if !STEP_KEYS.contains(&key.as_str()) {
+                self.warning(&format!("{location}.{key}"), "Unknown step key");
+            }
+        }
+
+        match map.get("script").and_then(Value::as_sequence) {
+            Some(script) if !script.is_empty() => {}
+            Some(_) => self.error(location, "'script' must not be empty"),
+            None => self.error(location, "Step must have a 'script' list"),
+        }
+
+        match map.get("trigger").and_then(Value::as_str) {
+            None | Some("automatic") => {}
+            Some("manual") if first => {
+                self.error(location, "The first step of a pipeline cannot be manual")
+            }
+            Some("manual") => {}
+            Some(other) => self.error(
+                location,
+                format!("Invalid trigger '{other}'. Must be one of: automatic, manual"),
+            ),
+        }
+
+        self.size_and_time(step, location);
+
+        for cache in names(map.get("caches")) {
+            if !BUILTIN_CACHES.contains(&cache.as_str()) && !self.caches.contains(&cache) {
+                self.error(
+                    location,
+                    format!(
+                        "Cache '{cache}' is not predefined or declared under definitions.caches"
+                    ),
+                );
+            }
+        }
+
+        let services = names(map.get("services"));
+        if services.len() > MAX_SERVICES {
+            self.error(
+                location,
+                format!(
+                    "Step uses {} services; the limit is {MAX_SERVICES}",
+                    services.len()
+                ),
+            );
+        }
+        let mut memory = 0;
+        for service in &services {
+            match self.services.iter().find(|(name, _)| name == service) {
+                Some((_, mb)) => memory += mb,
+                None if service == "docker" => memory += DEFAULT_SERVICE_MEMORY,
+                None => self.error(
+                    location,
+                    format!("Service '{service}' is not declared under definitions.services"),
+                ),
+            }
+        }
+        let size = map.get("size").and_then(Value::as_str).unwrap_or("1x");
+        if let Some(available) = step_memory(size) {
+            if memory + BUILD_CONTAINER_MEMORY > available {
+                self.error(
+                    location,
+                    format!(
+                        "Services need {memory} MB but a {size} step leaves {} MB for them",
+                        available - BUILD_CONTAINER_MEMORY
+                    ),
+                );
+            }
+        }
+    }
+
+    fn size_and_time(&mut self, value: &Value, location: &str) {
+        if let Some(size) = value.get("size") {
+            let valid = size.as_str().is_some_and(|s| STEP_SIZES.contains(&s));
+            if !valid {
+                self.error(
+                    location,
+                    format!(
+                        "Invalid size {}. Must be one of: {}",
+                        scalar_text(size),
+                        STEP_SIZES.join(", ")
+                    ),
+                );
+            }
+        }
+        if let Some(max_time) = value.get("max-time") {
+            match max_time.as_u64() {
+                Some(minutes) if (1..=MAX_TIME_MINUTES).contains(&minutes) => {}
+                _ => self.error(
+                    location,
+                    format!("'max-time' must be between 1 and {MAX_TIME_MINUTES} minutes"),
+                ),
+            }
+        }
+    }
+}
+
+/// Memory in MB available to a step of the given size.
+fn step_memory(size: &str) -> Option<u64> {
+    let factor: u64 = size.strip_suffix('x')?.parse().ok()?;
+    Some(factor * 4096)
+}
+
+fn single_entry(item: &Value) -> Option<(String, &Value)> {
+    let map = item.as_mapping()?;
+    if map.len() != 1 {
+        return None;
+    }
+    map.iter().next().map(|(k, v)| (key_text(k), v))
+}
+
+fn keys(map: &Mapping) -> Vec<String> {
+    map.keys().map(key_text).collect()
+}
+
+fn key_text(key: &Value) -> String {
+    scalar_text(key)
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+fn names(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_sequence)
+        .map(|items| items.iter().map(scalar_text).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(text: &str) -> Vec<String> {
+        lint(text)
+            .into_iter()
+            .map(|f| format!("{} {}: {}", f.severity, f.location, f.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config() {
+        let text = r#"
+image: rust:1.80
+definitions:
+  caches:
+    cargo: ~/.cargo
+  services:
+    postgres:
+      image: postgres:16
+      memory: 2048
+  steps:
+    - step: &test
+        name: Test
+        caches: [cargo]
+        services: [postgres]
+        size: 2x
+        script:
+          - cargo test
+pipelines:
+  default:
+    - step: *test
+  branches:
+    main:
+      - parallel:
+          - step:
+              script: [cargo clippy]
+          - step:
+              script: [cargo fmt --check]
+      - step:
+          trigger: manual
+          deployment: production
+          script: [./deploy.sh]
+  custom:
+    release:
+      - variables:
+          - name: VERSION
+      - step:
+          script: [./release.sh]
+"#;
+        assert_eq!(messages(text), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_common_errors() {
+        let text = r#"
+definitions:
+  services:
+    redis: {}
+pipelines:
+  default:
+    - step:
+        trigger: manual
+        size: 3x
+        max-time: 900
+        caches: [cargo]
+        services: [redis, mysql]
+        script: []
+  pull-requests:
+    '**':
+      - stage:
+          name: missing steps
+  nightly:
+    - step:
+        script: [echo]
+"#;
+        assert_eq!(
+            messages(text),
+            vec![
+                "error definitions.services.redis: Service must define an 'image'",
+                "error pipelines.default[0].step: 'script' must not be empty",
+                "error pipelines.default[0].step: The first step of a pipeline cannot be manual",
+                "error pipelines.default[0].step: Invalid size 3x. Must be one of: 1x, 2x, 4x, 8x, 16x, 32x",
+                "error pipelines.default[0].step: 'max-time' must be between 1 and 720 minutes",
+                "error pipelines.default[0].step: Cache 'cargo' is not predefined or declared under definitions.caches",
+                "error pipelines.default[0].step: Service 'mysql' is not declared under definitions.services",
+                "error pipelines.pull-requests.**[0].stage: 'stage' must list its 'steps'",
+                "error pipelines.nightly: Unknown pipeline section",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limits_and_structure() {
+        let services: String = (1..=6)
+            .map(|i| format!("    s{i}:\n      image: busybox\n      memory: 128\n"))
+            .collect();
+        let text = format!(
+            "definitions:\n  services:\n{services}pipelines:\n  default:\n    - step:\n        services: [s1, s2, s3, s4, s5, s6]\n        script: [echo]\n    - variables: []\n"
+        );
+        assert_eq!(
+            messages(&text),
+            vec![
+                "error pipelines.default[0].step: Step uses 6 services; the limit is 5",
+                "error pipelines.default[1]: 'variables' is only allowed as the first item of a custom pipeline",
+            ]
+        );
+
+        let heavy = "definitions:\n  services:\n    db:\n      image: postgres\n      memory: 3500\npipelines:\n  default:\n    - step:\n        services: [db]\n        script: [echo]\n";
+        assert_eq!(
+            messages(heavy),
+            vec!["error pipelines.default[0].step: Services need 3500 MB but a 1x step leaves 3072 MB for them"]
+        );
+
+        assert!(messages("pipelines: [")[0].starts_with("error file: Invalid YAML"));
+        assert_eq!(
+            messages("image: rust\n"),
+            vec!["error pipelines: Missing required 'pipelines' section"]
+        );
+    }
+}
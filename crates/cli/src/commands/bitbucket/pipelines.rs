@@ -1,11 +1,13 @@
 use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use atlassian_cli_output::OutputFormat;
 use serde::{Deserialize, Serialize};
 use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
+use super::utils::{BitbucketContext, LIST_LIMIT, MAX_PAGELEN};
+use crate::commands::cancel;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 
 // ============================================================================
 // API Response Structs
@@ -171,7 +173,7 @@ fn format_steps_summary(steps: &[StepInfo]) -> String {
         .join(" | ")
 }
 
-fn format_elapsed(start: Instant) -> String {
+pub(super) fn format_elapsed(start: Instant) -> String {
     let elapsed = start.elapsed();
     let secs = elapsed.as_secs();
     let mins = secs / 60;
@@ -355,70 +357,51 @@ async fn fetch_pipeline(
 // Command Implementations
 // ============================================================================
 
-#[allow(clippy::too_many_arguments)]
 pub async fn list_pipelines(
     ctx: &BitbucketContext<'_>,
     workspace: &str,
     repo_slug: &str,
-    limit: usize,
+    page: &PaginationArgs,
     sort: Option<&str>,
     recent: Option<usize>,
     branch: Option<&str>,
-    fetch_all: bool,
 ) -> Result<()> {
     // Handle --recent shorthand
-    let (effective_limit, effective_sort) = if let Some(n) = recent {
-        (n, "-created_on")
+    let (page, effective_sort) = if let Some(n) = recent {
+        let recent_page = PaginationArgs {
+            limit: Some(n),
+            all: false,
+            ..page.clone()
+        };
+        (recent_page, "-created_on")
     } else {
-        (limit, sort.unwrap_or("-created_on"))
+        (page.clone(), sort.unwrap_or("-created_on"))
     };
 
     // Validate sort field
     validate_sort_field(effective_sort)?;
 
-    // max_items: None = unlimited (--all or --limit 0), Some(n) = cap at n
-    let max_items: Option<usize> = if fetch_all || effective_limit == 0 {
-        None
-    } else {
-        Some(effective_limit)
-    };
-
-    let mut all_pipelines: Vec<Pipeline> = Vec::new();
-    let mut next_url: Option<String> = None;
-    let page_size = 100; // Max allowed by Bitbucket API
-
-    loop {
-        let path = build_request_path(
-            &next_url,
-            workspace,
-            repo_slug,
-            page_size,
-            effective_sort,
-            branch,
-        );
-
-        let response: PipelineList = ctx
-            .client
-            .get(&path)
-            .await
-            .with_context(|| format!("Failed to list pipelines for {workspace}/{repo_slug}"))?;
-
-        all_pipelines.extend(response.values);
-        next_url = response.next;
-
-        // Stop if: no more pages OR reached limit (when not unlimited)
-        let reached_limit = max_items.map(|m| all_pipelines.len() >= m).unwrap_or(false);
-        if next_url.is_none() || reached_limit {
-            break;
-        }
-    }
-
-    // Truncate to exact limit
-    if let Some(max) = max_items {
-        if all_pipelines.len() > max {
-            all_pipelines.truncate(max);
-        }
-    }
+    let all_pipelines: Vec<Pipeline> = page
+        .collect(LIST_LIMIT, MAX_PAGELEN, Paging::Offset, |request| {
+            let mut path = build_request_path(
+                &request.cursor,
+                workspace,
+                repo_slug,
+                request.size,
+                effective_sort,
+                branch,
+            );
+            if request.cursor.is_none() && request.start > 0 {
+                path.push_str(&format!("&page={}", request.number()));
+            }
+            async move {
+                let response: PipelineList = ctx.client.get(&path).await.with_context(|| {
+                    format!("Failed to list pipelines for {workspace}/{repo_slug}")
+                })?;
+                Ok(Page::cursor(response.values, response.next))
+            }
+        })
+        .await?;
 
     let rows: Vec<PipelineRow> = all_pipelines
         .iter()
@@ -726,7 +709,17 @@ pub async fn watch_pipeline(
             break;
         }
 
-        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if !cancel::sleep_unless_cancelled(Duration::from_secs(interval)).await {
+            if is_table {
+                println!();
+            }
+            bail!(
+                "Stopped watching pipeline {} after {} ({}); last status: {status}",
+                pipeline_id,
+                format_elapsed(start),
+                cancel::reason()
+            );
+        }
     }
 
     Ok(())
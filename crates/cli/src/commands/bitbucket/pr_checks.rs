@@ -0,0 +1,623 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::compliance::glob_matches;
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
+
+// Merge readiness preflight: the gates Bitbucket would apply when merging a
+// pull request (approvals, default reviewers, tasks, builds, who may merge,
+// conflicts), evaluated up front. Any failing check exits non-zero so the
+// command can gate a CI step
+
+#[derive(Deserialize)]
+struct PullRequest {
+    state: String,
+    destination: Endpoint,
+    #[serde(default)]
+    participants: Vec<Participant>,
+}
+
+#[derive(Deserialize)]
+struct Endpoint {
+    branch: BranchName,
+}
+
+#[derive(Deserialize)]
+struct BranchName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Participant {
+    #[serde(default)]
+    approved: bool,
+    /// `approved`, `changes_requested` or null.
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    user: Option<User>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    #[serde(default)]
+    uuid: String,
+    #[serde(default)]
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Task {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct BuildStatus {
+    state: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Restriction {
+    kind: String,
+    #[serde(default)]
+    branch_match_kind: Option<String>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    branch_type: Option<String>,
+    #[serde(default)]
+    value: Option<usize>,
+    #[serde(default)]
+    users: Vec<User>,
+    #[serde(default)]
+    groups: Vec<Group>,
+}
+
+#[derive(Deserialize)]
+struct Group {
+    #[serde(default)]
+    slug: String,
+}
+
+#[derive(Deserialize)]
+struct DiffEntry {
+    status: String,
+    #[serde(default)]
+    new: Option<DiffPath>,
+    #[serde(default)]
+    old: Option<DiffPath>,
+}
+
+#[derive(Deserialize)]
+struct DiffPath {
+    path: String,
+}
+
+/// The repository's effective branching model, for restrictions on branch types.
+#[derive(Deserialize, Default)]
+struct BranchingModel {
+    #[serde(default)]
+    development: Option<ModelBranch>,
+    #[serde(default)]
+    production: Option<ModelBranch>,
+    #[serde(default)]
+    branch_types: Vec<ModelBranchType>,
+}
+
+#[derive(Deserialize)]
+struct ModelBranch {
+    #[serde(default)]
+    branch: Option<BranchName>,
+}
+
+#[derive(Deserialize)]
+struct ModelBranchType {
+    kind: String,
+    #[serde(default)]
+    prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct CheckRow {
+    check: &'static str,
+    status: Status,
+    detail: String,
+}
+
+impl CheckRow {
+    fn new(check: &'static str, status: Status, detail: String) -> Self {
+        Self {
+            check,
+            status,
+            detail,
+        }
+    }
+}
+
+/// Everything the checks are evaluated against.
+#[derive(Default)]
+struct Snapshot {
+    tasks: Vec<Task>,
+    statuses: Vec<BuildStatus>,
+    /// Restrictions that apply to the destination branch.
+    restrictions: Vec<Restriction>,
+    default_reviewers: Vec<String>,
+    diffstat: Vec<DiffEntry>,
+    /// The caller, when a merge restriction names who may merge.
+    current_user: Option<String>,
+}
+
+pub async fn check_pull_request(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+    required_approvals: Option<usize>,
+) -> Result<()> {
+    let repo = format!("/2.0/repositories/{workspace}/{repo_slug}");
+    let base = format!("{repo}/pullrequests/{pr_id}");
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+
+    let pr: PullRequest = ctx.client.get(&base).await.with_context(|| {
+        format!("Failed to get pull request {pr_id} in {workspace}/{repo_slug}")
+    })?;
+    let destination = pr.destination.branch.name.as_str();
+
+    let restrictions: Vec<Restriction> = fetch_paged(
+        ctx,
+        &format!("{repo}/branch-restrictions"),
+        &everything,
+        Paging::Cursor,
+        &format!("branch restrictions for {workspace}/{repo_slug}"),
+    )
+    .await?;
+    let model = if restrictions
+        .iter()
+        .any(|r| r.branch_match_kind.as_deref() == Some("branching_model"))
+    {
+        ctx.client
+            .get(&format!("{repo}/effective-branching-model"))
+            .await
+            .with_context(|| format!("Failed to get branching model of {workspace}/{repo_slug}"))?
+    } else {
+        BranchingModel::default()
+    };
+    let restrictions: Vec<Restriction> = restrictions
+        .into_iter()
+        .filter(|r| applies_to(r, destination, &model))
+        .collect();
+
+    let mut snapshot = Snapshot {
+        tasks: fetch_paged(
+            ctx,
+            &format!("{base}/tasks"),
+            &everything,
+            Paging::Cursor,
+            &format!("tasks for pull request {pr_id}"),
+        )
+        .await?,
+        statuses: fetch_paged(
+            ctx,
+            &format!("{base}/statuses"),
+            &everything,
+            Paging::Cursor,
+            &format!("build statuses for pull request {pr_id}"),
+        )
+        .await?,
+        diffstat: fetch_paged(
+            ctx,
+            &format!("{base}/diffstat"),
+            &everything,
+            Paging::Cursor,
+            &format!("changed files for pull request {pr_id}"),
+        )
+        .await?,
+        ..Default::default()
+    };
+    if restricted(&restrictions, "require_default_reviewer_approvals_to_merge").is_some() {
+        let reviewers: Vec<User> = fetch_paged(
+            ctx,
+            &format!("{repo}/default-reviewers"),
+            &everything,
+            Paging::Cursor,
+            &format!("default reviewers for {workspace}/{repo_slug}"),
+        )
+        .await?;
+        snapshot.default_reviewers = reviewers.into_iter().map(|u| u.uuid).collect();
+    }
+    if restricted(&restrictions, "restrict_merges").is_some() {
+        let user: User = ctx
+            .client
+            .get("/2.0/user")
+            .await
+            .context("Failed to fetch current user from Bitbucket API")?;
+        snapshot.current_user = Some(user.uuid);
+    }
+    snapshot.restrictions = restrictions;
+
+    let rows = evaluate(&pr, &snapshot, required_approvals);
+    ctx.renderer.render(&rows)?;
+
+    let failed: Vec<&str> = rows
+        .iter()
+        .filter(|row| row.status == Status::Fail)
+        .map(|row| row.check)
+        .collect();
+    if !failed.is_empty() {
+        bail!(
+            "Pull request #{pr_id} is not ready to merge: {}",
+            failed.join(", ")
+        );
+    }
+
+    tracing::info!(
+        workspace,
+        repo_slug,
+        pr_id,
+        "Pull request merge checks passed"
+    );
+    Ok(())
+}
+
+fn evaluate(
+    pr: &PullRequest,
+    snapshot: &Snapshot,
+    required_approvals: Option<usize>,
+) -> Vec<CheckRow> {
+    let mut rows = Vec::new();
+    let pass_or_fail = |ok: bool| if ok { Status::Pass } else { Status::Fail };
+
+    rows.push(CheckRow::new(
+        "state",
+        pass_or_fail(pr.state == "OPEN"),
+        pr.state.to_lowercase(),
+    ));
+
+    let approved: Vec<&Participant> = pr.participants.iter().filter(|p| p.approved).collect();
+    let required = required_approvals
+        .or_else(|| restricted(&snapshot.restrictions, "require_approvals_to_merge"))
+        .unwrap_or(0);
+    rows.push(CheckRow::new(
+        "approvals",
+        pass_or_fail(approved.len() >= required),
+        format!("{} of {required} required", approved.len()),
+    ));
+
+    if let Some(required) = restricted(
+        &snapshot.restrictions,
+        "require_default_reviewer_approvals_to_merge",
+    ) {
+        let count = approved
+            .iter()
+            .filter(|p| {
+                p.user
+                    .as_ref()
+                    .is_some_and(|u| snapshot.default_reviewers.contains(&u.uuid))
+            })
+            .count();
+        rows.push(CheckRow::new(
+            "default reviewers",
+            pass_or_fail(count >= required),
+            format!("{count} of {required} required"),
+        ));
+    }
+
+    let requested: Vec<String> = pr
+        .participants
+        .iter()
+        .filter(|p| p.state.as_deref() == Some("changes_requested"))
+        .map(|p| {
+            p.user
+                .as_ref()
+                .and_then(|u| u.display_name.clone())
+                .unwrap_or_else(|| "unknown user".to_string())
+        })
+        .collect();
+    if !requested.is_empty() {
+        let blocking = restricted(&snapshot.restrictions, "require_no_changes_requested").is_some();
+        rows.push(CheckRow::new(
+            "changes requested",
+            if blocking { Status::Fail } else { Status::Warn },
+            format!("by {}", requested.join(", ")),
+        ));
+    }
+
+    let open_tasks = snapshot
+        .tasks
+        .iter()
+        .filter(|t| t.state == "UNRESOLVED")
+        .count();
+    rows.push(CheckRow::new(
+        "tasks",
+        pass_or_fail(open_tasks == 0),
+        format!("{open_tasks} unresolved"),
+    ));
+
+    rows.push(build_check(
+        &snapshot.statuses,
+        restricted(&snapshot.restrictions, "require_passing_builds_to_merge").unwrap_or(0),
+    ));
+
+    if let Some(restriction) = snapshot
+        .restrictions
+        .iter()
+        .find(|r| r.kind == "restrict_merges")
+    {
+        rows.push(merge_permission_check(
+            restriction,
+            snapshot.current_user.as_deref(),
+        ));
+    }
+
+    let conflicts: Vec<&str> = snapshot
+        .diffstat
+        .iter()
+        .filter(|entry| entry.status == "merge conflict")
+        .filter_map(|entry| entry.new.as_ref().or(entry.old.as_ref()))
+        .map(|path| path.path.as_str())
+        .collect();
+    rows.push(CheckRow::new(
+        "conflicts",
+        pass_or_fail(conflicts.is_empty()),
+        if conflicts.is_empty() {
+            "none".to_string()
+        } else {
+            conflicts.join(", ")
+        },
+    ));
+
+    rows
+}
+
+/// Builds fail the check when any failed or is still running, or when fewer
+/// passed than a restriction requires.
+fn build_check(statuses: &[BuildStatus], required_passing: usize) -> CheckRow {
+    let name = |s: &BuildStatus| {
+        s.name
+            .clone()
+            .or_else(|| s.key.clone())
+            .unwrap_or_else(|| "unnamed build".to_string())
+    };
+    let passed = statuses.iter().filter(|s| s.state == "SUCCESSFUL").count();
+    let failed: Vec<String> = statuses
+        .iter()
+        .filter(|s| matches!(s.state.as_str(), "FAILED" | "STOPPED"))
+        .map(name)
+        .collect();
+    let running: Vec<String> = statuses
+        .iter()
+        .filter(|s| s.state == "INPROGRESS")
+        .map(name)
+        .collect();
+
+    let mut detail = if statuses.is_empty() {
+        "no builds".to_string()
+    } else {
+        format!("{passed} of {} passed", statuses.len())
+    };
+    if required_passing > 0 {
+        detail.push_str(&format!(", {required_passing} required"));
+    }
+    if !failed.is_empty() {
+        detail.push_str(&format!("; failed: {}", failed.join(", ")));
+    }
+    if !running.is_empty() {
+        detail.push_str(&format!("; running: {}", running.join(", ")));
+    }
+
+    let ok = failed.is_empty() && running.is_empty() && passed >= required_passing;
+    CheckRow::new(
+        "builds",
+        if ok { Status::Pass } else { Status::Fail },
+        detail,
+    )
+}
+
+/// Only the listed users and groups may merge. Group membership is not looked
+/// up, so a caller who is not listed by name gets a warning when groups are set.
+fn merge_permission_check(restriction: &Restriction, current_user: Option<&str>) -> CheckRow {
+    let listed = current_user.is_some_and(|me| restriction.users.iter().any(|u| u.uuid == me));
+    let groups: Vec<&str> = restriction.groups.iter().map(|g| g.slug.as_str()).collect();
+    let (status, detail) = if listed {
+        (Status::Pass, "you are allowed to merge".to_string())
+    } else if groups.is_empty() {
+        (
+            Status::Fail,
+            "you are not among the users allowed to merge".to_string(),
+        )
+    } else {
+        (
+            Status::Warn,
+            format!("merging is limited to groups {}", groups.join(", ")),
+        )
+    };
+    CheckRow::new("merge permission", status, detail)
+}
+
+/// The value of the first restriction of `kind`, counting a restriction
+/// without a value as 1.
+fn restricted(restrictions: &[Restriction], kind: &str) -> Option<usize> {
+    restrictions
+        .iter()
+        .filter(|r| r.kind == kind)
+        .map(|r| r.value.unwrap_or(1))
+        .max()
+}
+
+/// Whether a restriction covers `branch`, by glob pattern or branching model type.
+fn applies_to(restriction: &Restriction, branch: &str, model: &BranchingModel) -> bool {
+    match restriction.branch_match_kind.as_deref() {
+        Some("branching_model") => {
+            let named = |setting: &Option<ModelBranch>| {
+                setting
+                    .as_ref()
+                    .and_then(|s| s.branch.as_ref())
+                    .is_some_and(|b| b.name == branch)
+            };
+            match restriction.branch_type.as_deref() {
+                Some("development") => named(&model.development),
+                Some("production") => named(&model.production),
+                Some(kind) => model.branch_types.iter().any(|t| {
+                    t.kind == kind
+                        && t.prefix
+                            .as_deref()
+                            .is_some_and(|prefix| branch.starts_with(prefix))
+                }),
+                None => false,
+            }
+        }
+        _ => restriction
+            .pattern
+            .as_deref()
+            .is_some_and(|pattern| glob_matches(pattern, branch)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pull_request(participants: serde_json::Value) -> PullRequest {
+        serde_json::from_value(json!({
+            "state": "OPEN",
+            "destination": { "branch": { "name": "main" } },
+            "participants": participants
+        }))
+        .unwrap()
+    }
+
+    fn restriction(value: serde_json::Value) -> Restriction {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let pr = pull_request(json!([
+            { "approved": true, "state": "approved", "user": { "uuid": "{a}" } },
+            { "approved": false, "state": "changes_requested",
+              "user": { "uuid": "{b}", "display_name": "Bo" } }
+        ]));
+        let snapshot = Snapshot {
+            tasks: vec![Task {
+                state: "RESOLVED".to_string(),
+            }],
+            statuses: vec![BuildStatus {
+                state: "SUCCESSFUL".to_string(),
+                name: Some("ci".to_string()),
+                key: None,
+            }],
+            restrictions: vec![restriction(json!({
+                "kind": "require_approvals_to_merge", "pattern": "main", "value": 2
+            }))],
+            diffstat: serde_json::from_value(json!([
+                { "status": "modified", "new": { "path": "src/lib.rs" } },
+                { "status": "merge conflict", "new": { "path": "Cargo.toml" } }
+            ]))
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let rows = evaluate(&pr, &snapshot, None);
+        let status = |check: &str| rows.iter().find(|r| r.check == check).unwrap().status;
+        assert_eq!(status("state"), Status::Pass);
+        assert_eq!(status("approvals"), Status::Fail);
+        assert_eq!(rows[1].detail, "1 of 2 required");
+        assert_eq!(status("changes requested"), Status::Warn);
+        assert_eq!(status("tasks"), Status::Pass);
+        assert_eq!(status("builds"), Status::Pass);
+        assert_eq!(status("conflicts"), Status::Fail);
+        assert_eq!(rows.last().unwrap().detail, "Cargo.toml");
+
+        // --required-approvals overrides the restriction
+        let rows = evaluate(&pr, &snapshot, Some(1));
+        assert_eq!(rows[1].status, Status::Pass);
+    }
+
+    #[test]
+    fn test_build_check() {
+        let build = |state: &str, name: &str| BuildStatus {
+            state: state.to_string(),
+            name: Some(name.to_string()),
+            key: None,
+        };
+        let row = build_check(
+            &[build("SUCCESSFUL", "lint"), build("INPROGRESS", "test")],
+            0,
+        );
+        assert_eq!(row.status, Status::Fail);
+        assert_eq!(row.detail, "1 of 2 passed; running: test");
+
+        let row = build_check(&[], 1);
+        assert_eq!(row.status, Status::Fail);
+        assert_eq!(row.detail, "no builds, 1 required");
+
+        assert_eq!(build_check(&[], 0).status, Status::Pass);
+    }
+
+    #[test]
+    fn test_merge_permission_check() {
+        let only_users = restriction(json!({
+            "kind": "restrict_merges", "pattern": "main",
+            "users": [{ "uuid": "{a}" }]
+        }));
+        assert_eq!(
+            merge_permission_check(&only_users, Some("{a}")).status,
+            Status::Pass
+        );
+        assert_eq!(
+            merge_permission_check(&only_users, Some("{b}")).status,
+            Status::Fail
+        );
+
+        let with_groups = restriction(json!({
+            "kind": "restrict_merges", "pattern": "main",
+            "groups": [{ "slug": "release-managers" }]
+        }));
+        let row = merge_permission_check(&with_groups, Some("{b}"));
+        assert_eq!(row.status, Status::Warn);
+        assert_eq!(row.detail, "merging is limited to groups release-managers");
+    }
+
+    #[test]
+    fn test_applies_to() {
+        let model: BranchingModel = serde_json::from_value(json!({
+            "development": { "branch": { "name": "develop" } },
+            "production": { "branch": { "name": "main" } },
+            "branch_types": [{ "kind": "release", "prefix": "release/" }]
+        }))
+        .unwrap();
+        let by_type = |branch_type: &str| {
+            restriction(json!({
+                "kind": "push",
+                "branch_match_kind": "branching_model",
+                "branch_type": branch_type
+            }))
+        };
+        assert!(applies_to(&by_type("production"), "main", &model));
+        assert!(!applies_to(&by_type("development"), "main", &model));
+        assert!(applies_to(&by_type("release"), "release/1.2", &model));
+
+        let glob = restriction(json!({
+            "kind": "push", "branch_match_kind": "glob", "pattern": "release/*"
+        }));
+        assert!(applies_to(&glob, "release/1.2", &model));
+        assert!(!applies_to(&glob, "main", &model));
+    }
+}
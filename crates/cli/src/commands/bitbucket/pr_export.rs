@@ -0,0 +1,225 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
+
+// Snapshot of a pull request for review records: metadata, diff, comments,
+// approvals, activity and build statuses in one zip, with a manifest of
+// SHA-256 checksums so the bundle can be verified later
+
+#[derive(Serialize)]
+struct ManifestFile {
+    name: String,
+    bytes: usize,
+    sha256: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Approval {
+    user: String,
+    uuid: String,
+    role: String,
+    approved_on: String,
+}
+
+pub async fn export_pull_request(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    pr_id: i64,
+    output: &Path,
+) -> Result<()> {
+    let base = format!("/2.0/repositories/{workspace}/{repo_slug}/pullrequests/{pr_id}");
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let list = |path: String, what: &'static str| {
+        let everything = &everything;
+        async move { fetch_paged::<Value>(ctx, &path, everything, Paging::Cursor, what).await }
+    };
+
+    let pull_request: Value = ctx.client.get(&base).await.with_context(|| {
+        format!("Failed to get pull request {pr_id} in {workspace}/{repo_slug}")
+    })?;
+    let (diff, comments, activity, statuses, commits) = futures::try_join!(
+        fetch_diff(ctx, &base),
+        list(format!("{base}/comments"), "pull request comments"),
+        list(format!("{base}/activity"), "pull request activity"),
+        list(format!("{base}/statuses"), "build statuses"),
+        list(format!("{base}/commits"), "pull request commits"),
+    )?;
+    let approvals = approvals(&pull_request);
+
+    let entries = vec![
+        ("pullrequest.json", to_json(&pull_request)?),
+        ("diff.patch", diff),
+        ("comments.json", to_json(&comments)?),
+        ("approvals.json", to_json(&approvals)?),
+        ("activity.json", to_json(&activity)?),
+        ("statuses.json", to_json(&statuses)?),
+        ("commits.json", to_json(&commits)?),
+    ];
+    let files: Vec<ManifestFile> = entries
+        .iter()
+        .map(|(name, content)| ManifestFile {
+            name: name.to_string(),
+            bytes: content.len(),
+            sha256: format!("{:x}", Sha256::digest(content)),
+        })
+        .collect();
+    let manifest = json!({
+        "workspace": workspace,
+        "repository": repo_slug,
+        "pull_request": pr_id,
+        "title": pull_request["title"],
+        "state": pull_request["state"],
+        "url": pull_request["links"]["html"]["href"],
+        "exported_at": Utc::now().to_rfc3339(),
+        "exported_by": format!("atlassian-cli {}", env!("CARGO_PKG_VERSION")),
+        "files": files,
+    });
+
+    write_archive(
+        output,
+        &format!("pr-{pr_id}"),
+        std::iter::once(("manifest.json", to_json(&manifest)?))
+            .chain(entries)
+            .collect(),
+    )?;
+
+    tracing::info!(
+        pr_id,
+        workspace,
+        repo_slug,
+        comments = comments.len(),
+        approvals = approvals.len(),
+        statuses = statuses.len(),
+        "Pull request exported"
+    );
+    println!(
+        "✓ Exported pull request #{pr_id} ({} comments, {} approvals, {} build statuses) to {}",
+        comments.len(),
+        approvals.len(),
+        statuses.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// The raw unified diff. It is served as text (after a redirect), so this
+/// bypasses the JSON client.
+async fn fetch_diff(ctx: &BitbucketContext<'_>, base: &str) -> Result<Vec<u8>> {
+    let url = format!("{}{base}/diff", ctx.client.base_url().trim_end_matches('/'));
+    let request = ctx.client.apply_auth(reqwest::Client::new().get(url));
+    let response = request
+        .send()
+        .await
+        .context("Failed to download pull request diff")?;
+    if !response.status().is_success() {
+        bail!(
+            "Failed to download pull request diff: HTTP {}",
+            response.status()
+        );
+    }
+    Ok(response
+        .bytes()
+        .await
+        .context("Failed to read pull request diff")?
+        .to_vec())
+}
+
+/// Participants who approved, with when they did.
+fn approvals(pull_request: &Value) -> Vec<Approval> {
+    let text = |value: &Value, pointer: &str| {
+        value
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    pull_request["participants"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|p| p["approved"].as_bool() == Some(true))
+        .map(|p| Approval {
+            user: text(p, "/user/display_name"),
+            uuid: text(p, "/user/uuid"),
+            role: text(p, "/role"),
+            approved_on: text(p, "/participated_on"),
+        })
+        .collect()
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec_pretty(value)?)
+}
+
+/// Write the entries under `folder/` in a new zip at `output`. Everything is
+/// fetched before this runs, so a failed export leaves no partial archive.
+fn write_archive(output: &Path, folder: &str, entries: Vec<(&str, Vec<u8>)>) -> Result<()> {
+    let file = std::fs::File::create(output)
+        .with_context(|| format!("Failed to write file: {}", output.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in entries {
+        zip.start_file(format!("{folder}/{name}"), options)?;
+        zip.write_all(&content)?;
+    }
+    zip.finish()
+        .with_context(|| format!("Failed to write file: {}", output.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approvals() {
+        let pull_request = json!({
+            "participants": [
+                {
+                    "user": { "display_name": "Ana", "uuid": "{a}" },
+                    "role": "REVIEWER",
+                    "approved": true,
+                    "participated_on": "2024-06-03T10:00:00+00:00"
+                },
+                { "user": { "display_name": "Bo", "uuid": "{b}" }, "role": "PARTICIPANT", "approved": false }
+            ]
+        });
+        assert_eq!(
+            approvals(&pull_request),
+            vec![Approval {
+                user: "Ana".to_string(),
+                uuid: "{a}".to_string(),
+                role: "REVIEWER".to_string(),
+                approved_on: "2024-06-03T10:00:00+00:00".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_archive() {
+        let output =
+            std::env::temp_dir().join(format!("atlassian-cli-pr-{}.zip", std::process::id()));
+        write_archive(&output, "pr-7", vec![("diff.patch", b"+line\n".to_vec())]).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        let mut entry = archive.by_name("pr-7/diff.patch").unwrap();
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut entry, &mut content).unwrap();
+        assert_eq!(content, "+line\n");
+        std::fs::remove_file(&output).unwrap();
+    }
+}
@@ -1,13 +1,10 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use atlassian_cli_api::ApiClient;
 use serde::{Deserialize, Serialize};
 use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
-
-#[derive(Deserialize)]
-struct PullRequestList {
-    values: Vec<PullRequest>,
-}
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
 
 #[derive(Deserialize)]
 struct PullRequest {
@@ -89,19 +86,15 @@ pub async fn list_pull_requests(
     workspace: &str,
     slug: &str,
     state: &str,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
     let query = form_urlencoded::Serializer::new(String::new())
         .append_pair("state", state)
-        .append_pair("pagelen", &limit.min(100).to_string())
         .finish();
     let path = format!("/2.0/repositories/{workspace}/{slug}/pullrequests?{query}");
-
-    let response: PullRequestList = ctx
-        .client
-        .get(&path)
-        .await
-        .with_context(|| format!("Failed to list pull requests for {workspace}/{slug}"))?;
+    let what = format!("pull requests for {workspace}/{slug}");
+    let pull_requests: Vec<PullRequest> =
+        fetch_paged(ctx, &path, page, Paging::Offset, &what).await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -113,8 +106,7 @@ pub async fn list_pull_requests(
         destination: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = pull_requests
         .iter()
         .map(|pr| Row {
             id: pr.id,
@@ -256,6 +248,80 @@ pub async fn create_pull_request(
     ctx.renderer.render(&created)
 }
 
+/// Title and description for a PR whose source branch names a Jira issue:
+/// "DEV-123: summary" goes in front of the title and a link to the issue in
+/// front of the description.
+pub async fn link_jira_issue(
+    jira: &ApiClient,
+    source_branch: &str,
+    title: Option<&str>,
+    description: Option<&str>,
+) -> Result<(String, String)> {
+    #[derive(Deserialize)]
+    struct Issue {
+        fields: IssueFields,
+    }
+
+    #[derive(Deserialize)]
+    struct IssueFields {
+        summary: String,
+    }
+
+    let key = jira_key_in_branch(source_branch).ok_or_else(|| {
+        anyhow!("Source branch '{source_branch}' does not contain a Jira issue key (e.g. feature/DEV-123-login)")
+    })?;
+    let issue: Issue = jira
+        .get(&format!("/rest/api/3/issue/{key}?fields=summary"))
+        .await
+        .with_context(|| format!("Failed to fetch Jira issue {key} from {}", jira.base_url()))?;
+
+    let prefix = format!("{key}: {}", issue.fields.summary);
+    let title = match title {
+        Some(title) if !title.trim().is_empty() => format!("{prefix} - {title}"),
+        _ => prefix,
+    };
+    let link = format!(
+        "Jira: [{key}]({}/browse/{key})",
+        jira.base_url().trim_end_matches('/')
+    );
+    let description = match description {
+        Some(desc) if !desc.trim().is_empty() => format!("{link}\n\n{desc}"),
+        _ => link,
+    };
+
+    tracing::debug!(%key, source_branch, "Linked pull request to Jira issue");
+    Ok((title, description))
+}
+
+/// First Jira issue key in a branch name, upper-cased: `feature/dev-123-login`
+/// gives `DEV-123`.
+fn jira_key_in_branch(branch: &str) -> Option<String> {
+    let chars: Vec<char> = branch.chars().collect();
+    for start in 0..chars.len() {
+        let boundary = start == 0 || !chars[start - 1].is_ascii_alphanumeric();
+        if !boundary || !chars[start].is_ascii_alphabetic() {
+            continue;
+        }
+        let project_len = chars[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+            .count();
+        let dash = start + project_len;
+        if project_len < 2 || chars.get(dash) != Some(&'-') {
+            continue;
+        }
+        let digits = chars[dash + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        let end = dash + 1 + digits;
+        if digits > 0 && !chars.get(end).is_some_and(|c| c.is_ascii_alphanumeric()) {
+            return Some(chars[start..end].iter().collect::<String>().to_uppercase());
+        }
+    }
+    None
+}
+
 pub async fn update_pull_request(
     ctx: &BitbucketContext<'_>,
     workspace: &str,
@@ -550,3 +616,24 @@ pub async fn get_pr_diff(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jira_key_in_branch() {
+        assert_eq!(
+            jira_key_in_branch("feature/DEV-123-add-login"),
+            Some("DEV-123".to_string())
+        );
+        assert_eq!(
+            jira_key_in_branch("bugfix/ops2-7_timeout"),
+            Some("OPS2-7".to_string())
+        );
+        assert_eq!(jira_key_in_branch("DEV-9"), Some("DEV-9".to_string()));
+        assert_eq!(jira_key_in_branch("release/1.2-rc"), None);
+        assert_eq!(jira_key_in_branch("feature/x-1"), None);
+        assert_eq!(jira_key_in_branch("hotfix/dev-12a"), None);
+    }
+}
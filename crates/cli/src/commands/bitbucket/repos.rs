@@ -1,13 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
-
-#[derive(Deserialize)]
-struct RepoList {
-    values: Vec<Repo>,
-}
+use super::tags;
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::dry_run::{field_changes, render_diff};
+use crate::commands::pagination::{PaginationArgs, Paging};
 
 #[derive(Deserialize)]
 struct Repo {
@@ -32,42 +29,13 @@ struct BranchRef {
     name: String,
 }
 
-pub async fn list_repos(ctx: &BitbucketContext<'_>, workspace: &str, limit: usize) -> Result<()> {
-    let query = form_urlencoded::Serializer::new(String::new())
-        .append_pair("pagelen", &limit.min(100).to_string())
-        .finish();
-    let path = format!("/2.0/repositories/{workspace}?{query}");
-
-    let response: RepoList = ctx
-        .client
-        .get(&path)
-        .await
-        .with_context(|| format!("Failed to list repositories for workspace {workspace}"))?;
-
-    #[derive(Serialize)]
-    struct Row<'a> {
-        slug: &'a str,
-        name: &'a str,
-        main_branch: &'a str,
-        visibility: &'a str,
-        language: &'a str,
-    }
-
-    let rows: Vec<Row<'_>> = response
-        .values
-        .iter()
-        .map(|repo| Row {
-            slug: repo.slug.as_str(),
-            name: repo.name.as_deref().unwrap_or(""),
-            main_branch: repo
-                .mainbranch
-                .as_ref()
-                .map(|b| b.name.as_str())
-                .unwrap_or(""),
-            visibility: if repo.is_private { "private" } else { "public" },
-            language: repo.language.as_deref().unwrap_or(""),
-        })
-        .collect();
+pub async fn list_repos(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    page: &PaginationArgs,
+    tags: &[String],
+) -> Result<()> {
+    let rows = fetch_repo_rows(ctx, workspace, page, tags).await?;
 
     if rows.is_empty() {
         tracing::info!(
@@ -77,7 +45,57 @@ pub async fn list_repos(ctx: &BitbucketContext<'_>, workspace: &str, limit: usiz
         return Ok(());
     }
 
-    ctx.renderer.render(&rows)
+    ctx.renderer.render_with_id(&rows, "slug")
+}
+
+#[derive(Serialize)]
+pub struct RepoRow {
+    slug: String,
+    name: String,
+    main_branch: String,
+    visibility: &'static str,
+    language: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    tags: String,
+}
+
+/// Fetch repositories for a workspace as table rows, keeping only those
+/// tagged with every topic in `tags`.
+pub async fn fetch_repo_rows(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    page: &PaginationArgs,
+    tags: &[String],
+) -> Result<Vec<RepoRow>> {
+    let mut path = format!("/2.0/repositories/{workspace}");
+    if !tags.is_empty() {
+        path.push_str(&format!(
+            "?q={}",
+            urlencoding::encode(&tags::tag_query(tags)?)
+        ));
+    }
+    let what = format!("repositories for workspace {workspace}");
+    let repos: Vec<Repo> = fetch_paged(ctx, &path, page, Paging::Offset, &what).await?;
+    let wanted: Vec<String> = tags.iter().map(|tag| tag.trim().to_lowercase()).collect();
+
+    Ok(repos
+        .into_iter()
+        .filter_map(|repo| {
+            let topics = tags::tags_in(repo.description.as_deref().unwrap_or_default());
+            wanted
+                .iter()
+                .all(|tag| topics.contains(tag))
+                .then_some((repo, topics))
+        })
+        .map(|(repo, topics)| RepoRow {
+            slug: repo.slug,
+            name: repo.name.unwrap_or_default(),
+            main_branch: repo.mainbranch.map(|b| b.name).unwrap_or_default(),
+            visibility: if repo.is_private { "private" } else { "public" },
+            language: repo.language.unwrap_or_default(),
+            tags: topics.join(", "),
+        })
+        .collect())
 }
 
 pub async fn get_repo(ctx: &BitbucketContext<'_>, workspace: &str, slug: &str) -> Result<()> {
@@ -87,7 +105,10 @@ pub async fn get_repo(ctx: &BitbucketContext<'_>, workspace: &str, slug: &str) -
         .get(&path)
         .await
         .with_context(|| format!("Failed to fetch repository {workspace}/{slug}"))?;
+    render_repo(ctx, &repo)
+}
 
+fn render_repo(ctx: &BitbucketContext<'_>, repo: &Repo) -> Result<()> {
     #[derive(Serialize)]
     struct View<'a> {
         slug: &'a str,
@@ -98,6 +119,8 @@ pub async fn get_repo(ctx: &BitbucketContext<'_>, workspace: &str, slug: &str) -
         visibility: &'a str,
         language: &'a str,
         size_bytes: String,
+        #[serde(skip_serializing_if = "String::is_empty")]
+        tags: String,
     }
 
     let view = View {
@@ -113,11 +136,13 @@ pub async fn get_repo(ctx: &BitbucketContext<'_>, workspace: &str, slug: &str) -
         visibility: if repo.is_private { "private" } else { "public" },
         language: repo.language.as_deref().unwrap_or(""),
         size_bytes: repo.size.map(|s| s.to_string()).unwrap_or_default(),
+        tags: tags::tags_in(repo.description.as_deref().unwrap_or_default()).join(", "),
     };
 
-    ctx.renderer.render(&view)
+    ctx.renderer.render_with_id(&view, "slug")
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_repo(
     ctx: &BitbucketContext<'_>,
     workspace: &str,
@@ -126,7 +151,23 @@ pub async fn create_repo(
     description: Option<&str>,
     is_private: bool,
     project_key: Option<&str>,
+    if_not_exists: bool,
 ) -> Result<()> {
+    let path = format!("/2.0/repositories/{workspace}/{slug}");
+
+    if if_not_exists {
+        let existing: Option<Repo> = ctx
+            .client
+            .get_optional(&path)
+            .await
+            .with_context(|| format!("Failed to check for repository {workspace}/{slug}"))?;
+        if let Some(repo) = existing {
+            tracing::info!(slug, workspace, "Repository already exists, not created");
+            println!("✅ Repository {workspace}/{slug} already exists");
+            return render_repo(ctx, &repo);
+        }
+    }
+
     let mut payload = serde_json::json!({
         "scm": "git",
         "is_private": is_private
@@ -144,7 +185,6 @@ pub async fn create_repo(
         payload["project"] = serde_json::json!({"key": pk});
     }
 
-    let path = format!("/2.0/repositories/{workspace}/{slug}");
     let repo: Repo = ctx
         .client
         .post(&path, &payload)
@@ -182,7 +222,41 @@ pub async fn update_repo(
     name: Option<&str>,
     description: Option<&str>,
     language: Option<&str>,
+    dry_run: bool,
 ) -> Result<()> {
+    let path = format!("/2.0/repositories/{workspace}/{slug}");
+
+    if dry_run {
+        let current: Repo = ctx
+            .client
+            .get(&path)
+            .await
+            .with_context(|| format!("Failed to get repository {workspace}/{slug}"))?;
+
+        let changes = field_changes(vec![
+            (
+                "name",
+                current.name.unwrap_or_default(),
+                name.map(str::to_string),
+            ),
+            (
+                "description",
+                current.description.unwrap_or_default(),
+                description.map(str::to_string),
+            ),
+            (
+                "language",
+                current.language.unwrap_or_default(),
+                language.map(str::to_string),
+            ),
+        ]);
+        return render_diff(
+            ctx.renderer,
+            &format!("repository {workspace}/{slug}"),
+            &changes,
+        );
+    }
+
     let mut payload = serde_json::json!({});
 
     if let Some(n) = name {
@@ -197,7 +271,6 @@ pub async fn update_repo(
         payload["language"] = serde_json::json!(l);
     }
 
-    let path = format!("/2.0/repositories/{workspace}/{slug}");
     let repo: Repo = ctx
         .client
         .put(&path, &payload)
@@ -0,0 +1,255 @@
+use anyhow::{Context, Result};
+use atlassian_cli_output::OutputFormat;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::utils::{self, BitbucketContext};
+
+// ============================================================================
+// API Response Structs
+// ============================================================================
+
+#[derive(Deserialize)]
+struct RunnerList {
+    values: Vec<Runner>,
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Runner {
+    uuid: String,
+    name: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    state: Option<RunnerState>,
+    #[serde(default)]
+    oauth_client: Option<OauthClient>,
+}
+
+#[derive(Deserialize)]
+struct RunnerState {
+    status: String,
+    #[serde(default)]
+    updated_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OauthClient {
+    id: String,
+    #[serde(default)]
+    secret: Option<String>,
+}
+
+// ============================================================================
+// Output Structs
+// ============================================================================
+
+#[derive(Serialize)]
+struct RunnerRow {
+    uuid: String,
+    name: String,
+    status: String,
+    labels: String,
+    updated: String,
+}
+
+#[derive(Serialize)]
+struct RunnerCredentials {
+    runner_uuid: String,
+    name: String,
+    workspace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repository: Option<String>,
+    oauth_client_id: String,
+    oauth_client_secret: String,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Runners are managed through Bitbucket's internal pipelines-config API, scoped
+/// either to the whole workspace or to a single repository.
+fn runners_path(workspace: &str, repo_slug: Option<&str>) -> String {
+    match repo_slug {
+        Some(repo) => format!("/internal/repositories/{workspace}/{repo}/pipelines-config/runners"),
+        None => format!("/internal/workspaces/{workspace}/pipelines-config/runners"),
+    }
+}
+
+fn normalize_labels(labels: &[String]) -> Vec<String> {
+    let mut normalized = vec!["self.hosted".to_string()];
+    for label in labels {
+        let label = label.trim().to_lowercase();
+        if !label.is_empty() && !normalized.contains(&label) {
+            normalized.push(label);
+        }
+    }
+    normalized
+}
+
+fn docker_command(creds: &RunnerCredentials) -> String {
+    let repository = creds
+        .repository
+        .as_deref()
+        .map(|repo| format!(" \\\n  -e REPOSITORY_UUID=<uuid of {repo}>"))
+        .unwrap_or_default();
+    format!(
+        "docker container run -it \\\n  -v /tmp:/tmp -v /var/run/docker.sock:/var/run/docker.sock \\\n  -e ACCOUNT_UUID=<uuid of {}>{repository} \\\n  -e RUNNER_UUID={} \\\n  -e OAUTH_CLIENT_ID={} \\\n  -e OAUTH_CLIENT_SECRET={} \\\n  -e WORKING_DIRECTORY=/tmp \\\n  --name runner-{} \\\n  docker-public.packages.atlassian.com/sox/atlassian/bitbucket-pipelines-runner:1",
+        creds.workspace,
+        creds.runner_uuid,
+        creds.oauth_client_id,
+        creds.oauth_client_secret,
+        creds.runner_uuid.trim_matches(|c| c == '{' || c == '}'),
+    )
+}
+
+// ============================================================================
+// Command Implementations
+// ============================================================================
+
+pub async fn list_runners(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: Option<&str>,
+) -> Result<()> {
+    let mut runners = Vec::new();
+    let mut path = runners_path(workspace, repo_slug);
+
+    loop {
+        let response: RunnerList = ctx
+            .client
+            .get(&path)
+            .await
+            .with_context(|| format!("Failed to list runners for {workspace}"))?;
+        runners.extend(response.values);
+        match response.next {
+            Some(next) => path = utils::strip_api_host(&next),
+            None => break,
+        }
+    }
+
+    if runners.is_empty() {
+        tracing::info!(workspace, "No runners registered");
+        return Ok(());
+    }
+
+    let rows: Vec<RunnerRow> = runners
+        .into_iter()
+        .map(|runner| RunnerRow {
+            uuid: runner.uuid,
+            name: runner.name,
+            status: runner
+                .state
+                .as_ref()
+                .map(|s| s.status.clone())
+                .unwrap_or_default(),
+            labels: runner.labels.join(","),
+            updated: runner.state.and_then(|s| s.updated_on).unwrap_or_default(),
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+pub async fn create_runner(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: Option<&str>,
+    name: &str,
+    labels: &[String],
+) -> Result<()> {
+    let payload = serde_json::json!({
+        "name": name,
+        "labels": normalize_labels(labels),
+    });
+
+    let runner: Runner = ctx
+        .client
+        .post(&runners_path(workspace, repo_slug), &payload)
+        .await
+        .with_context(|| format!("Failed to create runner {name} in {workspace}"))?;
+
+    tracing::info!(uuid = %runner.uuid, name, workspace, "Runner created");
+
+    let oauth = runner
+        .oauth_client
+        .context("Bitbucket did not return OAuth credentials for the new runner")?;
+
+    let creds = RunnerCredentials {
+        runner_uuid: runner.uuid,
+        name: runner.name,
+        workspace: workspace.to_string(),
+        repository: repo_slug.map(str::to_string),
+        oauth_client_id: oauth.id,
+        oauth_client_secret: oauth.secret.unwrap_or_default(),
+    };
+
+    if ctx.renderer.format() == OutputFormat::Table {
+        println!("✓ Runner '{}' created ({})", creds.name, creds.runner_uuid);
+        println!("\nOAuth client ID:     {}", creds.oauth_client_id);
+        println!("OAuth client secret: {}", creds.oauth_client_secret);
+        println!("\nThe secret is only shown once. Start the runner with:\n");
+        println!("{}", docker_command(&creds));
+        Ok(())
+    } else {
+        ctx.renderer.render(&creds)
+    }
+}
+
+pub async fn delete_runner(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: Option<&str>,
+    runner_uuid: &str,
+    force: bool,
+) -> Result<()> {
+    if !force {
+        println!("⚠️  About to delete runner {runner_uuid} in {workspace}");
+        println!("Use --force to confirm deletion");
+        return Ok(());
+    }
+
+    let path = format!("{}/{runner_uuid}", runners_path(workspace, repo_slug));
+    let _: Value = ctx
+        .client
+        .delete(&path)
+        .await
+        .with_context(|| format!("Failed to delete runner {runner_uuid}"))?;
+
+    tracing::info!(runner_uuid, workspace, "Runner deleted");
+    println!("✓ Deleted runner {runner_uuid}");
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runners_path_scopes() {
+        assert_eq!(
+            runners_path("ws", None),
+            "/internal/workspaces/ws/pipelines-config/runners"
+        );
+        assert_eq!(
+            runners_path("ws", Some("api")),
+            "/internal/repositories/ws/api/pipelines-config/runners"
+        );
+    }
+
+    #[test]
+    fn test_normalize_labels_adds_self_hosted() {
+        let labels = vec![
+            "Linux".to_string(),
+            "self.hosted".to_string(),
+            " ".to_string(),
+        ];
+        assert_eq!(normalize_labels(&labels), vec!["self.hosted", "linux"]);
+    }
+}
@@ -0,0 +1,363 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::utils::BitbucketContext;
+
+// Branch sync and cascade merges. Bitbucket Cloud has no branch-to-branch merge
+// endpoint, so every merge goes through a pull request that is merged right away
+// unless it conflicts, a merge check blocks it, or `--via-pr` is given.
+
+#[derive(Deserialize)]
+struct CommitPage {
+    values: Vec<serde_json::Value>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreatedPullRequest {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct DiffstatPage {
+    values: Vec<DiffstatEntry>,
+}
+
+#[derive(Deserialize)]
+struct DiffstatEntry {
+    status: String,
+    #[serde(default)]
+    old: Option<FileRef>,
+    #[serde(default)]
+    new: Option<FileRef>,
+}
+
+#[derive(Deserialize)]
+struct FileRef {
+    path: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct SyncRow {
+    from: String,
+    to: String,
+    status: String,
+    pull_request: Option<i64>,
+    detail: String,
+}
+
+impl SyncRow {
+    fn new(from: &str, to: &str, status: &str, pull_request: Option<i64>, detail: String) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            status: status.to_string(),
+            pull_request,
+            detail,
+        }
+    }
+
+    fn merged(&self) -> bool {
+        matches!(self.status.as_str(), "merged" | "up-to-date")
+    }
+}
+
+pub async fn sync_branch(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    from: &str,
+    to: &str,
+    via_pr: bool,
+) -> Result<()> {
+    let row = sync_one(ctx, workspace, repo_slug, from, to, via_pr).await?;
+    tracing::info!(
+        workspace,
+        repo_slug,
+        from,
+        to,
+        status = row.status.as_str(),
+        "Branch sync finished"
+    );
+    let rows = vec![row];
+    ctx.renderer.render(&rows)?;
+    ensure_merged(&rows, via_pr)
+}
+
+/// Merge each branch into the next one, e.g. `release/1.x,release/2.x,main`,
+/// stopping at the first step that could not be merged.
+pub async fn cascade(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    branches: &[String],
+    dry_run: bool,
+) -> Result<()> {
+    let steps = cascade_steps(branches)?;
+
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+    }
+
+    let mut rows = Vec::new();
+    let mut blocked = false;
+    for (from, to) in steps {
+        if blocked {
+            rows.push(SyncRow::new(
+                from,
+                to,
+                "skipped",
+                None,
+                "previous step was not merged".to_string(),
+            ));
+            continue;
+        }
+
+        let row = if dry_run {
+            let pending = pending_commits(ctx, workspace, repo_slug, from, to).await?;
+            match pending {
+                0 => SyncRow::new(from, to, "up-to-date", None, String::new()),
+                n => SyncRow::new(from, to, "would merge", None, format!("{n} commit(s)")),
+            }
+        } else {
+            sync_one(ctx, workspace, repo_slug, from, to, false).await?
+        };
+        blocked = !dry_run && !row.merged();
+        rows.push(row);
+    }
+
+    let merged = rows.iter().filter(|r| r.status == "merged").count();
+    tracing::info!(
+        workspace,
+        repo_slug,
+        steps = rows.len(),
+        merged,
+        "Cascade finished"
+    );
+    ctx.renderer.render(&rows)?;
+    if dry_run {
+        return Ok(());
+    }
+    ensure_merged(&rows, false)
+}
+
+/// Fail when any step ended in something other than a merge, so scripts can
+/// tell a blocked sync from a finished one. With `via_pr` an open pull request
+/// is the requested outcome, not a blocked step.
+fn ensure_merged(rows: &[SyncRow], via_pr: bool) -> Result<()> {
+    let blocked: Vec<String> = rows
+        .iter()
+        .filter(|row| !(row.merged() || via_pr && row.status == "pr-open"))
+        .map(|row| format!("{} -> {} ({})", row.from, row.to, row.status))
+        .collect();
+    if blocked.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "{} step(s) not merged: {}",
+        blocked.len(),
+        blocked.join(", ")
+    )
+}
+
+async fn sync_one(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    from: &str,
+    to: &str,
+    via_pr: bool,
+) -> Result<SyncRow> {
+    let pending = pending_commits(ctx, workspace, repo_slug, from, to).await?;
+    if pending == 0 {
+        return Ok(SyncRow::new(from, to, "up-to-date", None, String::new()));
+    }
+
+    let base = format!("/2.0/repositories/{workspace}/{repo_slug}/pullrequests");
+    let payload = json!({
+        "title": format!("Merge {from} into {to}"),
+        "source": { "branch": { "name": from } },
+        "destination": { "branch": { "name": to } },
+    });
+    let pr: CreatedPullRequest = match ctx.client.post(&base, &payload).await {
+        Ok(pr) => pr,
+        Err(err) => {
+            return Ok(SyncRow::new(
+                from,
+                to,
+                "failed",
+                None,
+                format!("could not open pull request: {err}"),
+            ))
+        }
+    };
+    tracing::info!(pr_id = pr.id, from, to, "Sync pull request created");
+
+    if via_pr {
+        return Ok(SyncRow::new(
+            from,
+            to,
+            "pr-open",
+            Some(pr.id),
+            format!("{pending} commit(s)"),
+        ));
+    }
+
+    let diffstat: DiffstatPage = ctx
+        .client
+        .get(&format!("{base}/{}/diffstat?pagelen=500", pr.id))
+        .await
+        .with_context(|| format!("Failed to get diffstat for pull request {}", pr.id))?;
+    let conflicts = conflicted_paths(&diffstat.values);
+    if !conflicts.is_empty() {
+        return Ok(SyncRow::new(
+            from,
+            to,
+            "conflict",
+            Some(pr.id),
+            conflicts.join(", "),
+        ));
+    }
+
+    let merge = json!({
+        "merge_strategy": "merge_commit",
+        "message": format!("Merge {from} into {to}"),
+    });
+    let merged: Result<serde_json::Value, _> = ctx
+        .client
+        .post(&format!("{base}/{}/merge", pr.id), &merge)
+        .await;
+    Ok(match merged {
+        Ok(_) => SyncRow::new(
+            from,
+            to,
+            "merged",
+            Some(pr.id),
+            format!("{pending} commit(s)"),
+        ),
+        // Merge checks (approvals, builds) leave the pull request open for review
+        Err(err) => SyncRow::new(from, to, "pr-open", Some(pr.id), err.to_string()),
+    })
+}
+
+/// Number of commits on `from` that `to` does not contain yet (capped at one page).
+async fn pending_commits(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    from: &str,
+    to: &str,
+) -> Result<usize> {
+    let path = format!(
+        "/2.0/repositories/{workspace}/{repo_slug}/commits?include={}&exclude={}&pagelen=100",
+        urlencoding::encode(from),
+        urlencoding::encode(to)
+    );
+    let page: CommitPage = ctx.client.get(&path).await.with_context(|| {
+        format!("Failed to compare {from} with {to} in {workspace}/{repo_slug}")
+    })?;
+    if page.next.is_some() {
+        tracing::debug!(from, to, "More than 100 pending commits");
+    }
+    Ok(page.values.len())
+}
+
+fn cascade_steps(branches: &[String]) -> Result<Vec<(&str, &str)>> {
+    let branches: Vec<&str> = branches
+        .iter()
+        .map(|b| b.trim())
+        .filter(|b| !b.is_empty())
+        .collect();
+    if branches.len() < 2 {
+        bail!("--branches needs at least two branches to cascade between");
+    }
+    Ok(branches.windows(2).map(|pair| (pair[0], pair[1])).collect())
+}
+
+fn conflicted_paths(entries: &[DiffstatEntry]) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|e| {
+            matches!(
+                e.status.as_str(),
+                "merge conflict" | "local deleted" | "remote deleted"
+            )
+        })
+        .filter_map(|e| e.new.as_ref().or(e.old.as_ref()))
+        .map(|f| f.path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cascade_steps() {
+        let branches: Vec<String> = ["release/1.x", " release/2.x", "main"]
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        assert_eq!(
+            cascade_steps(&branches).unwrap(),
+            vec![("release/1.x", "release/2.x"), ("release/2.x", "main")]
+        );
+        assert!(cascade_steps(&branches[..1]).is_err());
+    }
+
+    #[test]
+    fn test_ensure_merged() {
+        let mut rows = vec![
+            SyncRow::new(
+                "release/1.x",
+                "release/2.x",
+                "merged",
+                Some(7),
+                String::new(),
+            ),
+            SyncRow::new("release/2.x", "main", "up-to-date", None, String::new()),
+        ];
+        assert!(ensure_merged(&rows, false).is_ok());
+
+        let opened = [SyncRow::new(
+            "release/1.x",
+            "main",
+            "pr-open",
+            Some(9),
+            "2 commit(s)".to_string(),
+        )];
+        assert!(ensure_merged(&opened, true).is_ok());
+        assert!(ensure_merged(&opened, false).is_err());
+
+        rows[1] = SyncRow::new("release/2.x", "main", "conflict", Some(8), String::new());
+        rows.push(SyncRow::new(
+            "main",
+            "develop",
+            "skipped",
+            None,
+            String::new(),
+        ));
+        assert_eq!(
+            ensure_merged(&rows, true).unwrap_err().to_string(),
+            "2 step(s) not merged: release/2.x -> main (conflict), main -> develop (skipped)"
+        );
+    }
+
+    #[test]
+    fn test_conflicted_paths() {
+        let page: DiffstatPage = serde_json::from_value(json!({
+            "values": [
+                { "status": "modified", "new": { "path": "src/lib.rs" } },
+                { "status": "merge conflict", "old": { "path": "Cargo.toml" }, "new": { "path": "Cargo.toml" } },
+                { "status": "remote deleted", "old": { "path": "old.rs" }, "new": null }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            conflicted_paths(&page.values),
+            vec!["Cargo.toml".to_string(), "old.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::utils::BitbucketContext;
+
+// Repository topics: Bitbucket Cloud has none, so tags live on a managed last
+// line of the description, `[topics: platform, payments]`, which `repo list
+// --tag` filters on. The rest of the description is left as written.
+
+const TOPICS_PREFIX: &str = "[topics:";
+
+#[derive(Deserialize)]
+struct Repo {
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Add and remove topics on a repository.
+pub async fn update_tags(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    slug: &str,
+    add: &[String],
+    remove: &[String],
+) -> Result<()> {
+    let add = normalize_tags(add)?;
+    let remove = normalize_tags(remove)?;
+    let path = format!("/2.0/repositories/{workspace}/{slug}");
+    let repo: Repo = ctx
+        .client
+        .get(&path)
+        .await
+        .with_context(|| format!("Failed to fetch repository {workspace}/{slug}"))?;
+    let description = repo.description.unwrap_or_default();
+
+    let current = tags_in(&description);
+    let mut tags: Vec<String> = current
+        .iter()
+        .filter(|tag| !remove.contains(tag))
+        .cloned()
+        .collect();
+    for tag in add {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags.sort();
+
+    if tags == current {
+        println!(
+            "✓ Topics of {workspace}/{slug} unchanged: {}",
+            listed(&tags)
+        );
+        return Ok(());
+    }
+
+    let _: Value = ctx
+        .client
+        .put(
+            &path,
+            &json!({ "description": with_tags(&description, &tags) }),
+        )
+        .await
+        .with_context(|| format!("Failed to update repository {workspace}/{slug}"))?;
+
+    tracing::info!(slug, workspace, tags = ?tags, "Repository topics updated");
+    println!("✓ Topics of {workspace}/{slug}: {}", listed(&tags));
+    Ok(())
+}
+
+/// Topics are lowercase letters, digits, `-`, `_` and `.`.
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>> {
+    tags.iter()
+        .map(|tag| {
+            let tag = tag.trim().to_lowercase();
+            if tag.is_empty()
+                || !tag
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            {
+                bail!("Invalid topic '{tag}': use letters, digits, '-', '_' or '.'");
+            }
+            Ok(tag)
+        })
+        .collect()
+}
+
+/// Topics on the description's managed line, if it has one.
+pub fn tags_in(description: &str) -> Vec<String> {
+    description
+        .lines()
+        .rev()
+        .find_map(|line| {
+            line.trim()
+                .strip_prefix(TOPICS_PREFIX)
+                .and_then(|rest| rest.strip_suffix(']'))
+        })
+        .map(|list| {
+            list.split(',')
+                .map(|tag| tag.trim().to_lowercase())
+                .filter(|tag| !tag.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `description` with its managed line replaced by `tags`, or dropped when
+/// there are none.
+fn with_tags(description: &str, tags: &[String]) -> String {
+    let text: Vec<&str> = description
+        .lines()
+        .filter(|line| !line.trim().starts_with(TOPICS_PREFIX))
+        .collect();
+    let text = text.join("\n");
+    let text = text.trim_end();
+    match (text.is_empty(), tags.is_empty()) {
+        (_, true) => text.to_string(),
+        (true, false) => format!("{TOPICS_PREFIX} {}]", tags.join(", ")),
+        (false, false) => format!("{text}\n\n{TOPICS_PREFIX} {}]", tags.join(", ")),
+    }
+}
+
+/// Server-side pre-filter for `repo list --tag`; rows are then matched
+/// exactly with `tags_in`.
+pub fn tag_query(tags: &[String]) -> Result<String> {
+    let clauses: Vec<String> = normalize_tags(tags)?
+        .iter()
+        .map(|tag| format!("description ~ \"{tag}\""))
+        .collect();
+    Ok(clauses.join(" AND "))
+}
+
+fn listed(tags: &[String]) -> String {
+    if tags.is_empty() {
+        "none".to_string()
+    } else {
+        tags.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tags_in() {
+        assert_eq!(
+            tags_in("Payments API\n\n[topics: payments, Platform]"),
+            tags(&["payments", "platform"])
+        );
+        assert!(tags_in("Payments API").is_empty());
+        assert!(tags_in("").is_empty());
+    }
+
+    #[test]
+    fn test_with_tags() {
+        assert_eq!(
+            with_tags("Payments API", &tags(&["platform"])),
+            "Payments API\n\n[topics: platform]"
+        );
+        assert_eq!(
+            with_tags(
+                "Payments API\n\n[topics: platform]",
+                &tags(&["payments", "platform"])
+            ),
+            "Payments API\n\n[topics: payments, platform]"
+        );
+        assert_eq!(
+            with_tags("Payments API\n\n[topics: platform]", &[]),
+            "Payments API"
+        );
+        assert_eq!(with_tags("", &tags(&["infra"])), "[topics: infra]");
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        assert_eq!(
+            normalize_tags(&tags(&[" Platform ", "team.core"])).unwrap(),
+            tags(&["platform", "team.core"])
+        );
+        assert!(normalize_tags(&tags(&["two words"])).is_err());
+        assert!(normalize_tags(&tags(&[""])).is_err());
+    }
+}
@@ -1,12 +1,71 @@
+use anyhow::{Context, Result};
 use atlassian_cli_api::ApiClient;
 use atlassian_cli_output::OutputRenderer;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use url::Url;
 
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
 pub struct BitbucketContext<'a> {
     pub client: ApiClient,
     pub renderer: &'a OutputRenderer,
 }
 
+/// Results shown by list commands unless `--limit` says otherwise.
+pub const LIST_LIMIT: usize = 25;
+
+/// Largest `pagelen` Bitbucket accepts on most collections.
+pub const MAX_PAGELEN: usize = 100;
+
+/// Page through a 2.0 collection (`path` may already carry query parameters).
+/// `Paging::Offset` collections are entered at the `page` holding `--starting-at`;
+/// after that the `next` link is followed.
+pub async fn fetch_paged<T: DeserializeOwned>(
+    ctx: &BitbucketContext<'_>,
+    path: &str,
+    page: &PaginationArgs,
+    paging: Paging,
+    what: &str,
+) -> Result<Vec<T>> {
+    #[derive(Deserialize)]
+    struct PagedList<T> {
+        values: Vec<T>,
+        #[serde(default)]
+        next: Option<String>,
+    }
+
+    page.collect(LIST_LIMIT, MAX_PAGELEN, paging, |request| {
+        let path = match &request.cursor {
+            Some(next) => strip_api_host(next),
+            None => {
+                let separator = if path.contains('?') { '&' } else { '?' };
+                let mut first = format!("{path}{separator}pagelen={}", request.size);
+                if request.start > 0 {
+                    first.push_str(&format!("&page={}", request.number()));
+                }
+                first
+            }
+        };
+        async move {
+            let response: PagedList<T> = ctx
+                .client
+                .get(&path)
+                .await
+                .with_context(|| format!("Failed to list {what}"))?;
+            Ok(Page::cursor(response.values, response.next))
+        }
+    })
+    .await
+}
+
+/// Turn an absolute `next` link into a path for the API client.
+pub fn strip_api_host(url: &str) -> String {
+    url.strip_prefix("https://api.bitbucket.org")
+        .unwrap_or(url)
+        .to_string()
+}
+
 /// Extract Bitbucket workspace from a URL.
 /// Supports:
 /// - https://bitbucket.org/{workspace}
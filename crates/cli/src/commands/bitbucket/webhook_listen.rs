@@ -0,0 +1,466 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+use super::utils::BitbucketContext;
+use crate::commands::cancel;
+use crate::commands::webhook_events::{validate_events, BITBUCKET_EVENTS};
+use crate::commands::webhook_signature::{signature_matches, SIGNATURE_HEADER};
+
+// Local webhook receiver: registers (or reuses) a repository webhook pointing at
+// --url, serves it from a small HTTP listener and runs a script for every
+// signed delivery. The URL is usually a tunnel to the listen address; a webhook
+// created here is deleted again on Ctrl-C unless --keep is given
+
+const DESCRIPTION: &str = "atlassian-cli webhook listen";
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// A delivery must arrive in full within this long of the connection opening.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+/// Connections handled at once, including their scripts; others wait to be accepted.
+const MAX_CONCURRENT_DELIVERIES: usize = 8;
+
+#[derive(Deserialize)]
+struct WebhookList {
+    values: Vec<Webhook>,
+}
+
+#[derive(Deserialize)]
+struct Webhook {
+    uuid: String,
+    url: String,
+}
+
+/// What the spawned connection handlers need.
+struct Dispatch {
+    secret: String,
+    events: Vec<String>,
+    exec: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+struct RequestHead {
+    method: String,
+    headers: Vec<(String, String)>,
+    content_length: usize,
+}
+
+impl RequestHead {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// What to do with a delivery.
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    /// Signed and subscribed to: run the script.
+    Run {
+        event: String,
+    },
+    /// Signed, but for an event not passed to --events.
+    Ignore {
+        event: String,
+    },
+    Reject {
+        status: u16,
+        reason: String,
+    },
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn listen(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    url: &str,
+    events: Vec<String>,
+    exec: &Path,
+    secret: &str,
+    bind: SocketAddr,
+    keep: bool,
+) -> Result<()> {
+    validate_events(BITBUCKET_EVENTS, &events, "bitbucket webhook events")?;
+    if events.is_empty() {
+        bail!("--events is required, e.g. --events pullrequest:created");
+    }
+    if secret.is_empty() {
+        bail!("--secret must not be empty");
+    }
+
+    // Bind before touching the webhook so a busy port leaves nothing behind.
+    let listener = TcpListener::bind(bind)
+        .await
+        .with_context(|| format!("Failed to listen on {bind}"))?;
+    let (uuid, created) = register(ctx, workspace, repo_slug, url, &events, secret).await?;
+
+    tracing::info!(
+        webhook_uuid = uuid.as_str(),
+        %bind,
+        workspace,
+        repo_slug,
+        "Listening for webhook deliveries"
+    );
+    println!(
+        "✓ Listening on http://{bind} for {} on {workspace}/{repo_slug} (webhook {uuid}{})",
+        events.join(", "),
+        if created { "" } else { ", reused" }
+    );
+    println!(
+        "  Deliveries to {url} run {}; Ctrl-C to stop",
+        exec.display()
+    );
+
+    let dispatch = Arc::new(Dispatch {
+        secret: secret.to_string(),
+        events,
+        exec: exec.to_path_buf(),
+    });
+    let token = cancel::token();
+    let slots = Arc::new(Semaphore::new(MAX_CONCURRENT_DELIVERIES));
+    loop {
+        let permit = tokio::select! {
+            _ = token.cancelled() => break,
+            permit = Arc::clone(&slots).acquire_owned() => permit?,
+        };
+        tokio::select! {
+            _ = token.cancelled() => break,
+            accepted = listener.accept() => {
+                let (stream, peer) = match accepted {
+                    Ok(connection) => connection,
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to accept connection");
+                        continue;
+                    }
+                };
+                let dispatch = Arc::clone(&dispatch);
+                tokio::spawn(async move {
+                    if let Err(err) = handle(stream, &dispatch).await {
+                        tracing::warn!(%peer, error = %format!("{err:#}"), "Webhook delivery failed");
+                    }
+                    drop(permit);
+                });
+            }
+        }
+    }
+
+    if created && !keep {
+        let path = format!("/2.0/repositories/{workspace}/{repo_slug}/hooks/{uuid}");
+        let _: Value = ctx.client.delete(&path).await.with_context(|| {
+            format!("Failed to delete webhook {uuid} from {workspace}/{repo_slug}")
+        })?;
+        println!("✓ Webhook {uuid} deleted from {workspace}/{repo_slug}");
+    }
+    Ok(())
+}
+
+/// Point an existing webhook for `url` at the requested events and secret, or
+/// create one. Returns its UUID and whether it was created.
+async fn register(
+    ctx: &BitbucketContext<'_>,
+    workspace: &str,
+    repo_slug: &str,
+    url: &str,
+    events: &[String],
+    secret: &str,
+) -> Result<(String, bool)> {
+    let path = format!("/2.0/repositories/{workspace}/{repo_slug}/hooks");
+    let existing: WebhookList = ctx
+        .client
+        .get(&format!("{path}?pagelen=100"))
+        .await
+        .with_context(|| format!("Failed to list webhooks for {workspace}/{repo_slug}"))?;
+
+    let payload = json!({
+        "description": DESCRIPTION,
+        "url": url,
+        "active": true,
+        "events": events,
+        "secret": secret,
+    });
+
+    if let Some(hook) = existing.values.iter().find(|hook| hook.url == url) {
+        let _: Value = ctx
+            .client
+            .put(&format!("{path}/{}", hook.uuid), &payload)
+            .await
+            .with_context(|| format!("Failed to update webhook {}", hook.uuid))?;
+        return Ok((hook.uuid.clone(), false));
+    }
+
+    let hook: Webhook = ctx
+        .client
+        .post(&path, &payload)
+        .await
+        .with_context(|| format!("Failed to create webhook on {workspace}/{repo_slug}"))?;
+    Ok((hook.uuid, true))
+}
+
+async fn handle(mut stream: TcpStream, dispatch: &Dispatch) -> Result<()> {
+    let (head, body) = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(err)) => {
+            respond(&mut stream, 400, "bad request").await?;
+            return Err(err);
+        }
+        Err(_) => {
+            respond(&mut stream, 408, "request timeout").await?;
+            bail!("No complete request within {}s", READ_TIMEOUT.as_secs());
+        }
+    };
+
+    match verdict(&head, &body, &dispatch.secret, &dispatch.events) {
+        Verdict::Reject { status, reason } => {
+            respond(&mut stream, status, &reason).await?;
+            tracing::warn!(
+                status,
+                reason = reason.as_str(),
+                "Rejected webhook delivery"
+            );
+            Ok(())
+        }
+        Verdict::Ignore { event } => {
+            respond(&mut stream, 200, "ignored").await?;
+            tracing::info!(event = event.as_str(), "Ignored webhook delivery");
+            Ok(())
+        }
+        Verdict::Run { event } => {
+            // Answer first: Bitbucket gives up on slow receivers and retries.
+            respond(&mut stream, 202, "accepted").await?;
+            let request_uuid = head.header("X-Request-UUID").unwrap_or("");
+            let hook_uuid = head.header("X-Hook-UUID").unwrap_or("");
+            let status = run_script(&dispatch.exec, &event, request_uuid, hook_uuid, &body).await?;
+            println!("{event} {request_uuid} → {}", describe_exit(status));
+            Ok(())
+        }
+    }
+}
+
+/// Run the script with the payload on stdin and the delivery details in the
+/// environment.
+async fn run_script(
+    exec: &Path,
+    event: &str,
+    request_uuid: &str,
+    hook_uuid: &str,
+    body: &[u8],
+) -> Result<std::process::ExitStatus> {
+    let mut child = Command::new(exec)
+        .env("BITBUCKET_EVENT", event)
+        .env("BITBUCKET_REQUEST_UUID", request_uuid)
+        .env("BITBUCKET_HOOK_UUID", hook_uuid)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", exec.display()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A script that never reads its input is not an error.
+        let _ = stdin.write_all(body).await;
+    }
+    child
+        .wait()
+        .await
+        .with_context(|| format!("Failed to wait for {}", exec.display()))
+}
+
+fn describe_exit(status: std::process::ExitStatus) -> String {
+    match status.code() {
+        Some(0) => "ok".to_string(),
+        Some(code) => format!("exit {code}"),
+        None => "killed by signal".to_string(),
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<(RequestHead, Vec<u8>)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let head_end = loop {
+        if let Some(at) = find_head_end(&buffer) {
+            break at;
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            bail!("Request headers exceed {MAX_HEAD_BYTES} bytes");
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            bail!("Connection closed before the request headers ended");
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = parse_head(&buffer[..head_end])?;
+    if head.content_length > MAX_BODY_BYTES {
+        bail!("Request body exceeds {MAX_BODY_BYTES} bytes");
+    }
+    let mut body = buffer.split_off(head_end + 4);
+    while body.len() < head.content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            bail!("Connection closed before the request body ended");
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(head.content_length);
+    Ok((head, body))
+}
+
+fn find_head_end(buffer: &[u8]) -> Option<usize> {
+    buffer.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn parse_head(head: &[u8]) -> Result<RequestHead> {
+    let head = std::str::from_utf8(head).context("Request headers are not UTF-8")?;
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or("");
+    let method = request_line
+        .split_whitespace()
+        .next()
+        .filter(|method| !method.is_empty())
+        .ok_or_else(|| anyhow!("Malformed request line '{request_line}'"))?
+        .to_string();
+
+    let mut headers = Vec::new();
+    for line in lines {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Malformed header line '{line}'"))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut head = RequestHead {
+        method,
+        headers,
+        content_length: 0,
+    };
+    if let Some(length) = head.header("Content-Length") {
+        head.content_length = length
+            .parse()
+            .map_err(|_| anyhow!("Invalid Content-Length '{length}'"))?;
+    }
+    Ok(head)
+}
+
+fn verdict(head: &RequestHead, body: &[u8], secret: &str, events: &[String]) -> Verdict {
+    let reject = |status, reason: &str| Verdict::Reject {
+        status,
+        reason: reason.to_string(),
+    };
+    if head.method != "POST" {
+        return reject(405, "only POST is accepted");
+    }
+    let Some(signature) = head.header(SIGNATURE_HEADER) else {
+        return reject(401, "missing signature");
+    };
+    match signature_matches(secret, body, signature) {
+        Ok(true) => {}
+        Ok(false) => return reject(401, "signature mismatch"),
+        Err(_) => return reject(400, "malformed signature"),
+    }
+    let Some(event) = head.header("X-Event-Key") else {
+        return reject(400, "missing X-Event-Key");
+    };
+    let event = event.to_string();
+    if events.contains(&event) {
+        Verdict::Run { event }
+    } else {
+        Verdict::Ignore { event }
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, reason: &str) -> Result<()> {
+    let text = match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {text}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reason}",
+        reason.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::webhook_signature::sign;
+
+    fn head(method: &str, headers: &[(&str, &str)]) -> RequestHead {
+        RequestHead {
+            method: method.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            content_length: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_head() {
+        let raw = b"POST /hook HTTP/1.1\r\nHost: localhost\r\ncontent-length: 12\r\nX-Event-Key: repo:push";
+        let parsed = parse_head(raw).unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.content_length, 12);
+        assert_eq!(parsed.header("x-event-key"), Some("repo:push"));
+        assert!(parse_head(b"POST / HTTP/1.1\r\nbroken header").is_err());
+        assert!(parse_head(b"POST / HTTP/1.1\r\nContent-Length: lots").is_err());
+        assert_eq!(find_head_end(b"GET / HTTP/1.1\r\n\r\nbody"), Some(14));
+    }
+
+    #[test]
+    fn test_verdict() {
+        let body = br#"{"pullrequest":{"id":1}}"#;
+        let signature = format!("sha256={}", sign("s3cret", body));
+        let events = vec!["pullrequest:created".to_string()];
+
+        let signed = |event: &str| {
+            head(
+                "POST",
+                &[("X-Hub-Signature", &signature), ("X-Event-Key", event)],
+            )
+        };
+        assert_eq!(
+            verdict(&signed("pullrequest:created"), body, "s3cret", &events),
+            Verdict::Run {
+                event: "pullrequest:created".to_string()
+            }
+        );
+        assert_eq!(
+            verdict(&signed("repo:push"), body, "s3cret", &events),
+            Verdict::Ignore {
+                event: "repo:push".to_string()
+            }
+        );
+        assert!(matches!(
+            verdict(&signed("pullrequest:created"), body, "other", &events),
+            Verdict::Reject { status: 401, .. }
+        ));
+        assert!(matches!(
+            verdict(&head("POST", &[]), body, "s3cret", &events),
+            Verdict::Reject { status: 401, .. }
+        ));
+        assert!(matches!(
+            verdict(&head("GET", &[]), body, "s3cret", &events),
+            Verdict::Reject { status: 405, .. }
+        ));
+    }
+}
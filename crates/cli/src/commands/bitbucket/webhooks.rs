@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 use super::utils::BitbucketContext;
+use crate::commands::webhook_events::{validate_events, BITBUCKET_EVENTS};
 
 #[derive(Deserialize)]
 struct WebhookList {
@@ -85,6 +86,8 @@ pub async fn create_webhook(
     events: Vec<String>,
     active: bool,
 ) -> Result<()> {
+    validate_events(BITBUCKET_EVENTS, &events, "bitbucket webhook events")?;
+
     let mut payload = serde_json::json!({
         "url": url,
         "active": active,
@@ -1,14 +1,9 @@
 use anyhow::{Context, Result};
 use atlassian_cli_api::ApiClient;
 use serde::{Deserialize, Serialize};
-use url::form_urlencoded;
 
-use super::utils::BitbucketContext;
-
-#[derive(Deserialize)]
-struct WorkspaceList {
-    values: Vec<Workspace>,
-}
+use super::utils::{fetch_paged, BitbucketContext};
+use crate::commands::pagination::{PaginationArgs, Paging};
 
 #[derive(Deserialize)]
 struct Workspace {
@@ -20,11 +15,6 @@ struct Workspace {
     workspace_type: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct ProjectList {
-    values: Vec<Project>,
-}
-
 #[derive(Deserialize)]
 struct Project {
     key: String,
@@ -37,17 +27,9 @@ struct Project {
     uuid: Option<String>,
 }
 
-pub async fn list_workspaces(ctx: &BitbucketContext<'_>, limit: usize) -> Result<()> {
-    let query = form_urlencoded::Serializer::new(String::new())
-        .append_pair("pagelen", &limit.min(100).to_string())
-        .finish();
-    let path = format!("/2.0/workspaces?{query}");
-
-    let response: WorkspaceList = ctx
-        .client
-        .get(&path)
-        .await
-        .context("Failed to list workspaces")?;
+pub async fn list_workspaces(ctx: &BitbucketContext<'_>, page: &PaginationArgs) -> Result<()> {
+    let workspaces: Vec<Workspace> =
+        fetch_paged(ctx, "/2.0/workspaces", page, Paging::Offset, "workspaces").await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -56,8 +38,7 @@ pub async fn list_workspaces(ctx: &BitbucketContext<'_>, limit: usize) -> Result
         workspace_type: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = workspaces
         .iter()
         .map(|ws| Row {
             slug: ws.slug.as_str(),
@@ -103,18 +84,11 @@ pub async fn get_workspace(ctx: &BitbucketContext<'_>, workspace: &str) -> Resul
 pub async fn list_projects(
     ctx: &BitbucketContext<'_>,
     workspace: &str,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    let query = form_urlencoded::Serializer::new(String::new())
-        .append_pair("pagelen", &limit.min(100).to_string())
-        .finish();
-    let path = format!("/2.0/workspaces/{workspace}/projects?{query}");
-
-    let response: ProjectList = ctx
-        .client
-        .get(&path)
-        .await
-        .with_context(|| format!("Failed to list projects in workspace {workspace}"))?;
+    let path = format!("/2.0/workspaces/{workspace}/projects");
+    let what = format!("projects in workspace {workspace}");
+    let projects: Vec<Project> = fetch_paged(ctx, &path, page, Paging::Offset, &what).await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -124,8 +98,7 @@ pub async fn list_projects(
         visibility: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = projects
         .iter()
         .map(|proj| Row {
             key: proj.key.as_str(),
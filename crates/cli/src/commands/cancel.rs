@@ -0,0 +1,162 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use atlassian_cli_bulk::{CancellationToken, DEFAULT_GRACE_PERIOD};
+
+// Process-wide cancellation for Ctrl-C and `--timeout`. Bulk and watch commands
+// take the token, stop starting new work when it fires and exit with a partial
+// summary; every other command is dropped straight away.
+
+/// Exit status of a command that was stopped after doing part of its work.
+pub const PARTIAL_EXIT_CODE: i32 = 3;
+/// Exit status of a command that was stopped before it could wind down.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+static REASON: OnceLock<String> = OnceLock::new();
+/// Set once a command has taken the token and will stop on its own.
+static COOPERATIVE: AtomicBool = AtomicBool::new(false);
+
+fn shared() -> &'static CancellationToken {
+    TOKEN.get_or_init(CancellationToken::new)
+}
+
+/// The token a long-running command watches. Taking it tells `main` the
+/// command winds down by itself, so it gets the grace period to do so.
+pub fn token() -> CancellationToken {
+    COOPERATIVE.store(true, Ordering::SeqCst);
+    shared().clone()
+}
+
+/// Why the command was cancelled, e.g. "interrupted" or "timed out after 10m".
+pub fn reason() -> &'static str {
+    REASON.get().map(String::as_str).unwrap_or("cancelled")
+}
+
+pub fn is_cancelled() -> bool {
+    shared().is_cancelled()
+}
+
+fn cancel(reason: String) {
+    let _ = REASON.set(reason);
+    shared().cancel();
+}
+
+/// Cancel on Ctrl-C (a second Ctrl-C exits immediately) and after `timeout`.
+pub fn install(timeout: Option<Duration>, timeout_label: String) {
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        cancel("interrupted".to_string());
+        if COOPERATIVE.load(Ordering::SeqCst) {
+            eprintln!("\nInterrupted: waiting for in-flight requests (Ctrl-C again to quit now)");
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+    });
+
+    if let Some(timeout) = timeout {
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            cancel(format!("timed out after {timeout_label}"));
+        });
+    }
+}
+
+/// Resolves when the running command should be dropped: as soon as it is
+/// cancelled, or after the grace period if it stops on its own.
+pub async fn deadline() -> &'static str {
+    shared().cancelled().await;
+    if COOPERATIVE.load(Ordering::SeqCst) {
+        tokio::time::sleep(DEFAULT_GRACE_PERIOD + Duration::from_secs(2)).await;
+    }
+    reason()
+}
+
+/// Exit status for a command that failed after cancellation.
+pub fn exit_code() -> i32 {
+    if COOPERATIVE.load(Ordering::SeqCst) {
+        PARTIAL_EXIT_CODE
+    } else {
+        INTERRUPTED_EXIT_CODE
+    }
+}
+
+/// Sleep between polls of a watch loop. Returns false if the command was
+/// cancelled first.
+pub async fn sleep_unless_cancelled(duration: Duration) -> bool {
+    let token = token();
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => true,
+        _ = token.cancelled() => false,
+    }
+}
+
+/// Parse durations such as `90s`, `45m`, `2h` or `1h30m`; bare numbers are
+/// seconds. Used by `--timeout` and the watch commands.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let invalid = || anyhow!("Invalid duration '{input}'. Use e.g. 90s, 45m, 2h or 1h30m");
+    let too_long = || anyhow!("Duration '{input}' is too long");
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        if number.is_empty() {
+            return Err(invalid());
+        }
+        let value: u64 = number.parse().map_err(|_| too_long())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(too_long)?;
+        number.clear();
+    }
+
+    if !number.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("45m").unwrap(), Duration::from_secs(2700));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert!(parse_duration("2d").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("10m5").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_overflow() {
+        let err = parse_duration("18446744073709551615h").unwrap_err();
+        assert!(err.to_string().contains("too long"));
+        assert!(parse_duration("5124095576030432h").is_err());
+        assert!(parse_duration("5124095576030431h1m").is_err());
+        assert!(parse_duration("99999999999999999999999s").is_err());
+    }
+}
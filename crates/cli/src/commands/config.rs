@@ -0,0 +1,138 @@
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_config::Config;
+use clap::{Args, Subcommand};
+
+use super::init;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigCommand {
+    /// Export profiles and settings to a shareable bundle
+    Export(ExportArgs),
+    /// Import profiles and settings from a bundle
+    Import(ImportArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    /// Bundle file to write (prints to stdout when omitted)
+    pub file: Option<PathBuf>,
+    /// Leave out stored API tokens, credential helpers, emails and the vault key file path
+    #[arg(long)]
+    pub no_secrets: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ImportArgs {
+    /// Bundle file to read
+    pub file: PathBuf,
+    /// Replace profiles that already exist with the bundle's version
+    #[arg(long)]
+    pub overwrite: bool,
+    /// Accept credential helper commands from the bundle without asking
+    #[arg(long)]
+    pub trust_credential_helpers: bool,
+}
+
+pub fn handle(
+    command: ConfigCommand,
+    config: &mut Config,
+    config_path: Option<&Path>,
+) -> Result<()> {
+    match command {
+        ConfigCommand::Export(args) => export(args, config),
+        ConfigCommand::Import(args) => import(args, config, config_path),
+    }
+}
+
+fn export(args: ExportArgs, config: &Config) -> Result<()> {
+    if config.profiles.is_empty() {
+        bail!("No profiles configured. Run `atlassian-cli auth login` first.");
+    }
+
+    let bundle = if args.no_secrets {
+        config.without_secrets()
+    } else {
+        if config.has_secrets() {
+            eprintln!("⚠️  Bundle includes API tokens or credential helpers from the config file; use --no-secrets before sharing it");
+        }
+        config.clone()
+    };
+
+    match &args.file {
+        Some(path) => {
+            bundle
+                .save(Some(path))
+                .with_context(|| format!("Failed to write bundle {}", path.display()))?;
+            tracing::info!(path = %path.display(), profiles = bundle.profiles.len(), "Config exported");
+            println!(
+                "✅ Exported {} profiles to {}",
+                bundle.profiles.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", serde_yaml::to_string(&bundle)?),
+    }
+    Ok(())
+}
+
+fn import(args: ImportArgs, config: &mut Config, config_path: Option<&Path>) -> Result<()> {
+    if !args.file.exists() {
+        bail!("Bundle file {} does not exist", args.file.display());
+    }
+    let bundle = Config::load(Some(&args.file))?;
+    if bundle.profiles.is_empty() {
+        bail!("Bundle {} contains no profiles", args.file.display());
+    }
+    confirm_credential_helpers(&bundle, args.trust_credential_helpers)?;
+
+    let summary = config.merge(bundle, args.overwrite);
+    config
+        .save(config_path)
+        .context("Unable to persist configuration file")?;
+
+    for name in &summary.skipped {
+        println!("Skipped existing profile '{name}' (use --overwrite to replace it)");
+    }
+    for name in &summary.imported {
+        println!("✅ Imported profile '{name}'");
+    }
+    if !summary.imported.is_empty() {
+        println!("\nStore your own API token for each profile with `atlassian-cli auth login --profile <name> ...`");
+    }
+    Ok(())
+}
+
+/// A credential helper runs on every command that uses its profile, so show
+/// exactly what the bundle would run and have the user accept it first.
+fn confirm_credential_helpers(bundle: &Config, trusted: bool) -> Result<()> {
+    let mut helpers: Vec<(&String, String)> = bundle
+        .profiles
+        .iter()
+        .filter_map(|(name, profile)| {
+            let helper = profile.credential_helper.as_ref()?;
+            Some((name, helper.command_line()))
+        })
+        .collect();
+    if helpers.is_empty() {
+        return Ok(());
+    }
+    helpers.sort();
+
+    eprintln!("⚠️  The bundle sets credential helpers, which run these commands to get a token:");
+    for (name, command) in &helpers {
+        eprintln!("    {name}: {command}");
+    }
+    if trusted {
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!("Refusing to import credential helpers without confirmation; check the commands above and re-run with --trust-credential-helpers");
+    }
+    if !init::confirm("Import these commands", false)? {
+        bail!("Import cancelled");
+    }
+    Ok(())
+}
@@ -4,7 +4,8 @@ use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
 
-use super::utils::ConfluenceContext;
+use super::utils::{fetch_v2_list, ConfluenceContext, V2_LIMIT};
+use crate::commands::pagination::PaginationArgs;
 
 // List attachments
 pub async fn list_attachments(ctx: &ConfluenceContext<'_>, page_id: &str) -> Result<()> {
@@ -59,7 +60,7 @@ pub async fn get_attachment(ctx: &ConfluenceContext<'_>, attachment_id: &str) ->
         .await
         .with_context(|| format!("Failed to get attachment {}", attachment_id))?;
 
-    println!("{}", serde_json::to_string_pretty(&attachment)?);
+    ctx.renderer.print_json(&attachment)?;
     Ok(())
 }
 
@@ -69,6 +70,7 @@ pub async fn upload_attachment(
     page_id: &str,
     file_path: &PathBuf,
     comment: Option<&str>,
+    replace_existing: bool,
 ) -> Result<()> {
     let file_content = fs::read(file_path)
         .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
@@ -92,15 +94,28 @@ pub async fn upload_attachment(
         form
     };
 
+    let existing = if replace_existing {
+        find_attachment_by_name(ctx, page_id, file_name).await?
+    } else {
+        None
+    };
+
+    // A new version of an existing attachment is posted to its `data` endpoint
+    let path = match &existing {
+        Some(id) => format!("/wiki/rest/api/content/{page_id}/child/attachment/{id}/data"),
+        None => format!("/wiki/rest/api/content/{page_id}/child/attachment"),
+    };
+
+    if ctx.client.explained(reqwest::Method::POST, &path) {
+        return Ok(());
+    }
+
     // Note: This uses the raw reqwest client for multipart upload
-    let base_url = ctx.client.base_url();
+    let base_url = ctx.client.base_url().trim_end_matches('/');
     let http_client = reqwest::Client::new();
 
     let mut request = http_client
-        .post(format!(
-            "{}/wiki/rest/api/content/{}/child/attachment",
-            base_url, page_id
-        ))
+        .post(format!("{}{}", base_url, path))
         .multipart(form)
         .header("X-Atlassian-Token", "no-check");
 
@@ -120,11 +135,107 @@ pub async fn upload_attachment(
         ));
     }
 
-    tracing::info!(%page_id, file = %file_name, "Attachment uploaded successfully");
-    println!("✅ Uploaded attachment '{}' to page {}", file_name, page_id);
+    match existing {
+        Some(id) => {
+            tracing::info!(%page_id, %id, file = %file_name, "Attachment version uploaded successfully");
+            println!(
+                "✅ Uploaded new version of attachment '{}' ({}) on page {}",
+                file_name, id, page_id
+            );
+        }
+        None => {
+            tracing::info!(%page_id, file = %file_name, "Attachment uploaded successfully");
+            println!("✅ Uploaded attachment '{}' to page {}", file_name, page_id);
+        }
+    }
     Ok(())
 }
 
+/// ID of the attachment on a page with exactly this file name, if there is one.
+async fn find_attachment_by_name(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    file_name: &str,
+) -> Result<Option<String>> {
+    #[derive(Deserialize)]
+    struct AttachmentsResponse {
+        results: Vec<Attachment>,
+    }
+
+    #[derive(Deserialize)]
+    struct Attachment {
+        id: String,
+        title: String,
+    }
+
+    let response: AttachmentsResponse = ctx
+        .client
+        .get(&format!(
+            "/wiki/rest/api/content/{}/child/attachment?filename={}",
+            page_id,
+            urlencoding::encode(file_name)
+        ))
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to look up attachment '{}' on page {}",
+                file_name, page_id
+            )
+        })?;
+
+    Ok(response
+        .results
+        .into_iter()
+        .find(|a| a.title == file_name)
+        .map(|a| a.id))
+}
+
+// List attachment versions
+pub async fn list_attachment_versions(
+    ctx: &ConfluenceContext<'_>,
+    attachment_id: &str,
+    page: &PaginationArgs,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Version {
+        number: i64,
+        #[serde(rename = "createdAt", default)]
+        created_at: String,
+        #[serde(rename = "authorId", default)]
+        author_id: String,
+        #[serde(default)]
+        message: String,
+        #[serde(rename = "minorEdit", default)]
+        minor_edit: bool,
+    }
+
+    let path = format!("/wiki/api/v2/attachments/{}/versions", attachment_id);
+    let what = format!("versions of attachment {}", attachment_id);
+    let versions: Vec<Version> = fetch_v2_list(ctx, &path, page, V2_LIMIT, &what).await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        version: i64,
+        created: &'a str,
+        author_id: &'a str,
+        message: &'a str,
+        minor_edit: bool,
+    }
+
+    let rows: Vec<Row<'_>> = versions
+        .iter()
+        .map(|v| Row {
+            version: v.number,
+            created: v.created_at.as_str(),
+            author_id: v.author_id.as_str(),
+            message: v.message.as_str(),
+            minor_edit: v.minor_edit,
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
 // Download attachment
 pub async fn download_attachment(
     ctx: &ConfluenceContext<'_>,
@@ -146,7 +257,7 @@ pub async fn download_attachment(
         .with_context(|| format!("Failed to get attachment {}", attachment_id))?;
 
     // Download the file
-    let base_url = ctx.client.base_url();
+    let base_url = ctx.client.base_url().trim_end_matches('/');
     let http_client = reqwest::Client::new();
 
     let mut request = http_client.get(format!("{}{}", base_url, attachment.download_link));
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
-use atlassian_cli_bulk::BulkExecutor;
+use atlassian_cli_bulk::{BulkExecutor, PlannedOperation};
 use serde::Deserialize;
 use serde_json::{json, Value};
 use std::fs;
 use std::path::PathBuf;
 
 use super::utils::ConfluenceContext;
+use crate::commands::cancel;
+use crate::commands::dry_run::render_plan;
 
 // Bulk delete pages
 pub async fn bulk_delete_pages(
@@ -21,33 +23,34 @@ pub async fn bulk_delete_pages(
         return Ok(());
     }
 
-    println!("Found {} pages to delete", page_ids.len());
-
-    if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for id in &page_ids {
-            println!("  Would delete: {}", id);
-        }
-        return Ok(());
+    if !dry_run {
+        println!("Found {} pages to delete", page_ids.len());
     }
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
     let client = ctx.client.clone();
 
-    executor
-        .run(page_ids, move |id| {
-            let client = client.clone();
-            async move {
-                let _: Value = client
-                    .delete(&format!("/wiki/api/v2/pages/{}", id))
-                    .await
-                    .with_context(|| format!("Failed to delete page {}", id))?;
-                tracing::info!(%id, "Page deleted successfully");
-                Ok(())
-            }
-        })
+    let operations = executor
+        .run_planned(
+            page_ids,
+            |id| PlannedOperation::new(id, "delete page", ""),
+            move |id| {
+                let client = client.clone();
+                async move {
+                    let _: Value = client
+                        .delete(&format!("/wiki/api/v2/pages/{}", id))
+                        .await
+                        .with_context(|| format!("Failed to delete page {}", id))?;
+                    tracing::info!(%id, "Page deleted successfully");
+                    Ok(())
+                }
+            },
+        )
         .await?;
 
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
     println!("✅ Bulk delete completed");
     Ok(())
 }
@@ -67,43 +70,45 @@ pub async fn bulk_add_labels(
         return Ok(());
     }
 
-    println!("Found {} pages to label", page_ids.len());
-
-    if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for id in &page_ids {
-            println!("  Would add labels {:?} to page {}", labels, id);
-        }
-        return Ok(());
+    if !dry_run {
+        println!("Found {} pages to label", page_ids.len());
     }
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
     let client = ctx.client.clone();
-
-    executor
-        .run(page_ids, move |id| {
-            let client = client.clone();
-            let labels = labels.clone();
-            async move {
-                let label_objects: Vec<_> = labels
-                    .iter()
-                    .map(|l| json!({"prefix": "global", "name": l}))
-                    .collect();
-
-                let _: Value = client
-                    .post(
-                        &format!("/wiki/rest/api/content/{}/label", id),
-                        &label_objects,
-                    )
-                    .await
-                    .with_context(|| format!("Failed to add labels to page {}", id))?;
-
-                tracing::info!(%id, "Labels added successfully");
-                Ok(())
-            }
-        })
+    let target = labels.join(", ");
+
+    let operations = executor
+        .run_planned(
+            page_ids,
+            |id| PlannedOperation::new(id, "add labels", target.as_str()),
+            move |id| {
+                let client = client.clone();
+                let labels = labels.clone();
+                async move {
+                    let label_objects: Vec<_> = labels
+                        .iter()
+                        .map(|l| json!({"prefix": "global", "name": l}))
+                        .collect();
+
+                    let _: Value = client
+                        .post(
+                            &format!("/wiki/rest/api/content/{}/label", id),
+                            &label_objects,
+                        )
+                        .await
+                        .with_context(|| format!("Failed to add labels to page {}", id))?;
+
+                    tracing::info!(%id, "Labels added successfully");
+                    Ok(())
+                }
+            },
+        )
         .await?;
 
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
     println!("✅ Bulk label operation completed");
     Ok(())
 }
@@ -180,7 +185,7 @@ pub async fn bulk_export_pages(
 
 // Helper function to search for page IDs using CQL
 // Note: Currently limited to 1000 results. TODO: Implement cursor-based pagination for larger result sets
-async fn search_page_ids(ctx: &ConfluenceContext<'_>, cql: &str) -> Result<Vec<String>> {
+pub(super) async fn search_page_ids(ctx: &ConfluenceContext<'_>, cql: &str) -> Result<Vec<String>> {
     const MAX_RESULTS: usize = 1000;
 
     #[derive(Deserialize)]
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::search::{fetch_cql_rows, SEARCH_LIMIT};
+use super::utils::{fetch_v2_list, ConfluenceContext};
+use crate::commands::pagination::PaginationArgs;
+
+/// Content types that can carry labels.
+#[derive(Debug, Clone, Copy)]
+pub enum ContentKind {
+    Page,
+    Blogpost,
+    Attachment,
+}
+
+impl ContentKind {
+    fn noun(self) -> &'static str {
+        match self {
+            ContentKind::Page => "page",
+            ContentKind::Blogpost => "blog post",
+            ContentKind::Attachment => "attachment",
+        }
+    }
+
+    /// The v1 content API expects numeric IDs; v2 attachment IDs carry an `att` prefix.
+    fn content_id(self, id: &str) -> &str {
+        match self {
+            ContentKind::Attachment => id.strip_prefix("att").unwrap_or(id),
+            _ => id,
+        }
+    }
+}
+
+// Add label to content
+pub async fn add_label(
+    ctx: &ConfluenceContext<'_>,
+    kind: ContentKind,
+    id: &str,
+    label: &str,
+) -> Result<()> {
+    let payload = json!([{
+        "prefix": "global",
+        "name": label
+    }]);
+
+    let _: Value = ctx
+        .client
+        .post(
+            &format!("/wiki/rest/api/content/{}/label", kind.content_id(id)),
+            &payload,
+        )
+        .await
+        .with_context(|| format!("Failed to add label to {} {}", kind.noun(), id))?;
+
+    tracing::info!(%id, %label, kind = kind.noun(), "Label added successfully");
+    println!("✅ Added label '{}' to {} {}", label, kind.noun(), id);
+    Ok(())
+}
+
+// Remove label from content
+pub async fn remove_label(
+    ctx: &ConfluenceContext<'_>,
+    kind: ContentKind,
+    id: &str,
+    label: &str,
+) -> Result<()> {
+    let _: Value = ctx
+        .client
+        .delete(&format!(
+            "/wiki/rest/api/content/{}/label?name={}",
+            kind.content_id(id),
+            urlencoding::encode(label)
+        ))
+        .await
+        .with_context(|| format!("Failed to remove label from {} {}", kind.noun(), id))?;
+
+    tracing::info!(%id, %label, kind = kind.noun(), "Label removed successfully");
+    println!("✅ Removed label '{}' from {} {}", label, kind.noun(), id);
+    Ok(())
+}
+
+// List labels used on content in a space
+pub async fn list_space_labels(
+    ctx: &ConfluenceContext<'_>,
+    space_key: &str,
+    page: &PaginationArgs,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct SpacesResponse {
+        results: Vec<Space>,
+    }
+
+    #[derive(Deserialize)]
+    struct Space {
+        id: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Label {
+        id: String,
+        name: String,
+        #[serde(default)]
+        prefix: Option<String>,
+    }
+
+    let spaces: SpacesResponse = ctx
+        .client
+        .get(&format!(
+            "/wiki/api/v2/spaces?keys={}",
+            urlencoding::encode(space_key)
+        ))
+        .await
+        .with_context(|| format!("Failed to get space {}", space_key))?;
+
+    let space_id = spaces
+        .results
+        .into_iter()
+        .next()
+        .map(|s| s.id)
+        .ok_or_else(|| anyhow!("Space '{}' not found", space_key))?;
+
+    let path = format!("/wiki/api/v2/spaces/{}/content/labels", space_id);
+    let what = format!("labels in space {}", space_key);
+    let labels: Vec<Label> = fetch_v2_list(ctx, &path, page, 250, &what).await?;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        prefix: String,
+        id: String,
+    }
+
+    let rows: Vec<Row> = labels
+        .into_iter()
+        .map(|l| Row {
+            name: l.name,
+            prefix: l.prefix.unwrap_or_default(),
+            id: l.id,
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+// List pages carrying a label
+pub async fn list_labeled_pages(
+    ctx: &ConfluenceContext<'_>,
+    label: &str,
+    space_key: Option<&str>,
+    page: &PaginationArgs,
+) -> Result<()> {
+    let cql = labeled_pages_cql(label, space_key);
+    let rows = fetch_cql_rows(ctx, &cql, page, SEARCH_LIMIT).await?;
+    ctx.renderer.render(&rows)
+}
+
+pub fn labeled_pages_cql(label: &str, space_key: Option<&str>) -> String {
+    let mut cql = format!("type = page AND label = \"{}\"", label);
+    if let Some(space) = space_key {
+        cql.push_str(&format!(" AND space = \"{}\"", space));
+    }
+    cql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labeled_pages_cql() {
+        assert_eq!(
+            labeled_pages_cql("runbook", None),
+            "type = page AND label = \"runbook\""
+        );
+        assert_eq!(
+            labeled_pages_cql("runbook", Some("OPS")),
+            "type = page AND label = \"runbook\" AND space = \"OPS\""
+        );
+    }
+
+    #[test]
+    fn test_attachment_content_id_strips_prefix() {
+        assert_eq!(ContentKind::Attachment.content_id("att12345"), "12345");
+        assert_eq!(ContentKind::Attachment.content_id("12345"), "12345");
+        assert_eq!(ContentKind::Page.content_id("98765"), "98765");
+    }
+}
@@ -0,0 +1,447 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_api::error::ApiError;
+use clap::{Args, ValueEnum};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::bulk::search_page_ids;
+use super::utils::{fetch_v2_list, ConfluenceContext};
+use crate::commands::pagination::PaginationArgs;
+
+// Page linting: checks a page's storage content, labels and attachments
+// against a set of rules and prints one row per finding. Any finding makes the
+// command fail, so `bulk lint --cql ...` can gate a CI job on a whole space
+
+/// A lint rule.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// Links to pages, page IDs or attachments that do not exist
+    BrokenLinks,
+    /// Labels from --require-labels the page does not have
+    MissingLabels,
+    /// Tables with more rows than --max-table-rows
+    HugeTables,
+    /// Attachments larger than --max-attachment-mb
+    LargeAttachments,
+}
+
+impl LintRule {
+    fn name(self) -> &'static str {
+        match self {
+            LintRule::BrokenLinks => "broken-links",
+            LintRule::MissingLabels => "missing-labels",
+            LintRule::HugeTables => "huge-tables",
+            LintRule::LargeAttachments => "large-attachments",
+        }
+    }
+}
+
+/// Rule selection and thresholds shared by `page lint` and `bulk lint`.
+#[derive(Args, Debug, Clone)]
+pub struct LintArgs {
+    /// Rules to check (comma-separated; defaults to all)
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub rules: Vec<LintRule>,
+    /// Labels every page must have (comma-separated), for missing-labels
+    #[arg(long, value_delimiter = ',')]
+    pub require_labels: Vec<String>,
+    /// Most rows a table may have
+    #[arg(long, default_value_t = 200)]
+    pub max_table_rows: usize,
+    /// Largest attachment size in megabytes
+    #[arg(long, default_value_t = 25)]
+    pub max_attachment_mb: u64,
+}
+
+impl LintArgs {
+    fn enabled(&self, rule: LintRule) -> bool {
+        self.rules.is_empty() || self.rules.contains(&rule)
+    }
+}
+
+#[derive(Deserialize)]
+struct Page {
+    id: String,
+    title: String,
+    #[serde(default)]
+    space: Option<Space>,
+    #[serde(default)]
+    body: Option<Body>,
+    #[serde(default)]
+    metadata: Option<Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Space {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct Body {
+    storage: Storage,
+}
+
+#[derive(Deserialize)]
+struct Storage {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    labels: LabelList,
+}
+
+#[derive(Deserialize)]
+struct LabelList {
+    results: Vec<Label>,
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    title: String,
+    #[serde(rename = "fileSize", default)]
+    file_size: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct Finding {
+    page_id: String,
+    title: String,
+    rule: &'static str,
+    detail: String,
+}
+
+/// Links found in storage content, before they are resolved.
+#[derive(Debug, Default, PartialEq)]
+struct Links {
+    /// `(space key, title)` of `<ri:page>` links; the space is the page's own
+    /// when the link does not name one.
+    titles: Vec<(String, String)>,
+    /// Page IDs from `/pages/123` and `?pageId=123` URLs on this site.
+    ids: Vec<String>,
+    /// File names of attachments on the page itself.
+    attachments: Vec<String>,
+}
+
+/// Lint one page.
+pub async fn lint_page(ctx: &ConfluenceContext<'_>, page_id: &str, args: &LintArgs) -> Result<()> {
+    let findings = page_findings(ctx, page_id, args).await?;
+    report(ctx, &findings, 1)
+}
+
+/// Lint every page matching a CQL query.
+pub async fn bulk_lint(
+    ctx: &ConfluenceContext<'_>,
+    cql: &str,
+    args: &LintArgs,
+    concurrency: usize,
+) -> Result<()> {
+    let page_ids = search_page_ids(ctx, cql).await?;
+    if page_ids.is_empty() {
+        println!("No pages matched the CQL query");
+        return Ok(());
+    }
+
+    let findings: Vec<Vec<Finding>> = stream::iter(page_ids.iter())
+        .map(|id| page_findings(ctx, id, args))
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    let findings: Vec<Finding> = findings.into_iter().flatten().collect();
+    report(ctx, &findings, page_ids.len())
+}
+
+fn report(ctx: &ConfluenceContext<'_>, findings: &[Finding], pages: usize) -> Result<()> {
+    if findings.is_empty() {
+        tracing::info!(pages, "Lint passed");
+        println!("✅ No lint findings on {pages} page(s)");
+        return Ok(());
+    }
+    ctx.renderer.render(&findings)?;
+    let failing: HashSet<&str> = findings.iter().map(|f| f.page_id.as_str()).collect();
+    bail!(
+        "{} lint finding(s) on {} of {pages} page(s)",
+        findings.len(),
+        failing.len()
+    );
+}
+
+async fn page_findings(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    args: &LintArgs,
+) -> Result<Vec<Finding>> {
+    let page: Page = ctx
+        .client
+        .get(&format!(
+            "/wiki/rest/api/content/{page_id}?expand=body.storage,metadata.labels,space"
+        ))
+        .await
+        .with_context(|| format!("Failed to get page {page_id}"))?;
+    let storage = page.body.as_ref().map_or("", |b| b.storage.value.as_str());
+    let mut details: Vec<(LintRule, String)> = Vec::new();
+
+    let needs_attachments =
+        args.enabled(LintRule::BrokenLinks) || args.enabled(LintRule::LargeAttachments);
+    let attachments: Vec<Attachment> = if needs_attachments {
+        let everything = PaginationArgs {
+            all: true,
+            ..Default::default()
+        };
+        let path = format!("/wiki/api/v2/pages/{page_id}/attachments");
+        let what = format!("attachments of page {page_id}");
+        fetch_v2_list(ctx, &path, &everything, 250, &what).await?
+    } else {
+        Vec::new()
+    };
+
+    if args.enabled(LintRule::BrokenLinks) {
+        let space = page.space.as_ref().map_or("", |s| s.key.as_str());
+        let links = extract_links(storage, space, ctx.client.base_url());
+        for message in broken_links(ctx, &links, &attachments).await? {
+            details.push((LintRule::BrokenLinks, message));
+        }
+    }
+
+    if args.enabled(LintRule::MissingLabels) {
+        let labels: Vec<&str> = page
+            .metadata
+            .as_ref()
+            .map(|m| m.labels.results.iter().map(|l| l.name.as_str()).collect())
+            .unwrap_or_default();
+        let missing = missing_labels(&labels, &args.require_labels);
+        if !missing.is_empty() {
+            details.push((
+                LintRule::MissingLabels,
+                format!("missing label(s): {}", missing.join(", ")),
+            ));
+        }
+    }
+
+    if args.enabled(LintRule::HugeTables) {
+        for (index, rows) in table_rows(storage).into_iter().enumerate() {
+            if rows > args.max_table_rows {
+                details.push((
+                    LintRule::HugeTables,
+                    format!(
+                        "table {} has {rows} rows (max {})",
+                        index + 1,
+                        args.max_table_rows
+                    ),
+                ));
+            }
+        }
+    }
+
+    if args.enabled(LintRule::LargeAttachments) {
+        let limit = args.max_attachment_mb * 1024 * 1024;
+        for attachment in attachments.iter().filter(|a| a.file_size > limit) {
+            details.push((
+                LintRule::LargeAttachments,
+                format!(
+                    "{} is {:.1} MB (max {} MB)",
+                    attachment.title,
+                    attachment.file_size as f64 / (1024.0 * 1024.0),
+                    args.max_attachment_mb
+                ),
+            ));
+        }
+    }
+
+    Ok(details
+        .into_iter()
+        .map(|(rule, detail)| Finding {
+            page_id: page.id.clone(),
+            title: page.title.clone(),
+            rule: rule.name(),
+            detail,
+        })
+        .collect())
+}
+
+/// Resolve the links through the API and describe the ones that do not exist.
+async fn broken_links(
+    ctx: &ConfluenceContext<'_>,
+    links: &Links,
+    attachments: &[Attachment],
+) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct ContentList {
+        results: Vec<Value>,
+    }
+
+    let mut broken = Vec::new();
+    let mut checked: HashMap<String, bool> = HashMap::new();
+
+    for (space, title) in &links.titles {
+        let key = format!("{space}/{title}");
+        if checked.contains_key(&key) {
+            continue;
+        }
+        let found: ContentList = ctx
+            .client
+            .get(&format!(
+                "/wiki/rest/api/content?type=page&spaceKey={}&title={}&limit=1",
+                urlencoding::encode(space),
+                urlencoding::encode(title)
+            ))
+            .await
+            .with_context(|| format!("Failed to look up page '{title}' in space {space}"))?;
+        let exists = !found.results.is_empty();
+        if !exists {
+            broken.push(format!("link to missing page '{title}' in space {space}"));
+        }
+        checked.insert(key, exists);
+    }
+
+    for id in &links.ids {
+        if checked.contains_key(id) {
+            continue;
+        }
+        let exists = match ctx
+            .client
+            .get::<Value>(&format!("/wiki/api/v2/pages/{id}"))
+            .await
+        {
+            Ok(_) => true,
+            Err(ApiError::NotFound { .. }) => false,
+            Err(err) => {
+                return Err(anyhow::Error::new(err).context(format!("Failed to look up page {id}")))
+            }
+        };
+        if !exists {
+            broken.push(format!("link to missing page ID {id}"));
+        }
+        checked.insert(id.clone(), exists);
+    }
+
+    let names: HashSet<&str> = attachments.iter().map(|a| a.title.as_str()).collect();
+    let mut reported = HashSet::new();
+    for file in &links.attachments {
+        if !names.contains(file.as_str()) && reported.insert(file) {
+            broken.push(format!("link to missing attachment '{file}'"));
+        }
+    }
+
+    Ok(broken)
+}
+
+/// Page, page ID and attachment links in storage content. Links to pages on
+/// other sites are ignored.
+fn extract_links(storage: &str, space: &str, base_url: &str) -> Links {
+    let page_ref = Regex::new(r"<ri:page\s([^>]*?)/?>").expect("valid regex");
+    let title_attr = Regex::new(r#"ri:content-title="([^"]*)""#).expect("valid regex");
+    let space_attr = Regex::new(r#"ri:space-key="([^"]*)""#).expect("valid regex");
+    let own_attachment =
+        Regex::new(r#"<ri:attachment\s+ri:filename="([^"]+)"\s*/>"#).expect("valid regex");
+    let href = Regex::new(r#"href="([^"]*)""#).expect("valid regex");
+    let page_link = Regex::new(r"/pages/(\d+)|[?&]pageId=(\d+)").expect("valid regex");
+
+    let mut links = Links::default();
+    for page in page_ref.captures_iter(storage) {
+        let attributes = &page[1];
+        let Some(title) = title_attr.captures(attributes) else {
+            continue;
+        };
+        let space = space_attr
+            .captures(attributes)
+            .map_or(space.to_string(), |s| unescape(&s[1]));
+        links.titles.push((space, unescape(&title[1])));
+    }
+    for file in own_attachment.captures_iter(storage) {
+        links.attachments.push(unescape(&file[1]));
+    }
+    for link in href.captures_iter(storage) {
+        let url = unescape(&link[1]);
+        if !(url.starts_with("/wiki/") || url.starts_with(base_url)) || url.contains("/download/") {
+            continue;
+        }
+        if let Some(found) = page_link.captures(&url) {
+            let id = found.get(1).or(found.get(2)).map_or("", |m| m.as_str());
+            links.ids.push(id.to_string());
+        }
+    }
+    links
+}
+
+/// Row count of each table, in document order.
+fn table_rows(storage: &str) -> Vec<usize> {
+    let table = Regex::new(r"(?s)<table\b.*?</table>").expect("valid regex");
+    let row = Regex::new(r"<tr\b").expect("valid regex");
+    table
+        .find_iter(storage)
+        .map(|t| row.find_iter(t.as_str()).count())
+        .collect()
+}
+
+fn missing_labels(labels: &[&str], required: &[String]) -> Vec<String> {
+    required
+        .iter()
+        .filter(|r| !labels.iter().any(|l| l.eq_ignore_ascii_case(r)))
+        .cloned()
+        .collect()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let storage = r#"
+<p><ac:link><ri:page ri:content-title="Release &amp; Deploy" /></ac:link></p>
+<p><ac:link><ri:page ri:space-key="OPS" ri:content-title="Runbook" /></ac:link></p>
+<p><ac:link><ri:attachment ri:filename="diagram.png" /></ac:link></p>
+<p><ac:link><ri:attachment ri:filename="other.pdf"><ri:page ri:content-title="Specs" /></ri:attachment></ac:link></p>
+<p><a href="https://acme.atlassian.net/wiki/spaces/ENG/pages/12345/Intro">intro</a></p>
+<p><a href="/wiki/pages/viewpage.action?pageId=678&amp;x=1">old</a></p>
+<p><a href="https://example.com/pages/999">elsewhere</a></p>
+"#;
+        let links = extract_links(storage, "ENG", "https://acme.atlassian.net/wiki");
+        assert_eq!(
+            links.titles,
+            vec![
+                ("ENG".to_string(), "Release & Deploy".to_string()),
+                ("OPS".to_string(), "Runbook".to_string()),
+                ("ENG".to_string(), "Specs".to_string()),
+            ]
+        );
+        assert_eq!(links.ids, vec!["12345", "678"]);
+        assert_eq!(links.attachments, vec!["diagram.png"]);
+    }
+
+    #[test]
+    fn test_table_rows() {
+        let storage = "<table><tbody><tr><th>a</th></tr><tr><td>1</td></tr></tbody></table>\
+                       <p>text</p><table class=\"wide\"><tr><td>x</td></tr></table>";
+        assert_eq!(table_rows(storage), vec![2, 1]);
+        assert!(table_rows("<p>no tables</p>").is_empty());
+    }
+
+    #[test]
+    fn test_missing_labels() {
+        let required = vec!["owner-team".to_string(), "reviewed".to_string()];
+        assert_eq!(
+            missing_labels(&["Reviewed", "draft"], &required),
+            vec!["owner-team"]
+        );
+        assert!(missing_labels(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use super::mentions::escape;
+
+// Markdown to Confluence storage format. Covers the common subset used in
+// docs (headings, paragraphs, lists, quotes, rules, emphasis, links, images)
+// and maps fenced code, `> [!NOTE]` admonitions and `[TOC]` to native macros.
+
+/// Convert a Markdown document to Confluence storage format.
+pub fn to_storage(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = String::new();
+    convert_blocks(&lines, &mut out);
+    out
+}
+
+fn convert_blocks(lines: &[&str], out: &mut String) {
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some((fence, language)) = code_fence(trimmed) {
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with(fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            out.push_str(&code_macro(language, &code.join("\n")));
+        } else if trimmed.eq_ignore_ascii_case("[toc]") {
+            out.push_str("<ac:structured-macro ac:name=\"toc\" />");
+            i += 1;
+        } else if let Some(level) = heading_level(trimmed) {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim_end();
+            out.push_str(&format!("<h{level}>{}</h{level}>", inline(text)));
+            i += 1;
+        } else if is_rule(trimmed) {
+            out.push_str("<hr />");
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let content = lines[i].trim_start()[1..].strip_prefix(' ');
+                quoted.push(content.unwrap_or(&lines[i].trim_start()[1..]));
+                i += 1;
+            }
+            out.push_str(&blockquote(&quoted));
+        } else if list_marker(line).is_some() {
+            let start = i;
+            while i < lines.len()
+                && !lines[i].trim().is_empty()
+                && (list_marker(lines[i]).is_some() || indent(lines[i]) > indent(lines[start]))
+            {
+                i += 1;
+            }
+            out.push_str(&list(&lines[start..i]));
+        } else {
+            let mut paragraph = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines[i]) {
+                paragraph.push(lines[i].trim());
+                i += 1;
+            }
+            out.push_str(&format!("<p>{}</p>", inline(&paragraph.join(" "))));
+        }
+    }
+}
+
+/// Whether a line begins a block other than a paragraph continuation.
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim();
+    code_fence(trimmed).is_some()
+        || heading_level(trimmed).is_some()
+        || is_rule(trimmed)
+        || trimmed.starts_with('>')
+        || list_marker(line).is_some()
+        || trimmed.eq_ignore_ascii_case("[toc]")
+}
+
+fn code_fence(line: &str) -> Option<(&str, &str)> {
+    ["```", "~~~"]
+        .into_iter()
+        .find(|fence| line.starts_with(fence))
+        .map(|fence| (fence, line.trim_start_matches(fence).trim()))
+}
+
+fn code_macro(language: &str, code: &str) -> String {
+    let mut out = String::from("<ac:structured-macro ac:name=\"code\">");
+    if let Some(language) = language.split_whitespace().next() {
+        out.push_str(&format!(
+            "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+            escape(language)
+        ));
+    }
+    out.push_str(&format!(
+        "<ac:plain-text-body><![CDATA[{}]]></ac:plain-text-body></ac:structured-macro>",
+        code.replace("]]>", "]]]]><![CDATA[>")
+    ));
+    out
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&marker| chars.iter().all(|&c| c == marker))
+}
+
+/// Blockquote, or an info/tip/note/warning panel for GitHub-style
+/// `> [!NOTE]` admonitions (text after the marker becomes the panel title).
+fn blockquote(lines: &[&str]) -> String {
+    let mut body = String::new();
+    if let Some((name, title)) = lines.first().and_then(|first| admonition(first)) {
+        convert_blocks(&lines[1..], &mut body);
+        let title = if title.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<ac:parameter ac:name=\"title\">{}</ac:parameter>",
+                escape(title)
+            )
+        };
+        return format!(
+            "<ac:structured-macro ac:name=\"{name}\">{title}<ac:rich-text-body>{body}</ac:rich-text-body></ac:structured-macro>"
+        );
+    }
+    convert_blocks(lines, &mut body);
+    format!("<blockquote>{body}</blockquote>")
+}
+
+fn admonition(line: &str) -> Option<(&'static str, &str)> {
+    let rest = line.trim().strip_prefix("[!")?;
+    let (kind, title) = rest.split_once(']')?;
+    let name = match kind.to_ascii_uppercase().as_str() {
+        "NOTE" | "INFO" => "info",
+        "TIP" => "tip",
+        "IMPORTANT" => "note",
+        "WARNING" | "CAUTION" => "warning",
+        _ => return None,
+    };
+    Some((name, title.trim()))
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// `(ordered, content)` for a list item line.
+fn list_marker(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(content) = trimmed.strip_prefix(bullet) {
+            return (!is_rule(trimmed)).then_some((false, content));
+        }
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(content) = trimmed[digits..].strip_prefix(". ") {
+            return Some((true, content));
+        }
+    }
+    None
+}
+
+/// A list starting at `lines[0]`; more-indented lines belong to the item above
+/// them and are converted as nested blocks.
+fn list(lines: &[&str]) -> String {
+    let base = indent(lines[0]);
+    let ordered = list_marker(lines[0]).is_some_and(|(ordered, _)| ordered);
+    let tag = if ordered { "ol" } else { "ul" };
+
+    let mut out = format!("<{tag}>");
+    let mut i = 0;
+    while i < lines.len() {
+        let content = list_marker(lines[i]).map_or(lines[i].trim(), |(_, content)| content);
+        i += 1;
+        let start = i;
+        while i < lines.len() && indent(lines[i]) > base {
+            i += 1;
+        }
+
+        out.push_str(&format!("<li>{}", inline(content.trim())));
+        if start < i {
+            let nested: Vec<&str> = lines[start..i].to_vec();
+            convert_blocks(&nested, &mut out);
+        }
+        out.push_str("</li>");
+    }
+    out.push_str(&format!("</{tag}>"));
+    out
+}
+
+/// Inline formatting: code spans, images, links, strong and emphasis.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                out.push_str(&format!("<code>{}</code>", escape(&rest[1..1 + end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if c == '!' || c == '[' {
+            let is_image = c == '!';
+            let open = usize::from(is_image);
+            if let Some((label, url, len)) = link_at(&rest[open..]) {
+                if is_image {
+                    out.push_str(&format!(
+                        "<ac:image ac:alt=\"{}\"><ri:url ri:value=\"{}\" /></ac:image>",
+                        escape(label),
+                        escape(url)
+                    ));
+                } else {
+                    out.push_str(&format!(
+                        "<a href=\"{}\">{}</a>",
+                        escape(url),
+                        inline(label)
+                    ));
+                }
+                rest = &rest[open + len..];
+                continue;
+            }
+        }
+        let prev = text[..text.len() - rest.len()].chars().next_back();
+        // Underscores inside words (snake_case) are literal
+        let intraword = c == '_' && prev.is_some_and(char::is_alphanumeric);
+        if (c == '*' || c == '_') && !intraword {
+            let double = rest.len() > 1 && rest[1..].starts_with(c);
+            let delimiter = if double { &rest[..2] } else { &rest[..1] };
+            let inner = &rest[delimiter.len()..];
+            if let Some(end) = inner.find(delimiter).filter(|&end| end > 0) {
+                let tag = if double { "strong" } else { "em" };
+                out.push_str(&format!("<{tag}>{}</{tag}>", inline(&inner[..end])));
+                rest = &inner[end + delimiter.len()..];
+                continue;
+            }
+        }
+
+        out.push_str(&escape(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// `[label](url)` at the start of `text`: the label, URL and total length.
+fn link_at(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.strip_prefix('[')?.find(']')? + 1;
+    let after = text[label_end + 1..].strip_prefix('(')?;
+    let url_end = after.find(')')?;
+    Some((
+        &text[1..label_end],
+        after[..url_end].trim(),
+        label_end + 2 + url_end + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_and_inline() {
+        let markdown = "# Release *notes*\n\nShip **v2** with `cargo` & [docs](https://example.com/a?b=1&c=2).\nSecond line.\n\n- one\n- two\n  1. nested\n\n---";
+        assert_eq!(
+            to_storage(markdown),
+            "<h1>Release <em>notes</em></h1>\
+             <p>Ship <strong>v2</strong> with <code>cargo</code> &amp; \
+             <a href=\"https://example.com/a?b=1&amp;c=2\">docs</a>. Second line.</p>\
+             <ul><li>one</li><li>two<ol><li>nested</li></ol></li></ul><hr />"
+        );
+    }
+
+    #[test]
+    fn test_code_macro() {
+        assert_eq!(
+            to_storage("```rust\nfn main() {}\nlet x = \"]]>\";\n```"),
+            "<ac:structured-macro ac:name=\"code\"><ac:parameter ac:name=\"language\">rust</ac:parameter>\
+             <ac:plain-text-body><![CDATA[fn main() {}\nlet x = \"]]]]><![CDATA[>\";]]></ac:plain-text-body>\
+             </ac:structured-macro>"
+        );
+        assert_eq!(
+            to_storage("~~~\n<b>\n~~~"),
+            "<ac:structured-macro ac:name=\"code\"><ac:plain-text-body><![CDATA[<b>]]></ac:plain-text-body></ac:structured-macro>"
+        );
+    }
+
+    #[test]
+    fn test_panels_and_toc() {
+        assert_eq!(
+            to_storage("[TOC]\n\n> [!WARNING] Downtime\n> Deploys pause at **18:00**.\n\n> Just a quote"),
+            "<ac:structured-macro ac:name=\"toc\" />\
+             <ac:structured-macro ac:name=\"warning\"><ac:parameter ac:name=\"title\">Downtime</ac:parameter>\
+             <ac:rich-text-body><p>Deploys pause at <strong>18:00</strong>.</p></ac:rich-text-body></ac:structured-macro>\
+             <blockquote><p>Just a quote</p></blockquote>"
+        );
+        assert_eq!(
+            to_storage("> [!NOTE]\n> Read this"),
+            "<ac:structured-macro ac:name=\"info\"><ac:rich-text-body><p>Read this</p></ac:rich-text-body></ac:structured-macro>"
+        );
+    }
+
+    #[test]
+    fn test_images_and_literals() {
+        assert_eq!(
+            inline("![diagram](https://example.com/d.png) 2*3 snake_case_name _em_"),
+            "<ac:image ac:alt=\"diagram\"><ri:url ri:value=\"https://example.com/d.png\" /></ac:image> \
+             2*3 snake_case_name <em>em</em>"
+        );
+    }
+}
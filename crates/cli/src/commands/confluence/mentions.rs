@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use super::utils::ConfluenceContext;
+
+// `@user@example.com` mentions in plain text, turned into Confluence user links
+
+/// Email addresses mentioned as `@user@example.com`, in order of appearance.
+pub fn find_mentions(text: &str) -> Vec<String> {
+    let mut mentions = Vec::new();
+    for (start, _) in text.match_indices('@') {
+        if !at_word_start(text, start) {
+            continue;
+        }
+        if let Some(email) = email_at(&text[start + 1..]) {
+            if !mentions.iter().any(|m| m == email) {
+                mentions.push(email.to_string());
+            }
+        }
+    }
+    mentions
+}
+
+/// Replace mentions of resolved users with user links. The rest of `text` is
+/// left as it is, so storage markup around the mentions still works.
+pub fn link_mentions(text: &str, account_ids: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(pos) = rest.find('@') {
+        let offset = text.len() - rest.len();
+        let mention = at_word_start(text, offset + pos)
+            .then(|| email_at(&rest[pos + 1..]))
+            .flatten()
+            .and_then(|email| account_ids.get(email).map(|id| (email.len(), id)));
+
+        match mention {
+            Some((len, account_id)) => {
+                out.push_str(&rest[..pos]);
+                out.push_str(&user_link(account_id));
+                rest = &rest[pos + 1 + len..];
+            }
+            None => {
+                out.push_str(&rest[..=pos]);
+                rest = &rest[pos + 1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Mentions in the text of storage markup, as `find_mentions` finds them.
+/// Code and CDATA sections are left out, so addresses in code never count.
+pub fn find_storage_mentions(storage: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    for range in text_ranges(storage) {
+        for email in find_mentions(&storage[range]) {
+            if !mentions.contains(&email) {
+                mentions.push(email);
+            }
+        }
+    }
+    mentions
+}
+
+/// `link_mentions` applied to the text of storage markup only; tags,
+/// attributes, code and CDATA sections are copied unchanged.
+pub fn link_storage_mentions(storage: &str, account_ids: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut copied = 0;
+    for range in text_ranges(storage) {
+        out.push_str(&storage[copied..range.start]);
+        out.push_str(&link_mentions(&storage[range.clone()], account_ids));
+        copied = range.end;
+    }
+    out.push_str(&storage[copied..]);
+    out
+}
+
+/// Byte ranges of the text between tags, skipping code and CDATA sections.
+fn text_ranges(storage: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while pos < storage.len() {
+        let Some(lt) = storage[pos..].find('<').map(|i| pos + i) else {
+            ranges.push(pos..storage.len());
+            break;
+        };
+        if lt > pos {
+            ranges.push(pos..lt);
+        }
+        let tag = &storage[lt..];
+        let close = if tag.starts_with("<![CDATA[") {
+            "]]>"
+        } else if tag.starts_with("<code>") || tag.starts_with("<code ") {
+            "</code>"
+        } else {
+            ">"
+        };
+        pos = tag
+            .find(close)
+            .map_or(storage.len(), |end| lt + end + close.len());
+    }
+    ranges
+}
+
+pub fn user_link(account_id: &str) -> String {
+    format!(
+        "<ac:link><ri:user ri:account-id=\"{}\" /></ac:link>",
+        escape(account_id)
+    )
+}
+
+/// Look up account IDs for emails; values without an `@` are taken as account IDs.
+pub async fn resolve_users(
+    ctx: &ConfluenceContext<'_>,
+    users: &[String],
+) -> Result<HashMap<String, String>> {
+    #[derive(Deserialize)]
+    struct User {
+        #[serde(rename = "accountId")]
+        account_id: String,
+        #[serde(rename = "emailAddress", default)]
+        email_address: Option<String>,
+    }
+
+    let mut resolved = HashMap::new();
+    for user in users {
+        if !user.contains('@') {
+            resolved.insert(user.clone(), user.clone());
+            continue;
+        }
+
+        // The site's user search matches email addresses; CQL user search
+        // only looks at display names
+        let mut users: Vec<User> = ctx
+            .client
+            .get(&format!(
+                "/rest/api/3/user/search?query={}",
+                urlencoding::encode(user)
+            ))
+            .await
+            .with_context(|| format!("Failed to look up user {user}"))?;
+        // Prefix matches on other addresses are dropped when emails are visible
+        if users.len() > 1 {
+            users.retain(|u| {
+                u.email_address
+                    .as_deref()
+                    .is_none_or(|email| email.eq_ignore_ascii_case(user))
+            });
+        }
+
+        match users.as_slice() {
+            [single] => {
+                resolved.insert(user.clone(), single.account_id.clone());
+            }
+            [] => return Err(anyhow!("No Confluence user found for '{}'", user)),
+            _ => {
+                return Err(anyhow!(
+                    "'{}' matches several users; pass the account ID instead",
+                    user
+                ))
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+fn at_word_start(text: &str, index: usize) -> bool {
+    text[..index]
+        .chars()
+        .next_back()
+        .is_none_or(|c| c.is_whitespace() || "([{,;".contains(c))
+}
+
+/// The email address at the start of `text`, without trailing punctuation.
+fn email_at(text: &str) -> Option<&str> {
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || "._%+-@".contains(c)))
+        .unwrap_or(text.len());
+    let candidate = text[..end].trim_end_matches(['.', '-']);
+
+    let (local, domain) = candidate.split_once('@')?;
+    let valid = !local.is_empty()
+        && domain.contains('.')
+        && !domain.contains('@')
+        && !domain.starts_with('.');
+    valid.then_some(candidate)
+}
+
+pub(super) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mentions() {
+        assert_eq!(
+            find_mentions(
+                "@ana@example.com and @bo@example.org, please review. cc @ana@example.com."
+            ),
+            vec!["ana@example.com", "bo@example.org"]
+        );
+        assert!(find_mentions("mail ana@example.com or @team").is_empty());
+    }
+
+    #[test]
+    fn test_storage_mentions() {
+        let storage = "<p>@ana@example.com, see <a href=\"mailto:@bo@example.com\">mail</a></p>\
+            <ul><li><code>@cy@example.com</code> @bo@example.com</li></ul>\
+            <ac:plain-text-body><![CDATA[@dee@example.com <p>]]></ac:plain-text-body>";
+        assert_eq!(
+            find_storage_mentions(storage),
+            vec!["ana@example.com", "bo@example.com"]
+        );
+
+        let ids = HashMap::from([
+            ("ana@example.com".to_string(), "557058:ana".to_string()),
+            ("dee@example.com".to_string(), "557058:dee".to_string()),
+        ]);
+        assert_eq!(
+            link_storage_mentions(storage, &ids),
+            storage.replacen("@ana@example.com", &user_link("557058:ana"), 1)
+        );
+    }
+
+    #[test]
+    fn test_link_mentions() {
+        let ids = HashMap::from([("ana@example.com".to_string(), "557058:abc".to_string())]);
+        assert_eq!(
+            link_mentions("Hi @ana@example.com, see <b>this</b> & @bo@example.com.", &ids),
+            "Hi <ac:link><ri:user ri:account-id=\"557058:abc\" /></ac:link>, see <b>this</b> & @bo@example.com."
+        );
+        assert_eq!(
+            link_mentions("<i>no mentions</i>", &ids),
+            "<i>no mentions</i>"
+        );
+    }
+}
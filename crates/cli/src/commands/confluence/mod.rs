@@ -7,11 +7,26 @@ use clap::{Args, Subcommand};
 mod analytics;
 mod attachments;
 mod bulk;
+mod labels;
+mod lint;
+mod markdown;
+mod mentions;
 mod pages;
+mod permissions;
+pub mod publish;
+mod scheduler;
 mod search;
+mod site;
 mod spaces;
 pub mod utils;
 
+pub use spaces::SpaceTemplate;
+
+use super::dates;
+use super::entity_url;
+use super::multi_profile::{self, ProfileTarget};
+use super::pagination::PaginationArgs;
+use labels::ContentKind;
 use utils::ConfluenceContext;
 
 #[derive(Args, Debug, Clone)]
@@ -38,6 +53,10 @@ enum ConfluenceCommands {
     #[command(subcommand)]
     Attachment(AttachmentCommands),
 
+    /// Label navigation
+    #[command(subcommand)]
+    Label(LabelCommands),
+
     /// Search operations
     #[command(subcommand)]
     Search(SearchCommands),
@@ -49,15 +68,26 @@ enum ConfluenceCommands {
     /// Analytics operations
     #[command(subcommand)]
     Analytics(AnalyticsCommands),
+
+    /// Permission reviews
+    #[command(subcommand)]
+    Permission(PermissionCommands),
+
+    /// Scheduled publishing of page and blog post drafts
+    #[command(subcommand)]
+    Scheduler(SchedulerCommands),
+
+    /// Export content outside Confluence
+    #[command(subcommand)]
+    Export(ExportCommands),
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum SpaceCommands {
     /// List spaces
     List {
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
         /// Filter by space type (global, personal)
         #[arg(long)]
         space_type: Option<String>,
@@ -78,6 +108,18 @@ enum SpaceCommands {
         /// Space description
         #[arg(long)]
         description: Option<String>,
+        /// Homepage and permission baseline: team, project, kb, or a template YAML file
+        #[arg(long, value_name = "TEMPLATE")]
+        from_template: Option<String>,
+        /// Group granted the template's admin permissions
+        #[arg(long)]
+        admin_group: Option<String>,
+        /// Group granted the template's read permissions
+        #[arg(long)]
+        read_group: Option<String>,
+        /// Succeed and show the existing space if the key is already taken
+        #[arg(long)]
+        if_not_exists: bool,
     },
     /// Update space
     Update {
@@ -103,6 +145,9 @@ enum SpaceCommands {
         /// Space key
         key: String,
     },
+    /// Copy space permission grants between spaces
+    #[command(subcommand)]
+    Permission(SpacePermissionCommands),
     /// Add space permission
     AddPermission {
         /// Space key
@@ -119,6 +164,22 @@ enum SpaceCommands {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum SpacePermissionCommands {
+    /// Apply every user and group grant of one space to another
+    Copy {
+        /// Space key to copy permissions from
+        #[arg(long)]
+        from: String,
+        /// Space key to grant the permissions in
+        #[arg(long)]
+        to: String,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 enum PageCommands {
     /// List pages
@@ -126,13 +187,13 @@ enum PageCommands {
         /// Filter by space key
         #[arg(long)]
         space: Option<String>,
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get page details
     Get {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
     },
     /// Create a new page
@@ -143,27 +204,32 @@ enum PageCommands {
         /// Page title
         #[arg(long)]
         title: String,
-        /// Body content file (HTML storage format)
+        /// Body content file (HTML storage format, or Markdown for .md files)
         #[arg(long)]
         body: Option<std::path::PathBuf>,
         /// Parent page ID
-        #[arg(long)]
+        #[arg(long, value_parser = entity_url::page_id)]
         parent: Option<String>,
     },
     /// Update a page
     Update {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// New page title
         #[arg(long)]
         title: Option<String>,
-        /// New body content file (HTML storage format)
+        /// New body content file (HTML storage format, or Markdown for .md files)
         #[arg(long)]
         body: Option<std::path::PathBuf>,
+        /// Show a diff of what would change without updating the page
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Delete a page
     Delete {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Force deletion without confirmation
         #[arg(long)]
@@ -172,11 +238,22 @@ enum PageCommands {
     /// List page versions
     Versions {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
+        page_id: String,
+    },
+    /// Publish the page's draft at a set time (needs `confluence scheduler run`)
+    PublishAt {
+        /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
+        /// When to publish: local time (2024-07-01T09:00) or RFC 3339
+        #[arg(long)]
+        at: String,
     },
     /// Add label to page
     AddLabel {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Label name
         label: String,
@@ -184,6 +261,7 @@ enum PageCommands {
     /// Remove label from page
     RemoveLabel {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Label name
         label: String,
@@ -191,23 +269,39 @@ enum PageCommands {
     /// List page comments
     Comments {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
     },
-    /// Add comment to page
+    /// Add comment to page (`@user@example.com` mentions the user)
     AddComment {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Comment text
         comment: String,
     },
+    /// Share a page with users by mentioning them in a comment
+    Share {
+        /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
+        page_id: String,
+        /// Users to notify (emails or account IDs, comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        users: Vec<String>,
+        /// Message to include
+        #[arg(long)]
+        message: Option<String>,
+    },
     /// Get page restrictions
     GetRestrictions {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
     },
     /// Add page restriction
     AddRestriction {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Operation (read, update)
         #[arg(long)]
@@ -222,6 +316,7 @@ enum PageCommands {
     /// Remove page restriction
     RemoveRestriction {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// Operation (read, update)
         #[arg(long)]
@@ -233,6 +328,15 @@ enum PageCommands {
         #[arg(long)]
         subject_id: String,
     },
+    /// Check a page for broken links, missing labels, huge tables and
+    /// oversized attachments; exits non-zero when anything is found
+    Lint {
+        /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
+        page_id: String,
+        #[command(flatten)]
+        lint: lint::LintArgs,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -242,9 +346,8 @@ enum BlogCommands {
         /// Filter by space ID
         #[arg(long)]
         space: Option<String>,
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get blog post details
     Get {
@@ -259,7 +362,7 @@ enum BlogCommands {
         /// Blog post title
         #[arg(long)]
         title: String,
-        /// Body content file (HTML storage format)
+        /// Body content file (HTML storage format, or Markdown for .md files)
         #[arg(long)]
         body: Option<std::path::PathBuf>,
     },
@@ -270,10 +373,18 @@ enum BlogCommands {
         /// New blog post title
         #[arg(long)]
         title: Option<String>,
-        /// New body content file (HTML storage format)
+        /// New body content file (HTML storage format, or Markdown for .md files)
         #[arg(long)]
         body: Option<std::path::PathBuf>,
     },
+    /// Publish the blog post's draft at a set time (needs `confluence scheduler run`)
+    PublishAt {
+        /// Blog post ID
+        blogpost_id: String,
+        /// When to publish: local time (2024-07-01T09:00) or RFC 3339
+        #[arg(long)]
+        at: String,
+    },
     /// Delete a blog post
     Delete {
         /// Blog post ID
@@ -282,6 +393,20 @@ enum BlogCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Add label to blog post
+    AddLabel {
+        /// Blog post ID
+        blogpost_id: String,
+        /// Label name
+        label: String,
+    },
+    /// Remove label from blog post
+    RemoveLabel {
+        /// Blog post ID
+        blogpost_id: String,
+        /// Label name
+        label: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -289,6 +414,7 @@ enum AttachmentCommands {
     /// List attachments for a page
     List {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
     },
     /// Get attachment details
@@ -299,6 +425,7 @@ enum AttachmentCommands {
     /// Upload an attachment
     Upload {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
         /// File path to upload
         #[arg(long)]
@@ -306,6 +433,16 @@ enum AttachmentCommands {
         /// Optional comment
         #[arg(long)]
         comment: Option<String>,
+        /// Upload as a new version of the page's attachment with the same file name
+        #[arg(long)]
+        replace_existing: bool,
+    },
+    /// List versions of an attachment
+    Versions {
+        /// Attachment ID
+        attachment_id: String,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Download an attachment
     Download {
@@ -323,6 +460,42 @@ enum AttachmentCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Add label to attachment
+    AddLabel {
+        /// Attachment ID
+        attachment_id: String,
+        /// Label name
+        label: String,
+    },
+    /// Remove label from attachment
+    RemoveLabel {
+        /// Attachment ID
+        attachment_id: String,
+        /// Label name
+        label: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum LabelCommands {
+    /// List labels used on content in a space
+    List {
+        /// Space key
+        #[arg(long)]
+        space: String,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// List pages with a label
+    Pages {
+        /// Label name
+        label: String,
+        /// Restrict to a space key
+        #[arg(long)]
+        space: Option<String>,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -331,17 +504,15 @@ enum SearchCommands {
     Cql {
         /// CQL query
         query: String,
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Text search
     Text {
         /// Search query
         query: String,
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Search in space
     InSpace {
@@ -349,9 +520,8 @@ enum SearchCommands {
         space: String,
         /// Search query
         query: String,
-        /// Maximum number of results
-        #[arg(long)]
-        limit: Option<usize>,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Search using filter parameters
     Params {
@@ -383,9 +553,8 @@ enum SearchCommands {
         #[arg(long)]
         show_query: bool,
 
-        /// Maximum number of results
-        #[arg(long, default_value_t = 50)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
 }
 
@@ -430,6 +599,50 @@ enum BulkCommands {
         #[arg(long, default_value = "json")]
         format: String,
     },
+    /// Lint every page matching a CQL query (see `page lint`)
+    Lint {
+        /// CQL query to select pages
+        #[arg(long)]
+        cql: String,
+        #[command(flatten)]
+        lint: lint::LintArgs,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ExportCommands {
+    /// Write the labelled pages of a space as a static site, with an index
+    /// from the page tree, attachments copied and wiki links made local
+    Site {
+        /// Space key
+        #[arg(long)]
+        space: String,
+        /// Export only pages with this label
+        #[arg(long)]
+        label: String,
+        /// Output directory
+        #[arg(long)]
+        out: std::path::PathBuf,
+        /// Site format
+        #[arg(long, value_enum, default_value = "html")]
+        format: site::SiteFormat,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum PermissionCommands {
+    /// Show a user's effective space permissions, combining direct and group grants
+    Effective {
+        /// User account ID or email
+        #[arg(long)]
+        user: String,
+        /// Space key
+        #[arg(long)]
+        space: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -437,10 +650,11 @@ enum AnalyticsCommands {
     /// Get page view statistics
     PageViews {
         /// Page ID
+        #[arg(value_parser = entity_url::page_id)]
         page_id: String,
-        /// From date (YYYY-MM-DD)
-        #[arg(long)]
-        from: Option<String>,
+        /// From date (YYYY-MM-DD, or relative such as -30d or 'last monday')
+        #[arg(long, value_parser = dates::parse_date)]
+        from: Option<chrono::NaiveDate>,
     },
     /// Get space analytics
     SpaceStats {
@@ -449,24 +663,65 @@ enum AnalyticsCommands {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum SchedulerCommands {
+    /// List scheduled publishes for the active profile
+    List,
+    /// Remove a scheduled publish
+    Cancel {
+        /// Page or blog post ID
+        content_id: String,
+    },
+    /// Publish drafts as they fall due
+    Run {
+        /// Seconds between checks
+        #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Publish what is due now and exit (for cron)
+        #[arg(long)]
+        once: bool,
+    },
+}
+
 pub async fn execute(
     args: ConfluenceArgs,
     client: ApiClient,
     renderer: &OutputRenderer,
+    profile: &str,
 ) -> Result<()> {
-    let ctx = ConfluenceContext { client, renderer };
+    let ctx = ConfluenceContext {
+        client,
+        renderer,
+        profile,
+    };
 
     match args.command {
         ConfluenceCommands::Space(cmd) => match cmd {
-            SpaceCommands::List { limit, space_type } => {
-                spaces::list_spaces(&ctx, limit, space_type.as_deref()).await
+            SpaceCommands::List { page, space_type } => {
+                spaces::list_spaces(&ctx, &page, space_type.as_deref()).await
             }
             SpaceCommands::Get { key } => spaces::get_space(&ctx, &key).await,
             SpaceCommands::Create {
                 key,
                 name,
                 description,
-            } => spaces::create_space(&ctx, &key, &name, description.as_deref()).await,
+                from_template,
+                admin_group,
+                read_group,
+                if_not_exists,
+            } => {
+                spaces::create_space(
+                    &ctx,
+                    &key,
+                    &name,
+                    description.as_deref(),
+                    from_template.as_deref(),
+                    admin_group.as_deref(),
+                    read_group.as_deref(),
+                    if_not_exists,
+                )
+                .await
+            }
             SpaceCommands::Update {
                 space_id,
                 name,
@@ -478,6 +733,9 @@ pub async fn execute(
                 spaces::delete_space(&ctx, &space_id, force).await
             }
             SpaceCommands::Permissions { key } => spaces::get_space_permissions(&ctx, &key).await,
+            SpaceCommands::Permission(SpacePermissionCommands::Copy { from, to, dry_run }) => {
+                permissions::copy_space_permissions(&ctx, &from, &to, dry_run).await
+            }
             SpaceCommands::AddPermission {
                 key,
                 permission,
@@ -489,8 +747,8 @@ pub async fn execute(
             }
         },
         ConfluenceCommands::Page(cmd) => match cmd {
-            PageCommands::List { space, limit } => {
-                pages::list_pages(&ctx, space.as_deref(), limit).await
+            PageCommands::List { space, page } => {
+                pages::list_pages(&ctx, space.as_deref(), &page).await
             }
             PageCommands::Get { page_id } => pages::get_page(&ctx, &page_id).await,
             PageCommands::Create {
@@ -503,21 +761,30 @@ pub async fn execute(
                 page_id,
                 title,
                 body,
-            } => pages::update_page(&ctx, &page_id, title.as_deref(), body.as_ref()).await,
+                dry_run,
+            } => pages::update_page(&ctx, &page_id, title.as_deref(), body.as_ref(), dry_run).await,
             PageCommands::Delete { page_id, force } => {
                 pages::delete_page(&ctx, &page_id, force).await
             }
             PageCommands::Versions { page_id } => pages::list_page_versions(&ctx, &page_id).await,
+            PageCommands::PublishAt { page_id, at } => {
+                scheduler::publish_at(&ctx, scheduler::ContentKind::Page, &page_id, &at).await
+            }
             PageCommands::AddLabel { page_id, label } => {
-                pages::add_page_label(&ctx, &page_id, &label).await
+                labels::add_label(&ctx, ContentKind::Page, &page_id, &label).await
             }
             PageCommands::RemoveLabel { page_id, label } => {
-                pages::remove_page_label(&ctx, &page_id, &label).await
+                labels::remove_label(&ctx, ContentKind::Page, &page_id, &label).await
             }
             PageCommands::Comments { page_id } => pages::list_page_comments(&ctx, &page_id).await,
             PageCommands::AddComment { page_id, comment } => {
                 pages::add_page_comment(&ctx, &page_id, &comment).await
             }
+            PageCommands::Share {
+                page_id,
+                users,
+                message,
+            } => pages::share_page(&ctx, &page_id, &users, message.as_deref()).await,
             PageCommands::GetRestrictions { page_id } => {
                 pages::get_page_restrictions(&ctx, &page_id).await
             }
@@ -545,10 +812,11 @@ pub async fn execute(
                 )
                 .await
             }
+            PageCommands::Lint { page_id, lint } => lint::lint_page(&ctx, &page_id, &lint).await,
         },
         ConfluenceCommands::Blog(cmd) => match cmd {
-            BlogCommands::List { space, limit } => {
-                pages::list_blogposts(&ctx, space.as_deref(), limit).await
+            BlogCommands::List { space, page } => {
+                pages::list_blogposts(&ctx, space.as_deref(), &page).await
             }
             BlogCommands::Get { blogpost_id } => pages::get_blogpost(&ctx, &blogpost_id).await,
             BlogCommands::Create { space, title, body } => {
@@ -559,9 +827,19 @@ pub async fn execute(
                 title,
                 body,
             } => pages::update_blogpost(&ctx, &blogpost_id, title.as_deref(), body.as_ref()).await,
+            BlogCommands::PublishAt { blogpost_id, at } => {
+                scheduler::publish_at(&ctx, scheduler::ContentKind::Blogpost, &blogpost_id, &at)
+                    .await
+            }
             BlogCommands::Delete { blogpost_id, force } => {
                 pages::delete_blogpost(&ctx, &blogpost_id, force).await
             }
+            BlogCommands::AddLabel { blogpost_id, label } => {
+                labels::add_label(&ctx, ContentKind::Blogpost, &blogpost_id, &label).await
+            }
+            BlogCommands::RemoveLabel { blogpost_id, label } => {
+                labels::remove_label(&ctx, ContentKind::Blogpost, &blogpost_id, &label).await
+            }
         },
         ConfluenceCommands::Attachment(cmd) => match cmd {
             AttachmentCommands::List { page_id } => {
@@ -574,7 +852,21 @@ pub async fn execute(
                 page_id,
                 file,
                 comment,
-            } => attachments::upload_attachment(&ctx, &page_id, &file, comment.as_deref()).await,
+                replace_existing,
+            } => {
+                attachments::upload_attachment(
+                    &ctx,
+                    &page_id,
+                    &file,
+                    comment.as_deref(),
+                    replace_existing,
+                )
+                .await
+            }
+            AttachmentCommands::Versions {
+                attachment_id,
+                page,
+            } => attachments::list_attachment_versions(&ctx, &attachment_id, &page).await,
             AttachmentCommands::Download {
                 attachment_id,
                 output,
@@ -583,15 +875,29 @@ pub async fn execute(
                 attachment_id,
                 force,
             } => attachments::delete_attachment(&ctx, &attachment_id, force).await,
+            AttachmentCommands::AddLabel {
+                attachment_id,
+                label,
+            } => labels::add_label(&ctx, ContentKind::Attachment, &attachment_id, &label).await,
+            AttachmentCommands::RemoveLabel {
+                attachment_id,
+                label,
+            } => labels::remove_label(&ctx, ContentKind::Attachment, &attachment_id, &label).await,
+        },
+        ConfluenceCommands::Label(cmd) => match cmd {
+            LabelCommands::List { space, page } => {
+                labels::list_space_labels(&ctx, &space, &page).await
+            }
+            LabelCommands::Pages { label, space, page } => {
+                labels::list_labeled_pages(&ctx, &label, space.as_deref(), &page).await
+            }
         },
         ConfluenceCommands::Search(cmd) => match cmd {
-            SearchCommands::Cql { query, limit } => search::search_cql(&ctx, &query, limit).await,
-            SearchCommands::Text { query, limit } => search::search_text(&ctx, &query, limit).await,
-            SearchCommands::InSpace {
-                space,
-                query,
-                limit,
-            } => search::search_in_space(&ctx, &space, &query, limit).await,
+            SearchCommands::Cql { query, page } => search::search_cql(&ctx, &query, &page).await,
+            SearchCommands::Text { query, page } => search::search_text(&ctx, &query, &page).await,
+            SearchCommands::InSpace { space, query, page } => {
+                search::search_in_space(&ctx, &space, &query, &page).await
+            }
             SearchCommands::Params {
                 space,
                 r#type,
@@ -600,7 +906,7 @@ pub async fn execute(
                 title,
                 text,
                 show_query,
-                limit,
+                page,
             } => {
                 search::search_params(
                     &ctx,
@@ -611,7 +917,7 @@ pub async fn execute(
                     title.as_deref(),
                     text.as_deref(),
                     show_query,
-                    limit,
+                    &page,
                 )
                 .await
             }
@@ -645,14 +951,98 @@ pub async fn execute(
                 };
                 bulk::bulk_export_pages(&ctx, &cql, &output, export_format).await
             }
+            BulkCommands::Lint {
+                cql,
+                lint,
+                concurrency,
+            } => lint::bulk_lint(&ctx, &cql, &lint, concurrency).await,
         },
         ConfluenceCommands::Analytics(cmd) => match cmd {
             AnalyticsCommands::PageViews { page_id, from } => {
-                analytics::get_page_views(&ctx, &page_id, from.as_deref()).await
+                analytics::get_page_views(&ctx, &page_id, from.map(|d| d.to_string()).as_deref())
+                    .await
             }
             AnalyticsCommands::SpaceStats { space_key } => {
                 analytics::get_space_analytics(&ctx, &space_key).await
             }
         },
+        ConfluenceCommands::Permission(cmd) => match cmd {
+            PermissionCommands::Effective { user, space } => {
+                permissions::effective_permissions(&ctx, &user, &space).await
+            }
+        },
+        ConfluenceCommands::Scheduler(cmd) => match cmd {
+            SchedulerCommands::List => scheduler::list(&ctx),
+            SchedulerCommands::Cancel { content_id } => scheduler::cancel_entry(&ctx, &content_id),
+            SchedulerCommands::Run { interval, once } => scheduler::run(&ctx, interval, once).await,
+        },
+        ConfluenceCommands::Export(cmd) => match cmd {
+            ExportCommands::Site {
+                space,
+                label,
+                out,
+                format,
+            } => site::export_site(&ctx, &space, &label, &out, format).await,
+        },
     }
 }
+
+/// Fan a read-only Confluence command out across several profiles and render the merged rows.
+pub async fn execute_multi_profile(
+    args: ConfluenceArgs,
+    targets: Vec<ProfileTarget>,
+    renderer: &OutputRenderer,
+) -> Result<()> {
+    let (cql, page, default_limit) = match args.command {
+        ConfluenceCommands::Search(cmd) => match cmd {
+            SearchCommands::Cql { query, page } => (query, page, search::SEARCH_LIMIT),
+            SearchCommands::Text { query, page } => {
+                (search::text_cql(&query), page, search::SEARCH_LIMIT)
+            }
+            SearchCommands::InSpace { space, query, page } => (
+                search::in_space_cql(&space, &query),
+                page,
+                search::SEARCH_LIMIT,
+            ),
+            SearchCommands::Params {
+                space,
+                r#type,
+                creator,
+                label,
+                title,
+                text,
+                show_query,
+                page,
+            } => {
+                let cql = search::build_params_cql(
+                    space.as_deref(),
+                    r#type.as_deref(),
+                    creator.as_deref(),
+                    &label,
+                    title.as_deref(),
+                    text.as_deref(),
+                    show_query,
+                )?;
+                (cql, page, search::PARAMS_SEARCH_LIMIT)
+            }
+        },
+        _ => {
+            return Err(anyhow::anyhow!(
+                "--profiles/--all-profiles is only supported for `confluence search`"
+            ))
+        }
+    };
+    let (cql, page) = (&cql, &page);
+
+    let rows = multi_profile::fan_out(&targets, |target| {
+        let ctx = ConfluenceContext {
+            client: target.client.clone(),
+            renderer,
+            profile: &target.name,
+        };
+        async move { search::fetch_cql_rows(&ctx, cql, page, default_limit).await }
+    })
+    .await?;
+
+    renderer.render(&rows)
+}
@@ -1,22 +1,19 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::fs;
 use std::path::PathBuf;
 
-use super::utils::ConfluenceContext;
+use super::mentions::{escape, find_mentions, link_mentions, resolve_users, user_link};
+use super::utils::{fetch_v2_list, read_body, ConfluenceContext, V2_LIMIT};
+use crate::commands::dry_run::{field_changes, render_diff};
+use crate::commands::pagination::PaginationArgs;
 
 // List pages
 pub async fn list_pages(
     ctx: &ConfluenceContext<'_>,
     space_key: Option<&str>,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    #[derive(Deserialize)]
-    struct PagesResponse {
-        results: Vec<Page>,
-    }
-
     #[derive(Deserialize)]
     struct Page {
         id: String,
@@ -26,27 +23,12 @@ pub async fn list_pages(
         status: String,
     }
 
-    let mut query_params = Vec::new();
-
-    if let Some(l) = limit {
-        query_params.push(format!("limit={}", l));
-    }
-
+    let mut path = String::from("/wiki/api/v2/pages");
     if let Some(sk) = space_key {
-        query_params.push(format!("space-key={}", sk));
+        path.push_str(&format!("?space-key={}", sk));
     }
 
-    let query_string = if query_params.is_empty() {
-        String::new()
-    } else {
-        format!("?{}", query_params.join("&"))
-    };
-
-    let response: PagesResponse = ctx
-        .client
-        .get(&format!("/wiki/api/v2/pages{}", query_string))
-        .await
-        .context("Failed to list pages")?;
+    let pages: Vec<Page> = fetch_v2_list(ctx, &path, page, V2_LIMIT, "pages").await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -56,8 +38,7 @@ pub async fn list_pages(
         status: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .results
+    let rows: Vec<Row<'_>> = pages
         .iter()
         .map(|p| Row {
             id: p.id.as_str(),
@@ -67,7 +48,7 @@ pub async fn list_pages(
         })
         .collect();
 
-    ctx.renderer.render(&rows)
+    ctx.renderer.render_with_id(&rows, "id")
 }
 
 // Get page details
@@ -81,7 +62,7 @@ pub async fn get_page(ctx: &ConfluenceContext<'_>, page_id: &str) -> Result<()>
         .await
         .with_context(|| format!("Failed to get page {}", page_id))?;
 
-    println!("{}", serde_json::to_string_pretty(&page)?);
+    ctx.renderer.print_json(&page)?;
     Ok(())
 }
 
@@ -94,8 +75,7 @@ pub async fn create_page(
     parent_id: Option<&str>,
 ) -> Result<()> {
     let body_content = if let Some(file) = body_file {
-        fs::read_to_string(file)
-            .with_context(|| format!("Failed to read body file: {}", file.display()))?
+        read_body(ctx, file).await?
     } else {
         "<p>Page content</p>".to_string()
     };
@@ -137,14 +117,44 @@ pub async fn update_page(
     page_id: &str,
     title: Option<&str>,
     body_file: Option<&PathBuf>,
+    dry_run: bool,
 ) -> Result<()> {
-    // Get current page first to get version
+    let body_content = match body_file {
+        Some(file) => Some(read_body(ctx, file).await?),
+        None => None,
+    };
+
+    // Get current page first to get version (and its body when diffing)
+    let path = if dry_run {
+        format!("/wiki/api/v2/pages/{}?body-format=storage", page_id)
+    } else {
+        format!("/wiki/api/v2/pages/{}", page_id)
+    };
     let current: Value = ctx
         .client
-        .get(&format!("/wiki/api/v2/pages/{}", page_id))
+        .get(&path)
         .await
         .with_context(|| format!("Failed to get page {}", page_id))?;
 
+    if dry_run {
+        let changes = field_changes(vec![
+            (
+                "title",
+                current["title"].as_str().unwrap_or("").to_string(),
+                title.map(str::to_string),
+            ),
+            (
+                "body",
+                current["body"]["storage"]["value"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                body_content,
+            ),
+        ]);
+        return render_diff(ctx.renderer, &format!("page {}", page_id), &changes);
+    }
+
     let current_version = current
         .get("version")
         .and_then(|v| v.get("number"))
@@ -165,9 +175,7 @@ pub async fn update_page(
         payload["title"] = current.get("title").cloned().unwrap_or(json!("Untitled"));
     }
 
-    if let Some(file) = body_file {
-        let body_content = fs::read_to_string(file)
-            .with_context(|| format!("Failed to read body file: {}", file.display()))?;
+    if let Some(body_content) = body_content {
         payload["body"] = json!({
             "representation": "storage",
             "value": body_content
@@ -244,48 +252,7 @@ pub async fn list_page_versions(ctx: &ConfluenceContext<'_>, page_id: &str) -> R
         })
         .collect();
 
-    ctx.renderer.render(&rows)
-}
-
-// Add page label
-pub async fn add_page_label(ctx: &ConfluenceContext<'_>, page_id: &str, label: &str) -> Result<()> {
-    let payload = json!([{
-        "prefix": "global",
-        "name": label
-    }]);
-
-    let _: Value = ctx
-        .client
-        .post(
-            &format!("/wiki/rest/api/content/{}/label", page_id),
-            &payload,
-        )
-        .await
-        .with_context(|| format!("Failed to add label to page {}", page_id))?;
-
-    tracing::info!(%page_id, %label, "Label added successfully");
-    println!("✅ Added label '{}' to page {}", label, page_id);
-    Ok(())
-}
-
-// Remove page label
-pub async fn remove_page_label(
-    ctx: &ConfluenceContext<'_>,
-    page_id: &str,
-    label: &str,
-) -> Result<()> {
-    let _: Value = ctx
-        .client
-        .delete(&format!(
-            "/wiki/rest/api/content/{}/label?name={}",
-            page_id, label
-        ))
-        .await
-        .with_context(|| format!("Failed to remove label from page {}", page_id))?;
-
-    tracing::info!(%page_id, %label, "Label removed successfully");
-    println!("✅ Removed label '{}' from page {}", label, page_id);
-    Ok(())
+    ctx.renderer.render_with_id(&rows, "number")
 }
 
 // List page comments
@@ -335,12 +302,53 @@ pub async fn add_page_comment(
     page_id: &str,
     comment: &str,
 ) -> Result<()> {
+    let account_ids = resolve_users(ctx, &find_mentions(comment)).await?;
+    let comment_id = post_footer_comment(
+        ctx,
+        page_id,
+        &format!("<p>{}</p>", link_mentions(comment, &account_ids)),
+    )
+    .await?;
+
+    tracing::info!(page_id = %page_id, comment_id = %comment_id, "Comment added successfully");
+    println!("✅ Added comment to page {} (ID: {})", page_id, comment_id);
+    Ok(())
+}
+
+// Share a page by mentioning users in a comment, which notifies them
+pub async fn share_page(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    users: &[String],
+    message: Option<&str>,
+) -> Result<()> {
+    let account_ids = resolve_users(ctx, users).await?;
+
+    let links: Vec<String> = users
+        .iter()
+        .map(|user| user_link(&account_ids[user]))
+        .collect();
+    let note = escape(message.unwrap_or("please take a look"));
+    let body = format!("<p>{} {}</p>", links.join(" "), note);
+
+    let comment_id = post_footer_comment(ctx, page_id, &body).await?;
+
+    tracing::info!(page_id = %page_id, comment_id = %comment_id, users = users.len(), "Page shared");
+    println!("✅ Shared page {} with {} users", page_id, users.len());
+    Ok(())
+}
+
+async fn post_footer_comment(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    storage: &str,
+) -> Result<String> {
     let payload = json!({
         "pageId": page_id,
         "status": "current",
         "body": {
             "representation": "storage",
-            "value": format!("<p>{}</p>", comment)
+            "value": storage
         }
     });
 
@@ -354,10 +362,7 @@ pub async fn add_page_comment(
         .post("/wiki/api/v2/footer-comments", &payload)
         .await
         .with_context(|| format!("Failed to add comment to page {}", page_id))?;
-
-    tracing::info!(page_id = %page_id, comment_id = %response.id, "Comment added successfully");
-    println!("✅ Added comment to page {} (ID: {})", page_id, response.id);
-    Ok(())
+    Ok(response.id)
 }
 
 // Get page restrictions
@@ -368,7 +373,7 @@ pub async fn get_page_restrictions(ctx: &ConfluenceContext<'_>, page_id: &str) -
         .await
         .with_context(|| format!("Failed to get restrictions for page {}", page_id))?;
 
-    println!("{}", serde_json::to_string_pretty(&restrictions)?);
+    ctx.renderer.print_json(&restrictions)?;
     Ok(())
 }
 
@@ -436,13 +441,8 @@ pub async fn remove_page_restriction(
 pub async fn list_blogposts(
     ctx: &ConfluenceContext<'_>,
     space_id: Option<&str>,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    #[derive(Deserialize)]
-    struct BlogpostsResponse {
-        results: Vec<Blogpost>,
-    }
-
     #[derive(Deserialize)]
     struct Blogpost {
         id: String,
@@ -450,27 +450,12 @@ pub async fn list_blogposts(
         status: String,
     }
 
-    let mut query_params = Vec::new();
-
-    if let Some(l) = limit {
-        query_params.push(format!("limit={}", l));
-    }
-
+    let mut path = String::from("/wiki/api/v2/blogposts");
     if let Some(sid) = space_id {
-        query_params.push(format!("space-id={}", sid));
+        path.push_str(&format!("?space-id={}", sid));
     }
 
-    let query_string = if query_params.is_empty() {
-        String::new()
-    } else {
-        format!("?{}", query_params.join("&"))
-    };
-
-    let response: BlogpostsResponse = ctx
-        .client
-        .get(&format!("/wiki/api/v2/blogposts{}", query_string))
-        .await
-        .context("Failed to list blog posts")?;
+    let blogposts: Vec<Blogpost> = fetch_v2_list(ctx, &path, page, V2_LIMIT, "blog posts").await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -479,8 +464,7 @@ pub async fn list_blogposts(
         status: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .results
+    let rows: Vec<Row<'_>> = blogposts
         .iter()
         .map(|b| Row {
             id: b.id.as_str(),
@@ -489,7 +473,7 @@ pub async fn list_blogposts(
         })
         .collect();
 
-    ctx.renderer.render(&rows)
+    ctx.renderer.render_with_id(&rows, "id")
 }
 
 // Get blog post details
@@ -503,7 +487,7 @@ pub async fn get_blogpost(ctx: &ConfluenceContext<'_>, blogpost_id: &str) -> Res
         .await
         .with_context(|| format!("Failed to get blog post {}", blogpost_id))?;
 
-    println!("{}", serde_json::to_string_pretty(&blogpost)?);
+    ctx.renderer.print_json(&blogpost)?;
     Ok(())
 }
 
@@ -515,8 +499,7 @@ pub async fn create_blog(
     body_file: Option<&PathBuf>,
 ) -> Result<()> {
     let body_content = if let Some(file) = body_file {
-        fs::read_to_string(file)
-            .with_context(|| format!("Failed to read body file: {}", file.display()))?
+        read_body(ctx, file).await?
     } else {
         "<p>Blog post content</p>".to_string()
     };
@@ -587,8 +570,7 @@ pub async fn update_blogpost(
     }
 
     if let Some(file) = body_file {
-        let body_content = fs::read_to_string(file)
-            .with_context(|| format!("Failed to read body file: {}", file.display()))?;
+        let body_content = read_body(ctx, file).await?;
         payload["body"] = json!({
             "representation": "storage",
             "value": body_content
@@ -0,0 +1,422 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_output::OutputFormat;
+use serde::{Deserialize, Serialize};
+
+use super::mentions::resolve_users;
+use super::spaces::grant_permission;
+use super::utils::{fetch_v2_list, ConfluenceContext};
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Effective space permissions for a user (direct grants plus grants to their
+// groups), and copying every grant of one space to another
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct SpacePermission {
+    principal: Principal,
+    operation: Operation,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Principal {
+    #[serde(rename = "type")]
+    principal_type: String,
+    id: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Operation {
+    key: String,
+    #[serde(rename = "targetType")]
+    target_type: String,
+}
+
+#[derive(Deserialize)]
+struct UserGroup {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct EffectiveRow {
+    permission: String,
+    granted_via: String,
+}
+
+pub async fn effective_permissions(
+    ctx: &ConfluenceContext<'_>,
+    user: &str,
+    space_key: &str,
+) -> Result<()> {
+    let account_id = resolve_users(ctx, &[user.to_string()])
+        .await?
+        .remove(user)
+        .unwrap_or_else(|| user.to_string());
+
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let groups = user_groups(ctx, &account_id, &everything).await?;
+
+    let grants = space_grants(ctx, space_key, &everything).await?;
+
+    let rows = combine_grants(&grants, &account_id, &groups);
+    tracing::info!(
+        space_key,
+        %account_id,
+        groups = groups.len(),
+        permissions = rows.len(),
+        "Computed effective space permissions"
+    );
+
+    if rows.is_empty() {
+        println!("{} has no permissions in space {}", user, space_key);
+        return Ok(());
+    }
+
+    ctx.renderer.render(&rows)?;
+    if ctx.renderer.format() == OutputFormat::Table {
+        println!(
+            "\n{} permission(s) for {} in {} (member of {} group(s))",
+            rows.len(),
+            user,
+            space_key,
+            groups.len()
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct CopyRow {
+    subject: String,
+    permission: String,
+    status: &'static str,
+    detail: String,
+}
+
+/// Grant every permission of space `from` to the same users and groups in
+/// space `to`. Grants to other principals (roles, anonymous access) cannot be
+/// applied through the API and are reported instead.
+pub async fn copy_space_permissions(
+    ctx: &ConfluenceContext<'_>,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+) -> Result<()> {
+    if from.eq_ignore_ascii_case(to) {
+        bail!("Source and destination space are the same ({from})");
+    }
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let source = space_grants(ctx, from, &everything).await?;
+    let existing = space_grants(ctx, to, &everything).await?;
+    let plan = plan_copy(&source, &existing);
+
+    let mut group_names: HashMap<String, String> = HashMap::new();
+    let mut rows = Vec::with_capacity(plan.len());
+    for (grant, step) in plan {
+        let principal = &grant.principal;
+        let permission = format!("{}:{}", grant.operation.key, grant.operation.target_type);
+        let mut subject = format!("{} {}", principal.principal_type, principal.id);
+        if principal.principal_type == "group" {
+            if !group_names.contains_key(&principal.id) {
+                let name = group_name(ctx, &principal.id).await?;
+                group_names.insert(principal.id.clone(), name);
+            }
+            subject = format!("group {}", group_names[&principal.id]);
+        }
+
+        let (status, detail) = match step {
+            CopyStep::Exists => ("exists", format!("already granted in {to}")),
+            CopyStep::Unmappable => (
+                "unmappable",
+                format!(
+                    "{} grants cannot be set through the API",
+                    principal.principal_type
+                ),
+            ),
+            CopyStep::Grant if dry_run => ("would grant", String::new()),
+            CopyStep::Grant => {
+                let identifier = match principal.principal_type.as_str() {
+                    "group" => group_names[&principal.id].as_str(),
+                    _ => principal.id.as_str(),
+                };
+                match grant_permission(
+                    ctx,
+                    to,
+                    &principal.principal_type,
+                    identifier,
+                    &grant.operation.key,
+                    &grant.operation.target_type,
+                )
+                .await
+                {
+                    Ok(()) => ("granted", String::new()),
+                    Err(err) => ("failed", format!("{err:#}")),
+                }
+            }
+        };
+        rows.push(CopyRow {
+            subject,
+            permission,
+            status,
+            detail,
+        });
+    }
+
+    let count = |status: &str| rows.iter().filter(|r| r.status == status).count();
+    let (granted, exists, unmappable, failed) = (
+        count(if dry_run { "would grant" } else { "granted" }),
+        count("exists"),
+        count("unmappable"),
+        count("failed"),
+    );
+    tracing::info!(
+        from,
+        to,
+        granted,
+        exists,
+        unmappable,
+        failed,
+        dry_run,
+        "Space permission copy finished"
+    );
+
+    if rows.is_empty() {
+        println!("Space {from} has no permissions to copy");
+        return Ok(());
+    }
+    ctx.renderer.render(&rows)?;
+    if failed > 0 {
+        bail!(
+            "{failed} of {} permission(s) could not be copied to {to}",
+            rows.len()
+        );
+    }
+    if ctx.renderer.format() == OutputFormat::Table {
+        let verb = if dry_run { "to grant" } else { "granted" };
+        println!("\n{granted} {verb}, {exists} already present, {unmappable} unmappable");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CopyStep {
+    Grant,
+    Exists,
+    Unmappable,
+}
+
+/// What to do with each source grant, in source order.
+fn plan_copy(
+    source: &[SpacePermission],
+    existing: &[SpacePermission],
+) -> Vec<(SpacePermission, CopyStep)> {
+    source
+        .iter()
+        .map(|grant| {
+            let step = if !matches!(grant.principal.principal_type.as_str(), "user" | "group") {
+                CopyStep::Unmappable
+            } else if existing.contains(grant) {
+                CopyStep::Exists
+            } else {
+                CopyStep::Grant
+            };
+            (grant.clone(), step)
+        })
+        .collect()
+}
+
+async fn space_grants(
+    ctx: &ConfluenceContext<'_>,
+    space_key: &str,
+    page: &PaginationArgs,
+) -> Result<Vec<SpacePermission>> {
+    #[derive(Deserialize)]
+    struct SpacesResponse {
+        results: Vec<Space>,
+    }
+
+    #[derive(Deserialize)]
+    struct Space {
+        id: String,
+    }
+
+    let spaces: SpacesResponse = ctx
+        .client
+        .get(&format!(
+            "/wiki/api/v2/spaces?keys={}",
+            urlencoding::encode(space_key)
+        ))
+        .await
+        .with_context(|| format!("Failed to get space {}", space_key))?;
+    let space_id = spaces
+        .results
+        .into_iter()
+        .next()
+        .map(|s| s.id)
+        .ok_or_else(|| anyhow!("Space '{}' not found", space_key))?;
+
+    let path = format!("/wiki/api/v2/spaces/{}/permissions", space_id);
+    let what = format!("permissions for space {}", space_key);
+    fetch_v2_list(ctx, &path, page, 250, &what).await
+}
+
+/// Space permission grants identify groups by ID; granting takes the name.
+async fn group_name(ctx: &ConfluenceContext<'_>, group_id: &str) -> Result<String> {
+    let group: UserGroup = ctx
+        .client
+        .get(&format!(
+            "/wiki/rest/api/group/by-id?id={}",
+            urlencoding::encode(group_id)
+        ))
+        .await
+        .with_context(|| format!("Failed to get group {group_id}"))?;
+    Ok(group.name)
+}
+
+async fn user_groups(
+    ctx: &ConfluenceContext<'_>,
+    account_id: &str,
+    page: &PaginationArgs,
+) -> Result<Vec<UserGroup>> {
+    #[derive(Deserialize)]
+    struct GroupsResponse {
+        results: Vec<UserGroup>,
+        #[serde(rename = "_links", default)]
+        links: Option<Links>,
+    }
+
+    #[derive(Deserialize)]
+    struct Links {
+        next: Option<String>,
+    }
+
+    page.collect(200, 200, Paging::Offset, |request| {
+        let path = format!(
+            "/wiki/rest/api/user/memberof?accountId={}&start={}&limit={}",
+            urlencoding::encode(account_id),
+            request.start,
+            request.size
+        );
+        async move {
+            let response: GroupsResponse = ctx
+                .client
+                .get(&path)
+                .await
+                .with_context(|| format!("Failed to list groups of user {account_id}"))?;
+            let has_more = response.links.is_some_and(|l| l.next.is_some());
+            Ok(Page::offset(response.results, has_more))
+        }
+    })
+    .await
+}
+
+/// One row per permission held by the user, listing every grant that gives it
+/// (`user` for a direct grant, `group NAME` for a group grant).
+fn combine_grants(
+    grants: &[SpacePermission],
+    account_id: &str,
+    groups: &[UserGroup],
+) -> Vec<EffectiveRow> {
+    let mut via: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for grant in grants {
+        let source = match grant.principal.principal_type.as_str() {
+            "user" if grant.principal.id == account_id => "user".to_string(),
+            "group" => match groups
+                .iter()
+                .find(|g| g.id == grant.principal.id || g.name == grant.principal.id)
+            {
+                Some(group) => format!("group {}", group.name),
+                None => continue,
+            },
+            _ => continue,
+        };
+        let permission = format!("{}:{}", grant.operation.key, grant.operation.target_type);
+        let sources = via.entry(permission).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    }
+
+    via.into_iter()
+        .map(|(permission, sources)| EffectiveRow {
+            permission,
+            granted_via: sources.join(", "),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(principal_type: &str, id: &str, key: &str, target: &str) -> SpacePermission {
+        SpacePermission {
+            principal: Principal {
+                principal_type: principal_type.to_string(),
+                id: id.to_string(),
+            },
+            operation: Operation {
+                key: key.to_string(),
+                target_type: target.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_combine_grants() {
+        let groups = vec![UserGroup {
+            id: "g-1".to_string(),
+            name: "engineering".to_string(),
+        }];
+        let grants = vec![
+            grant("group", "g-1", "read", "space"),
+            grant("user", "acc-1", "read", "space"),
+            grant("group", "g-1", "create", "page"),
+            grant("user", "acc-2", "administer", "space"),
+            grant("group", "g-9", "delete", "space"),
+            grant("role", "r-1", "read", "space"),
+        ];
+
+        assert_eq!(
+            combine_grants(&grants, "acc-1", &groups),
+            vec![
+                EffectiveRow {
+                    permission: "create:page".to_string(),
+                    granted_via: "group engineering".to_string(),
+                },
+                EffectiveRow {
+                    permission: "read:space".to_string(),
+                    granted_via: "group engineering, user".to_string(),
+                },
+            ]
+        );
+        assert!(combine_grants(&grants, "acc-3", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_plan_copy() {
+        let source = vec![
+            grant("group", "g-1", "read", "space"),
+            grant("user", "acc-1", "administer", "space"),
+            grant("role", "r-1", "read", "space"),
+        ];
+        let existing = vec![grant("group", "g-1", "read", "space")];
+
+        let steps: Vec<CopyStep> = plan_copy(&source, &existing)
+            .into_iter()
+            .map(|(_, step)| step)
+            .collect();
+        assert_eq!(
+            steps,
+            vec![CopyStep::Exists, CopyStep::Grant, CopyStep::Unmappable]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::mentions::escape;
+use super::utils::ConfluenceContext;
+
+// Publishing generated content (e.g. other products' command output) as a page
+
+/// A page created or updated by `upsert_page`.
+pub struct PublishedPage {
+    pub id: String,
+    pub version: i64,
+    pub created: bool,
+}
+
+/// A report page body: an introductory paragraph followed by the rows as a
+/// table. With `link = (column, url_prefix)` that column's cells link to
+/// `url_prefix` + value.
+pub fn report_body(intro: &str, rows: &[Value], link: Option<(&str, &str)>) -> String {
+    let mut out = format!("<p>{}</p>", escape(intro));
+    if rows.is_empty() {
+        out.push_str("<p><em>No results.</em></p>");
+    } else {
+        out.push_str(&rows_table(rows, link));
+    }
+    out
+}
+
+/// Columns are every key seen across the rows, sorted as in table output.
+fn rows_table(rows: &[Value], link: Option<(&str, &str)>) -> String {
+    let columns: BTreeSet<&str> = rows
+        .iter()
+        .filter_map(Value::as_object)
+        .flat_map(|o| o.keys().map(String::as_str))
+        .collect();
+
+    let mut out = String::from("<table><tbody><tr>");
+    for column in &columns {
+        out.push_str(&format!("<th>{}</th>", escape(column)));
+    }
+    out.push_str("</tr>");
+    for row in rows {
+        out.push_str("<tr>");
+        for column in &columns {
+            let text = cell_text(&row[*column]);
+            let cell = match link {
+                Some((linked, prefix)) if linked == *column && !text.is_empty() => format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(&format!("{prefix}{text}")),
+                    escape(&text)
+                ),
+                _ => escape(&text),
+            };
+            out.push_str(&format!("<td>{cell}</td>"));
+        }
+        out.push_str("</tr>");
+    }
+    out.push_str("</tbody></table>");
+    out
+}
+
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Create the page titled `title` in the space, or replace the body of the
+/// existing one, so repeated runs keep a single up-to-date page.
+pub async fn upsert_page(
+    ctx: &ConfluenceContext<'_>,
+    space_key: &str,
+    title: &str,
+    body: &str,
+    parent_id: Option<&str>,
+) -> Result<PublishedPage> {
+    #[derive(Deserialize)]
+    struct Results<T> {
+        results: Vec<T>,
+    }
+
+    #[derive(Deserialize)]
+    struct Space {
+        id: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Page {
+        id: String,
+        #[serde(default)]
+        version: Option<Version>,
+    }
+
+    #[derive(Deserialize)]
+    struct Version {
+        number: i64,
+    }
+
+    let spaces: Results<Space> = ctx
+        .client
+        .get(&format!(
+            "/wiki/api/v2/spaces?keys={}",
+            urlencoding::encode(space_key)
+        ))
+        .await
+        .with_context(|| format!("Failed to get space {}", space_key))?;
+    let space_id = spaces
+        .results
+        .into_iter()
+        .next()
+        .map(|s| s.id)
+        .ok_or_else(|| anyhow!("Space '{}' not found", space_key))?;
+
+    let existing: Results<Page> = ctx
+        .client
+        .get(&format!(
+            "/wiki/api/v2/spaces/{}/pages?title={}&status=current",
+            space_id,
+            urlencoding::encode(title)
+        ))
+        .await
+        .with_context(|| format!("Failed to look up page '{}'", title))?;
+
+    let storage = json!({ "representation": "storage", "value": body });
+    match existing.results.into_iter().next() {
+        Some(page) => {
+            let version = page.version.map_or(1, |v| v.number) + 1;
+            let _: Value = ctx
+                .client
+                .put(
+                    &format!("/wiki/api/v2/pages/{}", page.id),
+                    &json!({
+                        "id": page.id,
+                        "status": "current",
+                        "title": title,
+                        "body": storage,
+                        "version": { "number": version }
+                    }),
+                )
+                .await
+                .with_context(|| format!("Failed to update page {}", page.id))?;
+            Ok(PublishedPage {
+                id: page.id,
+                version,
+                created: false,
+            })
+        }
+        None => {
+            let mut payload = json!({
+                "spaceId": space_id,
+                "status": "current",
+                "title": title,
+                "body": storage
+            });
+            if let Some(pid) = parent_id {
+                payload["parentId"] = json!(pid);
+            }
+            let page: Page = ctx
+                .client
+                .post("/wiki/api/v2/pages", &payload)
+                .await
+                .context("Failed to create page")?;
+            Ok(PublishedPage {
+                id: page.id,
+                version: 1,
+                created: true,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_body() {
+        assert_eq!(
+            report_body("Open bugs: 0 issue(s)", &[], None),
+            "<p>Open bugs: 0 issue(s)</p><p><em>No results.</em></p>"
+        );
+    }
+
+    #[test]
+    fn test_rows_table() {
+        let rows = vec![
+            json!({ "key": "DEV-1", "summary": "Fix <login>", "points": 3 }),
+            json!({ "key": "DEV-2", "summary": "A & B", "matched_in": "summary" }),
+        ];
+        assert_eq!(
+            rows_table(&rows, Some(("key", "https://site/browse/"))),
+            "<table><tbody><tr><th>key</th><th>matched_in</th><th>points</th><th>summary</th></tr>\
+             <tr><td><a href=\"https://site/browse/DEV-1\">DEV-1</a></td><td></td><td>3</td><td>Fix &lt;login&gt;</td></tr>\
+             <tr><td><a href=\"https://site/browse/DEV-2\">DEV-2</a></td><td>summary</td><td></td><td>A &amp; B</td></tr>\
+             </tbody></table>"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::OutputFormat;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::ConfluenceContext;
+use crate::commands::cancel;
+
+// Scheduled publishing. Confluence keeps drafts but cannot publish them at a
+// set time, so `publish-at` records the draft in a per-profile schedule file
+// and `scheduler run` publishes each entry once it falls due. Run it as a
+// long-lived process, or with `--once` from cron.
+
+/// Content that can be scheduled.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Page,
+    Blogpost,
+}
+
+impl ContentKind {
+    fn api_path(self) -> &'static str {
+        match self {
+            ContentKind::Page => "pages",
+            ContentKind::Blogpost => "blogposts",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ContentKind::Page => "page",
+            ContentKind::Blogpost => "blog post",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Entry {
+    id: String,
+    kind: ContentKind,
+    title: String,
+    publish_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Schedule {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct EntryRow {
+    id: String,
+    kind: String,
+    title: String,
+    publish_at: String,
+    last_error: String,
+}
+
+/// Schedule the pending draft of a page or blog post to be published at `at`.
+pub async fn publish_at(
+    ctx: &ConfluenceContext<'_>,
+    kind: ContentKind,
+    id: &str,
+    at: &str,
+) -> Result<()> {
+    let publish_at = parse_publish_time(at)?;
+    if publish_at <= Utc::now() {
+        bail!("Publish time {at} is in the past");
+    }
+
+    let draft = fetch_draft(ctx, kind, id).await?;
+    let title = draft["title"].as_str().unwrap_or_default().to_string();
+
+    let path = schedule_path(ctx.profile)?;
+    let mut schedule = load(&path)?;
+    schedule.entries.retain(|e| !(e.id == id && e.kind == kind));
+    schedule.entries.push(Entry {
+        id: id.to_string(),
+        kind,
+        title: title.clone(),
+        publish_at,
+        last_error: None,
+    });
+    schedule.entries.sort_by_key(|e| e.publish_at);
+    save(&path, &schedule)?;
+
+    tracing::info!(%id, %publish_at, "Scheduled {} for publishing", kind.label());
+    println!(
+        "✅ Scheduled {} '{title}' ({id}) for {}",
+        kind.label(),
+        format_local(publish_at)
+    );
+    println!("   Keep `atlassian-cli confluence scheduler run` running (or call it with --once from cron) to publish it");
+    Ok(())
+}
+
+/// Show the scheduled entries of the active profile.
+pub fn list(ctx: &ConfluenceContext<'_>) -> Result<()> {
+    let schedule = load(&schedule_path(ctx.profile)?)?;
+    if schedule.entries.is_empty() && ctx.renderer.format() == OutputFormat::Table {
+        println!("Nothing scheduled");
+        return Ok(());
+    }
+
+    let rows: Vec<EntryRow> = schedule
+        .entries
+        .into_iter()
+        .map(|e| EntryRow {
+            id: e.id,
+            kind: e.kind.label().to_string(),
+            title: e.title,
+            publish_at: format_local(e.publish_at),
+            last_error: e.last_error.unwrap_or_default(),
+        })
+        .collect();
+    ctx.renderer.render(&rows)
+}
+
+/// Drop a scheduled entry without publishing it.
+pub fn cancel_entry(ctx: &ConfluenceContext<'_>, id: &str) -> Result<()> {
+    let path = schedule_path(ctx.profile)?;
+    let mut schedule = load(&path)?;
+    let before = schedule.entries.len();
+    schedule.entries.retain(|e| e.id != id);
+    if schedule.entries.len() == before {
+        bail!("Content {id} is not scheduled");
+    }
+    save(&path, &schedule)?;
+
+    tracing::info!(%id, "Scheduled publish cancelled");
+    println!("✅ Cancelled scheduled publish of {id}");
+    Ok(())
+}
+
+/// Publish due entries, then keep checking every `interval` seconds unless
+/// `once` is set.
+pub async fn run(ctx: &ConfluenceContext<'_>, interval: u64, once: bool) -> Result<()> {
+    let path = schedule_path(ctx.profile)?;
+    if !once {
+        eprintln!(
+            "Publishing scheduled content for profile '{}' (checking every {interval}s, Ctrl-C to stop)",
+            ctx.profile
+        );
+    }
+
+    loop {
+        let due = due_entries(&load(&path)?, Utc::now());
+        for entry in due {
+            let result = publish(ctx, entry.kind, &entry.id).await;
+            // Nothing was published, so the entry must stay scheduled
+            if ctx.client.is_explaining() {
+                result?;
+                continue;
+            }
+
+            // Reload so entries added while publishing are kept
+            let mut schedule = load(&path)?;
+            match &result {
+                Ok(()) => {
+                    schedule.entries.retain(|e| e != &entry);
+                    println!(
+                        "✅ Published {} '{}' ({})",
+                        entry.kind.label(),
+                        entry.title,
+                        entry.id
+                    );
+                }
+                Err(err) => {
+                    let message = format!("{err:#}");
+                    eprintln!(
+                        "Failed to publish {} {}: {message}",
+                        entry.kind.label(),
+                        entry.id
+                    );
+                    if let Some(kept) = schedule.entries.iter_mut().find(|e| **e == entry) {
+                        kept.last_error = Some(message);
+                    }
+                }
+            }
+            save(&path, &schedule)?;
+        }
+
+        if once || !cancel::sleep_unless_cancelled(Duration::from_secs(interval)).await {
+            return Ok(());
+        }
+    }
+}
+
+/// Entries whose time has come. Entries that failed before are retried.
+fn due_entries(schedule: &Schedule, now: DateTime<Utc>) -> Vec<Entry> {
+    schedule
+        .entries
+        .iter()
+        .filter(|e| e.publish_at <= now)
+        .cloned()
+        .collect()
+}
+
+async fn fetch_draft(ctx: &ConfluenceContext<'_>, kind: ContentKind, id: &str) -> Result<Value> {
+    ctx.client
+        .get(&format!(
+            "/wiki/api/v2/{}/{id}?get-draft=true&body-format=storage",
+            kind.api_path()
+        ))
+        .await
+        .with_context(|| format!("Failed to get draft of {} {id}", kind.label()))
+}
+
+/// Make the draft the current version: a never-published draft becomes
+/// version 1, edits to a published page become the next version.
+async fn publish(ctx: &ConfluenceContext<'_>, kind: ContentKind, id: &str) -> Result<()> {
+    let draft = fetch_draft(ctx, kind, id).await?;
+    let current: Value = ctx
+        .client
+        .get(&format!(
+            "/wiki/api/v2/{}/{id}?status=current&status=draft",
+            kind.api_path()
+        ))
+        .await
+        .with_context(|| format!("Failed to get {} {id}", kind.label()))?;
+
+    let version = if current["status"] == "current" {
+        current["version"]["number"].as_i64().unwrap_or(1) + 1
+    } else {
+        1
+    };
+    let payload = json!({
+        "id": id,
+        "status": "current",
+        "title": draft["title"],
+        "body": {
+            "representation": "storage",
+            "value": draft["body"]["storage"]["value"].as_str().unwrap_or_default(),
+        },
+        "version": { "number": version },
+    });
+
+    let _: Value = ctx
+        .client
+        .put(&format!("/wiki/api/v2/{}/{id}", kind.api_path()), &payload)
+        .await
+        .with_context(|| format!("Failed to publish {} {id}", kind.label()))?;
+    tracing::info!(%id, version, "Published scheduled {}", kind.label());
+    Ok(())
+}
+
+/// Accept RFC 3339, or a local date and time such as `2024-07-01T09:00` or
+/// `2024-07-01 09:00`.
+fn parse_publish_time(value: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let normalized = value.replacen(' ', "T", 1);
+    let naive = ["%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&normalized, format).ok())
+        .with_context(|| {
+            format!("Invalid time '{value}': use 2024-07-01T09:00 (local time) or RFC 3339")
+        })?;
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| format!("Time '{value}' is ambiguous or skipped in the local time zone"))
+}
+
+fn format_local(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M %Z")
+        .to_string()
+}
+
+fn schedule_path(profile: &str) -> Result<PathBuf> {
+    let profile: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let home = dirs::home_dir().context("Cannot find the home directory")?;
+    Ok(home
+        .join(".atlassian-cli")
+        .join("schedule")
+        .join(format!("{profile}.json")))
+}
+
+fn load(path: &PathBuf) -> Result<Schedule> {
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid schedule file {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Schedule::default()),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Write through a temporary file so a concurrent `scheduler run` never reads
+/// a half-written schedule.
+fn save(path: &PathBuf, schedule: &Schedule) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(schedule)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, at: &str) -> Entry {
+        Entry {
+            id: id.to_string(),
+            kind: ContentKind::Page,
+            title: "Launch".to_string(),
+            publish_at: at.parse().unwrap(),
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_publish_time() {
+        assert_eq!(
+            parse_publish_time("2024-07-01T09:00:00+02:00").unwrap(),
+            "2024-07-01T07:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        let local = parse_publish_time("2024-07-01T09:00").unwrap();
+        assert_eq!(parse_publish_time("2024-07-01 09:00").unwrap(), local);
+        assert_eq!(
+            local.with_timezone(&Local).format("%H:%M").to_string(),
+            "09:00"
+        );
+        assert!(parse_publish_time("tomorrow").is_err());
+    }
+
+    #[test]
+    fn test_due_entries() {
+        let schedule = Schedule {
+            entries: vec![
+                entry("1", "2024-07-01T07:00:00Z"),
+                entry("2", "2024-07-02T07:00:00Z"),
+            ],
+        };
+        let now = "2024-07-01T08:00:00Z".parse().unwrap();
+        let due = due_entries(&schedule, now);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, "1");
+    }
+}
@@ -2,69 +2,155 @@ use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 
 use super::utils::ConfluenceContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 use crate::query::CqlBuilder;
 
+/// Results returned by CQL searches unless `--limit` says otherwise.
+pub const SEARCH_LIMIT: usize = 25;
+/// `search params` has always shown more results by default.
+pub const PARAMS_SEARCH_LIMIT: usize = 50;
+
 // Search using CQL
 pub async fn search_cql(
     ctx: &ConfluenceContext<'_>,
     cql: &str,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
+    let rows = fetch_cql_rows(ctx, cql, page, SEARCH_LIMIT).await?;
+    ctx.renderer.render(&rows)
+}
+
+#[derive(Serialize)]
+pub struct SearchRow {
+    id: String,
+    title: String,
+    content_type: String,
+    url: String,
+    excerpt: String,
+}
+
+/// Run a CQL search and flatten the results into table rows, with absolute
+/// web URLs and the matching excerpt.
+pub async fn fetch_cql_rows(
+    ctx: &ConfluenceContext<'_>,
+    cql: &str,
+    page: &PaginationArgs,
+    default_limit: usize,
+) -> Result<Vec<SearchRow>> {
     #[derive(Deserialize)]
     struct SearchResponse {
         results: Vec<SearchResult>,
+        #[serde(default, rename = "_links")]
+        links: Links,
+    }
+
+    #[derive(Deserialize, Default)]
+    struct Links {
+        base: Option<String>,
+        next: Option<String>,
+        webui: Option<String>,
     }
 
     #[derive(Deserialize)]
     struct SearchResult {
+        #[serde(default)]
+        content: Option<Content>,
+        #[serde(default)]
+        title: String,
+        #[serde(rename = "entityType", default)]
+        entity_type: String,
+        #[serde(default)]
+        url: Option<String>,
+        #[serde(default)]
+        excerpt: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Content {
         id: String,
         title: String,
         #[serde(rename = "type")]
         content_type: String,
+        #[serde(default, rename = "_links")]
+        links: Links,
     }
 
-    let mut query_params = vec![format!("cql={}", urlencoding::encode(cql))];
-
-    if let Some(l) = limit {
-        query_params.push(format!("limit={}", l));
-    }
-
-    let query_string = query_params.join("&");
+    let default_base = format!("{}/wiki", ctx.client.base_url().trim_end_matches('/'));
 
-    let response: SearchResponse = ctx
-        .client
-        .get(&format!("/wiki/rest/api/content/search?{}", query_string))
-        .await
-        .context("Failed to search with CQL")?;
+    // Deep pages are only reachable through the cursor in `_links.next`
+    page.collect(default_limit, 100, Paging::Cursor, |request| {
+        let path = match &request.cursor {
+            Some(next) => format!("/wiki{next}"),
+            None => format!(
+                "/wiki/rest/api/search?cql={}&limit={}&excerpt=highlight",
+                urlencoding::encode(cql),
+                request.size
+            ),
+        };
+        let default_base = &default_base;
+        async move {
+            let response: SearchResponse = ctx
+                .client
+                .get(&path)
+                .await
+                .context("Failed to search with CQL")?;
+            let base = response.links.base.as_deref().unwrap_or(default_base);
+            let rows = response
+                .results
+                .into_iter()
+                .map(|r| {
+                    let link = r
+                        .url
+                        .or_else(|| r.content.as_ref().and_then(|c| c.links.webui.clone()));
+                    let (id, title, content_type) = match r.content {
+                        Some(c) => (c.id, c.title, c.content_type),
+                        None => (String::new(), clean_excerpt(&r.title), r.entity_type),
+                    };
+                    SearchRow {
+                        id,
+                        title,
+                        content_type,
+                        url: link.map(|l| absolute_url(base, &l)).unwrap_or_default(),
+                        excerpt: clean_excerpt(&r.excerpt),
+                    }
+                })
+                .collect();
+            Ok(Page::cursor(rows, response.links.next))
+        }
+    })
+    .await
+}
 
-    #[derive(Serialize)]
-    struct Row<'a> {
-        id: &'a str,
-        title: &'a str,
-        content_type: &'a str,
+fn absolute_url(base: &str, link: &str) -> String {
+    if link.starts_with("http://") || link.starts_with("https://") {
+        link.to_string()
+    } else {
+        format!("{}{}", base.trim_end_matches('/'), link)
     }
+}
 
-    let rows: Vec<Row<'_>> = response
-        .results
-        .iter()
-        .map(|r| Row {
-            id: r.id.as_str(),
-            title: r.title.as_str(),
-            content_type: r.content_type.as_str(),
-        })
-        .collect();
-
-    ctx.renderer.render(&rows)
+/// Flatten a search excerpt to one line, marking highlighted terms with
+/// `*bold*` (readable in a terminal, rendered as bold in Slack).
+fn clean_excerpt(excerpt: &str) -> String {
+    let text = excerpt
+        .replace("@@@hl@@@", "*")
+        .replace("@@@endhl@@@", "*")
+        .replace("&hellip;", "...")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 // Text search
 pub async fn search_text(
     ctx: &ConfluenceContext<'_>,
     query: &str,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    let cql = format!("text ~ \"{}\"", query);
-    search_cql(ctx, &cql, limit).await
+    search_cql(ctx, &text_cql(query), page).await
 }
 
 // Search in space
@@ -72,10 +158,17 @@ pub async fn search_in_space(
     ctx: &ConfluenceContext<'_>,
     space_key: &str,
     query: &str,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    let cql = format!("space = \"{}\" AND text ~ \"{}\"", space_key, query);
-    search_cql(ctx, &cql, limit).await
+    search_cql(ctx, &in_space_cql(space_key, query), page).await
+}
+
+pub fn text_cql(query: &str) -> String {
+    format!("text ~ \"{}\"", query)
+}
+
+pub fn in_space_cql(space_key: &str, query: &str) -> String {
+    format!("space = \"{}\" AND text ~ \"{}\"", space_key, query)
 }
 
 // Search using filter parameters
@@ -89,8 +182,23 @@ pub async fn search_params(
     title: Option<&str>,
     text: Option<&str>,
     show_query: bool,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
+    let cql = build_params_cql(space, r#type, creator, label, title, text, show_query)?;
+    let rows = fetch_cql_rows(ctx, &cql, page, PARAMS_SEARCH_LIMIT).await?;
+    ctx.renderer.render(&rows)
+}
+
+/// Build CQL from filter flags, printing it when requested.
+pub fn build_params_cql(
+    space: Option<&str>,
+    r#type: Option<&str>,
+    creator: Option<&str>,
+    label: &[String],
+    title: Option<&str>,
+    text: Option<&str>,
+    show_query: bool,
+) -> Result<String> {
     let mut builder = CqlBuilder::new();
 
     // Add filters conditionally
@@ -125,5 +233,34 @@ pub async fn search_params(
         println!();
     }
 
-    search_cql(ctx, &cql, Some(limit)).await
+    Ok(cql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absolute_url() {
+        assert_eq!(
+            absolute_url(
+                "https://site.atlassian.net/wiki/",
+                "/spaces/DEV/pages/1/Home"
+            ),
+            "https://site.atlassian.net/wiki/spaces/DEV/pages/1/Home"
+        );
+        assert_eq!(
+            absolute_url("https://site/wiki", "https://other/x"),
+            "https://other/x"
+        );
+    }
+
+    #[test]
+    fn test_clean_excerpt() {
+        assert_eq!(
+            clean_excerpt("Restart the @@@hl@@@worker@@@endhl@@@ when\n  &quot;stuck&quot; &amp; idle&hellip;"),
+            "Restart the *worker* when \"stuck\" & idle..."
+        );
+        assert_eq!(clean_excerpt(""), "");
+    }
 }
@@ -0,0 +1,430 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+use super::mentions::escape;
+use super::utils::{fetch_v2_list, ConfluenceContext};
+use crate::commands::pagination::PaginationArgs;
+use crate::query::CqlBuilder;
+
+// Static site export: the labelled pages of a space as linked HTML files, with
+// an index built from the page tree, so selected wiki content can be published
+// outside Confluence
+
+/// Output format of `confluence export site`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiteFormat {
+    Html,
+}
+
+#[derive(Deserialize)]
+struct Content {
+    id: String,
+    title: String,
+    #[serde(default)]
+    ancestors: Vec<Ancestor>,
+    #[serde(default)]
+    body: Option<Body>,
+}
+
+#[derive(Deserialize)]
+struct Ancestor {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct Body {
+    export_view: View,
+}
+
+#[derive(Deserialize)]
+struct View {
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    title: String,
+    #[serde(rename = "downloadLink")]
+    download_link: String,
+}
+
+/// An exported page and where it sits in the exported tree.
+struct SitePage {
+    id: String,
+    title: String,
+    file: String,
+    /// Nearest ancestor that is also exported
+    parent: Option<String>,
+    html: String,
+}
+
+pub async fn export_site(
+    ctx: &ConfluenceContext<'_>,
+    space: &str,
+    label: &str,
+    out: &Path,
+    format: SiteFormat,
+) -> Result<()> {
+    // HTML is the only format so far
+    let SiteFormat::Html = format;
+    let cql = CqlBuilder::new()
+        .eq("space", space)
+        .eq("type", "page")
+        .eq("label", label)
+        .finish();
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let contents: Vec<Content> = fetch_v2_list(
+        ctx,
+        &format!(
+            "/wiki/rest/api/content/search?cql={}&expand=body.export_view,ancestors",
+            urlencoding::encode(&cql)
+        ),
+        &everything,
+        50,
+        "labelled pages",
+    )
+    .await?;
+    if contents.is_empty() {
+        bail!("No pages in space {space} are labelled '{label}'");
+    }
+
+    let pages = site_pages(contents);
+    let attachments_dir = out.join("attachments");
+    fs::create_dir_all(&attachments_dir)
+        .with_context(|| format!("Failed to create directory: {}", out.display()))?;
+
+    let mut copied = HashSet::new();
+    for page in &pages {
+        for name in download_attachments(ctx, &page.id, &attachments_dir.join(&page.id)).await? {
+            copied.insert((page.id.clone(), name));
+        }
+    }
+
+    let files: HashMap<&str, &str> = pages
+        .iter()
+        .map(|p| (p.id.as_str(), p.file.as_str()))
+        .collect();
+    let base = format!("{}/wiki", ctx.client.base_url().trim_end_matches('/'));
+    for page in &pages {
+        let body = rewrite_links(&page.html, &base, &files, &copied);
+        let path = out.join(&page.file);
+        fs::write(&path, render_page(page, &pages, &body))
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+    let index = out.join("index.html");
+    fs::write(&index, render_index(space, &pages))
+        .with_context(|| format!("Failed to write file: {}", index.display()))?;
+
+    tracing::info!(
+        pages = pages.len(),
+        attachments = copied.len(),
+        out = %out.display(),
+        "Exported static site"
+    );
+    println!(
+        "✅ Exported {} pages and {} attachments to {}",
+        pages.len(),
+        copied.len(),
+        out.display()
+    );
+    Ok(())
+}
+
+/// Give each page a file name and hang it under its nearest exported ancestor,
+/// so a labelled page below an unlabelled one still lands in the tree.
+fn site_pages(mut contents: Vec<Content>) -> Vec<SitePage> {
+    contents.sort_by(|a, b| a.title.cmp(&b.title).then(a.id.cmp(&b.id)));
+    let exported: HashSet<String> = contents.iter().map(|c| c.id.clone()).collect();
+
+    let mut used = HashSet::new();
+    contents
+        .into_iter()
+        .map(|content| {
+            let slug = slugify(&content.title);
+            let file = if used.insert(slug.clone()) {
+                format!("{slug}.html")
+            } else {
+                format!("{slug}-{}.html", content.id)
+            };
+            SitePage {
+                parent: content
+                    .ancestors
+                    .iter()
+                    .rev()
+                    .find(|a| exported.contains(&a.id))
+                    .map(|a| a.id.clone()),
+                html: content
+                    .body
+                    .map(|b| b.export_view.value)
+                    .unwrap_or_default(),
+                id: content.id,
+                title: content.title,
+                file,
+            }
+        })
+        .collect()
+}
+
+fn slugify(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    // index.html is the table of contents
+    match slug.as_str() {
+        "" => "page".to_string(),
+        "index" => "index-page".to_string(),
+        _ => slug,
+    }
+}
+
+/// Download every attachment of a page into `dir`, returning their file names.
+async fn download_attachments(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    dir: &Path,
+) -> Result<Vec<String>> {
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let attachments: Vec<Attachment> = fetch_v2_list(
+        ctx,
+        &format!("/wiki/api/v2/pages/{page_id}/attachments"),
+        &everything,
+        50,
+        "attachments",
+    )
+    .await?;
+    if attachments.is_empty() {
+        return Ok(Vec::new());
+    }
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+
+    let base = format!("{}/wiki", ctx.client.base_url().trim_end_matches('/'));
+    let http_client = reqwest::Client::new();
+    let mut names = Vec::new();
+    for attachment in attachments {
+        if attachment.title.contains(['/', '\\']) || matches!(attachment.title.as_str(), "." | "..")
+        {
+            tracing::warn!(page_id, title = %attachment.title, "Skipping attachment with a path in its name");
+            continue;
+        }
+        let request = ctx
+            .client
+            .apply_auth(http_client.get(format!("{base}{}", attachment.download_link)));
+        let response = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to download attachment {}", attachment.title))?;
+        let content = response
+            .bytes()
+            .await
+            .context("Failed to read attachment content")?;
+        let path = dir.join(&attachment.title);
+        fs::write(&path, content)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+        names.push(attachment.title);
+    }
+    Ok(names)
+}
+
+/// Point links at exported pages and attachments to the local copies, and make
+/// other wiki-relative links absolute so they still resolve off-site.
+fn rewrite_links(
+    html: &str,
+    base: &str,
+    files: &HashMap<&str, &str>,
+    attachments: &HashSet<(String, String)>,
+) -> String {
+    let attribute = Regex::new(r#"(href|src)="([^"]*)""#).expect("valid regex");
+    let page_link = Regex::new(r"/pages/(\d+)|[?&]pageId=(\d+)").expect("valid regex");
+    let attachment_link =
+        Regex::new(r"/download/(?:attachments|thumbnails)/(\d+)/([^?#]+)").expect("valid regex");
+
+    attribute
+        .replace_all(html, |caps: &Captures| {
+            let name = &caps[1];
+            let url = caps[2].replace("&amp;", "&");
+            let wiki = url.starts_with("/wiki/") || url.starts_with(base);
+
+            if wiki {
+                if let Some(found) = attachment_link.captures(&url) {
+                    let file = urlencoding::decode(&found[2])
+                        .map(|f| f.into_owned())
+                        .unwrap_or_else(|_| found[2].to_string());
+                    if attachments.contains(&(found[1].to_string(), file.clone())) {
+                        return format!(
+                            "{name}=\"attachments/{}/{}\"",
+                            &found[1],
+                            urlencoding::encode(&file)
+                        );
+                    }
+                } else if let Some(found) = page_link.captures(&url) {
+                    let id = found.get(1).or(found.get(2)).map_or("", |m| m.as_str());
+                    if let Some(file) = files.get(id) {
+                        let anchor = url.find('#').map_or("", |i| &url[i..]);
+                        return format!("{name}=\"{file}{}\"", escape(anchor));
+                    }
+                }
+            }
+            match url.strip_prefix("/wiki") {
+                Some(rest) => format!("{name}=\"{}\"", escape(&format!("{base}{rest}"))),
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}nav{font-size:.9rem;margin-bottom:1rem}img{max-width:100%}table{border-collapse:collapse}td,th{border:1px solid #ccc;padding:.25rem .5rem}";
+
+fn document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+fn render_page(page: &SitePage, pages: &[SitePage], body: &str) -> String {
+    let by_id: HashMap<&str, &SitePage> = pages.iter().map(|p| (p.id.as_str(), p)).collect();
+    let mut trail = Vec::new();
+    let mut parent = page.parent.as_deref();
+    while let Some(ancestor) = parent.and_then(|id| by_id.get(id)) {
+        trail.push(format!(
+            "<a href=\"{}\">{}</a>",
+            ancestor.file,
+            escape(&ancestor.title)
+        ));
+        parent = ancestor.parent.as_deref();
+    }
+    trail.push("<a href=\"index.html\">Home</a>".to_string());
+    trail.reverse();
+
+    let children: Vec<String> = pages
+        .iter()
+        .filter(|p| p.parent.as_deref() == Some(page.id.as_str()))
+        .map(|p| format!("<li><a href=\"{}\">{}</a></li>", p.file, escape(&p.title)))
+        .collect();
+    let children = if children.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n<h2>Child pages</h2>\n<ul>\n{}\n</ul>",
+            children.join("\n")
+        )
+    };
+
+    document(
+        &page.title,
+        &format!(
+            "<nav>{}</nav>\n<h1>{}</h1>\n{body}{children}",
+            trail.join(" › "),
+            escape(&page.title)
+        ),
+    )
+}
+
+fn render_index(space: &str, pages: &[SitePage]) -> String {
+    let mut children: BTreeMap<Option<&str>, Vec<&SitePage>> = BTreeMap::new();
+    for page in pages {
+        children
+            .entry(page.parent.as_deref())
+            .or_default()
+            .push(page);
+    }
+
+    fn tree(parent: Option<&str>, children: &BTreeMap<Option<&str>, Vec<&SitePage>>) -> String {
+        let Some(pages) = children.get(&parent) else {
+            return String::new();
+        };
+        let items: Vec<String> = pages
+            .iter()
+            .map(|page| {
+                format!(
+                    "<li><a href=\"{}\">{}</a>{}</li>",
+                    page.file,
+                    escape(&page.title),
+                    tree(Some(&page.id), children)
+                )
+            })
+            .collect();
+        format!("<ul>{}</ul>", items.join(""))
+    }
+
+    document(
+        space,
+        &format!("<h1>{}</h1>\n{}", escape(space), tree(None, &children)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(id: &str, title: &str, ancestors: &[&str]) -> Content {
+        Content {
+            id: id.to_string(),
+            title: title.to_string(),
+            ancestors: ancestors
+                .iter()
+                .map(|id| Ancestor { id: id.to_string() })
+                .collect(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_site_pages() {
+        let pages = site_pages(vec![
+            content("1", "Guide", &[]),
+            content("3", "Install", &["1", "2"]),
+            content("4", "Guide", &["9"]),
+        ]);
+        let install = pages.iter().find(|p| p.id == "3").unwrap();
+        assert_eq!(install.parent.as_deref(), Some("1"));
+        assert_eq!(install.file, "install.html");
+        assert_eq!(pages[0].file, "guide.html");
+        assert_eq!(pages[1].file, "guide-4.html");
+        assert_eq!(pages[1].parent, None);
+        assert_eq!(slugify("Index"), "index-page");
+    }
+
+    #[test]
+    fn test_rewrite_links() {
+        let files = HashMap::from([("42", "install.html")]);
+        let attachments = HashSet::from([("42".to_string(), "arch diagram.png".to_string())]);
+        let html = concat!(
+            r#"<a href="/wiki/spaces/DOC/pages/42/Install#setup">Install</a> "#,
+            r#"<img src="/wiki/download/attachments/42/arch%20diagram.png?version=1&amp;api=v2"> "#,
+            r#"<a href="/wiki/spaces/DOC/pages/7/Internal">Internal</a> "#,
+            r#"<a href="https://example.com">Out</a>"#
+        );
+        assert_eq!(
+            rewrite_links(
+                html,
+                "https://acme.atlassian.net/wiki",
+                &files,
+                &attachments
+            ),
+            concat!(
+                r#"<a href="install.html#setup">Install</a> "#,
+                r#"<img src="attachments/42/arch%20diagram.png"> "#,
+                r#"<a href="https://acme.atlassian.net/wiki/spaces/DOC/pages/7/Internal">Internal</a> "#,
+                r#"<a href="https://example.com">Out</a>"#
+            )
+        );
+    }
+}
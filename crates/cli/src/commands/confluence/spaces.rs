@@ -1,20 +1,18 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use super::utils::ConfluenceContext;
+use super::markdown;
+use super::utils::{fetch_v2_list, ConfluenceContext, V2_LIMIT};
+use crate::commands::pagination::PaginationArgs;
 
 // List spaces
 pub async fn list_spaces(
     ctx: &ConfluenceContext<'_>,
-    limit: Option<usize>,
+    page: &PaginationArgs,
     space_type: Option<&str>,
 ) -> Result<()> {
-    #[derive(Deserialize)]
-    struct SpacesResponse {
-        results: Vec<Space>,
-    }
-
     #[derive(Deserialize)]
     struct Space {
         id: String,
@@ -25,27 +23,12 @@ pub async fn list_spaces(
         status: String,
     }
 
-    let mut query_params = Vec::new();
-
-    if let Some(l) = limit {
-        query_params.push(format!("limit={}", l));
-    }
-
+    let mut path = String::from("/wiki/api/v2/spaces");
     if let Some(st) = space_type {
-        query_params.push(format!("type={}", st));
+        path.push_str(&format!("?type={}", st));
     }
 
-    let query_string = if query_params.is_empty() {
-        String::new()
-    } else {
-        format!("?{}", query_params.join("&"))
-    };
-
-    let response: SpacesResponse = ctx
-        .client
-        .get(&format!("/wiki/api/v2/spaces{}", query_string))
-        .await
-        .context("Failed to list spaces")?;
+    let spaces: Vec<Space> = fetch_v2_list(ctx, &path, page, V2_LIMIT, "spaces").await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -56,8 +39,7 @@ pub async fn list_spaces(
         status: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .results
+    let rows: Vec<Row<'_>> = spaces
         .iter()
         .map(|s| Row {
             id: s.id.as_str(),
@@ -68,7 +50,7 @@ pub async fn list_spaces(
         })
         .collect();
 
-    ctx.renderer.render(&rows)
+    ctx.renderer.render_with_id(&rows, "key")
 }
 
 // Get space details
@@ -79,17 +61,115 @@ pub async fn get_space(ctx: &ConfluenceContext<'_>, key: &str) -> Result<()> {
         .await
         .with_context(|| format!("Failed to get space {}", key))?;
 
-    println!("{}", serde_json::to_string_pretty(&space)?);
+    ctx.renderer.print_json(&space)?;
     Ok(())
 }
 
+/// Homepage and permission baseline applied to a newly created space.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SpaceTemplate {
+    /// Page set as the space homepage.
+    homepage: Option<Homepage>,
+    /// Grants for `--admin-group` and `--read-group`.
+    permissions: PermissionBaseline,
+}
+
+/// Homepage title and Markdown body; `{name}` and `{key}` are substituted.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct Homepage {
+    title: String,
+    body: String,
+}
+
+/// `OPERATION:TARGET` grants (e.g. `create:page`) for the admin and read groups.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(default)]
+struct PermissionBaseline {
+    admin: Vec<String>,
+    read: Vec<String>,
+}
+
+impl Default for PermissionBaseline {
+    fn default() -> Self {
+        Self {
+            admin: vec!["read:space".to_string(), "administer:space".to_string()],
+            read: vec!["read:space".to_string()],
+        }
+    }
+}
+
+/// Load a built-in template (`team`, `project`, `kb`) or a YAML template file.
+fn load_template(spec: &str) -> Result<SpaceTemplate> {
+    let content = match spec {
+        "team" => include_str!("../../../templates/confluence/team.yaml").to_string(),
+        "project" => include_str!("../../../templates/confluence/project.yaml").to_string(),
+        "kb" => include_str!("../../../templates/confluence/kb.yaml").to_string(),
+        path => std::fs::read_to_string(path).with_context(|| {
+            format!("Unknown template '{path}': expected team, project, kb or a template file")
+        })?,
+    };
+    let template: SpaceTemplate = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse space template '{spec}'"))?;
+
+    for grant in template
+        .permissions
+        .admin
+        .iter()
+        .chain(&template.permissions.read)
+    {
+        parse_grant(grant)?;
+    }
+    Ok(template)
+}
+
+fn parse_grant(grant: &str) -> Result<(&str, &str)> {
+    match grant.split_once(':') {
+        Some((operation, target)) if !operation.is_empty() && !target.is_empty() => {
+            Ok((operation, target))
+        }
+        _ => bail!("Invalid permission '{grant}': expected OPERATION:TARGET (e.g. create:page)"),
+    }
+}
+
+fn fill(text: &str, name: &str, key: &str) -> String {
+    text.replace("{name}", name).replace("{key}", key)
+}
+
 // Create space
+#[allow(clippy::too_many_arguments)]
 pub async fn create_space(
     ctx: &ConfluenceContext<'_>,
     key: &str,
     name: &str,
     description: Option<&str>,
+    template: Option<&str>,
+    admin_group: Option<&str>,
+    read_group: Option<&str>,
+    if_not_exists: bool,
 ) -> Result<()> {
+    let template = template.map(load_template).transpose()?.unwrap_or_default();
+
+    if if_not_exists {
+        let existing: Value = ctx
+            .client
+            .get(&format!(
+                "/wiki/api/v2/spaces?keys={}",
+                urlencoding::encode(key)
+            ))
+            .await
+            .with_context(|| format!("Failed to check for space {}", key))?;
+        if existing["results"]
+            .as_array()
+            .is_some_and(|r| !r.is_empty())
+        {
+            tracing::info!(%key, "Space already exists, not created");
+            println!("✅ Space {} already exists", key);
+            ctx.renderer.print_json(&existing)?;
+            return Ok(());
+        }
+    }
+
     let mut payload = json!({
         "key": key,
         "name": name,
@@ -110,6 +190,8 @@ pub async fn create_space(
         id: String,
         key: String,
         name: String,
+        #[serde(rename = "homepageId", default)]
+        homepage_id: Option<String>,
     }
 
     let response: CreateResponse = ctx
@@ -120,6 +202,76 @@ pub async fn create_space(
 
     tracing::info!(id = %response.id, key = %response.key, "Space created successfully");
     println!("✅ Created space: {} ({})", response.name, response.key);
+
+    for (group, grants) in [
+        (admin_group, &template.permissions.admin),
+        (read_group, &template.permissions.read),
+    ] {
+        let Some(group) = group else { continue };
+        for grant in grants {
+            let (operation, target) = parse_grant(grant)?;
+            grant_permission(ctx, &response.key, "group", group, operation, target)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Space {} was created, but granting {grant} to {group} failed",
+                        response.key
+                    )
+                })?;
+        }
+        println!("✅ Granted {} permissions to group {}", grants.len(), group);
+    }
+
+    if let Some(homepage) = &template.homepage {
+        let page_id = response
+            .homepage_id
+            .as_deref()
+            .context("Space was created without a homepage to fill from the template")?;
+        let title = fill(&homepage.title, &response.name, &response.key);
+        let body = markdown::to_storage(&fill(&homepage.body, &response.name, &response.key));
+        set_homepage(ctx, page_id, &title, &body)
+            .await
+            .with_context(|| {
+                format!(
+                    "Space {} was created, but setting its homepage failed",
+                    response.key
+                )
+            })?;
+        println!("✅ Set homepage: {} ({})", title, page_id);
+    }
+    Ok(())
+}
+
+async fn set_homepage(
+    ctx: &ConfluenceContext<'_>,
+    page_id: &str,
+    title: &str,
+    body: &str,
+) -> Result<()> {
+    let current: Value = ctx
+        .client
+        .get(&format!("/wiki/api/v2/pages/{}", page_id))
+        .await
+        .with_context(|| format!("Failed to get page {}", page_id))?;
+    let version = current["version"]["number"].as_i64().unwrap_or(1);
+
+    let payload = json!({
+        "id": page_id,
+        "status": "current",
+        "title": title,
+        "body": {
+            "representation": "storage",
+            "value": body
+        },
+        "version": {
+            "number": version + 1
+        }
+    });
+    let _: Value = ctx
+        .client
+        .put(&format!("/wiki/api/v2/pages/{}", page_id), &payload)
+        .await
+        .with_context(|| format!("Failed to update page {}", page_id))?;
     Ok(())
 }
 
@@ -192,7 +344,7 @@ pub async fn get_space_permissions(ctx: &ConfluenceContext<'_>, space_key: &str)
         .await
         .with_context(|| format!("Failed to get permissions for space {}", space_key))?;
 
-    println!("{}", serde_json::to_string_pretty(&permissions)?);
+    ctx.renderer.print_json(&permissions)?;
     Ok(())
 }
 
@@ -203,6 +355,32 @@ pub async fn add_space_permission(
     permission_type: &str,
     subject_type: &str,
     subject_id: &str,
+) -> Result<()> {
+    grant_permission(
+        ctx,
+        space_key,
+        subject_type,
+        subject_id,
+        permission_type,
+        "space",
+    )
+    .await?;
+
+    tracing::info!(%space_key, %permission_type, %subject_id, "Permission added successfully");
+    println!(
+        "✅ Added {} permission for {} to space {}",
+        permission_type, subject_id, space_key
+    );
+    Ok(())
+}
+
+pub async fn grant_permission(
+    ctx: &ConfluenceContext<'_>,
+    space_key: &str,
+    subject_type: &str,
+    subject_id: &str,
+    operation: &str,
+    target: &str,
 ) -> Result<()> {
     let payload = json!({
         "subject": {
@@ -210,8 +388,8 @@ pub async fn add_space_permission(
             "identifier": subject_id
         },
         "operation": {
-            "key": permission_type,
-            "target": "space"
+            "key": operation,
+            "target": target
         }
     });
 
@@ -223,11 +401,43 @@ pub async fn add_space_permission(
         )
         .await
         .with_context(|| format!("Failed to add permission to space {}", space_key))?;
-
-    tracing::info!(%space_key, %permission_type, %subject_id, "Permission added successfully");
-    println!(
-        "✅ Added {} permission for {} to space {}",
-        permission_type, subject_id, space_key
-    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates() {
+        for name in ["team", "project", "kb"] {
+            let template = load_template(name).unwrap();
+            let homepage = template
+                .homepage
+                .expect("built-in templates set a homepage");
+            assert!(homepage.title.contains("{name}"));
+            assert_eq!(template.permissions.admin[0], "read:space");
+            assert_eq!(template.permissions.read[0], "read:space");
+        }
+
+        let err = load_template("wiki").unwrap_err();
+        assert!(err.to_string().contains("expected team, project, kb"));
+    }
+
+    #[test]
+    fn test_template_defaults_and_grants() {
+        let template: SpaceTemplate =
+            serde_yaml::from_str("permissions:\n  read: [read:space, create:comment]").unwrap();
+        assert!(template.homepage.is_none());
+        assert_eq!(
+            template.permissions.admin,
+            vec!["read:space", "administer:space"]
+        );
+        assert_eq!(template.permissions.read.len(), 2);
+
+        assert_eq!(parse_grant("create:page").unwrap(), ("create", "page"));
+        assert!(parse_grant("administer").is_err());
+        assert!(parse_grant(":space").is_err());
+        assert_eq!(fill("{name} ({key})", "Docs", "DOC"), "Docs (DOC)");
+    }
+}
@@ -1,7 +1,90 @@
+use anyhow::{Context, Result};
 use atlassian_cli_api::ApiClient;
 use atlassian_cli_output::OutputRenderer;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::path::Path;
+
+use super::mentions::{find_storage_mentions, link_storage_mentions, resolve_users};
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 
 pub struct ConfluenceContext<'a> {
     pub client: ApiClient,
     pub renderer: &'a OutputRenderer,
+    /// Name of the active profile, used to key per-profile state.
+    pub profile: &'a str,
+}
+
+/// Read a page or blog body file, converting Markdown (`.md`, `.markdown`) to
+/// storage format with `@user@example.com` mentions turned into user links.
+pub async fn read_body(ctx: &ConfluenceContext<'_>, file: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read body file: {}", file.display()))?;
+    let is_markdown = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"));
+    if !is_markdown {
+        return Ok(content);
+    }
+
+    let storage = super::markdown::to_storage(&content);
+    let account_ids = resolve_users(ctx, &find_storage_mentions(&storage)).await?;
+    Ok(link_storage_mentions(&storage, &account_ids))
+}
+
+/// Results returned by v2 list endpoints unless `--limit` says otherwise.
+pub const V2_LIMIT: usize = 25;
+
+/// Page through a v2 list endpoint (`path` may already carry query parameters)
+/// by following the cursor in `_links.next`.
+pub async fn fetch_v2_list<T: DeserializeOwned>(
+    ctx: &ConfluenceContext<'_>,
+    path: &str,
+    page: &PaginationArgs,
+    default_limit: usize,
+    what: &str,
+) -> Result<Vec<T>> {
+    #[derive(Deserialize)]
+    struct ListResponse<T> {
+        results: Vec<T>,
+        #[serde(rename = "_links", default)]
+        links: Option<Links>,
+    }
+
+    #[derive(Deserialize)]
+    struct Links {
+        next: Option<String>,
+    }
+
+    page.collect(default_limit, 250, Paging::Cursor, |request| {
+        let path = match request.cursor {
+            Some(next) => v2_next_path(next),
+            None => {
+                let separator = if path.contains('?') { '&' } else { '?' };
+                format!("{path}{separator}limit={}", request.size)
+            }
+        };
+        async move {
+            let response: ListResponse<T> = ctx
+                .client
+                .get(&path)
+                .await
+                .with_context(|| format!("Failed to list {what}"))?;
+            Ok(Page::cursor(
+                response.results,
+                response.links.and_then(|l| l.next),
+            ))
+        }
+    })
+    .await
+}
+
+/// v2 next links are normally site-relative ("/wiki/api/v2/...").
+fn v2_next_path(next: String) -> String {
+    if next.starts_with("/wiki") {
+        next
+    } else {
+        format!("/wiki{}", next)
+    }
 }
@@ -0,0 +1,147 @@
+use chrono::{Datelike, Days, Local, Months, NaiveDate, Weekday};
+
+// Date flags (`--from`, `--release-date`, `--older-than`, ...) accept relative
+// and natural-language dates as well as YYYY-MM-DD, resolved against today's
+// local date when the command starts
+
+const ACCEPTED: &str =
+    "use YYYY-MM-DD, today, yesterday, tomorrow, -7d, 2w, +1m, '3 days ago', 'in 2 weeks', 'last monday' or 'next friday'";
+
+/// Clap value parser for date flags.
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    parse_date_from(input, Local::now().date_naive())
+}
+
+/// Resolve `input` relative to `today`. Offsets without a sign, such as `2w`,
+/// count back from today; `+` counts forward.
+pub fn parse_date_from(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let invalid = || format!("invalid date '{input}': {ACCEPTED}");
+    let normalized = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&normalized, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    match words.as_slice() {
+        ["today"] | ["now"] => Ok(today),
+        ["yesterday"] => offset(today, -1, Unit::Day).ok_or_else(invalid),
+        ["tomorrow"] => offset(today, 1, Unit::Day).ok_or_else(invalid),
+        ["last", day] => {
+            let weekday = day.parse::<Weekday>().map_err(|_| invalid())?;
+            let back =
+                (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday() - 1)
+                    % 7
+                    + 1;
+            offset(today, -i64::from(back), Unit::Day).ok_or_else(invalid)
+        }
+        ["next", day] => {
+            let weekday = day.parse::<Weekday>().map_err(|_| invalid())?;
+            let ahead =
+                (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday() - 1)
+                    % 7
+                    + 1;
+            offset(today, i64::from(ahead), Unit::Day).ok_or_else(invalid)
+        }
+        [amount, unit, "ago"] => {
+            let (amount, unit) = amount_and_unit(amount, unit).ok_or_else(invalid)?;
+            offset(today, -amount, unit).ok_or_else(invalid)
+        }
+        ["in", amount, unit] => {
+            let (amount, unit) = amount_and_unit(amount, unit).ok_or_else(invalid)?;
+            offset(today, amount, unit).ok_or_else(invalid)
+        }
+        [compact] => {
+            let (sign, rest) = match compact.strip_prefix('+') {
+                Some(rest) => (1, rest),
+                None => (-1, compact.strip_prefix('-').unwrap_or(compact)),
+            };
+            let split = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let (amount, unit) = rest.split_at(split);
+            let (amount, unit) = amount_and_unit(amount, unit).ok_or_else(invalid)?;
+            offset(today, sign * amount, unit).ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+fn amount_and_unit(amount: &str, unit: &str) -> Option<(i64, Unit)> {
+    let amount: i64 = amount.parse().ok()?;
+    let unit = match unit {
+        "d" | "day" | "days" => Unit::Day,
+        "w" | "week" | "weeks" => Unit::Week,
+        "m" | "mo" | "month" | "months" => Unit::Month,
+        "y" | "year" | "years" => Unit::Year,
+        _ => return None,
+    };
+    Some((amount, unit))
+}
+
+fn offset(today: NaiveDate, amount: i64, unit: Unit) -> Option<NaiveDate> {
+    let magnitude = amount.unsigned_abs();
+    match unit {
+        Unit::Day | Unit::Week => {
+            let days = Days::new(if matches!(unit, Unit::Week) {
+                magnitude.checked_mul(7)?
+            } else {
+                magnitude
+            });
+            if amount < 0 {
+                today.checked_sub_days(days)
+            } else {
+                today.checked_add_days(days)
+            }
+        }
+        Unit::Month | Unit::Year => {
+            let months = if matches!(unit, Unit::Year) {
+                magnitude.checked_mul(12)?
+            } else {
+                magnitude
+            };
+            let months = Months::new(u32::try_from(months).ok()?);
+            if amount < 0 {
+                today.checked_sub_months(months)
+            } else {
+                today.checked_add_months(months)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_from() {
+        // A Wednesday
+        let today = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+        let parse = |input: &str| parse_date_from(input, today).unwrap().to_string();
+
+        assert_eq!(parse("2024-01-31"), "2024-01-31");
+        assert_eq!(parse("Yesterday"), "2024-06-04");
+        assert_eq!(parse("-7d"), "2024-05-29");
+        assert_eq!(parse("2w"), "2024-05-22");
+        assert_eq!(parse("+1m"), "2024-07-05");
+        assert_eq!(parse("3 days ago"), "2024-06-02");
+        assert_eq!(parse("in 1 year"), "2025-06-05");
+        assert_eq!(parse("last monday"), "2024-06-03");
+        assert_eq!(parse("last wednesday"), "2024-05-29");
+        assert_eq!(parse("next friday"), "2024-06-07");
+        assert_eq!(parse("next wed"), "2024-06-12");
+
+        let err = parse_date_from("soon", today).unwrap_err();
+        assert!(err.starts_with("invalid date 'soon': use YYYY-MM-DD"));
+        assert!(parse_date_from("7x", today).is_err());
+        assert!(parse_date_from("last month", today).is_err());
+    }
+}
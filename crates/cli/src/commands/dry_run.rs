@@ -0,0 +1,149 @@
+use anyhow::Result;
+use atlassian_cli_bulk::PlannedOperation;
+use atlassian_cli_output::{OutputFormat, OutputRenderer};
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+/// A single field that a mutating command would change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub current: String,
+    pub proposed: String,
+}
+
+/// Compare `(field, current, proposed)` triples, keeping only the fields whose
+/// proposed value is set and differs from the current one.
+pub fn field_changes(fields: Vec<(&str, String, Option<String>)>) -> Vec<FieldChange> {
+    fields
+        .into_iter()
+        .filter_map(|(field, current, proposed)| {
+            let proposed = proposed?;
+            (proposed != current).then(|| FieldChange {
+                field: field.to_string(),
+                current,
+                proposed,
+            })
+        })
+        .collect()
+}
+
+/// Print what an update of `target` would change without sending it. Table
+/// output shows a field-by-field diff; other formats render the changes as rows.
+pub fn render_diff(renderer: &OutputRenderer, target: &str, changes: &[FieldChange]) -> Result<()> {
+    if renderer.format() != OutputFormat::Table {
+        return renderer.render(&changes);
+    }
+
+    println!("🔍 Dry run mode - no changes will be made:");
+    if changes.is_empty() {
+        println!("  No changes to {target}");
+        return Ok(());
+    }
+
+    println!("  Would update {target}:");
+    for change in changes {
+        println!("  {}:", change.field);
+        for line in diff_lines(&change.current, &change.proposed) {
+            println!("    {line}");
+        }
+    }
+    Ok(())
+}
+
+/// One value a bulk edit would change on one of many targets.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetChange {
+    pub target: String,
+    pub field: String,
+    pub current: String,
+    pub proposed: String,
+}
+
+/// Print the changes a bulk dry run would make, grouped by target. Non-table
+/// formats render the changes as rows.
+pub fn render_bulk_diff(renderer: &OutputRenderer, changes: &[TargetChange]) -> Result<()> {
+    if renderer.format() != OutputFormat::Table {
+        return renderer.render(&changes);
+    }
+
+    println!("🔍 Dry run mode - no changes will be made:");
+    let mut targets = 0;
+    let mut previous: Option<(&str, &str)> = None;
+    for change in changes {
+        let heading = (change.target.as_str(), change.field.as_str());
+        if previous != Some(heading) {
+            if previous.map(|(target, _)| target) != Some(heading.0) {
+                targets += 1;
+            }
+            println!("  {} {}:", change.target, change.field);
+            previous = Some(heading);
+        }
+        for line in diff_lines(&change.current, &change.proposed) {
+            println!("    {line}");
+        }
+    }
+    println!("  {} change(s) across {targets} target(s)", changes.len());
+    Ok(())
+}
+
+/// Print the operations a bulk dry run would perform. Non-table formats render
+/// only the operations, so `--output json` can be kept as a change plan.
+pub fn render_plan(renderer: &OutputRenderer, operations: &[PlannedOperation]) -> Result<()> {
+    if renderer.format() != OutputFormat::Table {
+        return renderer.render(&operations);
+    }
+
+    println!("🔍 Dry run mode - no changes will be made:");
+    renderer.render(&operations)?;
+    println!("  {} operation(s) planned", operations.len());
+    Ok(())
+}
+
+/// Line diff of two values; unchanged lines of multi-line values are kept as context.
+fn diff_lines(current: &str, proposed: &str) -> Vec<String> {
+    TextDiff::from_lines(current, proposed)
+        .iter_all_changes()
+        .map(|change| {
+            let sign = match change.tag() {
+                ChangeTag::Delete => "-",
+                ChangeTag::Insert => "+",
+                ChangeTag::Equal => " ",
+            };
+            format!("{sign} {}", change.value().trim_end_matches('\n'))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_changes_skips_unset_and_unchanged() {
+        let changes = field_changes(vec![
+            ("summary", "Old".to_string(), Some("New".to_string())),
+            ("priority", "High".to_string(), Some("High".to_string())),
+            ("description", "Text".to_string(), None),
+        ]);
+
+        assert_eq!(
+            changes,
+            vec![FieldChange {
+                field: "summary".to_string(),
+                current: "Old".to_string(),
+                proposed: "New".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(diff_lines("Old", "New"), vec!["- Old", "+ New"]);
+        assert_eq!(
+            diff_lines("<p>a</p>\n<p>b</p>\n", "<p>a</p>\n<p>c</p>\n"),
+            vec!["  <p>a</p>", "- <p>b</p>", "+ <p>c</p>"]
+        );
+        assert_eq!(diff_lines("", "New"), vec!["+ New"]);
+    }
+}
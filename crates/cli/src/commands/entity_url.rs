@@ -0,0 +1,177 @@
+use base64::Engine;
+use url::Url;
+
+// Arguments that take an issue key, page ID or pull request also accept the
+// URL copied from the browser. Anything that is not an http(s) URL is passed
+// through unchanged, so plain keys and IDs behave as before
+
+/// Clap value parser for issue keys: `DEV-1`, or a Jira URL such as
+/// `https://x.atlassian.net/browse/DEV-1` or a board URL with `?selectedIssue=DEV-1`.
+pub fn issue_key(input: &str) -> Result<String, String> {
+    let Some(url) = parse_url(input) else {
+        return Ok(input.to_string());
+    };
+    let selected = url
+        .query_pairs()
+        .find(|(name, _)| name == "selectedIssue")
+        .map(|(_, value)| value.into_owned());
+    selected
+        .into_iter()
+        .chain(segments(&url).into_iter().rev())
+        .find(|candidate| is_issue_key(candidate))
+        .map(|key| key.to_uppercase())
+        .ok_or_else(|| format!("no issue key found in URL '{input}'"))
+}
+
+/// Clap value parser for Confluence page IDs: `123456`, or a page URL such as
+/// `.../wiki/spaces/ENG/pages/123456/Title`, `...?pageId=123456` or a `/wiki/x/`
+/// short link.
+pub fn page_id(input: &str) -> Result<String, String> {
+    let Some(url) = parse_url(input) else {
+        return Ok(input.to_string());
+    };
+    let not_found = || format!("no page ID found in URL '{input}'");
+    if let Some((_, id)) = url.query_pairs().find(|(name, _)| name == "pageId") {
+        return Ok(id.into_owned());
+    }
+    let segments = segments(&url);
+    if let Some(at) = segments.iter().position(|s| s == "pages") {
+        return segments[at + 1..]
+            .iter()
+            .find(|s| is_numeric(s))
+            .cloned()
+            .ok_or_else(not_found);
+    }
+    if let [.., x, short] = segments.as_slice() {
+        if x == "x" {
+            return tiny_link_id(short).ok_or_else(not_found);
+        }
+    }
+    Err(not_found())
+}
+
+/// Clap value parser for pull request IDs: `42`, or a Bitbucket pull request URL
+/// such as `https://bitbucket.org/{workspace}/{repo}/pull-requests/42[/...]`.
+pub fn pull_request_id(input: &str) -> Result<i64, String> {
+    let Some(url) = parse_url(input) else {
+        return input
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid pull request ID '{input}'"));
+    };
+    match segments(&url).as_slice() {
+        [_, _, kind, id, ..] if kind == "pull-requests" => id.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("no pull request ID found in URL '{input}'"))
+}
+
+fn parse_url(input: &str) -> Option<Url> {
+    let input = input.trim();
+    if !(input.starts_with("https://") || input.starts_with("http://")) {
+        return None;
+    }
+    Url::parse(input).ok()
+}
+
+fn segments(url: &Url) -> Vec<String> {
+    url.path_segments()
+        .map(|segments| {
+            segments
+                .filter(|s| !s.is_empty())
+                .map(|s| urlencoding::decode(s).map_or_else(|_| s.to_string(), |s| s.into_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `PROJ-123`: a project key starting with a letter, a dash and a number.
+fn is_issue_key(s: &str) -> bool {
+    s.split_once('-').is_some_and(|(project, number)| {
+        project.starts_with(|c: char| c.is_ascii_alphabetic())
+            && project
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && is_numeric(number)
+    })
+}
+
+fn is_numeric(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Short links encode the page ID as little-endian bytes in base64, with `-`
+/// and `_` for `/` and `+` and the trailing zero bytes dropped.
+fn tiny_link_id(short: &str) -> Option<String> {
+    if short.is_empty() || short.len() > 11 {
+        return None;
+    }
+    let encoded = format!("{short:A<11}=").replace('-', "/").replace('_', "+");
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()?;
+    let bytes: [u8; 8] = bytes.try_into().ok()?;
+    Some(u64::from_le_bytes(bytes).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_key() {
+        assert_eq!(issue_key("DEV-1").unwrap(), "DEV-1");
+        assert_eq!(
+            issue_key("https://x.atlassian.net/browse/DEV-123").unwrap(),
+            "DEV-123"
+        );
+        assert_eq!(
+            issue_key(
+                "https://x.atlassian.net/jira/software/projects/DEV/boards/1?selectedIssue=DEV-9"
+            )
+            .unwrap(),
+            "DEV-9"
+        );
+        assert_eq!(
+            issue_key(
+                "https://x.atlassian.net/jira/servicedesk/projects/HELP/queues/custom/1/help-42"
+            )
+            .unwrap(),
+            "HELP-42"
+        );
+        assert!(issue_key("https://x.atlassian.net/jira/your-work").is_err());
+    }
+
+    #[test]
+    fn test_page_id() {
+        assert_eq!(page_id("123456").unwrap(), "123456");
+        assert_eq!(
+            page_id("https://x.atlassian.net/wiki/spaces/ENG/pages/123456/Release+Notes").unwrap(),
+            "123456"
+        );
+        assert_eq!(
+            page_id("https://x.atlassian.net/wiki/spaces/ENG/pages/edit-v2/123456").unwrap(),
+            "123456"
+        );
+        assert_eq!(
+            page_id("https://x.atlassian.net/wiki/pages/viewpage.action?pageId=98765").unwrap(),
+            "98765"
+        );
+        assert_eq!(
+            page_id("https://x.atlassian.net/wiki/x/0gKWSQ").unwrap(),
+            "1234567890"
+        );
+        assert!(page_id("https://x.atlassian.net/wiki/spaces/ENG/overview").is_err());
+    }
+
+    #[test]
+    fn test_pull_request_id() {
+        assert_eq!(pull_request_id("42").unwrap(), 42);
+        assert_eq!(
+            pull_request_id("https://bitbucket.org/acme/api/pull-requests/42/diff").unwrap(),
+            42
+        );
+        assert!(pull_request_id("api").is_err());
+        assert!(pull_request_id("https://bitbucket.org/acme/api/src/main").is_err());
+    }
+}
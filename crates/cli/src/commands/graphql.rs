@@ -0,0 +1,281 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_api::ApiClient;
+use atlassian_cli_output::OutputRenderer;
+use clap::{Args, Subcommand};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+const GATEWAY_PATH: &str = "/gateway/api/graphql";
+
+const ME_QUERY: &str = "query { me { user { accountId name picture } } }";
+
+const CLOUD_ID_QUERY: &str =
+    "query($hostNames: [String!]!) { tenantContexts(hostNames: $hostNames) { cloudId hostName } }";
+
+#[derive(Args, Debug, Clone)]
+pub struct GraphqlArgs {
+    #[command(subcommand)]
+    command: GraphqlCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum GraphqlCommands {
+    /// Run a query against the Atlassian GraphQL gateway.
+    Query {
+        /// File containing the GraphQL document.
+        #[arg(long, conflicts_with = "query")]
+        file: Option<PathBuf>,
+        /// Inline GraphQL document.
+        #[arg(long)]
+        query: Option<String>,
+        /// Query variables as a JSON object.
+        #[arg(long)]
+        vars: Option<String>,
+        /// Dot-separated path (under `data`) to a connection to page through, e.g. `jira.issueSearch`.
+        #[arg(long)]
+        paginate: Option<String>,
+        /// Variable that receives the `endCursor` of the previous page.
+        #[arg(long, default_value = "after")]
+        cursor_var: String,
+        /// Maximum number of pages to fetch when paginating.
+        #[arg(long, default_value_t = 10)]
+        max_pages: usize,
+    },
+    /// Show the account behind the current profile.
+    Me,
+    /// Resolve the cloud ID for the profile's site (needed by many gateway queries).
+    CloudId,
+}
+
+pub struct GraphqlContext<'a> {
+    pub client: ApiClient,
+    pub renderer: &'a OutputRenderer,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponse {
+    #[serde(default)]
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+    message: String,
+    #[serde(default)]
+    path: Option<Vec<Value>>,
+}
+
+pub async fn execute(args: GraphqlArgs, ctx: GraphqlContext<'_>) -> Result<()> {
+    match args.command {
+        GraphqlCommands::Query {
+            file,
+            query,
+            vars,
+            paginate,
+            cursor_var,
+            max_pages,
+        } => {
+            let document = match (file, query) {
+                (Some(path), _) => std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read query file {}", path.display()))?,
+                (None, Some(query)) => query,
+                (None, None) => bail!("Provide a query with --file or --query"),
+            };
+            let variables = parse_variables(vars.as_deref())?;
+
+            match paginate {
+                Some(path) => {
+                    let nodes =
+                        query_all_pages(&ctx, &document, variables, &path, &cursor_var, max_pages)
+                            .await?;
+                    ctx.renderer.render(&nodes)
+                }
+                None => {
+                    let data = run_query(&ctx, &document, &Value::Object(variables)).await?;
+                    ctx.renderer.render(&data)
+                }
+            }
+        }
+        GraphqlCommands::Me => {
+            let data = run_query(&ctx, ME_QUERY, &json!({})).await?;
+            ctx.renderer.render(&data["me"]["user"])
+        }
+        GraphqlCommands::CloudId => {
+            let host = ctx
+                .client
+                .base_url()
+                .parse::<url::Url>()
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .ok_or_else(|| anyhow!("Profile base_url has no host"))?;
+            let data = run_query(&ctx, CLOUD_ID_QUERY, &json!({ "hostNames": [host] })).await?;
+            ctx.renderer.render(&data["tenantContexts"])
+        }
+    }
+}
+
+async fn run_query(ctx: &GraphqlContext<'_>, query: &str, variables: &Value) -> Result<Value> {
+    let payload = json!({ "query": query, "variables": variables });
+
+    let response: GraphqlResponse = ctx
+        .client
+        .post(GATEWAY_PATH, &payload)
+        .await
+        .context("GraphQL gateway request failed")?;
+
+    let messages: Vec<String> = response
+        .errors
+        .iter()
+        .map(|err| match &err.path {
+            Some(path) if !path.is_empty() => {
+                let path = path
+                    .iter()
+                    .map(|p| {
+                        p.as_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| p.to_string())
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".");
+                format!("{} (at {path})", err.message)
+            }
+            _ => err.message.clone(),
+        })
+        .collect();
+
+    match response.data {
+        Some(data) if !data.is_null() => {
+            for message in &messages {
+                tracing::warn!("GraphQL partial error: {message}");
+            }
+            Ok(data)
+        }
+        _ if !messages.is_empty() => bail!("GraphQL query failed: {}", messages.join("; ")),
+        _ => bail!("GraphQL gateway returned no data"),
+    }
+}
+
+async fn query_all_pages(
+    ctx: &GraphqlContext<'_>,
+    query: &str,
+    mut variables: Map<String, Value>,
+    connection_path: &str,
+    cursor_var: &str,
+    max_pages: usize,
+) -> Result<Vec<Value>> {
+    let mut nodes = Vec::new();
+
+    for page in 1..=max_pages.max(1) {
+        let data = run_query(ctx, query, &Value::Object(variables.clone())).await?;
+        let connection = connection_at(&data, connection_path).ok_or_else(|| {
+            anyhow!("No connection found at '{connection_path}' in the response data")
+        })?;
+
+        nodes.extend(connection_nodes(connection));
+
+        match next_cursor(connection) {
+            Some(cursor) => {
+                tracing::debug!(page, cursor, "Fetching next GraphQL page");
+                variables.insert(cursor_var.to_string(), Value::String(cursor.to_string()));
+            }
+            None => return Ok(nodes),
+        }
+    }
+
+    tracing::warn!(
+        max_pages,
+        "Stopped paginating after --max-pages; more results remain"
+    );
+    Ok(nodes)
+}
+
+fn parse_variables(raw: Option<&str>) -> Result<Map<String, Value>> {
+    match raw {
+        None => Ok(Map::new()),
+        Some(raw) => match serde_json::from_str(raw).context("--vars must be valid JSON")? {
+            Value::Object(map) => Ok(map),
+            _ => bail!("--vars must be a JSON object"),
+        },
+    }
+}
+
+/// Walk a dot-separated path from the `data` root.
+fn connection_at<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(data, |value, segment| value.get(segment))
+}
+
+/// Collect items from a Relay-style connection, accepting either `nodes` or `edges[].node`.
+fn connection_nodes(connection: &Value) -> Vec<Value> {
+    if let Some(nodes) = connection.get("nodes").and_then(Value::as_array) {
+        return nodes.clone();
+    }
+
+    connection
+        .get("edges")
+        .and_then(Value::as_array)
+        .map(|edges| {
+            edges
+                .iter()
+                .filter_map(|edge| edge.get("node").cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn next_cursor(connection: &Value) -> Option<&str> {
+    let page_info = connection.get("pageInfo")?;
+    if !page_info.get("hasNextPage")?.as_bool()? {
+        return None;
+    }
+    page_info.get("endCursor")?.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_at_nested_path() {
+        let data = json!({"jira": {"issueSearch": {"nodes": []}}});
+        assert!(connection_at(&data, "jira.issueSearch").is_some());
+        assert!(connection_at(&data, "jira.missing").is_none());
+    }
+
+    #[test]
+    fn test_connection_nodes_from_edges() {
+        let connection = json!({"edges": [{"node": {"id": 1}}, {"node": {"id": 2}}]});
+        assert_eq!(connection_nodes(&connection).len(), 2);
+    }
+
+    #[test]
+    fn test_connection_nodes_prefers_nodes() {
+        let connection = json!({"nodes": [{"id": 1}], "edges": []});
+        assert_eq!(connection_nodes(&connection), vec![json!({"id": 1})]);
+    }
+
+    #[test]
+    fn test_next_cursor() {
+        let more = json!({"pageInfo": {"hasNextPage": true, "endCursor": "abc"}});
+        let done = json!({"pageInfo": {"hasNextPage": false, "endCursor": "abc"}});
+        assert_eq!(next_cursor(&more), Some("abc"));
+        assert_eq!(next_cursor(&done), None);
+        assert_eq!(next_cursor(&json!({})), None);
+    }
+
+    #[test]
+    fn test_parse_variables_requires_object() {
+        assert!(parse_variables(None).unwrap().is_empty());
+        assert_eq!(
+            parse_variables(Some(r#"{"first": 5}"#)).unwrap()["first"],
+            5
+        );
+        assert!(parse_variables(Some("[1]")).is_err());
+        assert!(parse_variables(Some("not json")).is_err());
+    }
+}
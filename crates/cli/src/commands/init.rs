@@ -0,0 +1,324 @@
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_auth::token_key;
+use atlassian_cli_config::{Config, CredentialStoreKind};
+use clap_complete::Shell;
+use url::Url;
+
+use super::auth::credential_store;
+
+// Interactive first-run setup: create a profile, pick where its token is
+// stored and install shell completions. Connection tests are run by the caller
+// with the same checks as `doctor`.
+
+const BIN_NAME: &str = "atlassian-cli";
+
+/// Ask whether to run the wizard when a command needs a profile and none exist.
+pub fn offer_first_run() -> Result<bool> {
+    println!("No profile configured yet.");
+    confirm("Set one up now?", true)
+}
+
+/// Walk through creating a profile. Returns its name, or `None` if the user
+/// backed out.
+pub fn wizard(config: &mut Config, config_path: Option<&Path>) -> Result<Option<String>> {
+    println!("Welcome to atlassian-cli! This sets up a profile for your Atlassian site.");
+    if !config.profiles.is_empty() {
+        let mut names: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+        names.sort();
+        println!("Existing profiles: {}", names.join(", "));
+        if !confirm("Add or update a profile?", true)? {
+            return Ok(None);
+        }
+    }
+
+    let default_name = if config.profiles.is_empty() {
+        "default"
+    } else {
+        ""
+    };
+    let name = ask_required("Profile name", default_name)?;
+    let existing = config.profiles.get(&name);
+    let site = ask_required(
+        "Atlassian site (e.g. acme or https://acme.atlassian.net)",
+        existing.and_then(|p| p.base_url.as_deref()).unwrap_or(""),
+    )?;
+    let base_url = site_url(&site)?;
+    let email = ask_required(
+        "Account email",
+        existing.and_then(|p| p.email.as_deref()).unwrap_or(""),
+    )?;
+    let workspace = ask(
+        "Bitbucket workspace (optional)",
+        existing.and_then(|p| p.workspace.as_deref()).unwrap_or(""),
+    )?;
+
+    println!();
+    println!("Where should the API token be stored?");
+    println!("  1) credentials file (~/.atlassian-cli/credentials, readable only by you)");
+    println!("  2) encrypted vault (unlocked with a passphrase or key file)");
+    println!("  3) system keyring (Keychain, Credential Manager or Secret Service)");
+    println!(
+        "  4) nowhere: I will set ATLASSIAN_CLI_TOKEN_{} myself",
+        env_name(&name)
+    );
+    let current = match config.credential_store {
+        CredentialStoreKind::File => "1",
+        CredentialStoreKind::EncryptedFile => "2",
+        CredentialStoreKind::Keyring => "3",
+    };
+    let storage = loop {
+        match ask("Choice", current)?.as_str() {
+            choice @ ("1" | "2" | "3" | "4") => break choice.to_string(),
+            other => println!("Please enter 1, 2, 3 or 4 (got '{other}')"),
+        }
+    };
+
+    let token = if storage == "4" {
+        None
+    } else {
+        println!(
+            "Create an API token at https://id.atlassian.com/manage-profile/security/api-tokens"
+        );
+        print!("API token: ");
+        io::stdout().flush().context("Failed to flush stdout")?;
+        let token = rpassword::read_password().context("Failed to read token")?;
+        let token = token.trim().to_string();
+        if token.is_empty() {
+            bail!("API token cannot be empty");
+        }
+        Some(token)
+    };
+
+    if storage == "2" {
+        config.credential_store = CredentialStoreKind::EncryptedFile;
+        let has_passphrase = std::env::var("ATLASSIAN_CLI_VAULT_PASSPHRASE")
+            .is_ok_and(|p| !p.is_empty())
+            || std::env::var_os("ATLASSIAN_CLI_VAULT_KEY_FILE").is_some();
+        if !has_passphrase {
+            let current = config
+                .vault_key_file
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default();
+            let key_file = ask_required("Key file that unlocks the vault", &current)?;
+            config.vault_key_file = Some(PathBuf::from(key_file));
+        }
+    } else if storage == "1" {
+        config.credential_store = CredentialStoreKind::File;
+    } else if storage == "3" {
+        config.credential_store = CredentialStoreKind::Keyring;
+    }
+
+    let profile = config.profiles.entry(name.clone()).or_default();
+    profile.base_url = Some(base_url.clone());
+    profile.email = Some(email);
+    profile.api_token = None;
+    profile.workspace = (!workspace.is_empty()).then_some(workspace);
+    let is_default = config.default_profile.as_deref() == Some(name.as_str());
+    if config.default_profile.is_none()
+        || (!is_default && confirm(&format!("Make '{name}' the default profile?"), false)?)
+    {
+        config.default_profile = Some(name.clone());
+    }
+
+    if let Some(token) = token {
+        credential_store(config)?
+            .set(&token_key(&name), &token)
+            .context("Failed to store token in credential store")?;
+    }
+    config
+        .save(config_path)
+        .context("Unable to persist configuration file")?;
+
+    tracing::info!(profile = %name, %base_url, "Profile created by setup wizard");
+    println!("✅ Saved profile '{name}' for {base_url}");
+    if storage == "4" {
+        println!(
+            "   Set ATLASSIAN_CLI_TOKEN_{} before running commands",
+            env_name(&name)
+        );
+    }
+    println!(
+        "   Bitbucket uses its own app password or token: set ATLASSIAN_CLI_BITBUCKET_TOKEN_{}",
+        env_name(&name)
+    );
+    Ok(Some(name))
+}
+
+/// Offer to write completions for the user's shell to where it picks them up.
+pub fn offer_completions(command: &mut clap::Command) -> Result<()> {
+    let Some(shell) = Shell::from_env() else {
+        println!("Shell completions: run `{BIN_NAME} completions <SHELL>` to print them");
+        return Ok(());
+    };
+    if !confirm(&format!("Install {shell} completions?"), true)? {
+        return Ok(());
+    }
+
+    let home = dirs::home_dir().context("Cannot find the home directory")?;
+    let Some(path) = completion_path(shell, &home) else {
+        println!(
+            "Add `{BIN_NAME} completions {shell} | Out-String | Invoke-Expression` (or your shell's equivalent) to your profile"
+        );
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    clap_complete::generate(shell, command, BIN_NAME, &mut file);
+
+    println!("✅ Installed completions to {}", path.display());
+    if shell == Shell::Zsh {
+        println!("   Make sure ~/.zfunc is on your fpath: add `fpath+=~/.zfunc` before `compinit` in ~/.zshrc");
+    }
+    Ok(())
+}
+
+/// Print completions for `shell` to stdout.
+pub fn print_completions(shell: Shell, command: &mut clap::Command) -> Result<()> {
+    // Generated into a buffer: clap_complete panics on write errors such as a
+    // closed pipe
+    let mut script = Vec::new();
+    clap_complete::generate(shell, command, BIN_NAME, &mut script);
+    io::stdout()
+        .write_all(&script)
+        .context("Failed to write completions")
+}
+
+/// Where each shell loads completions from without extra configuration.
+fn completion_path(shell: Shell, home: &Path) -> Option<PathBuf> {
+    match shell {
+        Shell::Bash => Some(
+            home.join(".local/share/bash-completion/completions")
+                .join(BIN_NAME),
+        ),
+        Shell::Zsh => Some(home.join(".zfunc").join(format!("_{BIN_NAME}"))),
+        Shell::Fish => Some(
+            home.join(".config/fish/completions")
+                .join(format!("{BIN_NAME}.fish")),
+        ),
+        _ => None,
+    }
+}
+
+/// Accept a bare site name (`acme`), a host or a full URL.
+fn site_url(site: &str) -> Result<String> {
+    let site = site.trim().trim_end_matches('/');
+    let url = if site.contains("://") {
+        site.to_string()
+    } else if site.contains('.') {
+        format!("https://{site}")
+    } else {
+        format!("https://{site}.atlassian.net")
+    };
+    let parsed = Url::parse(&url).with_context(|| format!("Invalid Atlassian site URL: {site}"))?;
+    if parsed.host_str().is_none() {
+        bail!("Invalid Atlassian site URL: {site}");
+    }
+    Ok(url)
+}
+
+/// Profile names as they appear in environment variable names.
+fn env_name(profile: &str) -> String {
+    profile.to_uppercase()
+}
+
+fn ask(label: &str, default: &str) -> Result<String> {
+    if default.is_empty() {
+        print!("{label}: ");
+    } else {
+        print!("{label} [{default}]: ");
+    }
+    io::stdout().flush().context("Failed to flush stdout")?;
+
+    let mut line = String::new();
+    let read = io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .context("Failed to read answer")?;
+    if read == 0 {
+        bail!("Setup cancelled");
+    }
+    let answer = line.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+fn ask_required(label: &str, default: &str) -> Result<String> {
+    loop {
+        let answer = ask(label, default)?;
+        if !answer.is_empty() {
+            return Ok(answer);
+        }
+        println!("A value is required");
+    }
+}
+
+pub fn confirm(label: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    loop {
+        let answer = ask(&format!("{label} ({hint})"), "")?;
+        match parse_yes_no(&answer, default) {
+            Some(yes) => return Ok(yes),
+            None => println!("Please answer y or n"),
+        }
+    }
+}
+
+fn parse_yes_no(answer: &str, default: bool) -> Option<bool> {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "" => Some(default),
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_url() {
+        assert_eq!(site_url("acme").unwrap(), "https://acme.atlassian.net");
+        assert_eq!(
+            site_url("acme.atlassian.net/").unwrap(),
+            "https://acme.atlassian.net"
+        );
+        assert_eq!(
+            site_url("https://jira.example.com").unwrap(),
+            "https://jira.example.com"
+        );
+        assert!(site_url("https://").is_err());
+    }
+
+    #[test]
+    fn test_parse_yes_no() {
+        assert_eq!(parse_yes_no("", true), Some(true));
+        assert_eq!(parse_yes_no("", false), Some(false));
+        assert_eq!(parse_yes_no(" Yes ", false), Some(true));
+        assert_eq!(parse_yes_no("n", true), Some(false));
+        assert_eq!(parse_yes_no("maybe", true), None);
+    }
+
+    #[test]
+    fn test_completion_path() {
+        let home = Path::new("/home/ana");
+        assert_eq!(
+            completion_path(Shell::Zsh, home),
+            Some(PathBuf::from("/home/ana/.zfunc/_atlassian-cli"))
+        );
+        assert_eq!(
+            completion_path(Shell::Fish, home),
+            Some(PathBuf::from(
+                "/home/ana/.config/fish/completions/atlassian-cli.fish"
+            ))
+        );
+        assert_eq!(completion_path(Shell::PowerShell, home), None);
+    }
+}
@@ -0,0 +1,270 @@
+use serde_json::{json, Value};
+
+// Markdown to Atlassian Document Format for comment bodies. Covers headings,
+// paragraphs, bullet and numbered lists, quotes, rules, fenced code, and
+// inline code, links, strong and emphasis.
+
+/// A plain-text body as a single-paragraph ADF document.
+pub fn text_doc(text: &str) -> Value {
+    json!({
+        "type": "doc",
+        "version": 1,
+        "content": [{
+            "type": "paragraph",
+            "content": [{ "type": "text", "text": text }]
+        }]
+    })
+}
+
+/// Convert a Markdown document to an ADF document.
+pub fn markdown_doc(markdown: &str) -> Value {
+    let lines: Vec<&str> = markdown.lines().collect();
+    json!({ "type": "doc", "version": 1, "content": blocks(&lines) })
+}
+
+fn blocks(lines: &[&str]) -> Vec<Value> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(language) = code_fence(trimmed) {
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && code_fence(lines[i].trim()).is_none() {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            let mut block = json!({
+                "type": "codeBlock",
+                "content": [{ "type": "text", "text": code.join("\n") }]
+            });
+            if !language.is_empty() {
+                block["attrs"] = json!({ "language": language });
+            }
+            out.push(block);
+        } else if let Some(level) = heading_level(trimmed) {
+            let text = trimmed[level..].trim().trim_end_matches('#').trim_end();
+            out.push(json!({
+                "type": "heading",
+                "attrs": { "level": level },
+                "content": inline(text)
+            }));
+            i += 1;
+        } else if is_rule(trimmed) {
+            out.push(json!({ "type": "rule" }));
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let rest = &lines[i].trim_start()[1..];
+                quoted.push(rest.strip_prefix(' ').unwrap_or(rest));
+                i += 1;
+            }
+            out.push(json!({ "type": "blockquote", "content": blocks(&quoted) }));
+        } else if list_marker(trimmed).is_some() {
+            let ordered = list_marker(trimmed).is_some_and(|(ordered, _)| ordered);
+            let mut items = Vec::new();
+            while let Some((_, content)) = lines.get(i).and_then(|l| list_marker(l.trim())) {
+                items.push(json!({
+                    "type": "listItem",
+                    "content": [{ "type": "paragraph", "content": inline(content.trim()) }]
+                }));
+                i += 1;
+            }
+            let kind = if ordered { "orderedList" } else { "bulletList" };
+            out.push(json!({ "type": kind, "content": items }));
+        } else {
+            let mut paragraph = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines[i]) {
+                paragraph.push(lines[i].trim());
+                i += 1;
+            }
+            out.push(json!({ "type": "paragraph", "content": inline(&paragraph.join(" ")) }));
+        }
+    }
+    out
+}
+
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim();
+    code_fence(trimmed).is_some()
+        || heading_level(trimmed).is_some()
+        || is_rule(trimmed)
+        || trimmed.starts_with('>')
+        || list_marker(trimmed).is_some()
+}
+
+fn code_fence(line: &str) -> Option<&str> {
+    ["```", "~~~"]
+        .into_iter()
+        .find_map(|fence| line.strip_prefix(fence))
+        .map(str::trim)
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|&marker| chars.iter().all(|&c| c == marker))
+}
+
+/// `(ordered, content)` for a list item line.
+fn list_marker(line: &str) -> Option<(bool, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(content) = line.strip_prefix(bullet) {
+            return (!is_rule(line)).then_some((false, content));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(content) = line[digits..].strip_prefix(". ") {
+            return Some((true, content));
+        }
+    }
+    None
+}
+
+/// Inline text nodes with code, link, strong and em marks.
+fn inline(text: &str) -> Vec<Value> {
+    let mut out: Vec<Value> = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let flush = |plain: &mut String, out: &mut Vec<Value>| {
+        if !plain.is_empty() {
+            out.push(json!({ "type": "text", "text": std::mem::take(plain) }));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                flush(&mut plain, &mut out);
+                out.push(json!({
+                    "type": "text",
+                    "text": &rest[1..1 + end],
+                    "marks": [{ "type": "code" }]
+                }));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some((label, url, len)) = link_at(rest) {
+                flush(&mut plain, &mut out);
+                out.extend(with_mark(
+                    inline(label),
+                    json!({ "type": "link", "attrs": { "href": url } }),
+                ));
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        let prev = text[..text.len() - rest.len()].chars().next_back();
+        // Underscores inside words (snake_case) are literal
+        let intraword = c == '_' && prev.is_some_and(char::is_alphanumeric);
+        if (c == '*' || c == '_') && !intraword {
+            let double = rest.len() > 1 && rest[1..].starts_with(c);
+            let delimiter = if double { &rest[..2] } else { &rest[..1] };
+            let inner = &rest[delimiter.len()..];
+            if let Some(end) = inner.find(delimiter).filter(|&end| end > 0) {
+                flush(&mut plain, &mut out);
+                let mark = if double { "strong" } else { "em" };
+                out.extend(with_mark(inline(&inner[..end]), json!({ "type": mark })));
+                rest = &inner[end + delimiter.len()..];
+                continue;
+            }
+        }
+
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut plain, &mut out);
+    out
+}
+
+fn with_mark(nodes: Vec<Value>, mark: Value) -> Vec<Value> {
+    nodes
+        .into_iter()
+        .map(|mut node| {
+            match node["marks"].as_array_mut() {
+                Some(marks) => marks.push(mark.clone()),
+                None => node["marks"] = json!([mark.clone()]),
+            }
+            node
+        })
+        .collect()
+}
+
+/// `[label](url)` at the start of `text`: the label, URL and total length.
+fn link_at(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.strip_prefix('[')?.find(']')? + 1;
+    let after = text[label_end + 1..].strip_prefix('(')?;
+    let url_end = after.find(')')?;
+    Some((
+        &text[1..label_end],
+        after[..url_end].trim(),
+        label_end + 2 + url_end + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_doc() {
+        let doc = markdown_doc(
+            "## Migration\n\nWe move **DEV** next week, see [plan](https://x/p).\n\n- freeze `main`\n- snake_case stays\n\n```sh\nmake migrate\n```",
+        );
+        assert_eq!(
+            doc,
+            json!({
+                "type": "doc",
+                "version": 1,
+                "content": [
+                    { "type": "heading", "attrs": { "level": 2 }, "content": [{ "type": "text", "text": "Migration" }] },
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "We move " },
+                        { "type": "text", "text": "DEV", "marks": [{ "type": "strong" }] },
+                        { "type": "text", "text": " next week, see " },
+                        { "type": "text", "text": "plan", "marks": [{ "type": "link", "attrs": { "href": "https://x/p" } }] },
+                        { "type": "text", "text": "." }
+                    ] },
+                    { "type": "bulletList", "content": [
+                        { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                            { "type": "text", "text": "freeze " },
+                            { "type": "text", "text": "main", "marks": [{ "type": "code" }] }
+                        ] }] },
+                        { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                            { "type": "text", "text": "snake_case stays" }
+                        ] }] }
+                    ] },
+                    { "type": "codeBlock", "attrs": { "language": "sh" }, "content": [{ "type": "text", "text": "make migrate" }] }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_nested_marks() {
+        assert_eq!(
+            inline("**bold _and em_**"),
+            vec![
+                json!({ "type": "text", "text": "bold ", "marks": [{ "type": "strong" }] }),
+                json!({ "type": "text", "text": "and em", "marks": [{ "type": "em" }, { "type": "strong" }] }),
+            ]
+        );
+    }
+}
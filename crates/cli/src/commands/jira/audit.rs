@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use serde_json::Value;
 
+use super::export;
 use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 
 // List audit records
 pub async fn list_audit_records(
@@ -10,11 +13,13 @@ pub async fn list_audit_records(
     from: Option<&str>,
     to: Option<&str>,
     filter: Option<&str>,
-    limit: Option<usize>,
+    page: &PaginationArgs,
 ) -> Result<()> {
     #[derive(Deserialize)]
     struct AuditResponse {
         records: Vec<AuditRecord>,
+        #[serde(default)]
+        total: usize,
     }
 
     #[derive(Deserialize)]
@@ -50,21 +55,24 @@ pub async fn list_audit_records(
         query_params.push(format!("filter={}", flt));
     }
 
-    if let Some(l) = limit {
-        query_params.push(format!("limit={}", l));
-    }
-
-    let query_string = if query_params.is_empty() {
-        String::new()
-    } else {
-        format!("?{}", query_params.join("&"))
-    };
-
-    let response: AuditResponse = ctx
-        .client
-        .get(&format!("/rest/api/3/auditing/record{}", query_string))
-        .await
-        .context("Failed to list audit records")?;
+    let query_params = &query_params;
+    let records = page
+        .collect(1000, 1000, Paging::Offset, |request| {
+            let mut params = query_params.clone();
+            params.push(format!("offset={}", request.start));
+            params.push(format!("limit={}", request.size));
+            let path = format!("/rest/api/3/auditing/record?{}", params.join("&"));
+            async move {
+                let response: AuditResponse = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .context("Failed to list audit records")?;
+                let has_more = request.start + response.records.len() < response.total;
+                Ok(Page::offset(response.records, has_more))
+            }
+        })
+        .await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -77,8 +85,7 @@ pub async fn list_audit_records(
         created: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .records
+    let rows: Vec<Row<'_>> = records
         .iter()
         .map(|r| Row {
             id: r.id,
@@ -100,14 +107,9 @@ pub async fn export_audit_records(
     from: Option<&str>,
     to: Option<&str>,
     filter: Option<&str>,
-    output: &std::path::PathBuf,
+    output: &std::path::Path,
     format: ExportFormat,
 ) -> Result<()> {
-    #[derive(Deserialize, Serialize)]
-    struct AuditResponse {
-        records: Vec<serde_json::Value>,
-    }
-
     let mut query_params = Vec::new();
 
     if let Some(f) = from {
@@ -128,77 +130,60 @@ pub async fn export_audit_records(
         format!("?{}", query_params.join("&"))
     };
 
-    let response: AuditResponse = ctx
+    let records = ctx
         .client
-        .get(&format!("/rest/api/3/auditing/record{}", query_string))
+        .request_array_stream::<Value, ()>(
+            Method::GET,
+            &format!("/rest/api/3/auditing/record{}", query_string),
+            None,
+            Some("records"),
+        )
         .await
         .context("Failed to export audit records")?;
 
-    match format {
-        ExportFormat::Json => {
-            let json_str = serde_json::to_string_pretty(&response.records)?;
-            fs::write(output, json_str)?;
-        }
+    let count = match format {
+        ExportFormat::Json => export::write_json_array(records, output).await?,
         ExportFormat::Csv => {
-            let mut wtr = csv::Writer::from_path(output)?;
-
-            // Write header
-            wtr.write_record([
-                "id",
-                "summary",
-                "category",
-                "object_type",
-                "object_name",
-                "author",
-                "created",
-            ])?;
-
-            // Write rows
-            for record in &response.records {
-                let id = record
-                    .get("id")
-                    .and_then(|v| v.as_i64())
-                    .unwrap_or(0)
-                    .to_string();
-                let summary = record.get("summary").and_then(|v| v.as_str()).unwrap_or("");
-                let category = record
-                    .get("category")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let object_type = record
-                    .get("objectItem")
-                    .and_then(|o| o.get("typeName"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let object_name = record
-                    .get("objectItem")
-                    .and_then(|o| o.get("name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let author = record
-                    .get("authorKey")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let created = record.get("created").and_then(|v| v.as_str()).unwrap_or("");
-
-                wtr.write_record([
-                    id.as_str(),
-                    summary,
-                    category,
-                    object_type,
-                    object_name,
-                    author,
-                    created,
-                ])?;
-            }
-
-            wtr.flush()?;
+            export::write_csv(
+                records,
+                output,
+                &[
+                    "id",
+                    "summary",
+                    "category",
+                    "object_type",
+                    "object_name",
+                    "author",
+                    "created",
+                ],
+                |record| {
+                    vec![
+                        record
+                            .get("id")
+                            .and_then(|v| v.as_i64())
+                            .unwrap_or(0)
+                            .to_string(),
+                        export::str_at(record, "/summary"),
+                        export::str_at(record, "/category"),
+                        export::str_at(record, "/objectItem/typeName"),
+                        export::str_at(record, "/objectItem/name"),
+                        export::str_at(record, "/authorKey"),
+                        export::str_at(record, "/created"),
+                    ]
+                },
+            )
+            .await?
         }
+    };
+
+    if count == 0 {
+        println!("No audit records matched");
+        return Ok(());
     }
 
     println!(
         "✅ Exported {} audit records to {}",
-        response.records.len(),
+        count,
         output.display()
     );
     Ok(())
@@ -62,7 +62,7 @@ pub async fn get_rule(ctx: &JiraContext<'_>, rule_id: i64) -> Result<()> {
         .await
         .with_context(|| format!("Failed to get automation rule {rule_id}"))?;
 
-    println!("{}", serde_json::to_string_pretty(&rule)?);
+    ctx.renderer.print_json(&rule)?;
     Ok(())
 }
 
@@ -0,0 +1,644 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::OutputFormat;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::JiraContext;
+
+// Agile board configuration: columns, status mapping, estimation, quick filters
+// and swimlanes. The public agile API only reads quick filters, so changes go
+// through the board configuration API (/rest/greenhopper/1.0) used by the
+// board settings page.
+
+#[derive(Deserialize)]
+struct RawBoardConfig {
+    id: u64,
+    name: String,
+    #[serde(rename = "type", default)]
+    board_type: Option<String>,
+    #[serde(default)]
+    filter: Option<IdRef>,
+    #[serde(rename = "columnConfig", default)]
+    column_config: Option<ColumnConfig>,
+    #[serde(default)]
+    estimation: Option<Estimation>,
+    #[serde(default)]
+    ranking: Option<Ranking>,
+}
+
+#[derive(Deserialize)]
+struct IdRef {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ColumnConfig {
+    #[serde(default)]
+    columns: Vec<RawColumn>,
+    #[serde(rename = "constraintType", default)]
+    constraint_type: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawColumn {
+    name: String,
+    #[serde(default)]
+    statuses: Vec<IdRef>,
+    #[serde(default)]
+    min: Option<u32>,
+    #[serde(default)]
+    max: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct Estimation {
+    #[serde(rename = "type")]
+    estimation_type: String,
+    #[serde(default)]
+    field: Option<EstimationField>,
+}
+
+#[derive(Deserialize)]
+struct EstimationField {
+    #[serde(rename = "fieldId")]
+    field_id: String,
+    #[serde(rename = "displayName", default)]
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Ranking {
+    #[serde(rename = "rankCustomFieldId")]
+    rank_custom_field_id: u64,
+}
+
+#[derive(Deserialize)]
+struct Status {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct QuickFilterPage {
+    #[serde(default)]
+    values: Vec<RawQuickFilter>,
+    #[serde(rename = "isLast", default)]
+    is_last: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct RawQuickFilter {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    jql: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct BoardConfig {
+    id: u64,
+    name: String,
+    board_type: String,
+    filter_id: Option<String>,
+    column_constraint: Option<String>,
+    columns: Vec<BoardColumn>,
+    estimation: Option<String>,
+    rank_field: Option<String>,
+    quick_filters: Vec<QuickFilter>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct BoardColumn {
+    column: String,
+    statuses: Vec<String>,
+    min: Option<u32>,
+    max: Option<u32>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct QuickFilter {
+    name: String,
+    jql: String,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct ColumnRow {
+    column: String,
+    statuses: String,
+    min: String,
+    max: String,
+}
+
+// Show a board's configuration
+pub async fn board_config(ctx: &JiraContext<'_>, board_id: u64) -> Result<()> {
+    let raw: RawBoardConfig = ctx
+        .client
+        .get(&format!("/rest/agile/1.0/board/{board_id}/configuration"))
+        .await
+        .with_context(|| format!("Failed to fetch configuration for board {board_id}"))?;
+
+    let statuses: Vec<Status> = ctx
+        .client
+        .get("/rest/api/3/status")
+        .await
+        .context("Failed to list statuses")?;
+    let status_names: HashMap<String, String> =
+        statuses.into_iter().map(|s| (s.id, s.name)).collect();
+
+    let quick_filters = fetch_quick_filters(ctx, board_id).await?;
+    let config = build_config(raw, &status_names, quick_filters);
+    tracing::info!(
+        board_id,
+        columns = config.columns.len(),
+        "Fetched board configuration"
+    );
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&config);
+    }
+
+    println!(
+        "Board {} - {} ({})",
+        config.id, config.name, config.board_type
+    );
+    if let Some(filter_id) = &config.filter_id {
+        println!("  Filter: {filter_id}");
+    }
+    println!(
+        "  Estimation: {}",
+        config.estimation.as_deref().unwrap_or("none")
+    );
+    if let Some(rank_field) = &config.rank_field {
+        println!("  Rank field: {rank_field}");
+    }
+    if let Some(constraint) = &config.column_constraint {
+        println!("  Column constraint: {constraint}");
+    }
+
+    println!("\nColumns:");
+    let rows: Vec<ColumnRow> = config
+        .columns
+        .iter()
+        .map(|c| ColumnRow {
+            column: c.column.clone(),
+            statuses: c.statuses.join(", "),
+            min: c.min.map(|v| v.to_string()).unwrap_or_default(),
+            max: c.max.map(|v| v.to_string()).unwrap_or_default(),
+        })
+        .collect();
+    ctx.renderer.render(&rows)?;
+
+    println!("\nQuick filters:");
+    if config.quick_filters.is_empty() {
+        println!("  None");
+        return Ok(());
+    }
+    ctx.renderer.render(&config.quick_filters)
+}
+
+#[derive(Serialize)]
+struct QuickFilterRow {
+    id: u64,
+    name: String,
+    jql: String,
+    description: String,
+}
+
+/// How issues on a board are split into horizontal lanes.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwimlaneStrategy {
+    /// One lane per JQL query (the swimlanes managed by these commands)
+    Custom,
+    Assignee,
+    Epic,
+    /// One lane per parent issue (stories with their sub-tasks)
+    Story,
+    Project,
+    None,
+}
+
+impl SwimlaneStrategy {
+    fn as_str(self) -> &'static str {
+        match self {
+            SwimlaneStrategy::Custom => "custom",
+            SwimlaneStrategy::Assignee => "assignee",
+            SwimlaneStrategy::Epic => "epic",
+            SwimlaneStrategy::Story => "parentChild",
+            SwimlaneStrategy::Project => "project",
+            SwimlaneStrategy::None => "none",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EditModel {
+    #[serde(rename = "swimlanesConfig")]
+    swimlanes_config: SwimlanesConfig,
+}
+
+#[derive(Deserialize)]
+struct SwimlanesConfig {
+    #[serde(rename = "swimlaneStrategy", default)]
+    swimlane_strategy: Option<String>,
+    #[serde(default)]
+    swimlanes: Vec<RawSwimlane>,
+}
+
+#[derive(Deserialize)]
+struct RawSwimlane {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "isDefault", default)]
+    is_default: bool,
+}
+
+#[derive(Serialize)]
+struct SwimlaneRow {
+    id: u64,
+    name: String,
+    jql: String,
+    description: String,
+    default: bool,
+}
+
+#[derive(Deserialize)]
+struct Created {
+    id: u64,
+}
+
+// List a board's quick filters
+pub async fn list_quick_filters(ctx: &JiraContext<'_>, board_id: u64) -> Result<()> {
+    let rows: Vec<QuickFilterRow> = fetch_quick_filters(ctx, board_id)
+        .await?
+        .into_iter()
+        .map(|f| QuickFilterRow {
+            id: f.id,
+            name: f.name,
+            jql: f.jql,
+            description: f.description.unwrap_or_default(),
+        })
+        .collect();
+
+    tracing::info!(board_id, count = rows.len(), "Listed quick filters");
+    ctx.renderer.render(&rows)
+}
+
+// Add a quick filter to a board
+pub async fn create_quick_filter(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    name: &str,
+    jql: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let existing = fetch_quick_filters(ctx, board_id).await?;
+    if existing.iter().any(|f| f.name == name) {
+        bail!("Board {board_id} already has a quick filter named '{name}'");
+    }
+
+    let payload = json!({
+        "name": name,
+        "query": jql,
+        "description": description.unwrap_or_default(),
+    });
+    let created: Created = ctx
+        .client
+        .post(
+            &format!("/rest/greenhopper/1.0/quickfilters/{board_id}"),
+            &payload,
+        )
+        .await
+        .with_context(|| format!("Failed to create quick filter on board {board_id}"))?;
+
+    tracing::info!(board_id, filter_id = created.id, "Quick filter created");
+    println!(
+        "✅ Created quick filter '{name}' ({}) on board {board_id}",
+        created.id
+    );
+    Ok(())
+}
+
+// Remove a quick filter from a board
+pub async fn delete_quick_filter(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    filter_id: u64,
+    force: bool,
+) -> Result<()> {
+    if !force {
+        println!(
+            "⚠️  This will delete quick filter {filter_id} from board {board_id}. Use --force to confirm."
+        );
+        return Ok(());
+    }
+
+    let _: Value = ctx
+        .client
+        .delete(&format!(
+            "/rest/greenhopper/1.0/quickfilters/{board_id}/{filter_id}"
+        ))
+        .await
+        .with_context(|| {
+            format!("Failed to delete quick filter {filter_id} on board {board_id}")
+        })?;
+
+    tracing::info!(board_id, filter_id, "Quick filter deleted");
+    println!("✅ Deleted quick filter {filter_id} from board {board_id}");
+    Ok(())
+}
+
+// List a board's custom swimlanes and the active swimlane strategy
+pub async fn list_swimlanes(ctx: &JiraContext<'_>, board_id: u64) -> Result<()> {
+    let config = fetch_swimlanes(ctx, board_id).await?;
+    let rows: Vec<SwimlaneRow> = config
+        .swimlanes
+        .into_iter()
+        .map(|s| SwimlaneRow {
+            id: s.id,
+            name: s.name,
+            jql: s.query.unwrap_or_default(),
+            description: s.description.unwrap_or_default(),
+            default: s.is_default,
+        })
+        .collect();
+
+    tracing::info!(board_id, count = rows.len(), "Listed swimlanes");
+    if ctx.renderer.format() == OutputFormat::Table {
+        println!(
+            "Swimlanes by: {}",
+            config.swimlane_strategy.as_deref().unwrap_or("none")
+        );
+    }
+    ctx.renderer.render(&rows)
+}
+
+// Add a JQL swimlane to a board
+pub async fn create_swimlane(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    name: &str,
+    jql: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let config = fetch_swimlanes(ctx, board_id).await?;
+    if config.swimlanes.iter().any(|s| s.name == name) {
+        bail!("Board {board_id} already has a swimlane named '{name}'");
+    }
+
+    let payload = json!({
+        "name": name,
+        "query": jql,
+        "description": description.unwrap_or_default(),
+    });
+    let created: Created = ctx
+        .client
+        .post(
+            &format!("/rest/greenhopper/1.0/swimlanes/{board_id}"),
+            &payload,
+        )
+        .await
+        .with_context(|| format!("Failed to create swimlane on board {board_id}"))?;
+
+    tracing::info!(board_id, swimlane_id = created.id, "Swimlane created");
+    println!(
+        "✅ Created swimlane '{name}' ({}) on board {board_id}",
+        created.id
+    );
+    if config.swimlane_strategy.as_deref() != Some("custom") {
+        println!(
+            "   The board is not using custom swimlanes; run `jira board swimlane strategy {board_id} custom` to show them"
+        );
+    }
+    Ok(())
+}
+
+// Remove a JQL swimlane from a board
+pub async fn delete_swimlane(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    swimlane_id: u64,
+    force: bool,
+) -> Result<()> {
+    if !force {
+        println!(
+            "⚠️  This will delete swimlane {swimlane_id} from board {board_id}. Use --force to confirm."
+        );
+        return Ok(());
+    }
+
+    let _: Value = ctx
+        .client
+        .delete(&format!(
+            "/rest/greenhopper/1.0/swimlanes/{board_id}/{swimlane_id}"
+        ))
+        .await
+        .with_context(|| format!("Failed to delete swimlane {swimlane_id} on board {board_id}"))?;
+
+    tracing::info!(board_id, swimlane_id, "Swimlane deleted");
+    println!("✅ Deleted swimlane {swimlane_id} from board {board_id}");
+    Ok(())
+}
+
+// Choose how a board groups issues into swimlanes
+pub async fn set_swimlane_strategy(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    strategy: SwimlaneStrategy,
+) -> Result<()> {
+    let payload = json!({
+        "id": board_id,
+        "swimlaneStrategyId": strategy.as_str(),
+    });
+    let _: Value = ctx
+        .client
+        .put("/rest/greenhopper/1.0/swimlaneStrategy", &payload)
+        .await
+        .with_context(|| format!("Failed to set swimlane strategy of board {board_id}"))?;
+
+    tracing::info!(
+        board_id,
+        strategy = strategy.as_str(),
+        "Swimlane strategy set"
+    );
+    println!(
+        "✅ Board {board_id} now groups swimlanes by {}",
+        strategy.as_str()
+    );
+    Ok(())
+}
+
+async fn fetch_swimlanes(ctx: &JiraContext<'_>, board_id: u64) -> Result<SwimlanesConfig> {
+    let model: EditModel = ctx
+        .client
+        .get(&format!(
+            "/rest/greenhopper/1.0/rapidviewconfig/editmodel.json?rapidViewId={board_id}"
+        ))
+        .await
+        .with_context(|| format!("Failed to fetch swimlanes for board {board_id}"))?;
+    Ok(model.swimlanes_config)
+}
+
+/// Field holding the board's estimates (e.g. story points), when it estimates by field.
+pub(super) async fn estimation_field(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+) -> Result<Option<String>> {
+    let raw: RawBoardConfig = ctx
+        .client
+        .get(&format!("/rest/agile/1.0/board/{board_id}/configuration"))
+        .await
+        .with_context(|| format!("Failed to fetch configuration for board {board_id}"))?;
+    Ok(raw.estimation.and_then(|e| e.field).map(|f| f.field_id))
+}
+
+async fn fetch_quick_filters(ctx: &JiraContext<'_>, board_id: u64) -> Result<Vec<RawQuickFilter>> {
+    let mut filters = Vec::new();
+    let mut start_at = 0;
+
+    loop {
+        let page: QuickFilterPage = ctx
+            .client
+            .get(&format!(
+                "/rest/agile/1.0/board/{board_id}/quickfilter?startAt={start_at}&maxResults=50"
+            ))
+            .await
+            .with_context(|| format!("Failed to list quick filters for board {board_id}"))?;
+        let count = page.values.len();
+        filters.extend(page.values);
+
+        if page.is_last.unwrap_or(true) || count == 0 {
+            break;
+        }
+        start_at += count;
+    }
+
+    Ok(filters)
+}
+
+/// Resolve status ids to names and flatten the agile API payload. Statuses the
+/// caller cannot see are kept as `#<id>` so the mapping stays comparable.
+fn build_config(
+    raw: RawBoardConfig,
+    status_names: &HashMap<String, String>,
+    quick_filters: Vec<RawQuickFilter>,
+) -> BoardConfig {
+    let (columns, column_constraint) = match raw.column_config {
+        Some(config) => (config.columns, config.constraint_type),
+        None => (Vec::new(), None),
+    };
+
+    BoardConfig {
+        id: raw.id,
+        name: raw.name,
+        board_type: raw.board_type.unwrap_or_default(),
+        filter_id: raw.filter.map(|f| f.id),
+        column_constraint,
+        columns: columns
+            .into_iter()
+            .map(|c| BoardColumn {
+                column: c.name,
+                statuses: c
+                    .statuses
+                    .iter()
+                    .map(|s| {
+                        status_names
+                            .get(&s.id)
+                            .cloned()
+                            .unwrap_or_else(|| format!("#{}", s.id))
+                    })
+                    .collect(),
+                min: c.min,
+                max: c.max,
+            })
+            .collect(),
+        estimation: raw.estimation.map(|e| match e.field {
+            Some(field) => format!(
+                "{} ({})",
+                field
+                    .display_name
+                    .unwrap_or_else(|| e.estimation_type.clone()),
+                field.field_id
+            ),
+            None => e.estimation_type,
+        }),
+        rank_field: raw
+            .ranking
+            .map(|r| format!("customfield_{}", r.rank_custom_field_id)),
+        quick_filters: quick_filters
+            .into_iter()
+            .map(|f| QuickFilter {
+                name: f.name,
+                jql: f.jql,
+                description: f.description.unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_config() {
+        let raw: RawBoardConfig = serde_json::from_value(json!({
+            "id": 84,
+            "name": "DEV board",
+            "type": "scrum",
+            "filter": { "id": "1001", "self": "https://example.atlassian.net/rest/api/3/filter/1001" },
+            "columnConfig": {
+                "columns": [
+                    { "name": "To Do", "statuses": [{ "id": "10000" }] },
+                    { "name": "In Progress", "statuses": [{ "id": "3" }, { "id": "42" }], "max": 5 }
+                ],
+                "constraintType": "issueCount"
+            },
+            "estimation": {
+                "type": "field",
+                "field": { "fieldId": "customfield_10016", "displayName": "Story Points" }
+            },
+            "ranking": { "rankCustomFieldId": 10019 }
+        }))
+        .unwrap();
+        let names = HashMap::from([
+            ("10000".to_string(), "To Do".to_string()),
+            ("3".to_string(), "In Progress".to_string()),
+        ]);
+        let filters = vec![RawQuickFilter {
+            id: 1,
+            name: "Mine".to_string(),
+            jql: "assignee = currentUser()".to_string(),
+            description: None,
+        }];
+
+        let config = build_config(raw, &names, filters);
+        assert_eq!(config.filter_id.as_deref(), Some("1001"));
+        assert_eq!(config.column_constraint.as_deref(), Some("issueCount"));
+        assert_eq!(
+            config.columns[1],
+            BoardColumn {
+                column: "In Progress".to_string(),
+                statuses: vec!["In Progress".to_string(), "#42".to_string()],
+                min: None,
+                max: Some(5),
+            }
+        );
+        assert_eq!(
+            config.estimation.as_deref(),
+            Some("Story Points (customfield_10016)")
+        );
+        assert_eq!(config.rank_field.as_deref(), Some("customfield_10019"));
+        assert_eq!(config.quick_filters[0].description, "");
+    }
+}
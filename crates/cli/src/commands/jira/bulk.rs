@@ -1,11 +1,21 @@
 use anyhow::{Context, Result};
-use atlassian_cli_bulk::BulkExecutor;
+use atlassian_cli_bulk::{BulkExecutor, PlannedOperation};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use reqwest::Method;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::adf;
+use super::export;
+use super::issues::{search_all, set_archived};
 use super::utils::JiraContext;
+use crate::commands::cancel;
+use crate::commands::dry_run::render_plan;
+use crate::query::JqlBuilder;
 
 // Bulk transition issues
 pub async fn bulk_transition(
@@ -23,38 +33,39 @@ pub async fn bulk_transition(
         return Ok(());
     }
 
-    println!("Found {} issues to transition", issue_keys.len());
-
-    if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for key in &issue_keys {
-            println!("  Would transition: {}", key);
-        }
-        return Ok(());
+    if !dry_run {
+        println!("Found {} issues to transition", issue_keys.len());
     }
 
-    // Get transition ID
+    // Get transition ID (also checks the transition exists before a dry run)
     let transition_id = get_transition_id(ctx, &issue_keys[0], transition).await?;
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
     let client = ctx.client.clone();
 
-    executor
-        .run(issue_keys, move |key| {
-            let client = client.clone();
-            let transition_id = transition_id.clone();
-            async move {
-                let payload = json!({ "transition": { "id": transition_id } });
-                let _: Value = client
-                    .post(&format!("/rest/api/3/issue/{key}/transitions"), &payload)
-                    .await
-                    .with_context(|| format!("Failed to transition issue {key}"))?;
-                tracing::info!(%key, "Transitioned successfully");
-                Ok(())
-            }
-        })
+    let operations = executor
+        .run_planned(
+            issue_keys,
+            |key| PlannedOperation::new(key, "transition", transition),
+            move |key| {
+                let client = client.clone();
+                let transition_id = transition_id.clone();
+                async move {
+                    let payload = json!({ "transition": { "id": transition_id } });
+                    let _: Value = client
+                        .post(&format!("/rest/api/3/issue/{key}/transitions"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to transition issue {key}"))?;
+                    tracing::info!(%key, "Transitioned successfully");
+                    Ok(())
+                }
+            },
+        )
         .await?;
 
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
     println!("✅ Bulk transition completed");
     Ok(())
 }
@@ -74,36 +85,37 @@ pub async fn bulk_assign(
         return Ok(());
     }
 
-    println!("Found {} issues to assign", issue_keys.len());
-
-    if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for key in &issue_keys {
-            println!("  Would assign {} to {}", key, assignee);
-        }
-        return Ok(());
+    if !dry_run {
+        println!("Found {} issues to assign", issue_keys.len());
     }
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
     let client = ctx.client.clone();
-    let assignee = assignee.to_string();
-
-    executor
-        .run(issue_keys, move |key| {
-            let client = client.clone();
-            let assignee = assignee.clone();
-            async move {
-                let payload = json!({ "accountId": assignee });
-                let _: Value = client
-                    .put(&format!("/rest/api/3/issue/{key}/assignee"), &payload)
-                    .await
-                    .with_context(|| format!("Failed to assign issue {key}"))?;
-                tracing::info!(%key, %assignee, "Assigned successfully");
-                Ok(())
-            }
-        })
+    let account_id = assignee.to_string();
+
+    let operations = executor
+        .run_planned(
+            issue_keys,
+            |key| PlannedOperation::new(key, "assign", assignee),
+            move |key| {
+                let client = client.clone();
+                let assignee = account_id.clone();
+                async move {
+                    let payload = json!({ "accountId": assignee });
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/issue/{key}/assignee"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to assign issue {key}"))?;
+                    tracing::info!(%key, %assignee, "Assigned successfully");
+                    Ok(())
+                }
+            },
+        )
         .await?;
 
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
     println!("✅ Bulk assign completed");
     Ok(())
 }
@@ -124,71 +136,382 @@ pub async fn bulk_label(
         return Ok(());
     }
 
-    println!("Found {} issues to label", issue_keys.len());
+    if !dry_run {
+        println!("Found {} issues to label", issue_keys.len());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let verb = match action {
+        LabelAction::Add => "add labels",
+        LabelAction::Remove => "remove labels",
+        LabelAction::Set => "set labels",
+    };
+    let target = labels.join(", ");
+
+    let operations = executor
+        .run_planned(
+            issue_keys,
+            |key| PlannedOperation::new(key, verb, target.as_str()),
+            move |key| {
+                let client = client.clone();
+                let labels = labels.clone();
+                let action = action.clone();
+                async move {
+                    // Get current labels
+                    let issue: IssueWithLabels = client
+                        .get(&format!("/rest/api/3/issue/{key}?fields=labels"))
+                        .await
+                        .with_context(|| format!("Failed to get issue {key}"))?;
+
+                    let new_labels = match action {
+                        LabelAction::Add => {
+                            let mut current = issue.fields.labels;
+                            for label in labels {
+                                if !current.contains(&label) {
+                                    current.push(label);
+                                }
+                            }
+                            current
+                        }
+                        LabelAction::Remove => issue
+                            .fields
+                            .labels
+                            .into_iter()
+                            .filter(|l| !labels.contains(l))
+                            .collect(),
+                        LabelAction::Set => labels,
+                    };
+
+                    let payload = json!({ "fields": { "labels": new_labels } });
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/issue/{key}"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to update labels for {key}"))?;
+
+                    tracing::info!(%key, "Labels updated successfully");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
 
     if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for key in &issue_keys {
-            println!("  Would {:?} labels {:?} on {}", action, labels, key);
-        }
+        return render_plan(ctx.renderer, &operations);
+    }
+    println!("✅ Bulk label operation completed");
+    Ok(())
+}
+
+// Bulk comment on issues
+pub async fn bulk_comment(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    body: &str,
+    markdown: bool,
+    notify: bool,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let issue_keys = search_issue_keys(ctx, jql).await?;
+
+    if issue_keys.is_empty() {
+        println!("No issues matched the JQL query");
         return Ok(());
     }
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
+    if !dry_run {
+        println!("Found {} issues to comment on", issue_keys.len());
+    }
+
+    let document = if markdown {
+        adf::markdown_doc(body)
+    } else {
+        adf::text_doc(body)
+    };
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
     let client = ctx.client.clone();
+    let target = comment_preview(body);
+
+    let operations = executor
+        .run_planned(
+            issue_keys,
+            |key| PlannedOperation::new(key, "comment", target.as_str()),
+            move |key| {
+                let client = client.clone();
+                let document = document.clone();
+                async move {
+                    // The comment endpoint has no notifyUsers flag; adding the
+                    // comment through an issue edit does
+                    if notify {
+                        let _: Value = client
+                            .post(
+                                &format!("/rest/api/3/issue/{key}/comment"),
+                                &json!({ "body": document }),
+                            )
+                            .await
+                            .with_context(|| format!("Failed to comment on {key}"))?;
+                    } else {
+                        let payload = json!({
+                            "update": { "comment": [{ "add": { "body": document } }] }
+                        });
+                        let _: Value = client
+                            .put(
+                                &format!("/rest/api/3/issue/{key}?notifyUsers=false"),
+                                &payload,
+                            )
+                            .await
+                            .with_context(|| format!("Failed to comment on {key}"))?;
+                    }
+
+                    tracing::info!(%key, notify, "Comment added");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
+
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
+    println!("✅ Bulk comment completed");
+    Ok(())
+}
 
-    executor
-        .run(issue_keys, move |key| {
+/// First line of the comment, shortened for the dry-run plan.
+fn comment_preview(body: &str) -> String {
+    let line = body
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or("")
+        .trim();
+    if line.chars().count() > 60 {
+        format!("{}…", line.chars().take(59).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Watcher lists fetched at once for `--notify-preview`.
+const NOTIFY_PREVIEW_CONCURRENCY: usize = 10;
+
+/// Watchers of one issue, as counted by `--notify-preview`.
+struct IssueWatchers {
+    key: String,
+    count: usize,
+    account_ids: Vec<String>,
+}
+
+/// Estimate the email a bulk update will trigger before it runs: each watcher
+/// of each matching issue gets one notification per issue.
+pub async fn preview_notifications(ctx: &JiraContext<'_>, jql: &str, notify: bool) -> Result<()> {
+    #[derive(Deserialize)]
+    struct WatchersResponse {
+        #[serde(rename = "watchCount", default)]
+        watch_count: usize,
+        #[serde(default)]
+        watchers: Vec<Watcher>,
+    }
+
+    #[derive(Deserialize)]
+    struct Watcher {
+        #[serde(rename = "accountId")]
+        account_id: String,
+    }
+
+    if !notify {
+        println!("ℹ️  --no-notify is set, so watchers will not be emailed");
+        return Ok(());
+    }
+
+    let issue_keys = search_issue_keys(ctx, jql).await?;
+    let watchers: Vec<IssueWatchers> = stream::iter(issue_keys)
+        .map(|key| async move {
+            let response: WatchersResponse = ctx
+                .client
+                .get(&format!("/rest/api/3/issue/{key}/watchers"))
+                .await
+                .with_context(|| format!("Failed to get watchers for {key}"))?;
+            Ok::<_, anyhow::Error>(IssueWatchers {
+                key,
+                count: response.watch_count.max(response.watchers.len()),
+                account_ids: response
+                    .watchers
+                    .into_iter()
+                    .map(|w| w.account_id)
+                    .collect(),
+            })
+        })
+        .buffer_unordered(NOTIFY_PREVIEW_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    println!("{}", notification_summary(&watchers));
+    Ok(())
+}
+
+fn notification_summary(watchers: &[IssueWatchers]) -> String {
+    let total: usize = watchers.iter().map(|w| w.count).sum();
+    if total == 0 {
+        return format!(
+            "ℹ️  None of the {} matching issues have watchers; no notifications expected",
+            watchers.len()
+        );
+    }
+
+    let people: HashSet<&str> = watchers
+        .iter()
+        .flat_map(|w| w.account_ids.iter().map(String::as_str))
+        .collect();
+    let mut summary = format!(
+        "⚠️  This will send about {total} watcher notifications to {} people across {} issues",
+        people.len(),
+        watchers.len()
+    );
+    if let Some(busiest) = watchers
+        .iter()
+        .max_by(|a, b| a.count.cmp(&b.count).then(b.key.cmp(&a.key)))
+    {
+        summary.push_str(&format!(
+            " (most: {} with {} watchers)",
+            busiest.key, busiest.count
+        ));
+    }
+    summary
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WatcherAction {
+    Add,
+    Remove,
+}
+
+// Bulk watcher subscriptions: every user on every matching issue
+pub async fn bulk_watchers(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    action: WatcherAction,
+    users: &[String],
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let issue_keys = search_issue_keys(ctx, jql).await?;
+
+    if issue_keys.is_empty() {
+        println!("No issues matched the JQL query");
+        return Ok(());
+    }
+
+    let mut accounts = Vec::with_capacity(users.len());
+    for user in users {
+        accounts.push((user.clone(), resolve_account_id(ctx, user).await?));
+    }
+
+    let items: Vec<(String, String, String)> = issue_keys
+        .iter()
+        .flat_map(|key| {
+            accounts
+                .iter()
+                .map(move |(user, id)| (key.clone(), user.clone(), id.clone()))
+        })
+        .collect();
+    let verb = match action {
+        WatcherAction::Add => "add watcher",
+        WatcherAction::Remove => "remove watcher",
+    };
+
+    if dry_run {
+        let operations: Vec<PlannedOperation> = items
+            .iter()
+            .map(|(key, user, _)| PlannedOperation::new(key, verb, user))
+            .collect();
+        return render_plan(ctx.renderer, &operations);
+    }
+
+    println!(
+        "Found {} issues; {} {} watcher(s) on each",
+        issue_keys.len(),
+        match action {
+            WatcherAction::Add => "adding",
+            WatcherAction::Remove => "removing",
+        },
+        accounts.len()
+    );
+
+    let executor = BulkExecutor::new(concurrency, false).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let result = executor
+        .execute_with_results(items.clone(), move |(key, user, account_id)| {
             let client = client.clone();
-            let labels = labels.clone();
-            let action = action.clone();
             async move {
-                // Get current labels
-                let issue: IssueWithLabels = client
-                    .get(&format!("/rest/api/3/issue/{key}?fields=labels"))
-                    .await
-                    .with_context(|| format!("Failed to get issue {key}"))?;
-
-                let new_labels = match action {
-                    LabelAction::Add => {
-                        let mut current = issue.fields.labels;
-                        for label in labels {
-                            if !current.contains(&label) {
-                                current.push(label);
-                            }
-                        }
-                        current
+                let path = format!("/rest/api/3/issue/{key}/watchers");
+                let _: Value = match action {
+                    WatcherAction::Add => client.post(&path, &json!(account_id)).await,
+                    WatcherAction::Remove => {
+                        client
+                            .delete(&format!(
+                                "{path}?accountId={}",
+                                urlencoding::encode(&account_id)
+                            ))
+                            .await
                     }
-                    LabelAction::Remove => issue
-                        .fields
-                        .labels
-                        .into_iter()
-                        .filter(|l| !labels.contains(l))
-                        .collect(),
-                    LabelAction::Set => labels,
-                };
-
-                let payload = json!({ "fields": { "labels": new_labels } });
-                let _: Value = client
-                    .put(&format!("/rest/api/3/issue/{key}"), &payload)
-                    .await
-                    .with_context(|| format!("Failed to update labels for {key}"))?;
-
-                tracing::info!(%key, "Labels updated successfully");
+                }
+                .with_context(|| format!("Failed to {verb} {user} on {key}"))?;
+                tracing::info!(%key, %user, "Watchers updated");
                 Ok(())
             }
         })
         .await?;
 
-    println!("✅ Bulk label operation completed");
-    Ok(())
+    if result.is_complete_success() {
+        println!("✅ Bulk watcher update completed");
+        return Ok(());
+    }
+
+    #[derive(Serialize)]
+    struct Failure<'a> {
+        issue: &'a str,
+        user: &'a str,
+        error: String,
+    }
+
+    let mut failures: Vec<Failure<'_>> = result
+        .failed
+        .iter()
+        .map(|(idx, err)| Failure {
+            issue: &items[*idx].0,
+            user: &items[*idx].1,
+            error: format!("{err:#}"),
+        })
+        .chain(result.not_started.iter().map(|idx| Failure {
+            issue: &items[*idx].0,
+            user: &items[*idx].1,
+            error: format!("not started: {}", cancel::reason()),
+        }))
+        .chain(result.abandoned.iter().map(|idx| Failure {
+            issue: &items[*idx].0,
+            user: &items[*idx].1,
+            error: format!("abandoned mid-request: {}", cancel::reason()),
+        }))
+        .collect();
+    failures.sort_by(|a, b| (a.issue, a.user).cmp(&(b.issue, b.user)));
+    ctx.renderer.render(&failures)?;
+
+    if result.is_interrupted() {
+        return Err(result.interrupted_error().into());
+    }
+    Err(anyhow::anyhow!(
+        "{} of {} watcher updates failed",
+        result.failure_count(),
+        items.len()
+    ))
 }
 
 // Bulk export issues
 pub async fn bulk_export(
     ctx: &JiraContext<'_>,
     jql: &str,
-    output: &PathBuf,
+    output: &Path,
     format: ExportFormat,
     fields: Vec<String>,
 ) -> Result<()> {
@@ -199,88 +522,54 @@ pub async fn bulk_export(
         fields.join(",")
     };
 
-    #[derive(Deserialize)]
-    struct SearchResponse {
-        issues: Vec<Value>,
-    }
-
     let payload = json!({
         "jql": jql,
         "maxResults": 1000,
         "fields": field_list,
     });
 
-    let response: SearchResponse = ctx
+    // Issues are streamed straight to disk rather than buffering the whole response
+    let issues = ctx
         .client
-        .post("/rest/api/3/search", &payload)
+        .request_array_stream::<Value, _>(
+            Method::POST,
+            "/rest/api/3/search",
+            Some(&payload),
+            Some("issues"),
+        )
         .await
         .context("Failed to search issues")?;
 
-    if response.issues.is_empty() {
-        println!("No issues matched the JQL query");
-        return Ok(());
-    }
-
-    println!("Found {} issues to export", response.issues.len());
-
-    match format {
-        ExportFormat::Json => {
-            let json_str = serde_json::to_string_pretty(&response.issues)?;
-            fs::write(output, json_str)?;
-        }
+    let count = match format {
+        ExportFormat::Json => export::write_json_array(issues, output).await?,
         ExportFormat::Csv => {
-            // Extract common fields for CSV
-            let mut wtr = csv::Writer::from_path(output)?;
-
-            // Write header
-            wtr.write_record([
-                "key", "summary", "status", "assignee", "reporter", "created",
-            ])?;
-
-            // Write rows
-            for issue in &response.issues {
-                let key = issue.get("key").and_then(|v| v.as_str()).unwrap_or("");
-                let summary = issue
-                    .get("fields")
-                    .and_then(|f| f.get("summary"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let status = issue
-                    .get("fields")
-                    .and_then(|f| f.get("status"))
-                    .and_then(|s| s.get("name"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let assignee = issue
-                    .get("fields")
-                    .and_then(|f| f.get("assignee"))
-                    .and_then(|a| a.get("displayName"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let reporter = issue
-                    .get("fields")
-                    .and_then(|f| f.get("reporter"))
-                    .and_then(|r| r.get("displayName"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                let created = issue
-                    .get("fields")
-                    .and_then(|f| f.get("created"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-
-                wtr.write_record([key, summary, status, assignee, reporter, created])?;
-            }
-
-            wtr.flush()?;
+            export::write_csv(
+                issues,
+                output,
+                &[
+                    "key", "summary", "status", "assignee", "reporter", "created",
+                ],
+                |issue| {
+                    vec![
+                        export::str_at(issue, "/key"),
+                        export::str_at(issue, "/fields/summary"),
+                        export::str_at(issue, "/fields/status/name"),
+                        export::str_at(issue, "/fields/assignee/displayName"),
+                        export::str_at(issue, "/fields/reporter/displayName"),
+                        export::str_at(issue, "/fields/created"),
+                    ]
+                },
+            )
+            .await?
         }
+    };
+
+    if count == 0 {
+        println!("No issues matched the JQL query");
+        return Ok(());
     }
 
-    println!(
-        "✅ Exported {} issues to {}",
-        response.issues.len(),
-        output.display()
-    );
+    println!("✅ Exported {} issues to {}", count, output.display());
     Ok(())
 }
 
@@ -300,97 +589,332 @@ pub async fn bulk_import(
         return Ok(());
     }
 
-    println!("Found {} issues to import", issues.len());
+    if !dry_run {
+        println!("Found {} issues to import", issues.len());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let project_key = project.to_string();
+
+    let operations = executor
+        .run_planned(
+            issues,
+            |issue| {
+                PlannedOperation::new(
+                    issue.summary.as_str(),
+                    "create",
+                    format!("{} in {}", issue.issue_type, project),
+                )
+            },
+            move |issue| {
+                let client = client.clone();
+                let project = project_key.clone();
+                async move {
+                    let mut fields = json!({
+                        "project": { "key": project },
+                        "issuetype": { "name": issue.issue_type },
+                        "summary": issue.summary,
+                    });
+
+                    if let Some(desc) = issue.description {
+                        fields["description"] = json!({
+                            "type": "doc",
+                            "version": 1,
+                            "content": [{
+                                "type": "paragraph",
+                                "content": [{ "type": "text", "text": desc }]
+                            }]
+                        });
+                    }
+
+                    if let Some(assignee) = issue.assignee {
+                        fields["assignee"] = json!({ "id": assignee });
+                    }
+
+                    if let Some(priority) = issue.priority {
+                        fields["priority"] = json!({ "name": priority });
+                    }
+
+                    if !issue.labels.is_empty() {
+                        fields["labels"] = json!(issue.labels);
+                    }
+
+                    let payload = json!({ "fields": fields });
+
+                    let response: CreateResponse = client
+                        .post("/rest/api/3/issue", &payload)
+                        .await
+                        .context("Failed to create issue")?;
+
+                    tracing::info!(key = %response.key, "Issue created successfully");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
 
     if dry_run {
-        println!("🔍 Dry run mode - no changes will be made:");
-        for (idx, issue) in issues.iter().enumerate() {
-            println!("  Would create: {} - {}", idx + 1, issue.summary);
-        }
+        return render_plan(ctx.renderer, &operations);
+    }
+    println!("✅ Bulk import completed");
+    Ok(())
+}
+
+// Bulk archive issues
+pub async fn bulk_archive(ctx: &JiraContext<'_>, jql: &str, dry_run: bool) -> Result<()> {
+    let issue_keys = search_issue_keys(ctx, jql).await?;
+
+    if issue_keys.is_empty() {
+        println!("No issues matched the JQL query");
         return Ok(());
     }
 
-    let executor = BulkExecutor::new(concurrency, dry_run);
-    let client = ctx.client.clone();
-    let project = project.to_string();
+    if dry_run {
+        let operations: Vec<PlannedOperation> = issue_keys
+            .iter()
+            .map(|key| PlannedOperation::new(key, "archive", ""))
+            .collect();
+        return render_plan(ctx.renderer, &operations);
+    }
 
-    executor
-        .run(issues, move |issue| {
-            let client = client.clone();
-            let project = project.clone();
-            async move {
-                let mut fields = json!({
-                    "project": { "key": project },
-                    "issuetype": { "name": issue.issue_type },
-                    "summary": issue.summary,
-                });
-
-                if let Some(desc) = issue.description {
-                    fields["description"] = json!({
-                        "type": "doc",
-                        "version": 1,
-                        "content": [{
-                            "type": "paragraph",
-                            "content": [{ "type": "text", "text": desc }]
-                        }]
-                    });
-                }
+    println!("Found {} issues to archive", issue_keys.len());
 
-                if let Some(assignee) = issue.assignee {
-                    fields["assignee"] = json!({ "id": assignee });
-                }
+    // The archive endpoint accepts up to 1000 issues per request
+    let mut archived = 0;
+    for chunk in issue_keys.chunks(1000) {
+        archived += set_archived(ctx, chunk, true).await?;
+    }
 
-                if let Some(priority) = issue.priority {
-                    fields["priority"] = json!({ "name": priority });
-                }
+    println!(
+        "✅ Bulk archive completed: {} of {} issues archived",
+        archived,
+        issue_keys.len()
+    );
+    Ok(())
+}
 
-                if !issue.labels.is_empty() {
-                    fields["labels"] = json!(issue.labels);
-                }
+#[derive(Serialize)]
+struct ReassignRow {
+    project: String,
+    assignee: usize,
+    reporter: usize,
+}
+
+// Hand over a departing user's open work to someone else
+#[allow(clippy::too_many_arguments)]
+pub async fn bulk_reassign(
+    ctx: &JiraContext<'_>,
+    from_user: &str,
+    to_user: &str,
+    projects: &[String],
+    include_reporter: bool,
+    include_filters: bool,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let from = resolve_account_id(ctx, from_user).await?;
+    let to = resolve_account_id(ctx, to_user).await?;
 
-                let payload = json!({ "fields": fields });
+    let assigned = search_issue_keys(ctx, &open_issues_jql("assignee", &from, projects)).await?;
+    let reported = if include_reporter {
+        search_issue_keys(ctx, &open_issues_jql("reporter", &from, projects)).await?
+    } else {
+        Vec::new()
+    };
+    let filters = if include_filters {
+        owned_filter_ids(ctx, &from).await?
+    } else {
+        Vec::new()
+    };
 
-                let response: CreateResponse = client
-                    .post("/rest/api/3/issue", &payload)
-                    .await
-                    .context("Failed to create issue")?;
+    if assigned.is_empty() && reported.is_empty() && filters.is_empty() {
+        println!("Nothing to reassign from {from_user}");
+        return Ok(());
+    }
 
-                tracing::info!(key = %response.key, "Issue created successfully");
-                Ok(())
-            }
+    let assigned_by_project = count_by_project(&assigned);
+    let reported_by_project = count_by_project(&reported);
+    let mut project_keys: Vec<&String> = assigned_by_project
+        .keys()
+        .chain(reported_by_project.keys())
+        .collect();
+    project_keys.sort();
+    project_keys.dedup();
+    let rows: Vec<ReassignRow> = project_keys
+        .into_iter()
+        .map(|project| ReassignRow {
+            project: project.clone(),
+            assignee: assigned_by_project.get(project).copied().unwrap_or(0),
+            reporter: reported_by_project.get(project).copied().unwrap_or(0),
         })
-        .await?;
+        .collect();
 
-    println!("✅ Bulk import completed");
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+        println!("  Would reassign open issues from {from_user} to {to_user}:");
+        ctx.renderer.render(&rows)?;
+        if include_filters {
+            println!("  Would transfer {} filters", filters.len());
+        }
+        return Ok(());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+
+    if !assigned.is_empty() {
+        let client = ctx.client.clone();
+        let to = to.clone();
+        executor
+            .run(assigned.clone(), move |key| {
+                let client = client.clone();
+                let payload = json!({ "accountId": to });
+                async move {
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/issue/{key}/assignee"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to reassign issue {key}"))?;
+                    tracing::info!(%key, "Assignee changed");
+                    Ok(())
+                }
+            })
+            .await?;
+    }
+
+    if !reported.is_empty() {
+        let client = ctx.client.clone();
+        let to = to.clone();
+        executor
+            .run(reported.clone(), move |key| {
+                let client = client.clone();
+                let payload = json!({ "fields": { "reporter": { "accountId": to } } });
+                async move {
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/issue/{key}"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to change reporter of {key}"))?;
+                    tracing::info!(%key, "Reporter changed");
+                    Ok(())
+                }
+            })
+            .await?;
+    }
+
+    for id in &filters {
+        let _: Value = ctx
+            .client
+            .put(
+                &format!("/rest/api/3/filter/{id}/owner"),
+                &json!({ "accountId": to }),
+            )
+            .await
+            .with_context(|| format!("Failed to transfer filter {id}"))?;
+        tracing::info!(filter = %id, "Filter owner changed");
+    }
+
+    println!(
+        "✅ Reassigned {} issues, {} reported issues and {} filters from {} to {}",
+        assigned.len(),
+        reported.len(),
+        filters.len(),
+        from_user,
+        to_user
+    );
     Ok(())
 }
 
 // Helper functions
 
-async fn search_issue_keys(ctx: &JiraContext<'_>, jql: &str) -> Result<Vec<String>> {
+/// Accept either an account ID or an email address / name to search for.
+pub async fn resolve_account_id(ctx: &JiraContext<'_>, user: &str) -> Result<String> {
     #[derive(Deserialize)]
-    struct SearchResponse {
-        issues: Vec<Issue>,
+    struct User {
+        #[serde(rename = "accountId")]
+        account_id: String,
     }
 
-    #[derive(Deserialize)]
-    struct Issue {
-        key: String,
+    if !user.contains('@') {
+        return Ok(user.to_string());
     }
 
-    let payload = json!({
-        "jql": jql,
-        "maxResults": 1000,
-        "fields": ["key"],
-    });
-
-    let response: SearchResponse = ctx
+    let users: Vec<User> = ctx
         .client
-        .post("/rest/api/3/search", &payload)
+        .get(&format!(
+            "/rest/api/3/user/search?query={}",
+            urlencoding::encode(user)
+        ))
         .await
-        .context("Failed to search issues")?;
+        .with_context(|| format!("Failed to look up user {user}"))?;
+
+    match users.as_slice() {
+        [single] => Ok(single.account_id.clone()),
+        [] => Err(anyhow::anyhow!("No Jira user found for '{}'", user)),
+        _ => Err(anyhow::anyhow!(
+            "'{}' matches {} users; pass the account ID instead",
+            user,
+            users.len()
+        )),
+    }
+}
+
+async fn owned_filter_ids(ctx: &JiraContext<'_>, account_id: &str) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct FilterPage {
+        values: Vec<Filter>,
+        #[serde(rename = "isLast", default)]
+        is_last: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    struct Filter {
+        id: String,
+    }
+
+    let mut ids = Vec::new();
+    let mut start_at = 0;
+    loop {
+        let page: FilterPage = ctx
+            .client
+            .get(&format!(
+                "/rest/api/3/filter/search?accountId={account_id}&startAt={start_at}&maxResults=50"
+            ))
+            .await
+            .context("Failed to search filters")?;
+        let count = page.values.len();
+        ids.extend(page.values.into_iter().map(|f| f.id));
+
+        if page.is_last.unwrap_or(true) || count == 0 {
+            break;
+        }
+        start_at += count;
+    }
+    Ok(ids)
+}
+
+fn open_issues_jql(field: &str, account_id: &str, projects: &[String]) -> String {
+    let filter = JqlBuilder::new()
+        .eq(field, account_id)
+        .in_list("project", projects)
+        .finish();
+    format!("{filter} AND statusCategory != Done")
+}
 
-    Ok(response.issues.into_iter().map(|i| i.key).collect())
+/// Count issue keys per project, using the key prefix.
+fn count_by_project(keys: &[String]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for key in keys {
+        let project = key.rsplit_once('-').map_or(key.as_str(), |(p, _)| p);
+        *counts.entry(project.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub(super) async fn search_issue_keys(ctx: &JiraContext<'_>, jql: &str) -> Result<Vec<String>> {
+    let issues = search_all(ctx, jql, &["key"]).await?;
+    Ok(issues
+        .iter()
+        .filter_map(|issue| issue["key"].as_str().map(str::to_string))
+        .collect())
 }
 
 async fn get_transition_id(ctx: &JiraContext<'_>, key: &str, transition: &str) -> Result<String> {
@@ -445,14 +969,18 @@ struct LabelsField {
     labels: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// One issue in a `jira bulk import` file (a JSON array of these).
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct ImportIssue {
     pub summary: String,
+    /// Issue type name, e.g. Task or Bug.
     pub issue_type: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Assignee account ID.
     #[serde(default)]
     pub assignee: Option<String>,
+    /// Priority name, e.g. High.
     #[serde(default)]
     pub priority: Option<String>,
     #[serde(default)]
@@ -463,3 +991,60 @@ pub struct ImportIssue {
 struct CreateResponse {
     key: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_issues_jql() {
+        assert_eq!(
+            open_issues_jql("assignee", "5b10ac8d82e05b22cc7d4ef5", &[]),
+            "assignee = \"5b10ac8d82e05b22cc7d4ef5\" AND statusCategory != Done"
+        );
+        assert_eq!(
+            open_issues_jql("reporter", "abc", &["DEV".to_string(), "OPS".to_string()]),
+            "reporter = \"abc\" AND project IN (\"DEV\", \"OPS\") AND statusCategory != Done"
+        );
+    }
+
+    #[test]
+    fn test_count_by_project() {
+        let keys = vec![
+            "DEV-1".to_string(),
+            "OPS-7".to_string(),
+            "DEV-12".to_string(),
+        ];
+        let counts = count_by_project(&keys);
+        assert_eq!(counts["DEV"], 2);
+        assert_eq!(counts["OPS"], 1);
+    }
+
+    #[test]
+    fn test_comment_preview() {
+        assert_eq!(
+            comment_preview("\nWe migrate next week\nDetails"),
+            "We migrate next week"
+        );
+        let long = "x".repeat(80);
+        assert_eq!(comment_preview(&long).chars().count(), 60);
+    }
+
+    #[test]
+    fn test_notification_summary() {
+        let watchers = |key: &str, ids: &[&str]| IssueWatchers {
+            key: key.to_string(),
+            count: ids.len(),
+            account_ids: ids.iter().map(|id| id.to_string()).collect(),
+        };
+        assert_eq!(
+            notification_summary(&[
+                watchers("DEV-1", &["a", "b"]),
+                watchers("DEV-2", &["a", "b", "c"]),
+                watchers("DEV-3", &[]),
+            ]),
+            "⚠️  This will send about 5 watcher notifications to 3 people across 3 issues (most: DEV-2 with 3 watchers)"
+        );
+        assert!(notification_summary(&[watchers("DEV-1", &[])]).contains("no notifications"));
+    }
+}
@@ -0,0 +1,324 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::projects::ensure_company_managed;
+use super::utils::JiraContext;
+
+// Component default assignees and declarative component sync
+
+/// Who new issues in a component are assigned to.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[value(rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AssigneeType {
+    ComponentLead,
+    ProjectLead,
+    ProjectDefault,
+    Unassigned,
+}
+
+impl AssigneeType {
+    fn as_str(self) -> &'static str {
+        match self {
+            AssigneeType::ComponentLead => "COMPONENT_LEAD",
+            AssigneeType::ProjectLead => "PROJECT_LEAD",
+            AssigneeType::ProjectDefault => "PROJECT_DEFAULT",
+            AssigneeType::Unassigned => "UNASSIGNED",
+        }
+    }
+}
+
+/// One entry of a `components.yaml` file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DesiredComponent {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// Lead account ID
+    #[serde(default)]
+    lead: Option<String>,
+    #[serde(default)]
+    assignee_type: Option<AssigneeType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExistingComponent {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    lead: Option<Lead>,
+    #[serde(rename = "assigneeType", default)]
+    assignee_type: Option<AssigneeType>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Lead {
+    #[serde(rename = "accountId")]
+    account_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Create(Value),
+    Update { id: String, payload: Value },
+    Delete { id: String },
+    Unchanged,
+}
+
+#[derive(Debug, PartialEq)]
+struct PlannedChange {
+    component: String,
+    action: Action,
+    changes: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct SyncRow<'a> {
+    component: &'a str,
+    action: &'static str,
+    changes: String,
+}
+
+pub async fn set_default_assignee(
+    ctx: &JiraContext<'_>,
+    id: &str,
+    assignee_type: AssigneeType,
+) -> Result<()> {
+    let _: Value = ctx
+        .client
+        .put(
+            &format!("/rest/api/3/component/{id}"),
+            &json!({ "assigneeType": assignee_type.as_str() }),
+        )
+        .await
+        .with_context(|| format!("Failed to update component {id}"))?;
+
+    tracing::info!(%id, assignee_type = assignee_type.as_str(), "Component default assignee set");
+    println!(
+        "✅ Set default assignee of component {} to {}",
+        id,
+        assignee_type.as_str()
+    );
+    Ok(())
+}
+
+/// Create and update components so the project matches the file; with `prune`,
+/// components missing from the file are deleted.
+pub async fn sync_components(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    file: &Path,
+    prune: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read components file: {}", file.display()))?;
+    let desired: Vec<DesiredComponent> = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse components file: {}", file.display()))?;
+
+    ensure_company_managed(ctx, project, "Components").await?;
+    let existing: Vec<ExistingComponent> = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}/components"))
+        .await
+        .with_context(|| format!("Failed to list components for project {project}"))?;
+
+    let plan = plan_sync(project, &desired, &existing, prune);
+    let rows: Vec<SyncRow> = plan
+        .iter()
+        .map(|change| SyncRow {
+            component: &change.component,
+            action: match change.action {
+                Action::Create(_) => "create",
+                Action::Update { .. } => "update",
+                Action::Delete { .. } => "delete",
+                Action::Unchanged => "unchanged",
+            },
+            changes: change.changes.join(", "),
+        })
+        .collect();
+
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+        return ctx.renderer.render(&rows);
+    }
+
+    let mut applied = 0;
+    for change in &plan {
+        let name = &change.component;
+        match &change.action {
+            Action::Create(payload) => {
+                let _: Value = ctx
+                    .client
+                    .post("/rest/api/3/component", payload)
+                    .await
+                    .with_context(|| format!("Failed to create component {name}"))?;
+            }
+            Action::Update { id, payload } => {
+                let _: Value = ctx
+                    .client
+                    .put(&format!("/rest/api/3/component/{id}"), payload)
+                    .await
+                    .with_context(|| format!("Failed to update component {name}"))?;
+            }
+            Action::Delete { id } => {
+                let _: Value = ctx
+                    .client
+                    .delete(&format!("/rest/api/3/component/{id}"))
+                    .await
+                    .with_context(|| format!("Failed to delete component {name}"))?;
+            }
+            Action::Unchanged => continue,
+        }
+        applied += 1;
+    }
+
+    tracing::info!(%project, applied, "Components synced");
+    ctx.renderer.render(&rows)?;
+    println!(
+        "✅ Synced components for {}: {} change(s) applied",
+        project, applied
+    );
+    Ok(())
+}
+
+/// Match components by name (case-insensitively, as Jira does) and only compare
+/// the fields the file sets.
+fn plan_sync(
+    project: &str,
+    desired: &[DesiredComponent],
+    existing: &[ExistingComponent],
+    prune: bool,
+) -> Vec<PlannedChange> {
+    let mut plan = Vec::new();
+
+    for want in desired {
+        let current = existing
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&want.name));
+        let mut payload = json!({});
+        let mut changes = Vec::new();
+
+        if let Some(description) = &want.description {
+            if current.and_then(|c| c.description.as_ref()) != Some(description) {
+                payload["description"] = json!(description);
+                changes.push("description");
+            }
+        }
+        if let Some(lead) = &want.lead {
+            if current.and_then(|c| c.lead.as_ref()).map(|l| &l.account_id) != Some(lead) {
+                payload["leadAccountId"] = json!(lead);
+                changes.push("lead");
+            }
+        }
+        if let Some(assignee_type) = want.assignee_type {
+            if current.and_then(|c| c.assignee_type) != Some(assignee_type) {
+                payload["assigneeType"] = json!(assignee_type.as_str());
+                changes.push("assignee_type");
+            }
+        }
+
+        let action = match current {
+            None => {
+                payload["name"] = json!(want.name);
+                payload["project"] = json!(project);
+                Action::Create(payload)
+            }
+            Some(_) if changes.is_empty() => Action::Unchanged,
+            Some(current) => Action::Update {
+                id: current.id.clone(),
+                payload,
+            },
+        };
+        plan.push(PlannedChange {
+            component: want.name.clone(),
+            action,
+            changes,
+        });
+    }
+
+    if prune {
+        for current in existing {
+            if !desired
+                .iter()
+                .any(|want| want.name.eq_ignore_ascii_case(&current.name))
+            {
+                plan.push(PlannedChange {
+                    component: current.name.clone(),
+                    action: Action::Delete {
+                        id: current.id.clone(),
+                    },
+                    changes: Vec::new(),
+                });
+            }
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_sync() {
+        let desired: Vec<DesiredComponent> = serde_yaml::from_str(
+            r#"
+- name: Backend
+  lead: acc-1
+  assignee_type: COMPONENT_LEAD
+- name: frontend
+  description: Web UI
+- name: Mobile
+  assignee_type: PROJECT_DEFAULT
+"#,
+        )
+        .unwrap();
+        let existing: Vec<ExistingComponent> = serde_json::from_value(json!([
+            { "id": "10", "name": "Backend", "lead": { "accountId": "acc-1" }, "assigneeType": "COMPONENT_LEAD" },
+            { "id": "11", "name": "Frontend", "description": "Old", "assigneeType": "PROJECT_DEFAULT" },
+            { "id": "12", "name": "Legacy" }
+        ]))
+        .unwrap();
+
+        let plan = plan_sync("DEV", &desired, &existing, false);
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0].action, Action::Unchanged);
+        assert_eq!(
+            plan[1].action,
+            Action::Update {
+                id: "11".to_string(),
+                payload: json!({ "description": "Web UI" }),
+            }
+        );
+        assert_eq!(
+            plan[2].action,
+            Action::Create(json!({
+                "name": "Mobile",
+                "project": "DEV",
+                "assigneeType": "PROJECT_DEFAULT"
+            }))
+        );
+        assert_eq!(plan[2].changes, vec!["assignee_type"]);
+
+        let pruned = plan_sync("DEV", &desired, &existing, true);
+        assert_eq!(
+            pruned[3],
+            PlannedChange {
+                component: "Legacy".to_string(),
+                action: Action::Delete {
+                    id: "12".to_string()
+                },
+                changes: Vec::new(),
+            }
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_bulk::{BulkExecutor, PlannedOperation};
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::components::AssigneeType;
+use super::projects::{ensure_company_managed, ensure_releases_enabled};
+use super::utils::JiraContext;
+use crate::commands::cancel;
+use crate::commands::dry_run::render_plan;
+
+// Bootstrapping a project's components and versions from CSV exports of
+// another tracker. Rows are validated up front; names already in the project
+// are skipped, so an interrupted import can be rerun
+
+/// One row of a components CSV.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ComponentRow {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// Lead account ID
+    #[serde(default)]
+    lead: Option<String>,
+    #[serde(default)]
+    assignee_type: Option<AssigneeType>,
+}
+
+/// One row of a versions CSV.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VersionRow {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    start_date: Option<String>,
+    #[serde(default)]
+    release_date: Option<String>,
+    #[serde(default)]
+    released: Option<bool>,
+    #[serde(default)]
+    archived: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct Existing {
+    name: String,
+}
+
+pub async fn import_components(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    file: &Path,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let rows: Vec<ComponentRow> = read_rows(file)?;
+    ensure_company_managed(ctx, project, "Components").await?;
+    let existing: Vec<Existing> = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}/components"))
+        .await
+        .with_context(|| format!("Failed to list components for project {project}"))?;
+
+    let total = rows.len();
+    let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+    let create = new_entries(&names, &existing, Vec::new())?;
+    let payloads: Vec<(String, Value)> = rows
+        .into_iter()
+        .zip(create)
+        .filter(|(_, create)| *create)
+        .map(|(row, _)| {
+            let mut payload = json!({ "name": row.name, "project": project });
+            if let Some(description) = row.description {
+                payload["description"] = json!(description);
+            }
+            if let Some(lead) = row.lead {
+                payload["leadAccountId"] = json!(lead);
+            }
+            if let Some(assignee_type) = row.assignee_type {
+                payload["assigneeType"] = json!(assignee_type);
+            }
+            (row.name, payload)
+        })
+        .collect();
+
+    create_all(
+        ctx,
+        project,
+        "component",
+        "/rest/api/3/component",
+        payloads,
+        total,
+        dry_run,
+        concurrency,
+    )
+    .await
+}
+
+pub async fn import_versions(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    file: &Path,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let rows: Vec<VersionRow> = read_rows(file)?;
+    ensure_releases_enabled(ctx, project).await?;
+    let existing: Vec<Existing> = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}/versions"))
+        .await
+        .with_context(|| format!("Failed to list versions for project {project}"))?;
+
+    let mut problems = Vec::new();
+    for (line, row) in lines(&rows) {
+        for (column, date) in [
+            ("start_date", &row.start_date),
+            ("release_date", &row.release_date),
+        ] {
+            if let Some(date) = date {
+                if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+                    problems.push((line, format!("{column} '{date}' is not a YYYY-MM-DD date")));
+                }
+            }
+        }
+    }
+    let total = rows.len();
+    let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+    let create = new_entries(&names, &existing, problems)?;
+    let payloads: Vec<(String, Value)> = rows
+        .into_iter()
+        .zip(create)
+        .filter(|(_, create)| *create)
+        .map(|(row, _)| {
+            let mut payload = json!({
+                "name": row.name,
+                "project": project,
+                "released": row.released.unwrap_or(false),
+                "archived": row.archived.unwrap_or(false),
+            });
+            if let Some(description) = row.description {
+                payload["description"] = json!(description);
+            }
+            if let Some(date) = row.start_date {
+                payload["startDate"] = json!(date);
+            }
+            if let Some(date) = row.release_date {
+                payload["releaseDate"] = json!(date);
+            }
+            (row.name, payload)
+        })
+        .collect();
+
+    create_all(
+        ctx,
+        project,
+        "version",
+        "/rest/api/3/version",
+        payloads,
+        total,
+        dry_run,
+        concurrency,
+    )
+    .await
+}
+
+/// Parse every row, with the header naming the columns and blank cells
+/// read as unset.
+fn read_rows<T: DeserializeOwned>(file: &Path) -> Result<Vec<T>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(file)
+        .with_context(|| format!("Failed to read CSV file: {}", file.display()))?;
+    let rows = reader
+        .deserialize()
+        .collect::<std::result::Result<Vec<T>, _>>()
+        .with_context(|| format!("Failed to parse CSV file: {}", file.display()))?;
+    if rows.is_empty() {
+        bail!("No rows to import in {}", file.display());
+    }
+    Ok(rows)
+}
+
+/// Rows with their line number in the file (the header is line 1).
+fn lines<T>(rows: &[T]) -> impl Iterator<Item = (usize, &T)> {
+    rows.iter().enumerate().map(|(i, row)| (i + 2, row))
+}
+
+/// Which rows to create: false for names the project already has. Blank
+/// names and names repeated in the file are added to `problems`, and any
+/// problem fails the whole import before anything is created. Names are
+/// compared case-insensitively, as Jira does.
+fn new_entries(
+    names: &[&str],
+    existing: &[Existing],
+    mut problems: Vec<(usize, String)>,
+) -> Result<Vec<bool>> {
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+    for (line, name) in lines(names) {
+        if name.is_empty() {
+            problems.push((line, "name is empty".to_string()));
+        } else {
+            let first = *first_seen.entry(name.to_lowercase()).or_insert(line);
+            if first != line {
+                problems.push((line, format!("'{name}' duplicates line {first}")));
+            }
+        }
+    }
+    if !problems.is_empty() {
+        problems.sort_by_key(|(line, _)| *line);
+        let lines: Vec<String> = problems
+            .iter()
+            .map(|(line, problem)| format!("line {line}: {problem}"))
+            .collect();
+        bail!(
+            "The file has {} problem(s), nothing was imported:\n  {}",
+            lines.len(),
+            lines.join("\n  ")
+        );
+    }
+
+    Ok(names
+        .iter()
+        .map(|name| {
+            !existing
+                .iter()
+                .any(|current| current.name.eq_ignore_ascii_case(name))
+        })
+        .collect())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn create_all(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    what: &'static str,
+    path: &'static str,
+    payloads: Vec<(String, Value)>,
+    total: usize,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let skipped = total - payloads.len();
+    if skipped > 0 {
+        println!("Skipping {skipped} {what}(s) that already exist in {project}");
+    }
+    if payloads.is_empty() {
+        println!("No new {what}s to import");
+        return Ok(());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let created = payloads.len();
+    let operations = executor
+        .run_planned(
+            payloads,
+            |(name, _)| {
+                PlannedOperation::new(name.as_str(), "create", format!("{what} in {project}"))
+            },
+            move |(name, payload)| {
+                let client = client.clone();
+                async move {
+                    let _: Value = client
+                        .post(path, &payload)
+                        .await
+                        .with_context(|| format!("Failed to create {what} {name}"))?;
+                    tracing::info!(%name, "Created {what}");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
+
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
+    tracing::info!(%project, created, skipped, "Imported {what}s");
+    println!("✅ Imported {created} {what}(s) into {project}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn existing(names: &[&str]) -> Vec<Existing> {
+        names
+            .iter()
+            .map(|name| Existing {
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new_entries() {
+        let create = new_entries(
+            &["Backend", "Mobile", "web"],
+            &existing(&["backend", "Web"]),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(create, vec![false, true, false]);
+
+        let err = new_entries(
+            &["API", "", "api"],
+            &[],
+            vec![(
+                4,
+                "release_date '2024-13-01' is not a YYYY-MM-DD date".to_string(),
+            )],
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "The file has 3 problem(s), nothing was imported:\n  \
+             line 3: name is empty\n  \
+             line 4: release_date '2024-13-01' is not a YYYY-MM-DD date\n  \
+             line 4: 'api' duplicates line 2"
+        );
+    }
+
+    #[test]
+    fn test_read_rows() {
+        let file =
+            std::env::temp_dir().join(format!("atlassian-cli-versions-{}.csv", std::process::id()));
+        std::fs::write(
+            &file,
+            "name,description,release_date,released\n2.0, First GA ,2024-05-01,true\n2.1,,,\n",
+        )
+        .unwrap();
+        let rows: Vec<VersionRow> = read_rows(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(rows[0].description.as_deref(), Some("First GA"));
+        assert_eq!(rows[0].released, Some(true));
+        assert_eq!(rows[1].description, None);
+        assert_eq!(rows[1].release_date, None);
+        assert_eq!(rows[1].released, None);
+    }
+}
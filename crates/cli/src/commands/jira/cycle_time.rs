@@ -0,0 +1,329 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::{parse_timestamp, OutputFormat};
+use chrono::{DateTime, FixedOffset};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::export::str_at;
+use super::issues::search_all;
+use super::utils::JiraContext;
+
+// Cycle and lead time statistics from status changes in issue changelogs
+
+const FETCH_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Deserialize)]
+struct History {
+    created: String,
+    #[serde(default)]
+    items: Vec<HistoryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryItem {
+    field: String,
+    #[serde(rename = "toString", default)]
+    to_string: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChangelogPage {
+    #[serde(default)]
+    values: Vec<History>,
+    #[serde(rename = "isLast", default)]
+    is_last: Option<bool>,
+}
+
+/// Times for one completed issue. Cycle time is empty when the issue never
+/// passed through the start status.
+#[derive(Debug, Serialize, PartialEq)]
+struct IssueTimes {
+    key: String,
+    summary: String,
+    created: String,
+    started: String,
+    completed: String,
+    cycle_days: Option<f64>,
+    lead_days: f64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct StatRow {
+    statistic: String,
+    cycle_days: String,
+    lead_days: String,
+}
+
+#[derive(Serialize)]
+struct CycleTimeReport {
+    completed: usize,
+    not_completed: usize,
+    stats: Vec<StatRow>,
+    issues: Vec<IssueTimes>,
+}
+
+pub async fn cycle_time_report(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    from_status: &str,
+    to_status: &str,
+    percentiles: &[u8],
+    csv: Option<&Path>,
+) -> Result<()> {
+    if let Some(p) = percentiles.iter().find(|&&p| p == 0 || p > 100) {
+        bail!("Percentiles must be between 1 and 100, got {p}");
+    }
+
+    let issues = search_all(ctx, jql, &["summary", "created"]).await?;
+    let histories: Vec<Vec<History>> = stream::iter(issues.iter())
+        .map(|issue| fetch_changelog(ctx, issue["key"].as_str().unwrap_or_default()))
+        .buffered(FETCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let times: Vec<IssueTimes> = issues
+        .iter()
+        .zip(&histories)
+        .filter_map(|(issue, history)| issue_times(issue, history, from_status, to_status))
+        .collect();
+    let report = CycleTimeReport {
+        completed: times.len(),
+        not_completed: issues.len() - times.len(),
+        stats: stat_rows(&times, percentiles),
+        issues: times,
+    };
+    tracing::info!(
+        completed = report.completed,
+        not_completed = report.not_completed,
+        "Built cycle time report"
+    );
+
+    if let Some(path) = csv {
+        write_csv(path, &report.issues)?;
+        println!(
+            "✅ Wrote {} issues to {}",
+            report.issues.len(),
+            path.display()
+        );
+    }
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&report);
+    }
+    if report.completed == 0 {
+        println!("No matching issues reached '{to_status}'");
+        return Ok(());
+    }
+    ctx.renderer.render(&report.stats)?;
+    println!(
+        "\n{} completed, {} not yet in '{}'",
+        report.completed, report.not_completed, to_status
+    );
+    Ok(())
+}
+
+async fn fetch_changelog(ctx: &JiraContext<'_>, key: &str) -> Result<Vec<History>> {
+    let mut histories = Vec::new();
+
+    loop {
+        let page: ChangelogPage = ctx
+            .client
+            .get(&format!(
+                "/rest/api/3/issue/{key}/changelog?startAt={}&maxResults=100",
+                histories.len()
+            ))
+            .await
+            .with_context(|| format!("Failed to fetch changelog for {key}"))?;
+        let count = page.values.len();
+        histories.extend(page.values);
+
+        if page.is_last.unwrap_or(true) || count == 0 {
+            break;
+        }
+    }
+
+    Ok(histories)
+}
+
+/// Cycle time runs from the first move into `from_status` to the last move into
+/// `to_status` (so reopened work counts until it is finally done); lead time
+/// runs from creation. Issues never moved into `to_status` are skipped.
+fn issue_times(
+    issue: &Value,
+    histories: &[History],
+    from_status: &str,
+    to_status: &str,
+) -> Option<IssueTimes> {
+    let mut moves: Vec<(DateTime<FixedOffset>, &str)> = histories
+        .iter()
+        .filter_map(|h| parse_timestamp(&h.created).map(|at| (at, h)))
+        .flat_map(|(at, h)| {
+            h.items
+                .iter()
+                .filter(|item| item.field == "status")
+                .filter_map(move |item| item.to_string.as_deref().map(|to| (at, to)))
+        })
+        .collect();
+    moves.sort_by_key(|(at, _)| *at);
+
+    let completed = moves
+        .iter()
+        .rev()
+        .find(|(_, to)| to.eq_ignore_ascii_case(to_status))?
+        .0;
+    let started = moves
+        .iter()
+        .find(|(at, to)| to.eq_ignore_ascii_case(from_status) && *at <= completed)
+        .map(|(at, _)| *at);
+    let created = str_at(issue, "/fields/created");
+    let days = |from: DateTime<FixedOffset>| (completed - from).num_minutes() as f64 / 1440.0;
+
+    Some(IssueTimes {
+        key: str_at(issue, "/key"),
+        summary: str_at(issue, "/fields/summary"),
+        started: started.map(|at| at.to_rfc3339()).unwrap_or_default(),
+        completed: completed.to_rfc3339(),
+        cycle_days: started.map(days).map(round),
+        lead_days: parse_timestamp(&created)
+            .map(days)
+            .map(round)
+            .unwrap_or(0.0),
+        created,
+    })
+}
+
+fn round(days: f64) -> f64 {
+    (days * 10.0).round() / 10.0
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f64], p: u8) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (f64::from(p) / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn stat_rows(times: &[IssueTimes], percentiles: &[u8]) -> Vec<StatRow> {
+    let sorted = |values: Vec<f64>| {
+        let mut values = values;
+        values.sort_by(f64::total_cmp);
+        values
+    };
+    let cycle = sorted(times.iter().filter_map(|t| t.cycle_days).collect());
+    let lead = sorted(times.iter().map(|t| t.lead_days).collect());
+    let show = |value: Option<f64>| value.map(|v| format!("{v:.1}")).unwrap_or_default();
+    let mean = |values: &[f64]| {
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+
+    let mut rows = vec![
+        StatRow {
+            statistic: "issues".to_string(),
+            cycle_days: cycle.len().to_string(),
+            lead_days: lead.len().to_string(),
+        },
+        StatRow {
+            statistic: "mean".to_string(),
+            cycle_days: show(mean(&cycle)),
+            lead_days: show(mean(&lead)),
+        },
+    ];
+    for &p in percentiles {
+        rows.push(StatRow {
+            statistic: format!("p{p}"),
+            cycle_days: show(percentile(&cycle, p)),
+            lead_days: show(percentile(&lead, p)),
+        });
+    }
+    rows.push(StatRow {
+        statistic: "max".to_string(),
+        cycle_days: show(cycle.last().copied()),
+        lead_days: show(lead.last().copied()),
+    });
+    rows
+}
+
+fn write_csv(path: &Path, issues: &[IssueTimes]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    for issue in issues {
+        writer.serialize(issue)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn history(created: &str, to: &str) -> History {
+        History {
+            created: created.to_string(),
+            items: vec![HistoryItem {
+                field: "status".to_string(),
+                to_string: Some(to.to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_issue_times() {
+        let issue = json!({
+            "key": "DEV-1",
+            "fields": { "summary": "Login", "created": "2024-03-01T09:00:00.000+0000" }
+        });
+        let histories = vec![
+            history("2024-03-04T09:00:00.000+0000", "In Progress"),
+            history("2024-03-06T21:00:00.000+0000", "Done"),
+            history("2024-03-07T09:00:00.000+0000", "In Progress"),
+            history("2024-03-08T09:00:00.000+0000", "Done"),
+        ];
+
+        let times = issue_times(&issue, &histories, "in progress", "Done").unwrap();
+        assert_eq!(times.cycle_days, Some(4.0));
+        assert_eq!(times.lead_days, 7.0);
+        assert_eq!(times.started, "2024-03-04T09:00:00+00:00");
+
+        let skipped = issue_times(&issue, &histories[..1], "In Progress", "Done");
+        assert!(skipped.is_none());
+        let straight = issue_times(&issue, &histories[1..2], "In Progress", "Done").unwrap();
+        assert_eq!(straight.cycle_days, None);
+        assert_eq!(straight.lead_days, 5.5);
+    }
+
+    #[test]
+    fn test_stat_rows() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 10.0];
+        assert_eq!(percentile(&sorted, 50), Some(3.0));
+        assert_eq!(percentile(&sorted, 85), Some(10.0));
+        assert_eq!(percentile(&sorted, 1), Some(1.0));
+        assert_eq!(percentile(&[], 50), None);
+
+        let times: Vec<IssueTimes> = [(Some(2.0), 5.0), (None, 3.0), (Some(4.0), 9.0)]
+            .into_iter()
+            .map(|(cycle_days, lead_days)| IssueTimes {
+                key: String::new(),
+                summary: String::new(),
+                created: String::new(),
+                started: String::new(),
+                completed: String::new(),
+                cycle_days,
+                lead_days,
+            })
+            .collect();
+        let rows = stat_rows(&times, &[50]);
+        let statistics: Vec<&str> = rows.iter().map(|r| r.statistic.as_str()).collect();
+        assert_eq!(statistics, vec!["issues", "mean", "p50", "max"]);
+        assert_eq!(rows[0].cycle_days, "2");
+        assert_eq!(rows[1].lead_days, "5.7");
+        assert_eq!(rows[2].cycle_days, "2.0");
+        assert_eq!(rows[3].lead_days, "9.0");
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+// `jira create --editor`: compose an issue in $EDITOR as YAML front matter plus a description
+
+const FRONT_MATTER: &str = "---";
+
+/// An issue as written in the editor. The description is the text after the front matter.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueDraft {
+    #[serde(default)]
+    pub project: String,
+    #[serde(default)]
+    pub issue_type: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// Extra fields by name or ID, resolved like `--field NAME=VALUE`.
+    #[serde(default)]
+    pub fields: BTreeMap<String, Value>,
+    #[serde(skip)]
+    pub description: Option<String>,
+}
+
+impl IssueDraft {
+    /// `fields` entries as `NAME=VALUE` arguments; lists become comma-separated values.
+    pub fn field_args(&self) -> Result<Vec<String>> {
+        self.fields
+            .iter()
+            .map(|(name, value)| Ok(format!("{name}={}", scalar_text(name, value)?)))
+            .collect()
+    }
+}
+
+/// The edited draft on disk, kept until the issue is created so a failed
+/// create does not lose what the user wrote.
+pub struct DraftFile {
+    path: PathBuf,
+}
+
+impl DraftFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Delete the draft once the issue exists.
+    pub fn discard(self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Open the draft in the user's editor until it parses and has the required
+/// fields, or the user gives up. The draft file is kept when editing is aborted.
+pub fn edit_draft(initial: &IssueDraft) -> Result<(IssueDraft, DraftFile)> {
+    // A fresh, owner-only file: the draft may hold confidential text
+    let path = tempfile::Builder::new()
+        .prefix("atlassian-cli-issue-")
+        .suffix(".md")
+        .tempfile()
+        .context("Failed to create draft file")?
+        .into_temp_path()
+        .keep()
+        .context("Failed to create draft file")?;
+    fs::write(&path, skeleton(initial))
+        .with_context(|| format!("Failed to write draft {}", path.display()))?;
+
+    loop {
+        open_editor(&path)?;
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read draft {}", path.display()))?;
+
+        match parse(&text) {
+            Ok(draft) => return Ok((draft, DraftFile { path })),
+            Err(err) => {
+                eprintln!("❌ {err:#}");
+                print!("Edit again? [Y/n]: ");
+                io::stdout().flush()?;
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                if input.trim().eq_ignore_ascii_case("n") {
+                    bail!("Issue not created; draft kept at {}", path.display());
+                }
+            }
+        }
+    }
+}
+
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    // Editors are often configured with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{editor}'"))?;
+    if !status.success() {
+        bail!(
+            "Editor '{editor}' exited with {status}; draft kept at {}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// The editor template: YAML front matter prefilled from the command line,
+/// followed by the description.
+pub fn skeleton(draft: &IssueDraft) -> String {
+    let optional = |value: &Option<String>| value.as_deref().map(quote).unwrap_or_default();
+    let components = if draft.components.is_empty() {
+        "[]".to_string()
+    } else {
+        format!(
+            "[{}]",
+            draft
+                .components
+                .iter()
+                .map(|c| quote(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+    let fields = if draft.fields.is_empty() {
+        " {}\n#  Story Points: 5\n#  Labels: [backend, urgent]\n".to_string()
+    } else {
+        let mut lines = String::from("\n");
+        for (name, value) in &draft.fields {
+            let value = serde_yaml::to_string(value).unwrap_or_default();
+            lines.push_str(&format!("  {}: {}\n", quote(name), value.trim_end()));
+        }
+        lines
+    };
+
+    format!(
+        "{FRONT_MATTER}
+# Fill in the issue below. project, issue_type and summary are required.
+# Everything after the closing --- is the description.
+project: {}
+issue_type: {}
+summary: {}
+assignee: {}
+priority: {}
+components: {components}
+fields:{fields}{FRONT_MATTER}
+{}
+",
+        quote(&draft.project),
+        quote(&draft.issue_type),
+        quote(&draft.summary),
+        optional(&draft.assignee),
+        optional(&draft.priority),
+        draft.description.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Parse and validate an edited draft.
+pub fn parse(text: &str) -> Result<IssueDraft> {
+    let rest = text
+        .trim_start()
+        .strip_prefix(FRONT_MATTER)
+        .ok_or_else(|| anyhow!("Draft must start with a '---' front matter block"))?;
+    let (header, body) = match rest.find("\n---") {
+        Some(end) => {
+            let body = &rest[end + 4..];
+            (&rest[..end], body.split_once('\n').map_or("", |(_, b)| b))
+        }
+        None => bail!("Front matter is not closed with '---'"),
+    };
+
+    let mut draft: IssueDraft = if header.trim().is_empty() {
+        IssueDraft::default()
+    } else {
+        serde_yaml::from_str(header).context("Invalid front matter")?
+    };
+
+    let mut missing = Vec::new();
+    for (name, value) in [
+        ("project", &draft.project),
+        ("issue_type", &draft.issue_type),
+        ("summary", &draft.summary),
+    ] {
+        if value.trim().is_empty() {
+            missing.push(name);
+        }
+    }
+    if !missing.is_empty() {
+        bail!("Missing required value(s): {}", missing.join(", "));
+    }
+    draft.field_args()?;
+
+    draft.assignee = draft.assignee.filter(|a| !a.trim().is_empty());
+    draft.priority = draft.priority.filter(|p| !p.trim().is_empty());
+    let body = body.trim();
+    draft.description = (!body.is_empty()).then(|| body.to_string());
+    Ok(draft)
+}
+
+fn scalar_text(name: &str, value: &Value) -> Result<String> {
+    Ok(match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Sequence(items) => items
+            .iter()
+            .map(|item| scalar_text(name, item))
+            .collect::<Result<Vec<_>>>()?
+            .join(","),
+        _ => bail!("Field '{name}' must be a text, number or list value"),
+    })
+}
+
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skeleton_round_trip() {
+        let draft = IssueDraft {
+            project: "DEV".to_string(),
+            issue_type: "Task".to_string(),
+            summary: "Fix: login \"timeout\"".to_string(),
+            components: vec!["API".to_string()],
+            description: Some("Steps:\n\n1. Log in".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(parse(&skeleton(&draft)).unwrap(), draft);
+    }
+
+    #[test]
+    fn test_parse_validates_required_fields() {
+        let err = parse(&skeleton(&IssueDraft::default())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Missing required value(s): project, issue_type, summary"
+        );
+        assert!(parse("project: DEV\n").is_err());
+        assert!(parse("---\nproject: [unclosed\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_field_args() {
+        let draft = parse(
+            "---\nproject: DEV\nissue_type: Bug\nsummary: Crash\nfields:\n  Story Points: 5\n  Labels: [a, b]\n---\n",
+        )
+        .unwrap();
+        assert_eq!(
+            draft.field_args().unwrap(),
+            vec!["Labels=a,b".to_string(), "Story Points=5".to_string()]
+        );
+        assert!(draft.description.is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use futures::stream::{Stream, StreamExt};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+// Streaming export writers. Items are written as they arrive from the API, and the
+// output file is only created once the first item is received.
+
+type ApiResult<T> = atlassian_cli_api::error::Result<T>;
+
+/// Write a stream of items to `output` as a pretty-printed JSON array.
+pub async fn write_json_array<S>(items: S, output: &Path) -> Result<usize>
+where
+    S: Stream<Item = ApiResult<Value>>,
+{
+    let mut items = std::pin::pin!(items);
+    let mut writer: Option<BufWriter<File>> = None;
+    let mut count = 0;
+
+    while let Some(item) = items.next().await {
+        let item = item.context("Failed to read export response")?;
+        let out = match writer.as_mut() {
+            Some(out) => {
+                out.write_all(b",\n")?;
+                out
+            }
+            None => {
+                let mut out = BufWriter::new(create(output)?);
+                out.write_all(b"[\n")?;
+                writer.insert(out)
+            }
+        };
+        serde_json::to_writer_pretty(&mut *out, &item)?;
+        count += 1;
+    }
+
+    if let Some(mut out) = writer {
+        out.write_all(b"\n]\n")?;
+        out.flush()?;
+    }
+
+    Ok(count)
+}
+
+/// Write a stream of items to `output` as CSV, using `row` to extract the columns.
+pub async fn write_csv<S, F>(items: S, output: &Path, header: &[&str], row: F) -> Result<usize>
+where
+    S: Stream<Item = ApiResult<Value>>,
+    F: Fn(&Value) -> Vec<String>,
+{
+    let mut items = std::pin::pin!(items);
+    let mut writer: Option<csv::Writer<File>> = None;
+    let mut count = 0;
+
+    while let Some(item) = items.next().await {
+        let item = item.context("Failed to read export response")?;
+        let wtr = match writer.as_mut() {
+            Some(wtr) => wtr,
+            None => {
+                let mut wtr = csv::Writer::from_writer(create(output)?);
+                wtr.write_record(header)?;
+                writer.insert(wtr)
+            }
+        };
+        wtr.write_record(row(&item))?;
+        count += 1;
+    }
+
+    if let Some(mut wtr) = writer {
+        wtr.flush()?;
+    }
+
+    Ok(count)
+}
+
+/// Read a string at a JSON pointer, defaulting to empty.
+pub fn str_at(value: &Value, pointer: &str) -> String {
+    value
+        .pointer(pointer)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn create(output: &Path) -> Result<File> {
+    File::create(output).with_context(|| format!("Failed to write file: {}", output.display()))
+}
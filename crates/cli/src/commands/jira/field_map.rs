@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::JiraContext;
+
+// Field name <-> id resolution backed by a per-profile cache of `/rest/api/3/field`
+
+/// Field metadata is refreshed from the API once a day.
+const CACHE_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMeta {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub schema: Option<FieldSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSchema {
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub items: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FieldCache {
+    fetched_at: u64,
+    fields: Vec<FieldMeta>,
+}
+
+/// All fields of a site, looked up by id or (case-insensitive) name.
+pub struct FieldMap {
+    fields: Vec<FieldMeta>,
+}
+
+/// A `--field NAME=VALUE` argument resolved to a field id and API value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldAssignment {
+    pub id: String,
+    pub name: String,
+    pub raw: String,
+    pub value: Value,
+}
+
+impl FieldMap {
+    pub fn new(fields: Vec<FieldMeta>) -> Self {
+        Self { fields }
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Find a field by id (e.g. `customfield_10016`) or by name (e.g. `Story Points`).
+    pub fn find(&self, name_or_id: &str) -> Option<&FieldMeta> {
+        self.fields.iter().find(|f| f.id == name_or_id).or_else(|| {
+            self.fields
+                .iter()
+                .find(|f| f.name.eq_ignore_ascii_case(name_or_id))
+        })
+    }
+
+    /// Resolve several names or ids, failing with all unknown entries at once.
+    pub fn resolve_all<'a>(&'a self, entries: &[&str]) -> Result<Vec<&'a FieldMeta>> {
+        let mut found = Vec::new();
+        let mut unknown = Vec::new();
+        for entry in entries {
+            match self.find(entry) {
+                Some(field) => found.push(field),
+                None => unknown.push(*entry),
+            }
+        }
+        if !unknown.is_empty() {
+            bail!(
+                "Unknown field(s): {}. Run `atlassian-cli jira fields list` to see available fields",
+                unknown.join(", ")
+            );
+        }
+        Ok(found)
+    }
+
+    /// Parse `NAME=VALUE` arguments into field ids and values shaped by the field schema.
+    pub fn assignments(&self, args: &[String]) -> Result<Vec<FieldAssignment>> {
+        let pairs = args
+            .iter()
+            .map(|arg| {
+                arg.split_once('=')
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .ok_or_else(|| anyhow!("Invalid --field '{arg}'. Expected NAME=VALUE"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let names: Vec<&str> = pairs.iter().map(|(name, _)| *name).collect();
+        let fields = self.resolve_all(&names)?;
+
+        pairs
+            .iter()
+            .zip(fields)
+            .map(|((name, raw), field)| {
+                Ok(FieldAssignment {
+                    id: field.id.clone(),
+                    name: name.to_string(),
+                    raw: raw.to_string(),
+                    value: field_value(field, raw)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Load the field map for the context's profile, from cache when it is fresh.
+pub async fn load(ctx: &JiraContext<'_>) -> Result<FieldMap> {
+    if let Some(cache) = cache_path(ctx.profile)
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str::<FieldCache>(&raw).ok())
+    {
+        if now().saturating_sub(cache.fetched_at) < CACHE_TTL_SECS {
+            return Ok(FieldMap::new(cache.fields));
+        }
+    }
+    refresh(ctx).await
+}
+
+/// Fetch the field list from the API and rewrite the profile's cache.
+pub async fn refresh(ctx: &JiraContext<'_>) -> Result<FieldMap> {
+    let fields: Vec<FieldMeta> = ctx
+        .client
+        .get("/rest/api/3/field")
+        .await
+        .context("Failed to list fields")?;
+
+    if let Some(path) = cache_path(ctx.profile) {
+        let cache = FieldCache {
+            fetched_at: now(),
+            fields: fields.clone(),
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, serde_json::to_vec(&cache).unwrap_or_default()));
+        if let Err(err) = written {
+            tracing::debug!(path = %path.display(), error = %err, "Unable to cache field list");
+        }
+    }
+
+    Ok(FieldMap::new(fields))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn cache_path(profile: &str) -> Option<PathBuf> {
+    let profile: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dirs::home_dir().map(|h| {
+        h.join(".atlassian-cli")
+            .join("cache")
+            .join(profile)
+            .join("fields.json")
+    })
+}
+
+/// Shape a raw CLI value for the field's schema type.
+fn field_value(field: &FieldMeta, raw: &str) -> Result<Value> {
+    let Some(schema) = &field.schema else {
+        return Ok(json!(raw));
+    };
+
+    let single = |kind: &str, value: &str| -> Result<Value> {
+        Ok(match kind {
+            "number" => {
+                let number: f64 = value.parse().map_err(|_| {
+                    anyhow!("Field '{}' expects a number, got '{value}'", field.name)
+                })?;
+                json!(number)
+            }
+            "option" => json!({ "value": value }),
+            "user" => json!({ "accountId": value }),
+            "priority" | "version" | "component" | "issuetype" => json!({ "name": value }),
+            _ => json!(value),
+        })
+    };
+
+    match schema.field_type.as_str() {
+        "array" => {
+            let kind = schema.items.as_deref().unwrap_or("string");
+            raw.split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(|v| single(kind, v))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array)
+        }
+        kind => single(kind, raw),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_map() -> FieldMap {
+        serde_json::from_value::<Vec<FieldMeta>>(json!([
+            { "id": "summary", "name": "Summary", "schema": { "type": "string" } },
+            { "id": "labels", "name": "Labels", "schema": { "type": "array", "items": "string" } },
+            { "id": "customfield_10016", "name": "Story Points", "schema": { "type": "number" } },
+            { "id": "customfield_10020", "name": "Team", "schema": { "type": "option" } },
+            { "id": "customfield_10030", "name": "Reviewers", "schema": { "type": "array", "items": "user" } }
+        ]))
+        .map(FieldMap::new)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_by_id_or_name() {
+        let map = field_map();
+        assert_eq!(map.find("story points").unwrap().id, "customfield_10016");
+        assert_eq!(map.find("customfield_10016").unwrap().name, "Story Points");
+        assert!(map.find("Sprint").is_none());
+
+        let err = map.resolve_all(&["Summary", "Sprint", "Epic"]).unwrap_err();
+        assert!(err.to_string().contains("Unknown field(s): Sprint, Epic"));
+    }
+
+    #[test]
+    fn test_assignments_follow_schema() {
+        let map = field_map();
+        let args: Vec<String> = [
+            "Story Points=5",
+            "Team = Platform",
+            "labels=a, b",
+            "Reviewers=123,456",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let values: Vec<(String, Value)> = map
+            .assignments(&args)
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.id, a.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("customfield_10016".to_string(), json!(5.0)),
+                (
+                    "customfield_10020".to_string(),
+                    json!({ "value": "Platform" })
+                ),
+                ("labels".to_string(), json!(["a", "b"])),
+                (
+                    "customfield_10030".to_string(),
+                    json!([{ "accountId": "123" }, { "accountId": "456" }])
+                ),
+            ]
+        );
+
+        assert!(map.assignments(&["Story Points=many".to_string()]).is_err());
+        assert!(map.assignments(&["Story Points".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::field_map::{self, FieldMap};
+use super::issues::{adf_to_text, SEARCH_PAGE_SIZE};
+use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// `--fields` support: request exactly the listed fields and render them as columns
+
+/// A requested field: the API id to fetch and the column it is shown under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedField {
+    pub id: String,
+    pub column: String,
+}
+
+/// Resolve `--fields` entries (ids like `customfield_10010` or names like
+/// `Story Points`) to field ids, using a per-site cache of the field list.
+pub async fn resolve_fields(
+    ctx: &JiraContext<'_>,
+    requested: &[String],
+) -> Result<Vec<SelectedField>> {
+    let known = field_map::load(ctx).await?;
+    select_fields(&known, requested)
+}
+
+/// Fetch one issue with only the selected fields.
+pub async fn fetch_issue_fields(
+    ctx: &JiraContext<'_>,
+    key: &str,
+    selected: &[SelectedField],
+) -> Result<Map<String, Value>> {
+    let issue: Value = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/issue/{key}?fields={}",
+            field_ids(selected)
+        ))
+        .await
+        .with_context(|| format!("Failed to fetch issue {key}"))?;
+    Ok(issue_row(&issue, selected))
+}
+
+/// Run a JQL search returning only the selected fields.
+pub async fn search_issue_fields(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    page: &PaginationArgs,
+    selected: &[SelectedField],
+) -> Result<Vec<Map<String, Value>>> {
+    #[derive(Deserialize)]
+    struct SearchResponse {
+        issues: Vec<Value>,
+        #[serde(rename = "nextPageToken")]
+        next_page_token: Option<String>,
+    }
+
+    let fields = field_ids(selected);
+    let issues = page
+        .collect(50, SEARCH_PAGE_SIZE, Paging::Cursor, |request| {
+            let mut query = format!(
+                "/rest/api/3/search/jql?jql={}&maxResults={}&fields={}",
+                urlencoding::encode(jql),
+                request.size,
+                fields
+            );
+            if let Some(token) = &request.cursor {
+                query.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+            }
+            async move {
+                let response: SearchResponse = ctx
+                    .client
+                    .get(&query)
+                    .await
+                    .context("Failed to execute search")?;
+                Ok(Page::cursor(response.issues, response.next_page_token))
+            }
+        })
+        .await?;
+
+    Ok(issues
+        .iter()
+        .map(|issue| issue_row(issue, selected))
+        .collect())
+}
+
+fn field_ids(selected: &[SelectedField]) -> String {
+    let ids: Vec<&str> = selected.iter().map(|f| f.id.as_str()).collect();
+    urlencoding::encode(&ids.join(",")).into_owned()
+}
+
+fn issue_row(issue: &Value, selected: &[SelectedField]) -> Map<String, Value> {
+    let mut row = Map::new();
+    row.insert(
+        "key".to_string(),
+        issue.get("key").cloned().unwrap_or(Value::Null),
+    );
+    for field in selected {
+        let value = issue["fields"].get(&field.id).unwrap_or(&Value::Null);
+        row.insert(field.column.clone(), Value::String(display_value(value)));
+    }
+    row
+}
+
+fn select_fields(known: &FieldMap, requested: &[String]) -> Result<Vec<SelectedField>> {
+    let entries: Vec<&str> = requested
+        .iter()
+        .map(|r| r.trim())
+        .filter(|r| !r.is_empty())
+        .collect();
+    let fields = known.resolve_all(&entries)?;
+
+    Ok(entries
+        .iter()
+        .zip(fields)
+        .map(|(entry, field)| SelectedField {
+            id: field.id.clone(),
+            column: entry.to_string(),
+        })
+        .collect())
+}
+
+/// Flatten a field value for display: users and options by name, documents as
+/// text, arrays comma-separated.
+pub(super) fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Object(obj) => {
+            if obj.get("type").and_then(Value::as_str) == Some("doc") {
+                return adf_to_text(value);
+            }
+            ["displayName", "name", "value", "key"]
+                .iter()
+                .find_map(|k| obj.get(*k).and_then(Value::as_str))
+                .map(str::to_string)
+                .unwrap_or_else(|| value.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn known() -> FieldMap {
+        serde_json::from_value::<Vec<field_map::FieldMeta>>(json!([
+            { "id": "summary", "name": "Summary" },
+            { "id": "customfield_10016", "name": "Story Points" }
+        ]))
+        .map(FieldMap::new)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_select_fields_by_id_and_name() {
+        let selected = select_fields(
+            &known(),
+            &["summary".to_string(), "story points".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            selected,
+            vec![
+                SelectedField {
+                    id: "summary".to_string(),
+                    column: "summary".to_string(),
+                },
+                SelectedField {
+                    id: "customfield_10016".to_string(),
+                    column: "story points".to_string(),
+                },
+            ]
+        );
+
+        let err = select_fields(&known(), &["Sprint".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Unknown field(s): Sprint"));
+    }
+
+    #[test]
+    fn test_display_value() {
+        assert_eq!(display_value(&json!(null)), "");
+        assert_eq!(display_value(&json!(5.0)), "5.0");
+        assert_eq!(display_value(&json!(["a", "b"])), "a, b");
+        assert_eq!(display_value(&json!({"displayName": "Ana"})), "Ana");
+        assert_eq!(
+            display_value(&json!([{"name": "Backend"}, {"value": "Red"}])),
+            "Backend, Red"
+        );
+        assert_eq!(
+            display_value(&json!({
+                "type": "doc",
+                "version": 1,
+                "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Hello"}]}]
+            })),
+            "Hello"
+        );
+    }
+}
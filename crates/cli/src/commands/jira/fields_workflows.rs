@@ -1,9 +1,14 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
+use super::field_map;
+use super::projects::{self, ProjectStyle};
 use super::utils::JiraContext;
+use super::validation;
 
 // Role Management Functions
 
@@ -15,7 +20,7 @@ pub async fn list_roles(ctx: &JiraContext<'_>, project: &str) -> Result<()> {
         .with_context(|| format!("Failed to list roles for project {project}"))?;
 
     println!("Roles for project {}:", project);
-    println!("{}", serde_json::to_string_pretty(&roles)?);
+    ctx.renderer.print_json(&roles)?;
     Ok(())
 }
 
@@ -26,7 +31,7 @@ pub async fn get_role(ctx: &JiraContext<'_>, project: &str, role_id: &str) -> Re
         .await
         .with_context(|| format!("Failed to get role {role_id} for project {project}"))?;
 
-    println!("{}", serde_json::to_string_pretty(&role)?);
+    ctx.renderer.print_json(&role)?;
     Ok(())
 }
 
@@ -39,7 +44,7 @@ pub async fn list_role_actors(ctx: &JiraContext<'_>, project: &str, role_id: &st
 
     if let Some(actors) = role.get("actors") {
         println!("Actors for role {}:", role_id);
-        println!("{}", serde_json::to_string_pretty(actors)?);
+        ctx.renderer.print_json(actors)?;
     } else {
         println!("No actors found for role {}", role_id);
     }
@@ -97,7 +102,15 @@ pub async fn remove_role_actor(
 
 // Field Management Functions
 
-pub async fn list_fields(ctx: &JiraContext<'_>) -> Result<()> {
+pub async fn list_fields(ctx: &JiraContext<'_>, project: Option<&str>) -> Result<()> {
+    if let Some(key) = project {
+        // Team-managed fields belong to their project and never show up in the
+        // global field list, so read them from the project's create metadata.
+        if projects::project_style(ctx, key).await? == ProjectStyle::TeamManaged {
+            return list_project_fields(ctx, key).await;
+        }
+    }
+
     #[derive(Deserialize)]
     struct Field {
         id: String,
@@ -134,6 +147,49 @@ pub async fn list_fields(ctx: &JiraContext<'_>) -> Result<()> {
     ctx.renderer.render(&rows)
 }
 
+async fn list_project_fields(ctx: &JiraContext<'_>, project: &str) -> Result<()> {
+    let issue_types = validation::fetch_issue_types(ctx, project).await?;
+
+    // Field ID -> (name, issue types using it), in first-seen order
+    let mut fields: Vec<(String, String, Vec<String>)> = Vec::new();
+    for issue_type in &issue_types {
+        let meta = validation::fetch_issue_type_fields(ctx, project, &issue_type.id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to get fields for issue type {} in project {project}",
+                    issue_type.name
+                )
+            })?;
+        for field in meta {
+            match fields.iter_mut().find(|(id, _, _)| *id == field.field_id) {
+                Some((_, _, types)) => types.push(issue_type.name.clone()),
+                None => fields.push((field.field_id, field.name, vec![issue_type.name.clone()])),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        name: &'a str,
+        custom: bool,
+        issue_types: String,
+    }
+
+    let rows: Vec<Row<'_>> = fields
+        .iter()
+        .map(|(id, name, types)| Row {
+            id: id.as_str(),
+            name: name.as_str(),
+            custom: id.starts_with("customfield_"),
+            issue_types: types.join(", "),
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
 pub async fn get_field(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
     let field: Value = ctx
         .client
@@ -141,7 +197,7 @@ pub async fn get_field(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
         .await
         .with_context(|| format!("Failed to get field {id}"))?;
 
-    println!("{}", serde_json::to_string_pretty(&field)?);
+    ctx.renderer.print_json(&field)?;
     Ok(())
 }
 
@@ -192,6 +248,17 @@ pub async fn delete_field(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
     Ok(())
 }
 
+pub async fn refresh_field_cache(ctx: &JiraContext<'_>) -> Result<()> {
+    let fields = field_map::refresh(ctx).await?;
+    tracing::info!(profile = %ctx.profile, count = fields.len(), "Field cache refreshed");
+    println!(
+        "✅ Cached {} fields for profile '{}'",
+        fields.len(),
+        ctx.profile
+    );
+    Ok(())
+}
+
 // Workflow Management Functions
 
 pub async fn list_workflows(ctx: &JiraContext<'_>) -> Result<()> {
@@ -247,7 +314,7 @@ pub async fn get_workflow(ctx: &JiraContext<'_>, name: &str) -> Result<()> {
         .await
         .with_context(|| format!("Failed to get workflow {name}"))?;
 
-    println!("{}", serde_json::to_string_pretty(&workflows)?);
+    ctx.renderer.print_json(&workflows)?;
     Ok(())
 }
 
@@ -256,9 +323,13 @@ pub async fn export_workflow(
     name: &str,
     output: Option<&str>,
 ) -> Result<()> {
+    // Statuses and transitions are needed to recreate the workflow with `workflows create`
     let workflow: Value = ctx
         .client
-        .get(&format!("/rest/api/3/workflow/search?workflowName={name}"))
+        .get(&format!(
+            "/rest/api/3/workflow/search?workflowName={}&expand=statuses,transitions,transitions.rules",
+            urlencoding::encode(name)
+        ))
         .await
         .with_context(|| format!("Failed to export workflow {name}"))?;
 
@@ -273,3 +344,305 @@ pub async fn export_workflow(
 
     Ok(())
 }
+
+pub async fn create_workflow(
+    ctx: &JiraContext<'_>,
+    file: &Path,
+    name: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Status {
+        id: String,
+        name: String,
+    }
+
+    let content = fs::read_to_string(file)
+        .with_context(|| format!("Failed to read file: {}", file.display()))?;
+    let source: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid JSON in {}", file.display()))?;
+
+    // Status IDs differ between sites, so statuses are matched by name
+    let statuses: Vec<Status> = ctx
+        .client
+        .get("/rest/api/3/status")
+        .await
+        .context("Failed to list statuses")?;
+    let status_ids: HashMap<String, String> = statuses
+        .into_iter()
+        .map(|s| (s.name.to_lowercase(), s.id))
+        .collect();
+
+    let payload = workflow_payload(&source, name, &status_ids)?;
+    let workflow_name = payload["name"].as_str().unwrap_or_default().to_string();
+
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+        ctx.renderer.print_json(&payload)?;
+        return Ok(());
+    }
+
+    let _: Value = ctx
+        .client
+        .post("/rest/api/3/workflow", &payload)
+        .await
+        .with_context(|| format!("Failed to create workflow {workflow_name}"))?;
+
+    tracing::info!(name = %workflow_name, "Workflow created");
+    println!("✅ Created workflow: {workflow_name}");
+    Ok(())
+}
+
+/// Build a create-workflow request from an exported workflow (a `workflows export`
+/// file or a single workflow object), remapping status IDs to this site's statuses.
+fn workflow_payload(
+    source: &Value,
+    name: Option<&str>,
+    status_ids: &HashMap<String, String>,
+) -> Result<Value> {
+    let workflow = match source.get("values").and_then(Value::as_array) {
+        Some(values) if values.len() == 1 => &values[0],
+        Some(values) => bail!("Expected one workflow in the file, found {}", values.len()),
+        None => source,
+    };
+
+    let name = name
+        .map(str::to_string)
+        .or_else(|| {
+            workflow
+                .pointer("/id/name")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .or_else(|| {
+            workflow
+                .get("name")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .ok_or_else(|| anyhow!("Workflow name missing; pass --name"))?;
+
+    let statuses = workflow
+        .get("statuses")
+        .and_then(Value::as_array)
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            anyhow!("Workflow has no statuses; export it with `jira workflows export` first")
+        })?;
+
+    let mut id_map = HashMap::new();
+    let mut payload_statuses = Vec::new();
+    for status in statuses {
+        let old_id = status.get("id").and_then(Value::as_str).unwrap_or_default();
+        let status_name = status
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Status {old_id} has no name"))?;
+        let new_id = status_ids.get(&status_name.to_lowercase()).ok_or_else(|| {
+            anyhow!("Status '{status_name}' does not exist on this site; create it first")
+        })?;
+        id_map.insert(old_id.to_string(), new_id.clone());
+
+        let mut entry = json!({ "id": new_id });
+        if let Some(properties) = status.get("properties").filter(|p| !p.is_null()) {
+            entry["properties"] = properties.clone();
+        }
+        payload_statuses.push(entry);
+    }
+
+    let remap = |id: &Value| -> Result<Value> {
+        let id = id.as_str().unwrap_or_default();
+        id_map
+            .get(id)
+            .map(|new_id| json!(new_id))
+            .ok_or_else(|| anyhow!("Transition refers to unknown status {id}"))
+    };
+
+    let mut transitions = Vec::new();
+    for transition in workflow
+        .get("transitions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let mut entry = json!({
+            "name": transition.get("name").cloned().unwrap_or_default(),
+            "to": remap(transition.get("to").unwrap_or(&Value::Null))?,
+            "type": transition.get("type").cloned().unwrap_or_else(|| json!("directed")),
+        });
+        if let Some(from) = transition.get("from").and_then(Value::as_array) {
+            entry["from"] = from.iter().map(&remap).collect::<Result<Vec<_>>>()?.into();
+        }
+        for key in ["description", "screen", "rules", "properties"] {
+            if let Some(value) = transition.get(key).filter(|v| !v.is_null()) {
+                entry[key] = value.clone();
+            }
+        }
+        transitions.push(entry);
+    }
+
+    let mut payload = json!({
+        "name": name,
+        "statuses": payload_statuses,
+        "transitions": transitions,
+    });
+    if let Some(description) = workflow.get("description").and_then(Value::as_str) {
+        payload["description"] = json!(description);
+    }
+    Ok(payload)
+}
+
+pub async fn assign_workflow(
+    ctx: &JiraContext<'_>,
+    workflow: &str,
+    project: &str,
+    issue_type: &str,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Project {
+        id: String,
+        #[serde(rename = "issueTypes", default)]
+        issue_types: Vec<IssueType>,
+    }
+
+    #[derive(Deserialize)]
+    struct IssueType {
+        id: String,
+        name: String,
+    }
+
+    #[derive(Deserialize)]
+    struct SchemeAssociations {
+        values: Vec<SchemeAssociation>,
+    }
+
+    #[derive(Deserialize)]
+    struct SchemeAssociation {
+        #[serde(rename = "workflowScheme")]
+        workflow_scheme: WorkflowScheme,
+    }
+
+    #[derive(Deserialize)]
+    struct WorkflowScheme {
+        #[serde(default)]
+        id: Option<i64>,
+        name: String,
+        #[serde(default)]
+        draft: bool,
+    }
+
+    projects::ensure_company_managed(ctx, project, "Workflow schemes").await?;
+
+    let details: Project = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}"))
+        .await
+        .with_context(|| format!("Failed to get project {project}"))?;
+    let issue_type_id = details
+        .issue_types
+        .iter()
+        .find(|t| t.id == issue_type || t.name.eq_ignore_ascii_case(issue_type))
+        .map(|t| t.id.clone())
+        .with_context(|| format!("Issue type '{issue_type}' not found in project {project}"))?;
+
+    let associations: SchemeAssociations = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/workflowscheme/project?projectId={}",
+            details.id
+        ))
+        .await
+        .with_context(|| format!("Failed to get workflow scheme for project {project}"))?;
+    let scheme = associations
+        .values
+        .into_iter()
+        .next()
+        .map(|a| a.workflow_scheme)
+        .with_context(|| format!("Project {project} has no workflow scheme"))?;
+    let Some(scheme_id) = scheme.id else {
+        bail!(
+            "Project {project} uses the default workflow scheme, which cannot be edited; \
+             assign it a custom workflow scheme first"
+        );
+    };
+
+    // Active schemes are changed through a draft that is then published
+    let payload = json!({
+        "issueType": issue_type_id,
+        "workflow": workflow,
+        "updateDraftIfNeeded": true,
+    });
+    let updated: WorkflowScheme = ctx
+        .client
+        .put(
+            &format!("/rest/api/3/workflowscheme/{scheme_id}/issuetype/{issue_type_id}"),
+            &payload,
+        )
+        .await
+        .with_context(|| format!("Failed to update workflow scheme {}", scheme.name))?;
+
+    if updated.draft {
+        let _: Value = ctx
+            .client
+            .post(
+                &format!("/rest/api/3/workflowscheme/{scheme_id}/draft/publish"),
+                &json!({ "statusMappings": [] }),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Saved a draft of workflow scheme {} but could not publish it; \
+                     publish it in Jira to migrate issue statuses",
+                    scheme.name
+                )
+            })?;
+    }
+
+    tracing::info!(workflow, project, issue_type, scheme = %scheme.name, "Workflow assigned");
+    println!(
+        "✅ Assigned workflow {workflow} to {issue_type} issues in {project} (scheme: {})",
+        scheme.name
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflow_payload() {
+        let export = json!({
+            "values": [{
+                "id": { "name": "Support", "entityId": "abc" },
+                "description": "Support flow",
+                "statuses": [
+                    { "id": "1", "name": "Open", "properties": {} },
+                    { "id": "10001", "name": "Waiting for customer" }
+                ],
+                "transitions": [
+                    { "id": "1", "name": "Create", "from": [], "to": "1", "type": "initial" },
+                    { "id": "11", "name": "Ask", "from": ["1"], "to": "10001", "type": "directed",
+                      "screen": null, "rules": { "conditions": [] } }
+                ]
+            }]
+        });
+        let site: HashMap<String, String> = [
+            ("open".to_string(), "1".to_string()),
+            ("waiting for customer".to_string(), "10200".to_string()),
+        ]
+        .into();
+
+        let payload = workflow_payload(&export, Some("Support v2"), &site).unwrap();
+        assert_eq!(payload["name"], "Support v2");
+        assert_eq!(payload["description"], "Support flow");
+        assert_eq!(payload["statuses"][1], json!({ "id": "10200" }));
+        assert_eq!(payload["transitions"][1]["from"], json!(["1"]));
+        assert_eq!(payload["transitions"][1]["to"], "10200");
+        assert!(payload["transitions"][1].get("screen").is_none());
+        assert_eq!(payload["transitions"][0]["type"], "initial");
+
+        let missing: HashMap<String, String> = HashMap::new();
+        assert!(workflow_payload(&export, None, &missing).is_err());
+    }
+}
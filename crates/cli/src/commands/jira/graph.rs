@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Deserialize;
+
+use super::utils::JiraContext;
+
+// Walk issue links breadth-first and render the result as Graphviz or Mermaid.
+
+const FETCH_CONCURRENCY: usize = 8;
+
+#[derive(Deserialize)]
+struct LinkedIssue {
+    key: String,
+    #[serde(default)]
+    fields: LinkedFields,
+}
+
+#[derive(Deserialize, Default)]
+struct LinkedFields {
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    status: Option<Status>,
+    #[serde(default)]
+    issuelinks: Vec<IssueLink>,
+}
+
+#[derive(Deserialize)]
+struct Status {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct IssueLink {
+    #[serde(rename = "type")]
+    link_type: LinkType,
+    #[serde(rename = "inwardIssue", default)]
+    inward_issue: Option<LinkedIssue>,
+    #[serde(rename = "outwardIssue", default)]
+    outward_issue: Option<LinkedIssue>,
+}
+
+#[derive(Deserialize)]
+struct LinkType {
+    outward: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    summary: String,
+    status: String,
+}
+
+#[derive(Debug, Default)]
+struct Graph {
+    nodes: BTreeMap<String, Node>,
+    /// (from, to, label), always in the link type's outward direction.
+    edges: BTreeSet<(String, String, String)>,
+}
+
+impl Graph {
+    fn add_node(&mut self, issue: &LinkedIssue) {
+        let node = Node {
+            summary: issue.fields.summary.clone().unwrap_or_default(),
+            status: issue
+                .fields
+                .status
+                .as_ref()
+                .map(|s| s.name.clone())
+                .unwrap_or_default(),
+        };
+        self.nodes.insert(issue.key.clone(), node);
+    }
+
+    /// Record an issue and its links, returning the keys it links to.
+    fn add_issue(&mut self, issue: &LinkedIssue) -> Vec<String> {
+        self.add_node(issue);
+
+        let mut neighbours = Vec::new();
+        for link in &issue.fields.issuelinks {
+            let label = link.link_type.outward.clone();
+            let (other, from, to) = match (&link.outward_issue, &link.inward_issue) {
+                (Some(other), _) => (other, issue.key.clone(), other.key.clone()),
+                (None, Some(other)) => (other, other.key.clone(), issue.key.clone()),
+                (None, None) => continue,
+            };
+            if !self.nodes.contains_key(&other.key) {
+                self.add_node(other);
+            }
+            self.edges.insert((from, to, label));
+            neighbours.push(other.key.clone());
+        }
+        neighbours
+    }
+}
+
+pub async fn export_graph(
+    ctx: &JiraContext<'_>,
+    key: &str,
+    depth: usize,
+    format: &str,
+    max_issues: usize,
+    output: Option<&Path>,
+) -> Result<()> {
+    let format = format.to_ascii_lowercase();
+    if format != "dot" && format != "mermaid" {
+        bail!("Invalid format '{}'. Must be one of: dot, mermaid", format);
+    }
+
+    let graph = walk_links(ctx, key, depth, max_issues).await?;
+    let rendered = match format.as_str() {
+        "dot" => render_dot(&graph),
+        _ => render_mermaid(&graph),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, rendered)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            tracing::info!(%key, file = %path.display(), "Issue graph exported");
+            println!(
+                "✅ Exported graph of {} issues and {} links to {}",
+                graph.nodes.len(),
+                graph.edges.len(),
+                path.display()
+            );
+        }
+        None => print!("{rendered}"),
+    }
+
+    Ok(())
+}
+
+async fn walk_links(
+    ctx: &JiraContext<'_>,
+    root: &str,
+    depth: usize,
+    max_issues: usize,
+) -> Result<Graph> {
+    let mut graph = Graph::default();
+    let mut visited = BTreeSet::new();
+    let mut frontier = vec![root.to_string()];
+
+    // Each fetched level adds the issues one link further out, so after `depth`
+    // levels the outermost issues appear as unexpanded leaf nodes.
+    for level in 0..depth.max(1) {
+        frontier.retain(|key| !visited.contains(key));
+        let remaining = max_issues.saturating_sub(visited.len());
+        if frontier.is_empty() || remaining == 0 {
+            break;
+        }
+        if frontier.len() > remaining {
+            tracing::warn!(max_issues, "Issue limit reached, graph is truncated");
+            frontier.truncate(remaining);
+        }
+        visited.extend(frontier.iter().cloned());
+
+        let issues: Vec<LinkedIssue> = stream::iter(frontier.iter())
+            .map(|key| async move {
+                ctx.client
+                    .get::<LinkedIssue>(&format!(
+                        "/rest/api/3/issue/{key}?fields=summary,status,issuelinks"
+                    ))
+                    .await
+                    .with_context(|| format!("Failed to fetch issue {key}"))
+            })
+            .buffer_unordered(FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut next = BTreeSet::new();
+        for issue in &issues {
+            next.extend(graph.add_issue(issue));
+        }
+        tracing::info!(level, fetched = issues.len(), "Walked issue links");
+        frontier = next.into_iter().collect();
+    }
+
+    Ok(graph)
+}
+
+fn render_dot(graph: &Graph) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+
+    let mut out = String::from("digraph issues {\n  rankdir=LR;\n  node [shape=box];\n");
+    for (key, node) in &graph.nodes {
+        let mut label = escape(key);
+        if !node.summary.is_empty() {
+            label.push_str(&format!("\\n{}", escape(&node.summary)));
+        }
+        if !node.status.is_empty() {
+            label.push_str(&format!("\\n[{}]", escape(&node.status)));
+        }
+        out.push_str(&format!("  \"{}\" [label=\"{}\"];\n", escape(key), label));
+    }
+    for (from, to, label) in &graph.edges {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            escape(from),
+            escape(to),
+            escape(label)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(graph: &Graph) -> String {
+    let id = |key: &str| key.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    let escape = |s: &str| s.replace('"', "#quot;").replace('|', "#124;");
+
+    let mut out = String::from("graph LR\n");
+    for (key, node) in &graph.nodes {
+        let mut label = key.clone();
+        if !node.summary.is_empty() {
+            label.push_str(&format!(": {}", node.summary));
+        }
+        if !node.status.is_empty() {
+            label.push_str(&format!(" [{}]", node.status));
+        }
+        out.push_str(&format!("  {}[\"{}\"]\n", id(key), escape(&label)));
+    }
+    for (from, to, label) in &graph.edges {
+        out.push_str(&format!(
+            "  {} -->|{}| {}\n",
+            id(from),
+            escape(label),
+            id(to)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_graph() -> Graph {
+        let issue: LinkedIssue = serde_json::from_value(json!({
+            "key": "DEV-1",
+            "fields": {
+                "summary": "Ship \"v2\"",
+                "status": { "name": "In Progress" },
+                "issuelinks": [
+                    {
+                        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                        "outwardIssue": { "key": "DEV-2", "fields": { "summary": "Docs" } }
+                    },
+                    {
+                        "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                        "inwardIssue": { "key": "OPS-7", "fields": { "status": { "name": "Done" } } }
+                    }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let mut graph = Graph::default();
+        assert_eq!(graph.add_issue(&issue), vec!["DEV-2", "OPS-7"]);
+        graph
+    }
+
+    #[test]
+    fn test_links_are_normalised_to_outward_direction() {
+        let graph = sample_graph();
+        let edges: Vec<_> = graph.edges.iter().cloned().collect();
+        assert_eq!(
+            edges,
+            vec![
+                (
+                    "DEV-1".to_string(),
+                    "DEV-2".to_string(),
+                    "blocks".to_string()
+                ),
+                (
+                    "OPS-7".to_string(),
+                    "DEV-1".to_string(),
+                    "blocks".to_string()
+                ),
+            ]
+        );
+        assert_eq!(graph.nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let dot = render_dot(&sample_graph());
+        assert!(dot.starts_with("digraph issues {\n"));
+        assert!(dot.contains("  \"DEV-1\" [label=\"DEV-1\\nShip \\\"v2\\\"\\n[In Progress]\"];\n"));
+        assert!(dot.contains("  \"OPS-7\" -> \"DEV-1\" [label=\"blocks\"];\n"));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let mermaid = render_mermaid(&sample_graph());
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("  DEV_1[\"DEV-1: Ship #quot;v2#quot; [In Progress]\"]\n"));
+        assert!(mermaid.contains("  DEV_1 -->|blocks| DEV_2\n"));
+        assert!(mermaid.contains("  OPS_7[\"OPS-7 [Done]\"]\n"));
+    }
+}
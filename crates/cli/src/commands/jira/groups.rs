@@ -0,0 +1,213 @@
+use std::cell::Cell;
+
+use anyhow::{Context, Result};
+use atlassian_cli_output::OutputFormat;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::bulk::resolve_account_id;
+use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Group listing and membership management
+
+const FETCH_CONCURRENCY: usize = 8;
+
+#[derive(Deserialize)]
+struct GroupPage {
+    values: Vec<Group>,
+    #[serde(rename = "isLast", default)]
+    is_last: bool,
+}
+
+#[derive(Deserialize)]
+struct Group {
+    name: String,
+    #[serde(rename = "groupId", default)]
+    group_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MemberPage {
+    #[serde(default)]
+    values: Vec<Member>,
+    #[serde(rename = "isLast", default)]
+    is_last: bool,
+    #[serde(default)]
+    total: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    #[serde(rename = "accountId")]
+    account_id: String,
+    #[serde(rename = "displayName", default)]
+    display_name: String,
+    #[serde(rename = "emailAddress", default)]
+    email_address: Option<String>,
+    #[serde(default)]
+    active: bool,
+}
+
+fn member_path(group: &str, start: usize, size: usize) -> String {
+    format!(
+        "/rest/api/3/group/member?groupname={}&startAt={}&maxResults={}&includeInactiveUsers=true",
+        urlencoding::encode(group),
+        start,
+        size
+    )
+}
+
+pub async fn list_groups(ctx: &JiraContext<'_>, page: &PaginationArgs) -> Result<()> {
+    let groups: Vec<Group> = page
+        .collect(50, 50, Paging::Offset, |request| {
+            let path = format!(
+                "/rest/api/3/group/bulk?startAt={}&maxResults={}",
+                request.start, request.size
+            );
+            async move {
+                let response: GroupPage = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .context("Failed to list groups")?;
+                Ok(Page::offset(response.values, !response.is_last))
+            }
+        })
+        .await?;
+
+    // Only the first member page is fetched; its `total` is the group size
+    let counts: Vec<Option<usize>> = stream::iter(groups.iter())
+        .map(|group| async move {
+            let members: MemberPage = ctx
+                .client
+                .get(&member_path(&group.name, 0, 1))
+                .await
+                .with_context(|| format!("Failed to count members of {}", group.name))?;
+            Ok::<_, anyhow::Error>(members.total)
+        })
+        .buffered(FETCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        name: &'a str,
+        group_id: &'a str,
+        members: String,
+    }
+
+    let rows: Vec<Row<'_>> = groups
+        .iter()
+        .zip(counts)
+        .map(|(group, count)| Row {
+            name: &group.name,
+            group_id: group.group_id.as_deref().unwrap_or(""),
+            members: count.map(|n| n.to_string()).unwrap_or_default(),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        tracing::info!("No groups found");
+        return Ok(());
+    }
+
+    ctx.renderer.render_with_id(&rows, "name")
+}
+
+pub async fn list_group_members(
+    ctx: &JiraContext<'_>,
+    group: &str,
+    page: &PaginationArgs,
+) -> Result<()> {
+    let total = Cell::new(None);
+    let members: Vec<Member> = page
+        .collect(50, 50, Paging::Offset, |request| {
+            let path = member_path(group, request.start, request.size);
+            let total = &total;
+            async move {
+                let response: MemberPage = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .with_context(|| format!("Failed to list members of group {group}"))?;
+                total.set(response.total);
+                Ok(Page::offset(response.values, !response.is_last))
+            }
+        })
+        .await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        account_id: &'a str,
+        display_name: &'a str,
+        email: &'a str,
+        active: bool,
+    }
+
+    let rows: Vec<Row<'_>> = members
+        .iter()
+        .map(|m| Row {
+            account_id: &m.account_id,
+            display_name: &m.display_name,
+            email: m.email_address.as_deref().unwrap_or(""),
+            active: m.active,
+        })
+        .collect();
+
+    if rows.is_empty() {
+        tracing::info!(group, "Group has no members");
+        return Ok(());
+    }
+
+    ctx.renderer.render_with_id(&rows, "account_id")?;
+    if ctx.renderer.format() == OutputFormat::Table {
+        let total = total.get().unwrap_or(rows.len());
+        println!(
+            "\nShowing {} of {} member(s) in {}",
+            rows.len(),
+            total,
+            group
+        );
+    }
+    Ok(())
+}
+
+pub async fn add_group_user(ctx: &JiraContext<'_>, group: &str, user: &str) -> Result<()> {
+    let account_id = resolve_account_id(ctx, user).await?;
+
+    let _: Value = ctx
+        .client
+        .post(
+            &format!(
+                "/rest/api/3/group/user?groupname={}",
+                urlencoding::encode(group)
+            ),
+            &json!({ "accountId": account_id }),
+        )
+        .await
+        .with_context(|| format!("Failed to add {user} to group {group}"))?;
+
+    tracing::info!(group, %account_id, "User added to group");
+    println!("✅ Added {} to group {}", user, group);
+    Ok(())
+}
+
+pub async fn remove_group_user(ctx: &JiraContext<'_>, group: &str, user: &str) -> Result<()> {
+    let account_id = resolve_account_id(ctx, user).await?;
+
+    let _: Value = ctx
+        .client
+        .delete(&format!(
+            "/rest/api/3/group/user?groupname={}&accountId={}",
+            urlencoding::encode(group),
+            urlencoding::encode(&account_id)
+        ))
+        .await
+        .with_context(|| format!("Failed to remove {user} from group {group}"))?;
+
+    tracing::info!(group, %account_id, "User removed from group");
+    println!("✅ Removed {} from group {}", user, group);
+    Ok(())
+}
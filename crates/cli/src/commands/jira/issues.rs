@@ -2,11 +2,19 @@ use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::field_map::FieldAssignment;
+use super::field_select::display_value;
 use super::utils::JiraContext;
+use super::validation::{validate_create, ProvidedFields};
+use crate::commands::dry_run::{field_changes, render_diff};
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 use crate::query::JqlBuilder;
 
 // Issue CRUD Operations
 
+/// Largest `maxResults` accepted by the enhanced JQL search endpoint.
+pub const SEARCH_PAGE_SIZE: usize = 100;
+
 #[allow(clippy::too_many_arguments)]
 pub async fn search_issues(
     ctx: &JiraContext<'_>,
@@ -18,9 +26,47 @@ pub async fn search_issues(
     r#type: Option<&str>,
     project: Option<&str>,
     text: Option<&str>,
+    text_in: &[TextField],
     show_query: bool,
-    limit: usize,
+    page: &PaginationArgs,
+    columns: &[String],
 ) -> Result<()> {
+    let final_jql = resolve_search_jql(
+        jql, assignee, status, priority, label, r#type, project, text, text_in, show_query,
+    )?;
+
+    let text_search = text.map(|text| TextSearch {
+        text,
+        fields: text_in,
+    });
+    let rows = fetch_search_rows(ctx, &final_jql, page, text_search, wants_votes(columns)).await?;
+
+    if rows.is_empty() {
+        tracing::info!("No issues matched the provided JQL.");
+        return Ok(());
+    }
+
+    if columns.is_empty() {
+        return ctx.renderer.render_with_id(&rows, "key");
+    }
+    ctx.renderer
+        .render_with_id(&select_columns(&rows, columns)?, "key")
+}
+
+/// Build the JQL for a search from either raw JQL or filter flags, printing it when requested.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_search_jql(
+    jql: Option<&str>,
+    assignee: Option<&str>,
+    status: &[String],
+    priority: Option<&str>,
+    label: &[String],
+    r#type: Option<&str>,
+    project: Option<&str>,
+    text: Option<&str>,
+    text_in: &[TextField],
+    show_query: bool,
+) -> Result<String> {
     // Build JQL from filters or use raw JQL
     let final_jql = if let Some(raw_jql) = jql {
         raw_jql.to_string()
@@ -47,7 +93,12 @@ pub async fn search_issues(
             builder = builder.eq("project", proj);
         }
         if let Some(txt) = text {
-            builder = builder.contains("summary", txt);
+            let fields: Vec<&str> = if text_in.is_empty() {
+                vec![TextField::Summary.jql_field()]
+            } else {
+                text_in.iter().map(|f| f.jql_field()).collect()
+            };
+            builder = builder.contains_any(&fields, txt);
         }
 
         let built_jql = builder.finish();
@@ -67,72 +118,275 @@ pub async fn search_issues(
         }
     }
 
+    Ok(final_jql)
+}
+
+/// Issue fields that `--text` can search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TextField {
+    Summary,
+    Description,
+    Comments,
+}
+
+impl TextField {
+    fn jql_field(self) -> &'static str {
+        match self {
+            TextField::Summary => "summary",
+            TextField::Description => "description",
+            TextField::Comments => "comment",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            TextField::Summary => "summary",
+            TextField::Description => "description",
+            TextField::Comments => "comments",
+        }
+    }
+}
+
+/// A free-text search whose matching fields should be reported per row.
+#[derive(Clone, Copy)]
+pub struct TextSearch<'a> {
+    pub text: &'a str,
+    pub fields: &'a [TextField],
+}
+
+#[derive(Serialize)]
+pub struct SearchRow {
+    key: String,
+    summary: String,
+    status: String,
+    assignee: String,
+    issue_type: String,
+    priority: String,
+    labels: String,
+    updated: String,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matched_in: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    votes: Option<u64>,
+}
+
+/// Column names of `SearchRow`, accepted by `jira search --columns`.
+pub const SEARCH_COLUMNS: &[&str] = &[
+    "key",
+    "summary",
+    "status",
+    "assignee",
+    "issue_type",
+    "priority",
+    "labels",
+    "updated",
+    "url",
+    "matched_in",
+    "votes",
+];
+
+/// Whether `--columns` asks for vote counts, which are only fetched on request.
+pub fn wants_votes(columns: &[String]) -> bool {
+    columns.iter().any(|c| c == "votes")
+}
+
+/// Order results by vote count unless the JQL already sets an order.
+fn order_by_votes(jql: &str) -> String {
+    if jql.to_ascii_uppercase().contains("ORDER BY") {
+        jql.to_string()
+    } else {
+        format!("{jql} ORDER BY votes DESC")
+    }
+}
+
+/// Keep only the requested search columns. A `profile` column added by
+/// multi-profile searches is always kept.
+pub fn select_columns<T: Serialize>(rows: &[T], columns: &[String]) -> Result<Vec<Value>> {
+    if let Some(unknown) = columns
+        .iter()
+        .find(|c| !SEARCH_COLUMNS.contains(&c.as_str()))
+    {
+        return Err(anyhow!(
+            "Unknown column '{}'. Available columns: {}",
+            unknown,
+            SEARCH_COLUMNS.join(", ")
+        ));
+    }
+
+    rows.iter()
+        .map(|row| {
+            let mut value = serde_json::to_value(row)?;
+            if let Some(object) = value.as_object_mut() {
+                object.retain(|key, _| key == "profile" || columns.iter().any(|c| c == key));
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Run a JQL search and flatten the matching issues into table rows. With
+/// `with_votes`, rows carry vote counts and come most-voted first.
+pub async fn fetch_search_rows(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    page: &PaginationArgs,
+    text_search: Option<TextSearch<'_>>,
+    with_votes: bool,
+) -> Result<Vec<SearchRow>> {
     #[derive(Deserialize)]
     struct SearchResponse {
         issues: Vec<Issue>,
-        #[allow(dead_code)]
-        #[serde(rename = "isLast")]
-        is_last: Option<bool>,
-        #[allow(dead_code)]
         #[serde(rename = "nextPageToken")]
         next_page_token: Option<String>,
     }
 
-    let max_results = limit.min(1000);
-    let query = format!(
-        "/rest/api/3/search/jql?jql={}&maxResults={}&fields=key,summary,status,assignee,issuetype",
-        urlencoding::encode(&final_jql),
-        max_results
-    );
+    let mut fields = String::from("key,summary,status,assignee,issuetype,priority,labels,updated");
+    if let Some(search) = text_search {
+        for field in search.fields {
+            match field {
+                TextField::Summary => {}
+                TextField::Description => fields.push_str(",description"),
+                TextField::Comments => fields.push_str(",comment"),
+            }
+        }
+    }
+    let jql = if with_votes {
+        fields.push_str(",votes");
+        order_by_votes(jql)
+    } else {
+        jql.to_string()
+    };
 
-    let response: SearchResponse = ctx
-        .client
-        .get(&query)
-        .await
-        .context("Failed to execute search")?;
+    let issues = page
+        .collect(50, SEARCH_PAGE_SIZE, Paging::Cursor, |request| {
+            let mut query = format!(
+                "/rest/api/3/search/jql?jql={}&maxResults={}&fields={}",
+                urlencoding::encode(&jql),
+                request.size,
+                fields
+            );
+            if let Some(token) = &request.cursor {
+                query.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+            }
+            async move {
+                let response: SearchResponse = ctx
+                    .client
+                    .get(&query)
+                    .await
+                    .context("Failed to execute search")?;
+                Ok(Page::cursor(response.issues, response.next_page_token))
+            }
+        })
+        .await?;
+
+    let browse = format!("{}/browse/", ctx.client.base_url().trim_end_matches('/'));
+    let rows = issues
+        .into_iter()
+        .map(|issue| SearchRow {
+            matched_in: text_search.map(|search| matched_fields(&issue.fields, search).join(", ")),
+            summary: issue.fields.summary.unwrap_or_default(),
+            status: issue.fields.status.map(|s| s.name).unwrap_or_default(),
+            assignee: issue
+                .fields
+                .assignee
+                .map(|a| a.display_name)
+                .unwrap_or_default(),
+            issue_type: issue.fields.issuetype.map(|t| t.name).unwrap_or_default(),
+            priority: issue.fields.priority.map(|p| p.name).unwrap_or_default(),
+            labels: issue.fields.labels.join(", "),
+            updated: issue.fields.updated.unwrap_or_default(),
+            url: format!("{browse}{}", issue.key),
+            votes: with_votes.then(|| issue.fields.votes.map(|v| v.votes).unwrap_or_default()),
+            key: issue.key,
+        })
+        .collect();
 
-    if response.issues.is_empty() {
-        tracing::info!("No issues matched the provided JQL.");
-        return Ok(());
-    }
+    Ok(rows)
+}
 
-    #[derive(Serialize)]
-    struct Row<'a> {
-        key: &'a str,
-        summary: &'a str,
-        status: &'a str,
-        assignee: &'a str,
-        issue_type: &'a str,
+/// Every issue matching `jql`, with only `fields`, following the enhanced
+/// search's `nextPageToken` through all pages.
+pub async fn search_all(ctx: &JiraContext<'_>, jql: &str, fields: &[&str]) -> Result<Vec<Value>> {
+    #[derive(Deserialize)]
+    struct SearchResponse {
+        issues: Vec<Value>,
+        #[serde(rename = "nextPageToken")]
+        next_page_token: Option<String>,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .issues
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let fields = urlencoding::encode(&fields.join(",")).into_owned();
+    everything
+        .collect(
+            SEARCH_PAGE_SIZE,
+            SEARCH_PAGE_SIZE,
+            Paging::Cursor,
+            |request| {
+                let mut query = format!(
+                    "/rest/api/3/search/jql?jql={}&maxResults={}&fields={fields}",
+                    urlencoding::encode(jql),
+                    request.size,
+                );
+                if let Some(token) = &request.cursor {
+                    query.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+                }
+                async move {
+                    let response: SearchResponse = ctx
+                        .client
+                        .get(&query)
+                        .await
+                        .context("Failed to search issues")?;
+                    Ok(Page::cursor(response.issues, response.next_page_token))
+                }
+            },
+        )
+        .await
+}
+
+/// Which of the searched fields contain every term of the search text. Jira's
+/// `~` operator also stems words, so a row can match without any field listed.
+fn matched_fields(fields: &IssueFields, search: TextSearch<'_>) -> Vec<&'static str> {
+    let terms: Vec<String> = search
+        .text
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let contains_terms = |value: &str| {
+        let value = value.to_lowercase();
+        terms.iter().all(|term| value.contains(term.as_str()))
+    };
+
+    search
+        .fields
         .iter()
-        .map(|issue| Row {
-            key: issue.key.as_str(),
-            summary: issue.fields.summary.as_deref().unwrap_or(""),
-            status: issue
-                .fields
-                .status
+        .filter(|field| match field {
+            TextField::Summary => fields.summary.as_deref().is_some_and(contains_terms),
+            TextField::Description => fields
+                .description
                 .as_ref()
-                .map(|s| s.name.as_str())
-                .unwrap_or(""),
-            assignee: issue
-                .fields
-                .assignee
-                .as_ref()
-                .map(|a| a.display_name.as_str())
-                .unwrap_or(""),
-            issue_type: issue
-                .fields
-                .issuetype
-                .as_ref()
-                .map(|t| t.name.as_str())
-                .unwrap_or(""),
+                .is_some_and(|d| contains_terms(&description_text(d))),
+            TextField::Comments => fields.comment.as_ref().is_some_and(|c| {
+                c["comments"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|comment| contains_terms(&adf_to_text(&comment["body"])))
+            }),
         })
-        .collect();
+        .map(|field| field.name())
+        .collect()
+}
 
-    ctx.renderer.render(&rows)
+/// Descriptions are ADF documents on API v3, but may come back as plain strings.
+fn description_text(description: &Value) -> String {
+    match description.as_str() {
+        Some(text) => text.to_string(),
+        None => adf_to_text(description),
+    }
 }
 
 pub async fn view_issue(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
@@ -153,6 +407,13 @@ pub async fn view_issue(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
         issue_type: &'a str,
     }
 
+    let description = issue
+        .fields
+        .description
+        .as_ref()
+        .map(description_text)
+        .unwrap_or_default();
+
     let view = IssueDetails {
         key: issue.key.as_str(),
         summary: issue.fields.summary.as_deref().unwrap_or(""),
@@ -162,7 +423,7 @@ pub async fn view_issue(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
             .as_ref()
             .map(|s| s.name.as_str())
             .unwrap_or(""),
-        description: issue.fields.description.as_deref().unwrap_or(""),
+        description: &description,
         assignee: issue
             .fields
             .assignee
@@ -183,9 +444,10 @@ pub async fn view_issue(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
             .unwrap_or(""),
     };
 
-    ctx.renderer.render(&view)
+    ctx.renderer.render_with_id(&view, "key")
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_issue(
     ctx: &JiraContext<'_>,
     project: &str,
@@ -194,9 +456,36 @@ pub async fn create_issue(
     description: Option<&str>,
     assignee: Option<&str>,
     priority: Option<&str>,
+    components: &[String],
+    custom_fields: &[FieldAssignment],
+    validate: bool,
 ) -> Result<()> {
     use serde_json::json;
 
+    if validate {
+        let mut field_ids = vec!["project", "issuetype", "summary"];
+        field_ids.extend(custom_fields.iter().map(|f| f.id.as_str()));
+        if description.is_some() {
+            field_ids.push("description");
+        }
+        if assignee.is_some() {
+            field_ids.push("assignee");
+        }
+        if priority.is_some() {
+            field_ids.push("priority");
+        }
+        if !components.is_empty() {
+            field_ids.push("components");
+        }
+
+        let provided = ProvidedFields {
+            field_ids,
+            priority,
+            components,
+        };
+        validate_create(ctx, project, issue_type, &provided).await?;
+    }
+
     let mut fields = json!({
         "project": { "key": project },
         "issuetype": { "name": issue_type },
@@ -222,6 +511,17 @@ pub async fn create_issue(
         fields["priority"] = json!({ "name": pri });
     }
 
+    if !components.is_empty() {
+        fields["components"] = components
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect();
+    }
+
+    for field in custom_fields {
+        fields[&field.id] = field.value.clone();
+    }
+
     let payload = json!({ "fields": fields });
 
     #[derive(Deserialize)]
@@ -247,9 +547,58 @@ pub async fn update_issue(
     summary: Option<&str>,
     description: Option<&str>,
     priority: Option<&str>,
+    custom_fields: &[FieldAssignment],
+    dry_run: bool,
 ) -> Result<()> {
     use serde_json::json;
 
+    if dry_run {
+        let mut requested = String::from("summary,description,priority");
+        for field in custom_fields {
+            requested.push(',');
+            requested.push_str(&field.id);
+        }
+        let current: Value = ctx
+            .client
+            .get(&format!(
+                "/rest/api/3/issue/{key}?fields={}",
+                urlencoding::encode(&requested)
+            ))
+            .await
+            .with_context(|| format!("Failed to fetch issue {key}"))?;
+        let current = &current["fields"];
+
+        let mut proposed = vec![
+            (
+                "summary",
+                current["summary"].as_str().unwrap_or("").to_string(),
+                summary.map(str::to_string),
+            ),
+            (
+                "description",
+                adf_to_text(&current["description"]),
+                description.map(str::to_string),
+            ),
+            (
+                "priority",
+                current["priority"]["name"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string(),
+                priority.map(str::to_string),
+            ),
+        ];
+        for field in custom_fields {
+            proposed.push((
+                field.name.as_str(),
+                display_value(&current[&field.id]),
+                Some(field.raw.clone()),
+            ));
+        }
+        let changes = field_changes(proposed);
+        return render_diff(ctx.renderer, &format!("issue {key}"), &changes);
+    }
+
     let mut fields = json!({});
 
     if let Some(s) = summary {
@@ -271,6 +620,10 @@ pub async fn update_issue(
         fields["priority"] = json!({ "name": pri });
     }
 
+    for field in custom_fields {
+        fields[&field.id] = field.value.clone();
+    }
+
     let payload = json!({ "fields": fields });
 
     let _: Value = ctx
@@ -284,6 +637,30 @@ pub async fn update_issue(
     Ok(())
 }
 
+/// Flatten an Atlassian Document Format value to plain text, one line per block.
+pub(super) fn adf_to_text(node: &Value) -> String {
+    fn collect(node: &Value, out: &mut String) {
+        if let Some(text) = node["text"].as_str() {
+            out.push_str(text);
+        }
+        if node["type"] == "hardBreak" {
+            out.push('\n');
+        }
+        if let Some(children) = node["content"].as_array() {
+            for child in children {
+                collect(child, out);
+                if child["content"].is_array() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    collect(node, &mut out);
+    out.trim_end_matches('\n').to_string()
+}
+
 pub async fn delete_issue(ctx: &JiraContext<'_>, key: &str, force: bool) -> Result<()> {
     if !force {
         println!("⚠️  About to delete issue: {}", key);
@@ -327,7 +704,13 @@ pub async fn transition_issue(ctx: &JiraContext<'_>, key: &str, transition: &str
         .transitions
         .iter()
         .find(|t| t.name.eq_ignore_ascii_case(transition) || t.id == transition)
-        .ok_or_else(|| anyhow::anyhow!("Transition '{}' not found", transition))?;
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Transition '{}' not found. Run `jira transitions {}` to list the available ones",
+                transition,
+                key
+            )
+        })?;
 
     let payload = json!({ "transition": { "id": target.id } });
 
@@ -342,6 +725,71 @@ pub async fn transition_issue(ctx: &JiraContext<'_>, key: &str, transition: &str
     Ok(())
 }
 
+#[derive(Debug, Serialize, PartialEq)]
+struct TransitionRow {
+    id: String,
+    name: String,
+    to_status: String,
+    status_category: String,
+    required_fields: String,
+}
+
+/// List the transitions currently available on an issue, with the fields each one requires.
+pub async fn list_transitions(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    let response: Value = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/issue/{key}/transitions?expand=transitions.fields"
+        ))
+        .await
+        .with_context(|| format!("Failed to get transitions for {key}"))?;
+
+    let rows = transition_rows(&response);
+    if rows.is_empty() {
+        tracing::info!(%key, "No transitions available for issue");
+        return Ok(());
+    }
+
+    ctx.renderer.render(&rows)
+}
+
+fn transition_rows(response: &Value) -> Vec<TransitionRow> {
+    let Some(transitions) = response["transitions"].as_array() else {
+        return Vec::new();
+    };
+
+    transitions
+        .iter()
+        .map(|t| {
+            let text = |pointer: &str| {
+                t.pointer(pointer)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let mut required: Vec<String> = t["fields"]
+                .as_object()
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .filter(|(_, field)| field["required"].as_bool().unwrap_or(false))
+                        .map(|(id, field)| field["name"].as_str().unwrap_or(id).to_string())
+                        .collect()
+                })
+                .unwrap_or_default();
+            required.sort();
+
+            TransitionRow {
+                id: text("/id"),
+                name: text("/name"),
+                to_status: text("/to/name"),
+                status_category: text("/to/statusCategory/name"),
+                required_fields: required.join(", "),
+            }
+        })
+        .collect()
+}
+
 pub async fn assign_issue(ctx: &JiraContext<'_>, key: &str, assignee: &str) -> Result<()> {
     use serde_json::json;
 
@@ -374,6 +822,70 @@ pub async fn unassign_issue(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
     Ok(())
 }
 
+// Archive operations (Jira Cloud Premium/Enterprise)
+
+pub async fn archive_issues(ctx: &JiraContext<'_>, keys: &[String]) -> Result<()> {
+    let updated = set_archived(ctx, keys, true).await?;
+    println!("✅ Archived {} of {} issue(s)", updated, keys.len());
+    Ok(())
+}
+
+pub async fn restore_issues(ctx: &JiraContext<'_>, keys: &[String]) -> Result<()> {
+    let updated = set_archived(ctx, keys, false).await?;
+    println!("✅ Restored {} of {} issue(s)", updated, keys.len());
+    Ok(())
+}
+
+/// Archive or restore up to 1000 issues in one request, returning how many were updated.
+/// Issues Jira refused are reported as warnings rather than failing the batch.
+pub async fn set_archived(ctx: &JiraContext<'_>, keys: &[String], archive: bool) -> Result<usize> {
+    #[derive(Deserialize)]
+    struct ArchiveResponse {
+        #[serde(default)]
+        errors: serde_json::Map<String, Value>,
+        #[serde(rename = "numberOfIssuesUpdated", default)]
+        number_of_issues_updated: usize,
+    }
+
+    let (endpoint, action) = if archive {
+        ("archive", "archive")
+    } else {
+        ("unarchive", "restore")
+    };
+    let payload = serde_json::json!({ "issueIdsOrKeys": keys });
+
+    let response: ArchiveResponse = ctx
+        .client
+        .put(&format!("/rest/api/3/issue/{endpoint}"), &payload)
+        .await
+        .with_context(|| format!("Failed to {action} issues"))?;
+
+    for (reason, detail) in &response.errors {
+        let message = detail
+            .get("message")
+            .and_then(|v| v.as_str())
+            .unwrap_or(reason);
+        let affected = detail
+            .get("issueIdsOrKeys")
+            .and_then(|v| v.as_array())
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|k| k.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        tracing::warn!(%reason, %affected, "Could not {action} issues: {message}");
+    }
+
+    tracing::info!(
+        updated = response.number_of_issues_updated,
+        requested = keys.len(),
+        "Issue {action} request completed"
+    );
+    Ok(response.number_of_issues_updated)
+}
+
 // Watcher operations
 
 pub async fn list_watchers(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
@@ -447,6 +959,77 @@ pub async fn remove_watcher(ctx: &JiraContext<'_>, key: &str, user: &str) -> Res
     Ok(())
 }
 
+// Vote operations
+
+pub async fn vote(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    use serde_json::json;
+
+    let _: Value = ctx
+        .client
+        .post(&format!("/rest/api/3/issue/{key}/votes"), &json!({}))
+        .await
+        .with_context(|| format!("Failed to vote for {key}"))?;
+
+    tracing::info!(%key, "Vote added successfully");
+    println!("✅ Voted for {}", key);
+    Ok(())
+}
+
+pub async fn unvote(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    let _: Value = ctx
+        .client
+        .delete(&format!("/rest/api/3/issue/{key}/votes"))
+        .await
+        .with_context(|| format!("Failed to remove vote from {key}"))?;
+
+    tracing::info!(%key, "Vote removed successfully");
+    println!("✅ Removed vote from {}", key);
+    Ok(())
+}
+
+pub async fn list_voters(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct VotesResponse {
+        votes: u64,
+        #[serde(rename = "hasVoted", default)]
+        has_voted: bool,
+        #[serde(default)]
+        voters: Vec<Voter>,
+    }
+
+    #[derive(Deserialize)]
+    struct Voter {
+        #[serde(rename = "accountId")]
+        account_id: String,
+        #[serde(rename = "displayName")]
+        display_name: String,
+    }
+
+    let response: VotesResponse = ctx
+        .client
+        .get(&format!("/rest/api/3/issue/{key}/votes"))
+        .await
+        .with_context(|| format!("Failed to get voters for {key}"))?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        display_name: &'a str,
+        account_id: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = response
+        .voters
+        .iter()
+        .map(|v| Row {
+            display_name: v.display_name.as_str(),
+            account_id: v.account_id.as_str(),
+        })
+        .collect();
+
+    tracing::info!(%key, votes = response.votes, has_voted = response.has_voted, "Listed voters");
+    ctx.renderer.render(&rows)
+}
+
 // Link operations
 
 pub async fn list_links(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
@@ -513,6 +1096,10 @@ pub async fn list_comments(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
         body: Value,
         author: UserField,
         created: String,
+        #[serde(default)]
+        visibility: Option<Visibility>,
+        #[serde(default)]
+        properties: Vec<Value>,
     }
 
     #[derive(Deserialize)]
@@ -521,9 +1108,18 @@ pub async fn list_comments(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
         display_name: String,
     }
 
+    #[derive(Deserialize)]
+    struct Visibility {
+        #[serde(rename = "type")]
+        kind: String,
+        value: String,
+    }
+
     let response: CommentsResponse = ctx
         .client
-        .get(&format!("/rest/api/3/issue/{key}/comment"))
+        .get(&format!(
+            "/rest/api/3/issue/{key}/comment?expand=properties"
+        ))
         .await
         .with_context(|| format!("Failed to get comments for {key}"))?;
 
@@ -532,6 +1128,7 @@ pub async fn list_comments(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
         id: &'a str,
         author: &'a str,
         created: &'a str,
+        visibility: String,
         body_preview: String,
     }
 
@@ -539,11 +1136,20 @@ pub async fn list_comments(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
         .comments
         .iter()
         .map(|c| {
-            let preview = format!("{:?}", c.body).chars().take(50).collect::<String>();
+            let preview = adf_to_text(&c.body).chars().take(50).collect::<String>();
+            let internal = c.properties.iter().any(|p| {
+                p["key"] == JSM_COMMENT_PROPERTY && p["value"]["internal"] == Value::Bool(true)
+            });
+            let visibility = match (&c.visibility, internal) {
+                (Some(v), _) => format!("{}:{}", v.kind, v.value),
+                (None, true) => "internal".to_string(),
+                (None, false) => "public".to_string(),
+            };
             Row {
                 id: c.id.as_str(),
                 author: c.author.display_name.as_str(),
                 created: c.created.as_str(),
+                visibility,
                 body_preview: preview,
             }
         })
@@ -552,10 +1158,36 @@ pub async fn list_comments(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
     ctx.renderer.render(&rows)
 }
 
-pub async fn add_comment(ctx: &JiraContext<'_>, key: &str, body: &str) -> Result<()> {
+/// Comment property JSM uses to mark a comment as internal (agents only).
+const JSM_COMMENT_PROPERTY: &str = "sd.public.comment";
+
+/// Parse a `role:NAME` or `group:NAME` visibility restriction.
+pub fn parse_visibility(spec: &str) -> Result<Value> {
     use serde_json::json;
 
-    let payload = json!({
+    match spec.split_once(':') {
+        Some(("role", name)) if !name.trim().is_empty() => {
+            Ok(json!({ "type": "role", "value": name.trim() }))
+        }
+        Some(("group", name)) if !name.trim().is_empty() => {
+            Ok(json!({ "type": "group", "value": name.trim() }))
+        }
+        _ => Err(anyhow!(
+            "Invalid visibility '{spec}'. Use role:NAME or group:NAME"
+        )),
+    }
+}
+
+pub async fn add_comment(
+    ctx: &JiraContext<'_>,
+    key: &str,
+    body: &str,
+    visibility: Option<&str>,
+    internal: bool,
+) -> Result<()> {
+    use serde_json::json;
+
+    let mut payload = json!({
         "body": {
             "type": "doc",
             "version": 1,
@@ -566,13 +1198,24 @@ pub async fn add_comment(ctx: &JiraContext<'_>, key: &str, body: &str) -> Result
         }
     });
 
+    if let Some(spec) = visibility {
+        payload["visibility"] = parse_visibility(spec)?;
+    }
+
+    if internal {
+        payload["properties"] = json!([{
+            "key": JSM_COMMENT_PROPERTY,
+            "value": { "internal": true }
+        }]);
+    }
+
     let _: Value = ctx
         .client
         .post(&format!("/rest/api/3/issue/{key}/comment"), &payload)
         .await
         .with_context(|| format!("Failed to add comment to {key}"))?;
 
-    tracing::info!(%key, "Comment added successfully");
+    tracing::info!(%key, internal, "Comment added successfully");
     println!("✅ Added comment to: {}", key);
     Ok(())
 }
@@ -633,9 +1276,25 @@ struct IssueFields {
     #[serde(default)]
     reporter: Option<UserField>,
     #[serde(default)]
-    description: Option<String>,
+    description: Option<Value>,
+    #[serde(default)]
+    comment: Option<Value>,
     #[serde(default)]
     issuetype: Option<IssueTypeField>,
+    #[serde(default)]
+    priority: Option<PriorityField>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    updated: Option<String>,
+    #[serde(default)]
+    votes: Option<VotesField>,
+}
+
+#[derive(Deserialize)]
+struct VotesField {
+    #[serde(default)]
+    votes: u64,
 }
 
 #[derive(Deserialize)]
@@ -653,3 +1312,139 @@ struct UserField {
 struct IssueTypeField {
     name: String,
 }
+
+#[derive(Deserialize)]
+struct PriorityField {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_transition_rows() {
+        let response = json!({
+            "transitions": [
+                {
+                    "id": "31",
+                    "name": "Done",
+                    "to": { "name": "Done", "statusCategory": { "name": "Done" } },
+                    "fields": {
+                        "resolution": { "required": true, "name": "Resolution" },
+                        "comment": { "required": false, "name": "Comment" },
+                        "customfield_10010": { "required": true, "name": "Fix Notes" }
+                    }
+                },
+                { "id": "11", "name": "Start", "to": { "name": "In Progress" } }
+            ]
+        });
+
+        let rows = transition_rows(&response);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].required_fields, "Fix Notes, Resolution");
+        assert_eq!(rows[0].status_category, "Done");
+        assert_eq!(rows[1].to_status, "In Progress");
+        assert_eq!(rows[1].required_fields, "");
+        assert!(transition_rows(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_adf_to_text() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "paragraph", "content": [
+                    { "type": "text", "text": "First " },
+                    { "type": "text", "text": "line" }
+                ]},
+                { "type": "paragraph", "content": [{ "type": "text", "text": "Second" }] }
+            ]
+        });
+
+        assert_eq!(adf_to_text(&doc), "First line\nSecond");
+        assert_eq!(adf_to_text(&Value::Null), "");
+    }
+
+    #[test]
+    fn test_parse_visibility() {
+        assert_eq!(
+            parse_visibility("role:Developers").unwrap(),
+            json!({ "type": "role", "value": "Developers" })
+        );
+        assert_eq!(
+            parse_visibility("group:ops").unwrap(),
+            json!({ "type": "group", "value": "ops" })
+        );
+        assert!(parse_visibility("team:ops").is_err());
+        assert!(parse_visibility("role:").is_err());
+        assert!(parse_visibility("Developers").is_err());
+    }
+
+    #[test]
+    fn test_matched_fields() {
+        let fields: IssueFields = serde_json::from_value(json!({
+            "summary": "Login page broken",
+            "description": "Users see a Timeout after login",
+            "comment": { "comments": [
+                { "body": { "type": "doc", "content": [
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "timeout on staging" }] }
+                ]}}
+            ]}
+        }))
+        .unwrap();
+        let all = [
+            TextField::Summary,
+            TextField::Description,
+            TextField::Comments,
+        ];
+
+        let search = TextSearch {
+            text: "timeout",
+            fields: &all,
+        };
+        assert_eq!(
+            matched_fields(&fields, search),
+            vec!["description", "comments"]
+        );
+
+        let search = TextSearch {
+            text: "login TIMEOUT",
+            fields: &all,
+        };
+        assert_eq!(matched_fields(&fields, search), vec!["description"]);
+    }
+
+    #[test]
+    fn test_select_columns() {
+        let rows = vec![json!({
+            "key": "DEV-1",
+            "summary": "Login",
+            "url": "https://site/browse/DEV-1",
+            "profile": "work"
+        })];
+        let columns = vec!["key".to_string(), "url".to_string()];
+        assert_eq!(
+            select_columns(&rows, &columns).unwrap(),
+            vec![json!({ "key": "DEV-1", "url": "https://site/browse/DEV-1", "profile": "work" })]
+        );
+
+        let err = select_columns(&rows, &["sprint".to_string()]).unwrap_err();
+        assert!(err.to_string().starts_with("Unknown column 'sprint'"));
+    }
+
+    #[test]
+    fn test_order_by_votes() {
+        assert_eq!(
+            order_by_votes("project = DEV"),
+            "project = DEV ORDER BY votes DESC"
+        );
+        assert_eq!(
+            order_by_votes("project = DEV order by created"),
+            "project = DEV order by created"
+        );
+        assert!(wants_votes(&["key".to_string(), "votes".to_string()]));
+    }
+}
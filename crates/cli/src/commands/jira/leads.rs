@@ -0,0 +1,247 @@
+use anyhow::{Context, Result};
+use atlassian_cli_bulk::{BulkExecutor, PlannedOperation};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::bulk::resolve_account_id;
+use super::utils::JiraContext;
+use crate::commands::cancel;
+use crate::commands::dry_run::render_plan;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Hand project and component leadership from one user to another, for reorgs
+// where a lead leaves or a team changes hands
+
+#[derive(Deserialize)]
+struct Lead {
+    #[serde(rename = "accountId")]
+    account_id: String,
+}
+
+#[derive(Deserialize)]
+struct Project {
+    key: String,
+    #[serde(default)]
+    lead: Option<Lead>,
+}
+
+#[derive(Deserialize)]
+struct Component {
+    id: String,
+    name: String,
+    #[serde(default)]
+    lead: Option<Lead>,
+}
+
+fn led_by(lead: &Option<Lead>, account_id: &str) -> bool {
+    lead.as_ref().is_some_and(|l| l.account_id == account_id)
+}
+
+/// Keys of the projects `account_id` leads.
+fn projects_led_by(projects: Vec<Project>, account_id: &str) -> Vec<String> {
+    projects
+        .into_iter()
+        .filter(|project| led_by(&project.lead, account_id))
+        .map(|project| project.key)
+        .collect()
+}
+
+/// `(project, component ID, component name)` for the components of `project`
+/// that `account_id` leads.
+fn components_led_by(
+    project: &str,
+    components: Vec<Component>,
+    account_id: &str,
+) -> Vec<(String, String, String)> {
+    components
+        .into_iter()
+        .filter(|component| led_by(&component.lead, account_id))
+        .map(|component| (project.to_string(), component.id, component.name))
+        .collect()
+}
+
+pub async fn bulk_project_lead(
+    ctx: &JiraContext<'_>,
+    from_user: &str,
+    to_user: &str,
+    projects: &[String],
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let from = resolve_account_id(ctx, from_user).await?;
+    let to = resolve_account_id(ctx, to_user).await?;
+
+    let keys = projects_led_by(list_projects(ctx, projects).await?, &from);
+
+    if keys.is_empty() {
+        println!("{from_user} leads no projects");
+        return Ok(());
+    }
+    if !dry_run {
+        println!("Found {} projects led by {from_user}", keys.len());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let operations = executor
+        .run_planned(
+            keys,
+            |key| PlannedOperation::new(key, "change project lead", to_user),
+            move |key| {
+                let client = client.clone();
+                let payload = json!({ "leadAccountId": to });
+                async move {
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/project/{key}"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to change lead of project {key}"))?;
+                    tracing::info!(project = %key, "Project lead changed");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
+
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
+    println!("✅ Project leads changed from {from_user} to {to_user}");
+    Ok(())
+}
+
+pub async fn bulk_component_lead(
+    ctx: &JiraContext<'_>,
+    from_user: &str,
+    to_user: &str,
+    projects: &[String],
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let from = resolve_account_id(ctx, from_user).await?;
+    let to = resolve_account_id(ctx, to_user).await?;
+
+    let mut components = Vec::new();
+    for project in list_projects(ctx, projects).await? {
+        let key = project.key;
+        let found: Vec<Component> = ctx
+            .client
+            .get(&format!("/rest/api/3/project/{key}/components"))
+            .await
+            .with_context(|| format!("Failed to list components for project {key}"))?;
+        components.extend(components_led_by(&key, found, &from));
+    }
+
+    if components.is_empty() {
+        println!("{from_user} leads no components");
+        return Ok(());
+    }
+    if !dry_run {
+        println!("Found {} components led by {from_user}", components.len());
+    }
+
+    let executor = BulkExecutor::new(concurrency, dry_run).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    let operations = executor
+        .run_planned(
+            components,
+            |(project, _, name)| {
+                PlannedOperation::new(
+                    format!("{project}/{name}"),
+                    "change component lead",
+                    to_user,
+                )
+            },
+            move |(project, id, name)| {
+                let client = client.clone();
+                let payload = json!({ "leadAccountId": to });
+                async move {
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/component/{id}"), &payload)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to change lead of component {project}/{name}")
+                        })?;
+                    tracing::info!(%project, component = %name, "Component lead changed");
+                    Ok(())
+                }
+            },
+        )
+        .await?;
+
+    if dry_run {
+        return render_plan(ctx.renderer, &operations);
+    }
+    println!("✅ Component leads changed from {from_user} to {to_user}");
+    Ok(())
+}
+
+/// The given projects, or every project visible to the user when none are given.
+async fn list_projects(ctx: &JiraContext<'_>, keys: &[String]) -> Result<Vec<Project>> {
+    #[derive(Deserialize)]
+    struct ProjectsResponse {
+        #[serde(default)]
+        values: Vec<Project>,
+        #[serde(rename = "isLast", default = "default_true")]
+        is_last: bool,
+    }
+
+    fn default_true() -> bool {
+        true
+    }
+
+    let keys: String = keys
+        .iter()
+        .map(|key| format!("&keys={}", urlencoding::encode(key)))
+        .collect();
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    everything
+        .collect(50, 50, Paging::Offset, |request| {
+            let path = format!(
+                "/rest/api/3/project/search?expand=lead&startAt={}&maxResults={}{keys}",
+                request.start, request.size
+            );
+            async move {
+                let response: ProjectsResponse = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .context("Failed to list projects")?;
+                Ok(Page::offset(response.values, !response.is_last))
+            }
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_projects_led_by() {
+        let projects: Vec<Project> = serde_json::from_value(json!([
+            { "key": "DEV", "lead": { "accountId": "alice" } },
+            { "key": "OPS", "lead": { "accountId": "bob" } },
+            { "key": "OLD" },
+            { "key": "WEB", "lead": { "accountId": "alice" } }
+        ]))
+        .unwrap();
+        assert_eq!(projects_led_by(projects, "alice"), vec!["DEV", "WEB"]);
+    }
+
+    #[test]
+    fn test_components_led_by() {
+        let components: Vec<Component> = serde_json::from_value(json!([
+            { "id": "10", "name": "API", "lead": { "accountId": "alice" } },
+            { "id": "11", "name": "UI", "lead": { "accountId": "bob" } },
+            { "id": "12", "name": "Docs", "lead": null }
+        ]))
+        .unwrap();
+        assert_eq!(
+            components_led_by("DEV", components, "alice"),
+            vec![("DEV".to_string(), "10".to_string(), "API".to_string())]
+        );
+    }
+}
@@ -4,15 +4,48 @@ use atlassian_cli_output::OutputRenderer;
 use clap::{Args, Subcommand};
 
 // Submodules
+mod adf;
 mod audit;
 mod automation;
+mod boards;
 mod bulk;
+mod components;
+mod csv_import;
+mod cycle_time;
+mod editor;
+mod export;
+mod field_map;
+mod field_select;
 mod fields_workflows;
+mod graph;
+mod groups;
 mod issues;
+mod leads;
+mod plans;
+mod priorities;
 mod projects;
+mod publish;
+mod quick;
+mod recurring;
+mod release;
+mod replace;
+mod reports;
+mod sprints;
+mod timesheet;
+mod transition_path;
+mod trash;
 pub mod utils;
+mod validation;
 mod webhooks;
 
+pub use bulk::ImportIssue;
+pub use recurring::IssueTemplate;
+
+use super::dates;
+use super::entity_url;
+use super::multi_profile::{self, ProfileTarget};
+use super::pagination::PaginationArgs;
+use super::webhook_events;
 use utils::JiraContext;
 
 #[derive(Args, Debug, Clone)]
@@ -54,36 +87,75 @@ enum JiraCommands {
         #[arg(short = 'p', long)]
         project: Option<String>,
 
-        /// Free text search in summary
+        /// Free text search (in summary unless --in is given)
         #[arg(long)]
         text: Option<String>,
 
+        /// Fields to search with --text (comma-separated)
+        #[arg(
+            long = "in",
+            value_enum,
+            value_delimiter = ',',
+            requires = "text",
+            default_value = "summary"
+        )]
+        text_in: Vec<issues::TextField>,
+
         /// Display generated JQL query
         #[arg(long)]
         show_query: bool,
 
-        /// Maximum number of issues to return
-        #[arg(long, default_value_t = 50)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
+
+        /// Fetch and show exactly these fields (ids or names, comma-separated)
+        #[arg(long, value_delimiter = ',', conflicts_with = "text_in")]
+        fields: Vec<String>,
+
+        /// Show only these columns: key, summary, status, assignee, issue_type,
+        /// priority, labels, updated, url, matched_in, votes (comma-separated).
+        /// With votes, results are ordered by vote count unless the JQL has an ORDER BY
+        #[arg(long, value_delimiter = ',', conflicts_with = "fields")]
+        columns: Vec<String>,
+
+        /// Write the results as a table to a Confluence page (created, or updated if it exists)
+        #[arg(long, requires_all = ["space", "title"])]
+        publish_to_confluence: bool,
+
+        /// Confluence space key to publish to
+        #[arg(long, requires = "publish_to_confluence")]
+        space: Option<String>,
+
+        /// Title of the Confluence page to create or update
+        #[arg(long, requires = "publish_to_confluence")]
+        title: Option<String>,
+
+        /// Parent page ID when the page is created
+        #[arg(long, requires = "publish_to_confluence")]
+        parent: Option<String>,
     },
 
     /// Fetch a single issue
     Get {
         /// Issue key (e.g. DEV-123)
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
+        /// Fetch and show exactly these fields (ids or names, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
     },
 
     /// Create a new issue
     Create {
         /// Project key
-        #[arg(long)]
-        project: String,
+        #[arg(long, required_unless_present = "editor")]
+        project: Option<String>,
         /// Issue type (e.g. Task, Bug, Story)
-        #[arg(long)]
-        issue_type: String,
+        #[arg(long, required_unless_present = "editor")]
+        issue_type: Option<String>,
         /// Issue summary
-        #[arg(long)]
-        summary: String,
+        #[arg(long, required_unless_present = "editor")]
+        summary: Option<String>,
         /// Issue description
         #[arg(long)]
         description: Option<String>,
@@ -93,11 +165,39 @@ enum JiraCommands {
         /// Priority name (e.g. High, Medium, Low)
         #[arg(long)]
         priority: Option<String>,
+        /// Component names (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        components: Vec<String>,
+        /// Check required fields, priority, and components against createmeta before creating
+        #[arg(long)]
+        validate: bool,
+        /// Set any field by name or ID, e.g. "Story Points=5" (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        field: Vec<String>,
+        /// Compose the issue in $EDITOR, prefilled with any other options given
+        #[arg(long)]
+        editor: bool,
+    },
+
+    /// Create an issue from one line: "PROJ: Summary #type @user !priority +label"
+    Quick {
+        /// Summary with optional PROJ: prefix and #type, @assignee (or @me), !priority, +label markers
+        text: String,
+        /// Project key when the text has no PROJ: prefix
+        #[arg(long)]
+        project: Option<String>,
+        /// Issue type when the text has no #type
+        #[arg(long, default_value = "Task")]
+        issue_type: String,
+        /// Show the parsed and resolved fields without creating the issue
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Update an existing issue
     Update {
         /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// New summary
         #[arg(long)]
@@ -108,29 +208,51 @@ enum JiraCommands {
         /// New priority
         #[arg(long)]
         priority: Option<String>,
+        /// Set any field by name or ID, e.g. "Story Points=5" (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        field: Vec<String>,
+        /// Show a diff of what would change without updating the issue
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Delete an issue
     Delete {
         /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
     },
 
+    /// List the transitions available on an issue and the fields they require
+    Transitions {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
+
     /// Transition an issue to a new status
     Transition {
         /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// Transition name or ID
+        #[arg(long, required_unless_present = "to", conflicts_with = "to")]
+        transition: Option<String>,
+        /// Target status name, reached by the transition leading to it
         #[arg(long)]
-        transition: String,
+        to: Option<String>,
+        /// Go through intermediate statuses, taking the shortest path in the workflow
+        #[arg(long, requires = "to")]
+        through_auto: bool,
     },
 
     /// Assign issue to user
     Assign {
         /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// User account ID or email
         #[arg(long)]
@@ -140,9 +262,64 @@ enum JiraCommands {
     /// Unassign issue
     Unassign {
         /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
+
+    /// Vote for an issue
+    Vote {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
+
+    /// Remove your vote from an issue
+    Unvote {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
+
+    /// List who voted for an issue
+    Voters {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
     },
 
+    /// Archive issues
+    Archive {
+        /// Issue keys
+        #[arg(required = true, value_parser = entity_url::issue_key)]
+        keys: Vec<String>,
+    },
+
+    /// Restore archived issues
+    Restore {
+        /// Issue keys
+        #[arg(required = true, value_parser = entity_url::issue_key)]
+        keys: Vec<String>,
+    },
+
+    /// Export the issue link graph around an issue (Graphviz or Mermaid)
+    Graph {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+        /// How many links away from the issue to follow
+        #[arg(long, default_value_t = 2)]
+        depth: usize,
+        /// Output format: dot or mermaid
+        #[arg(long, default_value = "dot")]
+        format: String,
+        /// Stop after fetching this many issues
+        #[arg(long, default_value_t = 200)]
+        max_issues: usize,
+        /// Output file path (defaults to stdout)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+
     /// Manage issue watchers
     #[command(subcommand)]
     Watchers(WatcherCommands),
@@ -171,6 +348,10 @@ enum JiraCommands {
     #[command(subcommand)]
     Roles(RoleCommands),
 
+    /// Manage user groups
+    #[command(subcommand)]
+    Group(GroupCommands),
+
     /// Manage custom fields
     #[command(subcommand)]
     Fields(FieldCommands),
@@ -179,6 +360,10 @@ enum JiraCommands {
     #[command(subcommand)]
     Workflows(WorkflowCommands),
 
+    /// Manage priorities and priority schemes
+    #[command(subcommand)]
+    Priorities(PriorityCommands),
+
     /// Bulk operations
     #[command(subcommand)]
     Bulk(BulkCommands),
@@ -194,20 +379,56 @@ enum JiraCommands {
     /// Audit log access
     #[command(subcommand)]
     Audit(AuditCommands),
+
+    /// Advanced Roadmaps plans
+    #[command(subcommand)]
+    Plan(PlanCommands),
+
+    /// Agile boards
+    #[command(subcommand)]
+    Board(BoardCommands),
+
+    /// Sprint reports
+    #[command(subcommand)]
+    Sprint(SprintCommands),
+
+    /// Release workflows
+    #[command(subcommand)]
+    Release(ReleaseCommands),
+
+    /// Issues created from a template on a cron schedule
+    #[command(subcommand)]
+    Recurring(RecurringCommands),
+
+    /// Deleted issues in the recycle bin, where the site has one
+    #[command(subcommand)]
+    Trash(TrashCommands),
+
+    /// Backlog reports
+    #[command(subcommand)]
+    Report(ReportCommands),
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum WatcherCommands {
     /// List watchers for an issue
-    List { key: String },
+    List {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
     /// Add watcher to an issue
     Add {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// User account ID or email
         user: String,
     },
     /// Remove watcher from an issue
     Remove {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// User account ID or email
         user: String,
@@ -217,12 +438,18 @@ enum WatcherCommands {
 #[derive(Subcommand, Debug, Clone)]
 enum LinkCommands {
     /// List links for an issue
-    List { key: String },
+    List {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
     /// Create a link between two issues
     Create {
         /// Source issue key
+        #[arg(value_parser = entity_url::issue_key)]
         from: String,
         /// Target issue key
+        #[arg(value_parser = entity_url::issue_key)]
         to: String,
         /// Link type (e.g. blocks, relates-to)
         #[arg(long)]
@@ -238,13 +465,25 @@ enum LinkCommands {
 #[derive(Subcommand, Debug, Clone)]
 enum CommentCommands {
     /// List comments on an issue
-    List { key: String },
+    List {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
+        key: String,
+    },
     /// Add a comment to an issue
     Add {
+        /// Issue key
+        #[arg(value_parser = entity_url::issue_key)]
         key: String,
         /// Comment body
         #[arg(long)]
         body: String,
+        /// Restrict the comment to a project role or group (role:NAME or group:NAME)
+        #[arg(long)]
+        visibility: Option<String>,
+        /// Mark the comment internal (JSM agents only)
+        #[arg(long)]
+        internal: bool,
     },
     /// Update a comment
     Update {
@@ -264,7 +503,11 @@ enum CommentCommands {
 #[derive(Subcommand, Debug, Clone)]
 enum ProjectCommands {
     /// List all projects
-    List,
+    List {
+        /// Include archived projects
+        #[arg(long)]
+        all: bool,
+    },
     /// Get project details
     Get {
         /// Project key
@@ -287,6 +530,9 @@ enum ProjectCommands {
         /// Description
         #[arg(long)]
         description: Option<String>,
+        /// Succeed and show the existing project if the key is already taken
+        #[arg(long)]
+        if_not_exists: bool,
     },
     /// Update project
     Update {
@@ -310,6 +556,32 @@ enum ProjectCommands {
         #[arg(long)]
         force: bool,
     },
+    /// Archive project
+    Archive {
+        /// Project key
+        key: String,
+    },
+    /// Restore an archived project
+    Restore {
+        /// Project key
+        key: String,
+    },
+    /// Project category operations
+    #[command(subcommand)]
+    Category(ProjectCategoryCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ProjectCategoryCommands {
+    /// List project categories
+    List,
+    /// Move a project into a category
+    Set {
+        /// Project key
+        key: String,
+        /// Category name or ID
+        category: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -355,6 +627,44 @@ enum ComponentCommands {
         /// Component ID
         id: String,
     },
+    /// Set who new issues in a component are assigned to
+    SetDefaultAssignee {
+        /// Component ID
+        id: String,
+        /// Default assignee
+        #[arg(long, value_enum, ignore_case = true)]
+        assignee_type: components::AssigneeType,
+    },
+    /// Create and update a project's components to match a YAML file
+    Sync {
+        /// YAML list of components (name, description, lead, assignee_type)
+        #[arg(long)]
+        from_file: std::path::PathBuf,
+        /// Project key
+        #[arg(long)]
+        project: String,
+        /// Delete components that are not in the file
+        #[arg(long)]
+        prune: bool,
+        /// Show the planned changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create components from a CSV file, skipping names the project already has
+    Import {
+        /// Project key
+        #[arg(long)]
+        project: String,
+        /// CSV with a header row: name, and optionally description, lead, assignee_type
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Show the components that would be created without creating them
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -380,18 +690,21 @@ enum VersionCommands {
         /// Description
         #[arg(long)]
         description: Option<String>,
-        /// Start date (YYYY-MM-DD)
-        #[arg(long)]
-        start_date: Option<String>,
-        /// Release date (YYYY-MM-DD)
-        #[arg(long)]
-        release_date: Option<String>,
+        /// Start date (YYYY-MM-DD, or relative such as +2w or 'next monday')
+        #[arg(long, value_parser = dates::parse_date)]
+        start_date: Option<chrono::NaiveDate>,
+        /// Release date (YYYY-MM-DD, or relative such as +2w or 'next friday')
+        #[arg(long, value_parser = dates::parse_date)]
+        release_date: Option<chrono::NaiveDate>,
         /// Mark as released
         #[arg(long)]
         released: bool,
         /// Mark as archived
         #[arg(long)]
         archived: bool,
+        /// Succeed and show the existing version if the name is already used in the project
+        #[arg(long)]
+        if_not_exists: bool,
     },
     /// Update a version
     Update {
@@ -422,6 +735,22 @@ enum VersionCommands {
         /// Target version ID
         to: String,
     },
+    /// Create versions from a CSV file, skipping names the project already has
+    Import {
+        /// Project key
+        #[arg(long)]
+        project: String,
+        /// CSV with a header row: name, and optionally description, start_date,
+        /// release_date, released, archived
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Show the versions that would be created without creating them
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -467,10 +796,46 @@ enum RoleCommands {
     },
 }
 
+#[derive(Subcommand, Debug, Clone)]
+enum GroupCommands {
+    /// List groups with their member counts
+    List {
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// List members of a group
+    Members {
+        /// Group name
+        group: String,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Add a user to a group
+    AddUser {
+        /// Group name
+        group: String,
+        /// User account ID or email
+        #[arg(long)]
+        user: String,
+    },
+    /// Remove a user from a group
+    RemoveUser {
+        /// Group name
+        group: String,
+        /// User account ID or email
+        #[arg(long)]
+        user: String,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 enum FieldCommands {
     /// List all fields
-    List,
+    List {
+        /// Project key; lists the project's own fields when it is team-managed
+        #[arg(long)]
+        project: Option<String>,
+    },
     /// Get field details
     Get {
         /// Field ID
@@ -493,6 +858,8 @@ enum FieldCommands {
         /// Field ID
         id: String,
     },
+    /// Refresh the cached field name to ID mapping for this profile
+    RefreshCache,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -512,33 +879,119 @@ enum WorkflowCommands {
         #[arg(long)]
         output: Option<String>,
     },
+    /// Create a workflow from an exported JSON file
+    Create {
+        /// File written by `workflows export`
+        #[arg(long)]
+        from_file: std::path::PathBuf,
+        /// Name for the new workflow (defaults to the name in the file)
+        #[arg(long)]
+        name: Option<String>,
+        /// Print the request without creating the workflow
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Use a workflow for an issue type in a project's workflow scheme
+    Assign {
+        /// Workflow name
+        #[arg(long)]
+        workflow: String,
+        /// Project key
+        #[arg(long)]
+        project: String,
+        /// Issue type name or ID
+        #[arg(long)]
+        issue_type: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
-enum BulkCommands {
-    /// Bulk transition issues
-    Transition {
-        /// JQL query to select issues
+enum PriorityCommands {
+    /// List priorities
+    List,
+    /// Create a priority
+    Create {
+        /// Priority name
         #[arg(long)]
-        jql: String,
-        /// Transition name or ID
+        name: String,
+        /// Status color as hex, e.g. #d04437
         #[arg(long)]
-        transition: String,
-        /// Dry run mode
+        color: String,
+        /// Description
         #[arg(long)]
-        dry_run: bool,
-        /// Concurrency level
-        #[arg(long, default_value_t = 4)]
-        concurrency: usize,
+        description: Option<String>,
     },
-    /// Bulk assign issues
+    /// Update a priority
+    Update {
+        /// Priority name or ID
+        priority: String,
+        /// New name
+        #[arg(long)]
+        name: Option<String>,
+        /// New status color as hex
+        #[arg(long)]
+        color: Option<String>,
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Delete a priority
+    Delete {
+        /// Priority name or ID
+        priority: String,
+        /// Skip confirmation
+        #[arg(long)]
+        force: bool,
+    },
+    /// Manage priority schemes
+    #[command(subcommand)]
+    Scheme(PrioritySchemeCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum PrioritySchemeCommands {
+    /// List priority schemes with their priorities and projects
+    List {
+        /// Only show the scheme used by this project
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Create a priority scheme
+    Create {
+        /// Scheme name
+        #[arg(long)]
+        name: String,
+        /// Priorities in the scheme (names or IDs, comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        priorities: Vec<String>,
+        /// Priority new issues get (name or ID)
+        #[arg(long)]
+        default: String,
+        /// Description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Make a project use a priority scheme
     Assign {
+        /// Scheme name or ID
+        #[arg(long)]
+        scheme: String,
+        /// Project key
+        #[arg(long)]
+        project: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BulkWatcherCommands {
+    /// Subscribe users to every issue matching a JQL query
+    Add {
         /// JQL query to select issues
         #[arg(long)]
         jql: String,
-        /// Assignee account ID
-        #[arg(long)]
-        assignee: String,
+        /// Users to add (emails or account IDs, comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        users: Vec<String>,
         /// Dry run mode
         #[arg(long)]
         dry_run: bool,
@@ -546,17 +999,14 @@ enum BulkCommands {
         #[arg(long, default_value_t = 4)]
         concurrency: usize,
     },
-    /// Bulk label operations
-    Label {
+    /// Unsubscribe users from every issue matching a JQL query
+    Remove {
         /// JQL query to select issues
         #[arg(long)]
         jql: String,
-        /// Action: add, remove, or set
-        #[arg(long)]
-        action: String,
-        /// Labels to apply
-        #[arg(long, value_delimiter = ',')]
-        labels: Vec<String>,
+        /// Users to remove (emails or account IDs, comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        users: Vec<String>,
         /// Dry run mode
         #[arg(long)]
         dry_run: bool,
@@ -564,7 +1014,125 @@ enum BulkCommands {
         #[arg(long, default_value_t = 4)]
         concurrency: usize,
     },
-    /// Export issues to file
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BulkCommands {
+    /// Bulk transition issues
+    Transition {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Transition name or ID
+        #[arg(long)]
+        transition: String,
+        /// Estimate watcher notifications (watchers per matching issue) before running
+        #[arg(long)]
+        notify_preview: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Bulk assign issues
+    Assign {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Assignee account ID
+        #[arg(long)]
+        assignee: String,
+        /// Estimate watcher notifications (watchers per matching issue) before running
+        #[arg(long)]
+        notify_preview: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Bulk label operations
+    Label {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Action: add, remove, or set
+        #[arg(long)]
+        action: String,
+        /// Labels to apply
+        #[arg(long, value_delimiter = ',')]
+        labels: Vec<String>,
+        /// Estimate watcher notifications (watchers per matching issue) before running
+        #[arg(long)]
+        notify_preview: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Add the same comment to every matching issue
+    Comment {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Comment text
+        #[arg(long)]
+        body: String,
+        /// Treat the body as Markdown
+        #[arg(long)]
+        markdown: bool,
+        /// Do not notify watchers
+        #[arg(long)]
+        no_notify: bool,
+        /// Estimate watcher notifications (watchers per matching issue) before running
+        #[arg(long)]
+        notify_preview: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Add or remove watchers on every matching issue
+    #[command(subcommand)]
+    Watchers(BulkWatcherCommands),
+    /// Find and replace text in a field of every matching issue
+    Replace {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Text field to edit (name or ID, e.g. description or customfield_10050)
+        #[arg(long)]
+        field: String,
+        /// Text to find
+        #[arg(long)]
+        find: String,
+        /// Replacement text (may use $1 capture groups with --regex)
+        #[arg(long)]
+        replace: String,
+        /// Treat --find as a regular expression
+        #[arg(long)]
+        regex: bool,
+        /// Do not notify watchers
+        #[arg(long)]
+        no_notify: bool,
+        /// Estimate watcher notifications (watchers per matching issue) before running
+        #[arg(long)]
+        notify_preview: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Export issues to file
     Export {
         /// JQL query to select issues
         #[arg(long)]
@@ -594,6 +1162,76 @@ enum BulkCommands {
         #[arg(long, default_value_t = 4)]
         concurrency: usize,
     },
+    /// Reassign a departing user's open issues to someone else
+    Reassign {
+        /// User to take work from (email or account ID)
+        #[arg(long)]
+        from_user: String,
+        /// User to hand work to (email or account ID)
+        #[arg(long)]
+        to_user: String,
+        /// Only reassign issues in these projects (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        projects: Vec<String>,
+        /// Also move open issues the user reported
+        #[arg(long)]
+        include_reporter: bool,
+        /// Also transfer ownership of the user's filters. Filter subscriptions are
+        /// not moved: Jira has no REST API to edit them
+        #[arg(long)]
+        include_filters: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Hand over the lead of every project a user leads
+    ProjectLead {
+        /// Current lead (email or account ID)
+        #[arg(long)]
+        from_user: String,
+        /// New lead (email or account ID)
+        #[arg(long)]
+        to_user: String,
+        /// Only look at these projects (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        projects: Vec<String>,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Hand over the lead of every component a user leads
+    ComponentLead {
+        /// Current lead (email or account ID)
+        #[arg(long)]
+        from_user: String,
+        /// New lead (email or account ID)
+        #[arg(long)]
+        to_user: String,
+        /// Only look at these projects (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        projects: Vec<String>,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Archive issues matching a JQL query
+    Archive {
+        /// JQL query to select issues
+        #[arg(long)]
+        jql: String,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -660,6 +1298,8 @@ enum AutomationCommands {
 enum WebhookCommands {
     /// List all webhooks
     List,
+    /// List the event names accepted by --events
+    Events,
     /// Get webhook details
     Get {
         /// Webhook ID
@@ -723,33 +1363,318 @@ enum WebhookCommands {
         /// Webhook ID
         webhook_id: i64,
     },
+    /// Verify a received payload against its X-Hub-Signature header
+    Verify {
+        /// Webhook secret
+        #[arg(long, env = "JIRA_WEBHOOK_SECRET", hide_env_values = true)]
+        secret: String,
+        /// File containing the raw request body
+        #[arg(long)]
+        payload: std::path::PathBuf,
+        /// Signature header value (sha256=<hex>)
+        #[arg(long, required_unless_present = "print_curl_example")]
+        signature: Option<String>,
+        /// Print a curl command that sends the payload with a valid signature
+        #[arg(long)]
+        print_curl_example: bool,
+        /// Receiver URL used by --print-curl-example
+        #[arg(long, default_value = "http://localhost:8080/webhook")]
+        url: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
-enum AuditCommands {
-    /// List audit records
+enum RecurringCommands {
+    /// Create issues from a YAML template on a cron schedule (local time)
+    Add {
+        /// YAML template: project, summary and optionally issue_type, description,
+        /// assignee, priority, labels, components; `{date}` is replaced by the run date
+        /// (see `schema template`)
+        #[arg(long)]
+        template: std::path::PathBuf,
+        /// Five-field cron expression, e.g. "0 9 * * MON"
+        #[arg(long)]
+        cron: String,
+        /// Name used in the recurring-<name> marker label (defaults to the template file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List recurring issues with their next due time
+    List,
+    /// Stop creating a recurring issue
+    Remove {
+        /// Recurring issue name
+        name: String,
+    },
+    /// Create the issues that are due and exit (for cron or CI)
+    Run {
+        /// Show what would be created without creating anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum TrashCommands {
+    /// List deleted issues that can still be restored
     List {
-        /// Start date (YYYY-MM-DD)
+        /// Only issues deleted from this project
+        #[arg(long)]
+        project: Option<String>,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Restore deleted issues
+    Restore {
+        /// Issue keys
+        #[arg(required = true, value_parser = entity_url::issue_key)]
+        keys: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ReleaseCommands {
+    /// Create a version, assign it to matching issues, and optionally release it
+    Create {
+        /// Project key
         #[arg(long)]
-        from: Option<String>,
-        /// End date (YYYY-MM-DD)
+        project: String,
+        /// Version name
         #[arg(long)]
-        to: Option<String>,
-        /// Filter by event type
+        name: String,
+        /// JQL query selecting the issues to include
         #[arg(long)]
-        filter: Option<String>,
-        /// Maximum number of records
+        from_jql: String,
+        /// Description
         #[arg(long)]
-        limit: Option<usize>,
+        description: Option<String>,
+        /// Mark the version as released once issues are assigned
+        #[arg(long)]
+        release: bool,
+        /// Release date (YYYY-MM-DD or relative such as yesterday, defaults to today)
+        #[arg(long, requires = "release", value_parser = dates::parse_date)]
+        release_date: Option<chrono::NaiveDate>,
+        /// Release even when the version still has unresolved issues
+        #[arg(long, requires = "release")]
+        allow_unresolved: bool,
+        /// Dry run mode
+        #[arg(long)]
+        dry_run: bool,
+        /// Concurrency level
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
     },
-    /// Export audit records
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum PlanCommands {
+    /// List plans
+    List {
+        /// Include archived plans
+        #[arg(long)]
+        include_archived: bool,
+        /// Include trashed plans
+        #[arg(long)]
+        include_trashed: bool,
+    },
+    /// Export a plan snapshot (configuration and teams)
     Export {
-        /// Start date (YYYY-MM-DD)
+        /// Plan ID
+        plan_id: String,
+        /// Export format: json
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Output file path (defaults to stdout)
         #[arg(long)]
-        from: Option<String>,
-        /// End date (YYYY-MM-DD)
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BoardCommands {
+    /// Show columns, status mapping, estimation and quick filters of a board
+    Config {
+        /// Board ID
+        board_id: u64,
+    },
+    /// Manage quick filters
+    #[command(subcommand)]
+    QuickFilter(BoardQuickFilterCommands),
+    /// Manage JQL swimlanes
+    #[command(subcommand)]
+    Swimlane(BoardSwimlaneCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BoardQuickFilterCommands {
+    /// List the quick filters of a board
+    List {
+        /// Board ID
+        board_id: u64,
+    },
+    /// Add a quick filter to a board
+    Create {
+        /// Board ID
+        board_id: u64,
+        /// Filter name
         #[arg(long)]
-        to: Option<String>,
+        name: String,
+        /// JQL the filter applies
+        #[arg(long)]
+        jql: String,
+        /// Filter description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Remove a quick filter from a board
+    Delete {
+        /// Board ID
+        board_id: u64,
+        /// Quick filter ID
+        filter_id: u64,
+        /// Skip confirmation
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum BoardSwimlaneCommands {
+    /// List the JQL swimlanes of a board and how it groups swimlanes
+    List {
+        /// Board ID
+        board_id: u64,
+    },
+    /// Add a JQL swimlane to a board
+    Create {
+        /// Board ID
+        board_id: u64,
+        /// Swimlane name
+        #[arg(long)]
+        name: String,
+        /// JQL selecting the issues in the swimlane
+        #[arg(long)]
+        jql: String,
+        /// Swimlane description
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Remove a JQL swimlane from a board
+    Delete {
+        /// Board ID
+        board_id: u64,
+        /// Swimlane ID
+        swimlane_id: u64,
+        /// Skip confirmation
+        #[arg(long)]
+        force: bool,
+    },
+    /// Choose how a board groups issues into swimlanes
+    Strategy {
+        /// Board ID
+        board_id: u64,
+        /// Grouping (custom uses the JQL swimlanes)
+        strategy: boards::SwimlaneStrategy,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum SprintCommands {
+    /// Committed vs completed estimates for a board's most recent closed sprints
+    Report {
+        /// Board ID
+        board_id: u64,
+        /// Number of closed sprints to include
+        #[arg(long, default_value_t = 5)]
+        last: usize,
+        /// Estimate field (defaults to the board's estimation field, e.g. story points)
+        #[arg(long)]
+        points_field: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ReportCommands {
+    /// Group issues by age since creation, with the oldest issues per bucket
+    Aging {
+        /// JQL query selecting the issues to report on
+        #[arg(long)]
+        jql: String,
+        /// Bucket boundaries in days (comma-separated)
+        #[arg(long, value_delimiter = ',', default_values_t = [7, 14, 30])]
+        buckets: Vec<i64>,
+        /// Number of oldest issues listed per bucket
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+        /// Also write every issue with its age and bucket to a CSV file
+        #[arg(long)]
+        csv: Option<std::path::PathBuf>,
+    },
+    /// Cycle and lead time statistics from status changes in issue changelogs
+    CycleTime {
+        /// JQL query selecting the issues to report on
+        #[arg(long)]
+        jql: String,
+        /// Status that starts the cycle (first move into it)
+        #[arg(long, default_value = "In Progress")]
+        from_status: String,
+        /// Status that ends the cycle (last move into it)
+        #[arg(long, default_value = "Done")]
+        to_status: String,
+        /// Percentiles to report (comma-separated)
+        #[arg(long, value_delimiter = ',', default_values_t = [50, 85, 95])]
+        percentiles: Vec<u8>,
+        /// Also write every completed issue with its times to a CSV file
+        #[arg(long)]
+        csv: Option<std::path::PathBuf>,
+    },
+    /// Hours logged per user, issue and/or day, from the worklogs of a JQL scope
+    Timesheet {
+        /// Only count work logged by these users (account IDs or emails, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        users: Vec<String>,
+        /// First day of the range (YYYY-MM-DD, or relative such as -7d or 'last monday')
+        #[arg(long, value_parser = dates::parse_date)]
+        from: chrono::NaiveDate,
+        /// Last day of the range, inclusive (YYYY-MM-DD, or relative such as today)
+        #[arg(long, value_parser = dates::parse_date)]
+        to: chrono::NaiveDate,
+        /// JQL limiting which issues are included (defaults to any issue with work in the range)
+        #[arg(long)]
+        jql: Option<String>,
+        /// Dimensions to total by (comma-separated: user, issue, day)
+        #[arg(long, value_delimiter = ',', default_values = ["user", "day"])]
+        group_by: Vec<timesheet::GroupBy>,
+        /// Also write the totals to a CSV file
+        #[arg(long)]
+        csv: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum AuditCommands {
+    /// List audit records
+    List {
+        /// Start date (YYYY-MM-DD, or relative such as -7d or 'last monday')
+        #[arg(long, value_parser = dates::parse_date)]
+        from: Option<chrono::NaiveDate>,
+        /// End date (YYYY-MM-DD, or relative such as today)
+        #[arg(long, value_parser = dates::parse_date)]
+        to: Option<chrono::NaiveDate>,
+        /// Filter by event type
+        #[arg(long)]
+        filter: Option<String>,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Export audit records
+    Export {
+        /// Start date (YYYY-MM-DD, or relative such as -7d or 'last monday')
+        #[arg(long, value_parser = dates::parse_date)]
+        from: Option<chrono::NaiveDate>,
+        /// End date (YYYY-MM-DD, or relative such as today)
+        #[arg(long, value_parser = dates::parse_date)]
+        to: Option<chrono::NaiveDate>,
         /// Filter by event type
         #[arg(long)]
         filter: Option<String>,
@@ -762,8 +1687,17 @@ enum AuditCommands {
     },
 }
 
-pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRenderer) -> Result<()> {
-    let ctx = JiraContext { client, renderer };
+pub async fn execute(
+    args: JiraArgs,
+    client: ApiClient,
+    renderer: &OutputRenderer,
+    profile: &str,
+) -> Result<()> {
+    let ctx = JiraContext {
+        client,
+        renderer,
+        profile,
+    };
 
     match args.command {
         JiraCommands::Search {
@@ -775,9 +1709,77 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             r#type,
             project,
             text,
+            text_in,
             show_query,
-            limit,
+            page,
+            fields,
+            columns,
+            publish_to_confluence,
+            space,
+            title,
+            parent,
         } => {
+            let publish_to = publish_to_confluence.then(|| publish::PageTarget {
+                space: space.unwrap_or_default(),
+                title: title.unwrap_or_default(),
+                parent,
+            });
+            if !fields.is_empty() {
+                let final_jql = issues::resolve_search_jql(
+                    jql.as_deref(),
+                    assignee.as_deref(),
+                    &status,
+                    priority.as_deref(),
+                    &label,
+                    r#type.as_deref(),
+                    project.as_deref(),
+                    text.as_deref(),
+                    &text_in,
+                    show_query,
+                )?;
+                let selected = field_select::resolve_fields(&ctx, &fields).await?;
+                let rows =
+                    field_select::search_issue_fields(&ctx, &final_jql, &page, &selected).await?;
+                if let Some(target) = &publish_to {
+                    return publish::publish_search(&ctx, &final_jql, &rows, target).await;
+                }
+                if rows.is_empty() {
+                    tracing::info!("No issues matched the provided JQL.");
+                    return Ok(());
+                }
+                return renderer.render(&rows);
+            }
+            if let Some(target) = &publish_to {
+                let final_jql = issues::resolve_search_jql(
+                    jql.as_deref(),
+                    assignee.as_deref(),
+                    &status,
+                    priority.as_deref(),
+                    &label,
+                    r#type.as_deref(),
+                    project.as_deref(),
+                    text.as_deref(),
+                    &text_in,
+                    show_query,
+                )?;
+                let text_search = text.as_deref().map(|text| issues::TextSearch {
+                    text,
+                    fields: &text_in,
+                });
+                let rows = issues::fetch_search_rows(
+                    &ctx,
+                    &final_jql,
+                    &page,
+                    text_search,
+                    issues::wants_votes(&columns),
+                )
+                .await?;
+                if !columns.is_empty() {
+                    let rows = issues::select_columns(&rows, &columns)?;
+                    return publish::publish_search(&ctx, &final_jql, &rows, target).await;
+                }
+                return publish::publish_search(&ctx, &final_jql, &rows, target).await;
+            }
             issues::search_issues(
                 &ctx,
                 jql.as_deref(),
@@ -788,12 +1790,27 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 r#type.as_deref(),
                 project.as_deref(),
                 text.as_deref(),
+                &text_in,
                 show_query,
-                limit,
+                &page,
+                &columns,
             )
             .await
         }
-        JiraCommands::Get { key } => issues::view_issue(&ctx, &key).await,
+        JiraCommands::Get { key, fields } => {
+            if fields.is_empty() {
+                return issues::view_issue(&ctx, &key).await;
+            }
+            let selected = field_select::resolve_fields(&ctx, &fields).await?;
+            let row = field_select::fetch_issue_fields(&ctx, &key, &selected).await?;
+            renderer.render(&row)
+        }
+        JiraCommands::Quick {
+            text,
+            project,
+            issue_type,
+            dry_run,
+        } => quick::quick_create(&ctx, &text, project.as_deref(), &issue_type, dry_run).await,
         JiraCommands::Create {
             project,
             issue_type,
@@ -801,39 +1818,105 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             description,
             assignee,
             priority,
+            components,
+            validate,
+            mut field,
+            editor,
         } => {
-            issues::create_issue(
-                &ctx,
-                &project,
-                &issue_type,
-                &summary,
-                description.as_deref(),
-                assignee.as_deref(),
-                priority.as_deref(),
-            )
-            .await
+            let mut draft = editor::IssueDraft {
+                project: project.unwrap_or_default(),
+                issue_type: issue_type.unwrap_or_default(),
+                summary: summary.unwrap_or_default(),
+                assignee,
+                priority,
+                components,
+                description,
+                ..Default::default()
+            };
+            let mut draft_file = None;
+            if editor {
+                for arg in field.drain(..) {
+                    let (name, value) = arg.split_once('=').ok_or_else(|| {
+                        anyhow::anyhow!("Invalid --field '{arg}'. Expected NAME=VALUE")
+                    })?;
+                    draft
+                        .fields
+                        .insert(name.trim().to_string(), value.trim().into());
+                }
+                let (edited, file) = editor::edit_draft(&draft)?;
+                draft = edited;
+                field = draft.field_args()?;
+                draft_file = Some(file);
+            }
+
+            let result = async {
+                let custom = resolve_field_args(&ctx, &field).await?;
+                issues::create_issue(
+                    &ctx,
+                    &draft.project,
+                    &draft.issue_type,
+                    &draft.summary,
+                    draft.description.as_deref(),
+                    draft.assignee.as_deref(),
+                    draft.priority.as_deref(),
+                    &draft.components,
+                    &custom,
+                    validate,
+                )
+                .await
+            }
+            .await;
+            if let Some(file) = draft_file {
+                match &result {
+                    Ok(()) => file.discard(),
+                    Err(_) => {
+                        eprintln!("Issue not created; draft kept at {}", file.path().display())
+                    }
+                }
+            }
+            result
         }
         JiraCommands::Update {
             key,
             summary,
             description,
             priority,
+            field,
+            dry_run,
         } => {
+            let custom = resolve_field_args(&ctx, &field).await?;
             issues::update_issue(
                 &ctx,
                 &key,
                 summary.as_deref(),
                 description.as_deref(),
                 priority.as_deref(),
+                &custom,
+                dry_run,
             )
             .await
         }
         JiraCommands::Delete { key, force } => issues::delete_issue(&ctx, &key, force).await,
-        JiraCommands::Transition { key, transition } => {
-            issues::transition_issue(&ctx, &key, &transition).await
-        }
+        JiraCommands::Transitions { key } => issues::list_transitions(&ctx, &key).await,
+        JiraCommands::Transition {
+            key,
+            transition,
+            to,
+            through_auto,
+        } => match to {
+            Some(to) => transition_path::transition_to(&ctx, &key, &to, through_auto).await,
+            None => {
+                let transition = transition.unwrap_or_default();
+                issues::transition_issue(&ctx, &key, &transition).await
+            }
+        },
         JiraCommands::Assign { key, assignee } => issues::assign_issue(&ctx, &key, &assignee).await,
         JiraCommands::Unassign { key } => issues::unassign_issue(&ctx, &key).await,
+        JiraCommands::Vote { key } => issues::vote(&ctx, &key).await,
+        JiraCommands::Unvote { key } => issues::unvote(&ctx, &key).await,
+        JiraCommands::Voters { key } => issues::list_voters(&ctx, &key).await,
+        JiraCommands::Archive { keys } => issues::archive_issues(&ctx, &keys).await,
+        JiraCommands::Restore { keys } => issues::restore_issues(&ctx, &keys).await,
         JiraCommands::Watchers(cmd) => match cmd {
             WatcherCommands::List { key } => issues::list_watchers(&ctx, &key).await,
             WatcherCommands::Add { key, user } => issues::add_watcher(&ctx, &key, &user).await,
@@ -841,6 +1924,13 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 issues::remove_watcher(&ctx, &key, &user).await
             }
         },
+        JiraCommands::Graph {
+            key,
+            depth,
+            format,
+            max_issues,
+            output,
+        } => graph::export_graph(&ctx, &key, depth, &format, max_issues, output.as_deref()).await,
         JiraCommands::Links(cmd) => match cmd {
             LinkCommands::List { key } => issues::list_links(&ctx, &key).await,
             LinkCommands::Create {
@@ -852,7 +1942,12 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
         },
         JiraCommands::Comments(cmd) => match cmd {
             CommentCommands::List { key } => issues::list_comments(&ctx, &key).await,
-            CommentCommands::Add { key, body } => issues::add_comment(&ctx, &key, &body).await,
+            CommentCommands::Add {
+                key,
+                body,
+                visibility,
+                internal,
+            } => issues::add_comment(&ctx, &key, &body, visibility.as_deref(), internal).await,
             CommentCommands::Update { comment_id, body } => {
                 issues::update_comment(&ctx, &comment_id, &body).await
             }
@@ -861,7 +1956,7 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             }
         },
         JiraCommands::Project(cmd) => match cmd {
-            ProjectCommands::List => projects::list_projects(&ctx).await,
+            ProjectCommands::List { all } => projects::list_projects(&ctx, all).await,
             ProjectCommands::Get { key } => projects::get_project(&ctx, &key).await,
             ProjectCommands::Create {
                 key,
@@ -869,6 +1964,7 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 project_type,
                 lead,
                 description,
+                if_not_exists,
             } => {
                 projects::create_project(
                     &ctx,
@@ -877,6 +1973,7 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                     &project_type,
                     lead.as_deref(),
                     description.as_deref(),
+                    if_not_exists,
                 )
                 .await
             }
@@ -898,6 +1995,14 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             ProjectCommands::Delete { key, force } => {
                 projects::delete_project(&ctx, &key, force).await
             }
+            ProjectCommands::Archive { key } => projects::archive_project(&ctx, &key).await,
+            ProjectCommands::Restore { key } => projects::restore_project(&ctx, &key).await,
+            ProjectCommands::Category(cmd) => match cmd {
+                ProjectCategoryCommands::List => projects::list_categories(&ctx).await,
+                ProjectCategoryCommands::Set { key, category } => {
+                    projects::set_category(&ctx, &key, &category).await
+                }
+            },
         },
         JiraCommands::Components(cmd) => match cmd {
             ComponentCommands::List { project } => projects::list_components(&ctx, &project).await,
@@ -925,6 +2030,21 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 projects::update_component(&ctx, &id, name.as_deref(), description.as_deref()).await
             }
             ComponentCommands::Delete { id } => projects::delete_component(&ctx, &id).await,
+            ComponentCommands::SetDefaultAssignee { id, assignee_type } => {
+                components::set_default_assignee(&ctx, &id, assignee_type).await
+            }
+            ComponentCommands::Sync {
+                from_file,
+                project,
+                prune,
+                dry_run,
+            } => components::sync_components(&ctx, &project, &from_file, prune, dry_run).await,
+            ComponentCommands::Import {
+                project,
+                file,
+                dry_run,
+                concurrency,
+            } => csv_import::import_components(&ctx, &project, &file, dry_run, concurrency).await,
         },
         JiraCommands::Versions(cmd) => match cmd {
             VersionCommands::List { project } => projects::list_versions(&ctx, &project).await,
@@ -937,16 +2057,18 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 release_date,
                 released,
                 archived,
+                if_not_exists,
             } => {
                 projects::create_version(
                     &ctx,
                     &project,
                     &name,
                     description.as_deref(),
-                    start_date.as_deref(),
-                    release_date.as_deref(),
+                    start_date.map(|d| d.to_string()).as_deref(),
+                    release_date.map(|d| d.to_string()).as_deref(),
                     released,
                     archived,
+                    if_not_exists,
                 )
                 .await
             }
@@ -969,6 +2091,12 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             }
             VersionCommands::Delete { id } => projects::delete_version(&ctx, &id).await,
             VersionCommands::Merge { from, to } => projects::merge_versions(&ctx, &from, &to).await,
+            VersionCommands::Import {
+                project,
+                file,
+                dry_run,
+                concurrency,
+            } => csv_import::import_versions(&ctx, &project, &file, dry_run, concurrency).await,
         },
         JiraCommands::Roles(cmd) => match cmd {
             RoleCommands::List { project } => fields_workflows::list_roles(&ctx, &project).await,
@@ -989,8 +2117,22 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 user,
             } => fields_workflows::remove_role_actor(&ctx, &project, &role_id, &user).await,
         },
+        JiraCommands::Group(cmd) => match cmd {
+            GroupCommands::List { page } => groups::list_groups(&ctx, &page).await,
+            GroupCommands::Members { group, page } => {
+                groups::list_group_members(&ctx, &group, &page).await
+            }
+            GroupCommands::AddUser { group, user } => {
+                groups::add_group_user(&ctx, &group, &user).await
+            }
+            GroupCommands::RemoveUser { group, user } => {
+                groups::remove_group_user(&ctx, &group, &user).await
+            }
+        },
         JiraCommands::Fields(cmd) => match cmd {
-            FieldCommands::List => fields_workflows::list_fields(&ctx).await,
+            FieldCommands::List { project } => {
+                fields_workflows::list_fields(&ctx, project.as_deref()).await
+            }
             FieldCommands::Get { id } => fields_workflows::get_field(&ctx, &id).await,
             FieldCommands::Create {
                 name,
@@ -1001,6 +2143,56 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                     .await
             }
             FieldCommands::Delete { id } => fields_workflows::delete_field(&ctx, &id).await,
+            FieldCommands::RefreshCache => fields_workflows::refresh_field_cache(&ctx).await,
+        },
+        JiraCommands::Priorities(cmd) => match cmd {
+            PriorityCommands::List => priorities::list_priorities(&ctx).await,
+            PriorityCommands::Create {
+                name,
+                color,
+                description,
+            } => priorities::create_priority(&ctx, &name, &color, description.as_deref()).await,
+            PriorityCommands::Update {
+                priority,
+                name,
+                color,
+                description,
+            } => {
+                priorities::update_priority(
+                    &ctx,
+                    &priority,
+                    name.as_deref(),
+                    color.as_deref(),
+                    description.as_deref(),
+                )
+                .await
+            }
+            PriorityCommands::Delete { priority, force } => {
+                priorities::delete_priority(&ctx, &priority, force).await
+            }
+            PriorityCommands::Scheme(cmd) => match cmd {
+                PrioritySchemeCommands::List { project } => {
+                    priorities::list_schemes(&ctx, project.as_deref()).await
+                }
+                PrioritySchemeCommands::Create {
+                    name,
+                    priorities,
+                    default,
+                    description,
+                } => {
+                    priorities::create_scheme(
+                        &ctx,
+                        &name,
+                        &priorities,
+                        &default,
+                        description.as_deref(),
+                    )
+                    .await
+                }
+                PrioritySchemeCommands::Assign { scheme, project } => {
+                    priorities::assign_scheme(&ctx, &scheme, &project).await
+                }
+            },
         },
         JiraCommands::Workflows(cmd) => match cmd {
             WorkflowCommands::List => fields_workflows::list_workflows(&ctx).await,
@@ -1008,27 +2200,55 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
             WorkflowCommands::Export { name, output } => {
                 fields_workflows::export_workflow(&ctx, &name, output.as_deref()).await
             }
+            WorkflowCommands::Create {
+                from_file,
+                name,
+                dry_run,
+            } => {
+                fields_workflows::create_workflow(&ctx, &from_file, name.as_deref(), dry_run).await
+            }
+            WorkflowCommands::Assign {
+                workflow,
+                project,
+                issue_type,
+            } => fields_workflows::assign_workflow(&ctx, &workflow, &project, &issue_type).await,
         },
         JiraCommands::Bulk(cmd) => match cmd {
             BulkCommands::Transition {
                 jql,
                 transition,
+                notify_preview,
                 dry_run,
                 concurrency,
-            } => bulk::bulk_transition(&ctx, &jql, &transition, dry_run, concurrency).await,
+            } => {
+                if notify_preview {
+                    bulk::preview_notifications(&ctx, &jql, true).await?;
+                }
+                bulk::bulk_transition(&ctx, &jql, &transition, dry_run, concurrency).await
+            }
             BulkCommands::Assign {
                 jql,
                 assignee,
+                notify_preview,
                 dry_run,
                 concurrency,
-            } => bulk::bulk_assign(&ctx, &jql, &assignee, dry_run, concurrency).await,
+            } => {
+                if notify_preview {
+                    bulk::preview_notifications(&ctx, &jql, true).await?;
+                }
+                bulk::bulk_assign(&ctx, &jql, &assignee, dry_run, concurrency).await
+            }
             BulkCommands::Label {
                 jql,
                 action,
                 labels,
+                notify_preview,
                 dry_run,
                 concurrency,
             } => {
+                if notify_preview {
+                    bulk::preview_notifications(&ctx, &jql, true).await?;
+                }
                 let label_action = match action.to_lowercase().as_str() {
                     "add" => bulk::LabelAction::Add,
                     "remove" => bulk::LabelAction::Remove,
@@ -1042,6 +2262,79 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 };
                 bulk::bulk_label(&ctx, &jql, label_action, labels, dry_run, concurrency).await
             }
+            BulkCommands::Comment {
+                jql,
+                body,
+                markdown,
+                no_notify,
+                notify_preview,
+                dry_run,
+                concurrency,
+            } => {
+                if notify_preview {
+                    bulk::preview_notifications(&ctx, &jql, !no_notify).await?;
+                }
+                bulk::bulk_comment(
+                    &ctx,
+                    &jql,
+                    &body,
+                    markdown,
+                    !no_notify,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
+            BulkCommands::Watchers(cmd) => {
+                let (action, jql, users, dry_run, concurrency) = match cmd {
+                    BulkWatcherCommands::Add {
+                        jql,
+                        users,
+                        dry_run,
+                        concurrency,
+                    } => (bulk::WatcherAction::Add, jql, users, dry_run, concurrency),
+                    BulkWatcherCommands::Remove {
+                        jql,
+                        users,
+                        dry_run,
+                        concurrency,
+                    } => (
+                        bulk::WatcherAction::Remove,
+                        jql,
+                        users,
+                        dry_run,
+                        concurrency,
+                    ),
+                };
+                bulk::bulk_watchers(&ctx, &jql, action, &users, dry_run, concurrency).await
+            }
+            BulkCommands::Replace {
+                jql,
+                field,
+                find,
+                replace,
+                regex,
+                no_notify,
+                notify_preview,
+                dry_run,
+                concurrency,
+            } => {
+                if notify_preview {
+                    bulk::preview_notifications(&ctx, &jql, !no_notify).await?;
+                }
+                replace::bulk_replace(
+                    &ctx,
+                    &jql,
+                    &field,
+                    &find,
+                    &replace,
+                    regex,
+                    !no_notify,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
             BulkCommands::Export {
                 jql,
                 output,
@@ -1066,6 +2359,62 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 dry_run,
                 concurrency,
             } => bulk::bulk_import(&ctx, &file, &project, dry_run, concurrency).await,
+            BulkCommands::ProjectLead {
+                from_user,
+                to_user,
+                projects,
+                dry_run,
+                concurrency,
+            } => {
+                leads::bulk_project_lead(
+                    &ctx,
+                    &from_user,
+                    &to_user,
+                    &projects,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
+            BulkCommands::ComponentLead {
+                from_user,
+                to_user,
+                projects,
+                dry_run,
+                concurrency,
+            } => {
+                leads::bulk_component_lead(
+                    &ctx,
+                    &from_user,
+                    &to_user,
+                    &projects,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
+            BulkCommands::Reassign {
+                from_user,
+                to_user,
+                projects,
+                include_reporter,
+                include_filters,
+                dry_run,
+                concurrency,
+            } => {
+                bulk::bulk_reassign(
+                    &ctx,
+                    &from_user,
+                    &to_user,
+                    &projects,
+                    include_reporter,
+                    include_filters,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
+            BulkCommands::Archive { jql, dry_run } => bulk::bulk_archive(&ctx, &jql, dry_run).await,
         },
         JiraCommands::Automation(cmd) => match cmd {
             AutomationCommands::List => automation::list_rules(&ctx).await,
@@ -1096,6 +2445,9 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
         },
         JiraCommands::Webhooks(cmd) => match cmd {
             WebhookCommands::List => webhooks::list_webhooks(&ctx).await,
+            WebhookCommands::Events => {
+                webhook_events::render_events(renderer, webhook_events::JIRA_EVENTS)
+            }
             WebhookCommands::Get { webhook_id } => webhooks::get_webhook(&ctx, webhook_id).await,
             WebhookCommands::Create {
                 name,
@@ -1134,20 +2486,33 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 webhooks::delete_webhook(&ctx, webhook_id, force).await
             }
             WebhookCommands::Test { webhook_id } => webhooks::test_webhook(&ctx, webhook_id).await,
+            WebhookCommands::Verify {
+                secret,
+                payload,
+                signature,
+                print_curl_example,
+                url,
+            } => webhooks::verify_signature(
+                &secret,
+                &payload,
+                signature.as_deref(),
+                print_curl_example,
+                &url,
+            ),
         },
         JiraCommands::Audit(cmd) => match cmd {
             AuditCommands::List {
                 from,
                 to,
                 filter,
-                limit,
+                page,
             } => {
                 audit::list_audit_records(
                     &ctx,
-                    from.as_deref(),
-                    to.as_deref(),
+                    from.map(|d| d.to_string()).as_deref(),
+                    to.map(|d| d.to_string()).as_deref(),
                     filter.as_deref(),
-                    limit,
+                    &page,
                 )
                 .await
             }
@@ -1170,8 +2535,8 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 };
                 audit::export_audit_records(
                     &ctx,
-                    from.as_deref(),
-                    to.as_deref(),
+                    from.map(|d| d.to_string()).as_deref(),
+                    to.map(|d| d.to_string()).as_deref(),
                     filter.as_deref(),
                     &output,
                     export_format,
@@ -1179,5 +2544,269 @@ pub async fn execute(args: JiraArgs, client: ApiClient, renderer: &OutputRendere
                 .await
             }
         },
+        JiraCommands::Recurring(cmd) => match cmd {
+            RecurringCommands::Add {
+                template,
+                cron,
+                name,
+            } => recurring::add(&ctx, &template, &cron, name.as_deref()),
+            RecurringCommands::List => recurring::list(&ctx),
+            RecurringCommands::Remove { name } => recurring::remove(&ctx, &name),
+            RecurringCommands::Run { dry_run } => recurring::run(&ctx, dry_run).await,
+        },
+        JiraCommands::Trash(cmd) => match cmd {
+            TrashCommands::List { project, page } => {
+                trash::list_trash(&ctx, project.as_deref(), &page).await
+            }
+            TrashCommands::Restore { keys } => trash::restore_from_trash(&ctx, &keys).await,
+        },
+        JiraCommands::Release(cmd) => match cmd {
+            ReleaseCommands::Create {
+                project,
+                name,
+                from_jql,
+                description,
+                release,
+                release_date,
+                allow_unresolved,
+                dry_run,
+                concurrency,
+            } => {
+                release::create_release(
+                    &ctx,
+                    &project,
+                    &name,
+                    &from_jql,
+                    description.as_deref(),
+                    release,
+                    release_date.map(|d| d.to_string()).as_deref(),
+                    allow_unresolved,
+                    dry_run,
+                    concurrency,
+                )
+                .await
+            }
+        },
+        JiraCommands::Plan(cmd) => match cmd {
+            PlanCommands::List {
+                include_archived,
+                include_trashed,
+            } => plans::list_plans(&ctx, include_archived, include_trashed).await,
+            PlanCommands::Export {
+                plan_id,
+                format,
+                output,
+            } => plans::export_plan(&ctx, &plan_id, &format, output.as_deref()).await,
+        },
+        JiraCommands::Board(cmd) => match cmd {
+            BoardCommands::Config { board_id } => boards::board_config(&ctx, board_id).await,
+            BoardCommands::QuickFilter(cmd) => match cmd {
+                BoardQuickFilterCommands::List { board_id } => {
+                    boards::list_quick_filters(&ctx, board_id).await
+                }
+                BoardQuickFilterCommands::Create {
+                    board_id,
+                    name,
+                    jql,
+                    description,
+                } => {
+                    boards::create_quick_filter(&ctx, board_id, &name, &jql, description.as_deref())
+                        .await
+                }
+                BoardQuickFilterCommands::Delete {
+                    board_id,
+                    filter_id,
+                    force,
+                } => boards::delete_quick_filter(&ctx, board_id, filter_id, force).await,
+            },
+            BoardCommands::Swimlane(cmd) => match cmd {
+                BoardSwimlaneCommands::List { board_id } => {
+                    boards::list_swimlanes(&ctx, board_id).await
+                }
+                BoardSwimlaneCommands::Create {
+                    board_id,
+                    name,
+                    jql,
+                    description,
+                } => {
+                    boards::create_swimlane(&ctx, board_id, &name, &jql, description.as_deref())
+                        .await
+                }
+                BoardSwimlaneCommands::Delete {
+                    board_id,
+                    swimlane_id,
+                    force,
+                } => boards::delete_swimlane(&ctx, board_id, swimlane_id, force).await,
+                BoardSwimlaneCommands::Strategy { board_id, strategy } => {
+                    boards::set_swimlane_strategy(&ctx, board_id, strategy).await
+                }
+            },
+        },
+        JiraCommands::Sprint(cmd) => match cmd {
+            SprintCommands::Report {
+                board_id,
+                last,
+                points_field,
+            } => sprints::sprint_report(&ctx, board_id, last, points_field.as_deref()).await,
+        },
+        JiraCommands::Report(cmd) => match cmd {
+            ReportCommands::Aging {
+                jql,
+                buckets,
+                top,
+                csv,
+            } => reports::aging_report(&ctx, &jql, &buckets, top, csv.as_deref()).await,
+            ReportCommands::CycleTime {
+                jql,
+                from_status,
+                to_status,
+                percentiles,
+                csv,
+            } => {
+                cycle_time::cycle_time_report(
+                    &ctx,
+                    &jql,
+                    &from_status,
+                    &to_status,
+                    &percentiles,
+                    csv.as_deref(),
+                )
+                .await
+            }
+            ReportCommands::Timesheet {
+                users,
+                from,
+                to,
+                jql,
+                group_by,
+                csv,
+            } => {
+                timesheet::timesheet_report(
+                    &ctx,
+                    &users,
+                    from,
+                    to,
+                    jql.as_deref(),
+                    &group_by,
+                    csv.as_deref(),
+                )
+                .await
+            }
+        },
+    }
+}
+
+/// Resolve `--field NAME=VALUE` arguments, loading the field map only when needed.
+async fn resolve_field_args(
+    ctx: &JiraContext<'_>,
+    args: &[String],
+) -> Result<Vec<field_map::FieldAssignment>> {
+    if args.is_empty() {
+        return Ok(Vec::new());
     }
+    field_map::load(ctx).await?.assignments(args)
+}
+
+/// Fan a read-only Jira command out across several profiles and render the merged rows.
+pub async fn execute_multi_profile(
+    args: JiraArgs,
+    targets: Vec<ProfileTarget>,
+    renderer: &OutputRenderer,
+) -> Result<()> {
+    let rows = match args.command {
+        JiraCommands::Search {
+            jql,
+            assignee,
+            status,
+            priority,
+            label,
+            r#type,
+            project,
+            text,
+            text_in,
+            show_query,
+            page,
+            fields,
+            columns,
+            publish_to_confluence,
+            ..
+        } => {
+            if publish_to_confluence {
+                return Err(anyhow::anyhow!(
+                    "--publish-to-confluence cannot be combined with --profiles/--all-profiles"
+                ));
+            }
+            let final_jql = issues::resolve_search_jql(
+                jql.as_deref(),
+                assignee.as_deref(),
+                &status,
+                priority.as_deref(),
+                &label,
+                r#type.as_deref(),
+                project.as_deref(),
+                text.as_deref(),
+                &text_in,
+                show_query,
+            )?;
+            let final_jql = &final_jql;
+            let page = &page;
+
+            if !fields.is_empty() {
+                let fields = &fields;
+                multi_profile::fan_out(&targets, |target| {
+                    let ctx = JiraContext {
+                        client: target.client.clone(),
+                        renderer,
+                        profile: &target.name,
+                    };
+                    async move {
+                        let selected = field_select::resolve_fields(&ctx, fields).await?;
+                        field_select::search_issue_fields(&ctx, final_jql, page, &selected).await
+                    }
+                })
+                .await?
+            } else {
+                let text_search = text.as_deref().map(|text| issues::TextSearch {
+                    text,
+                    fields: &text_in,
+                });
+                let with_votes = issues::wants_votes(&columns);
+
+                let rows = multi_profile::fan_out(&targets, |target| {
+                    let ctx = JiraContext {
+                        client: target.client.clone(),
+                        renderer,
+                        profile: &target.name,
+                    };
+                    async move {
+                        issues::fetch_search_rows(&ctx, final_jql, page, text_search, with_votes)
+                            .await
+                    }
+                })
+                .await?;
+                if columns.is_empty() {
+                    rows
+                } else {
+                    let mut rows = issues::select_columns(&rows, &columns)?;
+                    if with_votes {
+                        // Each profile's results are already most-voted first
+                        rows.sort_by_key(|row| std::cmp::Reverse(row["votes"].as_u64()));
+                    }
+                    rows
+                }
+            }
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "--profiles/--all-profiles is only supported for `jira search`"
+            ))
+        }
+    };
+
+    if rows.is_empty() {
+        tracing::info!("No issues matched the provided JQL in any profile.");
+        return Ok(());
+    }
+
+    renderer.render_with_id(&rows, "key")
 }
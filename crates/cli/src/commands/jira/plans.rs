@@ -0,0 +1,214 @@
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::utils::JiraContext;
+
+// Advanced Roadmaps (Jira Premium) plans API
+
+#[derive(Deserialize)]
+struct CursorPage<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+    /// Cursor for the next page; `cursor` only echoes the one sent.
+    #[serde(rename = "nextPageCursor", default)]
+    next_page_cursor: Option<String>,
+    #[serde(rename = "isLast", default)]
+    is_last: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct PlanSummary {
+    id: Value,
+    name: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(rename = "scenarioId", default)]
+    scenario_id: Option<Value>,
+    #[serde(rename = "issueSources", default)]
+    issue_sources: Vec<IssueSource>,
+}
+
+#[derive(Deserialize)]
+struct IssueSource {
+    #[serde(rename = "type")]
+    source_type: String,
+    value: Value,
+}
+
+#[derive(Serialize)]
+struct PlanRow {
+    id: String,
+    name: String,
+    status: String,
+    scenario: String,
+    sources: String,
+}
+
+/// Fetch every page of a cursor-paginated plans endpoint.
+async fn fetch_all<T: DeserializeOwned>(ctx: &JiraContext<'_>, path: &str) -> Result<Vec<T>> {
+    let separator = if path.contains('?') { '&' } else { '?' };
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let url = match &cursor {
+            Some(c) => format!(
+                "{path}{separator}maxResults=50&cursor={}",
+                urlencoding::encode(c)
+            ),
+            None => format!("{path}{separator}maxResults=50"),
+        };
+        let page: CursorPage<T> = ctx.client.get(&url).await?;
+        let count = page.values.len();
+        items.extend(page.values);
+
+        match page.next_page_cursor {
+            Some(next)
+                if !page.is_last.unwrap_or(true)
+                    && count > 0
+                    && cursor.as_deref() != Some(next.as_str()) =>
+            {
+                cursor = Some(next)
+            }
+            _ => break,
+        }
+    }
+
+    Ok(items)
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn describe_sources(sources: &[IssueSource]) -> String {
+    sources
+        .iter()
+        .map(|s| format!("{}:{}", s.source_type, value_to_string(&s.value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// List plans
+pub async fn list_plans(
+    ctx: &JiraContext<'_>,
+    include_archived: bool,
+    include_trashed: bool,
+) -> Result<()> {
+    let path = format!(
+        "/rest/api/3/plans/plan?includeArchived={include_archived}&includeTrashed={include_trashed}"
+    );
+    let plans: Vec<PlanSummary> = fetch_all(ctx, &path)
+        .await
+        .context("Failed to list plans (Advanced Roadmaps requires Jira Premium)")?;
+
+    let rows: Vec<PlanRow> = plans
+        .iter()
+        .map(|p| PlanRow {
+            id: value_to_string(&p.id),
+            name: p.name.clone(),
+            status: p.status.clone().unwrap_or_default(),
+            scenario: p
+                .scenario_id
+                .as_ref()
+                .map(value_to_string)
+                .unwrap_or_default(),
+            sources: describe_sources(&p.issue_sources),
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+#[derive(Serialize)]
+struct PlanSnapshot {
+    exported_at: String,
+    plan: Value,
+    teams: Vec<Value>,
+}
+
+// Export a plan snapshot (plan configuration plus teams)
+pub async fn export_plan(
+    ctx: &JiraContext<'_>,
+    plan_id: &str,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<()> {
+    if !format.eq_ignore_ascii_case("json") {
+        bail!("Invalid format '{}'. Must be one of: json", format);
+    }
+
+    let plan: Value = ctx
+        .client
+        .get(&format!("/rest/api/3/plans/plan/{plan_id}"))
+        .await
+        .with_context(|| format!("Failed to fetch plan {plan_id}"))?;
+
+    let teams: Vec<Value> = fetch_all(ctx, &format!("/rest/api/3/plans/plan/{plan_id}/team"))
+        .await
+        .with_context(|| format!("Failed to fetch teams for plan {plan_id}"))?;
+
+    let snapshot = PlanSnapshot {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        plan,
+        teams,
+    };
+    let json_str = serde_json::to_string_pretty(&snapshot)?;
+
+    match output {
+        Some(path) => {
+            fs::write(path, json_str)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            tracing::info!(plan_id, file = %path.display(), "Plan exported");
+            println!(
+                "✅ Exported plan {} ({} teams) to {}",
+                plan_id,
+                snapshot.teams.len(),
+                path.display()
+            );
+        }
+        None => println!("{json_str}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_describe_sources() {
+        let plan: PlanSummary = serde_json::from_value(json!({
+            "id": 10000,
+            "name": "Roadmap",
+            "issueSources": [
+                {"type": "Project", "value": 10001},
+                {"type": "Board", "value": "42"}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(value_to_string(&plan.id), "10000");
+        assert_eq!(
+            describe_sources(&plan.issue_sources),
+            "Project:10001, Board:42"
+        );
+    }
+
+    #[test]
+    fn test_cursor_page_defaults() {
+        let page: CursorPage<Value> = serde_json::from_value(json!({})).unwrap();
+        assert!(page.values.is_empty());
+        assert!(page.next_page_cursor.is_none());
+    }
+}
@@ -0,0 +1,383 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Priorities and priority schemes, so the same taxonomy can be set up on
+// several sites and projects pointed at it
+
+#[derive(Deserialize)]
+struct PriorityPage<T> {
+    values: Vec<T>,
+    #[serde(rename = "isLast", default = "default_true")]
+    is_last: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct Priority {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "statusColor", default)]
+    status_color: Option<String>,
+    #[serde(rename = "isDefault", default)]
+    is_default: bool,
+}
+
+#[derive(Serialize)]
+struct PriorityRow {
+    id: String,
+    name: String,
+    description: String,
+    color: String,
+    default: bool,
+}
+
+#[derive(Deserialize)]
+struct PriorityScheme {
+    id: String,
+    name: String,
+    #[serde(rename = "defaultPriorityId", default)]
+    default_priority_id: Option<String>,
+    #[serde(rename = "isDefault", default)]
+    is_default: bool,
+    #[serde(default)]
+    priorities: Option<Expanded<IdName>>,
+    #[serde(default)]
+    projects: Option<Expanded<ProjectRef>>,
+}
+
+#[derive(Deserialize)]
+struct Expanded<T> {
+    values: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct IdName {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ProjectRef {
+    key: String,
+}
+
+#[derive(Serialize)]
+struct SchemeRow {
+    id: String,
+    name: String,
+    priorities: String,
+    default_priority: String,
+    projects: String,
+    default: bool,
+}
+
+#[derive(Deserialize)]
+struct Created {
+    id: String,
+}
+
+pub async fn list_priorities(ctx: &JiraContext<'_>) -> Result<()> {
+    let rows: Vec<PriorityRow> = fetch_priorities(ctx)
+        .await?
+        .into_iter()
+        .map(|p| PriorityRow {
+            id: p.id,
+            name: p.name,
+            description: p.description.unwrap_or_default(),
+            color: p.status_color.unwrap_or_default(),
+            default: p.is_default,
+        })
+        .collect();
+
+    tracing::info!(count = rows.len(), "Listed priorities");
+    ctx.renderer.render(&rows)
+}
+
+pub async fn create_priority(
+    ctx: &JiraContext<'_>,
+    name: &str,
+    color: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let existing = fetch_priorities(ctx).await?;
+    if existing.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+        bail!("Priority '{name}' already exists");
+    }
+
+    let mut payload = json!({ "name": name, "statusColor": color });
+    if let Some(description) = description {
+        payload["description"] = json!(description);
+    }
+    let created: Created = ctx
+        .client
+        .post("/rest/api/3/priority", &payload)
+        .await
+        .with_context(|| format!("Failed to create priority {name}"))?;
+
+    tracing::info!(id = %created.id, %name, "Priority created");
+    println!("✅ Created priority: {name} (ID: {})", created.id);
+    Ok(())
+}
+
+pub async fn update_priority(
+    ctx: &JiraContext<'_>,
+    priority: &str,
+    name: Option<&str>,
+    color: Option<&str>,
+    description: Option<&str>,
+) -> Result<()> {
+    if name.is_none() && color.is_none() && description.is_none() {
+        bail!("Nothing to update: pass --name, --color or --description");
+    }
+    let id = resolve_priority_id(ctx, priority).await?;
+
+    let mut payload = json!({});
+    if let Some(name) = name {
+        payload["name"] = json!(name);
+    }
+    if let Some(color) = color {
+        payload["statusColor"] = json!(color);
+    }
+    if let Some(description) = description {
+        payload["description"] = json!(description);
+    }
+    let _: Value = ctx
+        .client
+        .put(&format!("/rest/api/3/priority/{id}"), &payload)
+        .await
+        .with_context(|| format!("Failed to update priority {priority}"))?;
+
+    tracing::info!(%id, "Priority updated");
+    println!("✅ Updated priority: {priority}");
+    Ok(())
+}
+
+pub async fn delete_priority(ctx: &JiraContext<'_>, priority: &str, force: bool) -> Result<()> {
+    if !force {
+        println!(
+            "⚠️  This will permanently delete priority {priority}. Issues using it move to the default priority. Use --force to confirm."
+        );
+        return Ok(());
+    }
+    let id = resolve_priority_id(ctx, priority).await?;
+
+    let _: Value = ctx
+        .client
+        .delete(&format!("/rest/api/3/priority/{id}"))
+        .await
+        .with_context(|| format!("Failed to delete priority {priority}"))?;
+
+    tracing::info!(%id, "Priority deleted");
+    println!("✅ Deleted priority: {priority}");
+    Ok(())
+}
+
+pub async fn list_schemes(ctx: &JiraContext<'_>, project: Option<&str>) -> Result<()> {
+    let schemes = fetch_schemes(ctx).await?;
+    let rows: Vec<SchemeRow> = schemes
+        .into_iter()
+        .map(|scheme| {
+            let priorities = scheme.priorities.map(|p| p.values).unwrap_or_default();
+            let default_priority = priorities
+                .iter()
+                .find(|p| Some(&p.id) == scheme.default_priority_id.as_ref())
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+            let projects: Vec<String> = scheme
+                .projects
+                .map(|p| p.values.into_iter().map(|p| p.key).collect())
+                .unwrap_or_default();
+            SchemeRow {
+                id: scheme.id,
+                name: scheme.name,
+                priorities: priorities
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                default_priority,
+                projects: projects.join(", "),
+                default: scheme.is_default,
+            }
+        })
+        .filter(|row| {
+            project.is_none_or(|key| {
+                row.projects
+                    .split(", ")
+                    .any(|p| p.eq_ignore_ascii_case(key))
+            })
+        })
+        .collect();
+
+    tracing::info!(count = rows.len(), "Listed priority schemes");
+    ctx.renderer.render(&rows)
+}
+
+pub async fn create_scheme(
+    ctx: &JiraContext<'_>,
+    name: &str,
+    priorities: &[String],
+    default: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    let available = fetch_priorities(ctx).await?;
+    let priority_ids = priorities
+        .iter()
+        .map(|p| find_priority(&available, p))
+        .collect::<Result<Vec<_>>>()?;
+    let default_id = find_priority(&available, default)?;
+    if !priority_ids.contains(&default_id) {
+        bail!("Default priority '{default}' must be one of --priorities");
+    }
+
+    let mut payload = json!({
+        "name": name,
+        "priorityIds": priority_ids,
+        "defaultPriorityId": default_id,
+    });
+    if let Some(description) = description {
+        payload["description"] = json!(description);
+    }
+    let created: Created = ctx
+        .client
+        .post("/rest/api/3/priorityscheme", &payload)
+        .await
+        .with_context(|| format!("Failed to create priority scheme {name}"))?;
+
+    tracing::info!(id = %created.id, %name, "Priority scheme created");
+    println!("✅ Created priority scheme: {name} (ID: {})", created.id);
+    Ok(())
+}
+
+/// Move a project onto a priority scheme.
+pub async fn assign_scheme(ctx: &JiraContext<'_>, scheme: &str, project: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Project {
+        id: String,
+    }
+
+    let schemes = fetch_schemes(ctx).await?;
+    let target = schemes
+        .iter()
+        .find(|s| s.id == scheme || s.name.eq_ignore_ascii_case(scheme))
+        .with_context(|| format!("Priority scheme '{scheme}' not found"))?;
+    let details: Project = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}"))
+        .await
+        .with_context(|| format!("Failed to get project {project}"))?;
+    let project_id: i64 = details
+        .id
+        .parse()
+        .with_context(|| format!("Project {project} has a non-numeric ID '{}'", details.id))?;
+
+    let payload = json!({
+        "projects": { "add": { "ids": [project_id] } }
+    });
+    let _: Value = ctx
+        .client
+        .put(
+            &format!("/rest/api/3/priorityscheme/{}", target.id),
+            &payload,
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to assign priority scheme {} to project {project}",
+                target.name
+            )
+        })?;
+
+    tracing::info!(scheme = %target.id, %project, "Priority scheme assigned");
+    println!(
+        "✅ Project {project} now uses priority scheme {}",
+        target.name
+    );
+    Ok(())
+}
+
+async fn fetch_priorities(ctx: &JiraContext<'_>) -> Result<Vec<Priority>> {
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    everything
+        .collect(50, 50, Paging::Offset, |request| async move {
+            let page: PriorityPage<Priority> = ctx
+                .client
+                .get(&format!(
+                    "/rest/api/3/priority/search?startAt={}&maxResults={}",
+                    request.start, request.size
+                ))
+                .await
+                .context("Failed to list priorities")?;
+            Ok(Page::offset(page.values, !page.is_last))
+        })
+        .await
+}
+
+async fn fetch_schemes(ctx: &JiraContext<'_>) -> Result<Vec<PriorityScheme>> {
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    everything
+        .collect(50, 50, Paging::Offset, |request| async move {
+            let page: PriorityPage<PriorityScheme> = ctx
+                .client
+                .get(&format!(
+                    "/rest/api/3/priorityscheme?startAt={}&maxResults={}&expand=priorities,projects",
+                    request.start, request.size
+                ))
+                .await
+                .context("Failed to list priority schemes")?;
+            Ok(Page::offset(page.values, !page.is_last))
+        })
+        .await
+}
+
+async fn resolve_priority_id(ctx: &JiraContext<'_>, priority: &str) -> Result<String> {
+    find_priority(&fetch_priorities(ctx).await?, priority)
+}
+
+/// Match a priority by ID or (case-insensitive) name.
+fn find_priority(priorities: &[Priority], wanted: &str) -> Result<String> {
+    priorities
+        .iter()
+        .find(|p| p.id == wanted || p.name.eq_ignore_ascii_case(wanted))
+        .map(|p| p.id.clone())
+        .with_context(|| {
+            let names: Vec<&str> = priorities.iter().map(|p| p.name.as_str()).collect();
+            format!(
+                "Priority '{wanted}' not found. Available: {}",
+                names.join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_priority() {
+        let priorities: Vec<Priority> = serde_json::from_value(json!([
+            { "id": "1", "name": "Highest", "statusColor": "#d04437" },
+            { "id": "3", "name": "Medium", "isDefault": true }
+        ]))
+        .unwrap();
+        assert_eq!(find_priority(&priorities, "medium").unwrap(), "3");
+        assert_eq!(find_priority(&priorities, "1").unwrap(), "1");
+        let err = find_priority(&priorities, "Blocker").unwrap_err();
+        assert!(err.to_string().contains("Available: Highest, Medium"));
+    }
+}
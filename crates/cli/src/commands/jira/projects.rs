@@ -1,26 +1,33 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
 
 // Project Operations
 
-pub async fn list_projects(ctx: &JiraContext<'_>) -> Result<()> {
+pub async fn list_projects(ctx: &JiraContext<'_>, include_archived: bool) -> Result<()> {
     #[derive(Deserialize)]
     struct ProjectsResponse {
         #[serde(default)]
         values: Vec<Project>,
+        #[serde(rename = "isLast", default = "default_true")]
+        is_last: bool,
     }
 
     #[derive(Deserialize)]
     struct Project {
         key: String,
         name: String,
-        #[serde(default)]
+        #[serde(rename = "projectTypeKey", default)]
         project_type_key: Option<String>,
         #[serde(default)]
         lead: Option<UserField>,
+        #[serde(rename = "projectCategory", default)]
+        category: Option<Category>,
+        #[serde(default)]
+        archived: bool,
     }
 
     #[derive(Deserialize)]
@@ -29,11 +36,40 @@ pub async fn list_projects(ctx: &JiraContext<'_>) -> Result<()> {
         display_name: String,
     }
 
-    let response: ProjectsResponse = ctx
-        .client
-        .get("/rest/api/3/project/search")
-        .await
-        .context("Failed to list projects")?;
+    #[derive(Deserialize)]
+    struct Category {
+        name: String,
+    }
+
+    fn default_true() -> bool {
+        true
+    }
+
+    let status = if include_archived {
+        "&status=live&status=archived"
+    } else {
+        ""
+    };
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let projects: Vec<Project> = everything
+        .collect(50, 50, Paging::Offset, |request| {
+            let path = format!(
+                "/rest/api/3/project/search?expand=lead&startAt={}&maxResults={}{status}",
+                request.start, request.size
+            );
+            async move {
+                let response: ProjectsResponse = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .context("Failed to list projects")?;
+                Ok(Page::offset(response.values, !response.is_last))
+            }
+        })
+        .await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -41,10 +77,11 @@ pub async fn list_projects(ctx: &JiraContext<'_>) -> Result<()> {
         name: &'a str,
         lead: &'a str,
         project_type: &'a str,
+        category: &'a str,
+        archived: bool,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = projects
         .iter()
         .map(|project| Row {
             key: project.key.as_str(),
@@ -55,6 +92,12 @@ pub async fn list_projects(ctx: &JiraContext<'_>) -> Result<()> {
                 .map(|lead| lead.display_name.as_str())
                 .unwrap_or(""),
             project_type: project.project_type_key.as_deref().unwrap_or(""),
+            category: project
+                .category
+                .as_ref()
+                .map(|c| c.name.as_str())
+                .unwrap_or(""),
+            archived: project.archived,
         })
         .collect();
 
@@ -66,42 +109,50 @@ pub async fn list_projects(ctx: &JiraContext<'_>) -> Result<()> {
     ctx.renderer.render(&rows)
 }
 
-pub async fn get_project(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
-    #[derive(Deserialize)]
-    struct ProjectDetails {
-        #[allow(dead_code)]
-        id: String,
-        key: String,
-        name: String,
-        #[serde(default)]
-        description: Option<String>,
-        #[serde(default)]
-        lead: Option<UserField>,
-        #[serde(rename = "projectTypeKey", default)]
-        project_type_key: Option<String>,
-        #[serde(default)]
-        #[allow(dead_code)]
-        url: Option<String>,
-    }
+#[derive(Deserialize)]
+struct ProjectDetails {
+    #[allow(dead_code)]
+    id: String,
+    key: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    lead: Option<ProjectLead>,
+    #[serde(rename = "projectTypeKey", default)]
+    project_type_key: Option<String>,
+    #[serde(default)]
+    style: Option<String>,
+    #[serde(default)]
+    simplified: Option<bool>,
+    #[serde(default)]
+    #[allow(dead_code)]
+    url: Option<String>,
+}
 
-    #[derive(Deserialize)]
-    struct UserField {
-        #[serde(rename = "displayName")]
-        display_name: String,
-    }
+#[derive(Deserialize)]
+struct ProjectLead {
+    #[serde(rename = "displayName")]
+    display_name: String,
+}
 
+pub async fn get_project(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
     let project: ProjectDetails = ctx
         .client
         .get(&format!("/rest/api/3/project/{key}"))
         .await
         .with_context(|| format!("Failed to get project {key}"))?;
+    render_project(ctx, &project)
+}
 
+fn render_project(ctx: &JiraContext<'_>, project: &ProjectDetails) -> Result<()> {
     #[derive(Serialize)]
     struct Row<'a> {
         key: &'a str,
         name: &'a str,
         lead: &'a str,
         project_type: &'a str,
+        style: &'static str,
         description: &'a str,
     }
 
@@ -114,12 +165,105 @@ pub async fn get_project(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
             .map(|l| l.display_name.as_str())
             .unwrap_or(""),
         project_type: project.project_type_key.as_deref().unwrap_or(""),
+        style: ProjectStyle::from_api(project.style.as_deref(), project.simplified).label(),
         description: project.description.as_deref().unwrap_or(""),
     };
 
     ctx.renderer.render(&[row])
 }
 
+/// How a project is configured. Team-managed (formerly next-gen) projects keep
+/// their own fields and features, so several project-level endpoints reject them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectStyle {
+    CompanyManaged,
+    TeamManaged,
+}
+
+impl ProjectStyle {
+    /// `style` is `classic` or `next-gen`; older responses only carry `simplified`.
+    fn from_api(style: Option<&str>, simplified: Option<bool>) -> Self {
+        match (style, simplified) {
+            (Some("next-gen"), _) | (None, Some(true)) => Self::TeamManaged,
+            _ => Self::CompanyManaged,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CompanyManaged => "company-managed",
+            Self::TeamManaged => "team-managed",
+        }
+    }
+}
+
+pub async fn project_style(ctx: &JiraContext<'_>, key: &str) -> Result<ProjectStyle> {
+    #[derive(Deserialize)]
+    struct StyleResponse {
+        #[serde(default)]
+        style: Option<String>,
+        #[serde(default)]
+        simplified: Option<bool>,
+    }
+
+    let project: StyleResponse = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{key}"))
+        .await
+        .with_context(|| format!("Failed to get project {key}"))?;
+    Ok(ProjectStyle::from_api(
+        project.style.as_deref(),
+        project.simplified,
+    ))
+}
+
+/// Fail with a readable error instead of the API's 400 when `feature` only
+/// exists on company-managed projects.
+pub async fn ensure_company_managed(ctx: &JiraContext<'_>, key: &str, feature: &str) -> Result<()> {
+    if project_style(ctx, key).await? == ProjectStyle::TeamManaged {
+        anyhow::bail!(
+            "{feature} are not supported for team-managed project {key}; \
+             use a company-managed project or manage them in the project settings"
+        );
+    }
+    Ok(())
+}
+
+/// Team-managed projects only expose versions when the Releases feature is on.
+pub async fn ensure_releases_enabled(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct FeaturesResponse {
+        #[serde(default)]
+        features: Vec<Feature>,
+    }
+
+    #[derive(Deserialize)]
+    struct Feature {
+        feature: String,
+        state: String,
+    }
+
+    if project_style(ctx, key).await? == ProjectStyle::CompanyManaged {
+        return Ok(());
+    }
+    let response: FeaturesResponse = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{key}/features"))
+        .await
+        .with_context(|| format!("Failed to get features for project {key}"))?;
+    let enabled = response
+        .features
+        .iter()
+        .any(|f| f.feature == "jsw.agility.releases" && f.state == "ENABLED");
+    if !enabled {
+        anyhow::bail!(
+            "Versions are not available for team-managed project {key}: \
+             turn on the Releases feature in the project settings first"
+        );
+    }
+    Ok(())
+}
+
 pub async fn create_project(
     ctx: &JiraContext<'_>,
     key: &str,
@@ -127,9 +271,23 @@ pub async fn create_project(
     project_type: &str,
     lead: Option<&str>,
     description: Option<&str>,
+    if_not_exists: bool,
 ) -> Result<()> {
     use serde_json::json;
 
+    if if_not_exists {
+        let existing: Option<ProjectDetails> = ctx
+            .client
+            .get_optional(&format!("/rest/api/3/project/{key}"))
+            .await
+            .with_context(|| format!("Failed to check for project {key}"))?;
+        if let Some(project) = existing {
+            tracing::info!(%key, "Project already exists, not created");
+            println!("✅ Project {key} already exists");
+            return render_project(ctx, &project);
+        }
+    }
+
     let mut payload = json!({
         "key": key,
         "name": name,
@@ -213,9 +371,118 @@ pub async fn delete_project(ctx: &JiraContext<'_>, key: &str, force: bool) -> Re
     Ok(())
 }
 
+pub async fn archive_project(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    use serde_json::json;
+
+    let _: Value = ctx
+        .client
+        .post(&format!("/rest/api/3/project/{key}/archive"), &json!({}))
+        .await
+        .with_context(|| format!("Failed to archive project {key}"))?;
+
+    tracing::info!(%key, "Project archived successfully");
+    println!("✅ Archived project: {}", key);
+    Ok(())
+}
+
+pub async fn restore_project(ctx: &JiraContext<'_>, key: &str) -> Result<()> {
+    use serde_json::json;
+
+    let _: Value = ctx
+        .client
+        .post(&format!("/rest/api/3/project/{key}/restore"), &json!({}))
+        .await
+        .with_context(|| format!("Failed to restore project {key}"))?;
+
+    tracing::info!(%key, "Project restored successfully");
+    println!("✅ Restored project: {}", key);
+    Ok(())
+}
+
+// Project Category Functions
+
+#[derive(Deserialize)]
+struct ProjectCategory {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+async fn fetch_categories(ctx: &JiraContext<'_>) -> Result<Vec<ProjectCategory>> {
+    ctx.client
+        .get("/rest/api/3/projectCategory")
+        .await
+        .context("Failed to list project categories")
+}
+
+pub async fn list_categories(ctx: &JiraContext<'_>) -> Result<()> {
+    let categories = fetch_categories(ctx).await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        name: &'a str,
+        description: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = categories
+        .iter()
+        .map(|c| Row {
+            id: c.id.as_str(),
+            name: c.name.as_str(),
+            description: c.description.as_deref().unwrap_or(""),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        tracing::info!("No project categories defined.");
+        return Ok(());
+    }
+
+    ctx.renderer.render(&rows)
+}
+
+/// Move a project into a category, given by name or ID.
+pub async fn set_category(ctx: &JiraContext<'_>, key: &str, category: &str) -> Result<()> {
+    use serde_json::json;
+
+    let categories = fetch_categories(ctx).await?;
+    let target = categories
+        .iter()
+        .find(|c| c.id == category || c.name.eq_ignore_ascii_case(category))
+        .ok_or_else(|| {
+            let names: Vec<&str> = categories.iter().map(|c| c.name.as_str()).collect();
+            anyhow!(
+                "Project category '{}' not found. Available: {}",
+                category,
+                names.join(", ")
+            )
+        })?;
+
+    let category_id: i64 = target.id.parse().with_context(|| {
+        format!(
+            "Project category '{}' has a non-numeric ID '{}'",
+            target.name, target.id
+        )
+    })?;
+    let payload = json!({ "categoryId": category_id });
+    let _: Value = ctx
+        .client
+        .put(&format!("/rest/api/3/project/{key}"), &payload)
+        .await
+        .with_context(|| format!("Failed to set category of project {key}"))?;
+
+    tracing::info!(%key, category = %target.name, "Project category updated");
+    println!("✅ Moved project {} to category: {}", key, target.name);
+    Ok(())
+}
+
 // Component Management Functions
 
 pub async fn list_components(ctx: &JiraContext<'_>, project: &str) -> Result<()> {
+    ensure_company_managed(ctx, project, "Components").await?;
+
     #[derive(Deserialize)]
     struct Component {
         id: String,
@@ -320,6 +587,8 @@ pub async fn create_component(
 ) -> Result<()> {
     use serde_json::json;
 
+    ensure_company_managed(ctx, project, "Components").await?;
+
     let mut payload = json!({
         "name": name,
         "project": project,
@@ -397,6 +666,8 @@ pub async fn delete_component(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
 // Version Management Functions
 
 pub async fn list_versions(ctx: &JiraContext<'_>, project: &str) -> Result<()> {
+    ensure_releases_enabled(ctx, project).await?;
+
     #[derive(Deserialize)]
     struct Version {
         id: String,
@@ -441,29 +712,32 @@ pub async fn list_versions(ctx: &JiraContext<'_>, project: &str) -> Result<()> {
     ctx.renderer.render(&rows)
 }
 
-pub async fn get_version(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
-    #[derive(Deserialize)]
-    struct Version {
-        id: String,
-        name: String,
-        #[serde(default)]
-        description: Option<String>,
-        #[serde(default)]
-        released: bool,
-        #[serde(default)]
-        archived: bool,
-        #[serde(rename = "releaseDate", default)]
-        release_date: Option<String>,
-        #[serde(rename = "startDate", default)]
-        start_date: Option<String>,
-    }
+#[derive(Deserialize)]
+struct Version {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    released: bool,
+    #[serde(default)]
+    archived: bool,
+    #[serde(rename = "releaseDate", default)]
+    release_date: Option<String>,
+    #[serde(rename = "startDate", default)]
+    start_date: Option<String>,
+}
 
+pub async fn get_version(ctx: &JiraContext<'_>, id: &str) -> Result<()> {
     let version: Version = ctx
         .client
         .get(&format!("/rest/api/3/version/{id}"))
         .await
         .with_context(|| format!("Failed to get version {id}"))?;
+    render_version(ctx, &version)
+}
 
+fn render_version(ctx: &JiraContext<'_>, version: &Version) -> Result<()> {
     #[derive(Serialize)]
     struct Row<'a> {
         id: &'a str,
@@ -498,9 +772,26 @@ pub async fn create_version(
     release_date: Option<&str>,
     released: bool,
     archived: bool,
+    if_not_exists: bool,
 ) -> Result<()> {
     use serde_json::json;
 
+    ensure_releases_enabled(ctx, project).await?;
+
+    if if_not_exists {
+        let versions: Vec<Version> = ctx
+            .client
+            .get(&format!("/rest/api/3/project/{project}/versions"))
+            .await
+            .with_context(|| format!("Failed to list versions for project {project}"))?;
+        // Jira rejects version names that differ only in case
+        if let Some(existing) = versions.iter().find(|v| v.name.eq_ignore_ascii_case(name)) {
+            tracing::info!(id = %existing.id, name = %existing.name, "Version already exists, not created");
+            println!("✅ Version {} already exists in {}", existing.name, project);
+            return render_version(ctx, existing);
+        }
+    }
+
     let mut payload = json!({
         "name": name,
         "project": project,
@@ -607,3 +898,28 @@ pub async fn merge_versions(ctx: &JiraContext<'_>, from: &str, to: &str) -> Resu
     println!("✅ Merged version {} into {}", from, to);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_style() {
+        assert_eq!(
+            ProjectStyle::from_api(Some("next-gen"), Some(true)),
+            ProjectStyle::TeamManaged
+        );
+        assert_eq!(
+            ProjectStyle::from_api(None, Some(true)),
+            ProjectStyle::TeamManaged
+        );
+        assert_eq!(
+            ProjectStyle::from_api(Some("classic"), None),
+            ProjectStyle::CompanyManaged
+        );
+        assert_eq!(
+            ProjectStyle::from_api(None, None).label(),
+            "company-managed"
+        );
+    }
+}
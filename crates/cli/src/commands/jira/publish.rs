@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::utils::JiraContext;
+use crate::commands::confluence::publish::{report_body, upsert_page};
+use crate::commands::confluence::utils::ConfluenceContext;
+
+// Publish search results to a Confluence page on the same site
+
+/// Where `jira search --publish-to-confluence` writes its results.
+pub struct PageTarget {
+    pub space: String,
+    pub title: String,
+    pub parent: Option<String>,
+}
+
+/// Write the rows as a table on the target page, creating it on the first run
+/// and replacing its body on later runs.
+pub async fn publish_search<T: Serialize>(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    rows: &[T],
+    target: &PageTarget,
+) -> Result<()> {
+    let rows: Vec<Value> = rows
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<_, _>>()
+        .context("Failed to serialize search results")?;
+    let intro = format!(
+        "{} issue(s) matching JQL: {} (as of {})",
+        rows.len(),
+        jql,
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    );
+    let browse = format!("{}/browse/", ctx.client.base_url().trim_end_matches('/'));
+    let body = report_body(&intro, &rows, Some(("key", &browse)));
+
+    // Confluence is served from the same site, so the Jira client works for both
+    let confluence = ConfluenceContext {
+        client: ctx.client.clone(),
+        renderer: ctx.renderer,
+        profile: ctx.profile,
+    };
+    let page = upsert_page(
+        &confluence,
+        &target.space,
+        &target.title,
+        &body,
+        target.parent.as_deref(),
+    )
+    .await?;
+
+    tracing::info!(id = %page.id, version = page.version, issues = rows.len(), "Search results published");
+    let verb = if page.created { "Created" } else { "Updated" };
+    println!(
+        "✅ {} page: {} (ID: {}, version {}) with {} issue(s)",
+        verb,
+        target.title,
+        page.id,
+        page.version,
+        rows.len()
+    );
+    Ok(())
+}
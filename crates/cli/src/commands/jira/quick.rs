@@ -0,0 +1,314 @@
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_output::normalize_field;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::field_map::FieldAssignment;
+use super::issues;
+use super::utils::JiraContext;
+use super::validation;
+
+// One-line issue capture: "PROJ: Fix login redirect #bug @alice !high +label1"
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct QuickIssue {
+    project: Option<String>,
+    issue_type: Option<String>,
+    assignee: Option<String>,
+    priority: Option<String>,
+    labels: Vec<String>,
+    summary: String,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct User {
+    #[serde(rename = "accountId")]
+    account_id: String,
+    #[serde(rename = "displayName", default)]
+    display_name: String,
+    #[serde(rename = "emailAddress", default)]
+    email_address: Option<String>,
+}
+
+pub async fn quick_create(
+    ctx: &JiraContext<'_>,
+    text: &str,
+    default_project: Option<&str>,
+    default_type: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let parsed = parse_quick(text)?;
+    let project = parsed
+        .project
+        .as_deref()
+        .or(default_project)
+        .ok_or_else(|| anyhow!("No project given: start with 'PROJ:' or pass --project"))?;
+
+    let issue_type = resolve_issue_type(
+        ctx,
+        project,
+        parsed.issue_type.as_deref().unwrap_or(default_type),
+    )
+    .await?;
+    let priority = match &parsed.priority {
+        Some(priority) => Some(resolve_priority(ctx, priority).await?),
+        None => None,
+    };
+    let assignee = match &parsed.assignee {
+        Some(who) => Some(resolve_user(ctx, who).await?),
+        None => None,
+    };
+
+    if dry_run {
+        let preview = QuickIssue {
+            project: Some(project.to_string()),
+            issue_type: Some(issue_type),
+            assignee: assignee.map(|(_, name)| name),
+            priority,
+            ..parsed
+        };
+        println!("🔍 Dry run mode - no changes will be made:");
+        return ctx.renderer.render(&preview);
+    }
+
+    let mut custom = Vec::new();
+    if !parsed.labels.is_empty() {
+        custom.push(FieldAssignment {
+            id: "labels".to_string(),
+            name: "Labels".to_string(),
+            raw: parsed.labels.join(","),
+            value: json!(parsed.labels),
+        });
+    }
+
+    issues::create_issue(
+        ctx,
+        project,
+        &issue_type,
+        &parsed.summary,
+        None,
+        assignee.as_ref().map(|(id, _)| id.as_str()),
+        priority.as_deref(),
+        &[],
+        &custom,
+        false,
+    )
+    .await
+}
+
+/// Split the one-line syntax into its parts. Markers only count at the start of
+/// a word, so `#123` (a number) and words like `C#` stay in the summary.
+fn parse_quick(text: &str) -> Result<QuickIssue> {
+    let mut issue = QuickIssue::default();
+    let mut rest = text.trim();
+
+    if let Some((prefix, after)) = rest.split_once(':') {
+        let is_key = !prefix.is_empty()
+            && prefix.starts_with(|c: char| c.is_ascii_uppercase())
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if is_key {
+            issue.project = Some(prefix.to_string());
+            rest = after;
+        }
+    }
+
+    let mut summary = Vec::new();
+    for word in rest.split_whitespace() {
+        let mut chars = word.chars();
+        let marker = chars.next();
+        let value = chars.as_str();
+        let is_value = !value.is_empty() && !value.chars().all(|c| c.is_ascii_digit());
+
+        match marker {
+            Some('#') if is_value => set_once(&mut issue.issue_type, value, "issue type")?,
+            Some('@') if is_value => set_once(&mut issue.assignee, value, "assignee")?,
+            Some('!') if is_value => set_once(&mut issue.priority, value, "priority")?,
+            Some('+') if is_value => issue.labels.push(value.to_string()),
+            _ => summary.push(word),
+        }
+    }
+
+    issue.summary = summary.join(" ");
+    if issue.summary.is_empty() {
+        bail!("Quick add needs a summary, e.g. \"PROJ: Fix login redirect #bug\"");
+    }
+    Ok(issue)
+}
+
+fn set_once(slot: &mut Option<String>, value: &str, what: &str) -> Result<()> {
+    if let Some(existing) = slot {
+        bail!("More than one {what} given: '{existing}' and '{value}'");
+    }
+    *slot = Some(value.to_string());
+    Ok(())
+}
+
+/// Compare normalized names so `#user_story` matches "User Story".
+fn find_named<'a, T>(
+    candidates: &'a [T],
+    wanted: &str,
+    name: impl Fn(&T) -> &str,
+) -> Option<&'a T> {
+    let wanted = normalize_field(wanted);
+    candidates
+        .iter()
+        .find(|c| normalize_field(name(c)) == wanted)
+}
+
+async fn resolve_issue_type(ctx: &JiraContext<'_>, project: &str, wanted: &str) -> Result<String> {
+    let types = validation::fetch_issue_types(ctx, project).await?;
+
+    match find_named(&types, wanted, |t| &t.name) {
+        Some(found) => Ok(found.name.clone()),
+        None => {
+            let available: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+            bail!(
+                "Issue type '{wanted}' is not available in project {project}. Available: {}",
+                available.join(", ")
+            )
+        }
+    }
+}
+
+async fn resolve_priority(ctx: &JiraContext<'_>, wanted: &str) -> Result<String> {
+    let priorities: Vec<Named> = ctx
+        .client
+        .get("/rest/api/3/priority")
+        .await
+        .context("Failed to list priorities")?;
+
+    match find_named(&priorities, wanted, |p| &p.name) {
+        Some(found) => {
+            tracing::debug!(id = %found.id, name = %found.name, "Resolved priority");
+            Ok(found.name.clone())
+        }
+        None => {
+            let available: Vec<&str> = priorities.iter().map(|p| p.name.as_str()).collect();
+            bail!(
+                "Unknown priority '{wanted}'. Available: {}",
+                available.join(", ")
+            )
+        }
+    }
+}
+
+/// `(account ID, display name)` for `me` or a user search term.
+async fn resolve_user(ctx: &JiraContext<'_>, who: &str) -> Result<(String, String)> {
+    if who.eq_ignore_ascii_case("me") {
+        let me: User = ctx
+            .client
+            .get("/rest/api/3/myself")
+            .await
+            .context("Failed to fetch the current user")?;
+        return Ok((me.account_id, me.display_name));
+    }
+
+    let users: Vec<User> = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/user/search?query={}",
+            urlencoding::encode(who)
+        ))
+        .await
+        .with_context(|| format!("Failed to look up user {who}"))?;
+
+    pick_user(users, who).map(|u| (u.account_id, u.display_name))
+}
+
+/// A single search hit, or the one whose display name or email user part is exactly `who`.
+fn pick_user(users: Vec<User>, who: &str) -> Result<User> {
+    let count = users.len();
+    let mut exact: Vec<User> = Vec::new();
+    let mut all = users.into_iter();
+    if count == 1 {
+        return Ok(all.next().expect("one user"));
+    }
+    for user in all {
+        let email_user = user
+            .email_address
+            .as_deref()
+            .and_then(|email| email.split('@').next())
+            .unwrap_or("");
+        if user.display_name.eq_ignore_ascii_case(who) || email_user.eq_ignore_ascii_case(who) {
+            exact.push(user);
+        }
+    }
+
+    match (count, exact.len()) {
+        (0, _) => bail!("No Jira user found for '@{who}'"),
+        (_, 1) => Ok(exact.remove(0)),
+        _ => bail!("'@{who}' matches {count} users; use a more specific name or email"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quick() {
+        assert_eq!(
+            parse_quick("PROJ: Fix login redirect #bug @alice !high +label1 +auth").unwrap(),
+            QuickIssue {
+                project: Some("PROJ".to_string()),
+                issue_type: Some("bug".to_string()),
+                assignee: Some("alice".to_string()),
+                priority: Some("high".to_string()),
+                labels: vec!["label1".to_string(), "auth".to_string()],
+                summary: "Fix login redirect".to_string(),
+            }
+        );
+
+        let plain = parse_quick("Retry: C# client drops #123 on reconnect !").unwrap();
+        assert_eq!(plain.project, None);
+        assert_eq!(plain.summary, "Retry: C# client drops #123 on reconnect !");
+
+        assert!(parse_quick("OPS: #bug @me").is_err());
+        assert!(parse_quick("OPS: Outage #bug #incident")
+            .unwrap_err()
+            .to_string()
+            .contains("More than one issue type"));
+    }
+
+    #[test]
+    fn test_matching() {
+        let types = vec![
+            Named {
+                id: "1".to_string(),
+                name: "User Story".to_string(),
+            },
+            Named {
+                id: "2".to_string(),
+                name: "Sub-task".to_string(),
+            },
+        ];
+        let find = |wanted| find_named(&types, wanted, |t| &t.name);
+        assert_eq!(find("user_story").unwrap().id, "1");
+        assert_eq!(find("subtask").unwrap().id, "2");
+        assert!(find("epic").is_none());
+
+        let user = |name: &str, email: &str| User {
+            account_id: format!("id-{name}"),
+            display_name: name.to_string(),
+            email_address: Some(email.to_string()),
+        };
+        let picked = pick_user(
+            vec![
+                user("Alice Smith", "alice@example.com"),
+                user("Alicia Keys", "alicia@example.com"),
+            ],
+            "alice",
+        )
+        .unwrap();
+        assert_eq!(picked.account_id, "id-Alice Smith");
+        assert!(pick_user(Vec::new(), "bob").is_err());
+    }
+}
@@ -0,0 +1,460 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::OutputFormat;
+use chrono::{DateTime, Local, Utc};
+use croner::Cron;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::adf;
+use super::bulk::{resolve_account_id, search_issue_keys};
+use super::utils::JiraContext;
+
+// Recurring tickets: `recurring add` records a YAML issue template and a cron
+// expression in a per-profile file, and `recurring run`, called from cron or
+// CI, creates the issues that have fallen due since their last run. Each
+// issue carries a `recurring-<name>` label, and no new one is created while an
+// earlier one is still open.
+
+/// An issue template file for `jira recurring add --template FILE`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IssueTemplate {
+    /// Project key the issue is created in
+    project: String,
+    /// Issue type name (defaults to Task)
+    #[serde(default = "default_issue_type")]
+    issue_type: String,
+    /// `{date}` is replaced by the date of the run
+    summary: String,
+    /// Plain text; `{date}` is replaced as in the summary
+    #[serde(default)]
+    description: Option<String>,
+    /// Account ID or email
+    #[serde(default)]
+    assignee: Option<String>,
+    /// Priority name, e.g. High
+    #[serde(default)]
+    priority: Option<String>,
+    /// Labels added next to the `recurring-<name>` marker label
+    #[serde(default)]
+    labels: Vec<String>,
+    /// Component names
+    #[serde(default)]
+    components: Vec<String>,
+}
+
+fn default_issue_type() -> String {
+    "Task".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Entry {
+    name: String,
+    template: PathBuf,
+    cron: String,
+    added_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_run: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_issue: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Recurring {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct EntryRow {
+    name: String,
+    cron: String,
+    template: String,
+    next_run: String,
+    last_issue: String,
+    last_error: String,
+}
+
+/// Register `template` to be created on the `cron` schedule (local time).
+pub fn add(ctx: &JiraContext<'_>, template: &Path, cron: &str, name: Option<&str>) -> Result<()> {
+    let schedule = parse_cron(cron)?;
+    let template = template
+        .canonicalize()
+        .with_context(|| format!("Failed to read template: {}", template.display()))?;
+    let parsed = load_template(&template)?;
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => template
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    let name = slug(&name);
+    if name.is_empty() {
+        bail!("Pass --name: the template file name has no usable characters");
+    }
+
+    let path = recurring_path(ctx.profile)?;
+    let mut recurring = load(&path)?;
+    if recurring.entries.iter().any(|e| e.name == name) {
+        bail!("A recurring issue named '{name}' already exists; remove it first or pass --name");
+    }
+    let added_at = Utc::now();
+    let next = next_run(&schedule, added_at)?;
+    recurring.entries.push(Entry {
+        name: name.clone(),
+        template,
+        cron: cron.to_string(),
+        added_at,
+        last_run: None,
+        last_issue: None,
+        last_error: None,
+    });
+    save(&path, &recurring)?;
+
+    tracing::info!(%name, %cron, "Recurring issue added");
+    println!(
+        "✅ Added recurring issue '{name}' in {} (label {}), next due {}",
+        parsed.project,
+        marker_label(&name),
+        format_local(next)
+    );
+    println!("   Call `atlassian-cli jira recurring run` from cron or CI to create issues when they fall due");
+    Ok(())
+}
+
+pub fn list(ctx: &JiraContext<'_>) -> Result<()> {
+    let recurring = load(&recurring_path(ctx.profile)?)?;
+    if recurring.entries.is_empty() && ctx.renderer.format() == OutputFormat::Table {
+        println!("No recurring issues");
+        return Ok(());
+    }
+
+    let rows: Vec<EntryRow> = recurring
+        .entries
+        .into_iter()
+        .map(|e| EntryRow {
+            next_run: parse_cron(&e.cron)
+                .and_then(|schedule| next_run(&schedule, e.last_run.unwrap_or(e.added_at)))
+                .map(format_local)
+                .unwrap_or_default(),
+            name: e.name,
+            cron: e.cron,
+            template: e.template.display().to_string(),
+            last_issue: e.last_issue.unwrap_or_default(),
+            last_error: e.last_error.unwrap_or_default(),
+        })
+        .collect();
+    ctx.renderer.render(&rows)
+}
+
+pub fn remove(ctx: &JiraContext<'_>, name: &str) -> Result<()> {
+    let path = recurring_path(ctx.profile)?;
+    let mut recurring = load(&path)?;
+    let before = recurring.entries.len();
+    recurring.entries.retain(|e| e.name != name);
+    if recurring.entries.len() == before {
+        bail!("No recurring issue named '{name}'");
+    }
+    save(&path, &recurring)?;
+
+    tracing::info!(%name, "Recurring issue removed");
+    println!("✅ Removed recurring issue '{name}'");
+    Ok(())
+}
+
+/// Create an issue for every entry that fell due since it last ran. Missed
+/// occurrences are not caught up one by one: a single issue covers them, and
+/// `{date}` is the date of the run.
+pub async fn run(ctx: &JiraContext<'_>, dry_run: bool) -> Result<()> {
+    let path = recurring_path(ctx.profile)?;
+    let now = Utc::now();
+    let mut due = Vec::new();
+    for entry in load(&path)?.entries {
+        if is_due(&entry, now)? {
+            due.push(entry);
+        }
+    }
+    if due.is_empty() {
+        println!("No recurring issues due");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for entry in due {
+        let result = create_due(ctx, &entry, now, dry_run).await;
+        // Nothing was created, so the entry must stay due
+        if dry_run || ctx.client.is_explaining() {
+            result?;
+            continue;
+        }
+
+        // Reload so entries changed while creating are kept
+        let mut recurring = load(&path)?;
+        let Some(kept) = recurring.entries.iter_mut().find(|e| e.name == entry.name) else {
+            continue;
+        };
+        // A failed entry stays due, so the next run retries it
+        match result {
+            Ok(created) => {
+                kept.last_run = Some(now);
+                kept.last_error = None;
+                if created.is_some() {
+                    kept.last_issue = created;
+                }
+            }
+            Err(err) => {
+                let message = format!("{err:#}");
+                eprintln!(
+                    "Failed to create recurring issue '{}': {message}",
+                    entry.name
+                );
+                kept.last_error = Some(message);
+                failed += 1;
+            }
+        }
+        save(&path, &recurring)?;
+    }
+
+    if failed > 0 {
+        bail!("{failed} recurring issue(s) could not be created");
+    }
+    Ok(())
+}
+
+/// Create the entry's issue unless one is still open. Returns the new key.
+async fn create_due(
+    ctx: &JiraContext<'_>,
+    entry: &Entry,
+    now: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Option<String>> {
+    let template = load_template(&entry.template)?;
+    let label = marker_label(&entry.name);
+    let open = search_issue_keys(
+        ctx,
+        &format!(
+            "project = \"{}\" AND labels = \"{label}\" AND statusCategory != Done",
+            template.project
+        ),
+    )
+    .await?;
+    if let Some(key) = open.first() {
+        println!(
+            "⏭️  '{}': {key} is still open, not creating another",
+            entry.name
+        );
+        return Ok(None);
+    }
+
+    let date = now.with_timezone(&Local).format("%Y-%m-%d").to_string();
+    let mut fields = issue_fields(&template, &label, &date);
+    if dry_run {
+        println!(
+            "🔍 Would create {} in {}: {}",
+            template.issue_type, template.project, fields["summary"]
+        );
+        return Ok(None);
+    }
+    if let Some(assignee) = &template.assignee {
+        fields["assignee"] = json!({ "id": resolve_account_id(ctx, assignee).await? });
+    }
+
+    #[derive(Deserialize)]
+    struct CreateResponse {
+        key: String,
+    }
+    let response: CreateResponse = ctx
+        .client
+        .post("/rest/api/3/issue", &json!({ "fields": fields }))
+        .await
+        .with_context(|| format!("Failed to create issue in {}", template.project))?;
+
+    tracing::info!(name = %entry.name, key = %response.key, "Recurring issue created");
+    println!("✅ '{}': created {}", entry.name, response.key);
+    Ok(Some(response.key))
+}
+
+fn issue_fields(template: &IssueTemplate, label: &str, date: &str) -> Value {
+    let mut labels = template.labels.clone();
+    labels.push(label.to_string());
+    let mut fields = json!({
+        "project": { "key": template.project },
+        "issuetype": { "name": template.issue_type },
+        "summary": template.summary.replace("{date}", date),
+        "labels": labels,
+    });
+    if let Some(description) = &template.description {
+        fields["description"] = adf::text_doc(&description.replace("{date}", date));
+    }
+    if let Some(priority) = &template.priority {
+        fields["priority"] = json!({ "name": priority });
+    }
+    if !template.components.is_empty() {
+        let components: Vec<Value> = template
+            .components
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect();
+        fields["components"] = json!(components);
+    }
+    fields
+}
+
+/// Whether a scheduled time has passed since the entry last ran.
+fn is_due(entry: &Entry, now: DateTime<Utc>) -> Result<bool> {
+    let schedule = parse_cron(&entry.cron)?;
+    Ok(next_run(&schedule, entry.last_run.unwrap_or(entry.added_at))? <= now)
+}
+
+fn parse_cron(cron: &str) -> Result<Cron> {
+    Cron::new(cron)
+        .parse()
+        .with_context(|| format!("Invalid cron expression '{cron}'"))
+}
+
+/// The first scheduled time after `after`, in local time.
+fn next_run(schedule: &Cron, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    schedule
+        .find_next_occurrence(&after.with_timezone(&Local), false)
+        .map(|next| next.with_timezone(&Utc))
+        .context("The cron expression never matches")
+}
+
+fn load_template(path: &Path) -> Result<IssueTemplate> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read template: {}", path.display()))?;
+    serde_yaml::from_str(&raw).with_context(|| format!("Invalid template: {}", path.display()))
+}
+
+fn marker_label(name: &str) -> String {
+    format!("recurring-{name}")
+}
+
+/// Lowercase letters, digits and dashes, usable in a label.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn format_local(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M %Z")
+        .to_string()
+}
+
+fn recurring_path(profile: &str) -> Result<PathBuf> {
+    let profile: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let home = dirs::home_dir().context("Cannot find the home directory")?;
+    Ok(home
+        .join(".atlassian-cli")
+        .join("recurring")
+        .join(format!("{profile}.json")))
+}
+
+fn load(path: &Path) -> Result<Recurring> {
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid recurring issues file {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Recurring::default()),
+        Err(err) => Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Write through a temporary file so a concurrent run never reads a
+/// half-written file.
+fn save(path: &Path, recurring: &Recurring) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(recurring)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(cron: &str, added_at: &str, last_run: Option<&str>) -> Entry {
+        Entry {
+            name: "ops-weekly".to_string(),
+            template: PathBuf::from("ops-weekly.yaml"),
+            cron: cron.to_string(),
+            added_at: added_at.parse().unwrap(),
+            last_run: last_run.map(|at| at.parse().unwrap()),
+            last_issue: None,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_is_due() {
+        // Every minute: due once a minute boundary has passed since the last run
+        let now: DateTime<Utc> = "2024-06-03T12:00:30Z".parse().unwrap();
+        let every_minute = entry("* * * * *", "2024-06-03T11:57:10Z", None);
+        assert!(is_due(&every_minute, now).unwrap());
+        let ran = entry(
+            "* * * * *",
+            "2024-06-03T11:57:10Z",
+            Some("2024-06-03T12:00:05Z"),
+        );
+        assert!(!is_due(&ran, now).unwrap());
+
+        assert!(is_due(&entry("not cron", "2024-06-03T11:57:10Z", None), now).is_err());
+    }
+
+    #[test]
+    fn test_issue_fields() {
+        let template: IssueTemplate = serde_yaml::from_str(
+            r#"
+project: OPS
+summary: "Weekly ops review {date}"
+labels: [ops]
+components: [Infra]
+"#,
+        )
+        .unwrap();
+        let fields = issue_fields(&template, "recurring-ops-weekly", "2024-06-03");
+        assert_eq!(
+            fields,
+            json!({
+                "project": { "key": "OPS" },
+                "issuetype": { "name": "Task" },
+                "summary": "Weekly ops review 2024-06-03",
+                "labels": ["ops", "recurring-ops-weekly"],
+                "components": [{ "name": "Infra" }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Ops Weekly"), "ops-weekly");
+        assert_eq!(slug("  on_call/rotation! "), "on-call-rotation");
+        assert_eq!(slug("***"), "");
+    }
+}
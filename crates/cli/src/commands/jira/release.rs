@@ -0,0 +1,165 @@
+use anyhow::{bail, Context, Result};
+use atlassian_cli_bulk::BulkExecutor;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::bulk::search_issue_keys;
+use super::utils::JiraContext;
+use crate::commands::cancel;
+
+// Composite release flow: create (or reuse) a fix version, assign it to the issues
+// matching a JQL query, and optionally mark it released.
+
+#[derive(Deserialize)]
+struct Version {
+    id: String,
+    name: String,
+    #[serde(default)]
+    released: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_release(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    name: &str,
+    from_jql: &str,
+    description: Option<&str>,
+    release: bool,
+    release_date: Option<&str>,
+    allow_unresolved: bool,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let issue_keys = search_issue_keys(ctx, from_jql).await?;
+    let existing = find_version(ctx, project, name).await?;
+
+    if dry_run {
+        println!("🔍 Dry run mode - no changes will be made:");
+        match &existing {
+            Some(v) => println!("  Would reuse existing version {} (ID: {})", v.name, v.id),
+            None => println!("  Would create version {} in {}", name, project),
+        }
+        for key in &issue_keys {
+            println!("  Would set fix version {} on {}", name, key);
+        }
+        if release {
+            println!("  Would mark {} as released", name);
+        }
+        return Ok(());
+    }
+
+    let version = match existing {
+        Some(v) => {
+            if v.released {
+                tracing::warn!(name = %v.name, "Version is already released");
+            }
+            println!("Using existing version: {} (ID: {})", v.name, v.id);
+            v
+        }
+        None => {
+            let mut payload = json!({
+                "name": name,
+                "project": project,
+            });
+            if let Some(desc) = description {
+                payload["description"] = json!(desc);
+            }
+
+            let created: Version = ctx
+                .client
+                .post("/rest/api/3/version", &payload)
+                .await
+                .with_context(|| format!("Failed to create version {name} in {project}"))?;
+            println!("✅ Created version: {} (ID: {})", created.name, created.id);
+            created
+        }
+    };
+
+    if issue_keys.is_empty() {
+        println!("No issues matched the JQL query");
+    } else {
+        println!(
+            "Assigning fix version {} to {} issues",
+            version.name,
+            issue_keys.len()
+        );
+
+        let executor = BulkExecutor::new(concurrency, false).with_cancellation(cancel::token());
+        let client = ctx.client.clone();
+        let version_id = version.id.clone();
+
+        executor
+            .run(issue_keys.clone(), move |key| {
+                let client = client.clone();
+                let version_id = version_id.clone();
+                async move {
+                    let payload = json!({
+                        "update": { "fixVersions": [{ "add": { "id": version_id } }] }
+                    });
+                    let _: Value = client
+                        .put(&format!("/rest/api/3/issue/{key}"), &payload)
+                        .await
+                        .with_context(|| format!("Failed to set fix version on {key}"))?;
+                    tracing::info!(%key, "Fix version assigned");
+                    Ok(())
+                }
+            })
+            .await?;
+    }
+
+    if release {
+        let unresolved = unresolved_issue_count(ctx, &version.id).await?;
+        if unresolved > 0 && !allow_unresolved {
+            bail!(
+                "Not releasing {}: {unresolved} issue(s) in it are unresolved; resolve them or pass --allow-unresolved",
+                version.name
+            );
+        }
+        let date = release_date
+            .map(str::to_string)
+            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+        let payload = json!({ "released": true, "releaseDate": date });
+
+        let _: Value = ctx
+            .client
+            .put(&format!("/rest/api/3/version/{}", version.id), &payload)
+            .await
+            .with_context(|| format!("Failed to release version {}", version.name))?;
+        println!("✅ Released version {} on {}", version.name, date);
+    }
+
+    println!(
+        "✅ Release {} complete: {} issues assigned",
+        version.name,
+        issue_keys.len()
+    );
+    Ok(())
+}
+
+async fn unresolved_issue_count(ctx: &JiraContext<'_>, version_id: &str) -> Result<u64> {
+    #[derive(Deserialize)]
+    struct Counts {
+        #[serde(rename = "issuesUnresolvedCount", default)]
+        issues_unresolved_count: u64,
+    }
+
+    let counts: Counts = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/version/{version_id}/unresolvedIssueCount"
+        ))
+        .await
+        .with_context(|| format!("Failed to count unresolved issues in version {version_id}"))?;
+    Ok(counts.issues_unresolved_count)
+}
+
+async fn find_version(ctx: &JiraContext<'_>, project: &str, name: &str) -> Result<Option<Version>> {
+    let versions: Vec<Version> = ctx
+        .client
+        .get(&format!("/rest/api/3/project/{project}/versions"))
+        .await
+        .with_context(|| format!("Failed to list versions for project {project}"))?;
+
+    Ok(versions.into_iter().find(|v| v.name == name))
+}
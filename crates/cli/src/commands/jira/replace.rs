@@ -0,0 +1,339 @@
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_bulk::BulkExecutor;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::field_map;
+use super::issues::SEARCH_PAGE_SIZE;
+use super::utils::JiraContext;
+use crate::commands::cancel;
+use crate::commands::dry_run::{render_bulk_diff, TargetChange};
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Find-and-replace across a text field of many issues. Rich-text (ADF) fields
+// are edited node by node: text, link targets and smart-link URLs.
+
+/// What `--find` matches: a literal substring or a regular expression.
+#[derive(Debug)]
+pub enum Matcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    pub fn new(find: &str, regex: bool) -> Result<Self> {
+        if find.is_empty() {
+            bail!("--find must not be empty");
+        }
+        if regex {
+            let re = Regex::new(find).with_context(|| format!("Invalid regex '{find}'"))?;
+            Ok(Matcher::Regex(re))
+        } else {
+            Ok(Matcher::Literal(find.to_string()))
+        }
+    }
+
+    /// `text` with every match replaced, or `None` when nothing matched.
+    /// Regex replacements may use `$1`/`${name}` capture groups.
+    fn replace(&self, text: &str, replacement: &str) -> Option<String> {
+        match self {
+            Matcher::Literal(find) => text
+                .contains(find.as_str())
+                .then(|| text.replace(find.as_str(), replacement)),
+            Matcher::Regex(re) => re
+                .is_match(text)
+                .then(|| re.replace_all(text, replacement).into_owned()),
+        }
+    }
+}
+
+/// A changed string inside a field value: the value before and after.
+#[derive(Debug, PartialEq)]
+struct Edit {
+    current: String,
+    proposed: String,
+}
+
+/// Apply the replacement to a plain string field or an ADF document. Returns
+/// the new value and each string that changed, or `None` when nothing matched.
+/// Matches that span differently formatted text nodes are not found.
+fn replace_in_value(
+    value: &Value,
+    matcher: &Matcher,
+    replacement: &str,
+) -> Option<(Value, Vec<Edit>)> {
+    fn visit(node: &mut Value, matcher: &Matcher, replacement: &str, edits: &mut Vec<Edit>) {
+        let mut edit = |slot: &mut Value| {
+            if let Some(current) = slot.as_str() {
+                if let Some(proposed) = matcher.replace(current, replacement) {
+                    edits.push(Edit {
+                        current: current.to_string(),
+                        proposed: proposed.clone(),
+                    });
+                    *slot = Value::String(proposed);
+                }
+            }
+        };
+
+        match node {
+            Value::Object(map) => {
+                if map.get("type").and_then(Value::as_str) == Some("text") {
+                    if let Some(text) = map.get_mut("text") {
+                        edit(text);
+                    }
+                }
+                if let Some(Value::Object(attrs)) = map.get_mut("attrs") {
+                    for key in ["href", "url"] {
+                        if let Some(target) = attrs.get_mut(key) {
+                            edit(target);
+                        }
+                    }
+                }
+                for (key, child) in map.iter_mut() {
+                    if key == "content" || key == "marks" {
+                        visit(child, matcher, replacement, edits);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    visit(item, matcher, replacement, edits);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut updated = value.clone();
+    let mut edits = Vec::new();
+    match &mut updated {
+        Value::String(_) => {
+            if let Some(proposed) = value.as_str().and_then(|v| matcher.replace(v, replacement)) {
+                edits.push(Edit {
+                    current: value.as_str().unwrap_or_default().to_string(),
+                    proposed: proposed.clone(),
+                });
+                updated = Value::String(proposed);
+            }
+        }
+        Value::Object(_) => visit(&mut updated, matcher, replacement, &mut edits),
+        _ => {}
+    }
+
+    (!edits.is_empty()).then_some((updated, edits))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn bulk_replace(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    field: &str,
+    find: &str,
+    replacement: &str,
+    regex: bool,
+    notify: bool,
+    dry_run: bool,
+    concurrency: usize,
+) -> Result<()> {
+    let matcher = Matcher::new(find, regex)?;
+    let fields = field_map::load(ctx).await?;
+    let meta = fields.find(field).ok_or_else(|| {
+        anyhow!(
+            "Unknown field '{field}'. Run `atlassian-cli jira fields list` to see available fields"
+        )
+    })?;
+    let is_text = meta
+        .schema
+        .as_ref()
+        .is_some_and(|s| s.field_type == "string");
+    if !is_text {
+        bail!("Field '{}' ({}) is not a text field", meta.name, meta.id);
+    }
+    let field_id = meta.id.clone();
+    let field_name = meta.name.clone();
+
+    let issues = fetch_field_values(ctx, jql, &field_id).await?;
+    let matched = issues.len();
+    let updates: Vec<(String, Value, Vec<Edit>)> = issues
+        .into_iter()
+        .filter_map(|(key, value)| {
+            replace_in_value(&value, &matcher, replacement)
+                .map(|(updated, edits)| (key, updated, edits))
+        })
+        .collect();
+    tracing::info!(
+        matched,
+        to_update = updates.len(),
+        field = %field_id,
+        "Computed replacements"
+    );
+
+    if updates.is_empty() {
+        println!("No issues contain '{find}' in {field_name} ({matched} matched the JQL query)");
+        return Ok(());
+    }
+
+    if dry_run {
+        let changes: Vec<TargetChange> = updates
+            .iter()
+            .flat_map(|(key, _, edits)| {
+                edits.iter().map(|edit| TargetChange {
+                    target: key.clone(),
+                    field: field_name.clone(),
+                    current: edit.current.clone(),
+                    proposed: edit.proposed.clone(),
+                })
+            })
+            .collect();
+        return render_bulk_diff(ctx.renderer, &changes);
+    }
+
+    println!(
+        "Found {} issues to update ({} matched the JQL query)",
+        updates.len(),
+        matched
+    );
+
+    let items: Vec<(String, Value)> = updates
+        .into_iter()
+        .map(|(key, value, _)| (key, value))
+        .collect();
+    let executor = BulkExecutor::new(concurrency, false).with_cancellation(cancel::token());
+    let client = ctx.client.clone();
+    executor
+        .run(items, move |(key, value)| {
+            let client = client.clone();
+            let field_id = field_id.clone();
+            async move {
+                let payload = json!({ "fields": { field_id: value } });
+                let _: Value = client
+                    .put(
+                        &format!("/rest/api/3/issue/{key}?notifyUsers={notify}"),
+                        &payload,
+                    )
+                    .await
+                    .with_context(|| format!("Failed to update {key}"))?;
+                tracing::info!(%key, "Text replaced");
+                Ok(())
+            }
+        })
+        .await?;
+
+    println!("✅ Bulk replace completed");
+    Ok(())
+}
+
+/// Every issue matching `jql` with the raw value of one field.
+async fn fetch_field_values(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    field_id: &str,
+) -> Result<Vec<(String, Value)>> {
+    #[derive(Deserialize)]
+    struct SearchResponse {
+        issues: Vec<Value>,
+        #[serde(rename = "nextPageToken")]
+        next_page_token: Option<String>,
+    }
+
+    let everything = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let issues = everything
+        .collect(
+            SEARCH_PAGE_SIZE,
+            SEARCH_PAGE_SIZE,
+            Paging::Cursor,
+            |request| {
+                let mut query = format!(
+                    "/rest/api/3/search/jql?jql={}&maxResults={}&fields={}",
+                    urlencoding::encode(jql),
+                    request.size,
+                    urlencoding::encode(field_id)
+                );
+                if let Some(token) = &request.cursor {
+                    query.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+                }
+                async move {
+                    let response: SearchResponse = ctx
+                        .client
+                        .get(&query)
+                        .await
+                        .context("Failed to execute search")?;
+                    Ok(Page::cursor(response.issues, response.next_page_token))
+                }
+            },
+        )
+        .await?;
+
+    Ok(issues
+        .into_iter()
+        .filter_map(|mut issue| {
+            let key = issue["key"].as_str()?.to_string();
+            let value = issue["fields"][field_id].take();
+            Some((key, value))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_in_plain_string() {
+        let matcher = Matcher::new("old-domain.com", false).unwrap();
+        let (value, edits) = replace_in_value(
+            &json!("See https://old-domain.com/wiki"),
+            &matcher,
+            "new-domain.com",
+        )
+        .unwrap();
+        assert_eq!(value, json!("See https://new-domain.com/wiki"));
+        assert_eq!(edits.len(), 1);
+        assert!(replace_in_value(&json!("unrelated"), &matcher, "x").is_none());
+        assert!(replace_in_value(&Value::Null, &matcher, "x").is_none());
+    }
+
+    #[test]
+    fn test_replace_in_adf_text_links_and_cards() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                { "type": "paragraph", "content": [
+                    { "type": "text", "text": "Docs at old-domain.com" },
+                    { "type": "text", "text": "runbook", "marks": [
+                        { "type": "link", "attrs": { "href": "https://old-domain.com/runbook" } }
+                    ] },
+                    { "type": "inlineCard", "attrs": { "url": "https://old-domain.com/x" } }
+                ] }
+            ]
+        });
+        let matcher = Matcher::new(r"old-domain\.com", true).unwrap();
+        let (value, edits) = replace_in_value(&doc, &matcher, "new-domain.com").unwrap();
+
+        assert_eq!(edits.len(), 3);
+        let paragraph = &value["content"][0]["content"];
+        assert_eq!(paragraph[0]["text"], "Docs at new-domain.com");
+        assert_eq!(paragraph[1]["text"], "runbook");
+        assert_eq!(
+            paragraph[1]["marks"][0]["attrs"]["href"],
+            "https://new-domain.com/runbook"
+        );
+        assert_eq!(paragraph[2]["attrs"]["url"], "https://new-domain.com/x");
+    }
+
+    #[test]
+    fn test_regex_capture_groups() {
+        let matcher = Matcher::new(r"JIRA-(\d+)", true).unwrap();
+        assert_eq!(
+            matcher.replace("see JIRA-12", "DEV-$1"),
+            Some("see DEV-12".to_string())
+        );
+        assert!(Matcher::new("(", true).is_err());
+        assert!(Matcher::new("", false).is_err());
+    }
+}
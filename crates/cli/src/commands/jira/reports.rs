@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::{parse_timestamp, OutputFormat};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::export::str_at;
+use super::issues::search_all;
+use super::utils::JiraContext;
+
+// Backlog aging: issues matching a JQL query grouped by age since creation
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+struct AgedIssue {
+    key: String,
+    summary: String,
+    status: String,
+    assignee: String,
+    created: String,
+    age_days: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct AgingBucket {
+    bucket: String,
+    count: usize,
+    oldest: Vec<AgedIssue>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct AgingReport {
+    total: usize,
+    buckets: Vec<AgingBucket>,
+}
+
+#[derive(Serialize)]
+struct BucketRow<'a> {
+    bucket: &'a str,
+    count: usize,
+    oldest: String,
+}
+
+pub async fn aging_report(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    buckets: &[i64],
+    top: usize,
+    csv: Option<&Path>,
+) -> Result<()> {
+    let thresholds = thresholds(buckets)?;
+    let issues = fetch_aged_issues(ctx, jql, Utc::now()).await?;
+
+    if let Some(path) = csv {
+        write_csv(path, &issues, &thresholds)?;
+        println!("✅ Wrote {} issues to {}", issues.len(), path.display());
+    }
+
+    let report = build_report(issues, &thresholds, top);
+    tracing::info!(total = report.total, "Built aging report");
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&report);
+    }
+    if report.total == 0 {
+        println!("No issues matched the JQL query");
+        return Ok(());
+    }
+
+    let rows: Vec<BucketRow> = report
+        .buckets
+        .iter()
+        .map(|b| BucketRow {
+            bucket: &b.bucket,
+            count: b.count,
+            oldest: b
+                .oldest
+                .iter()
+                .map(|i| format!("{} ({}d)", i.key, i.age_days))
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+        .collect();
+    ctx.renderer.render(&rows)?;
+    println!("\nTotal: {} issues", report.total);
+    Ok(())
+}
+
+async fn fetch_aged_issues(
+    ctx: &JiraContext<'_>,
+    jql: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<AgedIssue>> {
+    let issues = search_all(ctx, jql, &["summary", "status", "assignee", "created"]).await?;
+    Ok(issues.iter().map(|issue| aged_issue(issue, now)).collect())
+}
+
+fn aged_issue(issue: &Value, now: DateTime<Utc>) -> AgedIssue {
+    let created = str_at(issue, "/fields/created");
+    let age_days = parse_timestamp(&created)
+        .map(|date| now.signed_duration_since(date).num_days().max(0))
+        .unwrap_or(0);
+
+    AgedIssue {
+        key: str_at(issue, "/key"),
+        summary: str_at(issue, "/fields/summary"),
+        status: str_at(issue, "/fields/status/name"),
+        assignee: str_at(issue, "/fields/assignee/displayName"),
+        created,
+        age_days,
+    }
+}
+
+/// Sorted, de-duplicated bucket boundaries in days.
+fn thresholds(buckets: &[i64]) -> Result<Vec<i64>> {
+    if buckets.iter().any(|&b| b <= 0) {
+        bail!("Bucket boundaries must be positive numbers of days");
+    }
+    let mut thresholds = buckets.to_vec();
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    Ok(thresholds)
+}
+
+/// Labels for the half-open ranges between boundaries, e.g. `0-7d`, `7-14d`, `14d+`.
+fn bucket_labels(thresholds: &[i64]) -> Vec<String> {
+    let mut labels = Vec::new();
+    let mut lower = 0;
+    for &upper in thresholds {
+        labels.push(format!("{lower}-{upper}d"));
+        lower = upper;
+    }
+    labels.push(format!("{lower}d+"));
+    labels
+}
+
+fn bucket_index(thresholds: &[i64], age_days: i64) -> usize {
+    thresholds
+        .iter()
+        .position(|&upper| age_days < upper)
+        .unwrap_or(thresholds.len())
+}
+
+fn build_report(issues: Vec<AgedIssue>, thresholds: &[i64], top: usize) -> AgingReport {
+    let mut grouped: Vec<Vec<AgedIssue>> = vec![Vec::new(); thresholds.len() + 1];
+    let total = issues.len();
+    for issue in issues {
+        grouped[bucket_index(thresholds, issue.age_days)].push(issue);
+    }
+
+    let buckets = bucket_labels(thresholds)
+        .into_iter()
+        .zip(grouped)
+        .map(|(bucket, mut issues)| {
+            issues.sort_by(|a, b| b.age_days.cmp(&a.age_days).then(a.key.cmp(&b.key)));
+            let count = issues.len();
+            issues.truncate(top);
+            AgingBucket {
+                bucket,
+                count,
+                oldest: issues,
+            }
+        })
+        .collect();
+
+    AgingReport { total, buckets }
+}
+
+fn write_csv(path: &Path, issues: &[AgedIssue], thresholds: &[i64]) -> Result<()> {
+    let labels = bucket_labels(thresholds);
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    writer.write_record([
+        "key", "summary", "status", "assignee", "created", "age_days", "bucket",
+    ])?;
+    for issue in issues {
+        writer.write_record([
+            issue.key.as_str(),
+            issue.summary.as_str(),
+            issue.status.as_str(),
+            issue.assignee.as_str(),
+            issue.created.as_str(),
+            &issue.age_days.to_string(),
+            &labels[bucket_index(thresholds, issue.age_days)],
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(key: &str, age_days: i64) -> AgedIssue {
+        AgedIssue {
+            key: key.to_string(),
+            summary: String::new(),
+            status: "Open".to_string(),
+            assignee: String::new(),
+            created: String::new(),
+            age_days,
+        }
+    }
+
+    #[test]
+    fn test_aged_issue() {
+        let now = DateTime::parse_from_rfc3339("2024-03-11T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let aged = aged_issue(
+            &json!({
+                "key": "SUP-1",
+                "fields": {
+                    "summary": "Printer on fire",
+                    "status": { "name": "Waiting for support" },
+                    "created": "2024-03-01T09:30:00.000+0000"
+                }
+            }),
+            now,
+        );
+        assert_eq!(aged.age_days, 10);
+        assert_eq!(aged.status, "Waiting for support");
+        assert_eq!(aged.assignee, "");
+    }
+
+    #[test]
+    fn test_build_report() {
+        let bounds = thresholds(&[30, 7, 14, 7]).unwrap();
+        assert_eq!(
+            bucket_labels(&bounds),
+            vec!["0-7d", "7-14d", "14-30d", "30d+"]
+        );
+
+        let issues = vec![
+            issue("SUP-1", 2),
+            issue("SUP-2", 7),
+            issue("SUP-3", 45),
+            issue("SUP-4", 90),
+            issue("SUP-5", 31),
+        ];
+        let report = build_report(issues, &bounds, 2);
+        assert_eq!(report.total, 5);
+        let counts: Vec<usize> = report.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 1, 0, 3]);
+        let oldest: Vec<&str> = report.buckets[3]
+            .oldest
+            .iter()
+            .map(|i| i.key.as_str())
+            .collect();
+        assert_eq!(oldest, vec!["SUP-4", "SUP-3"]);
+
+        assert!(thresholds(&[7, 0]).is_err());
+    }
+}
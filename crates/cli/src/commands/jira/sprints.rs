@@ -0,0 +1,352 @@
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::{parse_timestamp, OutputFormat};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::boards;
+use super::export::str_at;
+use super::utils::JiraContext;
+
+// Sprint report: committed vs completed estimates for a board's closed sprints
+
+#[derive(Debug, Deserialize)]
+struct Sprint {
+    id: u64,
+    name: String,
+    #[serde(rename = "startDate", default)]
+    start_date: Option<String>,
+    #[serde(rename = "endDate", default)]
+    end_date: Option<String>,
+    #[serde(rename = "completeDate", default)]
+    complete_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SprintPage {
+    #[serde(default)]
+    values: Vec<Sprint>,
+    #[serde(rename = "isLast", default)]
+    is_last: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct IssuePage {
+    #[serde(default)]
+    issues: Vec<Value>,
+    #[serde(default)]
+    total: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct SprintRow {
+    sprint: u64,
+    name: String,
+    start: String,
+    end: String,
+    issues: usize,
+    committed: f64,
+    added: f64,
+    completed: f64,
+    completion: String,
+}
+
+pub async fn sprint_report(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    last: usize,
+    points_field: Option<&str>,
+) -> Result<()> {
+    if last == 0 {
+        bail!("--last must be at least 1");
+    }
+    let field = match points_field {
+        Some(field) => field.to_string(),
+        None => boards::estimation_field(ctx, board_id)
+            .await?
+            .with_context(|| {
+                format!("Board {board_id} does not estimate by field; pass --points-field")
+            })?,
+    };
+
+    let mut sprints = fetch_closed_sprints(ctx, board_id).await?;
+    sprints.sort_by_key(sprint_end);
+    let sprints = &sprints[sprints.len().saturating_sub(last)..];
+
+    let mut rows = Vec::new();
+    for sprint in sprints {
+        let issues = fetch_sprint_issues(ctx, board_id, sprint.id, &field).await?;
+        rows.push(sprint_row(sprint, &issues, &field));
+    }
+    tracing::info!(board_id, sprints = rows.len(), "Built sprint report");
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&rows);
+    }
+    if rows.is_empty() {
+        println!("No closed sprints found for board {board_id}");
+        return Ok(());
+    }
+    ctx.renderer.render(&rows)?;
+    println!(
+        "\nVelocity: {} points per sprint (average of {} sprints)",
+        format_points(velocity(&rows)),
+        rows.len()
+    );
+    Ok(())
+}
+
+async fn fetch_closed_sprints(ctx: &JiraContext<'_>, board_id: u64) -> Result<Vec<Sprint>> {
+    let mut sprints = Vec::new();
+    let mut start_at = 0;
+
+    loop {
+        let page: SprintPage = ctx
+            .client
+            .get(&format!(
+                "/rest/agile/1.0/board/{board_id}/sprint?state=closed&startAt={start_at}&maxResults=50"
+            ))
+            .await
+            .with_context(|| format!("Failed to list sprints for board {board_id}"))?;
+        let count = page.values.len();
+        sprints.extend(page.values);
+
+        if page.is_last.unwrap_or(true) || count == 0 {
+            break;
+        }
+        start_at += count;
+    }
+
+    Ok(sprints)
+}
+
+async fn fetch_sprint_issues(
+    ctx: &JiraContext<'_>,
+    board_id: u64,
+    sprint_id: u64,
+    field: &str,
+) -> Result<Vec<Value>> {
+    let mut issues = Vec::new();
+
+    loop {
+        let page: IssuePage = ctx
+            .client
+            .get(&format!(
+                "/rest/agile/1.0/board/{board_id}/sprint/{sprint_id}/issue?fields=status,created,statuscategorychangedate,{field}&expand=changelog&startAt={}&maxResults=100",
+                issues.len()
+            ))
+            .await
+            .with_context(|| format!("Failed to list issues in sprint {sprint_id}"))?;
+        let count = page.issues.len();
+        issues.extend(page.issues);
+
+        if count == 0 || issues.len() >= page.total {
+            break;
+        }
+    }
+
+    Ok(issues)
+}
+
+fn sprint_end(sprint: &Sprint) -> Option<DateTime<FixedOffset>> {
+    sprint
+        .complete_date
+        .as_deref()
+        .or(sprint.end_date.as_deref())
+        .and_then(parse_timestamp)
+}
+
+/// Issues that joined the sprint after it started count as added scope rather
+/// than commitment; an issue is completed when it reached a done status before
+/// the sprint closed (carried-over work finished in a later sprint is not).
+fn sprint_row(sprint: &Sprint, issues: &[Value], field: &str) -> SprintRow {
+    let start = sprint.start_date.as_deref().and_then(parse_timestamp);
+    let end = sprint_end(sprint);
+
+    let (mut committed, mut added, mut completed) = (0.0, 0.0, 0.0);
+    for issue in issues {
+        let points = issue["fields"][field].as_f64().unwrap_or(0.0);
+        match (start, joined_sprint_at(issue, sprint.id)) {
+            (Some(start), Some(joined)) if joined > start => added += points,
+            _ => committed += points,
+        }
+
+        let done = str_at(issue, "/fields/status/statusCategory/key") == "done";
+        let done_at = parse_timestamp(&str_at(issue, "/fields/statuscategorychangedate"));
+        let done_in_sprint = match (done_at, end) {
+            (Some(done_at), Some(end)) => done_at <= end,
+            _ => true,
+        };
+        if done && done_in_sprint {
+            completed += points;
+        }
+    }
+
+    let completion = if committed > 0.0 {
+        format!("{:.0}%", completed / committed * 100.0)
+    } else {
+        "-".to_string()
+    };
+    let day = |date: Option<&str>| {
+        date.and_then(|d| d.get(..10))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    SprintRow {
+        sprint: sprint.id,
+        name: sprint.name.clone(),
+        start: day(sprint.start_date.as_deref()),
+        end: day(sprint
+            .complete_date
+            .as_deref()
+            .or(sprint.end_date.as_deref())),
+        issues: issues.len(),
+        committed,
+        added,
+        completed,
+        completion,
+    }
+}
+
+/// When the issue was last put into the sprint, from the Sprint field's
+/// changelog. An issue with no such change was created in the sprint.
+fn joined_sprint_at(issue: &Value, sprint_id: u64) -> Option<DateTime<FixedOffset>> {
+    let id = sprint_id.to_string();
+    let lists = |value: &Value| {
+        value
+            .as_str()
+            .unwrap_or_default()
+            .split(',')
+            .any(|s| s.trim() == id)
+    };
+    let histories = issue["changelog"]["histories"].as_array();
+    let joined = histories
+        .into_iter()
+        .flatten()
+        .filter(|history| {
+            history["items"].as_array().is_some_and(|items| {
+                items.iter().any(|item| {
+                    item["field"] == "Sprint" && lists(&item["to"]) && !lists(&item["from"])
+                })
+            })
+        })
+        .filter_map(|history| parse_timestamp(&str_at(history, "/created")))
+        .max();
+    joined.or_else(|| parse_timestamp(&str_at(issue, "/fields/created")))
+}
+
+fn velocity(rows: &[SprintRow]) -> f64 {
+    if rows.is_empty() {
+        return 0.0;
+    }
+    rows.iter().map(|r| r.completed).sum::<f64>() / rows.len() as f64
+}
+
+fn format_points(points: f64) -> String {
+    let rounded = format!("{points:.1}");
+    rounded.trim_end_matches(".0").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(created: &str, points: f64, category: &str, changed: &str) -> Value {
+        json!({
+            "key": "DEV-1",
+            "fields": {
+                "created": created,
+                "status": { "statusCategory": { "key": category } },
+                "statuscategorychangedate": changed,
+                "customfield_10016": points
+            }
+        })
+    }
+
+    #[test]
+    fn test_sprint_row() {
+        let sprint = Sprint {
+            id: 7,
+            name: "Sprint 7".to_string(),
+            start_date: Some("2024-03-04T09:00:00.000Z".to_string()),
+            end_date: Some("2024-03-18T09:00:00.000Z".to_string()),
+            complete_date: Some("2024-03-18T10:30:00.000Z".to_string()),
+        };
+        let mut issues = vec![
+            issue(
+                "2024-02-20T10:00:00.000+0000",
+                5.0,
+                "done",
+                "2024-03-10T12:00:00.000+0000",
+            ),
+            issue(
+                "2024-02-21T10:00:00.000+0000",
+                3.0,
+                "done",
+                "2024-03-20T12:00:00.000+0000",
+            ),
+            issue(
+                "2024-03-06T10:00:00.000+0000",
+                2.0,
+                "done",
+                "2024-03-12T12:00:00.000+0000",
+            ),
+            issue("2024-02-22T10:00:00.000+0000", 8.0, "indeterminate", ""),
+        ];
+        // Created before the sprint but pulled in mid-sprint: added, not committed
+        let mut moved = issue(
+            "2024-02-23T10:00:00.000+0000",
+            1.0,
+            "done",
+            "2024-03-14T12:00:00.000+0000",
+        );
+        moved["changelog"] = json!({
+            "histories": [
+                {
+                    "created": "2024-02-26T10:00:00.000+0000",
+                    "items": [{ "field": "Sprint", "from": "", "to": "6" }]
+                },
+                {
+                    "created": "2024-03-11T10:00:00.000+0000",
+                    "items": [{ "field": "Sprint", "from": "6", "to": "6, 7" }]
+                }
+            ]
+        });
+        issues.push(moved);
+
+        assert_eq!(
+            sprint_row(&sprint, &issues, "customfield_10016"),
+            SprintRow {
+                sprint: 7,
+                name: "Sprint 7".to_string(),
+                start: "2024-03-04".to_string(),
+                end: "2024-03-18".to_string(),
+                issues: 5,
+                committed: 16.0,
+                added: 3.0,
+                completed: 8.0,
+                completion: "50%".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_velocity() {
+        let row = |completed| SprintRow {
+            sprint: 1,
+            name: String::new(),
+            start: String::new(),
+            end: String::new(),
+            issues: 0,
+            committed: 0.0,
+            added: 0.0,
+            completed,
+            completion: "-".to_string(),
+        };
+        assert_eq!(format_points(velocity(&[row(10.0), row(13.0)])), "11.5");
+        assert_eq!(format_points(velocity(&[row(8.0)])), "8");
+        assert_eq!(velocity(&[]), 0.0);
+    }
+}
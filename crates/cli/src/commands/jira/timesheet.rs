@@ -0,0 +1,346 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use atlassian_cli_output::{parse_timestamp, OutputFormat};
+use chrono::{Days, NaiveDate};
+use clap::ValueEnum;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::bulk::resolve_account_id;
+use super::export::str_at;
+use super::issues::search_all;
+use super::utils::JiraContext;
+
+// Timesheets: worklogs on the issues of a JQL scope, summed per user, issue
+// and/or day for a date range
+
+const FETCH_CONCURRENCY: usize = 8;
+
+/// Dimension a timesheet is totalled by.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    User,
+    Issue,
+    Day,
+}
+
+#[derive(Deserialize)]
+struct WorklogPage {
+    #[serde(default)]
+    worklogs: Vec<Worklog>,
+    #[serde(default)]
+    total: usize,
+}
+
+#[derive(Deserialize)]
+struct Worklog {
+    #[serde(default)]
+    author: Option<Author>,
+    started: String,
+    #[serde(rename = "timeSpentSeconds", default)]
+    time_spent_seconds: i64,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    #[serde(rename = "accountId")]
+    account_id: String,
+    #[serde(rename = "displayName", default)]
+    display_name: Option<String>,
+}
+
+/// One worklog reduced to what the timesheet groups on.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    user: String,
+    issue: String,
+    summary: String,
+    day: NaiveDate,
+    seconds: i64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct TimesheetRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day: Option<String>,
+    hours: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn timesheet_report(
+    ctx: &JiraContext<'_>,
+    users: &[String],
+    from: NaiveDate,
+    to: NaiveDate,
+    jql: Option<&str>,
+    group_by: &[GroupBy],
+    csv: Option<&Path>,
+) -> Result<()> {
+    if from > to {
+        bail!("--from {from} is after --to {to}");
+    }
+    if group_by.is_empty() {
+        bail!("--group-by needs at least one of user, issue, day");
+    }
+
+    let mut account_ids = Vec::new();
+    for user in users {
+        account_ids.push(resolve_account_id(ctx, user).await?);
+    }
+
+    let issues = search_all(ctx, &scope_jql(jql, from, to, &account_ids), &["summary"]).await?;
+    let worklogs: Vec<Vec<Worklog>> = stream::iter(issues.iter())
+        .map(|issue| fetch_worklogs(ctx, issue["key"].as_str().unwrap_or_default(), from, to))
+        .buffered(FETCH_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    let authors: HashSet<&str> = account_ids.iter().map(String::as_str).collect();
+    let entries: Vec<Entry> = issues
+        .iter()
+        .zip(worklogs)
+        .flat_map(|(issue, worklogs)| {
+            let key = str_at(issue, "/key");
+            let summary = str_at(issue, "/fields/summary");
+            worklogs.into_iter().filter_map(move |worklog| {
+                let author = worklog.author?;
+                let day = parse_timestamp(&worklog.started)?.date_naive();
+                Some((
+                    author,
+                    day,
+                    worklog.time_spent_seconds,
+                    key.clone(),
+                    summary.clone(),
+                ))
+            })
+        })
+        .filter(|(author, day, ..)| {
+            (authors.is_empty() || authors.contains(author.account_id.as_str()))
+                && (from..=to).contains(day)
+        })
+        .map(|(author, day, seconds, issue, summary)| Entry {
+            user: author.display_name.unwrap_or(author.account_id),
+            issue,
+            summary,
+            day,
+            seconds,
+        })
+        .collect();
+
+    let rows = aggregate(&entries, group_by);
+    let total_hours = hours(entries.iter().map(|e| e.seconds).sum());
+    tracing::info!(
+        issues = issues.len(),
+        worklogs = entries.len(),
+        rows = rows.len(),
+        "Built timesheet"
+    );
+
+    if let Some(path) = csv {
+        write_csv(path, &rows, group_by)?;
+        println!("✅ Wrote {} rows to {}", rows.len(), path.display());
+    }
+
+    if ctx.renderer.format() != OutputFormat::Table {
+        return ctx.renderer.render(&rows);
+    }
+    if rows.is_empty() {
+        println!("No work logged between {from} and {to}");
+        return Ok(());
+    }
+    ctx.renderer.render(&rows)?;
+    println!(
+        "\nTotal: {total_hours}h across {} worklogs on {} issues",
+        entries.len(),
+        issues.len()
+    );
+    Ok(())
+}
+
+/// Narrow the scope to issues with work logged in the range (and by the users,
+/// when given) so only those issues' worklogs are fetched.
+fn scope_jql(jql: Option<&str>, from: NaiveDate, to: NaiveDate, account_ids: &[String]) -> String {
+    let mut clauses = Vec::new();
+    if let Some(jql) = jql.filter(|j| !j.trim().is_empty()) {
+        clauses.push(format!("({jql})"));
+    }
+    clauses.push(format!("worklogDate >= \"{from}\""));
+    clauses.push(format!("worklogDate <= \"{to}\""));
+    if !account_ids.is_empty() {
+        let ids: Vec<String> = account_ids.iter().map(|id| format!("\"{id}\"")).collect();
+        clauses.push(format!("worklogAuthor in ({})", ids.join(", ")));
+    }
+    clauses.join(" AND ")
+}
+
+/// Worklogs of an issue started around the range. The window is padded by a
+/// day on each side because worklog dates are in each author's time zone; the
+/// exact range is applied afterwards.
+async fn fetch_worklogs(
+    ctx: &JiraContext<'_>,
+    key: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Worklog>> {
+    let millis = |day: NaiveDate| {
+        day.and_hms_opt(0, 0, 0)
+            .map(|time| time.and_utc().timestamp_millis())
+            .unwrap_or_default()
+    };
+    let after = millis(from - Days::new(1));
+    let before = millis(to + Days::new(2));
+
+    let mut worklogs = Vec::new();
+    loop {
+        let page: WorklogPage = ctx
+            .client
+            .get(&format!(
+                "/rest/api/3/issue/{key}/worklog?startAt={}&maxResults=1000&startedAfter={after}&startedBefore={before}",
+                worklogs.len()
+            ))
+            .await
+            .with_context(|| format!("Failed to fetch worklogs for {key}"))?;
+        let count = page.worklogs.len();
+        worklogs.extend(page.worklogs);
+
+        if count == 0 || worklogs.len() >= page.total {
+            break;
+        }
+    }
+
+    Ok(worklogs)
+}
+
+fn hours(seconds: i64) -> f64 {
+    (seconds as f64 / 36.0).round() / 100.0
+}
+
+/// User, issue and day of a total; dimensions not grouped by are `None`.
+type GroupKey<'a> = (Option<&'a str>, Option<&'a str>, Option<NaiveDate>);
+
+/// Sum entries per combination of the grouped dimensions, sorted by them.
+fn aggregate(entries: &[Entry], group_by: &[GroupBy]) -> Vec<TimesheetRow> {
+    let has = |dimension| group_by.contains(&dimension);
+    let mut totals: BTreeMap<GroupKey, (&str, i64)> = BTreeMap::new();
+    for entry in entries {
+        let key = (
+            has(GroupBy::User).then_some(entry.user.as_str()),
+            has(GroupBy::Issue).then_some(entry.issue.as_str()),
+            has(GroupBy::Day).then_some(entry.day),
+        );
+        totals.entry(key).or_insert((&entry.summary, 0)).1 += entry.seconds;
+    }
+
+    totals
+        .into_iter()
+        .map(|((user, issue, day), (summary, seconds))| TimesheetRow {
+            user: user.map(str::to_string),
+            summary: issue.map(|_| summary.to_string()),
+            issue: issue.map(str::to_string),
+            day: day.map(|d| d.to_string()),
+            hours: hours(seconds),
+        })
+        .collect()
+}
+
+/// CSV columns follow the `--group-by` order, then hours.
+fn write_csv(path: &Path, rows: &[TimesheetRow], group_by: &[GroupBy]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+
+    let mut header = Vec::new();
+    for dimension in group_by {
+        match dimension {
+            GroupBy::User => header.push("user"),
+            GroupBy::Issue => header.extend(["issue", "summary"]),
+            GroupBy::Day => header.push("day"),
+        }
+    }
+    header.push("hours");
+    writer.write_record(&header)?;
+
+    for row in rows {
+        let mut record = Vec::new();
+        for dimension in group_by {
+            match dimension {
+                GroupBy::User => record.push(row.user.clone().unwrap_or_default()),
+                GroupBy::Issue => {
+                    record.push(row.issue.clone().unwrap_or_default());
+                    record.push(row.summary.clone().unwrap_or_default());
+                }
+                GroupBy::Day => record.push(row.day.clone().unwrap_or_default()),
+            }
+        }
+        record.push(row.hours.to_string());
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user: &str, issue: &str, day: &str, seconds: i64) -> Entry {
+        Entry {
+            user: user.to_string(),
+            issue: issue.to_string(),
+            summary: format!("{issue} summary"),
+            day: day.parse().unwrap(),
+            seconds,
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let entries = vec![
+            entry("Ana", "DEV-1", "2024-06-03", 3600),
+            entry("Ana", "DEV-1", "2024-06-04", 1800),
+            entry("Ana", "DEV-2", "2024-06-03", 900),
+            entry("Bo", "DEV-1", "2024-06-03", 7200),
+        ];
+
+        let rows = aggregate(&entries, &[GroupBy::User, GroupBy::Issue]);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            TimesheetRow {
+                user: Some("Ana".to_string()),
+                issue: Some("DEV-1".to_string()),
+                summary: Some("DEV-1 summary".to_string()),
+                day: None,
+                hours: 1.5,
+            }
+        );
+        assert_eq!(rows[1].hours, 0.25);
+
+        let rows = aggregate(&entries, &[GroupBy::Day]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].day.as_deref(), Some("2024-06-03"));
+        assert_eq!(rows[0].hours, 3.25);
+        assert!(rows[0].user.is_none() && rows[0].summary.is_none());
+    }
+
+    #[test]
+    fn test_scope_jql() {
+        let from = "2024-06-01".parse().unwrap();
+        let to = "2024-06-30".parse().unwrap();
+        assert_eq!(
+            scope_jql(Some("project = DEV"), from, to, &["5b10".to_string()]),
+            "(project = DEV) AND worklogDate >= \"2024-06-01\" AND worklogDate <= \"2024-06-30\" AND worklogAuthor in (\"5b10\")"
+        );
+        assert_eq!(
+            scope_jql(None, from, to, &[]),
+            "worklogDate >= \"2024-06-01\" AND worklogDate <= \"2024-06-30\""
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::utils::JiraContext;
+
+// Moving an issue to a status several transitions away: the path is planned on
+// the issue's workflow, then each hop is taken from the transitions available
+// at that point, stopping with a report of how far it got
+
+#[derive(Deserialize)]
+struct IssueStatus {
+    fields: StatusFields,
+}
+
+#[derive(Deserialize)]
+struct StatusFields {
+    status: Named,
+    project: Named,
+    issuetype: Named,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    id: String,
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Available {
+    transitions: Vec<AvailableTransition>,
+}
+
+#[derive(Deserialize)]
+struct AvailableTransition {
+    id: String,
+    name: String,
+    to: Named,
+}
+
+#[derive(Deserialize)]
+struct WorkflowsResponse {
+    #[serde(default)]
+    statuses: Vec<WorkflowStatus>,
+    #[serde(default)]
+    workflows: Vec<Workflow>,
+}
+
+#[derive(Deserialize)]
+struct WorkflowStatus {
+    #[serde(rename = "statusReference")]
+    reference: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Workflow {
+    name: String,
+    #[serde(default)]
+    transitions: Vec<WorkflowTransition>,
+}
+
+#[derive(Deserialize)]
+struct WorkflowTransition {
+    id: String,
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(rename = "toStatusReference")]
+    to: String,
+    #[serde(default)]
+    links: Vec<Link>,
+}
+
+#[derive(Deserialize)]
+struct Link {
+    #[serde(rename = "fromStatusReference")]
+    from: String,
+}
+
+/// A workflow transition between statuses; global transitions have no `from`.
+#[derive(Debug, Clone, PartialEq)]
+struct Edge {
+    transition_id: String,
+    name: String,
+    from: Option<String>,
+    to: String,
+}
+
+/// Statuses (id → name) and the transitions between them.
+struct Graph {
+    statuses: HashMap<String, String>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    fn name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.statuses.get(id).map_or(id, String::as_str)
+    }
+}
+
+/// Move `key` to the status named `to`. Without `through`, only a single
+/// transition is taken; with it, the shortest path through the workflow is.
+pub async fn transition_to(
+    ctx: &JiraContext<'_>,
+    key: &str,
+    to: &str,
+    through: bool,
+) -> Result<()> {
+    let issue: IssueStatus = ctx
+        .client
+        .get(&format!(
+            "/rest/api/3/issue/{key}?fields=status,project,issuetype"
+        ))
+        .await
+        .with_context(|| format!("Failed to get issue {key}"))?;
+    let current = issue.fields.status;
+    if current.name.eq_ignore_ascii_case(to) {
+        println!("{key} is already in {}", current.name);
+        return Ok(());
+    }
+
+    if !through {
+        let available = available_transitions(ctx, key).await?;
+        let transition = available
+            .iter()
+            .find(|t| t.to.name.eq_ignore_ascii_case(to))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No transition from {} to {to} on {key}; add --through-auto to go through intermediate statuses",
+                    current.name
+                )
+            })?;
+        take(ctx, key, transition).await?;
+        println!("✅ Transitioned {key} to: {}", transition.to.name);
+        return Ok(());
+    }
+
+    let graph = workflow_graph(ctx, &issue.fields.project.id, &issue.fields.issuetype.id)
+        .await
+        .with_context(|| {
+            format!("Failed to read the workflow of {key}, which --through-auto plans the path on")
+        })?;
+    let path = shortest_path(&graph, &current.id, to)
+        .ok_or_else(|| anyhow!("The workflow has no path from {} to {to}", current.name))?;
+
+    let statuses: Vec<&str> = std::iter::once(graph.name(&current.id))
+        .chain(path.iter().map(|edge| graph.name(&edge.to)))
+        .collect();
+    println!(
+        "Path: {} ({} transitions)",
+        statuses.join(" → "),
+        path.len()
+    );
+
+    for (hop, edge) in path.iter().enumerate() {
+        let available = available_transitions(ctx, key).await?;
+        let Some(transition) = available
+            .iter()
+            .find(|t| t.id == edge.transition_id)
+            .or_else(|| available.iter().find(|t| t.to.id == edge.to))
+        else {
+            let names: Vec<&str> = available.iter().map(|t| t.name.as_str()).collect();
+            bail!(
+                "Blocked at {}: '{}' is not available (available: {})\n{}",
+                statuses[hop],
+                edge.name,
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                },
+                path_report(&statuses, hop)
+            );
+        };
+        take(ctx, key, transition)
+            .await
+            .with_context(|| path_report(&statuses, hop))?;
+        println!("✅ {key}: {} → {}", transition.name, transition.to.name);
+    }
+
+    tracing::info!(%key, hops = path.len(), to = %to, "Issue transitioned through path");
+    Ok(())
+}
+
+async fn available_transitions(
+    ctx: &JiraContext<'_>,
+    key: &str,
+) -> Result<Vec<AvailableTransition>> {
+    let available: Available = ctx
+        .client
+        .get(&format!("/rest/api/3/issue/{key}/transitions"))
+        .await
+        .with_context(|| format!("Failed to get transitions for {key}"))?;
+    Ok(available.transitions)
+}
+
+async fn take(ctx: &JiraContext<'_>, key: &str, transition: &AvailableTransition) -> Result<()> {
+    let _: Value = ctx
+        .client
+        .post(
+            &format!("/rest/api/3/issue/{key}/transitions"),
+            &json!({ "transition": { "id": transition.id } }),
+        )
+        .await
+        .with_context(|| format!("Failed to transition {key} with '{}'", transition.name))?;
+    tracing::info!(%key, transition = %transition.name, "Issue transitioned successfully");
+    Ok(())
+}
+
+/// The workflow used by an issue type in a project. Reading workflows needs
+/// project or Jira admin permission.
+async fn workflow_graph(
+    ctx: &JiraContext<'_>,
+    project_id: &str,
+    issue_type_id: &str,
+) -> Result<Graph> {
+    let payload = json!({
+        "projectAndIssueTypes": [{ "projectId": project_id, "issueTypeId": issue_type_id }]
+    });
+    let response: WorkflowsResponse = ctx.client.post("/rest/api/3/workflows", &payload).await?;
+    let workflow = response
+        .workflows
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No workflow found"))?;
+    tracing::debug!(workflow = %workflow.name, "Planning on workflow");
+
+    let edges = workflow
+        .transitions
+        .into_iter()
+        .filter(|t| t.kind != "INITIAL")
+        .flat_map(|t| {
+            let froms: Vec<Option<String>> = if t.links.is_empty() {
+                vec![None]
+            } else {
+                t.links.into_iter().map(|l| Some(l.from)).collect()
+            };
+            froms.into_iter().map(move |from| Edge {
+                transition_id: t.id.clone(),
+                name: t.name.clone(),
+                from,
+                to: t.to.clone(),
+            })
+        })
+        .collect();
+    Ok(Graph {
+        statuses: response
+            .statuses
+            .into_iter()
+            .map(|s| (s.reference, s.name))
+            .collect(),
+        edges,
+    })
+}
+
+/// Fewest transitions from status `from` to the status named `to` (breadth-first).
+fn shortest_path(graph: &Graph, from: &str, to: &str) -> Option<Vec<Edge>> {
+    // Status reached → the status it was reached from, and how
+    let mut previous: HashMap<&str, (&str, &Edge)> = HashMap::new();
+    let mut seen: HashSet<&str> = HashSet::from([from]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(status) = queue.pop_front() {
+        if graph.name(status).eq_ignore_ascii_case(to) {
+            let mut path = Vec::new();
+            let mut at = status;
+            while let Some((before, edge)) = previous.get(at) {
+                path.push((*edge).clone());
+                at = before;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for edge in graph
+            .edges
+            .iter()
+            .filter(|e| e.from.as_deref().is_none_or(|f| f == status))
+        {
+            if seen.insert(edge.to.as_str()) {
+                previous.insert(edge.to.as_str(), (status, edge));
+                queue.push_back(edge.to.as_str());
+            }
+        }
+    }
+    None
+}
+
+/// "To Do ✓ → In Progress ✗ → Done": statuses reached, and where it stopped.
+fn path_report(statuses: &[&str], reached: usize) -> String {
+    let marked: Vec<String> = statuses
+        .iter()
+        .enumerate()
+        .map(|(i, status)| match i.cmp(&reached) {
+            std::cmp::Ordering::Less | std::cmp::Ordering::Equal => format!("{status} ✓"),
+            _ if i == reached + 1 => format!("{status} ✗"),
+            _ => status.to_string(),
+        })
+        .collect();
+    format!("Path: {}", marked.join(" → "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(id: &str, from: Option<&str>, to: &str) -> Edge {
+        Edge {
+            transition_id: id.to_string(),
+            name: format!("t{id}"),
+            from: from.map(str::to_string),
+            to: to.to_string(),
+        }
+    }
+
+    fn graph(edges: Vec<Edge>) -> Graph {
+        Graph {
+            statuses: [
+                ("1", "To Do"),
+                ("2", "In Progress"),
+                ("3", "In Review"),
+                ("4", "Done"),
+            ]
+            .into_iter()
+            .map(|(id, name)| (id.to_string(), name.to_string()))
+            .collect(),
+            edges,
+        }
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let workflow = graph(vec![
+            edge("11", Some("1"), "2"),
+            edge("21", Some("2"), "3"),
+            edge("31", Some("3"), "4"),
+            edge("32", Some("3"), "2"),
+        ]);
+        let path = shortest_path(&workflow, "1", "done").unwrap();
+        let ids: Vec<&str> = path.iter().map(|e| e.transition_id.as_str()).collect();
+        assert_eq!(ids, vec!["11", "21", "31"]);
+        assert!(shortest_path(&workflow, "4", "To Do").is_none());
+
+        // A global transition into Done is a single hop from anywhere
+        let workflow = graph(vec![edge("11", Some("1"), "2"), edge("99", None, "4")]);
+        assert_eq!(
+            shortest_path(&workflow, "2", "Done").unwrap(),
+            vec![edge("99", None, "4")]
+        );
+    }
+
+    #[test]
+    fn test_path_report() {
+        assert_eq!(
+            path_report(&["To Do", "In Progress", "In Review", "Done"], 1),
+            "Path: To Do ✓ → In Progress ✓ → In Review ✗ → Done"
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::{anyhow, bail, Result};
+use atlassian_cli_api::error::ApiError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::utils::JiraContext;
+use crate::commands::pagination::{Page, PaginationArgs, Paging};
+
+// Deleted issues: sites with the recycle bin keep deleted issues for a while
+// and let project admins list and restore them. Other sites answer 404 or 405
+// on the trash endpoints, which is reported as a missing capability rather
+// than as an error in the request
+
+const TRASH_PATH: &str = "/rest/api/3/issue/trash";
+
+const UNSUPPORTED: &str = "This site does not have the Jira recycle bin, so deleted issues cannot be listed or restored. \
+     Archive issues instead of deleting them (`jira archive`), or ask Atlassian support to restore from backup";
+
+#[derive(Deserialize)]
+struct TrashPage {
+    #[serde(default)]
+    values: Vec<TrashedIssue>,
+    #[serde(rename = "isLast", default = "default_true")]
+    is_last: bool,
+}
+
+#[derive(Deserialize)]
+struct TrashedIssue {
+    key: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(rename = "deletedBy", default)]
+    deleted_by: Option<Value>,
+    #[serde(rename = "deletedDate", default)]
+    deleted_date: Option<String>,
+    #[serde(rename = "purgeDate", default)]
+    purge_date: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// List deleted issues that can still be restored.
+pub async fn list_trash(
+    ctx: &JiraContext<'_>,
+    project: Option<&str>,
+    page: &PaginationArgs,
+) -> Result<()> {
+    let project_filter = project
+        .map(|key| format!("&project={}", urlencoding::encode(key)))
+        .unwrap_or_default();
+    let issues: Vec<TrashedIssue> = page
+        .collect(50, 100, Paging::Offset, |request| {
+            let path = format!(
+                "{TRASH_PATH}?startAt={}&maxResults={}{project_filter}",
+                request.start, request.size
+            );
+            async move {
+                let response: TrashPage = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .map_err(|err| trash_error(err, "list deleted issues"))?;
+                Ok(Page::offset(response.values, !response.is_last))
+            }
+        })
+        .await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        key: &'a str,
+        summary: &'a str,
+        deleted_by: &'a str,
+        deleted: &'a str,
+        purged: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = issues
+        .iter()
+        .map(|issue| Row {
+            key: &issue.key,
+            summary: &issue.summary,
+            deleted_by: issue
+                .deleted_by
+                .as_ref()
+                .and_then(|user| user.get("displayName"))
+                .and_then(Value::as_str)
+                .unwrap_or(""),
+            deleted: issue.deleted_date.as_deref().unwrap_or(""),
+            purged: issue.purge_date.as_deref().unwrap_or(""),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        tracing::info!("No deleted issues found");
+        return Ok(());
+    }
+
+    ctx.renderer.render_with_id(&rows, "key")
+}
+
+/// Restore deleted issues from the recycle bin.
+pub async fn restore_from_trash(ctx: &JiraContext<'_>, keys: &[String]) -> Result<()> {
+    // A 404 on restore is ambiguous, so check the site has a recycle bin first
+    let _: TrashPage = ctx
+        .client
+        .get(&format!("{TRASH_PATH}?maxResults=1"))
+        .await
+        .map_err(|err| trash_error(err, "check the recycle bin"))?;
+
+    let mut missing = Vec::new();
+    for key in keys {
+        let path = format!("{TRASH_PATH}/{}/restore", urlencoding::encode(key));
+        match ctx.client.post::<Value, _>(&path, &json!({})).await {
+            Ok(_) => {
+                tracing::info!(key = %key, "Issue restored from trash");
+                println!("✅ Restored {key}");
+            }
+            Err(ApiError::NotFound { .. }) => {
+                eprintln!(
+                    "⚠️  {key} is not in the trash (never deleted, already restored or purged)"
+                );
+                missing.push(key.as_str());
+            }
+            Err(err) => return Err(trash_error(err, &format!("restore {key}"))),
+        }
+    }
+
+    if !missing.is_empty() {
+        bail!(
+            "Restored {} of {} issue(s); not in the trash: {}",
+            keys.len() - missing.len(),
+            keys.len(),
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The capability error when the site has no trash endpoints, else `err` with context.
+fn trash_error(err: ApiError, action: &str) -> anyhow::Error {
+    match err {
+        ApiError::NotFound { .. }
+        | ApiError::ServerError {
+            status: 405 | 501, ..
+        } => {
+            anyhow!(UNSUPPORTED)
+        }
+        err => anyhow::Error::new(err).context(format!("Failed to {action}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_error() {
+        let unsupported = trash_error(
+            ApiError::NotFound {
+                resource: TRASH_PATH.to_string(),
+            },
+            "list deleted issues",
+        );
+        assert!(unsupported
+            .to_string()
+            .starts_with("This site does not have the Jira recycle bin"));
+
+        let unsupported = trash_error(
+            ApiError::ServerError {
+                status: 405,
+                message: "Method Not Allowed".to_string(),
+            },
+            "list deleted issues",
+        );
+        assert!(unsupported.to_string().contains("recycle bin"));
+
+        let denied = trash_error(
+            ApiError::ServerError {
+                status: 403,
+                message: "Forbidden".to_string(),
+            },
+            "restore DEV-1",
+        );
+        assert_eq!(denied.to_string(), "Failed to restore DEV-1");
+    }
+
+    #[test]
+    fn test_trash_page() {
+        let page: TrashPage = serde_json::from_value(json!({
+            "values": [{
+                "key": "DEV-7",
+                "summary": "Flaky login test",
+                "deletedBy": { "displayName": "Ana" },
+                "deletedDate": "2024-06-03T10:00:00.000+0000"
+            }],
+            "isLast": true
+        }))
+        .unwrap();
+        assert!(page.is_last);
+        assert_eq!(page.values[0].key, "DEV-7");
+        assert!(page.values[0].purge_date.is_none());
+    }
+}
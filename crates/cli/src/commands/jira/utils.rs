@@ -4,4 +4,6 @@ use atlassian_cli_output::OutputRenderer;
 pub struct JiraContext<'a> {
     pub client: ApiClient,
     pub renderer: &'a OutputRenderer,
+    /// Name of the active profile, used to key per-profile caches.
+    pub profile: &'a str,
 }
@@ -0,0 +1,278 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::utils::JiraContext;
+
+// Createmeta-based validation for issue creation
+
+#[derive(Deserialize)]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+    #[serde(rename = "isLast", default)]
+    is_last: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct IssueTypeMeta {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct FieldMeta {
+    #[serde(rename = "fieldId")]
+    pub field_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(rename = "hasDefaultValue", default)]
+    pub has_default_value: bool,
+    #[serde(rename = "allowedValues", default)]
+    pub allowed_values: Vec<AllowedValue>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AllowedValue {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+impl AllowedValue {
+    fn label(&self) -> Option<&str> {
+        self.name.as_deref().or(self.value.as_deref())
+    }
+}
+
+/// Values the user supplied for a create request, keyed by field ID.
+#[derive(Default)]
+pub struct ProvidedFields<'a> {
+    pub field_ids: Vec<&'a str>,
+    pub priority: Option<&'a str>,
+    pub components: &'a [String],
+}
+
+/// Fetch createmeta for the project/issue type and fail with every problem found.
+pub async fn validate_create(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    issue_type: &str,
+    provided: &ProvidedFields<'_>,
+) -> Result<()> {
+    let fields = fetch_create_fields(ctx, project, issue_type).await?;
+    let problems = check_create_fields(&fields, provided);
+
+    if problems.is_empty() {
+        tracing::debug!(%project, %issue_type, "Create request passed createmeta validation");
+        return Ok(());
+    }
+
+    bail!(
+        "Issue would be rejected by Jira:\n  - {}",
+        problems.join("\n  - ")
+    )
+}
+
+async fn fetch_create_fields(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    issue_type: &str,
+) -> Result<Vec<FieldMeta>> {
+    let types = fetch_issue_types(ctx, project).await?;
+
+    let type_id = types
+        .iter()
+        .find(|t| t.name.eq_ignore_ascii_case(issue_type) || t.id == issue_type)
+        .map(|t| t.id.clone())
+        .ok_or_else(|| {
+            let available: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+            anyhow!(
+                "Issue type '{issue_type}' is not available in project {project}. Available: {}",
+                available.join(", ")
+            )
+        })?;
+
+    fetch_issue_type_fields(ctx, project, &type_id)
+        .await
+        .with_context(|| format!("Failed to fetch create metadata for {project}/{issue_type}"))
+}
+
+/// Every issue type that can be created in the project, across all createmeta pages.
+pub async fn fetch_issue_types(ctx: &JiraContext<'_>, project: &str) -> Result<Vec<IssueTypeMeta>> {
+    fetch_createmeta(
+        ctx,
+        &format!("/rest/api/3/issue/createmeta/{project}/issuetypes"),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch issue types for project {project}"))
+}
+
+/// Every field on the create screen of one issue type, across all createmeta pages.
+pub async fn fetch_issue_type_fields(
+    ctx: &JiraContext<'_>,
+    project: &str,
+    type_id: &str,
+) -> Result<Vec<FieldMeta>> {
+    fetch_createmeta(
+        ctx,
+        &format!("/rest/api/3/issue/createmeta/{project}/issuetypes/{type_id}"),
+    )
+    .await
+}
+
+async fn fetch_createmeta<T: DeserializeOwned>(
+    ctx: &JiraContext<'_>,
+    path: &str,
+) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    let mut start_at = 0;
+    loop {
+        let page: Page<T> = ctx
+            .client
+            .get(&format!("{path}?startAt={start_at}&maxResults=200"))
+            .await?;
+
+        let count = page.values.len();
+        values.extend(page.values);
+        if page.is_last.unwrap_or(true) || count == 0 {
+            break;
+        }
+        start_at += count;
+    }
+
+    Ok(values)
+}
+
+/// Compare the supplied values against createmeta and describe every mismatch.
+pub fn check_create_fields(fields: &[FieldMeta], provided: &ProvidedFields<'_>) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for field in fields {
+        if field.required
+            && !field.has_default_value
+            && !provided.field_ids.contains(&field.field_id.as_str())
+        {
+            problems.push(format!(
+                "Missing required field '{}' ({})",
+                field.name, field.field_id
+            ));
+        }
+    }
+
+    if let Some(priority) = provided.priority {
+        match fields.iter().find(|f| f.field_id == "priority") {
+            Some(meta) => check_allowed(meta, &[priority.to_string()], "priority", &mut problems),
+            None => problems.push(
+                "Field 'priority' is not on the create screen for this issue type".to_string(),
+            ),
+        }
+    }
+
+    if !provided.components.is_empty() {
+        match fields.iter().find(|f| f.field_id == "components") {
+            Some(meta) => check_allowed(meta, provided.components, "component", &mut problems),
+            None => problems.push(
+                "Field 'components' is not on the create screen for this issue type".to_string(),
+            ),
+        }
+    }
+
+    problems
+}
+
+fn check_allowed(meta: &FieldMeta, values: &[String], noun: &str, problems: &mut Vec<String>) {
+    if meta.allowed_values.is_empty() {
+        return;
+    }
+
+    let allowed: Vec<&str> = meta
+        .allowed_values
+        .iter()
+        .filter_map(AllowedValue::label)
+        .collect();
+
+    for value in values {
+        if !allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) {
+            problems.push(format!(
+                "Unknown {noun} '{value}'. Valid values: {}",
+                allowed.join(", ")
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(id: &str, required: bool, allowed: &[&str]) -> FieldMeta {
+        FieldMeta {
+            field_id: id.to_string(),
+            name: id.to_string(),
+            required,
+            has_default_value: false,
+            allowed_values: allowed
+                .iter()
+                .map(|name| AllowedValue {
+                    name: Some(name.to_string()),
+                    value: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let fields = vec![
+            field("summary", true, &[]),
+            field("customfield_1", true, &[]),
+        ];
+        let provided = ProvidedFields {
+            field_ids: vec!["summary"],
+            ..Default::default()
+        };
+
+        let problems = check_create_fields(&fields, &provided);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("customfield_1"));
+    }
+
+    #[test]
+    fn test_required_field_with_default_is_ok() {
+        let mut reporter = field("reporter", true, &[]);
+        reporter.has_default_value = true;
+
+        let problems = check_create_fields(&[reporter], &ProvidedFields::default());
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_priority_lists_valid_values() {
+        let fields = vec![field("priority", false, &["High", "Low"])];
+        let provided = ProvidedFields {
+            priority: Some("Urgent"),
+            ..Default::default()
+        };
+
+        let problems = check_create_fields(&fields, &provided);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("High, Low"));
+    }
+
+    #[test]
+    fn test_components_case_insensitive() {
+        let fields = vec![field("components", false, &["Backend", "UI"])];
+        let components = vec!["backend".to_string(), "Docs".to_string()];
+        let provided = ProvidedFields {
+            components: &components,
+            ..Default::default()
+        };
+
+        let problems = check_create_fields(&fields, &provided);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("Docs"));
+    }
+}
@@ -1,8 +1,14 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
 
 use super::utils::JiraContext;
+use crate::commands::webhook_events::{validate_events, JIRA_EVENTS};
+use crate::commands::webhook_signature::{sign, signature_matches, SIGNATURE_HEADER};
+
+const EVENTS_COMMAND: &str = "jira webhooks events";
 
 // List webhooks
 pub async fn list_webhooks(ctx: &JiraContext<'_>) -> Result<()> {
@@ -58,7 +64,7 @@ pub async fn get_webhook(ctx: &JiraContext<'_>, webhook_id: i64) -> Result<()> {
         .await
         .with_context(|| format!("Failed to get webhook {webhook_id}"))?;
 
-    println!("{}", serde_json::to_string_pretty(&webhook)?);
+    ctx.renderer.print_json(&webhook)?;
     Ok(())
 }
 
@@ -71,6 +77,8 @@ pub async fn create_webhook(
     enabled: bool,
     jql_filter: Option<&str>,
 ) -> Result<()> {
+    validate_events(JIRA_EVENTS, &events, EVENTS_COMMAND)?;
+
     let mut payload = json!({
         "name": name,
         "url": url,
@@ -113,6 +121,10 @@ pub async fn update_webhook(
     events: Option<Vec<String>>,
     enabled: Option<bool>,
 ) -> Result<()> {
+    if let Some(events) = &events {
+        validate_events(JIRA_EVENTS, events, EVENTS_COMMAND)?;
+    }
+
     // First get current webhook
     let current: Value = ctx
         .client
@@ -198,3 +210,37 @@ pub async fn test_webhook(ctx: &JiraContext<'_>, webhook_id: i64) -> Result<()>
     println!("✅ Test payload sent to webhook: {}", webhook_id);
     Ok(())
 }
+
+// Verify a webhook payload against its `X-Hub-Signature` (HMAC-SHA256 of the raw body)
+pub fn verify_signature(
+    secret: &str,
+    payload_file: &Path,
+    signature: Option<&str>,
+    print_curl_example: bool,
+    url: &str,
+) -> Result<()> {
+    let payload = fs::read(payload_file)
+        .with_context(|| format!("Failed to read payload file: {}", payload_file.display()))?;
+    let expected = sign(secret, &payload);
+
+    if print_curl_example {
+        println!(
+            "curl -X POST '{url}' \\\n  -H 'Content-Type: application/json' \\\n  -H '{SIGNATURE_HEADER}: sha256={expected}' \\\n  --data-binary @{}",
+            payload_file.display()
+        );
+        if signature.is_none() {
+            return Ok(());
+        }
+    }
+
+    let Some(signature) = signature else {
+        bail!("--signature is required unless --print-curl-example is given");
+    };
+    if !signature_matches(secret, &payload, signature)? {
+        bail!("Signature mismatch: payload was not signed with this secret");
+    }
+
+    tracing::info!(file = %payload_file.display(), "Webhook signature verified");
+    println!("✅ Signature is valid");
+    Ok(())
+}
@@ -1,9 +1,13 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use atlassian_cli_api::ApiClient;
 use atlassian_cli_output::OutputRenderer;
 use clap::{Args, Subcommand};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use url::form_urlencoded;
+use serde_json::{json, Map, Value};
+
+use super::entity_url;
+use super::pagination::{Page, PaginationArgs, Paging};
 
 #[derive(Args, Debug, Clone)]
 pub struct JsmArgs {
@@ -23,14 +27,19 @@ enum JsmCommands {
         #[command(subcommand)]
         command: RequestCommands,
     },
+    /// Request type discovery.
+    RequestType {
+        #[command(subcommand)]
+        command: RequestTypeCommands,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
 enum ServiceDeskCommands {
     /// List service desks available to the account.
     List {
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get a single service desk by ID.
     Get { id: i64 },
@@ -42,14 +51,47 @@ enum RequestCommands {
     List {
         #[arg(long)]
         servicedesk_id: Option<i64>,
-        #[arg(long, default_value_t = 25)]
-        limit: usize,
+        #[command(flatten)]
+        page: PaginationArgs,
     },
     /// Get request details (issue key or ID).
     Get {
-        #[arg(value_name = "ISSUE")]
+        #[arg(value_name = "ISSUE", value_parser = entity_url::issue_key)]
         key: String,
     },
+    /// Create a request, checking fields against the request type's field metadata.
+    Create {
+        /// Request type ID.
+        #[arg(long)]
+        request_type: String,
+        /// Service desk ID or project key (looked up from the request type if omitted).
+        #[arg(long)]
+        service_desk: Option<String>,
+        /// Field value by field ID or name, e.g. "summary=VPN down" (repeatable).
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        field: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum RequestTypeCommands {
+    /// List the request types of a service desk.
+    List {
+        /// Service desk ID or project key.
+        #[arg(long)]
+        service_desk: String,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Show the fields of a request type, marking required ones and valid values.
+    Fields {
+        /// Request type ID.
+        #[arg(value_name = "REQUEST_TYPE")]
+        id: String,
+        /// Service desk ID or project key (looked up from the request type if omitted).
+        #[arg(long)]
+        service_desk: Option<String>,
+    },
 }
 
 pub struct JsmContext<'a> {
@@ -67,6 +109,33 @@ struct RequestField {
     value: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct RequestTypeField {
+    #[serde(rename = "fieldId")]
+    field_id: String,
+    name: String,
+    #[serde(default)]
+    required: bool,
+    #[serde(rename = "validValues", default)]
+    valid_values: Vec<ValidValue>,
+    #[serde(rename = "jiraSchema", default)]
+    jira_schema: Option<JiraSchema>,
+}
+
+#[derive(Deserialize)]
+struct ValidValue {
+    value: String,
+    label: String,
+}
+
+#[derive(Deserialize)]
+struct JiraSchema {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    items: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct RequestReporter {
     #[serde(rename = "displayName")]
@@ -82,25 +151,69 @@ struct RequestStatus {
 pub async fn execute(args: JsmArgs, ctx: JsmContext<'_>) -> Result<()> {
     match args.command {
         JsmCommands::ServiceDesk { command } => match command {
-            ServiceDeskCommands::List { limit } => list_service_desks(&ctx, limit).await,
+            ServiceDeskCommands::List { page } => list_service_desks(&ctx, &page).await,
             ServiceDeskCommands::Get { id } => get_service_desk(&ctx, id).await,
         },
         JsmCommands::Request { command } => match command {
             RequestCommands::List {
                 servicedesk_id,
-                limit,
-            } => list_requests(&ctx, servicedesk_id, limit).await,
+                page,
+            } => list_requests(&ctx, servicedesk_id, &page).await,
             RequestCommands::Get { key } => get_request(&ctx, &key).await,
+            RequestCommands::Create {
+                request_type,
+                service_desk,
+                field,
+            } => create_request(&ctx, &request_type, service_desk.as_deref(), &field).await,
+        },
+        JsmCommands::RequestType { command } => match command {
+            RequestTypeCommands::List { service_desk, page } => {
+                list_request_types(&ctx, &service_desk, &page).await
+            }
+            RequestTypeCommands::Fields { id, service_desk } => {
+                request_type_fields(&ctx, &id, service_desk.as_deref()).await
+            }
         },
     }
 }
 
-async fn list_service_desks(ctx: &JsmContext<'_>, limit: usize) -> Result<()> {
+/// Page through a service desk API collection (`path` may already carry query parameters).
+async fn fetch_paged<T: DeserializeOwned>(
+    ctx: &JsmContext<'_>,
+    path: &str,
+    page: &PaginationArgs,
+    what: &str,
+) -> Result<Vec<T>> {
     #[derive(Deserialize)]
-    struct DeskList {
-        values: Vec<ServiceDesk>,
+    struct PagedList<T> {
+        values: Vec<T>,
+        #[serde(rename = "isLastPage", default = "default_last_page")]
+        is_last_page: bool,
     }
 
+    fn default_last_page() -> bool {
+        true
+    }
+
+    page.collect(25, 50, Paging::Offset, |request| {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let path = format!(
+            "{path}{separator}start={}&limit={}",
+            request.start, request.size
+        );
+        async move {
+            let response: PagedList<T> = ctx
+                .client
+                .get(&path)
+                .await
+                .with_context(|| format!("Failed to list {what}"))?;
+            Ok(Page::offset(response.values, !response.is_last_page))
+        }
+    })
+    .await
+}
+
+async fn list_service_desks(ctx: &JsmContext<'_>, page: &PaginationArgs) -> Result<()> {
     #[derive(Deserialize)]
     struct ServiceDesk {
         id: i64,
@@ -111,16 +224,13 @@ async fn list_service_desks(ctx: &JsmContext<'_>, limit: usize) -> Result<()> {
         project_name: Option<String>,
     }
 
-    let query = form_urlencoded::Serializer::new(String::new())
-        .append_pair("limit", &limit.min(50).to_string())
-        .finish();
-    let path = format!("/rest/servicedeskapi/servicedesk?{query}");
-
-    let response: DeskList = ctx
-        .client
-        .get(&path)
-        .await
-        .context("Failed to list service desks")?;
+    let desks: Vec<ServiceDesk> = fetch_paged(
+        ctx,
+        "/rest/servicedeskapi/servicedesk",
+        page,
+        "service desks",
+    )
+    .await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -130,8 +240,7 @@ async fn list_service_desks(ctx: &JsmContext<'_>, limit: usize) -> Result<()> {
         project_name: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = desks
         .iter()
         .map(|desk| Row {
             id: desk.id,
@@ -192,13 +301,8 @@ async fn get_service_desk(ctx: &JsmContext<'_>, id: i64) -> Result<()> {
 async fn list_requests(
     ctx: &JsmContext<'_>,
     servicedesk_id: Option<i64>,
-    limit: usize,
+    page: &PaginationArgs,
 ) -> Result<()> {
-    #[derive(Deserialize)]
-    struct RequestList {
-        values: Vec<Request>,
-    }
-
     #[derive(Deserialize)]
     struct Request {
         #[serde(rename = "issueId")]
@@ -216,18 +320,11 @@ async fn list_requests(
         current_status: Option<RequestStatus>,
     }
 
-    let mut serializer = form_urlencoded::Serializer::new(String::new());
-    serializer.append_pair("limit", &limit.min(50).to_string());
+    let mut path = String::from("/rest/servicedeskapi/request");
     if let Some(id) = servicedesk_id {
-        serializer.append_pair("serviceDeskId", &id.to_string());
+        path.push_str(&format!("?serviceDeskId={id}"));
     }
-    let path = format!("/rest/servicedeskapi/request?{}", serializer.finish());
-
-    let response: RequestList = ctx
-        .client
-        .get(&path)
-        .await
-        .context("Failed to list requests")?;
+    let requests: Vec<Request> = fetch_paged(ctx, &path, page, "requests").await?;
 
     #[derive(Serialize)]
     struct Row<'a> {
@@ -238,8 +335,7 @@ async fn list_requests(
         summary: &'a str,
     }
 
-    let rows: Vec<Row<'_>> = response
-        .values
+    let rows: Vec<Row<'_>> = requests
         .iter()
         .map(|request| {
             let summary = request
@@ -338,6 +434,311 @@ async fn get_request(ctx: &JsmContext<'_>, key: &str) -> Result<()> {
     ctx.renderer.render(&view)
 }
 
+async fn list_request_types(
+    ctx: &JsmContext<'_>,
+    service_desk: &str,
+    page: &PaginationArgs,
+) -> Result<()> {
+    #[derive(Deserialize)]
+    struct RequestType {
+        id: String,
+        name: String,
+        #[serde(default)]
+        description: Option<String>,
+        #[serde(rename = "issueTypeId", default)]
+        issue_type_id: Option<String>,
+    }
+
+    let desk = resolve_service_desk(ctx, service_desk).await?;
+    let path = format!("/rest/servicedeskapi/servicedesk/{desk}/requesttype");
+    let types: Vec<RequestType> = fetch_paged(ctx, &path, page, "request types").await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        name: &'a str,
+        issue_type_id: &'a str,
+        description: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = types
+        .iter()
+        .map(|t| Row {
+            id: t.id.as_str(),
+            name: t.name.as_str(),
+            issue_type_id: t.issue_type_id.as_deref().unwrap_or(""),
+            description: t.description.as_deref().unwrap_or(""),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        tracing::info!("No request types returned.");
+        return Ok(());
+    }
+
+    ctx.renderer.render_with_id(&rows, "id")
+}
+
+async fn request_type_fields(
+    ctx: &JsmContext<'_>,
+    request_type: &str,
+    service_desk: Option<&str>,
+) -> Result<()> {
+    let fields = fetch_request_type_fields(ctx, request_type, service_desk).await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        field_id: &'a str,
+        name: &'a str,
+        required: bool,
+        #[serde(rename = "type")]
+        kind: String,
+        valid_values: String,
+    }
+
+    let rows: Vec<Row<'_>> = fields
+        .iter()
+        .map(|f| Row {
+            field_id: f.field_id.as_str(),
+            name: f.name.as_str(),
+            required: f.required,
+            kind: f
+                .jira_schema
+                .as_ref()
+                .map(|schema| match &schema.items {
+                    Some(items) => format!("{}<{}>", schema.kind, items),
+                    None => schema.kind.clone(),
+                })
+                .unwrap_or_default(),
+            valid_values: f
+                .valid_values
+                .iter()
+                .map(|v| v.label.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        })
+        .collect();
+
+    ctx.renderer.render_with_id(&rows, "field_id")
+}
+
+async fn create_request(
+    ctx: &JsmContext<'_>,
+    request_type: &str,
+    service_desk: Option<&str>,
+    args: &[String],
+) -> Result<()> {
+    let desk = match service_desk {
+        Some(desk) => resolve_service_desk(ctx, desk).await?,
+        None => request_type_service_desk(ctx, request_type).await?,
+    };
+    let fields = fetch_request_type_fields(ctx, request_type, Some(&desk)).await?;
+    let values = request_field_values(&fields, args)?;
+
+    #[derive(Deserialize)]
+    struct Created {
+        #[serde(rename = "issueKey")]
+        issue_key: String,
+    }
+
+    let created: Created = ctx
+        .client
+        .post(
+            "/rest/servicedeskapi/request",
+            &json!({
+                "serviceDeskId": desk,
+                "requestTypeId": request_type,
+                "requestFieldValues": values,
+            }),
+        )
+        .await
+        .context("Failed to create request")?;
+
+    tracing::info!(key = %created.issue_key, "Request created successfully");
+    println!("✅ Created request: {}", created.issue_key);
+    Ok(())
+}
+
+async fn fetch_request_type_fields(
+    ctx: &JsmContext<'_>,
+    request_type: &str,
+    service_desk: Option<&str>,
+) -> Result<Vec<RequestTypeField>> {
+    #[derive(Deserialize)]
+    struct FieldsResponse {
+        #[serde(rename = "requestTypeFields", default)]
+        request_type_fields: Vec<RequestTypeField>,
+    }
+
+    let desk = match service_desk {
+        Some(desk) => resolve_service_desk(ctx, desk).await?,
+        None => request_type_service_desk(ctx, request_type).await?,
+    };
+    let path = format!("/rest/servicedeskapi/servicedesk/{desk}/requesttype/{request_type}/field");
+    let response: FieldsResponse = ctx
+        .client
+        .get(&path)
+        .await
+        .with_context(|| format!("Failed to fetch fields of request type {request_type}"))?;
+    Ok(response.request_type_fields)
+}
+
+/// Service desk ID for a numeric ID or a project key.
+async fn resolve_service_desk(ctx: &JsmContext<'_>, id_or_key: &str) -> Result<String> {
+    if id_or_key.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(id_or_key.to_string());
+    }
+
+    #[derive(Deserialize)]
+    struct ServiceDesk {
+        id: String,
+        #[serde(rename = "projectKey", default)]
+        project_key: Option<String>,
+    }
+
+    let all = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let desks: Vec<ServiceDesk> = fetch_paged(
+        ctx,
+        "/rest/servicedeskapi/servicedesk",
+        &all,
+        "service desks",
+    )
+    .await?;
+    desks
+        .into_iter()
+        .find(|desk| {
+            desk.project_key
+                .as_deref()
+                .is_some_and(|key| key.eq_ignore_ascii_case(id_or_key))
+        })
+        .map(|desk| desk.id)
+        .ok_or_else(|| anyhow!("No service desk found for project '{id_or_key}'"))
+}
+
+/// The service desk a request type belongs to, from the list of all request types.
+async fn request_type_service_desk(ctx: &JsmContext<'_>, request_type: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct RequestType {
+        id: String,
+        #[serde(rename = "serviceDeskId")]
+        service_desk_id: String,
+    }
+
+    let all = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    let types: Vec<RequestType> = fetch_paged(
+        ctx,
+        "/rest/servicedeskapi/requesttype",
+        &all,
+        "request types",
+    )
+    .await?;
+    types
+        .into_iter()
+        .find(|t| t.id == request_type)
+        .map(|t| t.service_desk_id)
+        .ok_or_else(|| anyhow!("Request type {request_type} not found; pass --service-desk"))
+}
+
+/// Turn `NAME=VALUE` arguments into `requestFieldValues`, matching fields by ID
+/// or name, mapping option labels to their IDs and checking required fields.
+fn request_field_values(
+    fields: &[RequestTypeField],
+    args: &[String],
+) -> Result<Map<String, Value>> {
+    let mut values = Map::new();
+
+    for arg in args {
+        let (name, raw) = arg
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid --field '{arg}'. Expected NAME=VALUE"))?;
+        let (name, raw) = (name.trim(), raw.trim());
+        let field = fields
+            .iter()
+            .find(|f| f.field_id.eq_ignore_ascii_case(name) || f.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let known: Vec<&str> = fields.iter().map(|f| f.field_id.as_str()).collect();
+                anyhow!(
+                    "Field '{name}' is not on this request type. Fields: {}",
+                    known.join(", ")
+                )
+            })?;
+        values.insert(field.field_id.clone(), field_value_json(field, raw)?);
+    }
+
+    let missing: Vec<&str> = fields
+        .iter()
+        .filter(|f| f.required && !values.contains_key(&f.field_id))
+        .map(|f| f.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        bail!("Missing required fields: {}", missing.join(", "));
+    }
+    Ok(values)
+}
+
+fn field_value_json(field: &RequestTypeField, raw: &str) -> Result<Value> {
+    let is_array = field
+        .jira_schema
+        .as_ref()
+        .is_some_and(|schema| schema.kind == "array");
+    let parts: Vec<&str> = if is_array {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect()
+    } else {
+        vec![raw]
+    };
+
+    let mut converted = Vec::new();
+    for part in parts {
+        let value = if field.valid_values.is_empty() {
+            match field
+                .jira_schema
+                .as_ref()
+                .map(|schema| schema.kind.as_str())
+            {
+                Some("number") => json!(part
+                    .parse::<f64>()
+                    .with_context(|| format!("{} expects a number, got '{part}'", field.name))?),
+                Some("user") => json!({ "accountId": part }),
+                _ => json!(part),
+            }
+        } else {
+            let option = field
+                .valid_values
+                .iter()
+                .find(|v| v.label.eq_ignore_ascii_case(part) || v.value == part)
+                .ok_or_else(|| {
+                    let labels: Vec<&str> = field
+                        .valid_values
+                        .iter()
+                        .map(|v| v.label.as_str())
+                        .collect();
+                    anyhow!(
+                        "'{part}' is not a valid value for {}. Valid: {}",
+                        field.name,
+                        labels.join(", ")
+                    )
+                })?;
+            json!({ "id": option.value })
+        };
+        converted.push(value);
+    }
+
+    Ok(if is_array {
+        Value::Array(converted)
+    } else {
+        converted.remove(0)
+    })
+}
+
 fn field_value<'a>(fields: &'a [RequestField], id_or_label: &str) -> &'a str {
     fields
         .iter()
@@ -352,3 +753,53 @@ fn field_value<'a>(fields: &'a [RequestField], id_or_label: &str) -> &'a str {
         })
         .unwrap_or("")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_field_values() {
+        let fields: Vec<RequestTypeField> = serde_json::from_value(json!([
+            { "fieldId": "summary", "name": "Summary", "required": true,
+              "jiraSchema": { "type": "string" } },
+            { "fieldId": "customfield_10010", "name": "Impact", "required": true,
+              "validValues": [{ "value": "1", "label": "High" }, { "value": "2", "label": "Low" }],
+              "jiraSchema": { "type": "option" } },
+            { "fieldId": "labels", "name": "Labels",
+              "jiraSchema": { "type": "array", "items": "string" } },
+            { "fieldId": "customfield_10020", "name": "Seats",
+              "jiraSchema": { "type": "number" } }
+        ]))
+        .unwrap();
+
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let values = request_field_values(
+            &fields,
+            &args(&[
+                "summary=VPN down",
+                "impact=high",
+                "Labels=vpn, network",
+                "Seats=3",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            Value::Object(values),
+            json!({
+                "summary": "VPN down",
+                "customfield_10010": { "id": "1" },
+                "labels": ["vpn", "network"],
+                "customfield_10020": 3.0
+            })
+        );
+
+        let missing = request_field_values(&fields, &args(&["summary=VPN down"])).unwrap_err();
+        assert_eq!(missing.to_string(), "Missing required fields: Impact");
+        assert!(request_field_values(&fields, &args(&["impact=Medium"]))
+            .unwrap_err()
+            .to_string()
+            .contains("Valid: High, Low"));
+        assert!(request_field_values(&fields, &args(&["team=core"])).is_err());
+    }
+}
@@ -1,7 +1,25 @@
+pub mod alias;
 pub mod auth;
 pub mod bamboo;
 pub mod bitbucket;
+pub mod cancel;
+pub mod config;
 pub mod confluence;
+pub mod dates;
+pub mod doctor;
+pub mod dry_run;
+pub mod entity_url;
+pub mod graphql;
+pub mod init;
 pub mod jira;
 pub mod jsm;
+pub mod multi_profile;
 pub mod opsgenie;
+pub mod pagination;
+pub mod playbook;
+pub mod schema;
+pub mod search;
+pub mod statuspage;
+pub mod webhook_events;
+pub mod webhook_signature;
+pub mod whoami;
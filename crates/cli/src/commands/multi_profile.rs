@@ -0,0 +1,115 @@
+use std::future::Future;
+
+use anyhow::{bail, Result};
+use atlassian_cli_api::ApiClient;
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::Value;
+
+/// A resolved profile that a read-only command should be fanned out to.
+pub struct ProfileTarget {
+    pub name: String,
+    pub client: ApiClient,
+    pub workspace: Option<String>,
+}
+
+/// Run `fetch` against every target concurrently and merge the rows, tagging each
+/// with the profile it came from. Profiles that fail are reported and skipped;
+/// the command only fails when no profile succeeded.
+pub async fn fan_out<'a, R, F, Fut>(targets: &'a [ProfileTarget], fetch: F) -> Result<Vec<Value>>
+where
+    R: Serialize,
+    F: Fn(&'a ProfileTarget) -> Fut,
+    Fut: Future<Output = Result<Vec<R>>>,
+{
+    let results = join_all(targets.iter().map(|target| {
+        let pending = fetch(target);
+        async move { (target.name.as_str(), pending.await) }
+    }))
+    .await;
+
+    let mut rows = Vec::new();
+    let mut failures = 0;
+
+    for (profile, result) in results {
+        match result {
+            Ok(items) => {
+                for item in items {
+                    rows.push(tag_row(profile, serde_json::to_value(item)?));
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                tracing::warn!(profile, error = %format!("{err:#}"), "Profile query failed");
+            }
+        }
+    }
+
+    if failures > 0 && failures == targets.len() {
+        bail!("Command failed for all {failures} profiles");
+    }
+
+    Ok(rows)
+}
+
+fn tag_row(profile: &str, row: Value) -> Value {
+    match row {
+        Value::Object(mut obj) => {
+            obj.insert("profile".to_string(), Value::String(profile.to_string()));
+            Value::Object(obj)
+        }
+        other => serde_json::json!({ "profile": profile, "value": other }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn target(name: &str) -> ProfileTarget {
+        ProfileTarget {
+            name: name.to_string(),
+            client: ApiClient::new("https://example.atlassian.net").unwrap(),
+            workspace: None,
+        }
+    }
+
+    #[test]
+    fn test_tag_row_object() {
+        let tagged = tag_row("work", json!({"key": "DEV-1"}));
+        assert_eq!(tagged["profile"], "work");
+        assert_eq!(tagged["key"], "DEV-1");
+    }
+
+    #[test]
+    fn test_tag_row_primitive() {
+        let tagged = tag_row("work", json!("DEV-1"));
+        assert_eq!(tagged, json!({"profile": "work", "value": "DEV-1"}));
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_merges_and_skips_failures() {
+        let targets = vec![target("a"), target("b")];
+        let rows = fan_out(&targets, |t| async move {
+            if t.name == "b" {
+                anyhow::bail!("boom");
+            }
+            Ok(vec![json!({"key": "A-1"})])
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(rows, vec![json!({"key": "A-1", "profile": "a"})]);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_all_failed() {
+        let targets = vec![target("a")];
+        let result = fan_out(&targets, |_| async {
+            Err::<Vec<Value>, _>(anyhow::anyhow!("boom"))
+        })
+        .await;
+        assert!(result.is_err());
+    }
+}
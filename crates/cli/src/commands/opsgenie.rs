@@ -1,6 +1,12 @@
-use clap::{Parser, Subcommand};
+use anyhow::{bail, Context, Result};
+use atlassian_cli_api::ApiClient;
+use atlassian_cli_output::OutputRenderer;
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-#[derive(Parser, Debug, Clone)]
+#[derive(Args, Debug, Clone)]
 pub struct OpsgenieArgs {
     #[command(subcommand)]
     command: OpsgenieCommands,
@@ -16,10 +22,463 @@ enum OpsgenieCommands {
     Schedule,
     /// Team management
     Team,
+    /// Maintenance windows that silence policies and integrations
+    Maintenance {
+        #[command(subcommand)]
+        command: MaintenanceCommands,
+    },
+    /// Alert policy management
+    Policy {
+        #[command(subcommand)]
+        command: PolicyCommands,
+    },
 }
 
-pub async fn execute(_args: OpsgenieArgs) -> anyhow::Result<()> {
-    println!("🚨 Opsgenie commands");
-    println!("⚠️  Not implemented yet - coming in Phase 6 (Weeks 15-16)");
+#[derive(Subcommand, Debug, Clone)]
+enum MaintenanceCommands {
+    /// List maintenance windows that have not expired yet
+    List,
+    /// Schedule a maintenance window
+    Create {
+        /// Start time: RFC 3339 (2024-05-01T22:00:00Z), `now`, or an offset such as `+15m`.
+        /// Offsets use m (minutes), h (hours) or d (days); unlike date flags, `m` is minutes
+        #[arg(long, default_value = "now")]
+        start: String,
+        /// End time: RFC 3339, or an offset from the start such as `+2h` (m, h or d)
+        #[arg(long)]
+        end: String,
+        /// Entities to disable, as `policy:NAME` or `integration:NAME` (IDs work too; comma-separated)
+        #[arg(long, value_delimiter = ',', required = true)]
+        entities: Vec<String>,
+        /// Description shown in Opsgenie
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Cancel a maintenance window before it ends
+    Cancel {
+        /// Maintenance ID
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum PolicyCommands {
+    /// List alert policies
+    List {
+        /// Team ID for team policies (global policies otherwise)
+        #[arg(long)]
+        team_id: Option<String>,
+    },
+    /// Enable an alert policy
+    Enable {
+        /// Policy name or ID
+        name: String,
+        /// Team ID for team policies
+        #[arg(long)]
+        team_id: Option<String>,
+    },
+    /// Disable an alert policy
+    Disable {
+        /// Policy name or ID
+        name: String,
+        /// Team ID for team policies
+        #[arg(long)]
+        team_id: Option<String>,
+    },
+}
+
+pub struct OpsgenieContext<'a> {
+    pub client: ApiClient,
+    pub renderer: &'a OutputRenderer,
+}
+
+/// Opsgenie wraps every response body in `data`.
+#[derive(Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct Policy {
+    id: String,
+    name: String,
+    #[serde(rename = "type", default)]
+    policy_type: String,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    order: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct Integration {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Maintenance {
+    id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    description: Option<String>,
+    time: MaintenanceTime,
+}
+
+#[derive(Deserialize)]
+struct MaintenanceTime {
+    #[serde(default)]
+    #[serde(rename = "startDate")]
+    start_date: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "endDate")]
+    end_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntityType {
+    Policy,
+    Integration,
+}
+
+impl EntityType {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Policy => "policy",
+            Self::Integration => "integration",
+        }
+    }
+}
+
+pub async fn execute(args: OpsgenieArgs, ctx: OpsgenieContext<'_>) -> Result<()> {
+    match args.command {
+        OpsgenieCommands::Alert
+        | OpsgenieCommands::Incident
+        | OpsgenieCommands::Schedule
+        | OpsgenieCommands::Team => {
+            println!("🚨 Opsgenie commands");
+            println!("⚠️  Not implemented yet - coming in Phase 6 (Weeks 15-16)");
+            Ok(())
+        }
+        OpsgenieCommands::Maintenance { command } => match command {
+            MaintenanceCommands::List => list_maintenance(&ctx).await,
+            MaintenanceCommands::Create {
+                start,
+                end,
+                entities,
+                description,
+            } => create_maintenance(&ctx, &start, &end, &entities, description.as_deref()).await,
+            MaintenanceCommands::Cancel { id } => cancel_maintenance(&ctx, &id).await,
+        },
+        OpsgenieCommands::Policy { command } => match command {
+            PolicyCommands::List { team_id } => list_policies(&ctx, team_id.as_deref()).await,
+            PolicyCommands::Enable { name, team_id } => {
+                set_policy_enabled(&ctx, &name, team_id.as_deref(), true).await
+            }
+            PolicyCommands::Disable { name, team_id } => {
+                set_policy_enabled(&ctx, &name, team_id.as_deref(), false).await
+            }
+        },
+    }
+}
+
+async fn list_maintenance(ctx: &OpsgenieContext<'_>) -> Result<()> {
+    let response: DataResponse<Vec<Maintenance>> = ctx
+        .client
+        .get("/v1/maintenance?type=non-expired")
+        .await
+        .context("Failed to list maintenance windows")?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        status: &'a str,
+        start: &'a str,
+        end: &'a str,
+        description: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = response
+        .data
+        .iter()
+        .map(|m| Row {
+            id: m.id.as_str(),
+            status: m.status.as_str(),
+            start: m.time.start_date.as_deref().unwrap_or(""),
+            end: m.time.end_date.as_deref().unwrap_or(""),
+            description: m.description.as_deref().unwrap_or(""),
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("No scheduled or active maintenance windows");
+        return Ok(());
+    }
+
+    ctx.renderer.render(&rows)
+}
+
+async fn create_maintenance(
+    ctx: &OpsgenieContext<'_>,
+    start: &str,
+    end: &str,
+    entities: &[String],
+    description: Option<&str>,
+) -> Result<()> {
+    let start = parse_time(start, Utc::now()).context("Invalid --start")?;
+    let end = parse_time(end, start).context("Invalid --end")?;
+    if end <= start {
+        bail!("--end must be after --start");
+    }
+
+    let mut rules = Vec::new();
+    for entity in entities {
+        let (entity_type, name) = parse_entity(entity)?;
+        let id = resolve_entity(ctx, entity_type, name).await?;
+        rules.push(json!({
+            "state": "disabled",
+            "entity": { "id": id, "type": entity_type.as_str() },
+        }));
+    }
+
+    let payload = json!({
+        "description": description.unwrap_or("Scheduled maintenance"),
+        "time": {
+            "type": "schedule",
+            "startDate": format_time(start),
+            "endDate": format_time(end),
+        },
+        "rules": rules,
+    });
+
+    #[derive(Deserialize)]
+    struct Created {
+        id: String,
+    }
+
+    let response: DataResponse<Created> = ctx
+        .client
+        .post("/v1/maintenance", &payload)
+        .await
+        .context("Failed to create maintenance window")?;
+
+    tracing::info!(id = %response.data.id, "Maintenance window created");
+    println!(
+        "✅ Scheduled maintenance {} from {} to {}",
+        response.data.id,
+        format_time(start),
+        format_time(end)
+    );
+    Ok(())
+}
+
+async fn cancel_maintenance(ctx: &OpsgenieContext<'_>, id: &str) -> Result<()> {
+    let _: Value = ctx
+        .client
+        .post(&format!("/v1/maintenance/{id}/cancel"), &json!({}))
+        .await
+        .with_context(|| format!("Failed to cancel maintenance {id}"))?;
+
+    tracing::info!(id, "Maintenance window cancelled");
+    println!("✅ Cancelled maintenance {id}");
+    Ok(())
+}
+
+async fn fetch_policies(ctx: &OpsgenieContext<'_>, team_id: Option<&str>) -> Result<Vec<Policy>> {
+    let path = match team_id {
+        Some(team) => format!("/v2/policies/alert?teamId={}", urlencoding::encode(team)),
+        None => "/v2/policies/alert".to_string(),
+    };
+    let response: DataResponse<Vec<Policy>> = ctx
+        .client
+        .get(&path)
+        .await
+        .context("Failed to list alert policies")?;
+    Ok(response.data)
+}
+
+async fn list_policies(ctx: &OpsgenieContext<'_>, team_id: Option<&str>) -> Result<()> {
+    let mut policies = fetch_policies(ctx, team_id).await?;
+    policies.sort_by_key(|p| p.order.unwrap_or(i64::MAX));
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        name: &'a str,
+        #[serde(rename = "type")]
+        policy_type: &'a str,
+        enabled: bool,
+    }
+
+    let rows: Vec<Row<'_>> = policies
+        .iter()
+        .map(|p| Row {
+            id: p.id.as_str(),
+            name: p.name.as_str(),
+            policy_type: p.policy_type.as_str(),
+            enabled: p.enabled,
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+async fn set_policy_enabled(
+    ctx: &OpsgenieContext<'_>,
+    name: &str,
+    team_id: Option<&str>,
+    enabled: bool,
+) -> Result<()> {
+    let policies = fetch_policies(ctx, team_id).await?;
+    let policy = find_by_name(&policies, name, |p| (&p.id, &p.name))
+        .with_context(|| format!("Alert policy '{name}' not found"))?;
+
+    let action = if enabled { "enable" } else { "disable" };
+    let mut path = format!("/v2/policies/{}/{action}", policy.id);
+    if let Some(team) = team_id {
+        path.push_str(&format!("?teamId={}", urlencoding::encode(team)));
+    }
+    let _: Value = ctx
+        .client
+        .post(&path, &json!({}))
+        .await
+        .with_context(|| format!("Failed to {action} alert policy '{}'", policy.name))?;
+
+    tracing::info!(id = %policy.id, enabled, "Alert policy updated");
+    let verb = if enabled { "Enabled" } else { "Disabled" };
+    println!("✅ {verb} alert policy: {}", policy.name);
     Ok(())
 }
+
+async fn resolve_entity(
+    ctx: &OpsgenieContext<'_>,
+    entity_type: EntityType,
+    name: &str,
+) -> Result<String> {
+    let id = match entity_type {
+        EntityType::Policy => {
+            let policies = fetch_policies(ctx, None).await?;
+            find_by_name(&policies, name, |p| (&p.id, &p.name)).map(|p| p.id.clone())
+        }
+        EntityType::Integration => {
+            let response: DataResponse<Vec<Integration>> = ctx
+                .client
+                .get("/v2/integrations")
+                .await
+                .context("Failed to list integrations")?;
+            find_by_name(&response.data, name, |i| (&i.id, &i.name)).map(|i| i.id.clone())
+        }
+    };
+    id.with_context(|| format!("No {} named '{name}' found", entity_type.as_str()))
+}
+
+/// Match an entity by exact ID, or by case-insensitive name.
+fn find_by_name<'a, T>(
+    items: &'a [T],
+    name: &str,
+    fields: impl Fn(&T) -> (&String, &String),
+) -> Option<&'a T> {
+    items
+        .iter()
+        .find(|item| fields(item).0 == name)
+        .or_else(|| {
+            items
+                .iter()
+                .find(|item| fields(item).1.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Split `policy:NAME` / `integration:NAME` into its type and name.
+fn parse_entity(entity: &str) -> Result<(EntityType, &str)> {
+    let (kind, name) = entity.split_once(':').with_context(|| {
+        format!("Invalid entity '{entity}': expected policy:NAME or integration:NAME")
+    })?;
+    let entity_type = match kind.trim() {
+        "policy" => EntityType::Policy,
+        "integration" => EntityType::Integration,
+        other => bail!("Invalid entity type '{other}'. Must be one of: policy, integration"),
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Invalid entity '{entity}': missing name");
+    }
+    Ok((entity_type, name))
+}
+
+/// Parse `now`, an offset from `base` such as `+90m`, `+2h` or `+1d`, or an RFC 3339 timestamp.
+/// Maintenance windows are sub-day, so `m` is minutes here rather than months as in date flags.
+fn parse_time(value: &str, base: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("now") {
+        return Ok(base);
+    }
+    if let Some(offset) = value.strip_prefix('+') {
+        let (amount, unit) = offset.split_at(offset.len().saturating_sub(1));
+        let amount: i64 = amount
+            .parse()
+            .with_context(|| format!("Invalid offset '{value}'"))?;
+        let duration = match unit {
+            "m" => TimeDelta::try_minutes(amount),
+            "h" => TimeDelta::try_hours(amount),
+            "d" => TimeDelta::try_days(amount),
+            _ => bail!("Invalid offset '{value}': use m (minutes), h or d, e.g. +30m"),
+        };
+        return duration
+            .and_then(|duration| base.checked_add_signed(duration))
+            .with_context(|| format!("Invalid offset '{value}': too far in the future"));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .with_context(|| {
+            format!("Invalid time '{value}': use RFC 3339 (2024-05-01T22:00:00Z), now, or +2h")
+        })
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        let base = DateTime::parse_from_rfc3339("2024-05-01T22:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_time("now", base).unwrap(), base);
+        assert_eq!(
+            format_time(parse_time("+90m", base).unwrap()),
+            "2024-05-01T23:30:00Z"
+        );
+        assert_eq!(
+            format_time(parse_time("+1d", base).unwrap()),
+            "2024-05-02T22:00:00Z"
+        );
+        assert_eq!(
+            format_time(parse_time("2024-05-02T01:00:00+02:00", base).unwrap()),
+            "2024-05-01T23:00:00Z"
+        );
+        assert!(parse_time("+2w", base).is_err());
+        assert!(parse_time("+99999999999999d", base).is_err());
+        assert!(parse_time("+9223372036854775807m", base).is_err());
+        assert!(parse_time("tomorrow", base).is_err());
+    }
+
+    #[test]
+    fn test_parse_entity() {
+        assert_eq!(
+            parse_entity("policy:Night paging").unwrap(),
+            (EntityType::Policy, "Night paging")
+        );
+        assert_eq!(
+            parse_entity("integration: Datadog").unwrap(),
+            (EntityType::Integration, "Datadog")
+        );
+        assert!(parse_entity("Datadog").is_err());
+        assert!(parse_entity("team:SRE").is_err());
+        assert!(parse_entity("policy:").is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use std::future::Future;
+
+use anyhow::Result;
+use clap::Args;
+
+/// Paging flags shared by every list command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PaginationArgs {
+    /// Maximum number of results (0 for no limit; default depends on the command)
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Number of results requested per API call
+    #[arg(long)]
+    pub page_size: Option<usize>,
+    /// Fetch every page (ignores --limit)
+    #[arg(long)]
+    pub all: bool,
+    /// Skip this many results before the first one shown
+    #[arg(long, default_value_t = 0)]
+    pub starting_at: usize,
+}
+
+/// How an endpoint pages through results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paging {
+    /// The first request can start at any offset (`startAt`, `start`, `page`).
+    Offset,
+    /// Pages can only be reached by following cursors from the beginning.
+    Cursor,
+}
+
+/// One API request to make: an offset and size, or the cursor the previous page returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest {
+    pub start: usize,
+    pub size: usize,
+    pub cursor: Option<String>,
+}
+
+impl PageRequest {
+    /// 1-based page number for APIs paged by `page` rather than offset.
+    pub fn number(&self) -> usize {
+        self.start / self.size + 1
+    }
+}
+
+/// Results of one API request.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub has_more: bool,
+    pub cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn offset(items: Vec<T>, has_more: bool) -> Self {
+        Self {
+            items,
+            has_more,
+            cursor: None,
+        }
+    }
+
+    pub fn cursor(items: Vec<T>, next: Option<String>) -> Self {
+        Self {
+            items,
+            has_more: next.is_some(),
+            cursor: next,
+        }
+    }
+}
+
+impl PaginationArgs {
+    /// Upper bound on returned results; `None` when every page is wanted.
+    pub fn max_items(&self, default_limit: usize) -> Option<usize> {
+        match self.limit.unwrap_or(default_limit) {
+            _ if self.all => None,
+            0 => None,
+            limit => Some(limit),
+        }
+    }
+
+    /// Results to request per call, capped at what the endpoint accepts.
+    pub fn request_size(&self, default_limit: usize, max_page_size: usize) -> usize {
+        let wanted = self
+            .page_size
+            .or_else(|| self.max_items(default_limit))
+            .unwrap_or(max_page_size);
+        wanted.clamp(1, max_page_size)
+    }
+
+    /// Fetch pages until the limit is reached or the endpoint runs out of results.
+    pub async fn collect<T, F, Fut>(
+        &self,
+        default_limit: usize,
+        max_page_size: usize,
+        paging: Paging,
+        mut fetch: F,
+    ) -> Result<Vec<T>>
+    where
+        F: FnMut(PageRequest) -> Fut,
+        Fut: Future<Output = Result<Page<T>>>,
+    {
+        let max_items = self.max_items(default_limit);
+        let size = self.request_size(default_limit, max_page_size);
+
+        // Offset endpoints start on the page holding `starting_at`; cursor
+        // endpoints are walked from the beginning and skipped client-side.
+        let first = match paging {
+            Paging::Offset => self.starting_at / size * size,
+            Paging::Cursor => 0,
+        };
+        let mut skip = self.starting_at - first;
+        let mut request = PageRequest {
+            start: first,
+            size,
+            cursor: None,
+        };
+        let mut items = Vec::new();
+
+        loop {
+            let page = fetch(request.clone()).await?;
+            let count = page.items.len();
+            items.extend(page.items.into_iter().skip(skip));
+            skip = skip.saturating_sub(count);
+
+            if max_items.is_some_and(|max| items.len() >= max) || count == 0 || !page.has_more {
+                break;
+            }
+            request = PageRequest {
+                start: request.start + count,
+                size,
+                cursor: page.cursor,
+            };
+        }
+
+        if let Some(max) = max_items {
+            items.truncate(max);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve `total` numbered items, honouring offsets and returning cursors like an API would.
+    fn serve(
+        request: PageRequest,
+        total: usize,
+        requests: &mut Vec<PageRequest>,
+    ) -> Result<Page<usize>> {
+        requests.push(request.clone());
+        let start = request
+            .cursor
+            .as_deref()
+            .map_or(request.start, |c| c.parse().unwrap());
+        let end = (start + request.size).min(total);
+        let next = (end < total).then(|| end.to_string());
+        Ok(Page::cursor((start..end).collect(), next))
+    }
+
+    #[test]
+    fn test_limits() {
+        let default = PaginationArgs::default();
+        assert_eq!(default.max_items(25), Some(25));
+        assert_eq!(default.request_size(25, 100), 25);
+        assert_eq!(default.request_size(500, 100), 100);
+
+        let all = PaginationArgs {
+            all: true,
+            limit: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(all.max_items(25), None);
+        assert_eq!(all.request_size(25, 100), 100);
+        let unlimited = PaginationArgs {
+            limit: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(unlimited.max_items(25), None);
+
+        let sized = PaginationArgs {
+            page_size: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(sized.request_size(25, 50), 50);
+    }
+
+    #[tokio::test]
+    async fn test_collect_offset() {
+        let args = PaginationArgs {
+            limit: Some(7),
+            page_size: Some(5),
+            starting_at: 12,
+            ..Default::default()
+        };
+        let mut requests = Vec::new();
+        let items = args
+            .collect(25, 100, Paging::Offset, |r| {
+                let result = serve(r, 30, &mut requests);
+                async move { result }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(items, (12..19).collect::<Vec<_>>());
+        assert_eq!(requests[0].start, 10);
+        assert_eq!(requests[0].number(), 3);
+        assert_eq!(requests.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_collect_cursor_all() {
+        let args = PaginationArgs {
+            all: true,
+            starting_at: 3,
+            ..Default::default()
+        };
+        let mut requests = Vec::new();
+        let items = args
+            .collect(25, 10, Paging::Cursor, |r| {
+                let result = serve(r, 23, &mut requests);
+                async move { result }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(items, (3..23).collect::<Vec<_>>());
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[2].cursor.as_deref(), Some("20"));
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_output::OutputRenderer;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::alias::split_words;
+
+// Playbooks for `atlassian-cli run FILE`: a YAML list of CLI commands with
+// shared `${name}` variables, run one after another in this process. Every
+// step is expanded and parsed before the first one starts, so a typo fails the
+// playbook instead of leaving it half done
+
+/// A playbook file.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Playbook {
+    /// Variables substituted for `${name}` in step commands; `--var` overrides them
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// Commands run in order
+    pub steps: Vec<PlaybookStep>,
+}
+
+/// One command of a playbook.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PlaybookStep {
+    /// Label shown in progress and the summary (defaults to the command)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Command without the program name, e.g. `jira transition DEV-1 Done`
+    pub run: String,
+    /// Keep going with the next step when this one fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// A step with its variables substituted and split into arguments.
+#[derive(Debug, PartialEq)]
+pub struct PlannedStep {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub continue_on_error: bool,
+}
+
+#[derive(Serialize)]
+struct StepRow {
+    step: usize,
+    name: String,
+    status: &'static str,
+    seconds: String,
+    error: String,
+}
+
+/// Read a playbook and expand its steps, with `overrides` (`KEY=VALUE`) taking
+/// precedence over the file's `vars`.
+pub fn load(path: &Path, overrides: &[String]) -> Result<Vec<PlannedStep>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read playbook {}", path.display()))?;
+    let playbook: Playbook = serde_yaml::from_str(&text)
+        .with_context(|| format!("Invalid playbook {}", path.display()))?;
+    plan(playbook, overrides)
+}
+
+fn plan(playbook: Playbook, overrides: &[String]) -> Result<Vec<PlannedStep>> {
+    let mut vars = playbook.vars;
+    for assignment in overrides {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Invalid --var '{assignment}'. Expected KEY=VALUE"))?;
+        vars.insert(key.trim().to_string(), value.to_string());
+    }
+    if playbook.steps.is_empty() {
+        bail!("Playbook has no steps");
+    }
+
+    playbook
+        .steps
+        .into_iter()
+        .enumerate()
+        .map(|(index, step)| {
+            let number = index + 1;
+            let command = substitute(&step.run, &vars)
+                .with_context(|| format!("Step {number}: {}", step.run))?;
+            let args =
+                split_words(&command).with_context(|| format!("Step {number}: {}", step.run))?;
+            if args.is_empty() {
+                bail!("Step {number} has an empty command");
+            }
+            Ok(PlannedStep {
+                name: step.name.unwrap_or_else(|| command.clone()),
+                command,
+                args,
+                continue_on_error: step.continue_on_error,
+            })
+        })
+        .collect()
+}
+
+/// Replace `${name}` with its variable; `$$` is a literal `$`.
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('$') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            out.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('{') {
+            let end = after
+                .find('}')
+                .ok_or_else(|| anyhow!("Unterminated '${{' in '{text}'"))?;
+            let name = after[..end].trim();
+            let value = vars
+                .get(name)
+                .ok_or_else(|| anyhow!("Undefined variable '{name}'"))?;
+            out.push_str(value);
+            rest = &after[end + 1..];
+        } else {
+            out.push('$');
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Print the expanded steps without running them.
+pub fn render_plan(steps: &[PlannedStep], renderer: &OutputRenderer) -> Result<()> {
+    #[derive(Serialize)]
+    struct PlanRow<'a> {
+        step: usize,
+        name: &'a str,
+        command: &'a str,
+        continue_on_error: bool,
+    }
+
+    let rows: Vec<PlanRow<'_>> = steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| PlanRow {
+            step: index + 1,
+            name: &step.name,
+            command: &step.command,
+            continue_on_error: step.continue_on_error,
+        })
+        .collect();
+    renderer.render(&rows)
+}
+
+/// Run the steps in order and print a summary. A failed step stops the
+/// playbook unless it has `continue_on_error`; the remaining steps are
+/// reported as skipped.
+pub async fn execute<T, F, Fut>(
+    steps: Vec<(PlannedStep, T)>,
+    renderer: &OutputRenderer,
+    mut run_step: F,
+) -> Result<()>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let total = steps.len();
+    let mut rows = Vec::with_capacity(total);
+    let mut stopped_at = None;
+
+    for (index, (step, parsed)) in steps.into_iter().enumerate() {
+        let number = index + 1;
+        if stopped_at.is_some() {
+            rows.push(StepRow {
+                step: number,
+                name: step.name,
+                status: "skipped",
+                seconds: String::new(),
+                error: String::new(),
+            });
+            continue;
+        }
+
+        println!("▶ [{number}/{total}] {}", step.name);
+        let started = Instant::now();
+        let result = run_step(parsed).await;
+        let seconds = format!("{:.1}", started.elapsed().as_secs_f64());
+        let (status, error) = match &result {
+            Ok(()) => ("ok", String::new()),
+            Err(err) if step.continue_on_error => ("failed (continued)", format!("{err:#}")),
+            Err(err) => ("failed", format!("{err:#}")),
+        };
+        if let Err(err) = &result {
+            eprintln!("Error: {err:#}");
+            if !step.continue_on_error {
+                stopped_at = Some(number);
+            }
+        }
+        rows.push(StepRow {
+            step: number,
+            name: step.name,
+            status,
+            seconds,
+            error,
+        });
+    }
+
+    println!();
+    renderer.render(&rows)?;
+    if let Some(number) = stopped_at {
+        bail!("Playbook stopped: step {number} failed");
+    }
+    tracing::info!(steps = total, "Playbook finished");
+    println!("✅ Playbook finished: {total} step(s)");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playbook(yaml: &str) -> Playbook {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let vars = BTreeMap::from([("version".to_string(), "1.4.0".to_string())]);
+        assert_eq!(
+            substitute("release ${version} costs $$5 in $HOME", &vars).unwrap(),
+            "release 1.4.0 costs $5 in $HOME"
+        );
+        assert!(substitute("${missing}", &vars).is_err());
+        assert!(substitute("${version", &vars).is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        let file = playbook(
+            r#"
+vars:
+  version: "1.4.0"
+  project: DEV
+steps:
+  - name: Move tickets
+    run: jira bulk transition --jql "fixVersion = ${version}" --transition Done
+    continue_on_error: true
+  - run: confluence page create --space ${project} --title "Release ${version}" --body notes.md
+"#,
+        );
+        let steps = plan(file, &["version=1.5.0".to_string()]).unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].name, "Move tickets");
+        assert!(steps[0].continue_on_error);
+        assert_eq!(
+            steps[0].args,
+            vec![
+                "jira",
+                "bulk",
+                "transition",
+                "--jql",
+                "fixVersion = 1.5.0",
+                "--transition",
+                "Done"
+            ]
+        );
+        assert_eq!(
+            steps[1].name,
+            "confluence page create --space DEV --title \"Release 1.5.0\" --body notes.md"
+        );
+        assert!(!steps[1].continue_on_error);
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let file = || playbook("steps:\n  - run: jira get ${key}\n");
+        assert!(plan(file(), &[]).is_err());
+        assert!(plan(file(), &["key".to_string()]).is_err());
+        assert!(plan(file(), &["key=DEV-1".to_string()]).is_ok());
+        assert!(plan(playbook("steps: []"), &[]).is_err());
+        assert!(serde_yaml::from_str::<Playbook>("steps:\n  - run: x\n    retry: 3\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_stops_at_failure() {
+        let step = |run: &str, continue_on_error| PlannedStep {
+            name: run.to_string(),
+            command: run.to_string(),
+            args: vec![run.to_string()],
+            continue_on_error,
+        };
+        let steps = vec![
+            (step("a", true), false),
+            (step("b", false), true),
+            (step("c", false), false),
+            (step("d", false), true),
+        ];
+        let mut ran = Vec::new();
+        let renderer = OutputRenderer::new(atlassian_cli_output::OutputFormat::Json);
+        let result = execute(steps, &renderer, |ok| {
+            ran.push(ok);
+            async move {
+                if ok {
+                    Ok(())
+                } else {
+                    Err(anyhow!("boom"))
+                }
+            }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(ran, vec![false, true, false]);
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use atlassian_cli_config::Config;
+use clap::ValueEnum;
+use schemars::Schema;
+
+use super::confluence::SpaceTemplate;
+use super::jira::{ImportIssue, IssueTemplate};
+use super::playbook::Playbook;
+
+// JSON Schemas for the files the CLI reads, generated from the serde types so
+// they never drift. Point an editor at them for validation and completion.
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// The config file (~/.atlassian-cli/config.yaml)
+    Config,
+    /// Issue templates for `jira recurring add --template FILE`
+    Template,
+    /// Batch playbooks for `run FILE`
+    #[value(alias = "playbook")]
+    Batch,
+    /// Space templates for `confluence space create --from-template FILE`
+    SpaceTemplate,
+    /// Issue lists for `jira bulk import --file FILE`
+    Import,
+}
+
+pub fn schema(kind: SchemaKind) -> Schema {
+    let mut schema = match kind {
+        SchemaKind::Config => schemars::schema_for!(Config),
+        SchemaKind::Template => schemars::schema_for!(IssueTemplate),
+        SchemaKind::Batch => schemars::schema_for!(Playbook),
+        SchemaKind::SpaceTemplate => schemars::schema_for!(SpaceTemplate),
+        SchemaKind::Import => schemars::schema_for!(Vec<ImportIssue>),
+    };
+    if kind == SchemaKind::Import {
+        schema.insert("title".to_string(), "ImportBatch".into());
+    }
+    schema
+}
+
+pub fn print(kind: SchemaKind) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&schema(kind))?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schemas_describe_files() {
+        let config = schema(SchemaKind::Config).to_value();
+        assert!(config["properties"]["profiles"].is_object());
+        assert!(config["properties"]["default_profile"].is_object());
+
+        let template = schema(SchemaKind::Template).to_value();
+        assert_eq!(template["title"], "IssueTemplate");
+        assert!(template["properties"]["summary"].is_object());
+        let required = template["required"].as_array().unwrap();
+        assert!(required.contains(&"project".into()));
+        assert!(!required.contains(&"issue_type".into()));
+
+        let batch = schema(SchemaKind::Batch).to_value();
+        assert_eq!(batch["title"], "Playbook");
+        assert!(batch["properties"]["steps"].is_object());
+        assert!(batch["properties"]["vars"].is_object());
+
+        let space = schema(SchemaKind::SpaceTemplate).to_value();
+        assert!(space["properties"]["homepage"].is_object());
+
+        let import = schema(SchemaKind::Import).to_value();
+        assert_eq!(import["type"], "array");
+        assert_eq!(import["title"], "ImportBatch");
+    }
+}
@@ -0,0 +1,310 @@
+use anyhow::{bail, Context, Result};
+use atlassian_cli_api::ApiClient;
+use atlassian_cli_output::{OutputFormat, OutputRenderer};
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::whoami::Product;
+use crate::query::{CqlBuilder, JqlBuilder};
+
+// One search across Jira issues, Confluence content and Bitbucket code and
+// pull requests, for "where did we discuss this?". Products are queried
+// concurrently; a product that fails is reported and the others still shown.
+
+/// Repositories searched for pull requests when none are named.
+const PR_REPO_LIMIT: usize = 25;
+const PR_CONCURRENCY: usize = 8;
+
+/// Clients for the products a search covers.
+pub struct SearchTargets {
+    /// Jira and Confluence share the site client
+    pub site: ApiClient,
+    pub bitbucket: ApiClient,
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct SearchHit {
+    product: &'static str,
+    kind: String,
+    title: String,
+    location: String,
+    url: String,
+}
+
+pub async fn execute(
+    query: &str,
+    products: &[Product],
+    repos: &[String],
+    limit: usize,
+    targets: SearchTargets,
+    renderer: &OutputRenderer,
+) -> Result<()> {
+    if query.trim().is_empty() {
+        bail!("Search query cannot be empty");
+    }
+
+    let searches = products.iter().map(|&product| {
+        let targets = &targets;
+        async move {
+            let hits = match product {
+                Product::Jira => search_jira(&targets.site, query, limit).await,
+                Product::Confluence => search_confluence(&targets.site, query, limit).await,
+                Product::Bitbucket => match targets.workspace.as_deref() {
+                    Some(workspace) => {
+                        search_bitbucket(&targets.bitbucket, workspace, repos, query, limit).await
+                    }
+                    None => Err(anyhow::anyhow!("no workspace configured for this profile")),
+                },
+            };
+            (product, hits)
+        }
+    });
+
+    let mut hits = Vec::new();
+    let mut failed = Vec::new();
+    for (product, result) in join_all(searches).await {
+        match result {
+            Ok(found) => hits.extend(found),
+            Err(err) => {
+                eprintln!("⚠️  Skipped {}: {err:#}", product.name());
+                failed.push(product);
+            }
+        }
+    }
+    if failed.len() == products.len() {
+        bail!("Search failed for every product");
+    }
+    tracing::info!(
+        hits = hits.len(),
+        failed = failed.len(),
+        "Searched products"
+    );
+
+    if renderer.format() != OutputFormat::Table {
+        return renderer.render(&hits);
+    }
+    if hits.is_empty() {
+        println!("No results for \"{query}\"");
+        return Ok(());
+    }
+
+    for product in products.iter().filter(|p| !failed.contains(p)) {
+        let group: Vec<&SearchHit> = hits
+            .iter()
+            .filter(|hit| hit.product == product.name())
+            .collect();
+        println!("\n{} ({})", product.name(), group.len());
+        if !group.is_empty() {
+            renderer.render(&group)?;
+        }
+    }
+    Ok(())
+}
+
+async fn search_jira(client: &ApiClient, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+    let jql = format!(
+        "{} ORDER BY updated DESC",
+        JqlBuilder::new().contains("text", query).finish()
+    );
+    let payload = json!({
+        "jql": jql,
+        "maxResults": limit,
+        "fields": ["summary", "status", "project"],
+    });
+    let response: Value = client
+        .post("/rest/api/3/search", &payload)
+        .await
+        .context("Failed to search Jira issues")?;
+
+    let browse = format!("{}/browse", client.base_url().trim_end_matches('/'));
+    Ok(response["issues"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|issue| {
+            let key = text(issue, "/key");
+            SearchHit {
+                product: Product::Jira.name(),
+                kind: "issue".to_string(),
+                title: format!("{key} {}", text(issue, "/fields/summary")),
+                location: text(issue, "/fields/status/name"),
+                url: format!("{browse}/{key}"),
+            }
+        })
+        .collect())
+}
+
+async fn search_confluence(
+    client: &ApiClient,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let cql = format!(
+        "{} AND type in (page, blogpost) ORDER BY lastmodified DESC",
+        CqlBuilder::new().contains("text", query).finish()
+    );
+    let response: Value = client
+        .get(&format!(
+            "/wiki/rest/api/search?cql={}&limit={limit}",
+            urlencoding::encode(&cql)
+        ))
+        .await
+        .context("Failed to search Confluence")?;
+
+    let default_base = format!("{}/wiki", client.base_url().trim_end_matches('/'));
+    let base = response["_links"]["base"]
+        .as_str()
+        .unwrap_or(&default_base)
+        .trim_end_matches('/')
+        .to_string();
+    Ok(response["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| SearchHit {
+            product: Product::Confluence.name(),
+            kind: text(result, "/content/type"),
+            title: text(result, "/content/title"),
+            location: text(result, "/resultGlobalContainer/title"),
+            url: format!("{base}{}", text(result, "/url")),
+        })
+        .collect())
+}
+
+/// Code search across the workspace, plus pull requests whose title or
+/// description mentions the query. Bitbucket cannot search pull requests
+/// workspace-wide, so they are looked up in `repos`, or else in the most
+/// recently updated repositories.
+async fn search_bitbucket(
+    client: &ApiClient,
+    workspace: &str,
+    repos: &[String],
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let code: Value = client
+        .get(&format!(
+            "/2.0/workspaces/{workspace}/search/code?search_query={}&pagelen={limit}",
+            urlencoding::encode(query)
+        ))
+        .await
+        .context("Failed to search Bitbucket code")?;
+    let mut hits: Vec<SearchHit> = code["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| {
+            let repo = text(result, "/file/commit/repository/full_name");
+            let path = text(result, "/file/path");
+            let hash = text(result, "/file/commit/hash");
+            SearchHit {
+                product: Product::Bitbucket.name(),
+                kind: "code".to_string(),
+                title: path.clone(),
+                url: format!("https://bitbucket.org/{repo}/src/{hash}/{path}"),
+                location: repo,
+            }
+        })
+        .collect();
+
+    let repos = if repos.is_empty() {
+        let recent: Value = client
+            .get(&format!(
+                "/2.0/repositories/{workspace}?sort=-updated_on&pagelen={PR_REPO_LIMIT}&fields=values.slug"
+            ))
+            .await
+            .context("Failed to list Bitbucket repositories")?;
+        recent["values"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|repo| text(repo, "/slug"))
+            .collect()
+    } else {
+        repos.to_vec()
+    };
+
+    let escaped = query.replace('\\', "\\\\").replace('"', "\\\"");
+    let filter = urlencoding::encode(&format!(
+        "title ~ \"{escaped}\" OR description ~ \"{escaped}\""
+    ))
+    .into_owned();
+    let pull_requests: Vec<Vec<SearchHit>> = stream::iter(repos)
+        .map(|repo| {
+            let filter = &filter;
+            async move {
+                let path = format!(
+                    "/2.0/repositories/{workspace}/{repo}/pullrequests?q={filter}&state=OPEN&state=MERGED&state=DECLINED&sort=-updated_on&pagelen={limit}"
+                );
+                match client.get::<Value>(&path).await {
+                    Ok(response) => pull_request_hits(&response, workspace, &repo),
+                    Err(err) => {
+                        tracing::debug!(%repo, error = %err, "Pull request search failed");
+                        Vec::new()
+                    }
+                }
+            }
+        })
+        .buffer_unordered(PR_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut pull_requests: Vec<SearchHit> = pull_requests.into_iter().flatten().collect();
+    pull_requests.sort_by(|a, b| a.location.cmp(&b.location).then(a.url.cmp(&b.url)));
+    pull_requests.truncate(limit);
+    hits.extend(pull_requests);
+    Ok(hits)
+}
+
+fn pull_request_hits(response: &Value, workspace: &str, repo: &str) -> Vec<SearchHit> {
+    response["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|pr| SearchHit {
+            product: Product::Bitbucket.name(),
+            kind: format!("pull request ({})", text(pr, "/state").to_lowercase()),
+            title: format!("#{} {}", pr["id"], text(pr, "/title")),
+            location: format!("{workspace}/{repo}"),
+            url: text(pr, "/links/html/href"),
+        })
+        .collect()
+}
+
+fn text(value: &Value, pointer: &str) -> String {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_request_hits() {
+        let response = json!({
+            "values": [{
+                "id": 42,
+                "title": "Fix payment outage retry",
+                "state": "MERGED",
+                "links": { "html": { "href": "https://bitbucket.org/acme/api/pull-requests/42" } }
+            }]
+        });
+        assert_eq!(
+            pull_request_hits(&response, "acme", "api"),
+            vec![SearchHit {
+                product: "bitbucket",
+                kind: "pull request (merged)".to_string(),
+                title: "#42 Fix payment outage retry".to_string(),
+                location: "acme/api".to_string(),
+                url: "https://bitbucket.org/acme/api/pull-requests/42".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,523 @@
+use anyhow::{anyhow, bail, Context, Result};
+use atlassian_cli_api::ApiClient;
+use atlassian_cli_output::OutputRenderer;
+use clap::{Args, Subcommand, ValueEnum};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use super::pagination::{Page, PaginationArgs, Paging};
+
+#[derive(Args, Debug, Clone)]
+pub struct StatuspageArgs {
+    /// Statuspage page ID (defaults to the page stored with `auth statuspage`).
+    #[arg(long, global = true)]
+    pub page_id: Option<String>,
+
+    #[command(subcommand)]
+    command: StatuspageCommands,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum StatuspageCommands {
+    /// Incident operations.
+    #[command(subcommand)]
+    Incidents(IncidentCommands),
+    /// Component operations.
+    #[command(subcommand)]
+    Components(ComponentCommands),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum IncidentCommands {
+    /// List incidents, newest first.
+    List {
+        /// Only show incidents that are not resolved yet
+        #[arg(long)]
+        unresolved: bool,
+        #[command(flatten)]
+        page: PaginationArgs,
+    },
+    /// Open an incident.
+    Create {
+        /// Incident title
+        #[arg(long)]
+        name: String,
+        /// Incident status
+        #[arg(long, value_enum, default_value_t = IncidentStatus::Investigating)]
+        status: IncidentStatus,
+        /// First update shown to subscribers
+        #[arg(long)]
+        body: Option<String>,
+        /// Override the impact calculated from component statuses
+        #[arg(long, value_enum)]
+        impact: Option<Impact>,
+        /// Affected components as NAME=STATUS (comma-separated), e.g. API=major-outage
+        #[arg(long, value_delimiter = ',')]
+        components: Vec<String>,
+        /// Do not notify subscribers
+        #[arg(long)]
+        no_notify: bool,
+    },
+    /// Post an update to an incident.
+    Update {
+        /// Incident ID
+        id: String,
+        /// New incident status
+        #[arg(long, value_enum)]
+        status: Option<IncidentStatus>,
+        /// Update text shown to subscribers
+        #[arg(long)]
+        body: Option<String>,
+        /// New incident title
+        #[arg(long)]
+        name: Option<String>,
+        /// Component statuses as NAME=STATUS (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        components: Vec<String>,
+        /// Do not notify subscribers
+        #[arg(long)]
+        no_notify: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ComponentCommands {
+    /// List components on the page.
+    List,
+    /// Set a component's status.
+    SetStatus {
+        /// Component name or ID
+        component: String,
+        /// New status
+        #[arg(value_enum)]
+        status: ComponentStatus,
+    },
+}
+
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum IncidentStatus {
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Impact {
+    None,
+    Minor,
+    Major,
+    Critical,
+}
+
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ComponentStatus {
+    Operational,
+    UnderMaintenance,
+    DegradedPerformance,
+    PartialOutage,
+    MajorOutage,
+}
+
+struct StatuspageContext<'a> {
+    client: ApiClient,
+    renderer: &'a OutputRenderer,
+    page_id: String,
+}
+
+#[derive(Deserialize)]
+struct Incident {
+    id: String,
+    name: String,
+    status: String,
+    #[serde(default)]
+    impact: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    shortlink: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Component {
+    id: String,
+    name: String,
+    status: String,
+    #[serde(default)]
+    group: bool,
+}
+
+#[derive(Serialize)]
+struct IncidentRow<'a> {
+    id: &'a str,
+    name: &'a str,
+    status: &'a str,
+    impact: &'a str,
+    created_at: &'a str,
+    shortlink: &'a str,
+}
+
+impl<'a> From<&'a Incident> for IncidentRow<'a> {
+    fn from(incident: &'a Incident) -> Self {
+        Self {
+            id: &incident.id,
+            name: &incident.name,
+            status: &incident.status,
+            impact: incident.impact.as_deref().unwrap_or(""),
+            created_at: incident.created_at.as_deref().unwrap_or(""),
+            shortlink: incident.shortlink.as_deref().unwrap_or(""),
+        }
+    }
+}
+
+pub async fn execute(
+    args: StatuspageArgs,
+    client: ApiClient,
+    renderer: &OutputRenderer,
+    profile_page_id: Option<&str>,
+) -> Result<()> {
+    let page_id = args
+        .page_id
+        .as_deref()
+        .or(profile_page_id)
+        .ok_or_else(|| {
+            anyhow!(
+                "Statuspage page ID required. Set --page-id, STATUSPAGE_PAGE_ID, \
+                 or store one with `atlassian-cli auth statuspage`"
+            )
+        })?
+        .to_string();
+    let ctx = StatuspageContext {
+        client,
+        renderer,
+        page_id,
+    };
+
+    match args.command {
+        StatuspageCommands::Incidents(cmd) => match cmd {
+            IncidentCommands::List { unresolved, page } => {
+                list_incidents(&ctx, unresolved, &page).await
+            }
+            IncidentCommands::Create {
+                name,
+                status,
+                body,
+                impact,
+                components,
+                no_notify,
+            } => {
+                create_incident(
+                    &ctx,
+                    &name,
+                    status,
+                    body.as_deref(),
+                    impact,
+                    &components,
+                    !no_notify,
+                )
+                .await
+            }
+            IncidentCommands::Update {
+                id,
+                status,
+                body,
+                name,
+                components,
+                no_notify,
+            } => {
+                update_incident(
+                    &ctx,
+                    &id,
+                    status,
+                    body.as_deref(),
+                    name.as_deref(),
+                    &components,
+                    !no_notify,
+                )
+                .await
+            }
+        },
+        StatuspageCommands::Components(cmd) => match cmd {
+            ComponentCommands::List => list_components(&ctx).await,
+            ComponentCommands::SetStatus { component, status } => {
+                set_component_status(&ctx, &component, status).await
+            }
+        },
+    }
+}
+
+async fn list_incidents(
+    ctx: &StatuspageContext<'_>,
+    unresolved: bool,
+    page: &PaginationArgs,
+) -> Result<()> {
+    let base = if unresolved {
+        format!("/v1/pages/{}/incidents/unresolved", ctx.page_id)
+    } else {
+        format!("/v1/pages/{}/incidents", ctx.page_id)
+    };
+
+    let incidents: Vec<Incident> = page
+        .collect(25, 100, Paging::Offset, |request| {
+            let path = format!("{base}?limit={}&page={}", request.size, request.number());
+            async move {
+                let items: Vec<Incident> = ctx
+                    .client
+                    .get(&path)
+                    .await
+                    .context("Failed to list incidents")?;
+                let has_more = items.len() == request.size;
+                Ok(Page::offset(items, has_more))
+            }
+        })
+        .await?;
+
+    if incidents.is_empty() {
+        println!("No incidents found");
+        return Ok(());
+    }
+
+    let rows: Vec<IncidentRow<'_>> = incidents.iter().map(IncidentRow::from).collect();
+    ctx.renderer.render(&rows)
+}
+
+async fn create_incident(
+    ctx: &StatuspageContext<'_>,
+    name: &str,
+    status: IncidentStatus,
+    body: Option<&str>,
+    impact: Option<Impact>,
+    components: &[String],
+    notify: bool,
+) -> Result<()> {
+    let mut incident = json!({
+        "name": name,
+        "status": status,
+        "deliver_notifications": notify,
+    });
+    if let Some(body) = body {
+        incident["body"] = json!(body);
+    }
+    if let Some(impact) = impact {
+        incident["impact_override"] = json!(impact);
+    }
+    add_components(ctx, &mut incident, components).await?;
+
+    let created: Incident = ctx
+        .client
+        .post(
+            &format!("/v1/pages/{}/incidents", ctx.page_id),
+            &json!({ "incident": incident }),
+        )
+        .await
+        .context("Failed to create incident")?;
+
+    tracing::info!(id = %created.id, "Incident created");
+    println!("✅ Created incident: {} (ID: {})", created.name, created.id);
+    Ok(())
+}
+
+async fn update_incident(
+    ctx: &StatuspageContext<'_>,
+    id: &str,
+    status: Option<IncidentStatus>,
+    body: Option<&str>,
+    name: Option<&str>,
+    components: &[String],
+    notify: bool,
+) -> Result<()> {
+    if status.is_none() && body.is_none() && name.is_none() && components.is_empty() {
+        bail!("Nothing to update. Pass --status, --body, --name, or --components");
+    }
+
+    let mut incident = json!({ "deliver_notifications": notify });
+    if let Some(status) = status {
+        incident["status"] = json!(status);
+    }
+    if let Some(body) = body {
+        incident["body"] = json!(body);
+    }
+    if let Some(name) = name {
+        incident["name"] = json!(name);
+    }
+    add_components(ctx, &mut incident, components).await?;
+
+    let updated: Incident = ctx
+        .client
+        .request(
+            Method::PATCH,
+            &format!("/v1/pages/{}/incidents/{id}", ctx.page_id),
+            Some(&json!({ "incident": incident })),
+        )
+        .await
+        .with_context(|| format!("Failed to update incident {id}"))?;
+
+    tracing::info!(id, status = %updated.status, "Incident updated");
+    println!("✅ Updated incident: {} ({})", updated.name, updated.status);
+    Ok(())
+}
+
+/// Resolve `NAME=STATUS` pairs into the incident's `components` and `component_ids`.
+async fn add_components(
+    ctx: &StatuspageContext<'_>,
+    incident: &mut Value,
+    components: &[String],
+) -> Result<()> {
+    if components.is_empty() {
+        return Ok(());
+    }
+
+    let known = fetch_components(ctx).await?;
+    let mut statuses = Map::new();
+    for pair in components {
+        let (component, status) = parse_component_status(pair)?;
+        let id = find_component(&known, component)?.id.clone();
+        statuses.insert(id, json!(status));
+    }
+    incident["component_ids"] = json!(statuses.keys().collect::<Vec<_>>());
+    incident["components"] = Value::Object(statuses);
+    Ok(())
+}
+
+async fn fetch_components(ctx: &StatuspageContext<'_>) -> Result<Vec<Component>> {
+    let all = PaginationArgs {
+        all: true,
+        ..Default::default()
+    };
+    all.collect(100, 100, Paging::Offset, |request| {
+        let path = format!(
+            "/v1/pages/{}/components?per_page={}&page={}",
+            ctx.page_id,
+            request.size,
+            request.number()
+        );
+        async move {
+            let items: Vec<Component> = ctx
+                .client
+                .get(&path)
+                .await
+                .context("Failed to list components")?;
+            let has_more = items.len() == request.size;
+            Ok(Page::offset(items, has_more))
+        }
+    })
+    .await
+}
+
+async fn list_components(ctx: &StatuspageContext<'_>) -> Result<()> {
+    let components = fetch_components(ctx).await?;
+
+    #[derive(Serialize)]
+    struct Row<'a> {
+        id: &'a str,
+        name: &'a str,
+        status: &'a str,
+    }
+
+    let rows: Vec<Row<'_>> = components
+        .iter()
+        .filter(|c| !c.group)
+        .map(|c| Row {
+            id: &c.id,
+            name: &c.name,
+            status: &c.status,
+        })
+        .collect();
+
+    ctx.renderer.render(&rows)
+}
+
+async fn set_component_status(
+    ctx: &StatuspageContext<'_>,
+    component: &str,
+    status: ComponentStatus,
+) -> Result<()> {
+    let components = fetch_components(ctx).await?;
+    let target = find_component(&components, component)?;
+
+    let _: Value = ctx
+        .client
+        .request(
+            Method::PATCH,
+            &format!("/v1/pages/{}/components/{}", ctx.page_id, target.id),
+            Some(&json!({ "component": { "status": status } })),
+        )
+        .await
+        .with_context(|| format!("Failed to update component {}", target.name))?;
+
+    let status = status
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default();
+    tracing::info!(id = %target.id, %status, "Component status updated");
+    println!("✅ Set {} to {status}", target.name);
+    Ok(())
+}
+
+/// Match a component by ID, or by case-insensitive name.
+fn find_component<'a>(components: &'a [Component], component: &str) -> Result<&'a Component> {
+    components
+        .iter()
+        .find(|c| c.id == component)
+        .or_else(|| {
+            components
+                .iter()
+                .find(|c| !c.group && c.name.eq_ignore_ascii_case(component))
+        })
+        .ok_or_else(|| anyhow!("Component '{component}' not found"))
+}
+
+/// Split `NAME=STATUS`, accepting statuses in either `major-outage` or `major_outage` form.
+fn parse_component_status(pair: &str) -> Result<(&str, ComponentStatus)> {
+    let (component, status) = pair
+        .rsplit_once('=')
+        .ok_or_else(|| anyhow!("Invalid component '{pair}': expected NAME=STATUS"))?;
+    let status =
+        ComponentStatus::from_str(&status.trim().replace('_', "-"), true).map_err(|_| {
+            anyhow!(
+                "Invalid component status '{status}'. Must be one of: operational, \
+                 under-maintenance, degraded-performance, partial-outage, major-outage"
+            )
+        })?;
+    Ok((component.trim(), status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_component_status() {
+        assert_eq!(
+            parse_component_status("API=major-outage").unwrap(),
+            ("API", ComponentStatus::MajorOutage)
+        );
+        assert_eq!(
+            parse_component_status("Web app = degraded_performance").unwrap(),
+            ("Web app", ComponentStatus::DegradedPerformance)
+        );
+        assert!(parse_component_status("API").is_err());
+        assert!(parse_component_status("API=down").is_err());
+        assert_eq!(
+            json!(ComponentStatus::UnderMaintenance),
+            json!("under_maintenance")
+        );
+    }
+
+    #[test]
+    fn test_find_component() {
+        let components: Vec<Component> = serde_json::from_value(json!([
+            { "id": "grp1", "name": "API", "status": "operational", "group": true },
+            { "id": "c1", "name": "API", "status": "operational" },
+            { "id": "c2", "name": "Web", "status": "major_outage" }
+        ]))
+        .unwrap();
+        assert_eq!(find_component(&components, "api").unwrap().id, "c1");
+        assert_eq!(find_component(&components, "c2").unwrap().name, "Web");
+        assert!(find_component(&components, "Billing").is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use anyhow::{bail, Result};
+use atlassian_cli_output::OutputRenderer;
+use serde::Serialize;
+
+// Webhook event catalogs, used to validate `--events` before anything is sent
+
+#[derive(Serialize)]
+pub struct WebhookEvent {
+    pub event: &'static str,
+    pub description: &'static str,
+}
+
+const fn event(event: &'static str, description: &'static str) -> WebhookEvent {
+    WebhookEvent { event, description }
+}
+
+/// Events accepted by Jira Cloud webhooks (`/rest/webhooks/1.0/webhook`).
+pub const JIRA_EVENTS: &[WebhookEvent] = &[
+    event("jira:issue_created", "An issue was created"),
+    event("jira:issue_updated", "An issue was updated"),
+    event("jira:issue_deleted", "An issue was deleted"),
+    event("comment_created", "A comment was added to an issue"),
+    event("comment_updated", "An issue comment was edited"),
+    event("comment_deleted", "An issue comment was deleted"),
+    event("attachment_created", "An attachment was added to an issue"),
+    event(
+        "attachment_deleted",
+        "An attachment was removed from an issue",
+    ),
+    event("issuelink_created", "Two issues were linked"),
+    event("issuelink_deleted", "An issue link was removed"),
+    event("worklog_created", "Work was logged on an issue"),
+    event("worklog_updated", "A worklog was edited"),
+    event("worklog_deleted", "A worklog was deleted"),
+    event("project_created", "A project was created"),
+    event("project_updated", "A project was updated"),
+    event("project_deleted", "A project was permanently deleted"),
+    event("project_soft_deleted", "A project was moved to the trash"),
+    event(
+        "project_restored_deleted",
+        "A project was restored from the trash",
+    ),
+    event("project_archived", "A project was archived"),
+    event(
+        "project_restored_archived",
+        "An archived project was restored",
+    ),
+    event("jira:version_created", "A version was created"),
+    event("jira:version_updated", "A version was updated"),
+    event("jira:version_released", "A version was released"),
+    event("jira:version_unreleased", "A version was unreleased"),
+    event("jira:version_moved", "A version was reordered"),
+    event("jira:version_merged", "A version was merged into another"),
+    event("jira:version_deleted", "A version was deleted"),
+    event("user_created", "A user was created"),
+    event("user_updated", "A user was updated"),
+    event("user_deleted", "A user was deleted"),
+    event("sprint_created", "A sprint was created"),
+    event("sprint_updated", "A sprint was updated"),
+    event("sprint_started", "A sprint was started"),
+    event("sprint_closed", "A sprint was completed"),
+    event("sprint_deleted", "A sprint was deleted"),
+    event("board_created", "A board was created"),
+    event("board_updated", "A board was updated"),
+    event("board_deleted", "A board was deleted"),
+    event(
+        "board_configuration_changed",
+        "A board's configuration changed",
+    ),
+    event("issuetype_created", "An issue type was created"),
+    event("issuetype_updated", "An issue type was updated"),
+    event("issuetype_deleted", "An issue type was deleted"),
+    event("option_voting_changed", "Voting was enabled or disabled"),
+    event(
+        "option_watching_changed",
+        "Watching was enabled or disabled",
+    ),
+    event(
+        "option_unassigned_issues_changed",
+        "Unassigned issues were allowed or disallowed",
+    ),
+    event(
+        "option_subtasks_changed",
+        "Subtasks were enabled or disabled",
+    ),
+    event(
+        "option_attachments_changed",
+        "Attachments were enabled or disabled",
+    ),
+    event(
+        "option_issuelinks_changed",
+        "Issue linking was enabled or disabled",
+    ),
+    event(
+        "option_timetracking_changed",
+        "Time tracking was enabled or disabled",
+    ),
+];
+
+/// Events accepted by Bitbucket Cloud repository webhooks.
+pub const BITBUCKET_EVENTS: &[WebhookEvent] = &[
+    event("repo:push", "Commits were pushed to the repository"),
+    event("repo:fork", "The repository was forked"),
+    event("repo:updated", "Repository settings changed"),
+    event("repo:transfer", "The repository was transferred"),
+    event("repo:imported", "The repository was imported"),
+    event("repo:deleted", "The repository was deleted"),
+    event("repo:commit_comment_created", "A commit was commented on"),
+    event(
+        "repo:commit_status_created",
+        "A build status was added to a commit",
+    ),
+    event(
+        "repo:commit_status_updated",
+        "A commit build status changed",
+    ),
+    event("issue:created", "An issue was created"),
+    event("issue:updated", "An issue was updated"),
+    event("issue:comment_created", "An issue was commented on"),
+    event("pullrequest:created", "A pull request was opened"),
+    event("pullrequest:updated", "A pull request was updated"),
+    event("pullrequest:approved", "A pull request was approved"),
+    event(
+        "pullrequest:unapproved",
+        "A pull request approval was removed",
+    ),
+    event(
+        "pullrequest:changes_request_created",
+        "Changes were requested",
+    ),
+    event(
+        "pullrequest:changes_request_removed",
+        "A change request was removed",
+    ),
+    event("pullrequest:fulfilled", "A pull request was merged"),
+    event("pullrequest:rejected", "A pull request was declined"),
+    event(
+        "pullrequest:comment_created",
+        "A pull request was commented on",
+    ),
+    event(
+        "pullrequest:comment_updated",
+        "A pull request comment was edited",
+    ),
+    event(
+        "pullrequest:comment_deleted",
+        "A pull request comment was deleted",
+    ),
+    event(
+        "pullrequest:comment_resolved",
+        "A pull request comment was resolved",
+    ),
+    event(
+        "pullrequest:comment_reopened",
+        "A resolved pull request comment was reopened",
+    ),
+];
+
+pub fn render_events(renderer: &OutputRenderer, catalog: &[WebhookEvent]) -> Result<()> {
+    renderer.render_with_id(&catalog, "event")
+}
+
+/// Reject events missing from the catalog, suggesting the closest valid name.
+/// `list_command` is shown so the user can see every valid event.
+pub fn validate_events(
+    catalog: &[WebhookEvent],
+    events: &[String],
+    list_command: &str,
+) -> Result<()> {
+    let problems: Vec<String> = events
+        .iter()
+        .filter(|e| !catalog.iter().any(|c| c.event == e.as_str()))
+        .map(|e| match closest_event(catalog, e) {
+            Some(suggestion) => format!("Unknown event '{e}'. Did you mean '{suggestion}'?"),
+            None => format!("Unknown event '{e}'."),
+        })
+        .collect();
+
+    if !problems.is_empty() {
+        bail!(
+            "{}\nRun `atlassian-cli {}` to list valid events.",
+            problems.join("\n"),
+            list_command
+        );
+    }
+    Ok(())
+}
+
+fn closest_event(catalog: &[WebhookEvent], input: &str) -> Option<&'static str> {
+    let input = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(2);
+    catalog
+        .iter()
+        .map(|c| (edit_distance(&input, c.event), c.event))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, event)| event)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("repo:push", "repo:push"), 0);
+    }
+
+    #[test]
+    fn test_validate_events() {
+        let valid = vec!["jira:issue_created".to_string()];
+        assert!(validate_events(JIRA_EVENTS, &valid, "jira webhooks events").is_ok());
+
+        let err = validate_events(
+            JIRA_EVENTS,
+            &["jira:issue_create".to_string(), "nonsense".to_string()],
+            "jira webhooks events",
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Unknown event 'jira:issue_create'. Did you mean 'jira:issue_created'?\n\
+             Unknown event 'nonsense'.\n\
+             Run `atlassian-cli jira webhooks events` to list valid events."
+        );
+
+        assert_eq!(
+            closest_event(BITBUCKET_EVENTS, "Repo:Push"),
+            Some("repo:push")
+        );
+    }
+}
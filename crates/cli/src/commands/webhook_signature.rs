@@ -0,0 +1,65 @@
+use anyhow::{anyhow, bail, Result};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+// Webhook payload signing shared by Jira and Bitbucket: both send an
+// `X-Hub-Signature: sha256=<hex>` header, the HMAC-SHA256 of the raw body
+
+/// Header carrying the payload signature.
+pub const SIGNATURE_HEADER: &str = "X-Hub-Signature";
+
+/// Hex-encoded HMAC-SHA256 of `payload`.
+pub fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Compare in constant time; accepts the header value with or without `sha256=`.
+pub fn signature_matches(secret: &str, payload: &[u8], signature: &str) -> Result<bool> {
+    let hex = signature.trim();
+    let hex = hex.strip_prefix("sha256=").unwrap_or(hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        bail!("Invalid signature '{signature}'. Expected sha256=<64 hex characters>");
+    }
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| anyhow!("Invalid signature '{signature}'. Expected hex characters"))?;
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    Ok(mac.verify_slice(&bytes).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_signature_matches() {
+        let payload = br#"{"webhookEvent":"jira:issue_created"}"#;
+        let signature = format!("sha256={}", sign("s3cret", payload));
+
+        assert!(signature_matches("s3cret", payload, &signature).unwrap());
+        assert!(signature_matches("s3cret", payload, &signature[7..]).unwrap());
+        assert!(!signature_matches("other", payload, &signature).unwrap());
+        assert!(signature_matches("s3cret", payload, "sha256=abc").is_err());
+    }
+}
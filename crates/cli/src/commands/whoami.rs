@@ -0,0 +1,156 @@
+use anyhow::Result;
+use atlassian_cli_api::ApiClient;
+use atlassian_cli_output::OutputRenderer;
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::Value;
+
+/// One product to ask "who am I?", with the credentials the CLI would use for it.
+pub struct WhoamiTarget {
+    pub product: Product,
+    pub client: ApiClient,
+    pub token_source: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Product {
+    Jira,
+    Confluence,
+    Bitbucket,
+}
+
+impl Product {
+    pub fn name(self) -> &'static str {
+        match self {
+            Product::Jira => "jira",
+            Product::Confluence => "confluence",
+            Product::Bitbucket => "bitbucket",
+        }
+    }
+
+    pub fn current_user_path(self) -> &'static str {
+        match self {
+            Product::Jira => "/rest/api/3/myself",
+            Product::Confluence => "/wiki/rest/api/user/current",
+            Product::Bitbucket => "/2.0/user",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct WhoamiRow {
+    profile: String,
+    product: &'static str,
+    base_url: String,
+    user: String,
+    account_id: String,
+    token_source: String,
+    rate_limit: String,
+    status: String,
+}
+
+/// Query every product in parallel; a failing product is reported in its row
+/// rather than failing the whole command.
+pub async fn execute(
+    profile: &str,
+    targets: Vec<WhoamiTarget>,
+    renderer: &OutputRenderer,
+) -> Result<()> {
+    let rows = join_all(
+        targets
+            .into_iter()
+            .map(|target| whoami_row(profile, target)),
+    )
+    .await;
+    let failed = rows.iter().filter(|r| r.status != "ok").count();
+    tracing::info!(products = rows.len(), failed, "Checked authenticated users");
+    renderer.render(&rows)
+}
+
+async fn whoami_row(profile: &str, target: WhoamiTarget) -> WhoamiRow {
+    let product = target.product;
+    let response: atlassian_cli_api::error::Result<Value> =
+        target.client.get(product.current_user_path()).await;
+    let info = target.client.rate_limiter().get_info().await;
+    let rate_limit = match (info.remaining, info.limit) {
+        (Some(remaining), Some(limit)) => match info.reset_at {
+            Some(reset) => format!("{remaining}/{limit} (resets {})", reset.format("%H:%M:%SZ")),
+            None => format!("{remaining}/{limit}"),
+        },
+        _ => "-".to_string(),
+    };
+
+    let (user, account_id, status) = match response {
+        Ok(body) => {
+            let (user, account_id) = user_identity(product, &body);
+            (user, account_id, "ok".to_string())
+        }
+        Err(err) => (String::new(), String::new(), format!("error: {err}")),
+    };
+
+    WhoamiRow {
+        profile: profile.to_string(),
+        product: product.name(),
+        base_url: target.client.base_url().trim_end_matches('/').to_string(),
+        user,
+        account_id,
+        token_source: target.token_source,
+        rate_limit,
+        status,
+    }
+}
+
+/// Display name (with email or username when the API exposes it) and account ID
+/// from a product's current-user response.
+pub fn user_identity(product: Product, body: &Value) -> (String, String) {
+    let text = |key: &str| body[key].as_str().unwrap_or_default().to_string();
+    let (name, detail, account_id) = match product {
+        Product::Jira => (text("displayName"), text("emailAddress"), text("accountId")),
+        Product::Confluence => (text("displayName"), text("email"), text("accountId")),
+        Product::Bitbucket => (text("display_name"), text("username"), text("account_id")),
+    };
+    let user = if detail.is_empty() {
+        name
+    } else {
+        format!("{name} ({detail})")
+    };
+    (user, account_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_user_identity() {
+        let jira = json!({
+            "displayName": "Ana Lima",
+            "emailAddress": "ana@example.com",
+            "accountId": "5b10a2844c20165700ede21g"
+        });
+        assert_eq!(
+            user_identity(Product::Jira, &jira),
+            (
+                "Ana Lima (ana@example.com)".to_string(),
+                "5b10a2844c20165700ede21g".to_string()
+            )
+        );
+
+        let confluence = json!({ "displayName": "Ana Lima", "accountId": "5b10" });
+        assert_eq!(
+            user_identity(Product::Confluence, &confluence).0,
+            "Ana Lima"
+        );
+
+        let bitbucket = json!({
+            "display_name": "Ana Lima",
+            "username": "ana",
+            "account_id": "5b10"
+        });
+        assert_eq!(
+            user_identity(Product::Bitbucket, &bitbucket),
+            ("Ana Lima (ana)".to_string(), "5b10".to_string())
+        );
+    }
+}
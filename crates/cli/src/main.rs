@@ -1,16 +1,31 @@
 mod commands;
 mod query;
 
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
+use atlassian_cli_api::auth::TokenRefresh;
+use atlassian_cli_api::error::ApiError;
+use atlassian_cli_api::explain::ExplainLog;
+use atlassian_cli_api::pool::{ConnectionStats, PoolConfig};
+use atlassian_cli_api::redact::TraceRedaction;
 use atlassian_cli_api::ApiClient;
 use atlassian_cli_auth::token_key;
-use atlassian_cli_config::{migrate_config_if_needed, Config, MigrationResult};
-use atlassian_cli_output::{OutputFormat, OutputRenderer};
-use clap::{Parser, Subcommand};
+use atlassian_cli_config::{migrate_config_if_needed, Config, HttpSettings, MigrationResult};
+use atlassian_cli_output::{
+    DateFormat, DateOptions, OutputFormat, OutputRenderer, QuietOptions, TableLayout, Timezone,
+};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use commands::alias::AliasCommand;
 use commands::auth::{self, AuthCommand};
 use commands::bitbucket::utils::extract_workspace_from_url;
+use commands::cancel;
+use commands::config::ConfigCommand;
+use commands::multi_profile::ProfileTarget;
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[derive(Parser, Debug)]
@@ -20,6 +35,14 @@ struct Cli {
     #[arg(short, long)]
     profile: Option<String>,
 
+    /// Run a read-only command against several profiles (comma-separated) and merge the results
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["profile", "all_profiles"])]
+    profiles: Vec<String>,
+
+    /// Run a read-only command against every configured profile and merge the results
+    #[arg(long, conflicts_with = "profile")]
+    all_profiles: bool,
+
     /// Path to config file (defaults to ~/.atlassian-cli/config.yaml)
     #[arg(long)]
     config: Option<PathBuf>,
@@ -28,10 +51,57 @@ struct Cli {
     #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
     output: OutputFormat,
 
+    /// Print table cells in full instead of fitting them to the terminal width
+    #[arg(long)]
+    full: bool,
+
+    /// Maximum width of any table column
+    #[arg(long, conflicts_with = "full")]
+    max_col_width: Option<usize>,
+
+    /// Zone timestamps are shown in: local, UTC or an IANA name such as Europe/London
+    #[arg(long, value_name = "ZONE")]
+    timezone: Option<Timezone>,
+
+    /// How table and CSV output show timestamps
+    #[arg(long, value_enum)]
+    date_format: Option<DateFormat>,
+
+    /// Field printed by `--output quiet` (defaults to the command's identifier)
+    #[arg(long, value_name = "NAME")]
+    quiet_field: Option<String>,
+
+    /// Separate `--output quiet` values with NUL instead of newlines (for `xargs -0`)
+    #[arg(short = '0', long = "null")]
+    null: bool,
+
     /// Enable verbose logging
     #[arg(long)]
     debug: bool,
 
+    /// Log every HTTP request and print connection reuse statistics on exit
+    #[arg(long)]
+    trace_http: bool,
+
+    /// Mask email addresses and the fields listed under `redact` in the config
+    /// in command output and `--trace-http` logs
+    #[arg(long)]
+    redact_pii: bool,
+
+    /// Print request, retry, 429, byte and wall-time totals when the command finishes
+    #[arg(long)]
+    stats: bool,
+
+    /// Stop the command after this long (e.g. 90s, 10m, 1h30m). Bulk and watch
+    /// commands let in-flight requests finish and print a partial summary
+    #[arg(long, value_name = "DURATION")]
+    timeout: Option<String>,
+
+    /// Print the API calls the command makes instead of changing anything:
+    /// reads are sent so the command can plan, writes are only listed
+    #[arg(long)]
+    explain: bool,
+
     #[command(subcommand)]
     command: AtlassianCommand,
 }
@@ -48,18 +118,123 @@ enum AtlassianCommand {
     Jsm(commands::jsm::JsmArgs),
     /// Opsgenie commands
     Opsgenie(commands::opsgenie::OpsgenieArgs),
+    /// Statuspage commands
+    Statuspage(commands::statuspage::StatuspageArgs),
     /// Bamboo commands
     Bamboo(commands::bamboo::BambooArgs),
+    /// Atlassian GraphQL gateway commands
+    Graphql(commands::graphql::GraphqlArgs),
+    /// Search Jira issues, Confluence pages and Bitbucket code and pull requests at once
+    Search {
+        /// Text to search for
+        query: String,
+        /// Products to search (comma-separated)
+        #[arg(long, value_enum, value_delimiter = ',', default_values = ["jira", "confluence", "bitbucket"])]
+        products: Vec<commands::whoami::Product>,
+        /// Repositories searched for pull requests (defaults to the most recently updated)
+        #[arg(long = "repo")]
+        repos: Vec<String>,
+        /// Maximum results per product
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Show the authenticated user, token source, and rate limits for each product
+    Whoami,
+    /// Diagnose connection problems: DNS, TLS, proxies, clock skew and tokens
+    Doctor,
+    /// Set up a profile interactively, test its connections and install shell completions
+    Init,
+    /// Print the JSON Schema of a file format the CLI reads, for editor validation
+    Schema {
+        /// File format to describe
+        #[arg(value_enum)]
+        kind: commands::schema::SchemaKind,
+    },
+    /// Print shell completions (e.g. `atlassian-cli completions zsh > ~/.zfunc/_atlassian-cli`)
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
     /// Authentication commands
     #[command(subcommand)]
     Auth(AuthCommand),
+    /// Export and import shareable config bundles
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Manage command aliases defined in the config file
+    #[command(subcommand)]
+    Alias(AliasCommand),
+    /// Run the commands listed in a playbook file, in order, and print a summary
+    Run {
+        /// Playbook file (YAML); see `schema batch`
+        file: PathBuf,
+        /// Set a playbook variable, overriding the file (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+        /// Print the expanded steps without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args = expand_aliases(std::env::args().collect())?;
+    let cli = Cli::parse_from(apply_profile_settings(args));
     init_tracing(cli.debug)?;
 
+    let timeout = cli
+        .timeout
+        .as_deref()
+        .map(cancel::parse_duration)
+        .transpose()?;
+    cancel::install(timeout, cli.timeout.clone().unwrap_or_default());
+
+    let trace_http = cli.trace_http;
+    let show_stats = cli.stats;
+    let started = Instant::now();
+    let stats = ConnectionStats::default();
+    let explain = cli.explain.then(ExplainLog::default);
+    if explain.is_some() {
+        eprintln!("Explain mode: writes are recorded, not sent");
+    }
+    let result = tokio::select! {
+        result = run(cli, &stats, explain.clone()) => result,
+        reason = cancel::deadline() => Err(anyhow!("Stopped: {reason}")),
+    };
+
+    if let Some(log) = &explain {
+        println!("{}", log.report());
+        // A write whose response the command needs ends the plan there
+        if let Some(stopped) = result.as_ref().err().and_then(|err| {
+            err.chain()
+                .find_map(|cause| cause.downcast_ref::<ApiError>())
+                .filter(|cause| matches!(cause, ApiError::ExplainStopped { .. }))
+        }) {
+            println!("Stopped: {stopped}");
+            return Ok(());
+        }
+    }
+
+    if trace_http {
+        eprintln!("HTTP connections: {stats}");
+    }
+    if show_stats {
+        eprintln!("HTTP stats: {}", stats.summary(started.elapsed()));
+    }
+
+    if let Err(err) = &result {
+        if cancel::is_cancelled() {
+            // Bulk and watch commands report what got done before they stopped
+            eprintln!("Error: {err:#}");
+            std::process::exit(cancel::exit_code());
+        }
+    }
+    result
+}
+
+async fn run(cli: Cli, stats: &ConnectionStats, explain: Option<ExplainLog>) -> Result<()> {
     // Perform config directory migration if needed (only when no custom path specified)
     if cli.config.is_none() {
         handle_migration();
@@ -67,11 +242,80 @@ async fn main() -> Result<()> {
 
     let config_path = cli.config.clone();
     let mut config = Config::load(config_path.as_ref())?;
-    let renderer = OutputRenderer::new(cli.output);
+    let redact: Vec<String> = if cli.redact_pii {
+        PII_FIELDS
+            .iter()
+            .map(|f| f.to_string())
+            .chain(config.redact.iter().cloned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let renderer = OutputRenderer::new(cli.output)
+        .with_table_layout(TableLayout {
+            full: cli.full,
+            max_col_width: cli.max_col_width,
+        })
+        .with_quiet_options(QuietOptions {
+            field: cli.quiet_field.clone(),
+            nul: cli.null,
+        })
+        .with_date_options(DateOptions {
+            timezone: cli.timezone,
+            format: cli.date_format,
+        })
+        .with_redaction(&redact);
+    let http = HttpOptions {
+        pool: pool_config(&config.http),
+        trace: cli.trace_http,
+        stats: stats.clone(),
+        redaction: cli.redact_pii.then(|| TraceRedaction::new(&redact)),
+        explain,
+    };
+
+    if cli.all_profiles || !cli.profiles.is_empty() {
+        let names = if cli.all_profiles {
+            let mut names: Vec<String> = config.profiles.keys().cloned().collect();
+            names.sort();
+            names
+        } else {
+            cli.profiles.clone()
+        };
+        return run_multi_profile(cli.command, &config, &names, &renderer, &http).await;
+    }
+
+    if let AtlassianCommand::Bitbucket(args) = &cli.command {
+        if args.is_local() {
+            return commands::bitbucket::execute_local(args.clone(), &renderer);
+        }
+    }
 
-    let profile_ctx = if matches!(cli.command, AtlassianCommand::Auth(_)) {
+    let profile_ctx = if matches!(
+        cli.command,
+        AtlassianCommand::Auth(_)
+            | AtlassianCommand::Config(_)
+            | AtlassianCommand::Alias(_)
+            | AtlassianCommand::Doctor
+            | AtlassianCommand::Init
+            | AtlassianCommand::Completions { .. }
+            | AtlassianCommand::Schema { .. }
+            | AtlassianCommand::Run { .. }
+    ) {
         None
     } else {
+        // First run: offer the setup wizard instead of failing
+        if config.profiles.is_empty()
+            && std::io::stdin().is_terminal()
+            && commands::init::offer_first_run()?
+        {
+            if run_init(&mut config, config_path.as_deref(), &http, &renderer)
+                .await?
+                .is_none()
+            {
+                return Ok(());
+            }
+            println!();
+        }
         Some(resolve_active_profile(&config, cli.profile.as_deref())?)
     };
 
@@ -80,29 +324,36 @@ async fn main() -> Result<()> {
             let profile = profile_ctx
                 .as_ref()
                 .expect("profile context is available for product commands");
-            let client = build_product_client(profile)?;
-            commands::jira::execute(args, client, &renderer).await?
+            let client = build_product_client(profile, &http)?;
+            commands::jira::execute(args, client, &renderer, &profile.name).await?
         }
         AtlassianCommand::Confluence(args) => {
             let profile = profile_ctx
                 .as_ref()
                 .expect("profile context is available for product commands");
-            let client = build_product_client(profile)?;
-            commands::confluence::execute(args, client, &renderer).await?
+            let client = build_product_client(profile, &http)?;
+            commands::confluence::execute(args, client, &renderer, &profile.name).await?
         }
         AtlassianCommand::Bitbucket(args) => {
             let profile = profile_ctx
                 .as_ref()
                 .expect("profile context is available for product commands");
-            let client = build_bitbucket_client(profile)?;
-            commands::bitbucket::execute(args, client, &renderer, profile.workspace.as_deref())
-                .await?
+            let client = build_bitbucket_client(profile, &http)?;
+            let jira_client = build_product_client(profile, &http)?;
+            commands::bitbucket::execute(
+                args,
+                client,
+                jira_client,
+                &renderer,
+                profile.workspace.as_deref(),
+            )
+            .await?
         }
         AtlassianCommand::Jsm(args) => {
             let profile = profile_ctx
                 .as_ref()
                 .expect("profile context is available for product commands");
-            let client = build_product_client(profile)?;
+            let client = build_product_client(profile, &http)?;
             commands::jsm::execute(
                 args,
                 commands::jsm::JsmContext {
@@ -112,16 +363,361 @@ async fn main() -> Result<()> {
             )
             .await?
         }
-        AtlassianCommand::Opsgenie(args) => commands::opsgenie::execute(args).await?,
+        AtlassianCommand::Graphql(args) => {
+            let profile = profile_ctx
+                .as_ref()
+                .expect("profile context is available for product commands");
+            let client = build_product_client(profile, &http)?;
+            commands::graphql::execute(
+                args,
+                commands::graphql::GraphqlContext {
+                    client,
+                    renderer: &renderer,
+                },
+            )
+            .await?
+        }
+        AtlassianCommand::Opsgenie(args) => {
+            let profile = profile_ctx
+                .as_ref()
+                .expect("profile context is available for product commands");
+            let client = build_opsgenie_client(profile, &http)?;
+            commands::opsgenie::execute(
+                args,
+                commands::opsgenie::OpsgenieContext {
+                    client,
+                    renderer: &renderer,
+                },
+            )
+            .await?
+        }
+        AtlassianCommand::Statuspage(args) => {
+            let profile = profile_ctx
+                .as_ref()
+                .expect("profile context is available for product commands");
+            let client = build_statuspage_client(profile, &http)?;
+            commands::statuspage::execute(
+                args,
+                client,
+                &renderer,
+                profile.statuspage_page_id.as_deref(),
+            )
+            .await?
+        }
         AtlassianCommand::Bamboo(args) => commands::bamboo::execute(args).await?,
+        AtlassianCommand::Search {
+            query,
+            products,
+            repos,
+            limit,
+        } => {
+            let profile = profile_ctx
+                .as_ref()
+                .expect("profile context is available for product commands");
+            let targets = commands::search::SearchTargets {
+                site: build_product_client(profile, &http)?,
+                bitbucket: build_bitbucket_client(profile, &http)?,
+                workspace: profile.workspace.clone(),
+            };
+            commands::search::execute(&query, &products, &repos, limit, targets, &renderer).await?
+        }
+        AtlassianCommand::Whoami => {
+            let profile = profile_ctx
+                .as_ref()
+                .expect("profile context is available for product commands");
+            let targets = whoami_targets(profile, &http)?;
+            commands::whoami::execute(&profile.name, targets, &renderer).await?
+        }
+        AtlassianCommand::Doctor => {
+            // Keep going without a usable profile: the network checks still help
+            let profile = doctor_profile(&config, cli.profile.as_deref(), &http);
+            let mut base_urls: Vec<String> = config
+                .profiles
+                .values()
+                .filter_map(|p| p.base_url.clone())
+                .chain(std::iter::once(bitbucket_api_url()))
+                .collect();
+            base_urls.sort();
+            base_urls.dedup();
+            commands::doctor::execute(base_urls, profile, &renderer).await?
+        }
+        AtlassianCommand::Init => {
+            run_init(&mut config, config_path.as_deref(), &http, &renderer).await?;
+        }
+        AtlassianCommand::Schema { kind } => commands::schema::print(kind)?,
+        AtlassianCommand::Completions { shell } => {
+            commands::init::print_completions(shell, &mut Cli::command())?
+        }
         AtlassianCommand::Auth(command) => {
             auth::handle(command, &mut config, config_path.as_deref(), &renderer).await?
         }
+        AtlassianCommand::Config(command) => {
+            commands::config::handle(command, &mut config, config_path.as_deref())?
+        }
+        AtlassianCommand::Alias(command) => commands::alias::handle(
+            command,
+            &mut config,
+            config_path.as_deref(),
+            &renderer,
+            &builtin_commands(),
+        )?,
+        AtlassianCommand::Run {
+            file,
+            vars,
+            dry_run,
+        } => {
+            let mut global = Vec::new();
+            if let Some(profile) = &cli.profile {
+                global.extend(["--profile".to_string(), profile.clone()]);
+            }
+            if let Some(config) = &cli.config {
+                global.extend(["--config".to_string(), config.display().to_string()]);
+            }
+            if let Some(output) = cli.output.to_possible_value() {
+                global.extend(["--output".to_string(), output.get_name().to_string()]);
+            }
+            run_playbook(&file, &vars, dry_run, &global, &renderer, &http).await?
+        }
     }
 
     Ok(())
 }
 
+/// Parse every step of a playbook, then run them through `run` in this
+/// process (or just list them with `--dry-run`). Steps share the playbook's
+/// `--profile`, `--config` and `--output` unless they set their own.
+async fn run_playbook(
+    file: &std::path::Path,
+    vars: &[String],
+    dry_run: bool,
+    global: &[String],
+    renderer: &OutputRenderer,
+    http: &HttpOptions,
+) -> Result<()> {
+    let steps = commands::playbook::load(file, vars)?;
+
+    let mut parsed = Vec::with_capacity(steps.len());
+    for (index, step) in steps.into_iter().enumerate() {
+        let mut args = vec!["atlassian-cli".to_string()];
+        for pair in global.chunks(2) {
+            let inline = format!("{}=", pair[0]);
+            if !step
+                .args
+                .iter()
+                .any(|arg| arg == &pair[0] || arg.starts_with(&inline))
+            {
+                args.extend_from_slice(pair);
+            }
+        }
+        args.extend(step.args.iter().cloned());
+        let args = apply_profile_settings(expand_aliases(args)?);
+        let cli = Cli::try_parse_from(&args)
+            .with_context(|| format!("Step {}: {}", index + 1, step.command))?;
+        if matches!(cli.command, AtlassianCommand::Run { .. }) {
+            return Err(anyhow!(
+                "Step {}: playbooks cannot run other playbooks",
+                index + 1
+            ));
+        }
+        parsed.push((step, cli));
+    }
+
+    if dry_run {
+        let steps: Vec<_> = parsed.into_iter().map(|(step, _)| step).collect();
+        return commands::playbook::render_plan(&steps, renderer);
+    }
+
+    commands::playbook::execute(parsed, renderer, |cli| {
+        let step: std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + '_>> =
+            Box::pin(run(cli, &http.stats, http.explain.clone()));
+        step
+    })
+    .await
+}
+
+/// Run the setup wizard, then test the new profile like `doctor` does and
+/// offer shell completions. Returns the profile name unless the user backed out.
+async fn run_init(
+    config: &mut Config,
+    config_path: Option<&std::path::Path>,
+    http: &HttpOptions,
+    renderer: &OutputRenderer,
+) -> Result<Option<String>> {
+    let Some(name) = commands::init::wizard(config, config_path)? else {
+        return Ok(None);
+    };
+
+    println!("\nTesting connections...");
+    let profile = config.resolved_profile(&name)?;
+    let mut base_urls: Vec<String> = profile.base_url.clone().into_iter().collect();
+    if profile.workspace.is_some() {
+        base_urls.push(bitbucket_api_url());
+    }
+    let profile = doctor_profile(config, Some(&name), http);
+    if let Err(err) = commands::doctor::execute(base_urls, profile, renderer).await {
+        // The profile is saved either way; a failed check is fixed separately
+        println!("⚠️  {err:#}. Fix the problem and run `atlassian-cli doctor` to check again");
+    }
+
+    println!();
+    commands::init::offer_completions(&mut Cli::command())?;
+    Ok(Some(name))
+}
+
+/// The profile name and token checks for `doctor`. Bitbucket is only checked
+/// when it has its own token; the site token is never valid there.
+fn doctor_profile(
+    config: &Config,
+    requested: Option<&str>,
+    http: &HttpOptions,
+) -> Result<(String, Vec<commands::whoami::WhoamiTarget>)> {
+    let profile = resolve_active_profile(config, requested)?;
+    let targets = whoami_targets(&profile, http)?
+        .into_iter()
+        .filter(|t| {
+            t.product != commands::whoami::Product::Bitbucket || profile.bitbucket_token.is_some()
+        })
+        .collect();
+    Ok((profile.name.clone(), targets))
+}
+
+async fn run_multi_profile(
+    command: AtlassianCommand,
+    config: &Config,
+    names: &[String],
+    renderer: &OutputRenderer,
+    http: &HttpOptions,
+) -> Result<()> {
+    if names.is_empty() {
+        return Err(anyhow!(
+            "No profiles configured. Run `atlassian-cli auth login` first."
+        ));
+    }
+
+    let targets = |build: ClientBuilder| -> Result<Vec<ProfileTarget>> {
+        names
+            .iter()
+            .map(|name| {
+                let profile = resolve_active_profile(config, Some(name))?;
+                Ok(ProfileTarget {
+                    name: name.clone(),
+                    client: build(&profile, http)?,
+                    workspace: profile.workspace,
+                })
+            })
+            .collect()
+    };
+
+    match command {
+        AtlassianCommand::Jira(args) => {
+            commands::jira::execute_multi_profile(args, targets(build_product_client)?, renderer)
+                .await
+        }
+        AtlassianCommand::Confluence(args) => {
+            commands::confluence::execute_multi_profile(
+                args,
+                targets(build_product_client)?,
+                renderer,
+            )
+            .await
+        }
+        AtlassianCommand::Bitbucket(args) => {
+            commands::bitbucket::execute_multi_profile(
+                args,
+                targets(build_bitbucket_client)?,
+                renderer,
+            )
+            .await
+        }
+        _ => Err(anyhow!(
+            "--profiles/--all-profiles is only supported for read-only jira, confluence, and bitbucket commands"
+        )),
+    }
+}
+
+/// Expand a config-defined alias in place of the command word. The config file
+/// is located from a `--config` argument before clap has parsed anything.
+fn expand_aliases(args: Vec<String>) -> Result<Vec<String>> {
+    let config_path = args.iter().enumerate().find_map(|(i, arg)| {
+        arg.strip_prefix("--config=")
+            .map(PathBuf::from)
+            .or_else(|| (arg == "--config").then(|| args.get(i + 1).map(PathBuf::from))?)
+    });
+    let Ok(config) = Config::load(config_path.as_ref()) else {
+        return Ok(args);
+    };
+    if config.aliases.is_empty() {
+        return Ok(args);
+    }
+
+    let value_options: Vec<String> = Cli::command()
+        .get_arguments()
+        .filter(|arg| arg.get_action().takes_values())
+        .flat_map(|arg| {
+            let short = arg.get_short().map(|c| format!("-{c}"));
+            let long = arg.get_long().map(|l| format!("--{l}"));
+            short.into_iter().chain(long)
+        })
+        .collect();
+    commands::alias::expand(args, &config.aliases, &builtin_commands(), &value_options)
+}
+
+/// Fill `--output`, `--timezone` and a command's `--concurrency` from the
+/// active profile (its own values, those it extends, or `defaults:`) when they
+/// are not on the command line. Parse errors and profile problems are left for
+/// the real parse and profile resolution to report.
+fn apply_profile_settings(mut args: Vec<String>) -> Vec<String> {
+    let Ok(matches) = Cli::command().try_get_matches_from(&args) else {
+        return args;
+    };
+    let Ok(config) = Config::load(matches.get_one::<PathBuf>("config")) else {
+        return args;
+    };
+    let requested = matches.get_one::<String>("profile").map(String::as_str);
+    let Ok(Some((_, profile))) = config.resolve_profile(requested) else {
+        return args;
+    };
+
+    let unset = |matches: &clap::ArgMatches, id: &str| {
+        !matches!(matches.value_source(id), Some(ValueSource::CommandLine))
+    };
+    let mut global = Vec::new();
+    if let Some(output) = profile.output.filter(|_| unset(&matches, "output")) {
+        global.extend(["--output".to_string(), output]);
+    }
+    if let Some(timezone) = profile.timezone.filter(|_| unset(&matches, "timezone")) {
+        global.extend(["--timezone".to_string(), timezone]);
+    }
+
+    let mut command = &matches;
+    while let Some((_, sub)) = command.subcommand() {
+        command = sub;
+    }
+    if let Some(concurrency) = profile.concurrency {
+        // After `--` the flag would be read as a positional argument
+        if command.value_source("concurrency") == Some(ValueSource::DefaultValue)
+            && !args.iter().any(|arg| arg == "--")
+        {
+            args.extend(["--concurrency".to_string(), concurrency.to_string()]);
+        }
+    }
+
+    args.splice(1..1, global);
+    args
+}
+
+fn builtin_commands() -> Vec<String> {
+    let mut names: Vec<String> = Cli::command()
+        .get_subcommands()
+        .flat_map(|cmd| {
+            std::iter::once(cmd.get_name().to_string())
+                .chain(cmd.get_all_aliases().map(str::to_string))
+        })
+        .collect();
+    names.push("help".to_string());
+    names
+}
+
 fn init_tracing(debug: bool) -> Result<()> {
     let default = if debug {
         "info,atlassian-cli=debug"
@@ -138,10 +734,19 @@ fn init_tracing(debug: bool) -> Result<()> {
 }
 
 struct ActiveProfile {
+    name: String,
     base_url: String,
     email: String,
     token: String,
+    /// Where `token` came from, e.g. `env ATLASSIAN_API_TOKEN`.
+    token_source: String,
+    /// Fetches a new `token` after a 401, when it came from a credential helper.
+    token_refresh: Option<Arc<dyn TokenRefresh>>,
     bitbucket_token: Option<String>,
+    bitbucket_token_source: Option<String>,
+    opsgenie_key: Option<String>,
+    statuspage_token: Option<String>,
+    statuspage_page_id: Option<String>,
     workspace: Option<String>,
 }
 
@@ -161,9 +766,19 @@ fn handle_migration() {
     }
 }
 
+/// First non-empty environment variable among `vars`, with where it came from.
+fn env_token(vars: &[&str]) -> Option<(String, String)> {
+    vars.iter().find_map(|var| {
+        std::env::var(var)
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| (t, format!("env {var}")))
+    })
+}
+
 fn resolve_active_profile(config: &Config, requested: Option<&str>) -> Result<ActiveProfile> {
     let (name, profile) = config
-        .resolve_profile(requested)
+        .resolve_profile(requested)?
         .ok_or_else(|| anyhow!("No profile configured. Run `atlassian-cli auth login` first."))?;
 
     let base_url = profile
@@ -175,52 +790,79 @@ fn resolve_active_profile(config: &Config, requested: Option<&str>) -> Result<Ac
         .clone()
         .ok_or_else(|| anyhow!("Profile '{name}' is missing an email."))?;
 
-    // Multi-tier token lookup: env var → credentials file
-    let token = {
-        // 1. Check profile-specific env var: ATLASSIAN_CLI_TOKEN_{PROFILE}
-        let profile_env_var = format!("ATLASSIAN_CLI_TOKEN_{}", name.to_uppercase());
-        std::env::var(&profile_env_var)
-            .ok()
-            .filter(|t| !t.trim().is_empty())
-            .or_else(|| {
-                // 2. Check generic env var: ATLASSIAN_API_TOKEN
-                std::env::var("ATLASSIAN_API_TOKEN")
-                    .ok()
-                    .filter(|t| !t.trim().is_empty())
-            })
-            .or_else(|| {
-                // 3. Try credentials file
-                let secret_key = token_key(name);
-                atlassian_cli_auth::get_secret(&secret_key).ok().flatten()
-            })
-            .ok_or_else(|| {
-                anyhow!(
-                    "No token found for profile '{name}'. Set ATLASSIAN_CLI_TOKEN_{} env var or run `atlassian-cli auth login --profile {name}`",
-                    name.to_uppercase()
-                )
-            })?
+    // Multi-tier token lookup: ATLASSIAN_CLI_TOKEN_{PROFILE} → ATLASSIAN_API_TOKEN →
+    // credential helper → credential store
+    let profile_env_var = format!("ATLASSIAN_CLI_TOKEN_{}", name.to_uppercase());
+    let from_env = env_token(&[&profile_env_var, "ATLASSIAN_API_TOKEN"]);
+    let from_helper = match from_env {
+        Some(_) => None,
+        None => auth::helper_token(config, name)?,
     };
+    let token_refresh = from_helper
+        .as_ref()
+        .and_then(|_| auth::helper_refresh(config, name));
+    let (token, token_source) = from_env
+        .or(from_helper)
+        .or_else(|| {
+            auth::stored_token(config, name).map(|t| {
+                let label = auth::credential_store_label(config);
+                (t, format!("{label} entry '{}'", token_key(name)))
+            })
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "No token found for profile '{name}'. Set ATLASSIAN_CLI_TOKEN_{} env var or run `atlassian-cli auth login --profile {name}`",
+                name.to_uppercase()
+            )
+        })?;
 
     // Bitbucket-specific token lookup (in priority order):
     // 1. ATLASSIAN_CLI_BITBUCKET_TOKEN_{PROFILE}
     // 2. ATLASSIAN_BITBUCKET_TOKEN
     // 3. BITBUCKET_TOKEN
-    let bitbucket_token = {
-        let profile_env_var = format!("ATLASSIAN_CLI_BITBUCKET_TOKEN_{}", name.to_uppercase());
+    let bitbucket_env_var = format!("ATLASSIAN_CLI_BITBUCKET_TOKEN_{}", name.to_uppercase());
+    let (bitbucket_token, bitbucket_token_source) = env_token(&[
+        &bitbucket_env_var,
+        "ATLASSIAN_BITBUCKET_TOKEN",
+        "BITBUCKET_TOKEN",
+    ])
+    .unzip();
+
+    // Opsgenie API key lookup (in priority order):
+    // 1. ATLASSIAN_CLI_OPSGENIE_KEY_{PROFILE}
+    // 2. OPSGENIE_API_KEY
+    let opsgenie_key = {
+        let profile_env_var = format!("ATLASSIAN_CLI_OPSGENIE_KEY_{}", name.to_uppercase());
         std::env::var(&profile_env_var)
             .ok()
             .filter(|t| !t.trim().is_empty())
             .or_else(|| {
-                std::env::var("ATLASSIAN_BITBUCKET_TOKEN")
+                std::env::var("OPSGENIE_API_KEY")
                     .ok()
                     .filter(|t| !t.trim().is_empty())
             })
+    };
+
+    // Statuspage API key lookup (in priority order):
+    // 1. ATLASSIAN_CLI_STATUSPAGE_KEY_{PROFILE}
+    // 2. STATUSPAGE_API_KEY
+    // 3. The credential store (`auth statuspage`)
+    let statuspage_token = {
+        let profile_env_var = format!("ATLASSIAN_CLI_STATUSPAGE_KEY_{}", name.to_uppercase());
+        std::env::var(&profile_env_var)
+            .ok()
+            .filter(|t| !t.trim().is_empty())
             .or_else(|| {
-                std::env::var("BITBUCKET_TOKEN")
+                std::env::var("STATUSPAGE_API_KEY")
                     .ok()
                     .filter(|t| !t.trim().is_empty())
             })
+            .or_else(|| auth::stored_statuspage_token(config, name))
     };
+    let statuspage_page_id = std::env::var("STATUSPAGE_PAGE_ID")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .or_else(|| profile.statuspage_page_id.clone());
 
     // Resolve workspace: explicit profile config, or infer from base_url
     let workspace = profile
@@ -229,22 +871,161 @@ fn resolve_active_profile(config: &Config, requested: Option<&str>) -> Result<Ac
         .or_else(|| extract_workspace_from_url(&base_url));
 
     Ok(ActiveProfile {
+        name: name.to_string(),
         base_url,
         email,
         token,
+        token_source,
+        token_refresh,
         bitbucket_token,
+        bitbucket_token_source,
+        opsgenie_key,
+        statuspage_token,
+        statuspage_page_id,
         workspace,
     })
 }
 
-fn build_product_client(profile: &ActiveProfile) -> Result<ApiClient> {
-    Ok(ApiClient::new(&profile.base_url)?
-        .with_basic_auth(profile.email.clone(), profile.token.clone()))
+/// HTTP settings applied to every client built for this invocation. All clients
+/// record into the same stats so `--trace-http` can report reuse across profiles.
+struct HttpOptions {
+    pool: PoolConfig,
+    trace: bool,
+    stats: ConnectionStats,
+    redaction: Option<TraceRedaction>,
+    explain: Option<ExplainLog>,
+}
+
+impl HttpOptions {
+    fn client(&self, base_url: &str) -> Result<ApiClient> {
+        let mut client = ApiClient::with_pool(base_url, &self.pool, self.stats.clone())?
+            .with_http_trace(self.trace);
+        if let Some(redaction) = &self.redaction {
+            client = client.with_trace_redaction(redaction.clone());
+        }
+        if let Some(log) = &self.explain {
+            client = client.with_explain(log.clone());
+        }
+        Ok(client)
+    }
+}
+
+type ClientBuilder = fn(&ActiveProfile, &HttpOptions) -> Result<ApiClient>;
+
+fn pool_config(settings: &HttpSettings) -> PoolConfig {
+    PoolConfig {
+        max_idle_per_host: settings.max_idle_per_host,
+        idle_timeout: settings.idle_timeout_secs.map(Duration::from_secs),
+        tcp_keepalive: settings.tcp_keepalive_secs.map(Duration::from_secs),
+    }
+}
+
+fn build_product_client(profile: &ActiveProfile, http: &HttpOptions) -> Result<ApiClient> {
+    let client = http
+        .client(&profile.base_url)?
+        .with_basic_auth(profile.email.clone(), profile.token.clone())
+        .with_credential_source(&profile.token_source);
+    Ok(match &profile.token_refresh {
+        Some(refresh) => client.with_token_refresh(refresh.clone()),
+        None => client,
+    })
+}
+
+/// The products `whoami` and `doctor` authenticate against, each with its token.
+fn whoami_targets(
+    profile: &ActiveProfile,
+    http: &HttpOptions,
+) -> Result<Vec<commands::whoami::WhoamiTarget>> {
+    let target = |product, client, token_source: &str| commands::whoami::WhoamiTarget {
+        product,
+        client,
+        token_source: token_source.to_string(),
+    };
+    Ok(vec![
+        target(
+            commands::whoami::Product::Jira,
+            build_product_client(profile, http)?,
+            &profile.token_source,
+        ),
+        target(
+            commands::whoami::Product::Confluence,
+            build_product_client(profile, http)?,
+            &profile.token_source,
+        ),
+        target(
+            commands::whoami::Product::Bitbucket,
+            build_bitbucket_client(profile, http)?,
+            profile
+                .bitbucket_token_source
+                .as_deref()
+                .unwrap_or(&profile.token_source),
+        ),
+    ])
+}
+
+/// Fields masked by `--redact-pii` even when the config lists none.
+const PII_FIELDS: &[&str] = &["email", "emailAddress"];
+
+const BITBUCKET_API_URL: &str = "https://api.bitbucket.org";
+
+/// Bitbucket Cloud API root; `BITBUCKET_API_URL` points it elsewhere, e.g. at
+/// a mock server in tests.
+fn bitbucket_api_url() -> String {
+    std::env::var("BITBUCKET_API_URL")
+        .ok()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| BITBUCKET_API_URL.to_string())
 }
 
-fn build_bitbucket_client(profile: &ActiveProfile) -> Result<ApiClient> {
+fn build_bitbucket_client(profile: &ActiveProfile, http: &HttpOptions) -> Result<ApiClient> {
     // Use Bitbucket-specific token if set, otherwise fall back to general token
-    let token = profile.bitbucket_token.as_ref().unwrap_or(&profile.token);
-    Ok(ApiClient::new("https://api.bitbucket.org")?
-        .with_basic_auth(profile.email.clone(), token.clone()))
+    let client = http.client(&bitbucket_api_url())?;
+    Ok(
+        match (&profile.bitbucket_token, &profile.bitbucket_token_source) {
+            (Some(token), Some(source)) => client
+                .with_basic_auth(profile.email.clone(), token.clone())
+                .with_credential_source(source),
+            _ => {
+                let client = client
+                    .with_basic_auth(profile.email.clone(), profile.token.clone())
+                    .with_credential_source(&profile.token_source);
+                match &profile.token_refresh {
+                    Some(refresh) => client.with_token_refresh(refresh.clone()),
+                    None => client,
+                }
+            }
+        },
+    )
+}
+
+fn build_opsgenie_client(profile: &ActiveProfile, http: &HttpOptions) -> Result<ApiClient> {
+    // Opsgenie only accepts API integration keys, so there is no fallback token
+    let key = profile.opsgenie_key.as_ref().ok_or_else(|| {
+        anyhow!(
+            "No Opsgenie API key found for profile '{}'. Set ATLASSIAN_CLI_OPSGENIE_KEY_{} or OPSGENIE_API_KEY",
+            profile.name,
+            profile.name.to_uppercase()
+        )
+    })?;
+    // EU accounts are served from api.eu.opsgenie.com
+    let base_url = std::env::var("OPSGENIE_API_URL")
+        .ok()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| "https://api.opsgenie.com".to_string());
+    Ok(http
+        .client(&base_url)?
+        .with_api_key("GenieKey", key.clone()))
+}
+
+fn build_statuspage_client(profile: &ActiveProfile, http: &HttpOptions) -> Result<ApiClient> {
+    let key = profile.statuspage_token.as_ref().ok_or_else(|| {
+        anyhow!(
+            "No Statuspage API key found for profile '{}'. Run `atlassian-cli auth statuspage --profile {}` or set STATUSPAGE_API_KEY",
+            profile.name,
+            profile.name
+        )
+    })?;
+    Ok(http
+        .client("https://api.statuspage.io")?
+        .with_api_key("OAuth", key.clone()))
 }
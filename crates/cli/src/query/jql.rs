@@ -38,6 +38,23 @@ impl JqlBuilder {
         self
     }
 
+    /// Add a text search across several fields ((f1 ~ "value" OR f2 ~ "value"))
+    pub fn contains_any(mut self, fields: &[&str], value: &str) -> Self {
+        match fields {
+            [] => self,
+            [field] => self.contains(field, value),
+            _ => {
+                let escaped = Self::escape_and_quote(value);
+                let clauses: Vec<String> = fields
+                    .iter()
+                    .map(|field| format!("{} ~ {}", field, escaped))
+                    .collect();
+                self.conditions.push(format!("({})", clauses.join(" OR ")));
+                self
+            }
+        }
+    }
+
     /// Build the final JQL query string
     pub fn finish(self) -> String {
         if self.conditions.is_empty() {
@@ -147,6 +164,23 @@ mod tests {
         assert_eq!(query, "summary ~ \"bug fix\"");
     }
 
+    #[test]
+    fn test_contains_any() {
+        let query = JqlBuilder::new()
+            .eq("project", "DEV")
+            .contains_any(&["summary", "description", "comment"], "timeout")
+            .finish();
+        assert_eq!(
+            query,
+            "project = \"DEV\" AND (summary ~ \"timeout\" OR description ~ \"timeout\" OR comment ~ \"timeout\")"
+        );
+
+        let single = JqlBuilder::new()
+            .contains_any(&["summary"], "timeout")
+            .finish();
+        assert_eq!(single, "summary ~ \"timeout\"");
+    }
+
     #[test]
     fn test_quote_escape() {
         let query = JqlBuilder::new()
@@ -1,5 +1,8 @@
+mod common;
+
 use atlassian_cli_api::ApiClient;
-use wiremock::matchers::{method, path, query_param, query_param_contains};
+use common::{stderr, stdout, Cli};
+use wiremock::matchers::{body_partial_json, method, path, query_param, query_param_contains};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -607,3 +610,338 @@ async fn test_pipeline_list_pagination() {
     let result = response.unwrap();
     assert!(result["next"].is_string()); // Verify pagination link exists
 }
+
+#[tokio::test]
+async fn test_bitbucket_repo_create_if_not_exists_when_present() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/2.0/repositories/myworkspace/myrepo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "slug": "myrepo",
+            "name": "My Repo",
+            "full_name": "myworkspace/myrepo",
+            "is_private": true,
+            "mainbranch": {"name": "main"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/2.0/repositories/myworkspace/myrepo"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "--output",
+            "json",
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "repo",
+            "create",
+            "myrepo",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("✅ Repository myworkspace/myrepo already exists"));
+    assert!(stdout.contains("\"main_branch\": \"main\""));
+}
+
+#[tokio::test]
+async fn test_bitbucket_repo_create_if_not_exists_when_missing() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/2.0/repositories/myworkspace/myrepo"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "type": "error",
+            "error": {"message": "Repository myworkspace/myrepo not found"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/2.0/repositories/myworkspace/myrepo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "slug": "myrepo",
+            "full_name": "myworkspace/myrepo",
+            "is_private": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "repo",
+            "create",
+            "myrepo",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("myworkspace/myrepo"));
+}
+
+#[tokio::test]
+async fn test_bitbucket_bulk_clone_fails_when_a_clone_fails() {
+    let mock_server = MockServer::start().await;
+    let sources = tempfile::TempDir::new().unwrap();
+    let good = sources.path().join("good.git");
+    let init = std::process::Command::new("git")
+        .args(["init", "--bare", "--quiet"])
+        .arg(&good)
+        .status()
+        .unwrap();
+    assert!(init.success());
+    let missing = sources.path().join("missing.git");
+
+    Mock::given(method("GET"))
+        .and(path("/2.0/repositories/myworkspace"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [
+                {
+                    "slug": "good",
+                    "links": {"clone": [{"name": "https", "href": good.display().to_string()}]}
+                },
+                {
+                    "slug": "missing",
+                    "links": {"clone": [{"name": "https", "href": missing.display().to_string()}]}
+                }
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let checkouts = tempfile::TempDir::new().unwrap();
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "bulk",
+            "clone",
+            "--dir",
+            checkouts.path().to_str().unwrap(),
+        ])
+        .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("1 of 2 repositories failed to clone or update"),
+        "{}",
+        stderr(&output)
+    );
+    assert!(checkouts.path().join("good").join(".git").exists());
+}
+
+#[tokio::test]
+async fn test_bitbucket_pr_approve_accepts_pull_request_url() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(
+            "/2.0/repositories/myworkspace/myrepo/pullrequests/42/approve",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "approved": true,
+            "user": {"display_name": "Test User"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&[
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "pr",
+            "approve",
+            "myrepo",
+            "https://bitbucket.org/myworkspace/myrepo/pull-requests/42/diff",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let rejected = cli
+        .run(&[
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "pr",
+            "approve",
+            "myrepo",
+            "https://bitbucket.org/myworkspace/myrepo/src/main",
+        ])
+        .await;
+    assert!(!rejected.status.success());
+    assert!(stderr(&rejected).contains("no pull request ID found in URL"));
+}
+
+#[tokio::test]
+async fn test_bitbucket_pr_merge_when_ready_follows_task_pages() {
+    let mock_server = MockServer::start().await;
+    let base = "/2.0/repositories/myworkspace/myrepo/pullrequests/7";
+
+    Mock::given(method("GET"))
+        .and(path(base))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "state": "OPEN",
+            "participants": [{"approved": true}]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{base}/tasks")))
+        .and(query_param("pagelen", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"state": "RESOLVED"}],
+            "next": format!("https://api.bitbucket.org{base}/tasks?page=2")
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{base}/tasks")))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"state": "RESOLVED"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{base}/statuses")))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"values": [{"state": "SUCCESSFUL"}]})),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(format!("{base}/merge")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 7,
+            "title": "Ship it",
+            "state": "MERGED",
+            "author": {"display_name": "Test User"},
+            "source": {"branch": {"name": "feature"}},
+            "destination": {"branch": {"name": "main"}}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let merge = |interval: &'static str| {
+        [
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "pr",
+            "merge",
+            "myrepo",
+            "7",
+            "--when-ready",
+            "--interval",
+            interval,
+        ]
+    };
+
+    let rejected = cli.run(&merge("0")).await;
+    assert!(!rejected.status.success());
+    assert!(stderr(&rejected).contains("--interval"));
+
+    let output = cli.run(&merge("1")).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("All merge checks passed"));
+}
+
+#[tokio::test]
+async fn test_bitbucket_repo_archive_tombstone_keeps_description() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/2.0/repositories/myworkspace/payments"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "slug": "payments",
+            "name": "payments",
+            "description": "Payments API",
+            "mainbranch": {"name": "main"}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/2.0/repositories/myworkspace/payments/branch-restrictions",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"values": []})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/2.0/repositories/myworkspace/payments/src"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/2.0/repositories/myworkspace/payments/branch-restrictions",
+        ))
+        .and(body_partial_json(
+            serde_json::json!({"kind": "push", "pattern": "*"}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({"id": 7})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    // The tombstone message goes in the README; the description keeps its text
+    Mock::given(method("PUT"))
+        .and(path("/2.0/repositories/myworkspace/payments"))
+        .and(body_partial_json(serde_json::json!({
+            "description": "[ARCHIVED] Payments API",
+            "has_issues": false
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "slug": "payments",
+            "name": "payments",
+            "description": "[ARCHIVED] Payments API"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "bitbucket",
+            "--workspace",
+            "myworkspace",
+            "repo",
+            "archive",
+            "payments",
+            "--tombstone",
+            "Moved to mono-repo",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✓ Repository myworkspace/payments archived"));
+
+    let readme = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.url.path().ends_with("/src"))
+        .unwrap();
+    assert!(String::from_utf8_lossy(&readme.body).contains("Moved to mono-repo"));
+}
@@ -0,0 +1,72 @@
+// Runs the built binary against a mock server, with a throwaway home
+// directory and a config whose only profile points at the server. Bitbucket
+// requests go to the same server
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use tempfile::TempDir;
+
+pub struct Cli {
+    home: TempDir,
+    config: PathBuf,
+    base_url: String,
+}
+
+impl Cli {
+    /// A CLI whose `test` profile talks to `base_url`.
+    pub fn new(base_url: &str) -> Self {
+        let home = TempDir::new().expect("temp home");
+        let config = home.path().join("config.yaml");
+        std::fs::write(
+            &config,
+            format!(
+                "default_profile: test\nprofiles:\n  test:\n    base_url: {base_url}\n    email: test@example.com\n"
+            ),
+        )
+        .expect("write config");
+        Self {
+            home,
+            config,
+            base_url: base_url.to_string(),
+        }
+    }
+
+    /// The throwaway home directory, for seeding and checking local state.
+    pub fn home(&self) -> &Path {
+        self.home.path()
+    }
+
+    /// Run `atlassian-cli --config <config> ARGS...` and wait for it.
+    pub async fn run(&self, args: &[&str]) -> Output {
+        self.run_with_env(args, &[]).await
+    }
+
+    /// Like `run`, with extra environment variables set.
+    pub async fn run_with_env(&self, args: &[&str], env: &[(&str, &str)]) -> Output {
+        tokio::process::Command::new(env!("CARGO_BIN_EXE_atlassian-cli"))
+            .arg("--config")
+            .arg(&self.config)
+            .args(args)
+            .env("HOME", self.home.path())
+            .env("BITBUCKET_API_URL", &self.base_url)
+            .env("ATLASSIAN_API_TOKEN", "fake-token")
+            .env("NO_COLOR", "1")
+            .env_remove("ATLASSIAN_CLI_TOKEN_TEST")
+            .env_remove("RUST_BACKTRACE")
+            .envs(env.iter().copied())
+            .output()
+            .await
+            .expect("run atlassian-cli")
+    }
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -1,5 +1,8 @@
+mod common;
+
 use atlassian_cli_api::ApiClient;
-use wiremock::matchers::{method, path, query_param};
+use common::{stderr, stdout, Cli};
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // ============================================================================
@@ -577,3 +580,489 @@ async fn test_bulk_add_labels() {
 
     assert!(response.is_ok());
 }
+
+#[tokio::test]
+async fn test_confluence_space_create_if_not_exists_when_present() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/spaces"))
+        .and(query_param("keys", "DOCS"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{"id": "98304", "key": "DOCS", "name": "Documentation"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/wiki/api/v2/spaces"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "confluence",
+            "space",
+            "create",
+            "--key",
+            "DOCS",
+            "--name",
+            "Documentation",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("✅ Space DOCS already exists"));
+    assert!(stdout.contains("\"id\": \"98304\""));
+}
+
+#[tokio::test]
+async fn test_confluence_space_create_if_not_exists_when_missing() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/spaces"))
+        .and(query_param("keys", "DOCS"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": []
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/wiki/api/v2/spaces"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "98304",
+            "key": "DOCS",
+            "name": "Documentation"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "confluence",
+            "space",
+            "create",
+            "--key",
+            "DOCS",
+            "--name",
+            "Documentation",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Created space: Documentation (DOCS)"));
+}
+
+#[tokio::test]
+async fn test_confluence_page_get_redacts_pii() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/pages/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "123",
+            "title": "Runbook",
+            "version": {"number": 3, "by": {"email": "ana@example.com"}}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let plain = cli.run(&["confluence", "page", "get", "123"]).await;
+    assert!(plain.status.success(), "{}", stderr(&plain));
+    assert!(stdout(&plain).contains("ana@example.com"));
+
+    let redacted = cli
+        .run(&["--redact-pii", "confluence", "page", "get", "123"])
+        .await;
+    assert!(redacted.status.success(), "{}", stderr(&redacted));
+    let stdout = stdout(&redacted);
+    assert!(!stdout.contains("ana@example.com"));
+    assert!(stdout.contains("\"email\": \"[redacted]\""));
+    assert!(stdout.contains("\"title\": \"Runbook\""));
+}
+
+#[tokio::test]
+async fn test_confluence_add_comment_resolves_mentions_by_email() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .and(query_param("query", "ana@example.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"accountId": "557058:ana", "emailAddress": "ana@example.com"},
+            {"accountId": "557058:anabel", "emailAddress": "ana@example.com.au"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/wiki/api/v2/footer-comments"))
+        .and(body_partial_json(serde_json::json!({
+            "pageId": "123",
+            "body": {
+                "value": "<p>Ping <ac:link><ri:user ri:account-id=\"557058:ana\" /></ac:link>, see <b>this</b></p>"
+            }
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "900"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "confluence",
+            "page",
+            "add-comment",
+            "123",
+            "Ping @ana@example.com, see <b>this</b>",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Added comment to page 123 (ID: 900)"));
+}
+
+#[tokio::test]
+async fn test_confluence_scheduler_run_explain_keeps_due_entries() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/pages/123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "123",
+            "status": "draft",
+            "title": "Launch",
+            "body": {"storage": {"value": "<p>Soon</p>"}},
+            "version": {"number": 1}
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let state = cli.home().join(".atlassian-cli/schedule/test.json");
+    std::fs::create_dir_all(state.parent().unwrap()).unwrap();
+    let recorded = serde_json::to_string_pretty(&serde_json::json!({
+        "entries": [{
+            "id": "123",
+            "kind": "page",
+            "title": "Launch",
+            "publish_at": "2024-01-01T00:00:00Z"
+        }]
+    }))
+    .unwrap();
+    std::fs::write(&state, &recorded).unwrap();
+
+    let output = cli
+        .run(&["--explain", "confluence", "scheduler", "run", "--once"])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(std::fs::read_to_string(&state).unwrap(), recorded);
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|r| r.method.as_str() == "GET"));
+}
+
+#[tokio::test]
+async fn test_confluence_page_create_links_markdown_mentions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .and(query_param("query", "ana@example.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"accountId": "557058:ana", "emailAddress": "ana@example.com"}
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/wiki/api/v2/pages"))
+        .and(body_partial_json(serde_json::json!({
+            "body": {
+                "value": "<p><ac:link><ri:user ri:account-id=\"557058:ana\" /></ac:link> owns <strong>this</strong></p>\
+                    <ac:structured-macro ac:name=\"code\"><ac:plain-text-body><![CDATA[@bo@example.com]]></ac:plain-text-body></ac:structured-macro>"
+            }
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": "900", "title": "Runbook"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let body = cli.home().join("runbook.md");
+    std::fs::write(
+        &body,
+        "@ana@example.com owns **this**\n\n```\n@bo@example.com\n```\n",
+    )
+    .unwrap();
+    let output = cli
+        .run(&[
+            "confluence",
+            "page",
+            "create",
+            "--space",
+            "123",
+            "--title",
+            "Runbook",
+            "--body",
+            body.to_str().unwrap(),
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Created page: Runbook (ID: 900)"));
+}
+
+#[tokio::test]
+async fn test_confluence_scheduler_run_publishes_due_drafts() {
+    let mock_server = MockServer::start().await;
+
+    for (id, status, version) in [("123", "current", 3), ("456", "draft", 1)] {
+        Mock::given(method("GET"))
+            .and(path(format!("/wiki/api/v2/pages/{id}")))
+            .and(query_param("get-draft", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": id,
+                "status": "draft",
+                "title": format!("Draft {id}"),
+                "body": {"storage": {"value": "<p>New text</p>"}}
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/wiki/api/v2/pages/{id}")))
+            .and(query_param("status", "current"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": id,
+                "status": status,
+                "version": {"number": version}
+            })))
+            .mount(&mock_server)
+            .await;
+    }
+    // A published page's draft becomes its next version
+    Mock::given(method("PUT"))
+        .and(path("/wiki/api/v2/pages/123"))
+        .and(body_partial_json(serde_json::json!({
+            "status": "current",
+            "title": "Draft 123",
+            "body": {"representation": "storage", "value": "<p>New text</p>"},
+            "version": {"number": 4}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "123"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/wiki/api/v2/pages/456"))
+        .and(body_partial_json(
+            serde_json::json!({"version": {"number": 1}}),
+        ))
+        .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+            "errors": [{"title": "Version conflict"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let state = cli.home().join(".atlassian-cli/schedule/test.json");
+    std::fs::create_dir_all(state.parent().unwrap()).unwrap();
+    let entry = |id: &str, at: &str| serde_json::json!({"id": id, "kind": "page", "title": format!("Draft {id}"), "publish_at": at});
+    std::fs::write(
+        &state,
+        serde_json::json!({"entries": [
+            entry("123", "2024-01-01T00:00:00Z"),
+            entry("456", "2024-01-01T00:00:00Z"),
+            entry("789", "2999-01-01T00:00:00Z")
+        ]})
+        .to_string(),
+    )
+    .unwrap();
+
+    let output = cli.run(&["confluence", "scheduler", "run", "--once"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Published page 'Draft 123' (123)"));
+    assert!(stderr(&output).contains("Failed to publish page 456"));
+
+    let schedule: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&state).unwrap()).unwrap();
+    let entries = schedule["entries"].as_array().unwrap();
+    let ids: Vec<&str> = entries.iter().map(|e| e["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["456", "789"]);
+    assert!(entries[0]["last_error"].is_string());
+    assert!(entries[1].get("last_error").is_none());
+
+    let rejected = cli
+        .run(&["confluence", "scheduler", "run", "--interval", "0"])
+        .await;
+    assert!(!rejected.status.success());
+    assert!(stderr(&rejected).contains("--interval"));
+}
+
+#[tokio::test]
+async fn test_confluence_attachment_upload_replace_existing() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/rest/api/content/123/child/attachment"))
+        .and(query_param("filename", "diagram.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{"id": "att77", "title": "diagram.png"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/wiki/rest/api/content/123/child/attachment/att77/data",
+        ))
+        .and(header("X-Atlassian-Token", "no-check"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "att77"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    // A second attachment with the same name must not be created
+    Mock::given(method("POST"))
+        .and(path("/wiki/rest/api/content/123/child/attachment"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let file = cli.home().join("diagram.png");
+    std::fs::write(&file, b"png bytes").unwrap();
+    let output = cli
+        .run(&[
+            "confluence",
+            "attachment",
+            "upload",
+            "123",
+            "--file",
+            file.to_str().unwrap(),
+            "--replace-existing",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output)
+        .contains("✅ Uploaded new version of attachment 'diagram.png' (att77) on page 123"));
+}
+
+#[tokio::test]
+async fn test_confluence_attachment_versions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/attachments/att77/versions"))
+        .and(query_param("limit", "250"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{
+                "number": 2,
+                "createdAt": "2025-03-02T10:00:00.000Z",
+                "authorId": "557058:ana",
+                "message": "New layout",
+                "minorEdit": true
+            }],
+            "_links": {"next": "/wiki/api/v2/attachments/att77/versions?cursor=page-2"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/attachments/att77/versions"))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{
+                "number": 1,
+                "createdAt": "2025-03-01T10:00:00.000Z",
+                "authorId": "557058:bo"
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "--output",
+            "json",
+            "confluence",
+            "attachment",
+            "versions",
+            "att77",
+            "--all",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let rows: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(rows[0]["version"], 2);
+    assert_eq!(rows[0]["message"], "New layout");
+    assert_eq!(rows[1]["version"], 1);
+    assert_eq!(rows[1]["author_id"], "557058:bo");
+    assert_eq!(rows.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_confluence_page_lint_reads_every_attachment_page() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/wiki/rest/api/content/42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "42",
+            "title": "Runbook",
+            "space": {"key": "ENG"},
+            "body": {"storage": {
+                "value": "<p><ac:link><ri:attachment ri:filename=\"diagram.png\" /></ac:link></p>"
+            }}
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/pages/42/attachments"))
+        .and(query_param("limit", "250"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{"title": "dump.bin", "fileSize": 30 * 1024 * 1024}],
+            "_links": {"next": "/wiki/api/v2/pages/42/attachments?cursor=page-2"}
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/wiki/api/v2/pages/42/attachments"))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [{"title": "diagram.png", "fileSize": 2048}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "--output",
+            "json",
+            "confluence",
+            "page",
+            "lint",
+            "42",
+            "--rules",
+            "broken-links,large-attachments",
+        ])
+        .await;
+    assert!(!output.status.success());
+    let findings: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    // diagram.png is on the second page of attachments, so its link is not broken
+    assert_eq!(findings.as_array().unwrap().len(), 1, "{findings}");
+    assert_eq!(findings[0]["rule"], "large-attachments");
+    assert!(stderr(&output).contains("1 lint finding(s) on 1 of 1 page(s)"));
+}
@@ -1,5 +1,9 @@
+mod common;
+
+use atlassian_cli_api::explain::ExplainLog;
 use atlassian_cli_api::ApiClient;
-use wiremock::matchers::{method, path, query_param};
+use common::{stderr, stdout, Cli};
+use wiremock::matchers::{body_json, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -319,6 +323,98 @@ async fn test_jira_audit_list() {
     assert_eq!(result["records"].as_array().unwrap().len(), 2);
 }
 
+#[tokio::test]
+async fn test_jira_export_streams_issues() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "startAt": 0,
+            "total": 2,
+            "issues": [
+                {"key": "TEST-1", "fields": {"summary": "First"}},
+                {"key": "TEST-2", "fields": {"summary": "Second"}}
+            ]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::new(mock_server.uri())
+        .unwrap()
+        .with_basic_auth("test@example.com", "fake-token");
+
+    let stream = client
+        .request_array_stream::<serde_json::Value, _>(
+            reqwest::Method::POST,
+            "/rest/api/3/search",
+            Some(&serde_json::json!({"jql": "project = TEST"})),
+            Some("issues"),
+        )
+        .await
+        .unwrap();
+
+    let issues: Vec<_> = stream.collect().await;
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[1].as_ref().unwrap()["key"], "TEST-2");
+}
+
+#[tokio::test]
+async fn test_cloned_clients_reuse_connections() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/myself"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"accountId": "abc"})),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::new(mock_server.uri())
+        .unwrap()
+        .with_basic_auth("test@example.com", "fake-token");
+
+    for _ in 0..3 {
+        let clone = client.clone();
+        let _: serde_json::Value = clone.get("/rest/api/3/myself").await.unwrap();
+    }
+
+    let stats = client.connection_stats();
+    assert_eq!(stats.requests(), 3);
+    assert_eq!(stats.connections_opened(), 1);
+    assert_eq!(stats.connections_reused(), 2);
+}
+
+#[tokio::test]
+async fn test_explain_writes_return_placeholders() {
+    let mock_server = MockServer::start().await;
+
+    let client = ApiClient::new(mock_server.uri())
+        .unwrap()
+        .with_basic_auth("test@example.com", "fake-token")
+        .with_explain(ExplainLog::default());
+
+    let created: serde_json::Value = client
+        .post("/rest/api/3/version", &serde_json::json!({ "name": "1.0" }))
+        .await
+        .unwrap();
+    assert_eq!(created["id"], "explain");
+    assert_eq!(created["name"], "1.0");
+
+    let _: () = client
+        .put(
+            "/rest/api/3/issue/DEV-1",
+            &serde_json::json!({ "fields": {} }),
+        )
+        .await
+        .unwrap();
+
+    assert!(mock_server.received_requests().await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn test_jira_list_webhooks() {
     let mock_server = MockServer::start().await;
@@ -369,3 +465,1123 @@ async fn test_jira_error_handling() {
 
     assert!(response.is_err());
 }
+
+#[tokio::test]
+async fn test_jira_project_archive_and_restore() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/project/OLD/archive"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/project/OLD/restore"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let archived = cli.run(&["jira", "project", "archive", "OLD"]).await;
+    assert!(archived.status.success(), "{}", stderr(&archived));
+    assert!(stdout(&archived).contains("Archived project: OLD"));
+
+    let restored = cli.run(&["jira", "project", "restore", "OLD"]).await;
+    assert!(restored.status.success(), "{}", stderr(&restored));
+    assert!(stdout(&restored).contains("Restored project: OLD"));
+}
+
+#[tokio::test]
+async fn test_jira_fields_list_team_managed_pages_createmeta() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/TEAM"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "key": "TEAM",
+            "style": "next-gen"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/createmeta/TEAM/issuetypes"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": "1", "name": "Task"}],
+            "isLast": false
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/createmeta/TEAM/issuetypes"))
+        .and(query_param("startAt", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": "2", "name": "Bug"}],
+            "isLast": true
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/createmeta/TEAM/issuetypes/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"fieldId": "summary", "name": "Summary"}],
+            "isLast": true
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/createmeta/TEAM/issuetypes/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [
+                {"fieldId": "summary", "name": "Summary"},
+                {"fieldId": "customfield_10042", "name": "Severity"}
+            ],
+            "isLast": true
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&[
+            "--output",
+            "json",
+            "jira",
+            "fields",
+            "list",
+            "--project",
+            "TEAM",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let rows: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([
+            {"id": "summary", "name": "Summary", "custom": false, "issue_types": "Task, Bug"},
+            {"id": "customfield_10042", "name": "Severity", "custom": true, "issue_types": "Bug"}
+        ])
+    );
+}
+
+fn project_categories() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!([
+        {"id": "10000", "name": "Internal", "description": "Internal tools"},
+        {"id": "10001", "name": "Customer"}
+    ]))
+}
+
+#[tokio::test]
+async fn test_jira_project_category_set_by_name() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/projectCategory"))
+        .respond_with(project_categories())
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/project/DEV"))
+        .and(body_json(serde_json::json!({"categoryId": 10001})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"key": "DEV"})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&["jira", "project", "category", "set", "DEV", "customer"])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Moved project DEV to category: Customer"));
+
+    let listed = cli
+        .run(&["--output", "json", "jira", "project", "category", "list"])
+        .await;
+    assert!(listed.status.success(), "{}", stderr(&listed));
+    let rows: serde_json::Value = serde_json::from_str(&stdout(&listed)).unwrap();
+    assert_eq!(rows[0]["name"], "Internal");
+    assert_eq!(rows[1]["description"], "");
+}
+
+#[tokio::test]
+async fn test_jira_project_category_set_rejects_unknown_and_non_numeric() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/projectCategory"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "10000", "name": "Internal"},
+            {"id": "legacy", "name": "Legacy"}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let unknown = cli
+        .run(&["jira", "project", "category", "set", "DEV", "Nope"])
+        .await;
+    assert!(!unknown.status.success());
+    assert!(stderr(&unknown).contains("Available: Internal, Legacy"));
+
+    let non_numeric = cli
+        .run(&["jira", "project", "category", "set", "DEV", "Legacy"])
+        .await;
+    assert!(!non_numeric.status.success());
+    assert!(stderr(&non_numeric).contains("non-numeric ID 'legacy'"));
+}
+
+fn project_details() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "id": "10000",
+        "key": "DEV",
+        "name": "Development",
+        "projectTypeKey": "software",
+        "style": "classic",
+        "lead": {"displayName": "Jane Doe"}
+    }))
+}
+
+#[tokio::test]
+async fn test_jira_project_create_if_not_exists_when_present() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV"))
+        .respond_with(project_details())
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/project"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "--output",
+            "json",
+            "jira",
+            "project",
+            "create",
+            "--key",
+            "DEV",
+            "--name",
+            "Development",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    assert!(stdout.contains("✅ Project DEV already exists"));
+    assert!(stdout.contains("\"lead\": \"Jane Doe\""));
+}
+
+#[tokio::test]
+async fn test_jira_project_create_if_not_exists_when_missing() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "errorMessages": ["No project could be found with key 'DEV'."]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/project"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(serde_json::json!({"id": "10000", "key": "DEV"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "project",
+            "create",
+            "--key",
+            "DEV",
+            "--name",
+            "Development",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Created project: DEV"));
+}
+
+#[tokio::test]
+async fn test_jira_version_create_if_not_exists() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV"))
+        .respond_with(project_details())
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV/versions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"id": "10100", "name": "v1.0", "released": true, "releaseDate": "2025-01-31"}
+        ])))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/version/10100"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/version"))
+        .and(body_json(serde_json::json!({
+            "name": "v2.0",
+            "project": "DEV",
+            "released": false,
+            "archived": false
+        })))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(serde_json::json!({"id": "10101", "name": "v2.0"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let existing = cli
+        .run(&[
+            "--output",
+            "json",
+            "jira",
+            "versions",
+            "create",
+            "--project",
+            "DEV",
+            "--name",
+            "V1.0",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(existing.status.success(), "{}", stderr(&existing));
+    let stdout_existing = stdout(&existing);
+    assert!(stdout_existing.contains("✅ Version v1.0 already exists in DEV"));
+    assert!(stdout_existing.contains("\"release_date\": \"2025-01-31\""));
+
+    let created = cli
+        .run(&[
+            "jira",
+            "versions",
+            "create",
+            "--project",
+            "DEV",
+            "--name",
+            "v2.0",
+            "--if-not-exists",
+        ])
+        .await;
+    assert!(created.status.success(), "{}", stderr(&created));
+    assert!(stdout(&created).contains("✅ Created version: v2.0 (ID: 10101)"));
+}
+
+fn watcher_search() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "issues": [{"key": "DEV-1"}, {"key": "DEV-2"}]
+    }))
+}
+
+#[tokio::test]
+async fn test_jira_bulk_watchers_add() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .respond_with(watcher_search())
+        .mount(&mock_server)
+        .await;
+    for key in ["DEV-1", "DEV-2"] {
+        Mock::given(method("POST"))
+            .and(path(format!("/rest/api/3/issue/{key}/watchers")))
+            .and(body_json(serde_json::json!("acc-1")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&[
+            "jira",
+            "bulk",
+            "watchers",
+            "add",
+            "--jql",
+            "project = DEV",
+            "--users",
+            "acc-1",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Bulk watcher update completed"));
+}
+
+#[tokio::test]
+async fn test_jira_bulk_watchers_partial_failure() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .respond_with(watcher_search())
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/rest/api/3/issue/DEV-1/watchers"))
+        .and(query_param("accountId", "acc-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/rest/api/3/issue/DEV-2/watchers"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+            "errorMessages": ["You do not have permission to manage the watcher list."]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&[
+            "jira",
+            "bulk",
+            "watchers",
+            "remove",
+            "--jql",
+            "project = DEV",
+            "--users",
+            "acc-1",
+        ])
+        .await;
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("1 of 2 watcher updates failed"),
+        "{}",
+        stderr(&output)
+    );
+
+    // Only the failed update is listed
+    let table = stdout(&output);
+    assert!(table.contains("│ DEV-2 "), "{table}");
+    assert!(!table.contains("│ DEV-1 "), "{table}");
+}
+
+#[tokio::test]
+async fn test_jira_watchers_list_accepts_issue_url() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/issue/DEV-7/watchers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "watchers": [{"accountId": "acc-1", "displayName": "Alice"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "watchers",
+            "list",
+            "https://x.atlassian.net/browse/DEV-7",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Alice"));
+}
+
+#[tokio::test]
+async fn test_jira_bulk_project_lead_resolves_and_filters_leads() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .and(query_param("query", "alice@example.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"accountId": "acc-alice"}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [
+                {"key": "DEV", "lead": {"accountId": "acc-alice"}},
+                {"key": "OPS", "lead": {"accountId": "acc-bob"}}
+            ],
+            "isLast": true
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/project/DEV"))
+        .and(body_json(serde_json::json!({"leadAccountId": "acc-carol"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/project/OPS"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "bulk",
+            "project-lead",
+            "--from-user",
+            "alice@example.com",
+            "--to-user",
+            "acc-carol",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Found 1 projects led by alice@example.com"));
+}
+
+#[tokio::test]
+async fn test_jira_plan_list_follows_next_page_cursor() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/plans/plan"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": 1, "name": "Roadmap"}],
+            "cursor": "",
+            "nextPageCursor": "page-2",
+            "isLast": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/plans/plan"))
+        .and(query_param("cursor", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": 2, "name": "Platform"}],
+            "cursor": "page-2",
+            "isLast": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&["--output", "json", "jira", "plan", "list"])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let rows: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let names: Vec<&str> = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Roadmap", "Platform"]);
+}
+
+#[tokio::test]
+async fn test_jira_plan_export_collects_every_team_page() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/plans/plan/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 7,
+            "name": "Roadmap"
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/plans/plan/7/team"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": "t1", "name": "Core"}],
+            "nextPageCursor": "next",
+            "isLast": false
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/plans/plan/7/team"))
+        .and(query_param("cursor", "next"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": "t2", "name": "Edge"}],
+            "isLast": true
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("plan.json");
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "plan",
+            "export",
+            "7",
+            "--output",
+            file.to_str().unwrap(),
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Exported plan 7 (2 teams)"));
+
+    let snapshot: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(snapshot["plan"]["name"], "Roadmap");
+    assert_eq!(snapshot["teams"][1]["name"], "Edge");
+}
+
+#[tokio::test]
+async fn test_jira_recurring_run_explain_leaves_state_untouched() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"issues": []})))
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let template = cli.home().join("standup.yaml");
+    std::fs::write(&template, "project: DEV\nsummary: Standup {date}\n").unwrap();
+    let state = cli.home().join(".atlassian-cli/recurring/test.json");
+    std::fs::create_dir_all(state.parent().unwrap()).unwrap();
+    let recorded = serde_json::to_string_pretty(&serde_json::json!({
+        "entries": [{
+            "name": "standup",
+            "template": template,
+            "cron": "* * * * *",
+            "added_at": "2024-01-01T00:00:00Z"
+        }]
+    }))
+    .unwrap();
+    std::fs::write(&state, &recorded).unwrap();
+
+    let output = cli.run(&["--explain", "jira", "recurring", "run"]).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("POST   /rest/api/3/issue  (not sent)"));
+    assert_eq!(std::fs::read_to_string(&state).unwrap(), recorded);
+    let requests = mock_server.received_requests().await.unwrap();
+    assert!(requests
+        .iter()
+        .all(|r| !r.url.path().starts_with("/rest/api/3/issue")));
+}
+
+#[tokio::test]
+async fn test_jira_create_editor_keeps_draft_until_created() {
+    let mock_server = MockServer::start().await;
+    let cli = Cli::new(&mock_server.uri());
+    let tmp = cli.home().join("tmp");
+    std::fs::create_dir(&tmp).unwrap();
+    let env = [("EDITOR", "true"), ("TMPDIR", tmp.to_str().unwrap())];
+    let args = [
+        "jira",
+        "create",
+        "--editor",
+        "--project",
+        "DEV",
+        "--issue-type",
+        "Task",
+        "--summary",
+        "Drafted",
+    ];
+    let drafts = || std::fs::read_dir(&tmp).unwrap().count();
+
+    let failing = Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "errorMessages": ["Issue type is required"]
+        })))
+        .mount_as_scoped(&mock_server)
+        .await;
+    let output = cli.run_with_env(&args, &env).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("draft kept at"));
+    let kept = std::fs::read_dir(&tmp).unwrap().next().unwrap().unwrap();
+    assert!(std::fs::read_to_string(kept.path())
+        .unwrap()
+        .contains("summary: \"Drafted\""));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = kept.metadata().unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    drop(failing);
+    std::fs::remove_file(kept.path()).unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/issue"))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(serde_json::json!({"id": "10001", "key": "DEV-1"})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    let output = cli.run_with_env(&args, &env).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(drafts(), 0);
+}
+
+#[tokio::test]
+async fn test_jira_bulk_reassign_counts_every_search_page() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .and(query_param_is_missing("nextPageToken"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "issues": [{"key": "DEV-1"}, {"key": "OPS-1"}],
+            "nextPageToken": "page-2"
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .and(query_param("nextPageToken", "page-2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "issues": [{"key": "DEV-2"}]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "--output",
+            "json",
+            "jira",
+            "bulk",
+            "reassign",
+            "--from-user",
+            "acc-old",
+            "--to-user",
+            "acc-new",
+            "--dry-run",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    let rows: serde_json::Value = serde_json::from_str(&out[out.find('[').unwrap()..]).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([
+            {"project": "DEV", "assignee": 2, "reporter": 0},
+            {"project": "OPS", "assignee": 1, "reporter": 0}
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_jira_archive_and_restore_issues() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/archive"))
+        .and(body_json(
+            serde_json::json!({"issueIdsOrKeys": ["DEV-1", "DEV-2"]}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "numberOfIssuesUpdated": 1,
+            "errors": {
+                "issueIsSubtask": {
+                    "count": 1,
+                    "issueIdsOrKeys": ["DEV-2"],
+                    "message": "Issue is subtask."
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/unarchive"))
+        .and(body_json(serde_json::json!({"issueIdsOrKeys": ["DEV-1"]})))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"numberOfIssuesUpdated": 1})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let archived = cli.run(&["jira", "archive", "DEV-1", "DEV-2"]).await;
+    assert!(archived.status.success(), "{}", stderr(&archived));
+    assert!(stdout(&archived).contains("✅ Archived 1 of 2 issue(s)"));
+
+    let restored = cli.run(&["jira", "restore", "DEV-1"]).await;
+    assert!(restored.status.success(), "{}", stderr(&restored));
+    assert!(stdout(&restored).contains("✅ Restored 1 of 1 issue(s)"));
+}
+
+#[tokio::test]
+async fn test_jira_bulk_archive() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "issues": [{"key": "DEV-1"}, {"key": "DEV-2"}]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/archive"))
+        .and(body_json(
+            serde_json::json!({"issueIdsOrKeys": ["DEV-1", "DEV-2"]}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"numberOfIssuesUpdated": 2})),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let args = ["jira", "bulk", "archive", "--jql", "project = DEV"];
+    let planned = cli.run(&[&args[..], &["--dry-run"]].concat()).await;
+    assert!(planned.status.success(), "{}", stderr(&planned));
+    assert!(stdout(&planned).contains("DEV-2"));
+
+    let output = cli.run(&args).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Bulk archive completed: 2 of 2 issues archived"));
+}
+
+#[tokio::test]
+async fn test_jira_archive_on_unsupported_site() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/archive"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+            "errorMessages": ["This feature is only available on Jira Premium and Enterprise."]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&["jira", "archive", "DEV-1"])
+        .await;
+    assert!(!output.status.success());
+    let stderr = stderr(&output);
+    assert!(stderr.contains("Failed to archive issues"));
+    assert!(stderr.contains("only available on Jira Premium and Enterprise"));
+}
+
+async fn mount_release_flow(mock_server: &MockServer, unresolved: u64) {
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/search/jql"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"issues": [{"key": "DEV-1"}]})),
+        )
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV/versions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/version"))
+        .and(body_json(
+            serde_json::json!({"name": "1.2.3", "project": "DEV"}),
+        ))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(serde_json::json!({"id": "10200", "name": "1.2.3"})),
+        )
+        .mount(mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/issue/DEV-1"))
+        .and(body_json(serde_json::json!({
+            "update": {"fixVersions": [{"add": {"id": "10200"}}]}
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/version/10200/unresolvedIssueCount"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "issuesCount": 1,
+            "issuesUnresolvedCount": unresolved
+        })))
+        .expect(1)
+        .mount(mock_server)
+        .await;
+}
+
+const RELEASE_ARGS: [&str; 12] = [
+    "jira",
+    "release",
+    "create",
+    "--project",
+    "DEV",
+    "--name",
+    "1.2.3",
+    "--from-jql",
+    "project = DEV AND status = Done",
+    "--release",
+    "--release-date",
+    "2025-02-01",
+];
+
+#[tokio::test]
+async fn test_jira_release_create_marks_version_released() {
+    let mock_server = MockServer::start().await;
+    mount_release_flow(&mock_server, 0).await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/version/10200"))
+        .and(body_json(serde_json::json!({
+            "released": true,
+            "releaseDate": "2025-02-01"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri()).run(&RELEASE_ARGS).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("✅ Released version 1.2.3 on 2025-02-01"));
+}
+
+#[tokio::test]
+async fn test_jira_release_create_refuses_unresolved_issues() {
+    let mock_server = MockServer::start().await;
+    mount_release_flow(&mock_server, 2).await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/version/10200"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri()).run(&RELEASE_ARGS).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Not releasing 1.2.3: 2 issue(s) in it are unresolved"));
+}
+
+#[tokio::test]
+async fn test_jira_group_members_follows_pages() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/group/member"))
+        .and(query_param("groupname", "jira-admins"))
+        .and(query_param("startAt", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [
+                {"accountId": "acc-1", "displayName": "Ana", "active": true},
+                {"accountId": "acc-2", "displayName": "Bo", "active": true}
+            ],
+            "isLast": false,
+            "total": 3
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/group/member"))
+        .and(query_param("groupname", "jira-admins"))
+        .and(query_param("startAt", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"accountId": "acc-3", "displayName": "Cy", "active": false}],
+            "isLast": true,
+            "total": 3
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let output = cli
+        .run(&["jira", "group", "members", "jira-admins", "--all"])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    assert!(out.contains("acc-3"), "{out}");
+    assert!(
+        out.contains("Showing 3 of 3 member(s) in jira-admins"),
+        "{out}"
+    );
+
+    // With a limit only the first page is read, and `total` reports the full size
+    let output = cli
+        .run(&["jira", "group", "members", "jira-admins", "--limit", "2"])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    let out = stdout(&output);
+    assert!(!out.contains("acc-3"), "{out}");
+    assert!(
+        out.contains("Showing 2 of 3 member(s) in jira-admins"),
+        "{out}"
+    );
+}
+
+#[tokio::test]
+async fn test_jira_group_add_user_resolves_email() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .and(query_param("query", "ana@example.com"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([{"accountId": "acc-ana"}])),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/group/user"))
+        .and(query_param("groupname", "jira-admins"))
+        .and(body_json(serde_json::json!({"accountId": "acc-ana"})))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "group",
+            "add-user",
+            "jira-admins",
+            "--user",
+            "ana@example.com",
+        ])
+        .await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Added ana@example.com to group jira-admins"));
+}
+
+#[tokio::test]
+async fn test_jira_group_add_user_ambiguous_email() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/user/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"accountId": "acc-1"},
+            {"accountId": "acc-2"}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/rest/api/3/group/user"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({})))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let output = Cli::new(&mock_server.uri())
+        .run(&[
+            "jira",
+            "group",
+            "add-user",
+            "jira-admins",
+            "--user",
+            "ana@example.com",
+        ])
+        .await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("'ana@example.com' matches 2 users"));
+}
+
+#[tokio::test]
+async fn test_jira_priority_scheme_assign() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/priorityscheme"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "values": [{"id": "3", "name": "Support"}],
+            "isLast": true
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/DEV"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "10001"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/rest/api/3/project/ODD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "odd"})))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/rest/api/3/priorityscheme/3"))
+        .and(body_json(
+            serde_json::json!({"projects": {"add": {"ids": [10001]}}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let cli = Cli::new(&mock_server.uri());
+    let args = |project| {
+        [
+            "jira",
+            "priorities",
+            "scheme",
+            "assign",
+            "--scheme",
+            "support",
+            "--project",
+            project,
+        ]
+    };
+    let output = cli.run(&args("DEV")).await;
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Project DEV now uses priority scheme Support"));
+
+    // A project ID that is not a number must not be sent as project 0
+    let output = cli.run(&args("ODD")).await;
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Project ODD has a non-numeric ID 'odd'"));
+}
@@ -1,10 +1,11 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
 };
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
@@ -72,12 +73,54 @@ pub fn migrate_config_if_needed() -> MigrationResult {
 }
 
 /// Represents the full CLI configuration stored on disk.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Config {
     #[serde(default)]
     pub default_profile: Option<String>,
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    /// Settings every profile starts from; a profile's own values, or those of
+    /// the profile it `extends`, take precedence.
+    #[serde(default, skip_serializing_if = "ProfileDefaults::is_empty")]
+    pub defaults: ProfileDefaults,
+    /// HTTP connection pool tuning shared by all profiles.
+    #[serde(default, skip_serializing_if = "HttpSettings::is_empty")]
+    pub http: HttpSettings,
+    /// Where API tokens are stored.
+    #[serde(default, skip_serializing_if = "CredentialStoreKind::is_default")]
+    pub credential_store: CredentialStoreKind,
+    /// Key file used to unlock the encrypted credential store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vault_key_file: Option<PathBuf>,
+    /// Command aliases expanded before argument parsing, e.g.
+    /// `standup: jira search -a @me -s "In Progress"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Response fields masked by `--redact-pii` in rendered output and
+    /// `--trace-http` logs, e.g. `[customer_email, description]`. Email fields
+    /// are always masked.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redact: Vec<String>,
+}
+
+/// Credential backend selected by `credential_store` in the config file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialStoreKind {
+    /// Plaintext credentials file (`~/.atlassian-cli/credentials`).
+    #[default]
+    File,
+    /// Passphrase-encrypted vault (`~/.atlassian-cli/credentials.vault`).
+    EncryptedFile,
+    /// The system keyring (macOS Keychain, Windows Credential Manager or the
+    /// Secret Service on Linux).
+    Keyring,
+}
+
+impl CredentialStoreKind {
+    fn is_default(&self) -> bool {
+        *self == CredentialStoreKind::default()
+    }
 }
 
 impl Config {
@@ -122,22 +165,117 @@ impl Config {
         self.profiles.get(name)
     }
 
-    /// Returns either the requested profile or falls back to the default one.
+    /// Returns either the requested profile or falls back to the default one,
+    /// with its `extends` chain and the `defaults` block applied. Fails when
+    /// the chain names an unknown profile or loops back on itself.
     pub fn resolve_profile<'a>(
         &'a self,
         requested: Option<&'a str>,
-    ) -> Option<(&'a str, &'a Profile)> {
-        if let Some(name) = requested {
-            self.profiles.get(name).map(|profile| (name, profile))
+    ) -> Result<Option<(&'a str, Profile)>> {
+        let name = if let Some(name) = requested {
+            name
         } else if let Some(default_name) = self.default_profile.as_deref() {
-            self.profiles
-                .get(default_name)
-                .map(|profile| (default_name, profile))
-        } else if let Some((name, profile)) = self.profiles.iter().next() {
-            Some((name.as_str(), profile))
+            default_name
+        } else if let Some(name) = self.profiles.keys().next() {
+            name.as_str()
         } else {
-            None
+            return Ok(None);
+        };
+        if !self.profiles.contains_key(name) {
+            return Ok(None);
         }
+        Ok(Some((name, self.resolved_profile(name)?)))
+    }
+
+    /// The named profile with the profiles it extends and the `defaults` block
+    /// filled in beneath its own values.
+    pub fn resolved_profile(&self, name: &str) -> Result<Profile> {
+        let mut chain = vec![name];
+        let mut profile = self
+            .profiles
+            .get(name)
+            .cloned()
+            .with_context(|| format!("Profile '{name}' not found"))?;
+        let mut parent_name = profile.extends.clone();
+        while let Some(parent) = parent_name {
+            if chain.contains(&parent.as_str()) {
+                bail!(
+                    "Profile inheritance cycle: {} -> {parent}",
+                    chain.join(" -> ")
+                );
+            }
+            let Some((parent, parent_profile)) = self.profiles.get_key_value(&parent) else {
+                bail!(
+                    "Profile '{}' extends unknown profile '{parent}'",
+                    chain.last().expect("chain starts with the profile itself")
+                );
+            };
+            chain.push(parent);
+            profile.inherit(parent_profile);
+            parent_name = parent_profile.extends.clone();
+        }
+        profile.apply_defaults(&self.defaults);
+        Ok(profile)
+    }
+
+    /// Copy of the configuration with every stored API token and credential
+    /// helper removed, suitable for sharing with a team. Each profile's email
+    /// and the vault key file path are per user and machine, so they go too.
+    /// Tokens kept in the keyring are never part of it.
+    pub fn without_secrets(&self) -> Config {
+        let mut config = self.clone();
+        config.vault_key_file = None;
+        for profile in config.profiles.values_mut() {
+            profile.email = None;
+            profile.api_token = None;
+            profile.credential_helper = None;
+        }
+        config
+    }
+
+    /// Whether any profile stores its API token, or the command that prints
+    /// it, in the config file itself.
+    pub fn has_secrets(&self) -> bool {
+        self.profiles
+            .values()
+            .any(|p| p.api_token.is_some() || p.credential_helper.is_some())
+    }
+
+    /// Merge profiles and settings from a shared bundle. Existing profiles are
+    /// kept unless `overwrite` is set; the bundle's default profile, profile
+    /// defaults, HTTP settings and aliases only apply where none are configured yet.
+    pub fn merge(&mut self, bundle: Config, overwrite: bool) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        let mut names: Vec<String> = bundle.profiles.keys().cloned().collect();
+        names.sort();
+        let mut profiles = bundle.profiles;
+        for name in names {
+            let profile = profiles.remove(&name).expect("name comes from the map");
+            if self.profiles.contains_key(&name) && !overwrite {
+                summary.skipped.push(name);
+                continue;
+            }
+            self.profiles.insert(name.clone(), profile);
+            summary.imported.push(name);
+        }
+
+        if self.default_profile.is_none() {
+            self.default_profile = bundle
+                .default_profile
+                .filter(|name| self.profiles.contains_key(name));
+        }
+        if self.defaults.is_empty() {
+            self.defaults = bundle.defaults;
+        }
+        if self.http.is_empty() {
+            self.http = bundle.http;
+        }
+        for (name, command) in bundle.aliases {
+            self.aliases.entry(name).or_insert(command);
+        }
+
+        summary
     }
 
     fn default_path() -> PathBuf {
@@ -148,9 +286,16 @@ impl Config {
     }
 }
 
+/// Profiles added or left untouched by [`Config::merge`], sorted by name.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
 /// Minimal representation of a profile. Values are optional to support
 /// partially configured setups (e.g., when storing tokens in the keyring).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Profile {
     pub base_url: Option<String>,
     pub email: Option<String>,
@@ -158,6 +303,141 @@ pub struct Profile {
     /// Bitbucket workspace slug (optional, can be inferred from base_url).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace: Option<String>,
+    /// Statuspage page ID used by `statuspage` commands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statuspage_page_id: Option<String>,
+    /// Command that prints the API token, run instead of reading a stored one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_helper: Option<CredentialHelper>,
+    /// Profile whose values fill in any this one leaves unset, for
+    /// per-environment variants of a shared profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Output format used when `--output` is not given (table, json, csv, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Concurrency for bulk commands when `--concurrency` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Zone timestamps are shown in when `--timezone` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl Profile {
+    /// Take `parent`'s value for every field left unset here.
+    fn inherit(&mut self, parent: &Profile) {
+        fn fill<T: Clone>(field: &mut Option<T>, parent: &Option<T>) {
+            if field.is_none() {
+                field.clone_from(parent);
+            }
+        }
+        fill(&mut self.base_url, &parent.base_url);
+        fill(&mut self.email, &parent.email);
+        fill(&mut self.api_token, &parent.api_token);
+        fill(&mut self.workspace, &parent.workspace);
+        fill(&mut self.statuspage_page_id, &parent.statuspage_page_id);
+        fill(&mut self.credential_helper, &parent.credential_helper);
+        fill(&mut self.output, &parent.output);
+        fill(&mut self.concurrency, &parent.concurrency);
+        fill(&mut self.timezone, &parent.timezone);
+    }
+
+    fn apply_defaults(&mut self, defaults: &ProfileDefaults) {
+        self.inherit(&Profile {
+            workspace: defaults.workspace.clone(),
+            output: defaults.output.clone(),
+            concurrency: defaults.concurrency,
+            timezone: defaults.timezone.clone(),
+            ..Default::default()
+        });
+    }
+}
+
+/// The `defaults:` block: values every profile inherits unless it sets its own.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+pub struct ProfileDefaults {
+    /// Output format used when `--output` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Concurrency for bulk commands when `--concurrency` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Zone timestamps are shown in when `--timezone` is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Bitbucket workspace slug.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+}
+
+impl ProfileDefaults {
+    pub fn is_empty(&self) -> bool {
+        self == &ProfileDefaults::default()
+    }
+}
+
+/// External source of a profile's API token, e.g.
+/// `credential_helper: !exec ["vault", "kv", "get", "-field=token", "secret/atlassian"]`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialHelper {
+    /// Run the program with its arguments and read the token from stdout.
+    Exec(Vec<String>),
+}
+
+impl CredentialHelper {
+    /// Short description for diagnostics, e.g. `credential helper (vault)`.
+    pub fn label(&self) -> String {
+        match self {
+            CredentialHelper::Exec(command) => format!(
+                "credential helper ({})",
+                command.first().map(String::as_str).unwrap_or("empty")
+            ),
+        }
+    }
+
+    /// The command as it would be typed in a shell, e.g.
+    /// `vault kv get -field=token 'secret/my team'`.
+    pub fn command_line(&self) -> String {
+        match self {
+            CredentialHelper::Exec(command) => command
+                .iter()
+                .map(|arg| {
+                    if !arg.is_empty()
+                        && arg
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || "-_=./:@%+,".contains(c))
+                    {
+                        arg.clone()
+                    } else {
+                        format!("'{}'", arg.replace('\'', r"'\''"))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// Connection pool and keepalive settings. Unset values use the client defaults.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq)]
+pub struct HttpSettings {
+    /// Maximum idle connections kept open per host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_idle_per_host: Option<usize>,
+    /// Seconds an idle pooled connection is kept before being closed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    /// TCP keepalive interval in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_keepalive_secs: Option<u64>,
+}
+
+impl HttpSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &HttpSettings::default()
+    }
 }
 
 #[cfg(test)]
@@ -213,6 +493,128 @@ mod tests {
         assert_eq!(work_profile.email, Some("test@example.com".to_string()));
     }
 
+    #[test]
+    fn test_http_settings_round_trip() {
+        let config: Config = serde_yaml::from_str(
+            "profiles: {}\nhttp:\n  max_idle_per_host: 8\n  idle_timeout_secs: 30\n",
+        )
+        .unwrap();
+        assert_eq!(config.http.max_idle_per_host, Some(8));
+        assert_eq!(config.http.idle_timeout_secs, Some(30));
+        assert!(config.http.tcp_keepalive_secs.is_none());
+
+        let serialized = serde_yaml::to_string(&Config::default()).unwrap();
+        assert!(!serialized.contains("http"));
+    }
+
+    #[test]
+    fn test_aliases_round_trip() {
+        let config: Config = serde_yaml::from_str(
+            "profiles: {}\naliases:\n  standup: jira search -a @me -s \"In Progress\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.aliases.get("standup").map(String::as_str),
+            Some("jira search -a @me -s \"In Progress\"")
+        );
+
+        let serialized = serde_yaml::to_string(&Config::default()).unwrap();
+        assert!(!serialized.contains("aliases"));
+    }
+
+    #[test]
+    fn test_without_secrets_strips_tokens() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "work".to_string(),
+            Profile {
+                base_url: Some("https://work.atlassian.net".to_string()),
+                email: Some("me@example.com".to_string()),
+                api_token: Some("secret".to_string()),
+                workspace: Some("team".to_string()),
+                ..Default::default()
+            },
+        );
+        config.vault_key_file = Some(PathBuf::from("/home/me/.vault-key"));
+        assert!(config.has_secrets());
+
+        let shared = config.without_secrets();
+        assert!(!shared.has_secrets());
+        let profile = shared.profile("work").unwrap();
+        assert_eq!(profile.workspace.as_deref(), Some("team"));
+        assert!(profile.email.is_none());
+        assert!(shared.vault_key_file.is_none());
+        let yaml = serde_yaml::to_string(&shared).unwrap();
+        assert!(!yaml.contains("secret"));
+        assert!(!yaml.contains("me@example.com"));
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_profiles() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "work".to_string(),
+            Profile {
+                email: Some("me@example.com".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let mut bundle = Config {
+            default_profile: Some("team".to_string()),
+            http: HttpSettings {
+                max_idle_per_host: Some(4),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for name in ["work", "team"] {
+            bundle.profiles.insert(
+                name.to_string(),
+                Profile {
+                    email: Some("shared@example.com".to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let summary = config.clone().merge(bundle.clone(), false);
+        assert_eq!(summary.imported, vec!["team"]);
+        assert_eq!(summary.skipped, vec!["work"]);
+
+        let summary = config.merge(bundle, true);
+        assert_eq!(summary.imported, vec!["team", "work"]);
+        assert_eq!(
+            config.profile("work").unwrap().email.as_deref(),
+            Some("shared@example.com")
+        );
+        assert_eq!(config.default_profile.as_deref(), Some("team"));
+        assert_eq!(config.http.max_idle_per_host, Some(4));
+    }
+
+    #[test]
+    fn test_credential_store_setting() {
+        let config: Config = serde_yaml::from_str(
+            "credential_store: encrypted-file\nvault_key_file: /etc/atlassian-cli/vault.key\n",
+        )
+        .unwrap();
+        assert_eq!(config.credential_store, CredentialStoreKind::EncryptedFile);
+        assert_eq!(
+            config.vault_key_file,
+            Some(PathBuf::from("/etc/atlassian-cli/vault.key"))
+        );
+
+        assert_eq!(
+            Config::default().credential_store,
+            CredentialStoreKind::File
+        );
+        let serialized = serde_yaml::to_string(&Config::default()).unwrap();
+        assert!(!serialized.contains("credential_store"));
+
+        let config: Config = serde_yaml::from_str("credential_store: keyring\n").unwrap();
+        assert_eq!(config.credential_store, CredentialStoreKind::Keyring);
+    }
+
     #[test]
     fn test_load_malformed_yaml() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -258,7 +660,7 @@ mod tests {
         };
         config.profiles.insert("work".to_string(), work_profile);
 
-        let (name, profile) = config.resolve_profile(Some("work")).unwrap();
+        let (name, profile) = config.resolve_profile(Some("work")).unwrap().unwrap();
         assert_eq!(name, "work");
         assert_eq!(
             profile.base_url,
@@ -281,7 +683,7 @@ mod tests {
             .profiles
             .insert("default".to_string(), default_profile);
 
-        let (name, profile) = config.resolve_profile(None).unwrap();
+        let (name, profile) = config.resolve_profile(None).unwrap().unwrap();
         assert_eq!(name, "default");
         assert_eq!(
             profile.base_url,
@@ -299,7 +701,7 @@ mod tests {
         };
         config.profiles.insert("only".to_string(), profile);
 
-        let result = config.resolve_profile(None);
+        let result = config.resolve_profile(None).unwrap();
         assert!(result.is_some());
         let (name, profile) = result.unwrap();
         assert_eq!(name, "only");
@@ -312,7 +714,7 @@ mod tests {
     #[test]
     fn test_resolve_profile_none_available() {
         let config = Config::default();
-        assert!(config.resolve_profile(None).is_none());
+        assert!(config.resolve_profile(None).unwrap().is_none());
     }
 
     #[test]
@@ -325,7 +727,81 @@ mod tests {
         };
         config.profiles.insert("test".to_string(), profile);
 
-        assert!(config.resolve_profile(Some("nonexistent")).is_none());
+        assert!(config
+            .resolve_profile(Some("nonexistent"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_resolve_profile_inheritance() {
+        let yaml = r#"
+defaults:
+  output: json
+  concurrency: 8
+  workspace: acme
+profiles:
+  prod:
+    base_url: https://acme.atlassian.net
+    email: ops@acme.com
+    timezone: UTC
+  staging:
+    extends: prod
+    base_url: https://acme-staging.atlassian.net
+    output: table
+  sandbox:
+    extends: staging
+    workspace: sandbox
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+
+        let (_, sandbox) = config.resolve_profile(Some("sandbox")).unwrap().unwrap();
+        assert_eq!(
+            sandbox.base_url.as_deref(),
+            Some("https://acme-staging.atlassian.net")
+        );
+        assert_eq!(sandbox.email.as_deref(), Some("ops@acme.com"));
+        assert_eq!(sandbox.output.as_deref(), Some("table"));
+        assert_eq!(sandbox.timezone.as_deref(), Some("UTC"));
+        assert_eq!(sandbox.workspace.as_deref(), Some("sandbox"));
+        assert_eq!(sandbox.concurrency, Some(8));
+
+        let prod = config.resolved_profile("prod").unwrap();
+        assert_eq!(prod.output.as_deref(), Some("json"));
+        assert_eq!(prod.workspace.as_deref(), Some("acme"));
+
+        // Stored profiles keep only their own values
+        let saved = serde_yaml::to_string(&config).unwrap();
+        let round_trip: Config = serde_yaml::from_str(&saved).unwrap();
+        assert!(round_trip.profiles["sandbox"].email.is_none());
+        assert_eq!(round_trip.defaults, config.defaults);
+    }
+
+    #[test]
+    fn test_resolve_profile_inheritance_errors() {
+        let yaml = r#"
+profiles:
+  a:
+    extends: b
+  b:
+    extends: c
+  c:
+    extends: a
+  orphan:
+    extends: missing
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+
+        let err = config.resolve_profile(Some("a")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile inheritance cycle: a -> b -> c -> a"
+        );
+        let err = config.resolved_profile("orphan").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile 'orphan' extends unknown profile 'missing'"
+        );
     }
 
     #[test]
@@ -363,4 +839,50 @@ mod tests {
         assert_eq!(deserialized.default_profile, config.default_profile);
         assert_eq!(deserialized.profiles.len(), 1);
     }
+
+    #[test]
+    fn test_credential_helper_exec_tag() {
+        let yaml = r#"
+profiles:
+  work:
+    base_url: https://work.atlassian.net
+    email: me@work.com
+    credential_helper: !exec ["vault", "kv", "get", "-field=token", "secret/atlassian"]
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let helper = config.profiles["work"].credential_helper.clone().unwrap();
+        assert_eq!(
+            helper,
+            CredentialHelper::Exec(vec![
+                "vault".to_string(),
+                "kv".to_string(),
+                "get".to_string(),
+                "-field=token".to_string(),
+                "secret/atlassian".to_string(),
+            ])
+        );
+        assert_eq!(helper.label(), "credential helper (vault)");
+        assert_eq!(
+            helper.command_line(),
+            "vault kv get -field=token secret/atlassian"
+        );
+        assert_eq!(
+            CredentialHelper::Exec(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo 'hi' $X".to_string()
+            ])
+            .command_line(),
+            r#"sh -c 'echo '\''hi'\'' $X'"#
+        );
+
+        let round_trip: Config =
+            serde_yaml::from_str(&serde_yaml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(round_trip.profiles["work"].credential_helper, Some(helper));
+
+        assert!(config.has_secrets());
+        let shared = config.without_secrets();
+        assert!(shared.profiles["work"].credential_helper.is_none());
+        assert!(!shared.has_secrets());
+    }
 }
@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// How timestamps are written in table and CSV output.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Default)]
+pub enum DateFormat {
+    /// `2024-06-03T14:05:00+01:00`
+    #[default]
+    Iso,
+    /// `2 hours ago`, `in 3 days`
+    Relative,
+    /// `2024-06-03 14:05`
+    Short,
+}
+
+/// Zone timestamps are shown in: `local`, `UTC` or an IANA name such as
+/// `Europe/London`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Timezone {
+    #[default]
+    Local,
+    Utc,
+    Named(chrono_tz::Tz),
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("local") {
+            Ok(Timezone::Local)
+        } else if s.eq_ignore_ascii_case("utc") {
+            Ok(Timezone::Utc)
+        } else {
+            s.parse().map(Timezone::Named).map_err(|_| {
+                format!(
+                    "unknown timezone '{s}' (use local, UTC or an IANA name such as Europe/London)"
+                )
+            })
+        }
+    }
+}
+
+/// Timestamp rendering. With neither option set, timestamps are printed
+/// exactly as the API returned them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DateOptions {
+    pub timezone: Option<Timezone>,
+    pub format: Option<DateFormat>,
+}
+
+impl DateOptions {
+    pub(crate) fn is_raw(&self) -> bool {
+        self.timezone.is_none() && self.format.is_none()
+    }
+
+    /// Rewrite every string in `value` that is a full timestamp.
+    pub(crate) fn apply(&self, value: &mut Value, now: DateTime<Utc>) {
+        match value {
+            Value::String(s) => {
+                if let Some(timestamp) = parse_timestamp(s) {
+                    *s = self.format_timestamp(timestamp, now);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.apply(item, now);
+                }
+            }
+            Value::Object(obj) => {
+                for field in obj.values_mut() {
+                    self.apply(field, now);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn format_timestamp(&self, timestamp: DateTime<FixedOffset>, now: DateTime<Utc>) -> String {
+        let format = self.format.unwrap_or_default();
+        if format == DateFormat::Relative {
+            return relative(timestamp.with_timezone(&Utc), now);
+        }
+        let pattern = match format {
+            DateFormat::Short => "%Y-%m-%d %H:%M",
+            _ => "%Y-%m-%dT%H:%M:%S%:z",
+        };
+        match self.timezone.unwrap_or_default() {
+            Timezone::Local => in_zone(timestamp, &Local, pattern),
+            Timezone::Utc => in_zone(timestamp, &Utc, pattern),
+            Timezone::Named(tz) => in_zone(timestamp, &tz, pattern),
+        }
+    }
+}
+
+fn in_zone<Tz: TimeZone>(timestamp: DateTime<FixedOffset>, zone: &Tz, pattern: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    timestamp.with_timezone(zone).format(pattern).to_string()
+}
+
+/// Timestamps with a time and an offset, as RFC 3339 (`2024-06-03T13:05:00Z`,
+/// Bitbucket, Confluence) or with a colon-less offset (`...00.000+0000`, Jira).
+/// Plain dates such as due dates are left alone.
+pub fn parse_timestamp(s: &str) -> Option<DateTime<FixedOffset>> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || bytes[10] != b'T' {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(s)
+        .or_else(|_| DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+}
+
+/// "just now", "5 minutes ago", "in 3 days".
+fn relative(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - timestamp).num_seconds();
+    let elapsed = seconds.unsigned_abs();
+    if elapsed < 45 {
+        return "just now".to_string();
+    }
+    let (amount, unit) = match elapsed {
+        s if s < 3_600 => ((s / 60).max(1), "minute"),
+        s if s < 86_400 => (s / 3_600, "hour"),
+        s if s < 30 * 86_400 => (s / 86_400, "day"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+    if seconds < 0 {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn now() -> DateTime<Utc> {
+        "2024-06-03T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut value = json!([{
+            "created": "2024-06-03T10:00:00.000+0000",
+            "updated_on": "2024-06-03T11:59:50.123456+00:00",
+            "due": "2024-06-10",
+            "key": "DEV-1"
+        }]);
+        let options = DateOptions {
+            timezone: Some(Timezone::Named(chrono_tz::Europe::London)),
+            format: Some(DateFormat::Short),
+        };
+        options.apply(&mut value, now());
+        assert_eq!(
+            value,
+            json!([{
+                "created": "2024-06-03 11:00",
+                "updated_on": "2024-06-03 12:59",
+                "due": "2024-06-10",
+                "key": "DEV-1"
+            }])
+        );
+
+        let mut value = json!("2024-06-03T10:00:00.000+0000");
+        DateOptions {
+            timezone: Some(Timezone::Utc),
+            format: None,
+        }
+        .apply(&mut value, now());
+        assert_eq!(value, json!("2024-06-03T10:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_relative() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(relative(at("2024-06-03T11:59:30Z"), now()), "just now");
+        assert_eq!(relative(at("2024-06-03T11:55:00Z"), now()), "5 minutes ago");
+        assert_eq!(relative(at("2024-06-03T10:00:00Z"), now()), "2 hours ago");
+        assert_eq!(relative(at("2024-06-02T12:00:00Z"), now()), "1 day ago");
+        assert_eq!(relative(at("2024-06-06T12:00:00Z"), now()), "in 3 days");
+        assert_eq!(relative(at("2022-01-01T00:00:00Z"), now()), "2 years ago");
+    }
+
+    #[test]
+    fn test_timezone_from_str() {
+        assert_eq!("UTC".parse::<Timezone>(), Ok(Timezone::Utc));
+        assert_eq!("Local".parse::<Timezone>(), Ok(Timezone::Local));
+        assert_eq!(
+            "Europe/London".parse::<Timezone>(),
+            Ok(Timezone::Named(chrono_tz::Europe::London))
+        );
+        assert!("Mars/Olympus".parse::<Timezone>().is_err());
+    }
+}
@@ -1,11 +1,18 @@
 use std::collections::BTreeSet;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::ValueEnum;
 use serde::Serialize;
 use serde_json::Value;
 use tabled::builder::Builder;
-use tabled::settings::Style;
+use tabled::settings::object::Segment;
+use tabled::settings::peaker::PriorityMax;
+use tabled::settings::{Style, Width};
+use tabled::Table;
+
+mod dates;
+
+pub use dates::{parse_timestamp, DateFormat, DateOptions, Timezone};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum, Default)]
 pub enum OutputFormat {
@@ -17,13 +24,79 @@ pub enum OutputFormat {
     Quiet,
 }
 
+/// How table cells are fitted to the available width.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableLayout {
+    /// Print every cell in full, ignoring the terminal width.
+    pub full: bool,
+    /// Upper bound for the width of any column.
+    pub max_col_width: Option<usize>,
+}
+
+/// How `--output quiet` picks the value printed for each record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuietOptions {
+    /// Field to print, overriding the identifier the command declares.
+    pub field: Option<String>,
+    /// Terminate values with NUL instead of a newline (for `xargs -0`).
+    pub nul: bool,
+}
+
+/// Fields tried, in order, when neither the user nor the command names one.
+const DEFAULT_ID_FIELDS: [&str; 3] = ["id", "key", "slug"];
+
+/// Placeholder printed in place of a redacted field.
+pub const REDACTED: &str = "[redacted]";
+
 pub struct OutputRenderer {
     format: OutputFormat,
+    layout: TableLayout,
+    quiet: QuietOptions,
+    dates: DateOptions,
+    /// Normalized names of fields masked in every output format.
+    redact: BTreeSet<String>,
 }
 
 impl OutputRenderer {
     pub fn new(format: OutputFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            layout: TableLayout::default(),
+            quiet: QuietOptions::default(),
+            dates: DateOptions::default(),
+            redact: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_table_layout(mut self, layout: TableLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn with_quiet_options(mut self, quiet: QuietOptions) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Rewrite timestamps in table and CSV output. JSON and YAML keep the API's
+    /// values so scripts see stable ISO strings.
+    pub fn with_date_options(mut self, dates: DateOptions) -> Self {
+        self.dates = dates;
+        self
+    }
+
+    /// Mask these fields wherever they appear in rendered records. Names match
+    /// case-insensitively and ignoring `_`, `-` and spaces.
+    pub fn with_redaction<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.redact = fields
+            .into_iter()
+            .map(|f| normalize_field(f.as_ref()))
+            .collect();
+        self
     }
 
     pub fn format(&self) -> OutputFormat {
@@ -31,7 +104,34 @@ impl OutputRenderer {
     }
 
     pub fn render<T: Serialize>(&self, value: &T) -> Result<()> {
-        let json_value = serde_json::to_value(value)?;
+        self.render_hinted(value, None)
+    }
+
+    /// Render records whose identifier is `id_field` (e.g. `key` for issues,
+    /// `slug` for repositories); quiet output prints that field.
+    pub fn render_with_id<T: Serialize>(&self, value: &T, id_field: &str) -> Result<()> {
+        self.render_hinted(value, Some(id_field))
+    }
+
+    /// Print a resource as the API returned it, as pretty JSON whatever the
+    /// output format, with redacted fields masked.
+    pub fn print_json<T: Serialize>(&self, value: &T) -> Result<()> {
+        let mut json_value = serde_json::to_value(value)?;
+        if !self.redact.is_empty() {
+            self.redact_value(&mut json_value);
+        }
+        println!("{}", serde_json::to_string_pretty(&json_value)?);
+        Ok(())
+    }
+
+    fn render_hinted<T: Serialize>(&self, value: &T, id_field: Option<&str>) -> Result<()> {
+        let mut json_value = serde_json::to_value(value)?;
+        if !self.redact.is_empty() {
+            self.redact_value(&mut json_value);
+        }
+        if matches!(self.format, OutputFormat::Table | OutputFormat::Csv) && !self.dates.is_raw() {
+            self.dates.apply(&mut json_value, chrono::Utc::now());
+        }
 
         match self.format {
             OutputFormat::Table => {
@@ -51,7 +151,7 @@ impl OutputRenderer {
                 }
             }
             OutputFormat::Quiet => {
-                if !self.render_quiet(&json_value) {
+                if !self.render_quiet(&json_value, id_field)? {
                     println!("{}", serde_json::to_string_pretty(&json_value)?);
                 }
             }
@@ -66,17 +166,45 @@ impl OutputRenderer {
             None => return Ok(false),
         };
 
+        let single_record = rows.len() == 1;
         let mut builder = Builder::default();
         builder.push_record(headers);
         for row in rows {
             builder.push_record(row);
         }
 
-        let table = builder.build().with(Style::rounded()).to_string();
+        let mut table = builder.build();
+        table.with(Style::rounded());
+        self.fit_table(&mut table, single_record, terminal_width());
         println!("{}", table);
         Ok(true)
     }
 
+    /// Shrink wide columns so the table fits the terminal. Cells are truncated
+    /// with an ellipsis, except for a single record, which is wrapped instead so
+    /// nothing is lost.
+    fn fit_table(&self, table: &mut Table, single_record: bool, terminal_width: Option<usize>) {
+        if self.layout.full {
+            return;
+        }
+
+        if let Some(max) = self.layout.max_col_width {
+            if single_record {
+                table.modify(Segment::all(), Width::wrap(max).keep_words(true));
+            } else {
+                table.modify(Segment::all(), Width::truncate(max).suffix("…"));
+            }
+        }
+
+        if let Some(width) = terminal_width {
+            if single_record {
+                table.with(Width::wrap(width).keep_words(true).priority(PriorityMax));
+            } else {
+                table.with(Width::truncate(width).suffix("…").priority(PriorityMax));
+            }
+        }
+    }
+
     fn render_csv(&self, value: &Value) -> Result<bool> {
         let (headers, rows) = match Self::coerce_rows(value) {
             Some(data) => data,
@@ -91,42 +219,70 @@ impl OutputRenderer {
         Ok(true)
     }
 
-    fn render_quiet(&self, value: &Value) -> bool {
-        match value {
-            Value::Array(rows) => {
-                let mut printed = false;
-                for row in rows {
-                    if let Value::Object(obj) = row {
-                        if let Some(id) = obj.get("id").and_then(Value::as_str) {
-                            println!("{id}");
-                            printed = true;
-                        } else if let Some(key) = obj.keys().next() {
-                            if let Some(val) = obj.get(key) {
-                                println!("{}", val);
-                                printed = true;
-                            }
-                        }
-                    } else if !row.is_null() {
-                        println!("{}", row);
-                        printed = true;
-                    }
-                }
-                printed
-            }
-            Value::Object(obj) => {
-                if let Some(id) = obj.get("id").and_then(Value::as_str) {
-                    println!("{id}");
-                    true
-                } else {
-                    false
-                }
-            }
-            Value::Null => false,
-            other => {
-                println!("{}", other);
-                true
+    /// Print the quiet values. Returns false when the caller should print JSON
+    /// instead; never with a named field or NUL separators, whose readers
+    /// cannot parse it.
+    fn render_quiet(&self, value: &Value, id_field: Option<&str>) -> Result<bool> {
+        let values = self.quiet_values(value, id_field);
+        if let (true, Some(field)) = (values.is_empty(), &self.quiet.field) {
+            let available = Self::record_fields(value);
+            if !available.is_empty() {
+                bail!(
+                    "Unknown --quiet-field '{field}'; records have: {}",
+                    available.into_iter().collect::<Vec<_>>().join(", ")
+                );
             }
         }
+
+        let terminator = if self.quiet.nul { '\0' } else { '\n' };
+        for value in &values {
+            print!("{value}{terminator}");
+        }
+        Ok(!values.is_empty() || self.quiet.field.is_some() || self.quiet.nul)
+    }
+
+    /// Field names of the record, or of every record in an array.
+    fn record_fields(value: &Value) -> BTreeSet<&str> {
+        let records: Vec<&Value> = match value {
+            Value::Array(rows) => rows.iter().collect(),
+            other => vec![other],
+        };
+        records
+            .into_iter()
+            .filter_map(Value::as_object)
+            .flat_map(|obj| obj.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Values printed by quiet output: the chosen field of each record, or
+    /// scalars as-is. Rows without the field are skipped, except that array
+    /// rows fall back to their first field when no field was named at all.
+    fn quiet_values(&self, value: &Value, id_field: Option<&str>) -> Vec<String> {
+        let field = self.quiet.field.as_deref().or(id_field);
+        let pick = |obj: &serde_json::Map<String, Value>, fallback: bool| -> Option<String> {
+            let found = match field {
+                Some(field) => obj.get(field),
+                None => DEFAULT_ID_FIELDS
+                    .iter()
+                    .find_map(|f| obj.get(*f))
+                    .or_else(|| obj.values().next().filter(|_| fallback)),
+            };
+            found.filter(|v| !v.is_null()).map(Self::value_to_string)
+        };
+
+        match value {
+            Value::Array(rows) => rows
+                .iter()
+                .filter_map(|row| match row {
+                    Value::Object(obj) => pick(obj, true),
+                    Value::Null => None,
+                    other => Some(Self::value_to_string(other)),
+                })
+                .collect(),
+            Value::Object(obj) => pick(obj, false).into_iter().collect(),
+            Value::Null => Vec::new(),
+            other => vec![Self::value_to_string(other)],
+        }
     }
 
     fn coerce_rows(value: &Value) -> Option<(Vec<String>, Vec<Vec<String>>)> {
@@ -165,6 +321,28 @@ impl OutputRenderer {
         Some((headers_vec, data))
     }
 
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(obj) => {
+                for (key, field) in obj.iter_mut() {
+                    if self.redact.contains(&normalize_field(key)) {
+                        if !field.is_null() {
+                            *field = Value::String(REDACTED.to_string());
+                        }
+                    } else {
+                        self.redact_value(field);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.redact_value(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn value_to_string(value: &Value) -> String {
         match value {
             Value::String(s) => s.clone(),
@@ -176,6 +354,20 @@ impl OutputRenderer {
     }
 }
 
+/// Field names compared without case, `_`, `-` or spaces, as `--redact-pii`
+/// matches them in rendered output and in `--trace-http` logs.
+pub fn normalize_field(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Width of the terminal attached to stdout, if any.
+fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| width as usize)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,6 +378,28 @@ mod tests {
         assert_eq!(OutputFormat::default(), OutputFormat::Table);
     }
 
+    #[test]
+    fn test_redact_value() {
+        let renderer = OutputRenderer::new(OutputFormat::Json)
+            .with_redaction(["customer_email", "description"]);
+        let mut value = json!([{
+            "key": "SD-1",
+            "customerEmail": "ana@example.com",
+            "fields": { "Description": "Call me on 555-0100", "summary": "VPN" },
+            "reporter": { "customer_email": null }
+        }]);
+        renderer.redact_value(&mut value);
+        assert_eq!(
+            value,
+            json!([{
+                "key": "SD-1",
+                "customerEmail": REDACTED,
+                "fields": { "Description": REDACTED, "summary": "VPN" },
+                "reporter": { "customer_email": null }
+            }])
+        );
+    }
+
     #[test]
     fn test_renderer_new() {
         let renderer = OutputRenderer::new(OutputFormat::Json);
@@ -287,14 +501,14 @@ mod tests {
     fn test_render_quiet_object_with_id() {
         let value = json!({"id": "123", "name": "Test"});
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(renderer.render_quiet(&value));
+        assert!(renderer.render_quiet(&value, None).unwrap());
     }
 
     #[test]
     fn test_render_quiet_object_without_id() {
         let value = json!({"name": "Test"});
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(!renderer.render_quiet(&value));
+        assert!(!renderer.render_quiet(&value, None).unwrap());
     }
 
     #[test]
@@ -304,28 +518,81 @@ mod tests {
             {"id": "2", "name": "Bob"}
         ]);
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(renderer.render_quiet(&value));
+        assert!(renderer.render_quiet(&value, None).unwrap());
     }
 
     #[test]
     fn test_render_quiet_primitive() {
         let value = json!("simple");
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(renderer.render_quiet(&value));
+        assert!(renderer.render_quiet(&value, None).unwrap());
     }
 
     #[test]
     fn test_render_quiet_null() {
         let value = json!(null);
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(!renderer.render_quiet(&value));
+        assert!(!renderer.render_quiet(&value, None).unwrap());
     }
 
     #[test]
     fn test_render_quiet_array_with_nulls() {
         let value = json!([null, null]);
         let renderer = OutputRenderer::new(OutputFormat::Quiet);
-        assert!(!renderer.render_quiet(&value));
+        assert!(!renderer.render_quiet(&value, None).unwrap());
+    }
+
+    #[test]
+    fn test_render_quiet_unknown_field() {
+        let renderer = OutputRenderer::new(OutputFormat::Quiet).with_quiet_options(QuietOptions {
+            field: Some("nmae".to_string()),
+            nul: false,
+        });
+        let err = renderer
+            .render_quiet(&json!([{"id": 1, "name": "a"}, {"key": "DEV-1"}]), None)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown --quiet-field 'nmae'; records have: id, key, name"
+        );
+        // Nothing to print is not an error, and does not fall back to JSON
+        assert!(renderer.render_quiet(&json!([]), None).unwrap());
+
+        let renderer = OutputRenderer::new(OutputFormat::Quiet).with_quiet_options(QuietOptions {
+            field: None,
+            nul: true,
+        });
+        assert!(renderer
+            .render_quiet(&json!({"name": "Test"}), None)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_quiet_values_field_selection() {
+        let rows = json!([
+            {"author": "ana", "id": 12, "title": "Fix"},
+            {"author": "bo", "key": "DEV-1", "slug": "api"},
+            {"author": "cy"}
+        ]);
+        let renderer = OutputRenderer::new(OutputFormat::Quiet);
+        assert_eq!(
+            renderer.quiet_values(&rows, None),
+            vec!["12", "DEV-1", "cy"]
+        );
+        assert_eq!(renderer.quiet_values(&rows, Some("slug")), vec!["api"]);
+
+        let renderer = renderer.with_quiet_options(QuietOptions {
+            field: Some("author".to_string()),
+            nul: true,
+        });
+        assert_eq!(
+            renderer.quiet_values(&rows, Some("slug")),
+            vec!["ana", "bo", "cy"]
+        );
+        assert_eq!(
+            renderer.quiet_values(&json!({"author": "ana"}), None),
+            vec!["ana"]
+        );
     }
 
     #[derive(Serialize)]
@@ -381,6 +648,57 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn fitted_table(layout: TableLayout, rows: usize, terminal_width: Option<usize>) -> String {
+        let mut builder = Builder::default();
+        builder.push_record(["key", "description"]);
+        for i in 0..rows {
+            builder.push_record([format!("DEV-{i}"), "a long description ".repeat(8)]);
+        }
+        let mut table = builder.build();
+        table.with(Style::rounded());
+
+        let renderer = OutputRenderer::new(OutputFormat::Table).with_table_layout(layout);
+        renderer.fit_table(&mut table, rows == 1, terminal_width);
+        table.to_string()
+    }
+
+    fn max_line_width(table: &str) -> usize {
+        table.lines().map(|l| l.chars().count()).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_table_truncates_to_terminal_width() {
+        let table = fitted_table(TableLayout::default(), 2, Some(60));
+        assert!(max_line_width(&table) <= 60);
+        assert!(table.contains('…'));
+        assert_eq!(table.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_single_record_wraps_instead_of_truncating() {
+        let table = fitted_table(TableLayout::default(), 1, Some(60));
+        assert!(max_line_width(&table) <= 60);
+        assert!(!table.contains('…'));
+        assert!(table.lines().count() > 5);
+    }
+
+    #[test]
+    fn test_max_col_width_and_full() {
+        let layout = TableLayout {
+            max_col_width: Some(20),
+            ..Default::default()
+        };
+        let table = fitted_table(layout, 2, None);
+        assert!(max_line_width(&table) < 40);
+
+        let full = TableLayout {
+            full: true,
+            max_col_width: Some(20),
+        };
+        let table = fitted_table(full, 2, Some(60));
+        assert!(max_line_width(&table) > 160);
+    }
+
     #[test]
     fn test_render_csv() {
         let test_data = vec![